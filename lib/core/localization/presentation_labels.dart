@@ -1,6 +1,7 @@
 import 'package:compact_games/l10n/app_localizations.dart';
 
 import '../../models/compression_algorithm.dart';
+import '../../models/compression_estimate.dart';
 import '../../models/game_info.dart';
 
 extension CompressionAlgorithmLocalizationX on CompressionAlgorithm {
@@ -14,6 +15,16 @@ extension CompressionAlgorithmLocalizationX on CompressionAlgorithm {
   }
 }
 
+extension EstimateConfidenceLocalizationX on EstimateConfidence {
+  String localizedLabel(AppLocalizations l10n) {
+    return switch (this) {
+      EstimateConfidence.low => l10n.gameEstimateConfidenceLow,
+      EstimateConfidence.medium => l10n.gameEstimateConfidenceMedium,
+      EstimateConfidence.high => l10n.gameEstimateConfidenceHigh,
+    };
+  }
+}
+
 extension PlatformLocalizationX on Platform {
   String localizedLabel(AppLocalizations l10n) {
     return switch (this) {
@@ -24,6 +35,7 @@ extension PlatformLocalizationX on Platform {
       Platform.eaApp => l10n.platformEaApp,
       Platform.battleNet => l10n.platformBattleNet,
       Platform.xboxGamePass => l10n.platformXboxGamePass,
+      Platform.riotGames => l10n.platformRiotGames,
       Platform.custom => l10n.platformCustom,
       Platform.application => l10n.platformApplication,
     };
@@ -12,6 +12,7 @@ IconData platformIcon(Platform platform) {
     Platform.eaApp => LucideIcons.gamepad2,
     Platform.battleNet => LucideIcons.cloudLightning,
     Platform.xboxGamePass => LucideIcons.tv2,
+    Platform.riotGames => LucideIcons.swords,
     Platform.custom => LucideIcons.folder,
     Platform.application => LucideIcons.archive,
   };
@@ -26,6 +27,7 @@ String platformGlyphAsset(Platform platform) {
     Platform.eaApp => 'assets/platforms/ea.svg',
     Platform.battleNet => 'assets/platforms/battlenet.svg',
     Platform.xboxGamePass => 'assets/platforms/xbox.svg',
+    Platform.riotGames => 'assets/platforms/riot.svg',
     Platform.custom => 'assets/platforms/custom.svg',
     Platform.application => 'assets/platforms/application.svg',
   };
@@ -262,9 +262,9 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
     );
   }
 
-  /// Whether DirectStorage protection blocks compression.
-  static bool _isDirectStorageBlocked(GameInfo game, bool allowOverride) =>
-      game.isDirectStorage && !allowOverride;
+  /// Whether DirectStorage or UWP package protection blocks compression.
+  static bool _isCompressionBlocked(GameInfo game, bool allowOverride) =>
+      game.isProtectedPackage || (game.isDirectStorage && !allowOverride);
 
   int? _estimatedSavedBytesFor(
     GameInfo game,
@@ -272,7 +272,7 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
     CompressionAlgorithm algorithm,
   ) {
     if (game.isCompressed) return null;
-    if (_isDirectStorageBlocked(game, allowDirectStorageOverride)) return null;
+    if (_isCompressionBlocked(game, allowDirectStorageOverride)) return null;
     if (!_hasDisplayableEstimate(game, algorithm)) return null;
     return _cachedEstimate?.estimatedSavedBytes;
   }
@@ -283,7 +283,7 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
     CompressionAlgorithm algorithm,
   ) {
     if (game.isCompressed) return false;
-    if (_isDirectStorageBlocked(game, allowDirectStorageOverride)) return false;
+    if (_isCompressionBlocked(game, allowDirectStorageOverride)) return false;
     if (!_hasDisplayableEstimate(game, algorithm)) return false;
     return _cachedEstimate?.showCommunityBadge ?? false;
   }
@@ -328,7 +328,7 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
     CompressionAlgorithm algorithm,
   ) {
     if (game.isCompressed) return;
-    if (_isDirectStorageBlocked(game, allowDirectStorageOverride)) return;
+    if (_isCompressionBlocked(game, allowDirectStorageOverride)) return;
     if (_hasFinalEstimate(game, algorithm)) return;
     if (_estimateFetchScheduled) return;
     if (_estimateFetchInFlight) return;
@@ -378,7 +378,7 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
           .startDecompression(gamePath: game.path, gameName: game.name);
       return;
     }
-    if (_isDirectStorageBlocked(game, allowDirectStorageOverride)) return;
+    if (_isCompressionBlocked(game, allowDirectStorageOverride)) return;
 
     final shouldCompress = await _confirmCompression(gameName: game.name);
     if (!mounted || !shouldCompress) return;
@@ -394,7 +394,7 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
 
   Future<void> _startCompressionAction(GameInfo game) async {
     final allowDirectStorageOverride = _readDirectStorageOverride();
-    if (_isDirectStorageBlocked(game, allowDirectStorageOverride)) {
+    if (_isCompressionBlocked(game, allowDirectStorageOverride)) {
       return;
     }
 
@@ -477,14 +477,14 @@ class _GameCardAdapterState extends ConsumerState<GameCardAdapter>
           value: GameContextAction.compress,
           enabled:
               !game.isCompressed &&
-              !_isDirectStorageBlocked(game, allowDirectStorageOverride),
+              !_isCompressionBlocked(game, allowDirectStorageOverride),
           child: _buildMenuLabel(l10n.gameMenuCompressNow, LucideIcons.archive),
         ),
         PopupMenuItem(
           value: GameContextAction.recompress,
           enabled:
               game.isCompressed &&
-              !_isDirectStorageBlocked(game, allowDirectStorageOverride),
+              !_isCompressionBlocked(game, allowDirectStorageOverride),
           child: _buildMenuLabel(l10n.gameMenuRecompress, LucideIcons.archive),
         ),
         PopupMenuItem(
@@ -4,6 +4,7 @@ import 'package:flutter/material.dart';
 import 'package:flutter/services.dart';
 import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:lucide_icons/lucide_icons.dart';
+import 'package:compact_games/l10n/app_localizations.dart';
 
 import '../../../../../core/localization/app_localization.dart';
 import '../../../../../core/localization/presentation_labels.dart';
@@ -119,7 +120,8 @@ class GameDetailsInfoCard extends ConsumerWidget {
       ),
     );
     final directStorageBlocked =
-        game.isDirectStorage && !allowDirectStorageOverride;
+        game.isProtectedPackage ||
+        (game.isDirectStorage && !allowDirectStorageOverride);
     final detailsEstimate = game.isCompressed || directStorageBlocked
         ? null
         : ref
@@ -226,6 +228,9 @@ class GameDetailsInfoCard extends ConsumerWidget {
                   value:
                       '${formatBytesDetailed(context.l10n, detailsEstimate.estimatedSavedBytes)} '
                       '(${detailsEstimate.estimatedSavingsPercent.toStringAsFixed(1)}%)',
+                  trailingText: detailsEstimate.hasRange
+                      ? _estimateRangeText(l10n, detailsEstimate)
+                      : null,
                   trailing: detailsEstimate.showCommunityBadge
                       ? Tooltip(
                           message: l10n.gameEstimateCommunityTooltip,
@@ -251,3 +256,11 @@ class GameDetailsInfoCard extends ConsumerWidget {
     );
   }
 }
+
+String _estimateRangeText(AppLocalizations l10n, CompressionEstimate estimate) {
+  return l10n.gameEstimateRange(
+    formatBytes(l10n, estimate.estimatedSavedLowBytes),
+    formatBytes(l10n, estimate.estimatedSavedHighBytes),
+    estimate.confidenceLevel.localizedLabel(l10n),
+  );
+}
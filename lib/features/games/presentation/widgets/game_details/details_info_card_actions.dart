@@ -17,9 +17,15 @@ class _StatusSectionHeaderState extends State<_StatusSectionHeader> {
     if (widget.game.isUnsupported) {
       return context.l10n.gameDetailsUnsupportedWarning;
     }
+    if (widget.game.isProtectedPackage) {
+      return context.l10n.gameDetailsProtectedPackageWarning;
+    }
     if (widget.game.isDirectStorage) {
       return context.l10n.gameDetailsDirectStorageWarning;
     }
+    if (widget.game.hasCloudPlaceholders) {
+      return context.l10n.gameDetailsCloudPlaceholderWarning;
+    }
     return null;
   }
 
@@ -363,7 +369,9 @@ class _StatusActionButtons extends ConsumerWidget {
       child: FilledButton.icon(
         key: _detailsStatusPrimaryActionKey,
         style: _primaryStyle,
-        onPressed: game.isDirectStorage && !allowDirectStorageOverride
+        onPressed:
+            game.isProtectedPackage ||
+                (game.isDirectStorage && !allowDirectStorageOverride)
             ? null
             : () => ref
                   .read(compressionProvider.notifier)
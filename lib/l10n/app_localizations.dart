@@ -166,6 +166,12 @@ abstract class AppLocalizations {
   /// **'Xbox Game Pass'**
   String get platformXboxGamePass;
 
+  /// Localized message for platform riot games.
+  ///
+  /// In en, this message translates to:
+  /// **'Riot Games'**
+  String get platformRiotGames;
+
   /// Localized message for platform custom.
   ///
   /// In en, this message translates to:
@@ -1390,6 +1396,30 @@ abstract class AppLocalizations {
   /// **'Based on CompactGUI community data'**
   String get gameEstimateCommunityTooltip;
 
+  /// Expected savings range and confidence shown next to a compression estimate.
+  ///
+  /// In en, this message translates to:
+  /// **'{low}–{high} expected ({confidence})'**
+  String gameEstimateRange(String low, String high, String confidence);
+
+  /// Confidence label for estimates without compression history.
+  ///
+  /// In en, this message translates to:
+  /// **'low confidence'**
+  String get gameEstimateConfidenceLow;
+
+  /// Confidence label for estimates backed by community data or some history.
+  ///
+  /// In en, this message translates to:
+  /// **'medium confidence'**
+  String get gameEstimateConfidenceMedium;
+
+  /// Confidence label for estimates backed by this game's own history.
+  ///
+  /// In en, this message translates to:
+  /// **'high confidence'**
+  String get gameEstimateConfidenceHigh;
+
   /// Localized message for game marked unsupported.
   ///
   /// In en, this message translates to:
@@ -1528,6 +1558,18 @@ abstract class AppLocalizations {
   /// **'DirectStorage detected. Compression can impact runtime performance.'**
   String get gameDetailsDirectStorageWarning;
 
+  /// Localized message for game details cloud placeholder warning.
+  ///
+  /// In en, this message translates to:
+  /// **'Contains online-only cloud files. They are skipped during compression so they are not downloaded.'**
+  String get gameDetailsCloudPlaceholderWarning;
+
+  /// Localized message for game details protected package warning.
+  ///
+  /// In en, this message translates to:
+  /// **'Xbox app package. Compression is disabled to keep the package signature valid.'**
+  String get gameDetailsProtectedPackageWarning;
+
   /// Localized message for game details unsupported warning.
   ///
   /// In en, this message translates to:
@@ -41,6 +41,9 @@ class AppLocalizationsEn extends AppLocalizations {
   @override
   String get platformXboxGamePass => 'Xbox Game Pass';
 
+  @override
+  String get platformRiotGames => 'Riot Games';
+
   @override
   String get platformCustom => 'Custom';
 
@@ -747,6 +750,20 @@ class AppLocalizationsEn extends AppLocalizations {
   String get gameEstimateCommunityTooltip =>
       'Based on CompactGUI community data';
 
+  @override
+  String gameEstimateRange(String low, String high, String confidence) {
+    return '$low–$high expected ($confidence)';
+  }
+
+  @override
+  String get gameEstimateConfidenceLow => 'low confidence';
+
+  @override
+  String get gameEstimateConfidenceMedium => 'medium confidence';
+
+  @override
+  String get gameEstimateConfidenceHigh => 'high confidence';
+
   @override
   String gameMarkedUnsupported(String gameName) {
     return '\"$gameName\" marked as unsupported.';
@@ -828,6 +845,14 @@ class AppLocalizationsEn extends AppLocalizations {
   String get gameDetailsDirectStorageWarning =>
       'DirectStorage detected. Compression can impact runtime performance.';
 
+  @override
+  String get gameDetailsCloudPlaceholderWarning =>
+      'Contains online-only cloud files. They are skipped during compression so they are not downloaded.';
+
+  @override
+  String get gameDetailsProtectedPackageWarning =>
+      'Xbox app package. Compression is disabled to keep the package signature valid.';
+
   @override
   String get gameDetailsUnsupportedWarning =>
       'Marked by the community as unsupported.';
@@ -41,6 +41,9 @@ class AppLocalizationsEs extends AppLocalizations {
   @override
   String get platformXboxGamePass => 'Xbox Game Pass';
 
+  @override
+  String get platformRiotGames => 'Riot Games';
+
   @override
   String get platformCustom => 'Personalizado';
 
@@ -763,6 +766,20 @@ class AppLocalizationsEs extends AppLocalizations {
   String get gameEstimateCommunityTooltip =>
       'Basado en datos de la comunidad de CompactGUI';
 
+  @override
+  String gameEstimateRange(String low, String high, String confidence) {
+    return '$low–$high previstos ($confidence)';
+  }
+
+  @override
+  String get gameEstimateConfidenceLow => 'confianza baja';
+
+  @override
+  String get gameEstimateConfidenceMedium => 'confianza media';
+
+  @override
+  String get gameEstimateConfidenceHigh => 'confianza alta';
+
   @override
   String gameMarkedUnsupported(String gameName) {
     return '\"$gameName\" se marcó como no compatible.';
@@ -845,6 +862,14 @@ class AppLocalizationsEs extends AppLocalizations {
   String get gameDetailsDirectStorageWarning =>
       'Se detectó DirectStorage. La compresión puede afectar el rendimiento en ejecución.';
 
+  @override
+  String get gameDetailsCloudPlaceholderWarning =>
+      'Contiene archivos de la nube solo en línea. Se omiten durante la compresión para no descargarlos.';
+
+  @override
+  String get gameDetailsProtectedPackageWarning =>
+      'Paquete de la app de Xbox. La compresión está desactivada para mantener válida la firma del paquete.';
+
   @override
   String get gameDetailsUnsupportedWarning =>
       'Marcado por la comunidad como no compatible.';
@@ -41,6 +41,9 @@ class AppLocalizationsZh extends AppLocalizations {
   @override
   String get platformXboxGamePass => 'Xbox Game Pass';
 
+  @override
+  String get platformRiotGames => 'Riot Games';
+
   @override
   String get platformCustom => '自定义';
 
@@ -715,6 +718,20 @@ class AppLocalizationsZh extends AppLocalizations {
   @override
   String get gameEstimateCommunityTooltip => '基于 CompactGUI 社区数据';
 
+  @override
+  String gameEstimateRange(String low, String high, String confidence) {
+    return '预计 $low–$high（$confidence）';
+  }
+
+  @override
+  String get gameEstimateConfidenceLow => '低置信度';
+
+  @override
+  String get gameEstimateConfidenceMedium => '中等置信度';
+
+  @override
+  String get gameEstimateConfidenceHigh => '高置信度';
+
   @override
   String gameMarkedUnsupported(String gameName) {
     return '“$gameName”已标记为不支持。';
@@ -795,6 +812,13 @@ class AppLocalizationsZh extends AppLocalizations {
   String get gameDetailsDirectStorageWarning =>
       '检测到 DirectStorage。压缩可能影响运行时性能。';
 
+  @override
+  String get gameDetailsCloudPlaceholderWarning =>
+      '包含仅在线的云文件。压缩时会跳过这些文件，以免触发下载。';
+
+  @override
+  String get gameDetailsProtectedPackageWarning => 'Xbox 应用包。为保持包签名有效，已禁用压缩。';
+
   @override
   String get gameDetailsUnsupportedWarning => '已由社区标记为不受支持。';
 
@@ -7,6 +7,16 @@ enum SchedulerState {
   compressing,
   paused,
   backoff,
+  pausedByUser,
+}
+
+/// How CPU and input idleness combine, mirroring Rust's IdlePolicy.
+enum IdlePolicy {
+  /// Idle only when the CPU is quiet and the user has been away.
+  all,
+
+  /// Idle when either the CPU is quiet or the user has been away.
+  any,
 }
 
 /// Automation job status.
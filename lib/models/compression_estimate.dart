@@ -1,4 +1,6 @@
-enum EstimateSource { heuristic, communityDb }
+enum EstimateSource { heuristic, communityDb, sampled }
+
+enum EstimateConfidence { low, medium, high }
 
 /// Pre-compression estimate used for confirmation UX.
 class CompressionEstimate {
@@ -12,6 +14,11 @@ class CompressionEstimate {
   final bool adaptiveApplied;
   final int? communitySamples;
   final bool communityLookupPending;
+  final int estimatedSavedLowBytes;
+  final int estimatedSavedHighBytes;
+
+  /// 0.0 (heuristic only) to 1.0 (backed by this game's own history).
+  final double confidence;
 
   const CompressionEstimate({
     required this.scannedFiles,
@@ -24,13 +31,25 @@ class CompressionEstimate {
     this.adaptiveApplied = false,
     this.communitySamples,
     this.communityLookupPending = false,
-  });
+    int? estimatedSavedLowBytes,
+    int? estimatedSavedHighBytes,
+    this.confidence = 0.0,
+  }) : estimatedSavedLowBytes = estimatedSavedLowBytes ?? estimatedSavedBytes,
+       estimatedSavedHighBytes = estimatedSavedHighBytes ?? estimatedSavedBytes;
 
   double get estimatedSavingsPercent => estimatedSavingsRatio * 100.0;
 
   bool get showCommunityBadge =>
       baseSource == EstimateSource.communityDb && (communitySamples ?? 0) >= 10;
 
+  EstimateConfidence get confidenceLevel {
+    if (confidence >= 0.7) return EstimateConfidence.high;
+    if (confidence >= 0.3) return EstimateConfidence.medium;
+    return EstimateConfidence.low;
+  }
+
+  bool get hasRange => estimatedSavedHighBytes > estimatedSavedLowBytes;
+
   bool get shouldRetryCommunityLookup =>
       baseSource == EstimateSource.heuristic && communityLookupPending;
 }
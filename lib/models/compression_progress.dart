@@ -7,6 +7,9 @@ class CompressionProgress {
   final int bytesCompressed;
   final int bytesSaved;
   final Duration? estimatedTimeRemaining;
+
+  /// Bytes finished per second, averaged over the last few seconds.
+  final int throughputBytesPerSec;
   final bool isComplete;
 
   const CompressionProgress({
@@ -17,6 +20,7 @@ class CompressionProgress {
     required this.bytesCompressed,
     required this.bytesSaved,
     this.estimatedTimeRemaining,
+    this.throughputBytesPerSec = 0,
     this.isComplete = false,
   });
 
@@ -40,6 +44,7 @@ class CompressionProgress {
           bytesCompressed == other.bytesCompressed &&
           bytesSaved == other.bytesSaved &&
           estimatedTimeRemaining == other.estimatedTimeRemaining &&
+          throughputBytesPerSec == other.throughputBytesPerSec &&
           isComplete == other.isComplete;
 
   @override
@@ -51,6 +56,7 @@ class CompressionProgress {
     bytesCompressed,
     bytesSaved,
     estimatedTimeRemaining,
+    throughputBytesPerSec,
     isComplete,
   );
 }
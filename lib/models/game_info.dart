@@ -7,6 +7,7 @@ enum Platform {
   eaApp,
   battleNet,
   xboxGamePass,
+  riotGames,
   custom,
   application;
 
@@ -18,6 +19,7 @@ enum Platform {
     eaApp => 'EA App',
     battleNet => 'Battle.net',
     xboxGamePass => 'Xbox Game Pass',
+    riotGames => 'Riot Games',
     custom => 'Custom',
     application => 'Application',
   };
@@ -33,12 +35,29 @@ class GameInfo {
   final bool isCompressed;
   final bool isDirectStorage;
   final bool isUnsupported;
+
+  /// Signed UWP package (Xbox app / Store install); never compressible.
+  final bool isProtectedPackage;
+
+  /// Folder holds online-only cloud files, which compression skips.
+  final bool hasCloudPlaceholders;
+
+  /// Many files are EFS-encrypted or sparse; compression skips them.
+  final bool hasEncryptedOrSparseFiles;
+
   final bool excluded;
   final int? steamAppId;
+  final String? epicCatalogItemId;
+
+  /// Cover art the launcher cached locally, resolved during discovery.
+  final String? artworkPath;
 
-  /// Legacy timestamp field from older transports. Avoid using this for new UI.
+  /// Last launch time reported by the platform launcher, when known.
   final DateTime? lastPlayed;
 
+  /// Total playtime reported by the platform launcher, when known.
+  final int? playtimeMinutes;
+
   /// Dedicated timestamp for last successful compression.
   final DateTime? lastCompressedAt;
 
@@ -57,9 +76,15 @@ class GameInfo {
     this.isCompressed = false,
     this.isDirectStorage = false,
     this.isUnsupported = false,
+    this.isProtectedPackage = false,
+    this.hasCloudPlaceholders = false,
+    this.hasEncryptedOrSparseFiles = false,
     this.excluded = false,
     this.steamAppId,
+    this.epicCatalogItemId,
+    this.artworkPath,
     this.lastPlayed,
+    this.playtimeMinutes,
     this.lastCompressedAt,
   });
 
@@ -78,7 +103,7 @@ class GameInfo {
     if (!isCompressed) {
       return null;
     }
-    return lastCompressedAt ?? lastPlayed;
+    return lastCompressedAt;
   }
 
   GameInfo copyWith({
@@ -90,9 +115,15 @@ class GameInfo {
     bool? isCompressed,
     bool? isDirectStorage,
     bool? isUnsupported,
+    bool? isProtectedPackage,
+    bool? hasCloudPlaceholders,
+    bool? hasEncryptedOrSparseFiles,
     bool? excluded,
     int? Function()? steamAppId,
+    String? Function()? epicCatalogItemId,
+    String? Function()? artworkPath,
     DateTime? Function()? lastPlayed,
+    int? Function()? playtimeMinutes,
     DateTime? Function()? lastCompressedAt,
   }) {
     return GameInfo(
@@ -106,9 +137,20 @@ class GameInfo {
       isCompressed: isCompressed ?? this.isCompressed,
       isDirectStorage: isDirectStorage ?? this.isDirectStorage,
       isUnsupported: isUnsupported ?? this.isUnsupported,
+      isProtectedPackage: isProtectedPackage ?? this.isProtectedPackage,
+      hasCloudPlaceholders: hasCloudPlaceholders ?? this.hasCloudPlaceholders,
+      hasEncryptedOrSparseFiles:
+          hasEncryptedOrSparseFiles ?? this.hasEncryptedOrSparseFiles,
       excluded: excluded ?? this.excluded,
       steamAppId: steamAppId != null ? steamAppId() : this.steamAppId,
+      epicCatalogItemId: epicCatalogItemId != null
+          ? epicCatalogItemId()
+          : this.epicCatalogItemId,
+      artworkPath: artworkPath != null ? artworkPath() : this.artworkPath,
       lastPlayed: lastPlayed != null ? lastPlayed() : this.lastPlayed,
+      playtimeMinutes: playtimeMinutes != null
+          ? playtimeMinutes()
+          : this.playtimeMinutes,
       lastCompressedAt: lastCompressedAt != null
           ? lastCompressedAt()
           : this.lastCompressedAt,
@@ -128,9 +170,15 @@ class GameInfo {
           isCompressed == other.isCompressed &&
           isDirectStorage == other.isDirectStorage &&
           isUnsupported == other.isUnsupported &&
+          isProtectedPackage == other.isProtectedPackage &&
+          hasCloudPlaceholders == other.hasCloudPlaceholders &&
+          hasEncryptedOrSparseFiles == other.hasEncryptedOrSparseFiles &&
           excluded == other.excluded &&
           steamAppId == other.steamAppId &&
+          epicCatalogItemId == other.epicCatalogItemId &&
+          artworkPath == other.artworkPath &&
           lastPlayed == other.lastPlayed &&
+          playtimeMinutes == other.playtimeMinutes &&
           lastCompressedAt == other.lastCompressedAt;
 
   @override
@@ -143,9 +191,15 @@ class GameInfo {
     isCompressed,
     isDirectStorage,
     isUnsupported,
+    isProtectedPackage,
+    hasCloudPlaceholders,
+    hasEncryptedOrSparseFiles,
     excluded,
     steamAppId,
+    epicCatalogItemId,
+    artworkPath,
     lastPlayed,
+    playtimeMinutes,
     lastCompressedAt,
   );
 
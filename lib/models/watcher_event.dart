@@ -1,5 +1,15 @@
 /// Type of watcher event.
-enum WatcherEventType { installed, modified, uninstalled }
+enum WatcherEventType {
+  installed,
+  modified,
+  uninstalled,
+
+  /// The drive holding watch root [WatcherEvent.gamePath] went away.
+  driveOffline,
+
+  /// The drive holding watch root [WatcherEvent.gamePath] is back.
+  driveOnline,
+}
 
 /// A file-system watcher event from the Rust backend.
 class WatcherEvent {
@@ -187,6 +187,7 @@ class AutomationDiscoveredWatchPaths {
     for (final game in games) {
       if (game.excluded ||
           game.isUnsupported ||
+          game.isProtectedPackage ||
           game.platform == Platform.application) {
         continue;
       }
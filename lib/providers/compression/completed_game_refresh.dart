@@ -94,7 +94,6 @@ void _applyOptimisticCompressionCompletionUpdate({
   read(gameListProvider.notifier).updateGame(
     currentGame.copyWith(
       isCompressed: true,
-      lastPlayed: () => completedAt,
       lastCompressedAt: () => completedAt,
     ),
   );
@@ -130,9 +129,6 @@ GameInfo _mergeCompletedGameRefresh({
     isCompressed: hydratedGame.isCompressed || currentGame.isCompressed,
     compressedSize: () =>
         hydratedGame.compressedSize ?? currentGame.compressedSize,
-    lastPlayed: shouldPreserveCurrentTimestamp
-        ? () => currentGame.lastCompressedAt ?? currentGame.lastPlayed
-        : null,
     lastCompressedAt: shouldPreserveCurrentTimestamp
         ? () => currentGame.lastCompressed
         : null,
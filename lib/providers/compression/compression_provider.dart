@@ -275,6 +275,7 @@ class CompressionNotifier extends Notifier<CompressionState> {
       bytesCompressed: progress.bytesCompressed,
       bytesSaved: progress.bytesSaved,
       estimatedTimeRemaining: progress.estimatedTimeRemaining,
+      throughputBytesPerSec: progress.throughputBytesPerSec,
       isComplete: progress.isComplete,
     );
   }
@@ -119,7 +119,10 @@ bool _matchesCompressionFilter(GameInfo game, CompressionFilter filter) {
     CompressionFilter.all => true,
     CompressionFilter.compressed => game.isCompressed,
     CompressionFilter.uncompressed =>
-      !game.isCompressed && !game.isDirectStorage && !game.isUnsupported,
+      !game.isCompressed &&
+          !game.isDirectStorage &&
+          !game.isUnsupported &&
+          !game.isProtectedPackage,
   };
 }
 
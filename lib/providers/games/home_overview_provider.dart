@@ -57,7 +57,9 @@ final homeOverviewProvider = Provider<HomeOverviewUiModel>((ref) {
       }
       continue;
     }
-    if (game.isDirectStorage || game.isUnsupported) {
+    if (game.isDirectStorage ||
+        game.isUnsupported ||
+        game.isProtectedPackage) {
       protectedCount += 1;
       continue;
     }
@@ -313,7 +313,9 @@ class CoverArtService {
     if (game.platform != Platform.steam) {
       return null;
     }
-    final steamCover = await _resolveSteamLibraryCover(game.path);
+    final steamCover =
+        await _existingArtworkPath(game) ??
+        await _resolveSteamLibraryCover(game.path);
     if (steamCover == null) {
       return null;
     }
@@ -1,6 +1,15 @@
 part of 'cover_art_service.dart';
 
 extension _CoverArtServiceSteam on CoverArtService {
+  /// Art discovery already resolved from the launcher cache, if still on disk.
+  Future<String?> _existingArtworkPath(GameInfo game) async {
+    final artworkPath = game.artworkPath;
+    if (artworkPath == null || artworkPath.isEmpty) {
+      return null;
+    }
+    return await File(artworkPath).exists() ? artworkPath : null;
+  }
+
   Future<String?> _resolveSteamLibraryCover(String gamePath) async {
     final steamAppsPath = _steamAppsPathFromGamePath(gamePath);
     if (steamAppsPath == null) {
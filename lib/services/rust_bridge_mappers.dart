@@ -6,6 +6,9 @@ GameInfo _mapFrbGameInfo(rust_types.FrbGameInfo frb) {
   final lastPlayed = frb.lastPlayed != null
       ? DateTime.fromMillisecondsSinceEpoch(frb.lastPlayed!.toInt())
       : null;
+  final lastCompressedAt = frb.lastCompressed != null
+      ? DateTime.fromMillisecondsSinceEpoch(frb.lastCompressed!.toInt())
+      : null;
   return GameInfo(
     name: frb.name,
     path: frb.path,
@@ -15,10 +18,16 @@ GameInfo _mapFrbGameInfo(rust_types.FrbGameInfo frb) {
     isCompressed: frb.isCompressed,
     isDirectStorage: frb.isDirectstorage,
     isUnsupported: frb.isUnsupported,
+    isProtectedPackage: frb.isProtectedPackage,
+    hasCloudPlaceholders: frb.hasCloudPlaceholders,
+    hasEncryptedOrSparseFiles: frb.hasEncryptedOrSparseFiles,
     excluded: frb.excluded,
     steamAppId: frb.steamAppId?.toInt(),
+    epicCatalogItemId: frb.epicCatalogItemId,
+    artworkPath: frb.artworkPath,
     lastPlayed: lastPlayed,
-    lastCompressedAt: lastPlayed,
+    playtimeMinutes: frb.playtimeMinutes?.toInt(),
+    lastCompressedAt: lastCompressedAt,
   );
 }
 
@@ -33,6 +42,7 @@ Platform _mapFrbPlatform(rust_types.FrbPlatform frb) {
     rust_types.FrbPlatform.xboxGamePass => Platform.xboxGamePass,
     rust_types.FrbPlatform.custom => Platform.custom,
     rust_types.FrbPlatform.application => Platform.application,
+    rust_types.FrbPlatform.riotGames => Platform.riotGames,
   };
 }
 
@@ -47,6 +57,7 @@ CompressionProgress _mapFrbProgress(rust_types.FrbCompressionProgress frb) {
     estimatedTimeRemaining: frb.estimatedTimeRemainingMs != null
         ? Duration(milliseconds: frb.estimatedTimeRemainingMs!.toInt())
         : null,
+    throughputBytesPerSec: frb.throughputBytesPerSec.toInt(),
     isComplete: frb.isComplete,
   );
 }
@@ -63,6 +74,9 @@ CompressionEstimate _mapFrbEstimate(rust_types.FrbCompressionEstimate frb) {
     adaptiveApplied: frb.adaptiveApplied,
     communitySamples: frb.communitySamples?.toInt(),
     communityLookupPending: frb.communityLookupPending,
+    estimatedSavedLowBytes: frb.estimatedSavedLowBytes.toInt(),
+    estimatedSavedHighBytes: frb.estimatedSavedHighBytes.toInt(),
+    confidence: frb.confidence,
   );
 }
 
@@ -74,6 +88,7 @@ EstimateSource _mapFrbEstimateSource(
       EstimateSource.heuristic,
     rust_types.FrbCompressionEstimateSource.communityDb =>
       EstimateSource.communityDb,
+    rust_types.FrbCompressionEstimateSource.sampled => EstimateSource.sampled,
   };
 }
 
@@ -102,6 +117,13 @@ rust_types.FrbCompressionAlgorithm _toFrbAlgorithm(CompressionAlgorithm algo) {
   };
 }
 
+rust_automation_types.FrbIdlePolicy _toFrbIdlePolicy(IdlePolicy policy) {
+  return switch (policy) {
+    IdlePolicy.all => rust_automation_types.FrbIdlePolicy.all,
+    IdlePolicy.any => rust_automation_types.FrbIdlePolicy.any,
+  };
+}
+
 rust_types.FrbPlatform _toFrbPlatform(Platform platform) {
   return switch (platform) {
     Platform.steam => rust_types.FrbPlatform.steam,
@@ -111,6 +133,7 @@ rust_types.FrbPlatform _toFrbPlatform(Platform platform) {
     Platform.eaApp => rust_types.FrbPlatform.eaApp,
     Platform.battleNet => rust_types.FrbPlatform.battleNet,
     Platform.xboxGamePass => rust_types.FrbPlatform.xboxGamePass,
+    Platform.riotGames => rust_types.FrbPlatform.riotGames,
     Platform.custom => rust_types.FrbPlatform.custom,
     Platform.application => rust_types.FrbPlatform.application,
   };
@@ -150,6 +173,18 @@ WatcherEvent _mapFrbWatcherEvent(rust_automation_types.FrbWatcherEvent frb) {
         gameName: gameName,
         timestamp: DateTime.now(),
       ),
+    rust_automation_types.FrbWatcherEvent_DriveOffline(:final path) =>
+      WatcherEvent(
+        type: WatcherEventType.driveOffline,
+        gamePath: path,
+        timestamp: DateTime.now(),
+      ),
+    rust_automation_types.FrbWatcherEvent_DriveOnline(:final path) =>
+      WatcherEvent(
+        type: WatcherEventType.driveOnline,
+        gamePath: path,
+        timestamp: DateTime.now(),
+      ),
   };
 }
 
@@ -215,5 +250,7 @@ SchedulerState _mapFrbSchedulerState(
       SchedulerState.compressing,
     rust_automation_types.FrbSchedulerState.paused => SchedulerState.paused,
     rust_automation_types.FrbSchedulerState.backoff => SchedulerState.backoff,
+    rust_automation_types.FrbSchedulerState.pausedByUser =>
+      SchedulerState.pausedByUser,
   };
 }
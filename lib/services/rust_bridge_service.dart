@@ -150,11 +150,16 @@ class RustBridgeService {
     rust_compression.cancelCompression();
   }
 
-  /// Persist compression history entries to disk.
+  /// Schedule a write of compression history entries to disk.
   void persistCompressionHistory() {
     rust_compression.persistCompressionHistory();
   }
 
+  /// Write pending compression history entries to disk now.
+  void flushCompressionHistory() {
+    rust_compression.flushCompressionHistory();
+  }
+
   CompressionProgress? getCompressionProgress() {
     final progress = rust_compression.getCompressionProgress();
     if (progress == null) {
@@ -332,6 +337,21 @@ class RustBridgeService {
     required CompressionAlgorithm algorithm,
     bool allowDirectStorageOverride = false,
     int? ioParallelismOverride,
+    int? maxConcurrentJobs,
+    List<String> blockingProcesses = const [],
+    bool includeRemovableDrives = false,
+    bool allowAnticheatCompression = false,
+    int? maxBytesPerSec,
+    List<String> watcherIgnoredDirs = const [],
+    List<String> watcherIgnoredExtensions = const [],
+    List<String> watcherTempFilePatterns = const [],
+    int? watcherMaxEventDepth,
+    bool dryRun = false,
+    int? inputIdleSeconds,
+    IdlePolicy idlePolicy = IdlePolicy.all,
+    bool ignoreFocusAssist = false,
+    bool pauseWhenThermallyConstrained = false,
+    int? maintenanceWakeMinuteOfDay,
   }) {
     return rust_automation.updateAutomationConfig(
       config: rust_automation_types.FrbAutomationConfig(
@@ -345,6 +365,25 @@ class RustBridgeService {
         ioParallelismOverride: ioParallelismOverride == null
             ? null
             : BigInt.from(ioParallelismOverride),
+        maxConcurrentJobs: maxConcurrentJobs,
+        blockingProcesses: blockingProcesses,
+        includeRemovableDrives: includeRemovableDrives,
+        allowAnticheatCompression: allowAnticheatCompression,
+        maxBytesPerSec: maxBytesPerSec == null
+            ? null
+            : BigInt.from(maxBytesPerSec),
+        watcherIgnoredDirs: watcherIgnoredDirs,
+        watcherIgnoredExtensions: watcherIgnoredExtensions,
+        watcherTempFilePatterns: watcherTempFilePatterns,
+        watcherMaxEventDepth: watcherMaxEventDepth,
+        dryRun: dryRun,
+        inputIdleSeconds: inputIdleSeconds == null
+            ? null
+            : BigInt.from(inputIdleSeconds),
+        idlePolicy: _toFrbIdlePolicy(idlePolicy),
+        ignoreFocusAssist: ignoreFocusAssist,
+        pauseWhenThermallyConstrained: pauseWhenThermallyConstrained,
+        maintenanceWakeMinuteOfDay: maintenanceWakeMinuteOfDay,
       ),
     );
   }
@@ -448,7 +487,7 @@ class RustBridgeService {
     await _waitForManualCompressionToStop(manualCompressionStopTimeout);
 
     try {
-      persistCompressionHistory();
+      flushCompressionHistory();
     } catch (_) {
       // Best effort: app is closing.
     }
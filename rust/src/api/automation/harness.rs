@@ -0,0 +1,325 @@
+//! End-to-end harness for the auto-compression worker.
+//!
+//! Runs the real `auto_loop` on its own thread with injected watcher
+//! events, a fake idle flag and a stand-in compression executor, so whole
+//! paths through the state machine (install, settle, idle, compress,
+//! complete) can be exercised without NTFS, user input or the engine.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+
+use super::worker::{self, LoopOverrides};
+use super::worker_compression::CompressionResult;
+use super::{shared_state_lock, AutomationControl, SharedAutoState};
+use crate::api::automation_types::FrbAutomationConfig;
+use crate::automation::journal::JournalWriter;
+use crate::automation::scheduler::AutomationJob;
+use crate::automation::watcher::WatchEvent;
+use crate::compression::engine::{CompressionStats, PauseHandle};
+use crate::settings::AutomationSettings;
+
+/// Serializes tests that run the worker; it publishes to process-wide state.
+pub(super) static SERVICE_TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+const TICK: Duration = Duration::from_millis(10);
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(super) struct AutomationHarness {
+    stop_tx: Sender<()>,
+    config_tx: Sender<FrbAutomationConfig>,
+    control_tx: Sender<AutomationControl>,
+    events_tx: Sender<WatchEvent>,
+    idle: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+    _journal_dir: TempDir,
+}
+
+impl AutomationHarness {
+    /// Start the worker with `executor` standing in for compression. The
+    /// user starts out active and jobs settle as soon as they are seen.
+    pub(super) fn start(
+        executor: impl Fn(&AutomationJob, &PauseHandle) -> CompressionResult + Send + Sync + 'static,
+    ) -> Self {
+        let journal_dir = TempDir::new().expect("journal dir");
+        let (stop_tx, stop_rx) = channel();
+        let (config_tx, config_rx) = channel();
+        let (control_tx, control_rx) = channel();
+        let (events_tx, events_rx) = channel();
+        let idle = Arc::new(AtomicBool::new(false));
+        let overrides = LoopOverrides {
+            tick: Some(TICK),
+            journal: Some(JournalWriter::new(
+                journal_dir.path().join("automation_journal.json"),
+            )),
+            idle: Some(idle.clone()),
+            events: Some(events_rx),
+            executor: Some(Arc::new(executor)),
+        };
+        let handle = std::thread::spawn(move || {
+            worker::auto_loop(stop_rx, config_rx, control_rx, overrides);
+        });
+
+        let harness = Self {
+            stop_tx,
+            config_tx,
+            control_tx,
+            events_tx,
+            idle,
+            handle: Some(handle),
+            _journal_dir: journal_dir,
+        };
+        harness.configure(FrbAutomationConfig {
+            cooldown_seconds: 0,
+            ..AutomationSettings::default().into()
+        });
+        harness
+    }
+
+    pub(super) fn configure(&self, config: FrbAutomationConfig) {
+        self.config_tx.send(config).expect("worker receives config");
+    }
+
+    pub(super) fn set_idle(&self, idle: bool) {
+        self.idle.store(idle, Ordering::Relaxed);
+    }
+
+    pub(super) fn inject(&self, event: WatchEvent) {
+        self.events_tx.send(event).expect("worker receives events");
+    }
+
+    pub(super) fn control(&self, command: AutomationControl) {
+        self.control_tx
+            .send(command)
+            .expect("worker receives controls");
+    }
+
+    /// Block until `predicate` holds for the published worker state.
+    ///
+    /// Panics with `what` if it does not within a few seconds.
+    pub(super) fn wait_for(&self, what: &str, predicate: impl Fn(&SharedAutoState) -> bool) {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while Instant::now() < deadline {
+            {
+                let state = shared_state_lock()
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                if predicate(&state) {
+                    return;
+                }
+            }
+            std::thread::sleep(TICK);
+        }
+        panic!("timed out waiting for {what}");
+    }
+
+    /// Ask the worker to stop without waiting for it.
+    pub(super) fn request_stop(&self) {
+        let _ = self.stop_tx.send(());
+    }
+
+    /// Block until the worker leaves its loop.
+    pub(super) fn wait_for_exit(&mut self) {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        let handle = self.handle.take().expect("worker still attached");
+        while !handle.is_finished() {
+            assert!(Instant::now() < deadline, "timed out waiting for exit");
+            std::thread::sleep(TICK);
+        }
+        handle.join().expect("worker exits cleanly");
+    }
+}
+
+impl Drop for AutomationHarness {
+    fn drop(&mut self) {
+        let _ = self.stop_tx.send(());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Synthetic install of a game at `path`.
+pub(super) fn installed(path: &str) -> WatchEvent {
+    let path = PathBuf::from(path);
+    WatchEvent::GameInstalled {
+        game_name: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+        path,
+    }
+}
+
+/// Result of a run that compressed `original_bytes` down by half.
+pub(super) fn success(job: &AutomationJob, original_bytes: u64) -> CompressionResult {
+    CompressionResult::Success {
+        idempotency_key: job.idempotency_key.clone(),
+        stats: CompressionStats {
+            original_bytes,
+            compressed_bytes: original_bytes / 2,
+            files_processed: 1,
+            files_skipped: 0,
+            files_skipped_cloud: 0,
+            files_skipped_permission: 0,
+            files_skipped_encrypted: 0,
+            files_skipped_sparse: 0,
+            attribute_skips: Vec::new(),
+            alternate_stream_files: 0,
+            alternate_stream_bytes: 0,
+            files_already_compressed: 0,
+            bytes_already_compressed: 0,
+            bytes_already_compressed_on_disk: 0,
+            was_cancelled: false,
+            throughput: None,
+            duration_ms: 1,
+            extensions: Vec::new(),
+        },
+    }
+}
+
+mod tests {
+    use super::*;
+    use crate::api::automation_types::{FrbAutomationJobStatus, FrbSchedulerState};
+
+    fn job_status(state: &SharedAutoState, path: &str) -> Option<FrbAutomationJobStatus> {
+        state
+            .queue
+            .iter()
+            .find(|job| job.game_path == path)
+            .map(|job| job.status)
+    }
+
+    #[test]
+    fn install_settles_then_compresses_once_idle() {
+        let _guard = SERVICE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let executor_ran = ran.clone();
+        let harness = AutomationHarness::start(move |job, _pause| {
+            executor_ran.lock().unwrap().push(job.game_path.clone());
+            success(job, 1024)
+        });
+        let game = r"C:\Games\HarnessInstall";
+
+        harness.inject(installed(game));
+        harness.wait_for("job to wait for idle", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::WaitingForIdle)
+        });
+        assert!(ran.lock().unwrap().is_empty(), "active user blocks work");
+
+        harness.set_idle(true);
+        harness.wait_for("job to complete", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::Completed)
+        });
+        assert_eq!(*ran.lock().unwrap(), vec![PathBuf::from(game)]);
+    }
+
+    #[test]
+    fn failure_holds_the_rest_of_the_queue_in_backoff() {
+        let _guard = SERVICE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let harness = AutomationHarness::start(|job, _pause| CompressionResult::Failed {
+            idempotency_key: job.idempotency_key.clone(),
+            error: "disk full".to_string(),
+        });
+
+        harness.inject(installed(r"C:\Games\HarnessFailing"));
+        harness.inject(installed(r"C:\Games\HarnessWaiting"));
+        harness.wait_for("both jobs queued", |state| state.queue.len() == 2);
+        harness.set_idle(true);
+        harness.wait_for("failure and backoff", |state| {
+            state.scheduler_state == FrbSchedulerState::Backoff
+                && state
+                    .queue
+                    .iter()
+                    .filter(|job| job.status == FrbAutomationJobStatus::Failed)
+                    .count()
+                    == 1
+        });
+    }
+
+    #[test]
+    fn drain_runs_the_queue_then_exits() {
+        let _guard = SERVICE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ran = Arc::new(Mutex::new(Vec::new()));
+        let executor_ran = ran.clone();
+        let mut harness = AutomationHarness::start(move |job, _pause| {
+            executor_ran.lock().unwrap().push(job.game_path.clone());
+            success(job, 2048)
+        });
+
+        harness.inject(installed(r"C:\Games\HarnessFirst"));
+        harness.inject(installed(r"C:\Games\HarnessSecond"));
+        harness.wait_for("both jobs queued", |state| state.queue.len() == 2);
+        harness.control(AutomationControl::Drain);
+        harness.set_idle(true);
+
+        harness.wait_for_exit();
+        assert_eq!(ran.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn user_pause_keeps_the_running_job_until_resume() {
+        let _guard = SERVICE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let parked = Arc::new(AtomicBool::new(false));
+        let executor_parked = parked.clone();
+        let harness = AutomationHarness::start(move |job, pause| {
+            // Stands in for the engine parking between files.
+            while !pause.is_paused() {
+                std::thread::sleep(TICK);
+            }
+            executor_parked.store(true, Ordering::Relaxed);
+            while pause.is_paused() {
+                std::thread::sleep(TICK);
+            }
+            success(job, 1024)
+        });
+        let game = r"C:\Games\HarnessPaused";
+
+        harness.inject(installed(game));
+        harness.set_idle(true);
+        harness.wait_for("job to start", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::Compressing)
+        });
+        harness.control(AutomationControl::Pause);
+        harness.wait_for("user pause", |state| {
+            state.scheduler_state == FrbSchedulerState::Paused
+        });
+        harness.wait_for("job to park", |_| parked.load(Ordering::Relaxed));
+        // Idle ticks while paused must not wake the job.
+        std::thread::sleep(TICK * 10);
+        harness.wait_for("job still running while paused", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::Compressing)
+        });
+
+        harness.control(AutomationControl::Resume);
+        harness.wait_for("job to complete after resume", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::Completed)
+        });
+    }
+
+    #[test]
+    fn hard_cancel_stops_waiting_for_a_stuck_job() {
+        let _guard = SERVICE_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (release_tx, release_rx) = channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let mut harness = AutomationHarness::start(move |job, _pause| {
+            // Ignores cancellation until the test lets it go.
+            let _ = release_rx.lock().unwrap().recv();
+            success(job, 1024)
+        });
+        let game = r"C:\Games\HarnessStuck";
+
+        harness.inject(installed(game));
+        harness.set_idle(true);
+        harness.wait_for("job to start", |state| {
+            job_status(state, game) == Some(FrbAutomationJobStatus::Compressing)
+        });
+        harness.request_stop();
+        harness.control(AutomationControl::HardCancel);
+
+        harness.wait_for_exit();
+        drop(release_tx);
+    }
+}
@@ -3,27 +3,59 @@
 //! Manages the auto-compression lifecycle: start/stop, watcher events,
 //! scheduler state, and automation queue streaming to Flutter.
 
+#[cfg(test)]
+mod harness;
 mod worker;
 mod worker_broadcast;
 mod worker_compression;
+mod worker_history;
+mod worker_notifications;
+mod worker_progress;
 mod worker_reconcile;
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Mutex, OnceLock};
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use flutter_rust_bridge::frb;
 
 use super::automation_types::{
-    FrbAutomationConfig, FrbAutomationError, FrbAutomationJob, FrbSchedulerState,
+    FrbAutomationConfig, FrbAutomationError, FrbAutomationHistoryEntry, FrbAutomationHistoryFilter,
+    FrbAutomationJob, FrbAutomationMetrics, FrbAutomationNotification,
+    FrbAutomationOverallProgress, FrbDaemonStatus, FrbPendingSettle, FrbSchedulerState,
+    FrbSchedulerStatus, FrbShutdownOutcome, FrbShutdownProgress, FrbShutdownStage,
     FrbWatcherDiagnostics, FrbWatcherEvent,
 };
+use super::types::FrbCompressionProgress;
+use crate::automation::duration::JobDurationEstimator;
+use crate::automation::event_log::AutomationEventLog;
+use crate::automation::instance_lock::{self, InstanceGuard};
+use crate::automation::watcher::{FilteredEventCounts, PendingSettle};
 use crate::frb_generated::StreamSink;
+use crate::settings::AutomationSettings;
+
+/// User commands delivered to the running auto_loop.
+pub(super) enum AutomationControl {
+    Pause,
+    Resume,
+    StopAfterCurrent,
+    Drain,
+    /// Queue these installed games, path and name, without a settle window.
+    Enqueue(Vec<(PathBuf, Option<String>)>),
+    /// Sent during a timed stop: give up waiting for cancelled jobs.
+    HardCancel,
+}
 
 struct ActiveAutoCompression {
     stop_tx: Sender<()>,
     config_tx: Sender<FrbAutomationConfig>,
+    control_tx: Sender<AutomationControl>,
     handle: JoinHandle<()>,
+    /// Keeps other processes from starting automation meanwhile.
+    _instance: InstanceGuard,
 }
 
 /// Shared state snapshot updated by the auto_loop, read by sync getters.
@@ -49,13 +81,47 @@ impl Default for SharedAutoState {
 
 static ACTIVE_AUTO: OnceLock<Mutex<Option<ActiveAutoCompression>>> = OnceLock::new();
 static SHARED_STATE: OnceLock<Mutex<SharedAutoState>> = OnceLock::new();
+/// Watcher filter counters, kept apart from `SharedAutoState` so the
+/// bridge type does not grow.
+static FILTERED_EVENTS: Mutex<FilteredEventCounts> = Mutex::new(FilteredEventCounts {
+    noise: 0,
+    user_state: 0,
+    ignore_marker: 0,
+    too_deep: 0,
+    self_compression: 0,
+});
+static PENDING_SETTLES: Mutex<Vec<PendingSettle>> = Mutex::new(Vec::new());
+/// Latest engine progress of each compressing job, by game path.
+static ACTIVE_JOB_PROGRESS: Mutex<Option<HashMap<PathBuf, FrbCompressionProgress>>> =
+    Mutex::new(None);
+static ACTIVE_JOB_PROGRESS_SINKS: Mutex<Vec<StreamSink<FrbCompressionProgress>>> =
+    Mutex::new(Vec::new());
+/// Structured status from the last loop pass; `None` while stopped.
+static SCHEDULER_STATUS: Mutex<Option<FrbSchedulerStatus>> = Mutex::new(None);
+/// Throughput estimates for the current settings; `None` while stopped.
+static JOB_DURATIONS: Mutex<Option<JobDurationEstimator>> = Mutex::new(None);
 static AUTO_STATUS_SINKS: OnceLock<Mutex<Vec<StreamSink<bool>>>> = OnceLock::new();
 static WATCHER_EVENT_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbWatcherEvent>>>> = OnceLock::new();
 static SCHEDULER_STATE_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbSchedulerState>>>> = OnceLock::new();
 static AUTOMATION_QUEUE_SINKS: OnceLock<Mutex<Vec<StreamSink<Vec<FrbAutomationJob>>>>> =
     OnceLock::new();
+static AUTOMATION_NOTIFICATION_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbAutomationNotification>>>> =
+    OnceLock::new();
+
+/// Progress of the running batch, replayed to new subscribers.
+static LATEST_OVERALL_PROGRESS: Mutex<Option<FrbAutomationOverallProgress>> = Mutex::new(None);
+static AUTOMATION_OVERALL_PROGRESS_SINKS: OnceLock<
+    Mutex<Vec<StreamSink<FrbAutomationOverallProgress>>>,
+> = OnceLock::new();
+static SHUTDOWN_PROGRESS_SINKS: Mutex<Vec<StreamSink<FrbShutdownProgress>>> =
+    Mutex::new(Vec::new());
+/// A service a timed stop gave up waiting for; no new service starts
+/// until its thread exits.
+static DETACHED_SERVICE: Mutex<Option<ActiveAutoCompression>> = Mutex::new(None);
 
 const MAX_STREAM_SINKS: usize = 32;
+/// How often a timed stop checks whether the service thread has exited.
+const SHUTDOWN_POLL: Duration = Duration::from_millis(50);
 
 fn active_auto_lock() -> &'static Mutex<Option<ActiveAutoCompression>> {
     ACTIVE_AUTO.get_or_init(|| Mutex::new(None))
@@ -65,6 +131,32 @@ pub(super) fn shared_state_lock() -> &'static Mutex<SharedAutoState> {
     SHARED_STATE.get_or_init(|| Mutex::new(SharedAutoState::default()))
 }
 
+pub(super) fn filtered_events_lock() -> &'static Mutex<FilteredEventCounts> {
+    &FILTERED_EVENTS
+}
+
+pub(super) fn pending_settles_lock() -> &'static Mutex<Vec<PendingSettle>> {
+    &PENDING_SETTLES
+}
+
+pub(super) fn active_job_progress_lock(
+) -> &'static Mutex<Option<HashMap<PathBuf, FrbCompressionProgress>>> {
+    &ACTIVE_JOB_PROGRESS
+}
+
+pub(super) fn active_job_progress_sinks_lock(
+) -> &'static Mutex<Vec<StreamSink<FrbCompressionProgress>>> {
+    &ACTIVE_JOB_PROGRESS_SINKS
+}
+
+pub(super) fn scheduler_status_lock() -> &'static Mutex<Option<FrbSchedulerStatus>> {
+    &SCHEDULER_STATUS
+}
+
+pub(super) fn job_durations_lock() -> &'static Mutex<Option<JobDurationEstimator>> {
+    &JOB_DURATIONS
+}
+
 pub(super) fn auto_status_sinks_lock() -> &'static Mutex<Vec<StreamSink<bool>>> {
     AUTO_STATUS_SINKS.get_or_init(|| Mutex::new(Vec::new()))
 }
@@ -82,6 +174,26 @@ pub(super) fn automation_queue_sinks_lock() -> &'static Mutex<Vec<StreamSink<Vec
     AUTOMATION_QUEUE_SINKS.get_or_init(|| Mutex::new(Vec::new()))
 }
 
+pub(super) fn automation_notification_sinks_lock(
+) -> &'static Mutex<Vec<StreamSink<FrbAutomationNotification>>> {
+    AUTOMATION_NOTIFICATION_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub(super) fn latest_overall_progress_lock() -> &'static Mutex<Option<FrbAutomationOverallProgress>>
+{
+    &LATEST_OVERALL_PROGRESS
+}
+
+pub(super) fn automation_overall_progress_sinks_lock(
+) -> &'static Mutex<Vec<StreamSink<FrbAutomationOverallProgress>>> {
+    AUTOMATION_OVERALL_PROGRESS_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub(super) fn shutdown_progress_sinks_lock() -> &'static Mutex<Vec<StreamSink<FrbShutdownProgress>>>
+{
+    &SHUTDOWN_PROGRESS_SINKS
+}
+
 // ── Public FRB API ──────────────────────────────────────────────────
 
 /// Start auto-compression background service.
@@ -94,14 +206,30 @@ pub fn start_auto_compression() -> Result<(), FrbAutomationError> {
     if guard.is_some() {
         return Err(FrbAutomationError::AlreadyRunning);
     }
+    if !reap_detached_service() {
+        return Err(FrbAutomationError::StartFailed {
+            message: "the previous auto-compression service is still shutting down".to_owned(),
+        });
+    }
+    let instance = instance_lock::acquire().map_err(|e| FrbAutomationError::RunningElsewhere {
+        owner: e
+            .owner
+            .map_or_else(|| "another process".to_owned(), |owner| owner.to_string()),
+    })?;
 
     let (stop_tx, stop_rx) = channel::<()>();
     let (config_tx, config_rx) = channel::<FrbAutomationConfig>();
+    let (control_tx, control_rx) = channel::<AutomationControl>();
 
     let handle = thread::Builder::new()
         .name("compact-games-auto-compression".to_owned())
         .spawn(move || {
-            worker::auto_loop(stop_rx, config_rx);
+            worker::auto_loop(
+                stop_rx,
+                config_rx,
+                control_rx,
+                worker::LoopOverrides::default(),
+            );
         })
         .map_err(|e| FrbAutomationError::StartFailed {
             message: e.to_string(),
@@ -110,7 +238,9 @@ pub fn start_auto_compression() -> Result<(), FrbAutomationError> {
     *guard = Some(ActiveAutoCompression {
         stop_tx,
         config_tx,
+        control_tx,
         handle,
+        _instance: instance,
     });
     drop(guard);
     worker::broadcast_auto_status(true);
@@ -120,26 +250,105 @@ pub fn start_auto_compression() -> Result<(), FrbAutomationError> {
 /// Stop auto-compression background service.
 #[frb(sync)]
 pub fn stop_auto_compression() -> Result<(), FrbAutomationError> {
-    let active = {
-        let mut guard = active_auto_lock().lock().unwrap_or_else(|poisoned| {
-            log::warn!("AUTO compression lock poisoned during stop; recovering");
-            poisoned.into_inner()
-        });
-        guard.take()
-    };
+    let active = take_active_service()?;
+    let _ = active.stop_tx.send(());
+    let join_result = active.handle.join();
+    worker::broadcast_auto_status(false);
+    join_result.map_err(|_| FrbAutomationError::StopFailed {
+        message: "auto-compression thread panicked during shutdown".to_owned(),
+    })?;
 
-    let Some(active) = active else {
-        return Err(FrbAutomationError::NotRunning);
-    };
+    Ok(())
+}
 
+/// Stop the service without waiting on jobs that ignore cancellation.
+///
+/// Jobs get the first half of `timeout_ms` to stop gracefully, then are
+/// hard-cancelled and no longer waited for. If the service thread is still
+/// busy at the deadline it is detached and finishes in the background;
+/// starting the service again fails until it has. Stages are reported to
+/// [`watch_shutdown_progress`] subscribers as they happen.
+pub fn stop_auto_compression_with_timeout(
+    timeout_ms: u64,
+) -> Result<FrbShutdownOutcome, FrbAutomationError> {
+    let started = Instant::now();
+    let timeout = Duration::from_millis(timeout_ms);
+    let active = take_active_service()?;
     let _ = active.stop_tx.send(());
+
+    let mut stage = FrbShutdownStage::SoftCancel;
+    while !active.handle.is_finished() {
+        let elapsed = started.elapsed();
+        if elapsed >= timeout {
+            log::warn!(
+                "Auto-compression did not stop within {timeout_ms} ms; leaving it to finish in the background"
+            );
+            worker::broadcast_shutdown_progress(FrbShutdownStage::Detached, None);
+            *DETACHED_SERVICE
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(active);
+            worker::broadcast_auto_status(false);
+            return Ok(FrbShutdownOutcome {
+                stopped_at: FrbShutdownStage::Detached,
+                elapsed_ms: elapsed.as_millis() as u64,
+            });
+        }
+        if stage == FrbShutdownStage::SoftCancel && elapsed >= timeout / 2 {
+            log::info!("Auto-compression jobs did not stop gracefully in time; cancelling them");
+            stage = FrbShutdownStage::HardCancel;
+            let _ = active.control_tx.send(AutomationControl::HardCancel);
+        }
+        thread::sleep(SHUTDOWN_POLL.min(timeout - elapsed));
+    }
+
     let join_result = active.handle.join();
     worker::broadcast_auto_status(false);
     join_result.map_err(|_| FrbAutomationError::StopFailed {
         message: "auto-compression thread panicked during shutdown".to_owned(),
     })?;
+    Ok(FrbShutdownOutcome {
+        stopped_at: stage,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
 
-    Ok(())
+fn take_active_service() -> Result<ActiveAutoCompression, FrbAutomationError> {
+    let mut guard = active_auto_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("AUTO compression lock poisoned during stop; recovering");
+        poisoned.into_inner()
+    });
+    guard.take().ok_or(FrbAutomationError::NotRunning)
+}
+
+/// Join a service detached by a timed stop once its thread has exited.
+/// False while it is still running.
+fn reap_detached_service() -> bool {
+    let mut guard = DETACHED_SERVICE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match guard.take() {
+        Some(service) if !service.handle.is_finished() => {
+            *guard = Some(service);
+            false
+        }
+        Some(service) => {
+            if service.handle.join().is_err() {
+                log::error!("Detached auto-compression thread panicked during shutdown");
+            }
+            true
+        }
+        None => true,
+    }
+}
+
+/// Drop the service handle after the worker stopped on its own, so the
+/// service reads as stopped and can be started again.
+pub(super) fn release_stopped_service() {
+    let mut guard = active_auto_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("AUTO compression lock poisoned during self-stop; recovering");
+        poisoned.into_inner()
+    });
+    guard.take();
 }
 
 /// Returns whether the auto-compression service is currently running.
@@ -187,6 +396,25 @@ pub fn watch_watcher_events(sink: StreamSink<FrbWatcherEvent>) -> Result<(), Frb
     Ok(())
 }
 
+/// Subscribe to shutdown progress, for an exit dialog shown while the
+/// service stops.
+pub fn watch_shutdown_progress(
+    sink: StreamSink<FrbShutdownProgress>,
+) -> Result<(), FrbAutomationError> {
+    let mut guard = shutdown_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Shutdown progress sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+    Ok(())
+}
+
 /// Subscribe to scheduler state changes.
 pub fn watch_scheduler_state(
     sink: StreamSink<FrbSchedulerState>,
@@ -223,6 +451,88 @@ pub fn watch_automation_queue(
     Ok(())
 }
 
+/// Subscribe to toast-ready automation notifications (job completed/failed,
+/// queue drained).
+pub fn watch_automation_notifications(
+    sink: StreamSink<FrbAutomationNotification>,
+) -> Result<(), FrbAutomationError> {
+    let mut guard = automation_notification_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Automation notification sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+    Ok(())
+}
+
+/// Subscribe to live engine progress of running automation jobs. Each
+/// snapshot names its game; concurrent jobs interleave on one stream.
+pub fn watch_active_job_progress(
+    sink: StreamSink<FrbCompressionProgress>,
+) -> Result<(), FrbAutomationError> {
+    let current: Vec<FrbCompressionProgress> = active_job_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .flat_map(|progress| progress.values().cloned())
+        .collect();
+    for progress in current {
+        if sink.add(progress).is_err() {
+            return Ok(());
+        }
+    }
+
+    let mut guard = active_job_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Active job progress sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+    Ok(())
+}
+
+/// Subscribe to combined progress across queued automation jobs. The
+/// current batch, if one is running, is sent immediately.
+pub fn watch_automation_overall_progress(
+    sink: StreamSink<FrbAutomationOverallProgress>,
+) -> Result<(), FrbAutomationError> {
+    let current = latest_overall_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Latest overall progress lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        })
+        .clone();
+    if let Some(progress) = current {
+        if sink.add(progress).is_err() {
+            return Ok(());
+        }
+    }
+
+    let mut guard = automation_overall_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Overall progress sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+    Ok(())
+}
+
 /// Get current automation queue snapshot from shared state.
 #[frb(sync)]
 pub fn get_automation_queue() -> Vec<FrbAutomationJob> {
@@ -243,8 +553,71 @@ pub fn get_scheduler_state() -> FrbSchedulerState {
     guard.scheduler_state
 }
 
+/// Get the scheduler state with the reason nothing is starting, backoff
+/// time left, running jobs with their progress, and job counts.
+#[frb(sync)]
+pub fn get_scheduler_status() -> FrbSchedulerStatus {
+    scheduler_status_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Shared state as JSON for the diagnostics bundle and IPC clients.
+pub(crate) fn status_snapshot() -> serde_json::Value {
+    let running = is_auto_compression_running();
+    let guard = shared_state_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("Shared state lock poisoned during diagnostics read; recovering");
+        poisoned.into_inner()
+    });
+    let queue: Vec<serde_json::Value> = guard
+        .queue
+        .iter()
+        .map(|job| {
+            serde_json::json!({
+                "game_path": job.game_path,
+                "game_name": job.game_name,
+                "kind": format!("{:?}", job.kind),
+                "status": format!("{:?}", job.status),
+                "queued_at_ms": job.queued_at_ms,
+                "started_at_ms": job.started_at_ms,
+                "error": job.error,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "running": running,
+        "state": format!("{:?}", guard.scheduler_state),
+        "watched_path_count": guard.watched_path_count,
+        "queue_depth": guard.queue_depth,
+        "last_error": guard.last_error,
+        "queue": queue,
+    })
+}
+
+/// Show `notification` to notification subscribers, for monitors that
+/// run outside the automation loop.
+pub(crate) fn publish_notification(
+    notification: crate::automation::notifications::AutomationNotification,
+) {
+    worker_broadcast::broadcast_notification(notification);
+}
+
 /// Push updated automation config to the running auto-compression service.
 pub fn update_automation_config(config: FrbAutomationConfig) -> Result<(), FrbAutomationError> {
+    // The daemon picks the saved copy up while the app is closed.
+    let stored = AutomationSettings::from(&config);
+    if let Err(e) = crate::settings::update(|settings| settings.automation = stored) {
+        log::warn!("Failed to save automation settings: {e}");
+    }
+    apply_automation_config(config)
+}
+
+/// Push `config` to the running service without saving it.
+pub(crate) fn apply_automation_config(
+    config: FrbAutomationConfig,
+) -> Result<(), FrbAutomationError> {
     let guard = active_auto_lock().lock().unwrap_or_else(|poisoned| {
         log::warn!("AUTO compression lock poisoned during config update; recovering");
         poisoned.into_inner()
@@ -261,6 +634,63 @@ pub fn update_automation_config(config: FrbAutomationConfig) -> Result<(), FrbAu
     Ok(())
 }
 
+/// Pause auto-compression until `resume_auto_compression` is called.
+///
+/// Running jobs park before their next file and pick up where they left
+/// off on resume; queued jobs are kept.
+pub fn pause_auto_compression() -> Result<(), FrbAutomationError> {
+    send_control(AutomationControl::Pause)
+}
+
+/// Resume auto-compression after `pause_auto_compression`.
+pub fn resume_auto_compression() -> Result<(), FrbAutomationError> {
+    send_control(AutomationControl::Resume)
+}
+
+/// Stop the service once the running jobs finish; nothing new starts.
+///
+/// Queued jobs stay in the journal and resume on the next start.
+pub fn stop_after_current_job() -> Result<(), FrbAutomationError> {
+    send_control(AutomationControl::StopAfterCurrent)
+}
+
+/// Work through every queued job, then stop the service.
+pub fn drain_queue() -> Result<(), FrbAutomationError> {
+    send_control(AutomationControl::Drain)
+}
+
+/// Queue installed games to compress once the user is idle, skipping the
+/// settle window new installs wait for.
+pub(crate) fn enqueue_games(
+    games: Vec<(PathBuf, Option<String>)>,
+) -> Result<(), FrbAutomationError> {
+    send_control(AutomationControl::Enqueue(games))
+}
+
+fn send_control(command: AutomationControl) -> Result<(), FrbAutomationError> {
+    let guard = active_auto_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("AUTO compression lock poisoned during control command; recovering");
+        poisoned.into_inner()
+    });
+
+    let Some(ref active) = *guard else {
+        return Err(FrbAutomationError::NotRunning);
+    };
+    active
+        .control_tx
+        .send(command)
+        .map_err(|_| FrbAutomationError::NotRunning)
+}
+
+/// Suggested do-not-disturb process list for the settings UI.
+#[frb(sync)]
+pub fn default_blocking_processes() -> Vec<String> {
+    crate::safety::process::DEFAULT_BLOCKING_PROCESSES
+        .iter()
+        .map(|name| (*name).to_owned())
+        .collect()
+}
+
 /// Get watcher diagnostics from shared state.
 #[frb(sync)]
 pub fn get_watcher_diagnostics() -> FrbWatcherDiagnostics {
@@ -268,22 +698,72 @@ pub fn get_watcher_diagnostics() -> FrbWatcherDiagnostics {
         log::warn!("Shared state lock poisoned during diagnostics read; recovering");
         poisoned.into_inner()
     });
+    let filtered = *filtered_events_lock()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
     FrbWatcherDiagnostics {
         is_watching: guard.watched_path_count > 0,
         watched_path_count: guard.watched_path_count,
         queue_depth: guard.queue_depth,
         last_error: guard.last_error.clone(),
+        filtered_noise_events: filtered.noise,
+        filtered_user_state_events: filtered.user_state,
+        filtered_ignore_marker_events: filtered.ignore_marker,
+        filtered_too_deep_events: filtered.too_deep,
+        filtered_self_compression_events: filtered.self_compression,
+        pending_settles: pending_settles_lock()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .map(FrbPendingSettle::from)
+            .collect(),
     }
 }
 
+/// Job counters since launch plus the current queue depth and state.
+#[frb(sync)]
+pub fn get_automation_metrics() -> FrbAutomationMetrics {
+    crate::automation::metrics::snapshot().into()
+}
+
+/// Heartbeat of the background daemon, or `None` if it has never run
+/// for this user.
+#[frb(sync)]
+pub fn get_daemon_status() -> Option<FrbDaemonStatus> {
+    crate::daemon::status::read()
+        .map(|status| FrbDaemonStatus::from_status(status, crate::utils::unix_now_ms()))
+}
+
+/// Read the automation audit log, newest entries first.
+///
+/// Returns at most `limit` entries matching `filter` (all entries when `None`).
+pub fn get_automation_history(
+    limit: u32,
+    filter: Option<FrbAutomationHistoryFilter>,
+) -> Vec<FrbAutomationHistoryEntry> {
+    AutomationEventLog::global()
+        .query(limit as usize, &filter.unwrap_or_default().into())
+        .into_iter()
+        .map(FrbAutomationHistoryEntry::from)
+        .collect()
+}
+
+/// Delete every entry from the automation audit log.
+#[frb(sync)]
+pub fn clear_automation_history() -> Result<(), FrbAutomationError> {
+    AutomationEventLog::global()
+        .clear()
+        .map_err(|e| FrbAutomationError::HistoryFailed {
+            message: e.to_string(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::{LazyLock, Mutex};
-
+    use super::harness::SERVICE_TEST_LOCK as TEST_MUTEX;
     use super::*;
 
-    static TEST_MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
-
     fn stop_if_running() {
         if is_auto_compression_running() {
             let _ = stop_auto_compression();
@@ -323,6 +803,21 @@ mod tests {
             algorithm: super::super::types::FrbCompressionAlgorithm::Xpress8K,
             allow_directstorage_override: false,
             io_parallelism_override: None,
+            max_bytes_per_sec: None,
+            max_concurrent_jobs: None,
+            blocking_processes: vec![],
+            include_removable_drives: false,
+            allow_anticheat_compression: false,
+            watcher_ignored_dirs: vec![],
+            watcher_ignored_extensions: vec![],
+            watcher_temp_file_patterns: vec![],
+            watcher_max_event_depth: None,
+            dry_run: false,
+            input_idle_seconds: None,
+            idle_policy: crate::api::automation_types::FrbIdlePolicy::All,
+            ignore_focus_assist: false,
+            pause_when_thermally_constrained: false,
+            maintenance_wake_minute_of_day: None,
         });
         assert!(result.is_ok());
     }
@@ -334,6 +829,20 @@ mod tests {
         assert_eq!(get_scheduler_state(), FrbSchedulerState::Idle);
     }
 
+    #[test]
+    fn pause_and_resume_require_running_service() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        stop_if_running();
+        assert!(matches!(
+            pause_auto_compression(),
+            Err(FrbAutomationError::NotRunning)
+        ));
+        assert!(matches!(
+            resume_auto_compression(),
+            Err(FrbAutomationError::NotRunning)
+        ));
+    }
+
     #[test]
     fn get_automation_queue_returns_empty_when_not_running() {
         let _guard = TEST_MUTEX.lock().unwrap();
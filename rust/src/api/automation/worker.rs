@@ -3,48 +3,110 @@
 //! Contains the main auto_loop, compression execution, and broadcast
 //! functions that run on the background auto-compression thread.
 //!
-//! Compression runs on a dedicated spawned thread so the main auto_loop
+//! Compression runs on dedicated spawned threads so the main auto_loop
 //! can continue processing stop signals, config updates, watcher events,
-//! and state broadcasts during long-running compression operations.
+//! and state broadcasts during long-running compression operations. Jobs
+//! on different disks may run concurrently, each with its own cancel token.
 
 use std::collections::{HashSet, VecDeque};
-use std::path::PathBuf;
-use std::sync::mpsc::RecvTimeoutError;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use super::{
     shared_state_lock, worker_broadcast, worker_compression::join_compression_worker,
-    worker_compression::spawn_compression_job, worker_compression::ActiveCompressionJob,
-    worker_compression::CompressionResult, worker_reconcile,
+    worker_compression::spawn_compression_job, worker_compression::spawn_executor_job,
+    worker_compression::ActiveCompressionJob, worker_compression::CompressionResult,
+    worker_compression::JobExecutor, worker_compression::JobGuards, worker_history,
+    worker_notifications, worker_progress::JobProgressFeed,
+    worker_progress::OverallProgressTracker, worker_reconcile, AutomationControl,
 };
-use crate::api::automation_types::{FrbAutomationConfig, FrbSchedulerState};
+use crate::api::automation_types::{FrbAutomationConfig, FrbSchedulerState, FrbShutdownStage};
+use crate::automation::duration::{JobDurationEstimator, ThroughputTable};
 use crate::automation::idle::{IdleConfig, IdleDetector};
 use crate::automation::journal::JournalWriter;
-use crate::automation::scheduler::{AutoScheduler, SchedulerAction, SchedulerConfig};
-use crate::automation::watcher::{GameWatcher, WatchEvent, WatcherConfig};
+use crate::automation::library_reconcile::{self, DriftedGame};
+use crate::automation::metrics;
+use crate::automation::notifications::{DrainSummary, GAME_RUNNING_ERROR};
+use crate::automation::scheduler::{
+    AutoScheduler, SchedulerAction, SchedulerConfig, StopRequest, WaitReason,
+    MAX_CONCURRENT_JOBS_LIMIT,
+};
+use crate::automation::thermal::ThermalMonitor;
+use crate::automation::wake::MaintenanceWake;
+use crate::automation::watcher::{
+    GameWatcher, NoiseRules, WatchEvent, WatcherBackendKind, WatcherConfig,
+};
 use crate::compression::algorithm::CompressionAlgorithm;
+use crate::discovery::install_state::InstallProgressMonitor;
+use crate::discovery::library_changes;
+use crate::discovery::steam::LibraryFoldersWatch;
+use crate::discovery::storage::drive_type_for_path;
+use crate::safety::anticheat::AntiCheatPolicy;
+use crate::safety::focus_assist::active_quiet_mode;
 use crate::safety::process::ProcessChecker;
+use crate::safety::running_games::RunningGamesMonitor;
+use crate::settings::{Exclusions, LibraryReconcileSettings};
 
 const WATCHER_EVENT_COALESCE_DELAY: Duration = Duration::from_secs(1);
+/// Activity longer than this turns a paused job into a cancelled one so
+/// its volume is not held for the rest of the session.
+const MAX_ACTIVITY_PAUSE: Duration = Duration::from_secs(10 * 60);
+/// Wait between auto_loop iterations.
+const LOOP_TICK: Duration = Duration::from_secs(2);
+/// How often shutdown re-checks cancelled jobs and the control channel.
+const SHUTDOWN_POLL: Duration = Duration::from_millis(100);
+
+/// Seams that let tests drive [`auto_loop`] without real filesystem events,
+/// user input or compression. The service starts with the default, which
+/// keeps every real source.
+#[derive(Default)]
+pub(super) struct LoopOverrides {
+    /// Wait between iterations instead of [`LOOP_TICK`].
+    pub(super) tick: Option<Duration>,
+    /// Journal to restore from and persist to instead of the app data one.
+    pub(super) journal: Option<JournalWriter>,
+    /// Stands in for the idle detector and the do-not-disturb check.
+    pub(super) idle: Option<Arc<AtomicBool>>,
+    /// Events fed to the scheduler alongside the real watcher's.
+    pub(super) events: Option<Receiver<WatchEvent>>,
+    /// Runs jobs instead of the compression engine and its pre-flight checks.
+    pub(super) executor: Option<JobExecutor>,
+}
 
 pub(super) fn broadcast_auto_status(is_running: bool) {
     worker_broadcast::broadcast_auto_status(is_running);
 }
 
+pub(super) fn broadcast_shutdown_progress(stage: FrbShutdownStage, jobs_remaining: Option<usize>) {
+    worker_broadcast::broadcast_shutdown_progress(stage, jobs_remaining);
+}
+
 pub(super) fn auto_loop(
-    stop_rx: std::sync::mpsc::Receiver<()>,
-    config_rx: std::sync::mpsc::Receiver<FrbAutomationConfig>,
+    stop_rx: Receiver<()>,
+    config_rx: Receiver<FrbAutomationConfig>,
+    control_rx: Receiver<AutomationControl>,
+    overrides: LoopOverrides,
 ) {
     let mut idle_detector = IdleDetector::default();
     let process_checker = ProcessChecker::new();
-
-    let journal = match JournalWriter::default_path() {
-        Ok(j) => j,
-        Err(e) => {
-            log::error!("Failed to initialize automation journal: {e}");
-            JournalWriter::new(PathBuf::from("automation_journal.json"))
-        }
+    let mut running_games = RunningGamesMonitor::new();
+    let mut install_progress = InstallProgressMonitor::new();
+    let mut steam_libraries = LibraryFoldersWatch::new();
+
+    let journal = match overrides.journal {
+        Some(journal) => journal,
+        None => match JournalWriter::default_path() {
+            Ok(j) => j,
+            Err(e) => {
+                log::error!("Failed to initialize automation journal: {e}");
+                JournalWriter::new(PathBuf::from("automation_journal.json"))
+            }
+        },
     };
+    let tick = overrides.tick.unwrap_or(LOOP_TICK);
 
     let scheduler_config = SchedulerConfig::default();
     let mut scheduler = AutoScheduler::restore_or_new(scheduler_config, journal);
@@ -54,13 +116,24 @@ pub(super) fn auto_loop(
         log::warn!("Watcher start failed (will retry on config update): {e}");
     }
 
-    let mut last_state = scheduler.state();
-    let mut active_compression: Option<ActiveCompressionJob> = None;
+    let mut last_state = worker_broadcast::frb_scheduler_state(&scheduler);
+    let mut active_compressions: Vec<ActiveCompressionJob> = Vec::new();
     let mut current_algorithm = CompressionAlgorithm::Xpress8K;
     let mut current_io_parallelism_override: Option<usize> = None;
+    let mut current_max_bytes_per_sec: Option<u64> = None;
     let mut current_watch_paths: Vec<PathBuf> = Vec::new();
-    let mut current_excluded_paths: HashSet<String> = HashSet::new();
+    let mut current_exclusions = Exclusions::default();
+    let mut current_library_reconcile = LibraryReconcileSettings::default();
+    let mut library_reconcile_rx: Option<Receiver<Vec<DriftedGame>>> = None;
+    let mut current_anticheat_policy = AntiCheatPolicy::default();
+    let mut current_dry_run = false;
+    let mut current_respect_focus_assist = true;
+    // Only built while enabled: the first sensor read can take a moment.
+    let mut thermal_monitor: Option<ThermalMonitor> = None;
+    let mut maintenance_wake = MaintenanceWake::new();
+    let mut current_wake_minute: Option<u32> = None;
     let mut has_received_config = false;
+    let mut latest_config: Option<FrbAutomationConfig> = None;
     let mut last_startup_reconcile_watch_paths: Vec<String> = Vec::new();
     let mut startup_reconcile_pending_watch_paths: Option<Vec<String>> = None;
     let mut startup_reconcile_pending_normalized_watch_paths: Vec<String> = Vec::new();
@@ -68,19 +141,15 @@ pub(super) fn auto_loop(
         VecDeque<worker_reconcile::ReconcileCandidate>,
     > = None;
     let mut startup_reconcile_attempted_paths: HashSet<String> = HashSet::new();
+    let mut drain_summary = DrainSummary::default();
+    let mut overall_progress = OverallProgressTracker::default();
+    let mut stopped_by_request = false;
+    let mut hard_cancel_requested = false;
 
     loop {
-        match stop_rx.recv_timeout(Duration::from_secs(2)) {
+        match stop_rx.recv_timeout(tick) {
             Ok(()) | Err(RecvTimeoutError::Disconnected) => {
-                if let Some(mut job) = active_compression.take() {
-                    job.cancel_token.cancel();
-                    if job.result_rx.recv_timeout(Duration::from_secs(5)).is_err() {
-                        log::warn!(
-                            "Timed out waiting for auto-compression cancellation result; waiting for worker thread join"
-                        );
-                    }
-                    join_compression_worker(&mut job, "shutdown");
-                }
+                wind_down_jobs(&mut active_compressions, &control_rx, hard_cancel_requested);
                 break;
             }
             Err(RecvTimeoutError::Timeout) => {}
@@ -93,7 +162,8 @@ pub(super) fn auto_loop(
             newest_config = Some(config);
             drained_updates = drained_updates.saturating_add(1);
         }
-        if let Some(new_config) = newest_config {
+        if let Some(mut new_config) = newest_config {
+            retain_automation_drives(&mut new_config);
             if drained_updates > 1 {
                 log::debug!(
                     "Coalesced {} pending automation config updates into latest snapshot",
@@ -104,17 +174,30 @@ pub(super) fn auto_loop(
             current_algorithm = frb_algorithm_to_internal(&new_config.algorithm);
             current_io_parallelism_override =
                 io_parallelism_override_to_usize(new_config.io_parallelism_override);
-            current_watch_paths = new_config.watch_paths.iter().map(PathBuf::from).collect();
-            current_excluded_paths = new_config
-                .excluded_paths
-                .iter()
-                .map(|path| crate::utils::normalize_path_key(PathBuf::from(path).as_path()))
-                .collect();
+            current_max_bytes_per_sec = new_config.max_bytes_per_sec.filter(|&rate| rate > 0);
+            current_watch_paths = automation_watch_paths(&new_config);
+            let settings = crate::settings::load();
+            current_exclusions =
+                Exclusions::with_excluded_paths(&settings.exclusions, &new_config.excluded_paths);
+            current_library_reconcile = settings.library_reconcile;
+            current_anticheat_policy =
+                AntiCheatPolicy::from_allow_compression(new_config.allow_anticheat_compression);
+            current_dry_run = new_config.dry_run;
+            current_respect_focus_assist = !new_config.ignore_focus_assist;
+            current_wake_minute = new_config.maintenance_wake_minute_of_day;
+            if !new_config.pause_when_thermally_constrained {
+                thermal_monitor = None;
+            } else if thermal_monitor.is_none() {
+                thermal_monitor = Some(ThermalMonitor::new());
+            }
+            refresh_job_durations(current_algorithm, current_max_bytes_per_sec);
             has_received_config = true;
             let normalized_watch_paths =
                 worker_reconcile::normalize_watch_paths(&new_config.watch_paths);
+            process_checker.set_blocking_processes(&new_config.blocking_processes);
             apply_config(
                 &new_config,
+                &current_exclusions,
                 &mut idle_detector,
                 &mut scheduler,
                 &mut watcher,
@@ -141,47 +224,154 @@ pub(super) fn auto_loop(
                 startup_reconcile_pending_candidates = None;
                 startup_reconcile_attempted_paths.clear();
             }
+            latest_config = Some(new_config);
         }
 
-        let mut finished_result: Option<CompressionResult> = None;
-        let mut worker_disconnected = false;
-        if let Some(active_job) = active_compression.as_mut() {
-            match active_job.result_rx.try_recv() {
-                Ok(result) => finished_result = Some(result),
-                Err(crossbeam_channel::TryRecvError::Empty) => {}
-                Err(crossbeam_channel::TryRecvError::Disconnected) => worker_disconnected = true,
+        // Steam rewrites libraryfolders.vdf when a library is added or
+        // removed; re-derive the watch roots without waiting for Dart.
+        if steam_libraries.changed() {
+            if let Some(config) = latest_config.as_ref() {
+                log::info!(
+                    "[automation][config] Steam library folders changed; refreshing watch paths"
+                );
+                current_watch_paths = automation_watch_paths(config);
+                apply_config(
+                    config,
+                    &current_exclusions,
+                    &mut idle_detector,
+                    &mut scheduler,
+                    &mut watcher,
+                );
+                worker_broadcast::update_shared_state(&scheduler, &watcher);
             }
         }
 
-        if let Some(result) = finished_result {
-            if let Some(mut finished_job) = active_compression.take() {
-                join_compression_worker(&mut finished_job, "completion");
+        while let Ok(command) = control_rx.try_recv() {
+            match command {
+                AutomationControl::Pause => {
+                    log::info!("[automation][control] paused by user");
+                    scheduler.pause();
+                    for job in &active_compressions {
+                        job.pause.pause();
+                    }
+                }
+                AutomationControl::Resume => {
+                    log::info!("[automation][control] resumed by user");
+                    scheduler.resume();
+                    for job in &mut active_compressions {
+                        job.paused_since = None;
+                        job.pause.resume();
+                    }
+                }
+                AutomationControl::StopAfterCurrent => {
+                    log::info!("[automation][control] stopping after the current job");
+                    scheduler.request_stop(StopRequest::AfterCurrentJob);
+                }
+                AutomationControl::Drain => {
+                    log::info!("[automation][control] draining queue before stopping");
+                    scheduler.request_stop(StopRequest::WhenDrained);
+                }
+                AutomationControl::Enqueue(games) => {
+                    let requested = games.len();
+                    let queued = games
+                        .into_iter()
+                        .filter(|(path, name)| {
+                            scheduler.enqueue_opportunistic(path.clone(), name.clone())
+                        })
+                        .count();
+                    log::info!(
+                        "[automation][control] queued {queued} of {requested} requested games"
+                    );
+                }
+                // Sent after a stop request this pass has not seen yet.
+                AutomationControl::HardCancel => hard_cancel_requested = true,
             }
+        }
+
+        let mut any_finished = false;
+        let mut index = 0;
+        while index < active_compressions.len() {
+            let result = match active_compressions[index].result_rx.try_recv() {
+                Ok(result) => result,
+                Err(crossbeam_channel::TryRecvError::Empty) => {
+                    index += 1;
+                    continue;
+                }
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    log::error!("Compression worker thread disconnected unexpectedly");
+                    let mut disconnected_job = active_compressions.swap_remove(index);
+                    join_compression_worker(&mut disconnected_job, "disconnect");
+                    continue;
+                }
+            };
+
+            let mut finished_job = active_compressions.swap_remove(index);
+            join_compression_worker(&mut finished_job, "completion");
+            let result = match result {
+                CompressionResult::Failed {
+                    idempotency_key, ..
+                } if finished_job.game_launched => CompressionResult::Failed {
+                    idempotency_key,
+                    error: GAME_RUNNING_ERROR.to_string(),
+                },
+                other => other,
+            };
+            worker_history::record_job_outcome(&finished_job, &result);
+            worker_notifications::notify_job_outcome(&finished_job, &result, &mut drain_summary);
+            any_finished = true;
             match result {
-                CompressionResult::Success { idempotency_key } => {
+                CompressionResult::Success {
+                    idempotency_key,
+                    stats,
+                } => {
+                    metrics::record_job_completed(stats.bytes_saved_this_run());
                     scheduler.job_completed(&idempotency_key);
                 }
+                CompressionResult::Simulated {
+                    idempotency_key, ..
+                } => {
+                    scheduler.job_simulated(&idempotency_key);
+                }
+                CompressionResult::Failed {
+                    idempotency_key, ..
+                } if finished_job.game_launched => {
+                    scheduler.job_deferred(&idempotency_key);
+                }
+                // The drive was unplugged mid-run; the job waits for it to
+                // come back instead of counting as a failure.
+                CompressionResult::Failed {
+                    idempotency_key, ..
+                } if !volume_reachable(&finished_job.game_path) => {
+                    log::info!(
+                        "Drive went away during auto-compression; deferring: {}",
+                        finished_job.game_path.display()
+                    );
+                    scheduler.job_deferred(&idempotency_key);
+                }
                 CompressionResult::Failed {
                     idempotency_key,
                     error,
                 } => {
+                    metrics::record_job_failed();
                     scheduler.job_failed(&idempotency_key, error);
                 }
                 CompressionResult::Skipped {
                     idempotency_key,
                     reason,
                 } => {
+                    metrics::record_job_skipped();
                     scheduler.job_skipped(&idempotency_key, reason);
                 }
             }
+        }
+        // One merged broadcast even when several volumes finish together.
+        if any_finished {
+            // Finished runs add throughput samples.
+            refresh_job_durations(current_algorithm, current_max_bytes_per_sec);
             worker_broadcast::broadcast_automation_queue(scheduler.queue_snapshot());
-        } else if worker_disconnected {
-            log::error!("Compression worker thread disconnected unexpectedly");
-            if let Some(mut disconnected_job) = active_compression.take() {
-                join_compression_worker(&mut disconnected_job, "disconnect");
-            }
         }
 
+        watcher.poll_drives();
         if let Some(rx) = watcher.event_channel() {
             while let Ok(event) = rx.try_recv() {
                 on_watcher_event(&event);
@@ -189,23 +379,84 @@ pub(super) fn auto_loop(
                 scheduler.on_event(event);
             }
         }
+        if let Some(rx) = overrides.events.as_ref() {
+            while let Ok(event) = rx.try_recv() {
+                worker_broadcast::broadcast_watcher_event(&event);
+                scheduler.on_event(event);
+            }
+        }
 
         maybe_run_startup_reconcile(
             &mut scheduler,
-            &active_compression,
+            !active_compressions.is_empty(),
             &mut startup_reconcile_pending_watch_paths,
             &mut startup_reconcile_pending_normalized_watch_paths,
             &mut startup_reconcile_pending_candidates,
             &mut last_startup_reconcile_watch_paths,
             &mut startup_reconcile_attempted_paths,
         );
+        if poll_library_reconcile(
+            &mut scheduler,
+            &mut library_reconcile_rx,
+            &current_library_reconcile,
+            has_received_config && active_compressions.is_empty(),
+        ) {
+            worker_broadcast::broadcast_automation_queue(scheduler.queue_snapshot());
+        }
 
-        let is_idle = idle_detector.is_idle();
+        // Queued games that are running are passed over, and a job whose
+        // game launches mid-run is cancelled and goes back in the queue.
+        if let Some(running) = running_games.poll(&scheduler.unfinished_game_paths()) {
+            scheduler.set_running_games(running);
+        }
+        // Settle ends when the launcher finishes writing, not after a fixed
+        // quiet period.
+        if let Some(states) = install_progress.poll(&scheduler.settling_game_paths()) {
+            scheduler.set_install_states(states);
+        }
+        for job in &mut active_compressions {
+            if !job.game_launched && running_games.is_running(&job.game_path) {
+                log::info!(
+                    "Game launched during auto-compression; deferring: {}",
+                    job.game_path.display()
+                );
+                job.game_launched = true;
+                job.cancel_token.cancel_gracefully();
+                metrics::record_activity_cancellation();
+            }
+        }
+
+        // Do-not-disturb processes, Focus Assist and a hot CPU count as user
+        // activity: running jobs are paused and nothing new starts until
+        // they end.
+        let (user_idle, busy_reason) = match overrides.idle.as_ref() {
+            Some(idle) => (idle.load(Ordering::Relaxed), None),
+            None => {
+                let user_idle = idle_detector.is_idle();
+                let busy_reason = if !user_idle {
+                    None
+                } else if process_checker.is_any_blocking_process_running() {
+                    Some(WaitReason::BlockingProcess)
+                } else if current_respect_focus_assist && active_quiet_mode().is_some() {
+                    Some(WaitReason::FocusAssist)
+                } else if thermal_monitor
+                    .as_mut()
+                    .is_some_and(ThermalMonitor::is_constrained)
+                {
+                    Some(WaitReason::ThermallyConstrained)
+                } else {
+                    None
+                };
+                (user_idle, busy_reason)
+            }
+        };
+        let is_idle = user_idle && busy_reason.is_none();
         let cpu_usage_percent = idle_detector.cpu_usage();
 
-        if !is_idle {
-            if let Some(ref job) = active_compression {
-                job.cancel_token.cancel();
+        // A user pause holds jobs parked regardless of activity.
+        if !scheduler.is_user_paused() {
+            for job in &mut active_compressions {
+                pause_for_activity(job, is_idle);
             }
         }
 
@@ -213,15 +464,31 @@ pub(super) fn auto_loop(
             if let Some(action) = scheduler.tick(is_idle, false) {
                 match action {
                     SchedulerAction::Compress(job) => {
-                        active_compression = Some(spawn_compression_job(
-                            &job,
-                            &process_checker,
-                            current_algorithm,
-                            cpu_usage_percent,
-                            current_io_parallelism_override,
-                            current_watch_paths.clone(),
-                            current_excluded_paths.clone(),
-                        ));
+                        let active = match overrides.executor.as_ref() {
+                            Some(executor) => spawn_executor_job(&job, executor.clone()),
+                            None => spawn_compression_job(
+                                &job,
+                                &process_checker,
+                                current_algorithm,
+                                cpu_usage_percent,
+                                current_io_parallelism_override,
+                                current_max_bytes_per_sec,
+                                JobGuards {
+                                    watch_roots: current_watch_paths.clone(),
+                                    exclusions: current_exclusions.clone(),
+                                    anticheat_policy: current_anticheat_policy,
+                                    dry_run: current_dry_run,
+                                    include_user_data: crate::settings::load()
+                                        .user_data
+                                        .include_in_automation,
+                                },
+                            ),
+                        };
+                        if active.has_worker() {
+                            worker_history::record_job_started(&active);
+                        }
+                        active_compressions.push(active);
+                        worker_broadcast::broadcast_automation_queue(scheduler.queue_snapshot());
                     }
                     SchedulerAction::Persist => {
                         if let Err(e) = scheduler.persist() {
@@ -232,19 +499,43 @@ pub(super) fn auto_loop(
             }
         }
 
-        let current_state = scheduler.state();
+        update_overall_progress(&mut overall_progress, &scheduler, &active_compressions);
+        if update_active_job_progress(&mut active_compressions) {
+            worker_broadcast::broadcast_automation_queue(scheduler.queue_snapshot());
+        }
+
+        worker_notifications::maybe_notify_queue_drained(
+            &mut drain_summary,
+            !active_compressions.is_empty(),
+            scheduler.pending_queue_len(),
+        );
+
+        let current_state = worker_broadcast::frb_scheduler_state(&scheduler);
         if current_state != last_state {
             worker_broadcast::broadcast_scheduler_state(current_state);
             worker_broadcast::broadcast_automation_queue(scheduler.queue_snapshot());
             last_state = current_state;
         }
         worker_broadcast::update_shared_state(&scheduler, &watcher);
+        worker_broadcast::update_scheduler_status(&scheduler, &active_compressions, busy_reason);
+        maintenance_wake.update(
+            current_wake_minute,
+            scheduler.pending_queue_len() > 0,
+            !active_compressions.is_empty(),
+        );
+
+        if scheduler.should_stop() {
+            log::info!("[automation][control] requested stop reached; shutting down");
+            stopped_by_request = true;
+            break;
+        }
     }
 
     watcher.stop();
     if let Err(e) = scheduler.persist() {
         log::error!("Failed to persist journal during shutdown: {e}");
     }
+    crate::compression::history::flush_now();
 
     {
         let mut guard = shared_state_lock().lock().unwrap_or_else(|poisoned| {
@@ -256,12 +547,235 @@ pub(super) fn auto_loop(
         guard.watched_path_count = 0;
         guard.queue_depth = 0;
     }
-
+    metrics::set_gauges(None, 0);
+    *super::latest_overall_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    *super::job_durations_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    *super::scheduler_status_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    *super::active_job_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+
+    if stopped_by_request {
+        super::release_stopped_service();
+    } else {
+        broadcast_shutdown_progress(FrbShutdownStage::Stopped, Some(0));
+    }
     broadcast_auto_status(false);
 }
 
+/// Cancel running jobs gracefully and wait for each, recording its outcome.
+/// A [`AutomationControl::HardCancel`], received now or earlier, ends the
+/// wait; jobs still running then are cancelled outright and left to exit on
+/// their own.
+fn wind_down_jobs(
+    jobs: &mut Vec<ActiveCompressionJob>,
+    control_rx: &Receiver<AutomationControl>,
+    mut hard_cancel: bool,
+) {
+    for job in jobs.iter() {
+        job.cancel_token.cancel_gracefully();
+    }
+    let mut reported = None;
+    while !jobs.is_empty() {
+        if reported != Some(jobs.len()) {
+            reported = Some(jobs.len());
+            broadcast_shutdown_progress(FrbShutdownStage::SoftCancel, Some(jobs.len()));
+        }
+        hard_cancel |= control_rx
+            .try_iter()
+            .any(|command| matches!(command, AutomationControl::HardCancel));
+        if hard_cancel {
+            broadcast_shutdown_progress(FrbShutdownStage::HardCancel, Some(jobs.len()));
+            for job in jobs.drain(..) {
+                job.cancel_token.cancel();
+                job.pause.resume();
+                log::warn!(
+                    "Leaving the compression worker for {} to exit on its own",
+                    job.game_path.display()
+                );
+            }
+            return;
+        }
+
+        let mut index = 0;
+        while index < jobs.len() {
+            match jobs[index].result_rx.try_recv() {
+                Ok(result) => {
+                    let mut job = jobs.swap_remove(index);
+                    worker_history::record_job_outcome(&job, &result);
+                    join_compression_worker(&mut job, "shutdown");
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => index += 1,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    let mut job = jobs.swap_remove(index);
+                    join_compression_worker(&mut job, "shutdown");
+                }
+            }
+        }
+        if !jobs.is_empty() {
+            std::thread::sleep(SHUTDOWN_POLL);
+        }
+    }
+}
+
+/// Park `job` between files while the user is active and pick it back up
+/// when they leave. Activity that outlasts `MAX_ACTIVITY_PAUSE` cancels the
+/// job instead, keeping the files finished so far.
+fn pause_for_activity(job: &mut ActiveCompressionJob, is_idle: bool) {
+    if is_idle {
+        if job.paused_since.take().is_some() {
+            log::info!(
+                "User idle again; resuming auto-compression: {}",
+                job.game_path.display()
+            );
+            job.pause.resume();
+        }
+        return;
+    }
+
+    let paused_since = *job.paused_since.get_or_insert_with(|| {
+        log::info!(
+            "User activity; pausing auto-compression: {}",
+            job.game_path.display()
+        );
+        job.pause.pause();
+        Instant::now()
+    });
+    if paused_since.elapsed() >= MAX_ACTIVITY_PAUSE && !job.cancel_token.is_cancelled() {
+        log::info!(
+            "User activity outlasted the pause; cancelling auto-compression: {}",
+            job.game_path.display()
+        );
+        job.cancel_token.cancel_gracefully();
+        metrics::record_activity_cancellation();
+    }
+}
+
+fn update_overall_progress(
+    tracker: &mut OverallProgressTracker,
+    scheduler: &AutoScheduler,
+    active_compressions: &[ActiveCompressionJob],
+) {
+    let durations = super::job_durations_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    tracker.observe_queue(
+        &scheduler.queue_snapshot(),
+        |path| crate::discovery::cache::lookup_stale(path).map(|stats| stats.logical_size),
+        |path| {
+            durations
+                .as_ref()
+                .map_or(0, |durations| durations.bytes_per_sec(path))
+        },
+    );
+    drop(durations);
+    for job in active_compressions {
+        if let Some(counters) = job.counters.get() {
+            tracker.observe_active(
+                &job.game_path,
+                counters.bytes_total.load(Ordering::Relaxed),
+                counters.bytes_processed.load(Ordering::Relaxed),
+            );
+        }
+    }
+    if let Some(progress) = tracker.take_changed() {
+        worker_broadcast::broadcast_overall_progress(progress);
+    }
+}
+
+/// Run the library check off-thread when due; returns whether it queued jobs.
+fn poll_library_reconcile(
+    scheduler: &mut AutoScheduler,
+    pending: &mut Option<Receiver<Vec<DriftedGame>>>,
+    settings: &LibraryReconcileSettings,
+    can_start: bool,
+) -> bool {
+    let Some(rx) = pending.as_ref() else {
+        if can_start && library_reconcile::is_due(settings) {
+            let (tx, rx) = mpsc::sync_channel(1);
+            let min_retained = settings.min_retained_savings();
+            let spawned = std::thread::Builder::new()
+                .name("compact-games-library-reconcile".into())
+                .spawn(move || {
+                    let _ = tx.send(library_reconcile::find_drifted_games(min_retained));
+                });
+            match spawned {
+                Ok(_) => *pending = Some(rx),
+                Err(e) => log::warn!("Failed to start library reconcile: {e}"),
+            }
+        }
+        return false;
+    };
+    let drifted = match rx.try_recv() {
+        Ok(drifted) => drifted,
+        Err(TryRecvError::Empty) => return false,
+        Err(TryRecvError::Disconnected) => Vec::new(),
+    };
+    *pending = None;
+    log::info!(
+        "Library reconcile: {} compressed games lost savings",
+        drifted.len()
+    );
+    for game in &drifted {
+        scheduler.on_event(WatchEvent::GameModified {
+            path: game.game_path.clone(),
+            game_name: Some(game.game_name.clone()),
+        });
+    }
+    !drifted.is_empty()
+}
+
+/// Sample each running job's engine counters and stream fresh snapshots.
+/// Returns whether any job advanced.
+fn update_active_job_progress(active_compressions: &mut [ActiveCompressionJob]) -> bool {
+    let mut advanced = false;
+    for job in active_compressions.iter_mut() {
+        if job.progress_feed.is_none() {
+            if let Some(counters) = job.counters.get() {
+                let game_name = job
+                    .game_name
+                    .clone()
+                    .unwrap_or_else(|| job.game_path.to_string_lossy().into_owned());
+                job.progress_feed = Some(JobProgressFeed::start(counters.clone(), &game_name));
+            }
+        }
+        if let Some(progress) = job.progress_feed.as_mut().and_then(JobProgressFeed::poll) {
+            worker_broadcast::broadcast_active_job_progress(progress.clone());
+            advanced = true;
+        }
+    }
+    worker_broadcast::publish_active_job_progress(
+        active_compressions
+            .iter()
+            .filter_map(|job| {
+                let progress = job.progress_feed.as_ref()?.latest()?;
+                Some((job.game_path.clone(), progress.clone()))
+            })
+            .collect(),
+    );
+    advanced
+}
+
+/// Rebuild duration estimates from history under the current settings.
+fn refresh_job_durations(algorithm: CompressionAlgorithm, max_bytes_per_sec: Option<u64>) {
+    *super::job_durations_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(JobDurationEstimator::new(
+        ThroughputTable::from_history(),
+        algorithm,
+        max_bytes_per_sec,
+    ));
+}
+
 fn apply_config(
     config: &FrbAutomationConfig,
+    exclusions: &Exclusions,
     idle_detector: &mut IdleDetector,
     scheduler: &mut AutoScheduler,
     watcher: &mut GameWatcher,
@@ -269,41 +783,56 @@ fn apply_config(
     idle_detector.update_config(IdleConfig {
         cpu_threshold_percent: config.cpu_threshold_percent,
         idle_duration: Duration::from_secs(config.idle_duration_seconds),
+        input_idle_duration: config.input_idle_seconds.map(Duration::from_secs),
+        policy: config.idle_policy.into(),
     });
 
-    let excluded: HashSet<String> = config
-        .excluded_paths
-        .iter()
-        .map(|p| p.to_ascii_lowercase())
-        .collect();
-    let watch_paths: Vec<PathBuf> = config.watch_paths.iter().map(PathBuf::from).collect();
+    let watch_paths = automation_watch_paths(config);
+    let max_concurrent_jobs = config
+        .max_concurrent_jobs
+        .map_or(1, |n| n as usize)
+        .clamp(1, MAX_CONCURRENT_JOBS_LIMIT);
 
     scheduler.update_config(SchedulerConfig {
         cooldown: Duration::from_secs(config.cooldown_seconds),
-        excluded_paths: excluded,
+        exclusions: exclusions.clone(),
         watch_paths: watch_paths.clone(),
+        max_concurrent_jobs,
+        ..SchedulerConfig::default()
     });
 
     log::info!(
-        "[automation][config] watch_paths={} scheduler_cooldown_seconds={} idle_duration_seconds={} watcher_event_delay_seconds={}",
+        "[automation][config] watch_paths={} scheduler_cooldown_seconds={} idle_duration_seconds={} input_idle_seconds={:?} idle_policy={:?} max_concurrent_jobs={} watcher_event_delay_seconds={}",
         watch_paths.len(),
         config.cooldown_seconds,
         config.idle_duration_seconds,
+        config.input_idle_seconds,
+        config.idle_policy,
+        max_concurrent_jobs,
         WATCHER_EVENT_COALESCE_DELAY.as_secs()
     );
     for path in &watch_paths {
         log::debug!("[automation][config] watching path=\"{}\"", path.display());
     }
 
+    // Large libraries are cheaper to track through the change journal; roots
+    // on volumes without an accessible journal fall back to notify.
     watcher.update_config(WatcherConfig {
         watch_paths,
         cooldown: WATCHER_EVENT_COALESCE_DELAY,
+        backend: WatcherBackendKind::UsnJournal,
+        noise_rules: NoiseRules {
+            ignored_dirs: config.watcher_ignored_dirs.clone(),
+            ignored_extensions: config.watcher_ignored_extensions.clone(),
+            temp_file_patterns: config.watcher_temp_file_patterns.clone(),
+            max_event_depth: config.watcher_max_event_depth.filter(|&depth| depth > 0),
+        },
     });
 }
 
 fn maybe_run_startup_reconcile(
     scheduler: &mut AutoScheduler,
-    active_compression: &Option<ActiveCompressionJob>,
+    has_active_compression: bool,
     pending_watch_paths: &mut Option<Vec<String>>,
     pending_normalized_watch_paths: &mut Vec<String>,
     pending_candidates: &mut Option<VecDeque<worker_reconcile::ReconcileCandidate>>,
@@ -316,7 +845,7 @@ fn maybe_run_startup_reconcile(
 
     // Keep startup reconcile lightweight: only scan when no compression is active
     // and the scheduler queue has drained from prior reconcile batches.
-    if active_compression.is_some() || scheduler.pending_queue_len() > 0 {
+    if has_active_compression || scheduler.pending_queue_len() > 0 {
         return;
     }
 
@@ -361,6 +890,54 @@ fn maybe_run_startup_reconcile(
     attempted_paths.clear();
 }
 
+/// Drop watch paths on removable and network volumes unless the user
+/// opted in. Compressing over SMB fails part-way through, and removable
+/// drives disappear under a running job.
+fn retain_automation_drives(config: &mut FrbAutomationConfig) {
+    let include_removable = config.include_removable_drives;
+    config
+        .watch_paths
+        .retain(|path| is_automation_drive(Path::new(path), include_removable));
+}
+
+/// Paths Dart sent plus persisted custom library roots and every Steam
+/// library from libraryfolders.vdf, so neither needs to be mirrored in the
+/// settings. Added roots get the same drive filtering as configured paths.
+fn automation_watch_paths(config: &FrbAutomationConfig) -> Vec<PathBuf> {
+    let mut paths = crate::discovery::custom_roots::with_custom_roots(
+        config.watch_paths.iter().map(PathBuf::from).collect(),
+    );
+    // A library under an already-watched path is covered by that watch.
+    let covered: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| PathBuf::from(crate::utils::normalize_path_key(path)))
+        .collect();
+    for root in crate::discovery::steam::steam_library_roots() {
+        let key = PathBuf::from(crate::utils::normalize_path_key(&root));
+        if !covered.iter().any(|watched| key.starts_with(watched)) {
+            paths.push(root);
+        }
+    }
+    paths.retain(|path| is_automation_drive(path, config.include_removable_drives));
+    paths
+}
+
+fn is_automation_drive(path: &Path, include_removable: bool) -> bool {
+    if include_removable {
+        return true;
+    }
+    let drive_type = drive_type_for_path(path);
+    if drive_type.is_removable_or_network() {
+        log::info!(
+            "[automation][config] skipping {:?} drive path=\"{}\"",
+            drive_type,
+            path.display()
+        );
+        return false;
+    }
+    true
+}
+
 fn frb_algorithm_to_internal(
     algo: &crate::api::types::FrbCompressionAlgorithm,
 ) -> CompressionAlgorithm {
@@ -377,12 +954,22 @@ fn io_parallelism_override_to_usize(io_parallelism_override: Option<u64>) -> Opt
     crate::utils::io_parallelism_override_to_usize(io_parallelism_override)
 }
 
+fn volume_reachable(path: &Path) -> bool {
+    path.ancestors().last().is_some_and(Path::exists)
+}
+
 fn on_watcher_event(event: &WatchEvent) {
-    if let WatchEvent::GameUninstalled { path, .. } = event {
-        crate::discovery::utils::evict_discovery_entry(path);
-        crate::discovery::cache::persist_if_dirty();
-        crate::discovery::index::persist_if_dirty();
-        crate::discovery::change_feed::persist_if_dirty();
-        crate::discovery::install_history::persist_if_dirty();
+    let change = match event {
+        WatchEvent::GameInstalled { path, game_name } => {
+            library_changes::game_installed(path, game_name.as_deref())
+        }
+        WatchEvent::GameModified { path, game_name } => {
+            library_changes::game_modified(path, game_name.as_deref())
+        }
+        WatchEvent::GameUninstalled { path, .. } => Some(library_changes::game_uninstalled(path)),
+        WatchEvent::DriveOffline { .. } | WatchEvent::DriveOnline { .. } => None,
+    };
+    if let Some(change) = change {
+        crate::api::discovery::broadcast_library_change(change);
     }
 }
@@ -1,9 +1,25 @@
+use super::worker_compression::ActiveCompressionJob;
 use super::{
-    auto_status_sinks_lock, automation_queue_sinks_lock, scheduler_state_sinks_lock,
-    shared_state_lock, watcher_event_sinks_lock,
+    active_job_progress_lock, active_job_progress_sinks_lock, auto_status_sinks_lock,
+    automation_notification_sinks_lock, automation_overall_progress_sinks_lock,
+    automation_queue_sinks_lock, filtered_events_lock, job_durations_lock,
+    latest_overall_progress_lock, pending_settles_lock, scheduler_state_sinks_lock,
+    scheduler_status_lock, shared_state_lock, shutdown_progress_sinks_lock,
+    watcher_event_sinks_lock,
 };
-use crate::api::automation_types::{FrbAutomationJob, FrbSchedulerState, FrbWatcherEvent};
-use crate::automation::scheduler::{AutoScheduler, SchedulerState};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::api::automation_types::{
+    FrbActiveAutomationJob, FrbAutomationJob, FrbAutomationNotification,
+    FrbAutomationOverallProgress, FrbSchedulerState, FrbSchedulerStatus, FrbShutdownProgress,
+    FrbShutdownStage, FrbWatcherEvent,
+};
+use crate::api::types::FrbCompressionProgress;
+use crate::automation::metrics;
+use crate::automation::notifications::AutomationNotification;
+use crate::automation::scheduler::{AutoScheduler, AutomationJob, JobStatus, WaitReason};
 use crate::automation::watcher::{GameWatcher, WatchEvent};
 
 pub(super) fn broadcast_auto_status(is_running: bool) {
@@ -14,6 +30,32 @@ pub(super) fn broadcast_auto_status(is_running: bool) {
     guard.retain(|sink| sink.add(is_running).is_ok());
 }
 
+/// Jobs still winding down at the last shutdown report.
+static SHUTDOWN_JOBS_REMAINING: AtomicU32 = AtomicU32::new(0);
+
+/// `jobs_remaining` of `None` repeats the last reported count.
+pub(super) fn broadcast_shutdown_progress(stage: FrbShutdownStage, jobs_remaining: Option<usize>) {
+    let jobs_remaining = match jobs_remaining {
+        Some(count) => {
+            let count = u32::try_from(count).unwrap_or(u32::MAX);
+            SHUTDOWN_JOBS_REMAINING.store(count, Ordering::Relaxed);
+            count
+        }
+        None => SHUTDOWN_JOBS_REMAINING.load(Ordering::Relaxed),
+    };
+    let progress = FrbShutdownProgress {
+        stage,
+        jobs_remaining,
+    };
+    let mut guard = shutdown_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Shutdown progress sinks lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+    guard.retain(|sink| sink.add(progress).is_ok());
+}
+
 pub(super) fn broadcast_watcher_event(event: &WatchEvent) {
     let mut guard = watcher_event_sinks_lock()
         .lock()
@@ -28,8 +70,17 @@ pub(super) fn broadcast_watcher_event(event: &WatchEvent) {
     guard.retain(|sink| sink.add(frb_event.clone()).is_ok());
 }
 
-pub(super) fn broadcast_scheduler_state(state: SchedulerState) {
-    let frb_state: FrbSchedulerState = state.into();
+/// Scheduler state as shown to Flutter, distinguishing a user pause from
+/// an activity pause.
+pub(super) fn frb_scheduler_state(scheduler: &AutoScheduler) -> FrbSchedulerState {
+    if scheduler.is_user_paused() {
+        FrbSchedulerState::PausedByUser
+    } else {
+        scheduler.state().into()
+    }
+}
+
+pub(super) fn broadcast_scheduler_state(frb_state: FrbSchedulerState) {
     let mut guard = scheduler_state_sinks_lock()
         .lock()
         .unwrap_or_else(|poisoned| {
@@ -39,7 +90,68 @@ pub(super) fn broadcast_scheduler_state(state: SchedulerState) {
     guard.retain(|sink| sink.add(frb_state).is_ok());
 }
 
-pub(super) fn broadcast_automation_queue(jobs: Vec<crate::automation::scheduler::AutomationJob>) {
+/// Bridge view of the queue: duration estimates for unfinished jobs, start
+/// order for waiting ones and live progress for running ones.
+fn frb_jobs(jobs: Vec<AutomationJob>) -> Vec<FrbAutomationJob> {
+    let durations = job_durations_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let progress = active_job_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let positions = queue_positions(&jobs);
+    jobs.into_iter()
+        .enumerate()
+        .map(|(index, job)| {
+            let unfinished = !matches!(
+                job.status,
+                JobStatus::Completed | JobStatus::Failed | JobStatus::Skipped
+            );
+            let estimate = durations
+                .as_ref()
+                .filter(|_| unfinished)
+                .and_then(|durations| {
+                    crate::discovery::cache::lookup_stale(&job.game_path).map(|stats| {
+                        durations
+                            .estimate(&job.game_path, stats.logical_size)
+                            .as_millis() as u64
+                    })
+                });
+            let live = (job.status == JobStatus::Compressing)
+                .then(|| progress.as_ref()?.get(&job.game_path).cloned())
+                .flatten();
+            let mut frb_job: FrbAutomationJob = job.into();
+            frb_job.estimated_duration_ms = estimate;
+            frb_job.queue_position = positions.get(&index).copied();
+            frb_job.progress = live;
+            frb_job
+        })
+        .collect()
+}
+
+/// 1-based start order of waiting jobs, by index into `jobs`: the kind's
+/// start rank first, then queue order, as the scheduler picks them.
+fn queue_positions(jobs: &[AutomationJob]) -> HashMap<usize, u32> {
+    let mut waiting: Vec<(u8, usize)> = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, job)| {
+            matches!(
+                job.status,
+                JobStatus::Pending | JobStatus::WaitingForSettle | JobStatus::WaitingForIdle
+            )
+        })
+        .map(|(index, job)| (job.kind.start_rank(), index))
+        .collect();
+    waiting.sort_unstable();
+    waiting
+        .into_iter()
+        .enumerate()
+        .map(|(position, (_, index))| (index, position as u32 + 1))
+        .collect()
+}
+
+pub(super) fn broadcast_automation_queue(jobs: Vec<AutomationJob>) {
     let mut guard = automation_queue_sinks_lock()
         .lock()
         .unwrap_or_else(|poisoned| {
@@ -49,25 +161,178 @@ pub(super) fn broadcast_automation_queue(jobs: Vec<crate::automation::scheduler:
     if guard.is_empty() {
         return;
     }
-    let frb_jobs: Vec<FrbAutomationJob> = jobs.into_iter().map(Into::into).collect();
+    let frb_jobs = frb_jobs(jobs);
     guard.retain(|sink| sink.add(frb_jobs.clone()).is_ok());
 }
 
+pub(super) fn broadcast_notification(notification: AutomationNotification) {
+    let mut guard = automation_notification_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Automation notification sinks lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+    if guard.is_empty() {
+        return;
+    }
+    let frb_notification: FrbAutomationNotification = notification.into();
+    guard.retain(|sink| sink.add(frb_notification.clone()).is_ok());
+}
+
+pub(super) fn broadcast_active_job_progress(progress: FrbCompressionProgress) {
+    let mut guard = active_job_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Active job progress sinks lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+    guard.retain(|sink| sink.add(progress.clone()).is_ok());
+}
+
+/// Keep the latest progress of each running job for queue snapshots and
+/// new subscribers.
+pub(super) fn publish_active_job_progress(progress: HashMap<PathBuf, FrbCompressionProgress>) {
+    *active_job_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(progress);
+}
+
+/// Publish combined batch progress; a completed batch is not replayed to
+/// later subscribers.
+pub(super) fn broadcast_overall_progress(progress: FrbAutomationOverallProgress) {
+    *latest_overall_progress_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+        (!progress.is_complete).then(|| progress.clone());
+    let mut guard = automation_overall_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Overall progress sinks lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+    guard.retain(|sink| sink.add(progress.clone()).is_ok());
+}
+
 pub(super) fn update_shared_state(scheduler: &AutoScheduler, watcher: &GameWatcher) {
     let mut guard = shared_state_lock().lock().unwrap_or_else(|poisoned| {
         log::warn!("Shared state lock poisoned during update; recovering");
         poisoned.into_inner()
     });
-    guard.scheduler_state = scheduler.state().into();
-    guard.queue = scheduler
-        .queue_snapshot()
-        .into_iter()
-        .map(Into::into)
-        .collect();
+    guard.scheduler_state = frb_scheduler_state(scheduler);
+    guard.queue = frb_jobs(scheduler.queue_snapshot());
     guard.watched_path_count = if watcher.is_running() {
         watcher.watched_path_count() as u32
     } else {
         0
     };
     guard.queue_depth = scheduler.pending_queue_len() as u32;
+    drop(guard);
+    metrics::set_gauges(Some(scheduler.state()), scheduler.pending_queue_len());
+    *filtered_events_lock()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = watcher.filtered_event_counts();
+    *pending_settles_lock()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = watcher.pending_settles();
+}
+
+/// Publish the structured status for `get_scheduler_status`. A blocking
+/// process or Focus Assist, passed as `busy_reason`, is reported as such
+/// rather than as user activity.
+pub(super) fn update_scheduler_status(
+    scheduler: &AutoScheduler,
+    active_compressions: &[ActiveCompressionJob],
+    busy_reason: Option<WaitReason>,
+) {
+    let status = scheduler.status();
+    let wait_reason = match (status.wait_reason, busy_reason) {
+        (Some(WaitReason::NotIdle), Some(reason)) => Some(reason.into()),
+        (reason, _) => reason.map(Into::into),
+    };
+    let queue = scheduler.queue_snapshot();
+    let active_jobs = active_compressions
+        .iter()
+        .map(|job| {
+            let mut active = FrbActiveAutomationJob {
+                game_path: job.game_path.to_string_lossy().into_owned(),
+                game_name: job.game_name.clone(),
+                started_at_ms: queue
+                    .iter()
+                    .find(|queued| {
+                        queued.status == JobStatus::Compressing && queued.game_path == job.game_path
+                    })
+                    .and_then(|queued| queued.started_at)
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as i64),
+                is_paused: job.paused_since.is_some(),
+                ..FrbActiveAutomationJob::default()
+            };
+            if let Some(counters) = job.counters.get() {
+                let load = |counter: &std::sync::atomic::AtomicU64| {
+                    counter.load(std::sync::atomic::Ordering::Relaxed)
+                };
+                active.files_total = load(&counters.files_total);
+                active.files_processed = load(&counters.files_processed);
+                active.bytes_total = load(&counters.bytes_total);
+                active.bytes_processed = load(&counters.bytes_processed);
+                active.bytes_saved =
+                    load(&counters.bytes_original).saturating_sub(load(&counters.bytes_compressed));
+            }
+            active
+        })
+        .collect();
+    *scheduler_status_lock()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(FrbSchedulerStatus {
+        state: frb_scheduler_state(scheduler),
+        wait_reason,
+        backoff_remaining_seconds: status.backoff_remaining.map(|d| d.as_secs()),
+        active_jobs,
+        pending_count: status.pending as u32,
+        settling_count: status.settling as u32,
+        completed_count: status.completed as u32,
+        failed_count: status.failed as u32,
+        skipped_count: status.skipped as u32,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+    use crate::automation::scheduler::JobKind;
+
+    fn job(kind: JobKind, status: JobStatus) -> AutomationJob {
+        AutomationJob {
+            game_path: PathBuf::from(r"C:\Games\Game"),
+            game_name: None,
+            kind,
+            status,
+            idempotency_key: String::new(),
+            queued_at: SystemTime::now(),
+            started_at: None,
+            error: None,
+            simulated: false,
+        }
+    }
+
+    #[test]
+    fn reconcile_jobs_are_ahead_of_earlier_installs() {
+        let jobs = [
+            job(JobKind::NewInstall, JobStatus::WaitingForIdle),
+            job(JobKind::NewInstall, JobStatus::Compressing),
+            job(JobKind::Opportunistic, JobStatus::Pending),
+            job(JobKind::Reconcile, JobStatus::WaitingForSettle),
+            job(JobKind::NewInstall, JobStatus::Completed),
+        ];
+
+        let positions = queue_positions(&jobs);
+
+        assert_eq!(positions.get(&3), Some(&1));
+        assert_eq!(positions.get(&0), Some(&2));
+        assert_eq!(positions.get(&2), Some(&3));
+        assert_eq!(positions.get(&1), None);
+        assert_eq!(positions.get(&4), None);
+    }
 }
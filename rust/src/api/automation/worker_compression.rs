@@ -1,17 +1,29 @@
-use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
 
+use super::worker_progress::JobProgressFeed;
+use crate::automation::notifications::{CANCELLED_FOR_ACTIVITY_ERROR, GAME_RUNNING_ERROR};
 use crate::automation::scheduler::AutomationJob;
 use crate::compression::algorithm::CompressionAlgorithm;
-use crate::compression::engine::{CancellationToken, CompressionEngine};
+use crate::compression::engine::{
+    CancellationToken, CompressionEngine, CompressionStats, PauseHandle,
+};
 use crate::compression::history::{record_compression, CompressionHistoryEntry};
 use crate::compression::thread_policy::compute_thread_policy;
+use crate::compression::user_data;
+use crate::discovery::utils::refresh_cached_stats;
+use crate::progress::reporter::EngineCounters;
+use crate::safety::anticheat::{detect_anticheat, AntiCheatPolicy};
 use crate::safety::directstorage::is_directstorage_game;
+use crate::safety::ignore_marker::has_ignore_marker;
 use crate::safety::process::ProcessChecker;
+use crate::settings::Exclusions;
 
 pub(super) enum CompressionResult {
     Success {
         idempotency_key: String,
+        stats: CompressionStats,
     },
     Failed {
         idempotency_key: String,
@@ -21,12 +33,87 @@ pub(super) enum CompressionResult {
         idempotency_key: String,
         reason: String,
     },
+    /// Dry run: the job passed every guard and was estimated, not compressed.
+    Simulated {
+        idempotency_key: String,
+        original_bytes: u64,
+        estimated_saved_bytes: u64,
+    },
 }
 
 pub(super) struct ActiveCompressionJob {
     pub(super) result_rx: crossbeam_channel::Receiver<CompressionResult>,
     pub(super) cancel_token: CancellationToken,
+    pub(super) game_path: PathBuf,
+    pub(super) game_name: Option<String>,
+    pub(super) started_at: Instant,
+    /// Set by the worker once its engine exists.
+    pub(super) counters: Arc<OnceLock<EngineCounters>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
+    /// Set when the game started while this job was compressing; the job
+    /// was cancelled and goes back in the queue.
+    pub(super) game_launched: bool,
+    /// Parks the worker between files during brief user activity.
+    pub(super) pause: PauseHandle,
+    /// When the current activity pause began.
+    pub(super) paused_since: Option<Instant>,
+    /// Started by the worker loop once the engine counters exist.
+    pub(super) progress_feed: Option<JobProgressFeed>,
+}
+
+impl ActiveCompressionJob {
+    /// True when the job passed pre-flight checks and compression actually began.
+    pub(super) fn has_worker(&self) -> bool {
+        self.worker_handle.is_some()
+    }
+}
+
+/// Runs a job in place of the compression engine, given the job's pause
+/// switch.
+pub(super) type JobExecutor =
+    Arc<dyn Fn(&AutomationJob, &PauseHandle) -> CompressionResult + Send + Sync>;
+
+/// Which games unattended compression may touch, and whether it may write.
+pub(super) struct JobGuards {
+    pub(super) watch_roots: Vec<PathBuf>,
+    pub(super) exclusions: Exclusions,
+    pub(super) anticheat_policy: AntiCheatPolicy,
+    /// Estimate savings instead of compressing.
+    pub(super) dry_run: bool,
+    /// Let jobs run on save and config folders; see
+    /// [`crate::compression::user_data`].
+    pub(super) include_user_data: bool,
+}
+
+/// Run `job` through `executor` on a dedicated thread, skipping the
+/// pre-flight checks and the engine.
+pub(super) fn spawn_executor_job(
+    job: &AutomationJob,
+    executor: JobExecutor,
+) -> ActiveCompressionJob {
+    let (result_tx, result_rx) = crossbeam_channel::bounded::<CompressionResult>(1);
+    let worker_job = job.clone();
+    let pause = PauseHandle::new();
+    let worker_pause = pause.clone();
+    let worker_handle = std::thread::Builder::new()
+        .name("compact-games-auto-compress".to_owned())
+        .spawn(move || {
+            let _ = result_tx.send(executor(&worker_job, &worker_pause));
+        })
+        .ok();
+    ActiveCompressionJob {
+        result_rx,
+        cancel_token: CancellationToken::new(),
+        game_path: job.game_path.clone(),
+        game_name: job.game_name.clone(),
+        started_at: Instant::now(),
+        counters: Arc::default(),
+        worker_handle,
+        game_launched: false,
+        pause,
+        paused_since: None,
+        progress_feed: None,
+    }
 }
 
 /// Spawn compression on a dedicated thread so auto_loop stays responsive.
@@ -36,16 +123,18 @@ pub(super) fn spawn_compression_job(
     algorithm: CompressionAlgorithm,
     cpu_usage_percent: f32,
     io_parallelism_override: Option<usize>,
-    watch_roots: Vec<PathBuf>,
-    excluded_paths: HashSet<String>,
+    max_bytes_per_sec: Option<u64>,
+    guards: JobGuards,
 ) -> ActiveCompressionJob {
     let game_path = job.game_path.clone();
     let game_name = job.game_name.clone();
     let idempotency_key = job.idempotency_key.clone();
     let (result_tx, result_rx) = crossbeam_channel::bounded::<CompressionResult>(1);
     let cancel_token = CancellationToken::new();
+    let pause = PauseHandle::new();
+    let started_at = Instant::now();
 
-    if !is_authorized_game_path(&game_path, &watch_roots, &excluded_paths) {
+    if !is_authorized_game_path(&game_path, &guards.watch_roots) {
         log::warn!(
             "Skipping automation job outside configured library roots: {}",
             game_path.display()
@@ -57,7 +146,89 @@ pub(super) fn spawn_compression_job(
         return ActiveCompressionJob {
             result_rx,
             cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
+            worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
+        };
+    }
+
+    if let Some(rule) = guards.exclusions.matching_install(&game_path) {
+        log::info!(
+            "Skipping game excluded by {rule:?}: {}",
+            game_path.display()
+        );
+        let _ = result_tx.send(CompressionResult::Skipped {
+            idempotency_key,
+            reason: "Excluded from compression".to_string(),
+        });
+        return ActiveCompressionJob {
+            result_rx,
+            cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
+            worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
+        };
+    }
+
+    if !guards.include_user_data
+        && user_data::is_user_data_path(&game_path, &crate::settings::load().user_data)
+    {
+        log::info!(
+            "Skipping save or config folder excluded from automation: {}",
+            game_path.display()
+        );
+        let _ = result_tx.send(CompressionResult::Skipped {
+            idempotency_key,
+            reason: "Save and config folders are excluded from automation".to_string(),
+        });
+        return ActiveCompressionJob {
+            result_rx,
+            cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
             worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
+        };
+    }
+
+    if has_ignore_marker(&game_path) {
+        log::info!(
+            "Skipping game with an ignore marker: {}",
+            game_path.display()
+        );
+        let _ = result_tx.send(CompressionResult::Skipped {
+            idempotency_key,
+            reason: "Ignore marker present".to_string(),
+        });
+        return ActiveCompressionJob {
+            result_rx,
+            cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
+            worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
         };
     }
 
@@ -70,20 +241,70 @@ pub(super) fn spawn_compression_job(
         return ActiveCompressionJob {
             result_rx,
             cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
             worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
         };
     }
 
+    if let Some(anticheat) = detect_anticheat(&game_path) {
+        match guards.anticheat_policy {
+            AntiCheatPolicy::Skip => {
+                log::info!(
+                    "Skipping {} game: {}",
+                    anticheat.label(),
+                    game_path.display()
+                );
+                let _ = result_tx.send(CompressionResult::Skipped {
+                    idempotency_key,
+                    reason: format!("Kernel anti-cheat detected ({})", anticheat.label()),
+                });
+                return ActiveCompressionJob {
+                    result_rx,
+                    cancel_token,
+                    game_path,
+                    game_name,
+                    started_at,
+                    counters: Arc::default(),
+                    worker_handle: None,
+                    game_launched: false,
+                    pause,
+                    paused_since: None,
+                    progress_feed: None,
+                };
+            }
+            AntiCheatPolicy::Warn => log::warn!(
+                "Compressing {} game; it may fail integrity checks: {}",
+                anticheat.label(),
+                game_path.display()
+            ),
+        }
+    }
+
     if process_checker.is_game_running(&game_path) {
         log::info!("Game is running, deferring: {}", game_path.display());
         let _ = result_tx.send(CompressionResult::Failed {
             idempotency_key,
-            error: "Game is currently running".to_string(),
+            error: GAME_RUNNING_ERROR.to_string(),
         });
         return ActiveCompressionJob {
             result_rx,
             cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
             worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
         };
     }
 
@@ -96,13 +317,27 @@ pub(super) fn spawn_compression_job(
         return ActiveCompressionJob {
             result_rx,
             cancel_token,
+            game_path,
+            game_name,
+            started_at,
+            counters: Arc::default(),
             worker_handle: None,
+            game_launched: false,
+            pause,
+            paused_since: None,
+            progress_feed: None,
         };
     }
 
     let token = cancel_token.clone();
+    let worker_pause = pause.clone();
+    let counters: Arc<OnceLock<EngineCounters>> = Arc::default();
+    let worker_counters = counters.clone();
+    let job_game_path = game_path.clone();
+    let job_game_name = game_name.clone();
     let spawn_fail_tx = result_tx.clone();
     let spawn_fail_key = idempotency_key.clone();
+    let dry_run = guards.dry_run;
     let spawn_result = std::thread::Builder::new()
         .name("compact-games-auto-compress".to_owned())
         .spawn(move || {
@@ -114,7 +349,16 @@ pub(super) fn spawn_compression_job(
             );
             let engine = CompressionEngine::new(algorithm)
                 .with_thread_policy(policy)
-                .with_cancel_token(token.clone());
+                .with_cancel_token(token.clone())
+                .with_pause_handle(worker_pause)
+                .with_rate_limit(max_bytes_per_sec)
+                .with_backup_privilege(crate::settings::load().compress_admin_only_files);
+            let _ = worker_counters.set(engine.engine_counters());
+
+            if dry_run {
+                let _ = result_tx.send(simulate_job(&engine, &game_path, idempotency_key));
+                return;
+            }
 
             log::info!(
                 "Auto-compressing: {} ({}) with {:?}",
@@ -126,6 +370,28 @@ pub(super) fn spawn_compression_job(
             let result = engine.compress_folder(&game_path);
 
             let compression_result = match result {
+                Ok(stats) if stats.was_cancelled => {
+                    log::info!(
+                        "Auto-compression stopped early for {}: {} files done, {} bytes saved",
+                        game_path.display(),
+                        stats.files_processed,
+                        stats.bytes_saved_this_run()
+                    );
+                    record_compression(CompressionHistoryEntry::from_compression_stats(
+                        game_path.to_string_lossy().into_owned(),
+                        game_name.clone().unwrap_or_else(|| "unknown".to_string()),
+                        None,
+                        &stats,
+                        algorithm,
+                    ));
+                    refresh_cached_stats(&game_path);
+                    // Not finished: the job is retried or deferred like any
+                    // other cancellation.
+                    CompressionResult::Failed {
+                        idempotency_key,
+                        error: CANCELLED_FOR_ACTIVITY_ERROR.to_string(),
+                    }
+                }
                 Ok(stats) => {
                     log::info!(
                         "Auto-compression complete: {} saved {:.1}% ({} bytes)",
@@ -142,13 +408,25 @@ pub(super) fn spawn_compression_job(
                         &stats,
                         algorithm,
                     ));
-                    CompressionResult::Success { idempotency_key }
+                    refresh_cached_stats(&game_path);
+                    CompressionResult::Success {
+                        idempotency_key,
+                        stats,
+                    }
                 }
                 Err(crate::compression::error::CompressionError::Cancelled) => {
                     log::info!("Auto-compression cancelled for: {}", game_path.display());
                     CompressionResult::Failed {
                         idempotency_key,
-                        error: "Cancelled due to user activity".to_string(),
+                        error: CANCELLED_FOR_ACTIVITY_ERROR.to_string(),
+                    }
+                }
+                // Another instance, possibly another Windows account, is
+                // already compressing this game.
+                Err(e @ crate::compression::error::CompressionError::GameFolderLocked { .. }) => {
+                    CompressionResult::Skipped {
+                        idempotency_key,
+                        reason: e.to_string(),
                     }
                 }
                 Err(e) => {
@@ -178,15 +456,53 @@ pub(super) fn spawn_compression_job(
     ActiveCompressionJob {
         result_rx,
         cancel_token,
+        game_path: job_game_path,
+        game_name: job_game_name,
+        started_at,
+        counters,
         worker_handle,
+        game_launched: false,
+        pause,
+        paused_since: None,
+        progress_feed: None,
     }
 }
 
-fn is_authorized_game_path(
+/// Estimate what compressing `game_path` would save, without writing.
+fn simulate_job(
+    engine: &CompressionEngine,
     game_path: &Path,
-    watch_roots: &[PathBuf],
-    excluded_paths: &HashSet<String>,
-) -> bool {
+    idempotency_key: String,
+) -> CompressionResult {
+    match engine.estimate_folder_savings(game_path) {
+        Ok(estimate) => {
+            log::info!(
+                "Dry run: {} would save {} of {} bytes",
+                game_path.display(),
+                estimate.estimated_saved_bytes,
+                estimate.sampled_bytes
+            );
+            CompressionResult::Simulated {
+                idempotency_key,
+                original_bytes: estimate.sampled_bytes,
+                estimated_saved_bytes: estimate.estimated_saved_bytes,
+            }
+        }
+        Err(crate::compression::error::CompressionError::Cancelled) => CompressionResult::Failed {
+            idempotency_key,
+            error: CANCELLED_FOR_ACTIVITY_ERROR.to_string(),
+        },
+        Err(e) => {
+            log::error!("Dry-run estimate failed for {}: {e}", game_path.display());
+            CompressionResult::Failed {
+                idempotency_key,
+                error: e.to_string(),
+            }
+        }
+    }
+}
+
+fn is_authorized_game_path(game_path: &Path, watch_roots: &[PathBuf]) -> bool {
     let Ok(metadata) = std::fs::symlink_metadata(game_path) else {
         return false;
     };
@@ -194,11 +510,6 @@ fn is_authorized_game_path(
         return false;
     }
 
-    let normalized_path = crate::utils::normalize_path_key(game_path);
-    if excluded_paths.contains(&normalized_path) {
-        return false;
-    }
-
     let Ok(canonical_game_path) = std::fs::canonicalize(game_path) else {
         return false;
     };
@@ -245,6 +556,17 @@ mod tests {
             queued_at: SystemTime::now(),
             started_at: None,
             error: None,
+            simulated: false,
+        }
+    }
+
+    fn guards_for(path: &std::path::Path, anticheat_policy: AntiCheatPolicy) -> JobGuards {
+        JobGuards {
+            watch_roots: vec![path.parent().expect("temp parent").to_path_buf()],
+            exclusions: Exclusions::default(),
+            anticheat_policy,
+            dry_run: false,
+            include_user_data: false,
         }
     }
 
@@ -262,8 +584,8 @@ mod tests {
             CompressionAlgorithm::Xpress8K,
             0.0,
             None,
-            vec![dir.path().parent().expect("temp parent").to_path_buf()],
-            HashSet::new(),
+            None,
+            guards_for(dir.path(), AntiCheatPolicy::Skip),
         );
 
         let result = active
@@ -296,8 +618,8 @@ mod tests {
             CompressionAlgorithm::Xpress8K,
             0.0,
             None,
-            vec![dir.path().parent().expect("temp parent").to_path_buf()],
-            HashSet::new(),
+            None,
+            guards_for(dir.path(), AntiCheatPolicy::Skip),
         );
 
         let result = active
@@ -314,6 +636,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn anticheat_job_skips_under_skip_policy() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        std::fs::create_dir_all(dir.path().join("EasyAntiCheat"))
+            .expect("anti-cheat marker should be created");
+        let job = make_job(dir.path());
+        let process_checker = ProcessChecker::new();
+
+        let active = spawn_compression_job(
+            &job,
+            &process_checker,
+            CompressionAlgorithm::Xpress8K,
+            0.0,
+            None,
+            None,
+            guards_for(dir.path(), AntiCheatPolicy::Skip),
+        );
+
+        let result = active
+            .result_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("worker should emit a result");
+
+        assert!(
+            matches!(
+                result,
+                CompressionResult::Skipped { reason, .. } if reason.starts_with("Kernel anti-cheat")
+            ),
+            "expected anti-cheat skip under the default policy"
+        );
+        assert!(!active.has_worker());
+    }
+
+    #[test]
+    fn dry_run_job_estimates_without_compressing() {
+        let root = TempDir::new().expect("library root");
+        let game = root.path().join("Game");
+        std::fs::create_dir_all(&game).expect("game directory");
+        std::fs::write(game.join("data.bin"), vec![0_u8; 64 * 1024]).expect("sample file");
+        let job = make_job(&game);
+        let process_checker = ProcessChecker::new();
+        let mut guards = guards_for(&game, AntiCheatPolicy::Skip);
+        guards.dry_run = true;
+
+        let mut active = spawn_compression_job(
+            &job,
+            &process_checker,
+            CompressionAlgorithm::Xpress8K,
+            0.0,
+            None,
+            None,
+            guards,
+        );
+
+        let result = active
+            .result_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("worker should emit a result");
+        join_compression_worker(&mut active, "test");
+
+        assert!(
+            matches!(result, CompressionResult::Simulated { original_bytes, .. } if original_bytes > 0),
+            "dry run should report an estimate"
+        );
+        let algorithm = crate::compression::wof::wof_get_compression(&game.join("data.bin"))
+            .expect("query compression state");
+        assert!(algorithm.is_none(), "dry run must not compress files");
+    }
+
     #[test]
     fn authorization_rejects_paths_outside_the_configured_library_root() {
         let root = TempDir::new().expect("library root");
@@ -321,15 +712,10 @@ mod tests {
         let outside = TempDir::new().expect("outside path");
         std::fs::create_dir_all(&game).expect("game directory");
 
-        assert!(is_authorized_game_path(
-            &game,
-            &[root.path().to_path_buf()],
-            &HashSet::new(),
-        ));
+        assert!(is_authorized_game_path(&game, &[root.path().to_path_buf()],));
         assert!(!is_authorized_game_path(
             outside.path(),
             &[root.path().to_path_buf()],
-            &HashSet::new(),
         ));
     }
 
@@ -340,7 +726,6 @@ mod tests {
         assert!(is_authorized_game_path(
             game.path(),
             &[game.path().to_path_buf()],
-            &HashSet::new(),
         ));
     }
 }
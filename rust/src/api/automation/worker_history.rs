@@ -0,0 +1,54 @@
+//! Automation audit-log recording for the auto-compression worker.
+
+use crate::automation::event_log::{AutomationEvent, AutomationEventKind, AutomationEventLog};
+
+use super::worker_compression::{ActiveCompressionJob, CompressionResult};
+
+pub(super) fn record_job_started(job: &ActiveCompressionJob) {
+    append(AutomationEvent::new(
+        AutomationEventKind::JobStarted,
+        job.game_path.clone(),
+        job.game_name.clone(),
+    ));
+}
+
+pub(super) fn record_job_outcome(job: &ActiveCompressionJob, result: &CompressionResult) {
+    let (kind, message) = match result {
+        CompressionResult::Success { .. } => (AutomationEventKind::JobCompleted, None),
+        CompressionResult::Failed { error, .. } => {
+            (AutomationEventKind::JobFailed, Some(error.clone()))
+        }
+        CompressionResult::Skipped { reason, .. } => {
+            (AutomationEventKind::JobSkipped, Some(reason.clone()))
+        }
+        CompressionResult::Simulated { .. } => (AutomationEventKind::JobSimulated, None),
+    };
+
+    let mut event = AutomationEvent::new(kind, job.game_path.clone(), job.game_name.clone());
+    event.message = message;
+    event.duration_ms = Some(job.started_at.elapsed().as_millis() as u64);
+    if let CompressionResult::Success { stats, .. } = result {
+        event.original_bytes = Some(stats.original_bytes);
+        event.compressed_bytes = Some(stats.compressed_bytes);
+        event.duration_ms = Some(stats.duration_ms);
+    }
+    if let CompressionResult::Simulated {
+        original_bytes,
+        estimated_saved_bytes,
+        ..
+    } = result
+    {
+        event.original_bytes = Some(*original_bytes);
+        event.compressed_bytes = Some(original_bytes.saturating_sub(*estimated_saved_bytes));
+    }
+    append(event);
+}
+
+fn append(event: AutomationEvent) {
+    if let Err(e) = AutomationEventLog::global().append(&event) {
+        log::warn!(
+            "[automation][history] failed to record {:?}: {e}",
+            event.kind
+        );
+    }
+}
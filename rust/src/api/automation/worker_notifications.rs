@@ -0,0 +1,66 @@
+//! Toast notifications for auto-compression outcomes.
+
+use crate::automation::notifications::{self, DrainSummary};
+
+use super::worker_broadcast;
+use super::worker_compression::{ActiveCompressionJob, CompressionResult};
+
+/// Notify about a finished job and fold it into the pending drain summary.
+pub(super) fn notify_job_outcome(
+    job: &ActiveCompressionJob,
+    result: &CompressionResult,
+    summary: &mut DrainSummary,
+) {
+    let game_name = job.game_name.as_deref();
+    let notification = match result {
+        CompressionResult::Success { stats, .. } => {
+            summary.completed = summary.completed.saturating_add(1);
+            summary.bytes_saved = summary
+                .bytes_saved
+                .saturating_add(stats.original_bytes.saturating_sub(stats.compressed_bytes));
+            Some(notifications::job_completed(
+                &job.game_path,
+                game_name,
+                stats.original_bytes,
+                stats.compressed_bytes,
+            ))
+        }
+        CompressionResult::Failed { error, .. } => {
+            let notification = notifications::job_failed(&job.game_path, game_name, error);
+            if notification.is_some() {
+                summary.failed = summary.failed.saturating_add(1);
+            }
+            notification
+        }
+        CompressionResult::Simulated {
+            estimated_saved_bytes,
+            ..
+        } => {
+            summary.simulated = summary.simulated.saturating_add(1);
+            summary.bytes_would_save = summary
+                .bytes_would_save
+                .saturating_add(*estimated_saved_bytes);
+            Some(notifications::job_simulated(
+                &job.game_path,
+                game_name,
+                *estimated_saved_bytes,
+            ))
+        }
+        CompressionResult::Skipped { .. } => None,
+    };
+    if let Some(notification) = notification {
+        worker_broadcast::broadcast_notification(notification);
+    }
+}
+
+/// Emit a queue-drained notification once all work from the current batch is done.
+pub(super) fn maybe_notify_queue_drained(
+    summary: &mut DrainSummary,
+    has_active_job: bool,
+    pending_jobs: usize,
+) {
+    if has_active_job || pending_jobs > 0 || summary.is_empty() {
+        return;
+    }
+    worker_broadcast::broadcast_notification(notifications::queue_drained(std::mem::take(summary)));
+}
@@ -0,0 +1,302 @@
+//! Combined progress across the automation jobs of one queue drain.
+//!
+//! A batch starts when a job is queued while nothing else is outstanding
+//! and ends once every job it picked up has finished, failed or been
+//! skipped. Job sizes come from the discovery cache until the engine
+//! reports its own total for a running job.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crossbeam_channel::Receiver;
+
+use crate::api::automation_types::FrbAutomationOverallProgress;
+use crate::api::types::FrbCompressionProgress;
+use crate::automation::duration::duration_for;
+use crate::automation::scheduler::{AutomationJob, JobStatus};
+use crate::progress::reporter::{EngineCounters, ProgressReporter};
+use crate::progress::tracker::CompressionProgress;
+
+struct BatchJob {
+    game_path: PathBuf,
+    game_name: Option<String>,
+    /// Logical bytes; 0 while unknown.
+    size_bytes: u64,
+    processed_bytes: u64,
+    /// Expected throughput, for the remaining-time estimate; 0 while unknown.
+    bytes_per_sec: u64,
+    compressing: bool,
+    finished: bool,
+}
+
+#[derive(Default)]
+pub(super) struct OverallProgressTracker {
+    jobs: HashMap<String, BatchJob>,
+    last_emitted: Option<FrbAutomationOverallProgress>,
+}
+
+impl OverallProgressTracker {
+    /// Fold the scheduler queue into the batch. `size_of` and `rate_of`
+    /// are only asked once per job.
+    pub(super) fn observe_queue(
+        &mut self,
+        queue: &[AutomationJob],
+        size_of: impl Fn(&Path) -> Option<u64>,
+        rate_of: impl Fn(&Path) -> u64,
+    ) {
+        for batch_job in self.jobs.values_mut() {
+            batch_job.compressing = false;
+        }
+        for job in queue {
+            let outstanding = is_outstanding(job.status);
+            let batch_job = match self.jobs.get_mut(&job.idempotency_key) {
+                Some(batch_job) => batch_job,
+                // Finished jobs from an earlier batch linger in the queue.
+                None if !outstanding => continue,
+                None => self
+                    .jobs
+                    .entry(job.idempotency_key.clone())
+                    .or_insert_with(|| BatchJob {
+                        game_path: job.game_path.clone(),
+                        game_name: job.game_name.clone(),
+                        size_bytes: size_of(&job.game_path).unwrap_or(0),
+                        processed_bytes: 0,
+                        bytes_per_sec: rate_of(&job.game_path),
+                        compressing: false,
+                        finished: false,
+                    }),
+            };
+            // A failed job can be retried within the same batch.
+            batch_job.finished = !outstanding;
+            batch_job.compressing = job.status == JobStatus::Compressing;
+            if batch_job.finished {
+                batch_job.processed_bytes = batch_job.size_bytes;
+            }
+        }
+        let queued: std::collections::HashSet<&str> = queue
+            .iter()
+            .map(|job| job.idempotency_key.as_str())
+            .collect();
+        for (key, batch_job) in &mut self.jobs {
+            if !queued.contains(key.as_str()) {
+                batch_job.finished = true;
+                batch_job.processed_bytes = batch_job.size_bytes;
+            }
+        }
+    }
+
+    /// Engine counters for a running job; they replace the cached size.
+    pub(super) fn observe_active(
+        &mut self,
+        game_path: &Path,
+        bytes_total: u64,
+        bytes_processed: u64,
+    ) {
+        let Some(batch_job) = self
+            .jobs
+            .values_mut()
+            .find(|batch_job| !batch_job.finished && batch_job.game_path == game_path)
+        else {
+            return;
+        };
+        if bytes_total > 0 {
+            batch_job.size_bytes = bytes_total;
+        }
+        batch_job.processed_bytes = bytes_processed.min(batch_job.size_bytes);
+    }
+
+    /// The current snapshot when it differs from the last one returned.
+    /// The batch is reset after its completion snapshot.
+    pub(super) fn take_changed(&mut self) -> Option<FrbAutomationOverallProgress> {
+        if self.jobs.is_empty() {
+            return None;
+        }
+        let progress = self.snapshot();
+        if progress.is_complete {
+            self.jobs.clear();
+            self.last_emitted = None;
+            return Some(progress);
+        }
+        if self.last_emitted.as_ref() == Some(&progress) {
+            return None;
+        }
+        self.last_emitted = Some(progress.clone());
+        Some(progress)
+    }
+
+    fn snapshot(&self) -> FrbAutomationOverallProgress {
+        let mut progress = FrbAutomationOverallProgress::default();
+        for batch_job in self.jobs.values() {
+            progress.games_total += 1;
+            progress.bytes_total = progress.bytes_total.saturating_add(batch_job.size_bytes);
+            progress.bytes_processed = progress
+                .bytes_processed
+                .saturating_add(batch_job.processed_bytes);
+            if !batch_job.finished && batch_job.bytes_per_sec > 0 {
+                let remaining = batch_job
+                    .size_bytes
+                    .saturating_sub(batch_job.processed_bytes);
+                progress.estimated_remaining_ms = progress.estimated_remaining_ms.saturating_add(
+                    duration_for(remaining, batch_job.bytes_per_sec).as_millis() as u64,
+                );
+            }
+            if batch_job.finished {
+                progress.games_finished += 1;
+            } else if batch_job.compressing {
+                progress.current_games.push(
+                    batch_job
+                        .game_name
+                        .clone()
+                        .unwrap_or_else(|| batch_job.game_path.to_string_lossy().into_owned()),
+                );
+            }
+        }
+        progress.current_games.sort();
+        progress.is_complete = progress.games_finished == progress.games_total;
+        progress
+    }
+}
+
+/// Live engine progress of one running job, sampled by a reporter thread.
+pub(super) struct JobProgressFeed {
+    _reporter: ProgressReporter,
+    rx: Receiver<CompressionProgress>,
+    latest: Option<FrbCompressionProgress>,
+}
+
+impl JobProgressFeed {
+    pub(super) fn start(counters: EngineCounters, game_name: &str) -> Self {
+        let (reporter, rx) = ProgressReporter::new(counters, game_name.into());
+        Self {
+            _reporter: reporter,
+            rx,
+            latest: None,
+        }
+    }
+
+    /// The newest snapshot, when one arrived since the last poll.
+    pub(super) fn poll(&mut self) -> Option<&FrbCompressionProgress> {
+        let newest = self.rx.try_iter().last()?;
+        self.latest = Some(newest.into());
+        self.latest.as_ref()
+    }
+
+    pub(super) fn latest(&self) -> Option<&FrbCompressionProgress> {
+        self.latest.as_ref()
+    }
+}
+
+fn is_outstanding(status: JobStatus) -> bool {
+    matches!(
+        status,
+        JobStatus::Pending
+            | JobStatus::WaitingForSettle
+            | JobStatus::WaitingForIdle
+            | JobStatus::Compressing
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+    use crate::automation::scheduler::JobKind;
+
+    fn job(key: &str, status: JobStatus) -> AutomationJob {
+        AutomationJob {
+            game_path: PathBuf::from(format!("C:\\Games\\{key}")),
+            game_name: Some(key.to_string()),
+            kind: JobKind::NewInstall,
+            status,
+            idempotency_key: key.to_string(),
+            queued_at: SystemTime::now(),
+            started_at: None,
+            error: None,
+            simulated: false,
+        }
+    }
+
+    fn size_of(_path: &Path) -> Option<u64> {
+        Some(1_000)
+    }
+
+    fn rate_of(_path: &Path) -> u64 {
+        100
+    }
+
+    #[test]
+    fn batch_counts_finished_games_and_in_flight_bytes() {
+        let mut tracker = OverallProgressTracker::default();
+        tracker.observe_queue(
+            &[
+                job("a", JobStatus::Completed),
+                job("b", JobStatus::Compressing),
+                job("c", JobStatus::Pending),
+            ],
+            size_of,
+            rate_of,
+        );
+        // "a" finished before the batch started, so it is not counted.
+        let first = tracker.take_changed().expect("batch started");
+        assert_eq!((first.games_finished, first.games_total), (0, 2));
+        assert_eq!(first.current_games, vec!["b".to_string()]);
+        assert_eq!(first.estimated_remaining_ms, 20_000);
+
+        tracker.observe_active(Path::new("C:\\Games\\b"), 4_000, 1_000);
+        let second = tracker.take_changed().expect("bytes advanced");
+        assert_eq!(second.bytes_total, 5_000);
+        assert_eq!(second.bytes_processed, 1_000);
+        assert_eq!(second.estimated_remaining_ms, 40_000);
+        assert!(tracker.take_changed().is_none(), "unchanged snapshot");
+
+        tracker.observe_queue(
+            &[
+                job("b", JobStatus::Completed),
+                job("c", JobStatus::Compressing),
+            ],
+            size_of,
+            rate_of,
+        );
+        let third = tracker.take_changed().expect("job finished");
+        assert_eq!((third.games_finished, third.games_total), (1, 2));
+        assert_eq!(third.bytes_processed, 4_000);
+        assert!(!third.is_complete);
+    }
+
+    #[test]
+    fn batch_completes_once_and_resets() {
+        let mut tracker = OverallProgressTracker::default();
+        tracker.observe_queue(&[job("a", JobStatus::Pending)], size_of, rate_of);
+        tracker.take_changed();
+
+        // Jobs dropped from the queue count as finished.
+        tracker.observe_queue(&[], size_of, rate_of);
+        let done = tracker.take_changed().expect("completion snapshot");
+        assert!(done.is_complete);
+        assert_eq!(done.bytes_processed, done.bytes_total);
+        assert!(tracker.take_changed().is_none());
+
+        tracker.observe_queue(&[job("b", JobStatus::Pending)], size_of, rate_of);
+        let next = tracker.take_changed().expect("new batch");
+        assert_eq!((next.games_finished, next.games_total), (0, 1));
+    }
+
+    #[test]
+    fn retried_job_is_outstanding_again() {
+        let mut tracker = OverallProgressTracker::default();
+        tracker.observe_queue(
+            &[job("a", JobStatus::Failed), job("b", JobStatus::Pending)],
+            size_of,
+            rate_of,
+        );
+        tracker.observe_queue(
+            &[job("a", JobStatus::Failed), job("b", JobStatus::Failed)],
+            size_of,
+            rate_of,
+        );
+        tracker.observe_queue(&[job("b", JobStatus::Pending)], size_of, rate_of);
+        let progress = tracker.take_changed().expect("snapshot");
+        assert_eq!((progress.games_finished, progress.games_total), (0, 1));
+    }
+}
@@ -8,6 +8,7 @@ use crate::automation::scheduler::{AutoScheduler, JobStatus};
 use crate::automation::watcher::coalescer::{is_noise_path, is_user_state_subpath};
 use crate::compression::history::with_latest_compression_timestamps_by_path;
 use crate::discovery::cache::{has_entry as has_discovery_cache_entry, normalize_path_key};
+use crate::safety::ignore_marker::has_ignore_marker;
 
 const MAX_RECONCILE_JOBS_PER_PASS: usize = 256;
 const RECONCILE_PROBE_MAX_DEPTH: usize = 6;
@@ -61,6 +62,9 @@ fn game_change_marker_ms(path: &Path) -> Option<u64> {
             .follow_links(false)
             .into_iter()
             .filter_entry(|entry| {
+                if entry.file_type().is_dir() && has_ignore_marker(entry.path()) {
+                    return false;
+                }
                 let relative_path = entry.path().strip_prefix(path).unwrap_or(entry.path());
                 !is_noise_path(relative_path) && !is_user_state_subpath(relative_path)
             })
@@ -98,7 +102,10 @@ fn filtered_child_max_mtime_ms(path: &Path) -> Option<u64> {
             let relative_path = child_path
                 .strip_prefix(path)
                 .unwrap_or(child_path.as_path());
-            if is_noise_path(relative_path) || is_user_state_subpath(relative_path) {
+            if is_noise_path(relative_path)
+                || is_user_state_subpath(relative_path)
+                || has_ignore_marker(&child_path)
+            {
                 continue;
             }
             let child_mtime = entry.metadata().ok().and_then(|m| metadata_modified_ms(&m));
@@ -37,6 +37,8 @@ fn add_compression_history(path: &Path, timestamp_ms: u64) {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 1,
+        extensions: Vec::new(),
+        throughput: None,
     });
 }
 
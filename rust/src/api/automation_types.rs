@@ -8,6 +8,7 @@ use std::time::UNIX_EPOCH;
 use thiserror::Error;
 
 use super::types::FrbCompressionAlgorithm;
+use crate::automation::idle::IdlePolicy;
 
 // ── Automation errors ────────────────────────────────────────────────
 
@@ -16,6 +17,10 @@ use super::types::FrbCompressionAlgorithm;
 pub enum FrbAutomationError {
     #[error("Auto-compression is already running")]
     AlreadyRunning,
+    /// Another process, such as the daemon or a second copy of the app,
+    /// runs automation for the same data directory.
+    #[error("Auto-compression is already running in {owner}")]
+    RunningElsewhere { owner: String },
     #[error("Auto-compression is not running")]
     NotRunning,
     #[error("Failed to start auto-compression: {message}")]
@@ -24,6 +29,8 @@ pub enum FrbAutomationError {
     StopFailed { message: String },
     #[error("Failed to update automation config: {message}")]
     ConfigUpdateFailed { message: String },
+    #[error("Automation history operation failed: {message}")]
+    HistoryFailed { message: String },
 }
 
 // ── Watcher events ───────────────────────────────────────────────────
@@ -43,6 +50,10 @@ pub enum FrbWatcherEvent {
         path: String,
         game_name: Option<String>,
     },
+    /// The drive holding this watch root went away.
+    DriveOffline { path: String },
+    /// The drive holding this watch root is back and watched again.
+    DriveOnline { path: String },
 }
 
 impl From<crate::automation::watcher::WatchEvent> for FrbWatcherEvent {
@@ -66,6 +77,12 @@ impl From<crate::automation::watcher::WatchEvent> for FrbWatcherEvent {
                     game_name,
                 }
             }
+            crate::automation::watcher::WatchEvent::DriveOffline { path } => Self::DriveOffline {
+                path: path.to_string_lossy().into_owned(),
+            },
+            crate::automation::watcher::WatchEvent::DriveOnline { path } => Self::DriveOnline {
+                path: path.to_string_lossy().into_owned(),
+            },
         }
     }
 }
@@ -126,6 +143,15 @@ pub struct FrbAutomationJob {
     pub queued_at_ms: i64,
     pub started_at_ms: Option<i64>,
     pub error: Option<String>,
+    /// Finished as a dry run; nothing was compressed.
+    pub simulated: bool,
+    /// Expected time to compress the whole game, from its cached size and
+    /// past throughput. `None` once finished or while the size is unknown.
+    pub estimated_duration_ms: Option<u64>,
+    /// 1-based order in which waiting jobs will start; `None` once started.
+    pub queue_position: Option<u32>,
+    /// Live engine progress while compressing.
+    pub progress: Option<crate::api::types::FrbCompressionProgress>,
 }
 
 impl From<crate::automation::scheduler::AutomationJob> for FrbAutomationJob {
@@ -146,6 +172,10 @@ impl From<crate::automation::scheduler::AutomationJob> for FrbAutomationJob {
                     .map(|d| d.as_millis() as i64)
             }),
             error: j.error,
+            simulated: j.simulated,
+            estimated_duration_ms: None,
+            queue_position: None,
+            progress: None,
         }
     }
 }
@@ -162,6 +192,8 @@ pub enum FrbSchedulerState {
     Compressing,
     Paused,
     Backoff,
+    /// Paused via `pause_auto_compression`; stays paused until resumed.
+    PausedByUser,
 }
 
 impl From<crate::automation::scheduler::SchedulerState> for FrbSchedulerState {
@@ -178,6 +210,99 @@ impl From<crate::automation::scheduler::SchedulerState> for FrbSchedulerState {
     }
 }
 
+/// Why the scheduler is not starting work, for Flutter display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbSchedulerWaitReason {
+    /// Auto-compression is not running.
+    ServiceStopped,
+    NoJobs,
+    Settling,
+    InstallInProgress,
+    NotIdle,
+    BlockingProcess,
+    /// Focus Assist, presentation mode or a full-screen game is on.
+    FocusAssist,
+    /// The CPU is running hot or its clock is capped for heat or power.
+    ThermallyConstrained,
+    Backoff,
+    PausedByUser,
+    GamesRunning,
+    DrivesOffline,
+    FolderBusy,
+    StoppingAfterCurrent,
+}
+
+impl From<crate::automation::scheduler::WaitReason> for FrbSchedulerWaitReason {
+    fn from(r: crate::automation::scheduler::WaitReason) -> Self {
+        use crate::automation::scheduler::WaitReason;
+        match r {
+            WaitReason::NoJobs => Self::NoJobs,
+            WaitReason::Settling => Self::Settling,
+            WaitReason::InstallInProgress => Self::InstallInProgress,
+            WaitReason::NotIdle => Self::NotIdle,
+            WaitReason::BlockingProcess => Self::BlockingProcess,
+            WaitReason::FocusAssist => Self::FocusAssist,
+            WaitReason::ThermallyConstrained => Self::ThermallyConstrained,
+            WaitReason::Backoff => Self::Backoff,
+            WaitReason::UserPaused => Self::PausedByUser,
+            WaitReason::GamesRunning => Self::GamesRunning,
+            WaitReason::DrivesOffline => Self::DrivesOffline,
+            WaitReason::FolderBusy => Self::FolderBusy,
+            WaitReason::StoppingAfterCurrent => Self::StoppingAfterCurrent,
+        }
+    }
+}
+
+/// A compressing automation job with its engine counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrbActiveAutomationJob {
+    pub game_path: String,
+    pub game_name: Option<String>,
+    pub started_at_ms: Option<i64>,
+    pub files_total: u64,
+    pub files_processed: u64,
+    /// Logical bytes of the game; 0 until the engine has scanned it.
+    pub bytes_total: u64,
+    pub bytes_processed: u64,
+    pub bytes_saved: u64,
+    /// Parked between files for user activity.
+    pub is_paused: bool,
+}
+
+/// Structured scheduler snapshot, so the UI need not infer why nothing
+/// is happening from the bare state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrbSchedulerStatus {
+    pub state: FrbSchedulerState,
+    /// `None` while work is running or about to start.
+    pub wait_reason: Option<FrbSchedulerWaitReason>,
+    pub backoff_remaining_seconds: Option<u64>,
+    pub active_jobs: Vec<FrbActiveAutomationJob>,
+    /// Queued and past their settle window.
+    pub pending_count: u32,
+    pub settling_count: u32,
+    pub completed_count: u32,
+    pub failed_count: u32,
+    pub skipped_count: u32,
+}
+
+impl Default for FrbSchedulerStatus {
+    /// Status while auto-compression is stopped.
+    fn default() -> Self {
+        Self {
+            state: FrbSchedulerState::Idle,
+            wait_reason: Some(FrbSchedulerWaitReason::ServiceStopped),
+            backoff_remaining_seconds: None,
+            active_jobs: Vec::new(),
+            pending_count: 0,
+            settling_count: 0,
+            completed_count: 0,
+            failed_count: 0,
+            skipped_count: 0,
+        }
+    }
+}
+
 // ── Configuration ────────────────────────────────────────────────────
 
 /// Configuration pushed from Flutter settings to Rust automation.
@@ -191,6 +316,77 @@ pub struct FrbAutomationConfig {
     pub algorithm: FrbCompressionAlgorithm,
     pub allow_directstorage_override: bool,
     pub io_parallelism_override: Option<u64>,
+    /// Throughput cap for each auto-compression job in bytes per second,
+    /// so background work on a hard drive leaves room for other apps.
+    /// `None` runs unthrottled.
+    pub max_bytes_per_sec: Option<u64>,
+    /// Maximum concurrent auto-compression jobs; jobs only run in parallel
+    /// when they target different physical disks. `None` keeps one job at
+    /// a time.
+    pub max_concurrent_jobs: Option<u32>,
+    /// Process names (e.g. `obs64.exe`) that block compression while running.
+    pub blocking_processes: Vec<String>,
+    /// Watch and auto-compress games on removable and network drives.
+    /// Off by default: WOF compression over SMB is not supported.
+    pub include_removable_drives: bool,
+    /// Compress games that ship kernel anti-cheat with a warning instead of
+    /// skipping them. Off by default: a driver that hash-checks files can
+    /// refuse to launch the game afterwards.
+    pub allow_anticheat_compression: bool,
+    /// Extra directory names whose changes the watcher ignores.
+    pub watcher_ignored_dirs: Vec<String>,
+    /// Extra file extensions, without the dot, the watcher ignores.
+    pub watcher_ignored_extensions: Vec<String>,
+    /// Launcher temp-file name patterns (`*` and `?` wildcards) the
+    /// watcher ignores.
+    pub watcher_temp_file_patterns: Vec<String>,
+    /// Ignore changes more than this many levels below a game folder.
+    pub watcher_max_event_depth: Option<u32>,
+    /// Observe-only mode: jobs run the savings estimator and report what
+    /// they would have saved instead of compressing.
+    pub dry_run: bool,
+    /// Seconds without keyboard or mouse input before the user counts as
+    /// away. `None` judges idleness by CPU alone.
+    pub input_idle_seconds: Option<u64>,
+    /// How the CPU and input signals combine when both are enabled.
+    pub idle_policy: FrbIdlePolicy,
+    /// Start jobs even while Focus Assist, presentation mode or a
+    /// full-screen game is on. Off by default: those count as the user
+    /// being busy.
+    pub ignore_focus_assist: bool,
+    /// Pause jobs while the CPU is hot or throttled, so small-form-factor
+    /// machines are not kept loud and clock-limited by background work.
+    pub pause_when_thermally_constrained: bool,
+    /// Local time, in minutes after midnight, to wake the machine from
+    /// sleep while jobs are queued. `None` never wakes it.
+    pub maintenance_wake_minute_of_day: Option<u32>,
+}
+
+/// Mirror of `IdlePolicy` for FRB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbIdlePolicy {
+    /// Idle only when the CPU is quiet and the user has been away.
+    All,
+    /// Idle when either the CPU is quiet or the user has been away.
+    Any,
+}
+
+impl From<IdlePolicy> for FrbIdlePolicy {
+    fn from(policy: IdlePolicy) -> Self {
+        match policy {
+            IdlePolicy::All => Self::All,
+            IdlePolicy::Any => Self::Any,
+        }
+    }
+}
+
+impl From<FrbIdlePolicy> for IdlePolicy {
+    fn from(policy: FrbIdlePolicy) -> Self {
+        match policy {
+            FrbIdlePolicy::All => Self::All,
+            FrbIdlePolicy::Any => Self::Any,
+        }
+    }
 }
 
 /// Watcher diagnostics for Flutter display.
@@ -200,4 +396,339 @@ pub struct FrbWatcherDiagnostics {
     pub watched_path_count: u32,
     pub queue_depth: u32,
     pub last_error: Option<String>,
+    /// Raw events dropped as noise: temp files, ignored extensions and
+    /// directories.
+    pub filtered_noise_events: u64,
+    /// Raw events for saves, configs, logs and caches.
+    pub filtered_user_state_events: u64,
+    /// Raw events below a folder with an ignore marker.
+    pub filtered_ignore_marker_events: u64,
+    /// Raw events deeper than the configured depth limit.
+    pub filtered_too_deep_events: u64,
+    /// Raw events echoing the app's own compression.
+    pub filtered_self_compression_events: u64,
+    /// Changes waiting out their settle cooldown, soonest first.
+    pub pending_settles: Vec<FrbPendingSettle>,
+}
+
+/// Settle timer of one game with coalesced filesystem changes.
+#[derive(Debug, Clone)]
+pub struct FrbPendingSettle {
+    pub game_path: String,
+    pub game_name: Option<String>,
+    /// Raw events coalesced so far.
+    pub event_count: u32,
+    /// Time from the first to the latest event of the burst.
+    pub burst_ms: u64,
+    /// Cooldown chosen for the burst; longer bursts wait longer.
+    pub cooldown_ms: u64,
+    /// Quiet time left before the change is handed to the scheduler.
+    pub remaining_ms: u64,
+}
+
+impl From<crate::automation::watcher::PendingSettle> for FrbPendingSettle {
+    fn from(settle: crate::automation::watcher::PendingSettle) -> Self {
+        Self {
+            game_path: settle.path.to_string_lossy().into_owned(),
+            game_name: settle.game_name,
+            event_count: settle.event_count,
+            burst_ms: settle.burst.as_millis() as u64,
+            cooldown_ms: settle.cooldown.as_millis() as u64,
+            remaining_ms: settle.remaining.as_millis() as u64,
+        }
+    }
+}
+
+/// Background daemon heartbeat for Flutter display.
+#[derive(Debug, Clone)]
+pub struct FrbDaemonStatus {
+    /// Heartbeat is fresh and the daemon has not shut down.
+    pub is_alive: bool,
+    pub is_service: bool,
+    pub pid: u32,
+    pub started_at_ms: i64,
+    pub updated_at_ms: i64,
+    pub automation_running: bool,
+    pub scheduler_state: String,
+    pub queue_depth: u32,
+    pub watched_path_count: u32,
+    pub last_error: Option<String>,
+}
+
+impl FrbDaemonStatus {
+    pub(crate) fn from_status(status: crate::daemon::status::DaemonStatus, now_ms: u64) -> Self {
+        Self {
+            is_alive: status.is_alive(now_ms),
+            is_service: status.is_service,
+            pid: status.pid,
+            started_at_ms: status.started_at_ms as i64,
+            updated_at_ms: status.updated_at_ms as i64,
+            automation_running: status.automation_running,
+            scheduler_state: status.scheduler_state,
+            queue_depth: status.queue_depth,
+            watched_path_count: status.watched_path_count,
+            last_error: status.last_error,
+        }
+    }
+}
+
+// ── Automation history ───────────────────────────────────────────────
+
+/// Kind of automation audit-log entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbAutomationEventKind {
+    JobStarted,
+    JobCompleted,
+    JobFailed,
+    JobSkipped,
+    JobSimulated,
+}
+
+impl From<crate::automation::event_log::AutomationEventKind> for FrbAutomationEventKind {
+    fn from(k: crate::automation::event_log::AutomationEventKind) -> Self {
+        match k {
+            crate::automation::event_log::AutomationEventKind::JobStarted => Self::JobStarted,
+            crate::automation::event_log::AutomationEventKind::JobCompleted => Self::JobCompleted,
+            crate::automation::event_log::AutomationEventKind::JobFailed => Self::JobFailed,
+            crate::automation::event_log::AutomationEventKind::JobSkipped => Self::JobSkipped,
+            crate::automation::event_log::AutomationEventKind::JobSimulated => Self::JobSimulated,
+        }
+    }
+}
+
+impl From<FrbAutomationEventKind> for crate::automation::event_log::AutomationEventKind {
+    fn from(k: FrbAutomationEventKind) -> Self {
+        match k {
+            FrbAutomationEventKind::JobStarted => Self::JobStarted,
+            FrbAutomationEventKind::JobCompleted => Self::JobCompleted,
+            FrbAutomationEventKind::JobFailed => Self::JobFailed,
+            FrbAutomationEventKind::JobSkipped => Self::JobSkipped,
+            FrbAutomationEventKind::JobSimulated => Self::JobSimulated,
+        }
+    }
+}
+
+/// A single automation audit-log entry for Flutter display.
+#[derive(Debug, Clone)]
+pub struct FrbAutomationHistoryEntry {
+    pub timestamp_ms: i64,
+    pub kind: FrbAutomationEventKind,
+    pub game_path: String,
+    pub game_name: Option<String>,
+    pub original_bytes: Option<u64>,
+    pub compressed_bytes: Option<u64>,
+    pub bytes_saved: Option<u64>,
+    pub duration_ms: Option<u64>,
+    pub message: Option<String>,
+}
+
+impl From<crate::automation::event_log::AutomationEvent> for FrbAutomationHistoryEntry {
+    fn from(e: crate::automation::event_log::AutomationEvent) -> Self {
+        Self {
+            timestamp_ms: e.timestamp_ms as i64,
+            kind: e.kind.into(),
+            bytes_saved: e.bytes_saved(),
+            game_path: e.game_path.to_string_lossy().into_owned(),
+            game_name: e.game_name,
+            original_bytes: e.original_bytes,
+            compressed_bytes: e.compressed_bytes,
+            duration_ms: e.duration_ms,
+            message: e.message,
+        }
+    }
+}
+
+/// Optional constraints for `get_automation_history`. Empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct FrbAutomationHistoryFilter {
+    pub kinds: Vec<FrbAutomationEventKind>,
+    pub path_contains: Option<String>,
+    pub since_ms: Option<i64>,
+}
+
+impl From<FrbAutomationHistoryFilter> for crate::automation::event_log::AutomationEventFilter {
+    fn from(f: FrbAutomationHistoryFilter) -> Self {
+        Self {
+            kinds: f.kinds.into_iter().map(Into::into).collect(),
+            path_contains: f.path_contains,
+            since_ms: f.since_ms.map(|ms| ms.max(0) as u64),
+        }
+    }
+}
+
+// ── Notifications ────────────────────────────────────────────────────
+
+/// Severity of an automation notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbNotificationSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl From<crate::automation::notifications::NotificationSeverity> for FrbNotificationSeverity {
+    fn from(s: crate::automation::notifications::NotificationSeverity) -> Self {
+        match s {
+            crate::automation::notifications::NotificationSeverity::Info => Self::Info,
+            crate::automation::notifications::NotificationSeverity::Success => Self::Success,
+            crate::automation::notifications::NotificationSeverity::Warning => Self::Warning,
+            crate::automation::notifications::NotificationSeverity::Error => Self::Error,
+        }
+    }
+}
+
+/// Follow-up action the UI can offer alongside a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbSuggestedAction {
+    None,
+    ViewHistory,
+    CloseGame,
+    RetryManually,
+}
+
+impl From<crate::automation::notifications::SuggestedAction> for FrbSuggestedAction {
+    fn from(a: crate::automation::notifications::SuggestedAction) -> Self {
+        match a {
+            crate::automation::notifications::SuggestedAction::None => Self::None,
+            crate::automation::notifications::SuggestedAction::ViewHistory => Self::ViewHistory,
+            crate::automation::notifications::SuggestedAction::CloseGame => Self::CloseGame,
+            crate::automation::notifications::SuggestedAction::RetryManually => Self::RetryManually,
+        }
+    }
+}
+
+/// Event that produced an automation notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbNotificationKind {
+    JobCompleted,
+    JobFailed,
+    QueueDrained,
+    CrashRollback,
+    JobSimulated,
+}
+
+impl From<crate::automation::notifications::NotificationKind> for FrbNotificationKind {
+    fn from(k: crate::automation::notifications::NotificationKind) -> Self {
+        match k {
+            crate::automation::notifications::NotificationKind::JobCompleted => Self::JobCompleted,
+            crate::automation::notifications::NotificationKind::JobFailed => Self::JobFailed,
+            crate::automation::notifications::NotificationKind::QueueDrained => Self::QueueDrained,
+            crate::automation::notifications::NotificationKind::CrashRollback => {
+                Self::CrashRollback
+            }
+            crate::automation::notifications::NotificationKind::JobSimulated => Self::JobSimulated,
+        }
+    }
+}
+
+/// Toast-ready automation notification sent to Flutter via stream.
+#[derive(Debug, Clone)]
+pub struct FrbAutomationNotification {
+    pub kind: FrbNotificationKind,
+    pub severity: FrbNotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub suggested_action: FrbSuggestedAction,
+    pub game_path: Option<String>,
+    pub bytes_saved: Option<u64>,
+    pub timestamp_ms: i64,
+}
+
+impl From<crate::automation::notifications::AutomationNotification> for FrbAutomationNotification {
+    fn from(n: crate::automation::notifications::AutomationNotification) -> Self {
+        Self {
+            kind: n.kind.into(),
+            severity: n.severity.into(),
+            title: n.title,
+            body: n.body,
+            suggested_action: n.suggested_action.into(),
+            game_path: n.game_path.map(|p| p.to_string_lossy().into_owned()),
+            bytes_saved: n.bytes_saved,
+            timestamp_ms: n.timestamp_ms as i64,
+        }
+    }
+}
+
+/// Combined progress of the automation jobs queued since the queue was
+/// last empty, e.g. "3 of 7 games, 41% total".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrbAutomationOverallProgress {
+    pub games_total: u32,
+    /// Completed, failed or skipped.
+    pub games_finished: u32,
+    /// Sizes from the discovery cache, or the engine's total once a job
+    /// runs; games with unknown size count as 0 until then.
+    pub bytes_total: u64,
+    pub bytes_processed: u64,
+    /// Names of the games compressing right now.
+    pub current_games: Vec<String>,
+    pub is_complete: bool,
+    /// Expected time to work through the remaining bytes, e.g. "~2h 15m".
+    /// Games with unknown size are not counted.
+    pub estimated_remaining_ms: u64,
+}
+
+/// Automation counters since launch and the scheduler's current gauges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrbAutomationMetrics {
+    pub jobs_completed: u64,
+    pub jobs_failed: u64,
+    pub jobs_skipped: u64,
+    /// Bytes freed by automation runs.
+    pub bytes_saved: u64,
+    /// Jobs cancelled because a game launched or the user stayed active.
+    pub activity_cancellations: u64,
+    pub queue_depth: u32,
+    pub running: bool,
+    /// `Idle` while the service is stopped.
+    pub scheduler_state: FrbSchedulerState,
+}
+
+impl From<crate::automation::metrics::AutomationMetrics> for FrbAutomationMetrics {
+    fn from(m: crate::automation::metrics::AutomationMetrics) -> Self {
+        Self {
+            jobs_completed: m.jobs_completed,
+            jobs_failed: m.jobs_failed,
+            jobs_skipped: m.jobs_skipped,
+            bytes_saved: m.bytes_saved,
+            activity_cancellations: m.activity_cancellations,
+            queue_depth: m.queue_depth.min(u64::from(u32::MAX)) as u32,
+            running: m.state.is_some(),
+            scheduler_state: m.state.map_or(FrbSchedulerState::Idle, Into::into),
+        }
+    }
+}
+
+// ── Shutdown ─────────────────────────────────────────────────────────
+
+/// How far a timed stop of the automation service had to escalate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbShutdownStage {
+    /// Running jobs were asked to finish their current files and stop.
+    SoftCancel,
+    /// Jobs were cancelled outright and their results abandoned.
+    HardCancel,
+    /// The service thread was still busy at the deadline and was left to
+    /// finish in the background.
+    Detached,
+    /// The service thread has exited.
+    Stopped,
+}
+
+/// Shutdown progress for the exit dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrbShutdownProgress {
+    pub stage: FrbShutdownStage,
+    /// Compression jobs still winding down.
+    pub jobs_remaining: u32,
+}
+
+/// Result of `stop_auto_compression_with_timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrbShutdownOutcome {
+    /// The stage in effect when the service stopped, or `Detached` when it
+    /// did not stop in time.
+    pub stopped_at: FrbShutdownStage,
+    pub elapsed_ms: u64,
 }
@@ -1,10 +1,13 @@
 //! Compression API exposed to Flutter via FRB.
 //!
-//! Uses a module-level `OnceLock<Mutex<..>>` to track the active
-//! manual compression/decompression operation so it can be cancelled from Dart.
+//! Manual compression/decompression jobs are tracked in a module-level
+//! registry keyed by job id, so Dart can cancel, pause and query them one
+//! at a time or all together. Jobs on different volumes run concurrently.
 
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 use std::time::Duration;
 
 use crossbeam_channel::RecvTimeoutError;
@@ -12,47 +15,156 @@ use flutter_rust_bridge::frb;
 use sysinfo::System;
 
 use super::types::{
-    FrbCompressionAlgorithm, FrbCompressionError, FrbCompressionEstimate, FrbCompressionProgress,
-    FrbCompressionStats, FrbEstimateContext,
+    FrbBenchmarkReport, FrbCompressionAlgorithm, FrbCompressionError, FrbCompressionEstimate,
+    FrbCompressionHistoryEntry, FrbCompressionJob, FrbCompressionJobKind, FrbCompressionProgress,
+    FrbCompressionStats, FrbDecompressionStats, FrbDirectStorageConfidence, FrbEstimateContext,
+    FrbHistoryFilter, FrbHistoryPruneResult, FrbHistoryRetention, FrbInterruptedOperation,
+    FrbKnownGamesDatabase, FrbSavingsBucket, FrbSavingsPoint, FrbSavingsSummary,
 };
 use crate::compression::algorithm::CompressionAlgorithm;
 use crate::compression::engine::{
     CancellationToken, CompressionEngine, CompressionProgressHandle, EstimateGameContext,
+    PauseHandle,
 };
 use crate::compression::error::CompressionError;
+use crate::compression::external;
 use crate::compression::history::{
-    persist_if_dirty, record_compression, CompressionHistoryEntry, EstimateSnapshot,
+    analytics, decompression, flush_now as flush_history_now,
+    prune_history as prune_compression_history, record_compression, retention, schedule_persist,
+    CompressionHistoryEntry, EstimateSnapshot,
 };
+use decompression::DecompressionHistoryEntry;
 
+use crate::compression::op_journal;
 use crate::compression::thread_policy::compute_thread_policy;
+use crate::compression::user_data;
+use crate::discovery::storage::volume_key_for_path;
+use crate::discovery::utils::refresh_cached_stats;
 use crate::frb_generated::StreamSink;
 use crate::progress::tracker::CompressionProgress;
-use crate::safety::directstorage::is_directstorage_game;
+use crate::safety::directstorage::{detect_directstorage, is_directstorage_game};
+use crate::safety::directstorage_cache;
+use crate::safety::known_games::{self, KnownGamesError};
 use crate::safety::process::ProcessChecker;
+use crate::utils::normalize_path_key;
 
-// ── Active manual-operation tracking ──────────────────────────────────
+const KNOWN_GAMES_DATABASE_ENDPOINT: &str =
+    "https://github.com/g1mliii/compact-games/releases/latest/download/directstorage_games.json";
+const KNOWN_GAMES_BUNDLE_ENDPOINT: &str =
+    "https://github.com/g1mliii/compact-games/releases/latest/download/directstorage_games.bundle.json";
+const KNOWN_GAMES_USER_AGENT: &str = "CompactGames-DirectStorage-List/1";
+const MAX_KNOWN_GAMES_BODY_BYTES: u64 = 1024 * 1024;
 
-struct ActiveCompression {
-    cancel_token: CancellationToken,
+#[derive(serde::Deserialize)]
+struct KnownGamesBundle {
+    sha256: String,
 }
 
-static ACTIVE: OnceLock<Mutex<Option<ActiveCompression>>> = OnceLock::new();
-static ACTIVE_PROGRESS: OnceLock<Mutex<Option<CompressionProgress>>> = OnceLock::new();
+// ── Manual job registry ───────────────────────────────────────────────
 
-fn active_lock() -> &'static Mutex<Option<ActiveCompression>> {
-    ACTIVE.get_or_init(|| Mutex::new(None))
-}
+/// Jobs on one volume compete for the same disk, so by default a volume
+/// runs one manual job at a time.
+const DEFAULT_MAX_JOBS_PER_VOLUME: usize = 1;
+/// Upper bound on manual jobs across all volumes, and on the per-volume
+/// limit.
+const MAX_MANUAL_JOBS: usize = 8;
 
-fn active_progress_lock() -> &'static Mutex<Option<CompressionProgress>> {
-    ACTIVE_PROGRESS.get_or_init(|| Mutex::new(None))
+struct ManualJob {
+    id: u64,
+    kind: FrbCompressionJobKind,
+    path: PathBuf,
+    volume: String,
+    cancel_token: CancellationToken,
+    pause: PauseHandle,
+    progress: Option<CompressionProgress>,
 }
 
-fn set_active_progress(progress: Option<CompressionProgress>) {
-    let mut guard = active_progress_lock().lock().unwrap_or_else(|e| {
-        log::warn!("ACTIVE progress lock was poisoned; recovering");
+/// Running manual jobs in start order.
+static JOBS: Mutex<Vec<ManualJob>> = Mutex::new(Vec::new());
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+static MAX_JOBS_PER_VOLUME: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_JOBS_PER_VOLUME);
+
+fn jobs_lock() -> MutexGuard<'static, Vec<ManualJob>> {
+    JOBS.lock().unwrap_or_else(|e| {
+        log::warn!("Manual job registry lock was poisoned; recovering");
         e.into_inner()
+    })
+}
+
+/// Add a job for `engine` working on `path`. Fails when the path already
+/// has a job or its volume or the registry is at its limit.
+fn register_job(
+    engine: &CompressionEngine,
+    kind: FrbCompressionJobKind,
+    path: &Path,
+) -> Result<u64, FrbCompressionError> {
+    let path_key = normalize_path_key(path);
+    let volume = volume_key_for_path(path);
+    let mut jobs = jobs_lock();
+    if jobs
+        .iter()
+        .any(|job| normalize_path_key(&job.path) == path_key)
+    {
+        return Err(FrbCompressionError::IoError {
+            message: format!(
+                "{} is already being compressed or decompressed",
+                path.display()
+            ),
+        });
+    }
+    let per_volume_limit = MAX_JOBS_PER_VOLUME.load(Ordering::Relaxed);
+    if jobs.iter().filter(|job| job.volume == volume).count() >= per_volume_limit {
+        return Err(FrbCompressionError::IoError {
+            message: format!(
+                "A compression or decompression operation is already in progress on {volume}"
+            ),
+        });
+    }
+    if jobs.len() >= MAX_MANUAL_JOBS {
+        return Err(FrbCompressionError::IoError {
+            message: "Too many compression or decompression operations are in progress".into(),
+        });
+    }
+
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    jobs.push(ManualJob {
+        id,
+        kind,
+        path: path.to_path_buf(),
+        volume,
+        cancel_token: engine.cancel_token(),
+        pause: engine.pause_handle(),
+        progress: None,
     });
-    *guard = progress;
+    Ok(id)
+}
+
+fn unregister_job(id: u64) {
+    jobs_lock().retain(|job| job.id != id);
+}
+
+fn set_job_progress(id: u64, progress: CompressionProgress) {
+    if let Some(job) = jobs_lock().iter_mut().find(|job| job.id == id) {
+        job.progress = Some(progress);
+    }
+}
+
+/// Apply `f` to the job `id`. Returns `false` when it is not running.
+fn with_job(id: u64, f: impl FnOnce(&ManualJob)) -> bool {
+    match jobs_lock().iter().find(|job| job.id == id) {
+        Some(job) => {
+            f(job);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Apply `f` to every job. Returns `false` when none is running.
+fn with_all_jobs(f: impl Fn(&ManualJob)) -> bool {
+    let jobs = jobs_lock();
+    jobs.iter().for_each(f);
+    !jobs.is_empty()
 }
 
 fn cancelled_stats() -> FrbCompressionStats {
@@ -61,60 +173,51 @@ fn cancelled_stats() -> FrbCompressionStats {
         compressed_bytes: 0,
         files_processed: 0,
         files_skipped: 0,
+        files_skipped_cloud: 0,
+        files_skipped_permission: 0,
+        files_skipped_encrypted: 0,
+        files_skipped_sparse: 0,
+        skipped_encrypted_paths: Vec::new(),
+        skipped_sparse_paths: Vec::new(),
+        alternate_stream_bytes: 0,
+        has_significant_alternate_streams: false,
+        files_already_compressed: 0,
+        bytes_already_compressed: 0,
+        bytes_saved_this_run: 0,
+        was_cancelled: true,
         duration_ms: 0,
     }
 }
 
-fn install_active_operation(cancel_token: &CancellationToken) -> Result<(), FrbCompressionError> {
-    let mut guard = active_lock().lock().unwrap_or_else(|e| {
-        log::warn!("ACTIVE manual-operation lock was poisoned; recovering");
-        e.into_inner()
-    });
-    if guard.is_some() {
-        return Err(FrbCompressionError::IoError {
-            message: "A compression or decompression operation is already in progress".into(),
-        });
+fn cancelled_decompression_stats() -> FrbDecompressionStats {
+    FrbDecompressionStats {
+        files_decompressed: 0,
+        bytes_restored_logical: 0,
+        bytes_grown_physical: 0,
+        was_cancelled: true,
+        duration_ms: 0,
     }
-    *guard = Some(ActiveCompression {
-        cancel_token: cancel_token.clone(),
-    });
-    Ok(())
-}
-
-fn clear_active_operation() {
-    let mut guard = active_lock().lock().unwrap_or_else(|e| {
-        log::warn!("ACTIVE manual-operation lock was poisoned during cleanup; recovering");
-        e.into_inner()
-    });
-    *guard = None;
-}
-
-fn rollback_active_operation() {
-    let mut guard = active_lock().lock().unwrap_or_else(|poisoned| {
-        log::warn!("ACTIVE manual-operation lock was poisoned during rollback; recovering");
-        poisoned.into_inner()
-    });
-    *guard = None;
-    drop(guard);
-    set_active_progress(None);
 }
 
-fn drain_progress_stream(
-    handle: CompressionProgressHandle,
+/// Forward engine progress to `on_progress` until the operation ends.
+/// `on_progress` returning `false` means the listener went away, which
+/// cancels the operation.
+fn drain_progress_stream<T>(
+    job_id: u64,
+    handle: CompressionProgressHandle<T>,
     cancel_token: &CancellationToken,
-    sink: StreamSink<FrbCompressionProgress>,
-) -> Option<Result<crate::compression::engine::CompressionStats, CompressionError>> {
+    on_progress: &mut dyn FnMut(&CompressionProgress) -> bool,
+) -> Option<Result<T, CompressionError>> {
     let CompressionProgressHandle { progress, result } = handle;
-    let mut sink_is_open = true;
+    let mut listener_is_open = true;
 
     loop {
         match progress.recv_timeout(Duration::from_millis(200)) {
             Ok(progress) => {
-                set_active_progress(Some(progress.clone()));
-                let frb_progress: FrbCompressionProgress = progress.into();
-                if sink_is_open && sink.add(frb_progress).is_err() {
+                set_job_progress(job_id, progress.clone());
+                if listener_is_open && !on_progress(&progress) {
                     cancel_token.cancel();
-                    sink_is_open = false;
+                    listener_is_open = false;
                 }
             }
             Err(RecvTimeoutError::Timeout) => continue,
@@ -125,6 +228,32 @@ fn drain_progress_stream(
     result.recv().ok()
 }
 
+/// Run `start` as a manual job on `path`, forwarding its progress until
+/// it ends. Fails without starting if the registry refuses the job.
+fn run_manual_job<T>(
+    engine: &CompressionEngine,
+    kind: FrbCompressionJobKind,
+    path: &Path,
+    start: impl FnOnce() -> Result<CompressionProgressHandle<T>, CompressionError>,
+    on_progress: &mut dyn FnMut(&CompressionProgress) -> bool,
+) -> Result<Option<Result<T, CompressionError>>, FrbCompressionError> {
+    let cancel_token = engine.cancel_token();
+    let job_id = register_job(engine, kind, path)?;
+
+    let handle = match start() {
+        Ok(handle) => handle,
+        Err(e) => {
+            unregister_job(job_id);
+            return Err(e.into());
+        }
+    };
+
+    let result = drain_progress_stream(job_id, handle, &cancel_token, on_progress);
+
+    unregister_job(job_id);
+    Ok(result)
+}
+
 // ── Public API ────────────────────────────────────────────────────────
 
 /// Start compression with progress streaming.
@@ -132,16 +261,46 @@ fn drain_progress_stream(
 /// FRB translates `StreamSink<T>` in Rust to `Stream<T>` on the Dart side.
 /// The function blocks on a thread-pool thread, forwarding crossbeam
 /// channel messages to the sink. Dart listens via `await for`.
+///
+/// `max_bytes_per_sec` caps throughput for this job; `None` runs
+/// unthrottled.
 pub fn compress_game(
     game_path: String,
     game_name: String,
     algorithm: FrbCompressionAlgorithm,
     allow_directstorage_override: bool,
     io_parallelism_override: Option<u64>,
+    max_bytes_per_sec: Option<u64>,
     sink: StreamSink<FrbCompressionProgress>,
+) -> Result<FrbCompressionStats, FrbCompressionError> {
+    compress_game_with_progress(
+        game_path,
+        game_name,
+        algorithm,
+        allow_directstorage_override,
+        io_parallelism_override,
+        max_bytes_per_sec,
+        &mut |progress| sink.add(progress.clone().into()).is_ok(),
+    )
+}
+
+/// [`compress_game`] for Rust callers such as the IPC server.
+pub(crate) fn compress_game_with_progress(
+    game_path: String,
+    game_name: String,
+    algorithm: FrbCompressionAlgorithm,
+    allow_directstorage_override: bool,
+    io_parallelism_override: Option<u64>,
+    max_bytes_per_sec: Option<u64>,
+    on_progress: &mut dyn FnMut(&CompressionProgress) -> bool,
 ) -> Result<FrbCompressionStats, FrbCompressionError> {
     let algo: CompressionAlgorithm = algorithm.into();
     let path = PathBuf::from(&game_path);
+    // The user asked for this one; the UI warned them through
+    // `find_exclusion` before starting.
+    if let Some(rule) = crate::settings::Exclusions::load().matching_install(&path) {
+        log::warn!("Compressing {game_path} despite exclusion {rule:?}");
+    }
 
     // User-initiated compression: full parallelism (is_background = false)
     let policy = compute_thread_policy(
@@ -155,7 +314,9 @@ pub fn compress_game(
         .with_safety(crate::compression::engine::SafetyConfig {
             process_checker: Arc::new(ProcessChecker::new()),
         })
-        .with_directstorage_override(allow_directstorage_override);
+        .with_directstorage_override(allow_directstorage_override)
+        .with_backup_privilege(crate::settings::load().compress_admin_only_files)
+        .with_rate_limit(max_bytes_per_sec.filter(|&rate| rate > 0));
     let cancel_token = engine.cancel_token();
     let file_manifest = engine.build_file_manifest(&path)?;
 
@@ -176,25 +337,19 @@ pub fn compress_game(
         Err(_) => None,
     };
 
-    install_active_operation(&cancel_token)?;
-    set_active_progress(None);
-
-    let handle = match engine.compress_folder_with_progress_with_manifest(
+    let result = run_manual_job(
+        &engine,
+        FrbCompressionJobKind::Compress,
         &path,
-        Arc::from(game_name.clone()),
-        file_manifest,
-    ) {
-        Ok(handle) => handle,
-        Err(e) => {
-            rollback_active_operation();
-            return Err(e.into());
-        }
-    };
-
-    let result = drain_progress_stream(handle, &cancel_token, sink);
-
-    clear_active_operation();
-    set_active_progress(None);
+        || {
+            engine.compress_folder_with_progress_with_manifest(
+                &path,
+                Arc::from(game_name.clone()),
+                file_manifest,
+            )
+        },
+        on_progress,
+    )?;
 
     match result {
         Some(Ok(stats)) => {
@@ -205,7 +360,7 @@ pub fn compress_game(
                 0.0
             };
             log::info!(
-                "[compression][summary] game=\"{}\" algo={} processed={} skipped={} original={} compressed={} saved={} ({:.2}%)",
+                "[compression][summary] game=\"{}\" algo={} processed={} skipped={} original={} compressed={} saved={} ({:.2}%) cancelled={}",
                 game_path,
                 algo,
                 stats.files_processed,
@@ -213,9 +368,13 @@ pub fn compress_game(
                 stats.original_bytes,
                 stats.compressed_bytes,
                 saved,
-                saved_ratio
+                saved_ratio,
+                stats.was_cancelled
             );
 
+            // A partial run saved less than the whole-folder estimate, so
+            // it must not teach the adaptive estimator anything.
+            let estimate_snapshot = estimate_snapshot.filter(|_| !stats.was_cancelled);
             record_compression(CompressionHistoryEntry::from_compression_stats(
                 game_path.clone(),
                 game_name.clone(),
@@ -223,6 +382,7 @@ pub fn compress_game(
                 &stats,
                 algo,
             ));
+            refresh_cached_stats(&path);
 
             Ok(stats.into())
         }
@@ -235,36 +395,209 @@ pub fn compress_game(
     }
 }
 
-/// Cancel the active manual compression/decompression job.
+/// Compress any folder, such as an engine SDK or a modding tool, with
+/// progress streaming. The run is recorded in history under `name`, or
+/// under the folder name when `None`. No game-specific overrides apply.
+pub fn compress_path(
+    path: String,
+    name: Option<String>,
+    algorithm: FrbCompressionAlgorithm,
+    sink: StreamSink<FrbCompressionProgress>,
+) -> Result<FrbCompressionStats, FrbCompressionError> {
+    let name = name.unwrap_or_else(|| folder_display_name(Path::new(&path)));
+    compress_game_with_progress(path, name, algorithm, false, None, None, &mut |progress| {
+        sink.add(progress.clone().into()).is_ok()
+    })
+}
+
+/// Estimate savings for any folder before [`compress_path`].
+pub fn estimate_path_savings(
+    path: String,
+    algorithm: FrbCompressionAlgorithm,
+) -> Result<FrbCompressionEstimate, FrbCompressionError> {
+    let engine = CompressionEngine::new(algorithm.into());
+    let estimate = engine.estimate_folder_savings(Path::new(&path))?;
+    Ok(estimate.into())
+}
+
+fn folder_display_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy())
+        .into_owned()
+}
+
+/// Compress an approved save or config folder as the active manual
+/// operation. Callers check [`crate::compression::user_data`]'s safeguards
+/// first; savings are recorded there instead of in the game history.
+pub(crate) fn compress_user_data_with_progress(
+    path: &Path,
+    on_progress: &mut dyn FnMut(&CompressionProgress) -> bool,
+) -> Result<FrbCompressionStats, FrbCompressionError> {
+    let policy = compute_thread_policy(path, false, current_cpu_usage_percent(), None);
+    let engine = CompressionEngine::new(user_data::USER_DATA_ALGORITHM)
+        .with_thread_policy(policy)
+        .with_safety(crate::compression::engine::SafetyConfig {
+            process_checker: Arc::new(ProcessChecker::new()),
+        });
+    let cancel_token = engine.cancel_token();
+    let label: Arc<str> = Arc::from(folder_display_name(path));
+
+    let result = run_manual_job(
+        &engine,
+        FrbCompressionJobKind::Compress,
+        path,
+        || engine.compress_folder_with_progress(path, label),
+        on_progress,
+    )?;
+
+    match result {
+        Some(Ok(stats)) => {
+            log::info!(
+                "[compression][user-data] path=\"{}\" processed={} original={} compressed={} cancelled={}",
+                path.display(),
+                stats.files_processed,
+                stats.original_bytes,
+                stats.compressed_bytes,
+                stats.was_cancelled
+            );
+            user_data::record(path, &stats);
+            Ok(stats.into())
+        }
+        Some(Err(CompressionError::Cancelled)) => Ok(cancelled_stats()),
+        Some(Err(e)) => Err(e.into()),
+        None if cancel_token.is_cancelled() => Ok(cancelled_stats()),
+        None => Err(FrbCompressionError::IoError {
+            message: "Compression ended without a result".into(),
+        }),
+    }
+}
+
+/// Cancel every manual job.
 #[frb(sync)]
 pub fn cancel_compression() {
-    let guard = active_lock().lock().unwrap_or_else(|e| {
-        log::warn!("ACTIVE manual-operation lock was poisoned during cancel; recovering");
-        e.into_inner()
-    });
-    if let Some(active) = guard.as_ref() {
-        active.cancel_token.cancel();
-    }
+    with_all_jobs(|job| job.cancel_token.cancel());
+}
+
+/// Cancel one manual job. Returns `false` when it is not running.
+#[frb(sync)]
+pub fn cancel_compression_job(job_id: u64) -> bool {
+    with_job(job_id, |job| job.cancel_token.cancel())
+}
+
+/// Stop every manual compression after the files in flight finish.
+/// Each job still completes with the stats of what was compressed and
+/// `was_cancelled` set, and the partial run is recorded in history.
+#[frb(sync)]
+pub fn cancel_compression_gracefully() {
+    with_all_jobs(|job| job.cancel_token.cancel_gracefully());
+}
+
+/// Pause every manual compression/decompression job once the files in
+/// flight finish. Returns `false` when no job is running.
+#[frb(sync)]
+pub fn pause_compression() -> bool {
+    with_all_jobs(|job| job.pause.pause())
+}
+
+/// Resume paused manual jobs. Returns `false` when no job is running.
+#[frb(sync)]
+pub fn resume_compression() -> bool {
+    with_all_jobs(|job| job.pause.resume())
 }
 
-/// Return the latest known progress for the active manual operation.
+/// Return the latest known progress of the most recently started manual
+/// job that has reported any.
 #[frb(sync)]
 pub fn get_compression_progress() -> Option<FrbCompressionProgress> {
-    let guard = active_progress_lock().lock().unwrap_or_else(|e| {
-        log::warn!("ACTIVE progress lock was poisoned during read; recovering");
-        e.into_inner()
-    });
     // Convert to FRB type only when requested (cheap Arc<str> clone on CompressionProgress)
-    guard.clone().map(Into::into)
+    active_compression_progress().map(Into::into)
+}
+
+/// [`get_compression_progress`] for Rust callers such as the IPC server.
+pub(crate) fn active_compression_progress() -> Option<CompressionProgress> {
+    jobs_lock()
+        .iter()
+        .rev()
+        .find_map(|job| job.progress.clone())
+}
+
+/// Return the latest known progress of one manual job.
+#[frb(sync)]
+pub fn get_compression_job_progress(job_id: u64) -> Option<FrbCompressionProgress> {
+    jobs_lock()
+        .iter()
+        .find(|job| job.id == job_id)
+        .and_then(|job| job.progress.clone())
+        .map(Into::into)
+}
+
+/// Running manual jobs in start order. A job's id is what
+/// [`cancel_compression_job`] and [`get_compression_job_progress`] take.
+#[frb(sync)]
+pub fn get_compression_jobs() -> Vec<FrbCompressionJob> {
+    manual_jobs()
+        .into_iter()
+        .map(|job| FrbCompressionJob {
+            job_id: job.id,
+            game_path: job.path.to_string_lossy().into_owned(),
+            kind: job.kind,
+            progress: job.progress.map(Into::into),
+        })
+        .collect()
+}
+
+/// A running manual job, for Rust callers such as the IPC server.
+pub(crate) struct ManualJobInfo {
+    pub id: u64,
+    pub kind: FrbCompressionJobKind,
+    pub path: PathBuf,
+    pub progress: Option<CompressionProgress>,
+}
+
+/// [`get_compression_jobs`] for Rust callers such as the IPC server.
+pub(crate) fn manual_jobs() -> Vec<ManualJobInfo> {
+    jobs_lock()
+        .iter()
+        .map(|job| ManualJobInfo {
+            id: job.id,
+            kind: job.kind,
+            path: job.path.clone(),
+            progress: job.progress.clone(),
+        })
+        .collect()
 }
 
-/// Decompress a game folder with progress streaming.
+/// Set how many manual jobs may run at once on one volume, between 1 and
+/// 8. Running jobs are not affected.
+#[frb(sync)]
+pub fn set_max_compression_jobs_per_volume(limit: u32) {
+    let limit = (limit as usize).clamp(1, MAX_MANUAL_JOBS);
+    MAX_JOBS_PER_VOLUME.store(limit, Ordering::Relaxed);
+}
+
+/// Decompress a game folder with progress streaming. Returns how much the
+/// game grew on disk.
 pub fn decompress_game(
     game_path: String,
     game_name: String,
     io_parallelism_override: Option<u64>,
     sink: StreamSink<FrbCompressionProgress>,
-) -> Result<(), FrbCompressionError> {
+) -> Result<FrbDecompressionStats, FrbCompressionError> {
+    decompress_game_with_progress(
+        game_path,
+        game_name,
+        io_parallelism_override,
+        &mut |progress| sink.add(progress.clone().into()).is_ok(),
+    )
+}
+
+/// [`decompress_game`] for Rust callers such as the IPC server.
+pub(crate) fn decompress_game_with_progress(
+    game_path: String,
+    game_name: String,
+    io_parallelism_override: Option<u64>,
+    on_progress: &mut dyn FnMut(&CompressionProgress) -> bool,
+) -> Result<FrbDecompressionStats, FrbCompressionError> {
     let path = PathBuf::from(&game_path);
     // User-initiated decompression: full parallelism
     let policy = compute_thread_policy(
@@ -280,44 +613,146 @@ pub fn decompress_game(
         });
     let cancel_token = engine.cancel_token();
 
-    install_active_operation(&cancel_token)?;
-    set_active_progress(None);
-
-    let handle = match engine.decompress_folder_with_progress(&path, Arc::from(game_name)) {
-        Ok(handle) => handle,
-        Err(e) => {
-            rollback_active_operation();
-            return Err(e.into());
-        }
-    };
-
-    let result = drain_progress_stream(handle, &cancel_token, sink);
-
-    clear_active_operation();
-    set_active_progress(None);
+    let result = run_manual_job(
+        &engine,
+        FrbCompressionJobKind::Decompress,
+        &path,
+        || engine.decompress_folder_with_progress(&path, Arc::from(game_name.clone())),
+        on_progress,
+    )?;
 
     match result {
         Some(Ok(stats)) => {
-            let restored = stats.original_bytes.saturating_sub(stats.compressed_bytes);
             log::info!(
-                "[decompression][summary] game=\"{}\" processed={} original={} compressed={} restored={}",
+                "[decompression][summary] game=\"{}\" decompressed={} restored={} grown={}",
                 game_path,
-                stats.files_processed,
-                stats.original_bytes,
-                stats.compressed_bytes,
-                restored
+                stats.files_decompressed,
+                stats.bytes_restored_logical,
+                stats.bytes_grown_physical
             );
-            Ok(())
+            user_data::forget(&path);
+            crate::automation::library_reconcile::record_decompression(&path);
+            decompression::record_decompression(DecompressionHistoryEntry {
+                game_path,
+                game_name,
+                timestamp_ms: crate::utils::unix_now_ms(),
+                stats,
+            });
+            refresh_cached_stats(&path);
+            Ok(stats.into())
         }
-        Some(Err(CompressionError::Cancelled)) => Ok(()),
+        Some(Err(CompressionError::Cancelled)) => Ok(cancelled_decompression_stats()),
         Some(Err(e)) => Err(e.into()),
-        None if cancel_token.is_cancelled() => Ok(()),
+        None if cancel_token.is_cancelled() => Ok(cancelled_decompression_stats()),
         None => Err(FrbCompressionError::IoError {
             message: "Decompression ended without a result".into(),
         }),
     }
 }
 
+// ── Queued decompression ──────────────────────────────────────────────
+
+/// How often a queued decompression checks whether its volume has room.
+const DECOMPRESSION_QUEUE_RETRY: Duration = Duration::from_secs(5);
+
+type DecompressionDone = Box<dyn FnOnce(Result<FrbDecompressionStats, FrbCompressionError>) + Send>;
+
+struct QueuedDecompression {
+    path: PathBuf,
+    on_done: DecompressionDone,
+}
+
+/// Decompressions asked for by monitors rather than the user, oldest
+/// first.
+static DECOMPRESSION_QUEUE: Mutex<VecDeque<QueuedDecompression>> = Mutex::new(VecDeque::new());
+/// Guarded by the queue lock, like the sink-driven monitor threads.
+static DECOMPRESSION_QUEUE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn decompression_queue_lock() -> MutexGuard<'static, VecDeque<QueuedDecompression>> {
+    DECOMPRESSION_QUEUE.lock().unwrap_or_else(|e| {
+        log::warn!("Decompression queue lock was poisoned; recovering");
+        e.into_inner()
+    })
+}
+
+/// Decompress the game at `path` as a manual job on a background thread,
+/// after any decompression queued before it, and hand the outcome to
+/// `on_done` there. Where the registry would refuse the job because its
+/// volume is busy, it waits instead. Returns `false` when `path` is
+/// already queued.
+pub(crate) fn queue_decompression(
+    path: PathBuf,
+    on_done: impl FnOnce(Result<FrbDecompressionStats, FrbCompressionError>) + Send + 'static,
+) -> bool {
+    let path_key = normalize_path_key(&path);
+    let mut queue = decompression_queue_lock();
+    if queue
+        .iter()
+        .any(|queued| normalize_path_key(&queued.path) == path_key)
+    {
+        return false;
+    }
+    log::info!("Queued decompression of {}", path.display());
+    queue.push_back(QueuedDecompression {
+        path,
+        on_done: Box::new(on_done),
+    });
+    if !DECOMPRESSION_QUEUE_ACTIVE.swap(true, Ordering::AcqRel) {
+        if let Err(e) = std::thread::Builder::new()
+            .name("compact-games-decompression-queue".to_string())
+            .spawn(decompression_queue_loop)
+        {
+            log::warn!("Failed to spawn decompression queue: {e}");
+            DECOMPRESSION_QUEUE_ACTIVE.store(false, Ordering::Release);
+        }
+    }
+    true
+}
+
+fn decompression_queue_loop() {
+    loop {
+        let next = {
+            let mut queue = decompression_queue_lock();
+            match queue.pop_front() {
+                Some(next) => next,
+                None => {
+                    DECOMPRESSION_QUEUE_ACTIVE.store(false, Ordering::Release);
+                    return;
+                }
+            }
+        };
+
+        while !has_room_for_job(&next.path) {
+            std::thread::sleep(DECOMPRESSION_QUEUE_RETRY);
+        }
+        let game_name = next
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| next.path.to_string_lossy().into_owned());
+        let result = decompress_game_with_progress(
+            next.path.to_string_lossy().into_owned(),
+            game_name,
+            None,
+            &mut |_| true,
+        );
+        (next.on_done)(result);
+    }
+}
+
+/// Whether [`register_job`] would accept a job on `path` right now.
+fn has_room_for_job(path: &Path) -> bool {
+    let path_key = normalize_path_key(path);
+    let volume = volume_key_for_path(path);
+    let jobs = jobs_lock();
+    let per_volume_limit = MAX_JOBS_PER_VOLUME.load(Ordering::Relaxed);
+    jobs.len() < MAX_MANUAL_JOBS
+        && jobs.iter().filter(|job| job.volume == volume).count() < per_volume_limit
+        && !jobs
+            .iter()
+            .any(|job| normalize_path_key(&job.path) == path_key)
+}
+
 /// Get the compression ratio for a folder.
 pub fn get_compression_ratio(folder_path: String) -> Result<f64, FrbCompressionError> {
     let path = PathBuf::from(&folder_path);
@@ -344,16 +779,194 @@ pub fn estimate_compression_savings(
     Ok(estimate.into())
 }
 
+/// Estimate savings by compressing temp copies of a sample of the game's
+/// files. Takes seconds to minutes; `cancel_compression` stops it.
+pub fn estimate_compression_savings_sampled(
+    game_path: String,
+    algorithm: FrbCompressionAlgorithm,
+) -> Result<FrbCompressionEstimate, FrbCompressionError> {
+    let path = PathBuf::from(&game_path);
+    let engine = CompressionEngine::new(algorithm.into());
+    let job_id = register_job(&engine, FrbCompressionJobKind::Sample, &path)?;
+    let result = engine.estimate_folder_savings_sampled(&path);
+    unregister_job(job_id);
+    Ok(result?.into())
+}
+
+/// Compress copies of a sample of the game with each algorithm and compare
+/// savings against speed, with a recommendation. Takes seconds to a
+/// minute; `cancel_compression` stops it. The game's files are not changed.
+pub fn benchmark_game(game_path: String) -> Result<FrbBenchmarkReport, FrbCompressionError> {
+    let path = PathBuf::from(&game_path);
+    let engine = CompressionEngine::new(CompressionAlgorithm::default());
+    let job_id = register_job(&engine, FrbCompressionJobKind::Sample, &path)?;
+    let result = engine.benchmark_folder(&path);
+    unregister_job(job_id);
+    Ok(result?.into())
+}
+
+/// Compress and decompress runs left unfinished by an earlier session,
+/// oldest first. Resume by compressing the game again or roll back by
+/// decompressing it, then call [`dismiss_interrupted_operation`].
+pub fn get_interrupted_operations() -> Vec<FrbInterruptedOperation> {
+    op_journal::interrupted_operations()
+        .into_iter()
+        .map(FrbInterruptedOperation::from)
+        .collect()
+}
+
+/// Forget an interrupted operation. Returns `false` if it was not found.
+#[frb(sync)]
+pub fn dismiss_interrupted_operation(id: String) -> bool {
+    op_journal::dismiss(&id)
+}
+
 /// Check if a game uses DirectStorage.
 #[frb(sync)]
 pub fn is_directstorage(game_path: String) -> bool {
     is_directstorage_game(Path::new(&game_path))
 }
 
-/// Persist compression history to disk.
+/// Evidence behind a DirectStorage detection, ignoring any override.
+/// `None` if the game was not detected.
+#[frb(sync)]
+pub fn get_directstorage_confidence(game_path: String) -> Option<FrbDirectStorageConfidence> {
+    detect_directstorage(Path::new(&game_path)).map(FrbDirectStorageConfidence::from)
+}
+
+/// Override DirectStorage detection for a game. `allow = true` lets a
+/// false positive be compressed; `false` keeps a game uncompressed even
+/// when nothing was detected.
+pub fn set_directstorage_override(
+    game_path: String,
+    allow: bool,
+) -> Result<(), FrbCompressionError> {
+    directstorage_cache::set_override(Path::new(&game_path), allow).map_err(|e| {
+        FrbCompressionError::IoError {
+            message: e.to_string(),
+        }
+    })
+}
+
+/// Re-read the user-editable DirectStorage games database from the config
+/// directory and merge it with the embedded list.
+pub fn reload_known_games() -> Result<FrbKnownGamesDatabase, String> {
+    known_games::reload_known_games()
+        .map(FrbKnownGamesDatabase::from)
+        .map_err(|e| e.to_string())
+}
+
+/// Download the DirectStorage games database published with the latest
+/// release and install it when it is newer than the loaded one.
+pub fn fetch_known_games_database() -> Result<FrbKnownGamesDatabase, String> {
+    let Some(bundle_body) = crate::net::fetch_text(
+        KNOWN_GAMES_BUNDLE_ENDPOINT,
+        KNOWN_GAMES_USER_AGENT,
+        MAX_KNOWN_GAMES_BODY_BYTES,
+    )?
+    else {
+        // Not published with this release; keep whatever is loaded.
+        return Ok(known_games::known_games_database().into());
+    };
+    let bundle: KnownGamesBundle = serde_json::from_str(&bundle_body)
+        .map_err(|e| format!("Invalid DirectStorage games bundle: {e}"))?;
+    let Some(body) = crate::net::fetch_text(
+        KNOWN_GAMES_DATABASE_ENDPOINT,
+        KNOWN_GAMES_USER_AGENT,
+        MAX_KNOWN_GAMES_BODY_BYTES,
+    )?
+    else {
+        return Ok(known_games::known_games_database().into());
+    };
+    crate::net::github_release_fetcher::verify_sha256(body.as_bytes(), &bundle.sha256)?;
+
+    match known_games::install_known_games_database(&body) {
+        Ok(summary) => Ok(summary.into()),
+        Err(KnownGamesError::NotNewer { .. }) => Ok(known_games::known_games_database().into()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Return a game to detected behaviour. Returns `false` when no override
+/// was set.
+pub fn clear_directstorage_override(game_path: String) -> Result<bool, FrbCompressionError> {
+    directstorage_cache::clear_override(Path::new(&game_path)).map_err(|e| {
+        FrbCompressionError::IoError {
+            message: e.to_string(),
+        }
+    })
+}
+
+/// Persist compression history to disk in the background. Returns at once;
+/// the write happens after a short debounce.
 #[frb(sync)]
 pub fn persist_compression_history() {
-    persist_if_dirty();
+    schedule_persist();
+}
+
+/// Write any pending compression history before the app exits.
+#[frb(sync)]
+pub fn flush_compression_history() {
+    flush_history_now();
+}
+
+/// Recorded compressions matching `filter`, newest first.
+pub fn get_compression_history(filter: FrbHistoryFilter) -> Vec<FrbCompressionHistoryEntry> {
+    analytics::history(&filter.into())
+        .into_iter()
+        .map(FrbCompressionHistoryEntry::from)
+        .collect()
+}
+
+/// Lifetime bytes saved, in total and per platform and algorithm.
+pub fn get_total_savings_summary() -> FrbSavingsSummary {
+    analytics::total_savings_summary().into()
+}
+
+/// Bytes saved per day, week or month, oldest first.
+pub fn get_savings_over_time(bucket: FrbSavingsBucket) -> Vec<FrbSavingsPoint> {
+    analytics::savings_over_time(bucket.into())
+        .into_iter()
+        .map(FrbSavingsPoint::from)
+        .collect()
+}
+
+/// Current history retention limits.
+#[frb(sync)]
+pub fn get_history_retention() -> FrbHistoryRetention {
+    retention::retention().into()
+}
+
+/// Persist new retention limits and apply them immediately. Limits below
+/// the minimum that adaptive estimation needs are raised; the applied
+/// limits are returned.
+pub fn set_history_retention(
+    retention: FrbHistoryRetention,
+) -> Result<FrbHistoryRetention, FrbCompressionError> {
+    let applied =
+        retention::set_retention(retention.into()).map_err(|e| FrbCompressionError::IoError {
+            message: e.to_string(),
+        })?;
+    prune_compression_history();
+    Ok(applied.into())
+}
+
+/// Compact and drop history entries outside the retention limits now
+/// instead of on the next recorded compression.
+pub fn prune_history() -> FrbHistoryPruneResult {
+    prune_compression_history().into()
+}
+
+/// Backfill history for a game compressed outside the app (for example
+/// with `compact /exe`) so it shows as compressed with the savings found
+/// on disk. Returns `None` when the game already has history or nothing
+/// in it is WOF-compressed.
+pub fn import_external_compression(
+    game_path: String,
+    game_name: String,
+) -> Option<FrbCompressionHistoryEntry> {
+    external::import_external_compression(Path::new(&game_path), &game_name)
+        .map(FrbCompressionHistoryEntry::from)
 }
 
 /// Cached CPU monitor that persists between calls so `sysinfo` can compute
@@ -397,3 +1010,41 @@ fn current_cpu_usage_percent() -> Option<f32> {
 fn io_override_to_usize(io_parallelism_override: Option<u64>) -> Option<usize> {
     crate::utils::io_parallelism_override_to_usize(io_parallelism_override)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_limits_jobs_per_volume_and_cancels_by_id() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let first_path = temp.path().join("RegistryFirst");
+        let second_path = temp.path().join("RegistrySecond");
+        let first_engine = CompressionEngine::new(CompressionAlgorithm::default());
+        let second_engine = CompressionEngine::new(CompressionAlgorithm::default());
+
+        let first = register_job(&first_engine, FrbCompressionJobKind::Compress, &first_path)
+            .expect("first job on the volume");
+        assert!(
+            register_job(&first_engine, FrbCompressionJobKind::Sample, &first_path).is_err(),
+            "a path runs one job at a time"
+        );
+
+        MAX_JOBS_PER_VOLUME.store(2, Ordering::Relaxed);
+        let second = register_job(
+            &second_engine,
+            FrbCompressionJobKind::Decompress,
+            &second_path,
+        )
+        .expect("second job once the volume allows two");
+        MAX_JOBS_PER_VOLUME.store(DEFAULT_MAX_JOBS_PER_VOLUME, Ordering::Relaxed);
+
+        assert!(cancel_compression_job(second));
+        assert!(second_engine.cancel_token().is_cancelled());
+        assert!(!first_engine.cancel_token().is_cancelled());
+
+        unregister_job(first);
+        unregister_job(second);
+        assert!(!cancel_compression_job(second));
+    }
+}
@@ -0,0 +1,232 @@
+//! Crash rollback monitor.
+//!
+//! While enabled in settings, the monitor follows the processes of
+//! discovered games through [`crate::safety::game_processes`] and records
+//! how each run ends (see [`crate::compression::crash_rollback`]). A
+//! compressed game that keeps crashing right after launch is queued for
+//! decompression, excluded from automation and the user notified.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::automation::notifications;
+use crate::compression::crash_rollback::{CrashTracker, GameExit};
+use crate::safety::game_processes::{self, GameProcessSample, SubscriptionId};
+use crate::settings::Settings;
+
+static ACTIVE_MONITOR: Mutex<Option<SubscriptionId>> = Mutex::new(None);
+
+/// Start or stop the crash monitor to match `settings`.
+pub(crate) fn apply_settings(settings: &Settings) {
+    let mut guard = ACTIVE_MONITOR.lock().unwrap_or_else(|e| e.into_inner());
+    if !settings.rollback_on_crashes {
+        if let Some(subscription) = guard.take() {
+            game_processes::unsubscribe(subscription);
+            log::info!("Crash monitor stopped");
+        }
+        return;
+    }
+    if guard.is_none() {
+        let mut monitor = CrashMonitor::default();
+        *guard = Some(game_processes::subscribe(move |sample| {
+            monitor.observe(sample);
+            true
+        }));
+        log::info!("Crash monitor started");
+    }
+}
+
+struct TrackedProcess {
+    game: PathBuf,
+    /// Kept open so the exit code can be read after the process is gone.
+    handle: Option<ProcessHandle>,
+}
+
+struct GameRun {
+    launched_at: Instant,
+    /// False for games already running when the monitor started; their
+    /// launch time is unknown.
+    watched: bool,
+    abnormal: bool,
+}
+
+#[derive(Default)]
+struct CrashMonitor {
+    processes: HashMap<u32, TrackedProcess>,
+    runs: HashMap<PathBuf, GameRun>,
+    tracker: CrashTracker,
+    primed: bool,
+}
+
+impl CrashMonitor {
+    fn observe(&mut self, sample: &GameProcessSample) {
+        let now = sample.taken_at;
+        let mut alive: HashSet<u32> = HashSet::new();
+        for process in &sample.processes {
+            alive.insert(process.pid);
+            if self.processes.contains_key(&process.pid) {
+                continue;
+            }
+            self.runs.entry(process.game.clone()).or_insert(GameRun {
+                launched_at: now,
+                watched: self.primed,
+                abnormal: false,
+            });
+            self.processes.insert(
+                process.pid,
+                TrackedProcess {
+                    game: process.game.clone(),
+                    handle: ProcessHandle::open(process.pid),
+                },
+            );
+        }
+        self.primed = true;
+
+        let exited: Vec<u32> = self
+            .processes
+            .keys()
+            .filter(|pid| !alive.contains(pid))
+            .copied()
+            .collect();
+        for pid in exited {
+            let Some(process) = self.processes.remove(&pid) else {
+                continue;
+            };
+            let Some(code) = process.handle.as_ref().and_then(ProcessHandle::exit_code) else {
+                continue;
+            };
+            if code != 0 {
+                log::info!(
+                    "Process {pid} of {} exited with code {code:#x}",
+                    process.game.display()
+                );
+                if let Some(run) = self.runs.get_mut(&process.game) {
+                    run.abnormal = true;
+                }
+            }
+        }
+
+        let finished: Vec<PathBuf> = self
+            .runs
+            .keys()
+            .filter(|game| {
+                !self
+                    .processes
+                    .values()
+                    .any(|process| &process.game == *game)
+            })
+            .cloned()
+            .collect();
+        for game in finished {
+            let Some(run) = self.runs.remove(&game) else {
+                continue;
+            };
+            if !run.watched {
+                continue;
+            }
+            let exit = GameExit {
+                launched_at: run.launched_at,
+                exited_at: now,
+                abnormal: run.abnormal,
+            };
+            on_game_exited(&mut self.tracker, &game, exit);
+        }
+    }
+}
+
+fn on_game_exited(tracker: &mut CrashTracker, game: &Path, exit: GameExit) {
+    let compressed =
+        crate::discovery::cache::lookup_stale(game).is_some_and(|stats| stats.is_compressed);
+    if !compressed {
+        return;
+    }
+    if exit.is_startup_crash() {
+        log::warn!("{} crashed on startup while compressed", game.display());
+    }
+    if let Some(crashes) = tracker.record_exit(game, exit) {
+        roll_back(game, crashes);
+    }
+}
+
+/// Queue the game for decompression; once it is done, exclude it so
+/// automation does not compress it again.
+fn roll_back(game: &Path, crashes: usize) {
+    log::info!(
+        "Decompressing {} after {crashes} startup crashes",
+        game.display()
+    );
+    let game = game.to_path_buf();
+    crate::api::compression::queue_decompression(game.clone(), move |result| {
+        let error = match result {
+            Ok(stats) if !stats.was_cancelled => {
+                if let Err(e) = crate::api::exclusions::exclude_game(&game) {
+                    log::warn!("Failed to exclude {} after rollback: {e}", game.display());
+                }
+                None
+            }
+            Ok(_) => Some("Decompression was cancelled".to_string()),
+            Err(e) => {
+                log::warn!("Crash rollback of {} failed: {e}", game.display());
+                Some(e.to_string())
+            }
+        };
+        crate::api::automation::publish_notification(notifications::crash_rollback(
+            &game,
+            None,
+            crashes,
+            error.as_deref(),
+        ));
+    });
+}
+
+#[cfg(windows)]
+struct ProcessHandle(windows::Win32::Foundation::HANDLE);
+
+// SAFETY: the handle is only used through kernel calls, which accept it
+// from any thread, and is closed once on drop.
+#[cfg(windows)]
+unsafe impl Send for ProcessHandle {}
+
+#[cfg(windows)]
+impl ProcessHandle {
+    fn open(pid: u32) -> Option<Self> {
+        use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+        unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }
+            .ok()
+            .map(Self)
+    }
+
+    /// Exit code, or `None` while the process is still running.
+    fn exit_code(&self) -> Option<u32> {
+        use windows::Win32::System::Threading::GetExitCodeProcess;
+
+        const STILL_ACTIVE: u32 = 259;
+        let mut code = 0u32;
+        unsafe { GetExitCodeProcess(self.0, &mut code) }.ok()?;
+        (code != STILL_ACTIVE).then_some(code)
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ProcessHandle {
+    fn drop(&mut self) {
+        let _ = unsafe { windows::Win32::Foundation::CloseHandle(self.0) };
+    }
+}
+
+#[cfg(not(windows))]
+struct ProcessHandle;
+
+#[cfg(not(windows))]
+impl ProcessHandle {
+    fn open(_pid: u32) -> Option<Self> {
+        None
+    }
+
+    fn exit_code(&self) -> Option<u32> {
+        None
+    }
+}
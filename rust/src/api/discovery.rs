@@ -1,12 +1,47 @@
 //! Game discovery API exposed to Flutter via FRB.
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
 
 use flutter_rust_bridge::frb;
 
-use super::types::{FrbDiscoveryError, FrbGameInfo, FrbPlatform};
+use super::types::{
+    FrbDiscoveryCacheStats, FrbDiscoveryError, FrbDiscoveryProgress, FrbDriveSummary,
+    FrbDuplicateGroup, FrbGameInfo, FrbLibraryChange, FrbLibraryExportFormat, FrbPlatform,
+};
+use crate::discovery::library_changes::LibraryChange;
 use crate::discovery::platform::{DiscoveryScanMode, Platform};
 use crate::discovery::utils;
+use crate::frb_generated::StreamSink;
+use crate::safety::game_processes::{self, GameProcessSample};
+
+static LIBRARY_CHANGE_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbLibraryChange>>>> = OnceLock::new();
+static RUNNING_GAME_SINKS: OnceLock<Mutex<Vec<StreamSink<Vec<String>>>>> = OnceLock::new();
+static DISCOVERY_PROGRESS_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbDiscoveryProgress>>>> =
+    OnceLock::new();
+static LATEST_RUNNING_GAMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+/// Guarded by the running-game sinks lock so a subscriber never lands
+/// just as the process subscription decides to end.
+static RUNNING_GAMES_MONITOR_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Guarded by the discovery progress sinks lock, like the running-games flag.
+static DISCOVERY_PROGRESS_MONITOR_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+const MAX_STREAM_SINKS: usize = 32;
+const DISCOVERY_PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+fn library_change_sinks_lock() -> &'static Mutex<Vec<StreamSink<FrbLibraryChange>>> {
+    LIBRARY_CHANGE_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn running_game_sinks_lock() -> &'static Mutex<Vec<StreamSink<Vec<String>>>> {
+    RUNNING_GAME_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn discovery_progress_sinks_lock() -> &'static Mutex<Vec<StreamSink<FrbDiscoveryProgress>>> {
+    DISCOVERY_PROGRESS_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
 
 /// Scan all platforms and return discovered games.
 ///
@@ -26,6 +61,258 @@ pub fn get_all_games_quick() -> Result<Vec<FrbGameInfo>, FrbDiscoveryError> {
     Ok(frb_games)
 }
 
+/// Quick scan of a single platform, e.g. to refresh the Steam tab after a
+/// Steam update without re-running every scanner.
+pub fn quick_scan_platform(platform: FrbPlatform) -> Result<Vec<FrbGameInfo>, FrbDiscoveryError> {
+    let games = utils::scan_platform_with_mode(platform.into(), DiscoveryScanMode::Quick);
+    Ok(games.into_iter().map(FrbGameInfo::from).collect())
+}
+
+/// Full scan that streams games to Dart as each platform scanner finishes,
+/// so the library can render progressively during slow HDD scans.
+///
+/// Each game is emitted once. The stream closes when all scanners are done.
+pub fn discover_games_stream(sink: StreamSink<FrbGameInfo>) {
+    let sink_open = AtomicBool::new(true);
+    let games = utils::scan_all_platforms_streaming(DiscoveryScanMode::Full, |batch| {
+        if !sink_open.load(Ordering::Relaxed) {
+            return;
+        }
+        for game in batch {
+            if sink.add(FrbGameInfo::from(game.clone())).is_err() {
+                sink_open.store(false, Ordering::Relaxed);
+                return;
+            }
+        }
+    });
+    log::info!("Discovery stream finished: {} games", games.len());
+}
+
+/// Per-volume summary: capacity, free space, SSD/HDD class, discovered
+/// games, compressed vs uncompressed bytes, and projected savings.
+///
+/// Uses a quick discovery pass, so it is cheap once the cache is warm.
+pub fn get_drive_summaries() -> Result<Vec<FrbDriveSummary>, FrbDiscoveryError> {
+    let games = utils::scan_all_platforms_with_mode(DiscoveryScanMode::Quick);
+    Ok(crate::discovery::drive_summary::drive_summaries(&games)
+        .into_iter()
+        .map(FrbDriveSummary::from)
+        .collect())
+}
+
+/// Games installed more than once across libraries, largest potential
+/// reclaim first.
+///
+/// Copies match on Steam app id, or on normalized name when their sizes
+/// are within 10% of each other.
+pub fn get_duplicate_installs() -> Result<Vec<FrbDuplicateGroup>, FrbDiscoveryError> {
+    let games = utils::scan_all_platforms_with_mode(DiscoveryScanMode::Quick);
+    Ok(
+        crate::discovery::duplicates::find_duplicate_installs(&games)
+            .into_iter()
+            .map(FrbDuplicateGroup::from)
+            .collect(),
+    )
+}
+
+/// Write every discovered game to `path` as JSON or CSV: platform, sizes,
+/// compression state, savings and exclusion.
+///
+/// `excluded_paths` is the user's exclusion list, so the `excluded` column
+/// can be edited and re-imported with [`import_exclusions`].
+pub fn export_library(
+    format: FrbLibraryExportFormat,
+    path: String,
+    excluded_paths: Vec<String>,
+) -> Result<u32, FrbDiscoveryError> {
+    let games = utils::scan_all_platforms_with_mode(DiscoveryScanMode::Quick);
+    crate::discovery::library_export::export_library(
+        &games,
+        &excluded_paths,
+        format.into(),
+        Path::new(&path),
+    )
+    .map_err(|e| FrbDiscoveryError::LibraryFileFailed {
+        path: path.clone(),
+        message: e.to_string(),
+    })?;
+    Ok(games.len() as u32)
+}
+
+/// Read an exclusion list from a JSON or CSV file, such as one written by
+/// [`export_library`]. Returns the paths to exclude; merging them into the
+/// settings is left to the caller.
+pub fn import_exclusions(path: String) -> Result<Vec<String>, FrbDiscoveryError> {
+    crate::discovery::library_export::import_exclusions(Path::new(&path)).map_err(|e| {
+        FrbDiscoveryError::LibraryFileFailed {
+            path: path.clone(),
+            message: e.to_string(),
+        }
+    })
+}
+
+/// Subscribe to incremental library changes.
+///
+/// While auto-compression is running, its directory watcher keeps the
+/// discovery cache current and pushes one delta per installed, updated or
+/// removed game, so the library view does not need a full rescan.
+pub fn watch_library_changes(sink: StreamSink<FrbLibraryChange>) -> Result<(), FrbDiscoveryError> {
+    let mut guard = library_change_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Library change sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+    Ok(())
+}
+
+pub(crate) fn broadcast_library_change(change: LibraryChange) {
+    let mut guard = library_change_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Library change sinks lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+    if guard.is_empty() {
+        return;
+    }
+    let frb_change: FrbLibraryChange = change.into();
+    guard.retain(|sink| sink.add(frb_change.clone()).is_ok());
+}
+
+/// Subscribe to the set of discovered games that are running.
+///
+/// Emits the current set on subscribe and again whenever a game launches
+/// or exits. Process sampling runs only while someone is subscribed.
+pub fn watch_running_games(sink: StreamSink<Vec<String>>) -> Result<(), FrbDiscoveryError> {
+    let mut guard = running_game_sinks_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("Running game sinks lock poisoned during subscribe; recovering");
+        poisoned.into_inner()
+    });
+
+    let latest = LATEST_RUNNING_GAMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if sink.add(latest).is_err() {
+        return Ok(());
+    }
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+
+    if !RUNNING_GAMES_MONITOR_ACTIVE.swap(true, Ordering::AcqRel) {
+        // The observer takes the sinks lock while the sampler holds its
+        // own, so subscribe without holding ours.
+        drop(guard);
+        let mut reported: Vec<PathBuf> = Vec::new();
+        game_processes::subscribe(move |sample| report_running_games(&mut reported, sample));
+    }
+    Ok(())
+}
+
+/// Forward the running set to subscribers when it changes. Returns
+/// `false`, ending the subscription, once nobody is listening.
+fn report_running_games(reported: &mut Vec<PathBuf>, sample: &GameProcessSample) -> bool {
+    let running = sample.running_games();
+    let changed = (running != *reported).then(|| {
+        for launched in running.iter().filter(|path| !reported.contains(path)) {
+            log::info!("Game launched: {}", launched.display());
+        }
+        *reported = running;
+        reported
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<String>>()
+    });
+
+    let mut guard = running_game_sinks_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("Running game sinks lock poisoned; recovering");
+        poisoned.into_inner()
+    });
+    if let Some(running) = changed {
+        *LATEST_RUNNING_GAMES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = running.clone();
+        guard.retain(|sink| sink.add(running.clone()).is_ok());
+    }
+    if guard.is_empty() {
+        // The next subscription starts from nothing and re-reports.
+        LATEST_RUNNING_GAMES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+        RUNNING_GAMES_MONITOR_ACTIVE.store(false, Ordering::Release);
+        return false;
+    }
+    true
+}
+
+/// Subscribe to the progress of discovery scans: which scanners are
+/// running, how many game folders were found and sized, and elapsed time.
+///
+/// Emits the current progress on subscribe, then every change while a scan
+/// runs. The last scan's final counts stay current once it finishes.
+pub fn watch_discovery_progress(
+    sink: StreamSink<FrbDiscoveryProgress>,
+) -> Result<(), FrbDiscoveryError> {
+    let mut guard = discovery_progress_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Discovery progress sinks lock poisoned during subscribe; recovering");
+            poisoned.into_inner()
+        });
+
+    let current = FrbDiscoveryProgress::from(crate::discovery::progress::snapshot());
+    if sink.add(current).is_err() {
+        return Ok(());
+    }
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+
+    if !DISCOVERY_PROGRESS_MONITOR_ACTIVE.swap(true, Ordering::AcqRel) {
+        if let Err(e) = std::thread::Builder::new()
+            .name("compact-games-discovery-progress".to_string())
+            .spawn(discovery_progress_loop)
+        {
+            log::warn!("Failed to spawn discovery progress monitor: {e}");
+            DISCOVERY_PROGRESS_MONITOR_ACTIVE.store(false, Ordering::Release);
+        }
+    }
+    Ok(())
+}
+
+fn discovery_progress_loop() {
+    let mut last = FrbDiscoveryProgress::from(crate::discovery::progress::snapshot());
+    loop {
+        std::thread::sleep(DISCOVERY_PROGRESS_POLL_INTERVAL);
+        let progress = FrbDiscoveryProgress::from(crate::discovery::progress::snapshot());
+
+        let mut guard = discovery_progress_sinks_lock()
+            .lock()
+            .unwrap_or_else(|poisoned| {
+                log::warn!("Discovery progress sinks lock poisoned; recovering");
+                poisoned.into_inner()
+            });
+        if progress != last {
+            guard.retain(|sink| sink.add(progress.clone()).is_ok());
+            last = progress;
+        }
+        if guard.is_empty() {
+            DISCOVERY_PROGRESS_MONITOR_ACTIVE.store(false, Ordering::Release);
+            return;
+        }
+    }
+}
+
 /// Clear persisted and in-memory discovery cache.
 #[frb(sync)]
 pub fn clear_discovery_cache() {
@@ -42,13 +329,31 @@ pub fn clear_discovery_cache() {
 /// re-evaluated on the next scan.
 #[frb(sync)]
 pub fn clear_discovery_cache_entry(path: String) {
+    invalidate_cache_entry(path);
+}
+
+/// Evict discovery cache for a single game path.
+///
+/// Returns `true` when the stats cache held an entry for the path (or its
+/// Xbox `Content` folder) before it was evicted.
+#[frb(sync)]
+pub fn invalidate_cache_entry(path: String) -> bool {
     if path.trim().is_empty() {
-        return;
+        return false;
     }
     let path = PathBuf::from(path);
+    let had_entry = crate::discovery::cache::has_entry(&path)
+        || crate::discovery::cache::has_entry(&path.join("Content"));
     clear_discovery_metadata_for_candidate_paths(&path);
     persist_discovery_metadata_if_dirty();
     log::info!("Discovery cache entry cleared: {}", path.display());
+    had_entry
+}
+
+/// Entry count, hit rate, and on-disk footprint of the discovery stats cache.
+#[frb(sync)]
+pub fn get_discovery_cache_stats() -> FrbDiscoveryCacheStats {
+    crate::discovery::cache::stats().into()
 }
 
 /// Evict all discovery caches for a path and hide the current on-disk install
@@ -85,6 +390,47 @@ pub fn scan_custom_folder(path: String) -> Result<Vec<FrbGameInfo>, FrbDiscovery
     }
 }
 
+/// Persist `path` as a library root. It is scanned by every discovery
+/// pass and watched by automation from the next config update.
+///
+/// Returns `false` when the root was already registered.
+pub fn add_custom_root(path: String) -> Result<bool, FrbDiscoveryError> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err(FrbDiscoveryError::InvalidPath {
+            message: "path cannot be empty".to_owned(),
+        });
+    }
+    crate::discovery::custom_roots::add(Path::new(trimmed)).map_err(custom_root_error)
+}
+
+/// Forget a persisted library root. Games already discovered under it stay
+/// in the cache until the next scan.
+pub fn remove_custom_root(path: String) -> Result<bool, FrbDiscoveryError> {
+    crate::discovery::custom_roots::remove(Path::new(path.trim())).map_err(custom_root_error)
+}
+
+/// Persisted library roots, in the order they were added.
+#[frb(sync)]
+pub fn list_custom_roots() -> Vec<String> {
+    crate::discovery::custom_roots::list()
+        .into_iter()
+        .map(|root| root.to_string_lossy().into_owned())
+        .collect()
+}
+
+fn custom_root_error(e: crate::discovery::custom_roots::CustomRootError) -> FrbDiscoveryError {
+    use crate::discovery::custom_roots::CustomRootError;
+    match e {
+        CustomRootError::NotADirectory(_) | CustomRootError::TooManyRoots => {
+            FrbDiscoveryError::InvalidPath {
+                message: e.to_string(),
+            }
+        }
+        CustomRootError::Persist(message) => FrbDiscoveryError::DiscoveryFailed { message },
+    }
+}
+
 /// Add an arbitrary application folder (not a game) for compression.
 ///
 /// Unlike `scan_custom_folder`, this skips game-likeness heuristics and
@@ -174,11 +520,16 @@ pub fn hydrate_game(
 
     // The shared builder doesn't know about Steam manifests, so backfill the
     // app id here so hydrated games keep the primary key used by the
-    // community compression DB lookup.
+    // community compression DB lookup, and the cover art cached for it.
     if let Some(info) = game.as_mut() {
         if info.platform == Platform::Steam && info.steam_app_id.is_none() {
             info.steam_app_id = crate::discovery::steam::lookup_steam_app_id_for_path(&info.path);
         }
+        if info.artwork_path.is_none() {
+            info.artwork_path = info
+                .steam_app_id
+                .and_then(crate::discovery::steam::steam_library_artwork);
+        }
     }
 
     // Hydration upserts to the in-memory cache; flush to disk so
@@ -194,6 +545,7 @@ fn persist_discovery_metadata_if_dirty() {
     crate::discovery::change_feed::persist_if_dirty();
     crate::discovery::hidden_paths::persist_if_dirty();
     crate::discovery::install_history::persist_if_dirty();
+    crate::safety::directstorage_cache::persist_if_dirty();
 }
 
 fn clear_discovery_metadata_for_path(path: &Path) {
@@ -0,0 +1,106 @@
+//! Exclusion rules API exposed to Flutter via FRB.
+//!
+//! Rules live in the settings file; see [`crate::settings::exclusions`].
+//! Saving them pushes the automation config again so a running service
+//! picks them up.
+
+use std::io;
+use std::path::Path;
+
+use flutter_rust_bridge::frb;
+
+use super::settings::FrbSettingsError;
+use super::types::FrbPlatform;
+use crate::settings::{self, ExclusionRule, Exclusions};
+
+/// Mirror of `ExclusionRule` for FRB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrbExclusionRule {
+    /// This folder and everything below it.
+    Path { path: String },
+    /// `*` and `?` wildcards over the whole folder path.
+    Glob { pattern: String },
+    /// Every game from this launcher.
+    Platform { platform: FrbPlatform },
+}
+
+impl From<ExclusionRule> for FrbExclusionRule {
+    fn from(rule: ExclusionRule) -> Self {
+        match rule {
+            ExclusionRule::Path(path) => Self::Path { path },
+            ExclusionRule::Glob(pattern) => Self::Glob { pattern },
+            ExclusionRule::Platform(platform) => Self::Platform {
+                platform: platform.into(),
+            },
+        }
+    }
+}
+
+impl From<FrbExclusionRule> for ExclusionRule {
+    fn from(rule: FrbExclusionRule) -> Self {
+        match rule {
+            FrbExclusionRule::Path { path } => Self::Path(path),
+            FrbExclusionRule::Glob { pattern } => Self::Glob(pattern),
+            FrbExclusionRule::Platform { platform } => Self::Platform(platform.into()),
+        }
+    }
+}
+
+/// Saved exclusion rules, in the order the user added them.
+#[frb(sync)]
+pub fn get_exclusion_rules() -> Vec<FrbExclusionRule> {
+    settings::load()
+        .exclusions
+        .into_iter()
+        .map(FrbExclusionRule::from)
+        .collect()
+}
+
+/// Replace the exclusion rules. Returns them as saved, without blank or
+/// duplicate rules. Rediscover the library to refresh `excluded` flags.
+pub fn set_exclusion_rules(
+    rules: Vec<FrbExclusionRule>,
+) -> Result<Vec<FrbExclusionRule>, FrbSettingsError> {
+    let rules: Vec<ExclusionRule> = rules.into_iter().map(ExclusionRule::from).collect();
+    let saved = settings::update(|current| current.exclusions = rules).map_err(|e| {
+        FrbSettingsError::SaveFailed {
+            message: e.to_string(),
+        }
+    })?;
+    if let Err(e) = crate::api::automation::apply_automation_config(saved.automation.clone().into())
+    {
+        log::warn!("Saved exclusions but failed to update running automation: {e}");
+    }
+    Ok(saved
+        .exclusions
+        .into_iter()
+        .map(FrbExclusionRule::from)
+        .collect())
+}
+
+/// Add a folder rule for the game at `game_path`, for monitors that take
+/// a game out of automation's hands, and push it to running automation.
+pub(crate) fn exclude_game(game_path: &Path) -> io::Result<()> {
+    let rule = ExclusionRule::Path(game_path.to_string_lossy().into_owned());
+    let saved = settings::update(|current| current.exclusions.push(rule))?;
+    if let Err(e) = crate::api::automation::apply_automation_config(saved.automation.into()) {
+        log::warn!(
+            "Excluded {} but failed to update running automation: {e}",
+            game_path.display()
+        );
+    }
+    log::info!("Excluded {} from automation", game_path.display());
+    Ok(())
+}
+
+/// The rule excluding the game at `game_path`, if any, so the UI can warn
+/// before compressing it by hand. Includes automation's excluded folders.
+#[frb(sync)]
+pub fn find_exclusion(
+    game_path: String,
+    platform: Option<FrbPlatform>,
+) -> Option<FrbExclusionRule> {
+    Exclusions::load()
+        .matching(Path::new(&game_path), platform.map(Into::into))
+        .map(FrbExclusionRule::from)
+}
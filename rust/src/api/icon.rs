@@ -344,8 +344,9 @@ pub fn extract_exe_icon(exe_path: String) -> Option<Vec<u8>> {
 }
 
 /// Walk a game folder and return the path to the most likely primary game
-/// executable. Skips known non-game names (installers, crash reporters,
-/// redistributables) and picks the largest remaining .exe.
+/// executable, for cover art when the game has no `artwork_path`. Skips
+/// known non-game names (installers, crash reporters, redistributables) and
+/// picks the largest remaining .exe.
 ///
 /// Walks at most 4 levels deep and scans at most 600 entries so the call
 /// stays bounded on large libraries. Runs on the FRB thread pool (off the
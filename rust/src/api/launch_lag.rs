@@ -0,0 +1,191 @@
+//! Launch-lag detection API exposed to Flutter via FRB.
+//!
+//! While enabled in settings, a monitor fed by
+//! [`crate::safety::game_processes`] times every launch of a discovered
+//! game (see [`crate::compression::launch_lag`]). Games that
+//! launch clearly slower since compression are listed as decompression
+//! recommendations, or queued for decompression once the game exits and
+//! then excluded from automation when the user opted into that too.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::compression::launch_lag::{self, LaunchLagReport, LaunchProgress, LaunchTimer};
+use crate::safety::game_processes::{self, GameProcessSample, SubscriptionId};
+use crate::settings::Settings;
+
+/// A game that launches significantly slower since it was compressed.
+#[derive(Debug, Clone)]
+pub struct FrbLaunchLagReport {
+    pub game_path: String,
+    pub baseline_ms: u64,
+    pub compressed_ms: u64,
+    pub slowdown_ratio: f64,
+}
+
+impl From<LaunchLagReport> for FrbLaunchLagReport {
+    fn from(report: LaunchLagReport) -> Self {
+        Self {
+            game_path: report.game_path,
+            baseline_ms: report.baseline_ms,
+            compressed_ms: report.compressed_ms,
+            slowdown_ratio: report.slowdown_ratio,
+        }
+    }
+}
+
+struct ActiveMonitor {
+    subscription: SubscriptionId,
+    auto_decompress: Arc<AtomicBool>,
+}
+
+static ACTIVE_MONITOR: Mutex<Option<ActiveMonitor>> = Mutex::new(None);
+
+/// Compressed games whose launches got significantly slower, worst first.
+pub fn get_launch_lag_recommendations() -> Vec<FrbLaunchLagReport> {
+    launch_lag::lagging_games()
+        .into_iter()
+        .map(FrbLaunchLagReport::from)
+        .collect()
+}
+
+/// Start, stop or reconfigure the launch monitor to match `settings`.
+pub(crate) fn apply_settings(settings: &Settings) {
+    let mut guard = ACTIVE_MONITOR.lock().unwrap_or_else(|e| e.into_inner());
+    if !settings.detect_launch_lag {
+        if let Some(active) = guard.take() {
+            game_processes::unsubscribe(active.subscription);
+            log::info!("Launch-lag monitor stopped");
+        }
+        return;
+    }
+
+    if let Some(active) = guard.as_ref() {
+        active
+            .auto_decompress
+            .store(settings.auto_decompress_on_launch_lag, Ordering::Relaxed);
+        return;
+    }
+
+    let auto_decompress = Arc::new(AtomicBool::new(settings.auto_decompress_on_launch_lag));
+    let mut monitor = LaunchMonitor::new(auto_decompress.clone());
+    let subscription = game_processes::subscribe(move |sample| {
+        monitor.observe(sample);
+        true
+    });
+    log::info!("Launch-lag monitor started");
+    *guard = Some(ActiveMonitor {
+        subscription,
+        auto_decompress,
+    });
+}
+
+struct LaunchMonitor {
+    auto_decompress: Arc<AtomicBool>,
+    timers: HashMap<PathBuf, LaunchTimer>,
+    running: HashSet<PathBuf>,
+    pending_decompress: HashSet<PathBuf>,
+    /// Games already running when the monitor starts are not timed; their
+    /// launch happened before anyone was watching.
+    primed: bool,
+}
+
+impl LaunchMonitor {
+    fn new(auto_decompress: Arc<AtomicBool>) -> Self {
+        Self {
+            auto_decompress,
+            timers: HashMap::new(),
+            running: HashSet::new(),
+            pending_decompress: HashSet::new(),
+            primed: false,
+        }
+    }
+
+    fn observe(&mut self, sample: &GameProcessSample) {
+        let mut read_bytes: HashMap<PathBuf, u64> = HashMap::new();
+        for process in &sample.processes {
+            let total = read_bytes.entry(process.game.clone()).or_insert(0);
+            *total = total.saturating_add(process.total_read_bytes);
+        }
+
+        let now = sample.taken_at;
+        for (game, bytes) in &read_bytes {
+            if self.running.insert(game.clone()) && self.primed {
+                self.timers.insert(game.clone(), LaunchTimer::start(now));
+            }
+            let Some(timer) = self.timers.get_mut(game) else {
+                continue;
+            };
+            match timer.observe(*bytes, now) {
+                LaunchProgress::Loading => {}
+                LaunchProgress::Abandoned => {
+                    self.timers.remove(game);
+                }
+                LaunchProgress::Settled(duration) => {
+                    self.timers.remove(game);
+                    if on_launch_measured(game, duration) {
+                        self.pending_decompress.insert(game.clone());
+                    }
+                }
+            }
+        }
+
+        self.primed = true;
+
+        let exited: Vec<PathBuf> = self
+            .running
+            .iter()
+            .filter(|game| !read_bytes.contains_key(*game))
+            .cloned()
+            .collect();
+        for game in exited {
+            self.running.remove(&game);
+            self.timers.remove(&game);
+            if self.pending_decompress.remove(&game) && self.auto_decompress.load(Ordering::Relaxed)
+            {
+                decompress_lagging_game(&game);
+            }
+        }
+    }
+}
+
+/// Record a measured launch. Returns whether the game now shows launch lag.
+fn on_launch_measured(game: &Path, duration: Duration) -> bool {
+    let compressed =
+        crate::discovery::cache::lookup_stale(game).is_some_and(|stats| stats.is_compressed);
+    launch_lag::record_launch(game, duration, compressed);
+    if !compressed {
+        return false;
+    }
+    let Some(report) = launch_lag::assess(game) else {
+        return false;
+    };
+    log::warn!(
+        "{} launches {:.0}% slower since compression ({} ms vs {} ms); decompression recommended",
+        game.display(),
+        (report.slowdown_ratio - 1.0) * 100.0,
+        report.compressed_ms,
+        report.baseline_ms
+    );
+    true
+}
+
+/// Queue the game for decompression; once it is done, exclude it so
+/// automation does not compress it again.
+fn decompress_lagging_game(game: &Path) {
+    log::info!("Decompressing {} after launch lag", game.display());
+    let game = game.to_path_buf();
+    crate::api::compression::queue_decompression(game.clone(), move |result| match result {
+        Ok(stats) if stats.was_cancelled => {}
+        Ok(_) => {
+            launch_lag::forget_compressed_launches(&game);
+            if let Err(e) = crate::api::exclusions::exclude_game(&game) {
+                log::warn!("Failed to exclude {} after launch lag: {e}", game.display());
+            }
+        }
+        Err(e) => log::warn!("Automatic decompression of {} failed: {e}", game.display()),
+    });
+}
@@ -0,0 +1,111 @@
+//! Log console and bug-report API exposed to Flutter via FRB.
+
+use std::path::Path;
+use std::sync::{Mutex, Once, OnceLock};
+
+use thiserror::Error;
+
+use crate::frb_generated::StreamSink;
+use crate::logging::{self, LogEvent, LogLevel};
+
+static LOG_EVENT_SINKS: OnceLock<Mutex<Vec<StreamSink<FrbLogEvent>>>> = OnceLock::new();
+static LOG_LISTENER: Once = Once::new();
+
+const MAX_STREAM_SINKS: usize = 32;
+
+#[derive(Debug, Error)]
+pub enum FrbDiagnosticsError {
+    #[error("Diagnostics bundle '{path}' failed: {message}")]
+    ExportFailed { path: String, message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbLogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for FrbLogLevel {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::Error,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Info => Self::Info,
+            LogLevel::Debug => Self::Debug,
+            LogLevel::Trace => Self::Trace,
+        }
+    }
+}
+
+/// One captured log line for the in-app log console.
+#[derive(Debug, Clone)]
+pub struct FrbLogEvent {
+    pub timestamp_ms: i64,
+    pub level: FrbLogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+impl From<LogEvent> for FrbLogEvent {
+    fn from(event: LogEvent) -> Self {
+        Self {
+            timestamp_ms: event.timestamp_ms as i64,
+            level: event.level.into(),
+            target: event.target,
+            message: event.message,
+        }
+    }
+}
+
+fn log_event_sinks_lock() -> &'static Mutex<Vec<StreamSink<FrbLogEvent>>> {
+    LOG_EVENT_SINKS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Subscribe to log events captured from now on (warnings from any source,
+/// info and above from the core).
+pub fn watch_log_events(sink: StreamSink<FrbLogEvent>) {
+    LOG_LISTENER.call_once(|| {
+        logging::add_listener(Box::new(|event| {
+            let mut guard = log_event_sinks_lock()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if !guard.is_empty() {
+                let frb_event = FrbLogEvent::from(event.clone());
+                guard.retain(|sink| sink.add(frb_event.clone()).is_ok());
+            }
+            true
+        }));
+    });
+
+    let mut guard = log_event_sinks_lock()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if guard.len() >= MAX_STREAM_SINKS {
+        guard.swap_remove(0);
+    }
+    guard.push(sink);
+}
+
+/// Up to `count` of the most recent log events this session, oldest first.
+pub fn get_recent_logs(count: u32) -> Vec<FrbLogEvent> {
+    logging::recent(count as usize)
+        .into_iter()
+        .map(FrbLogEvent::from)
+        .collect()
+}
+
+/// Zip recent logs, the automation journal and queue, discovery cache
+/// stats, a savings summary and system info into `path` for a support
+/// request.
+pub fn export_diagnostics_bundle(path: String) -> Result<(), FrbDiagnosticsError> {
+    let scheduler = crate::api::automation::status_snapshot();
+    crate::diagnostics::export_bundle(Path::new(&path), scheduler).map_err(|e| {
+        FrbDiagnosticsError::ExportFailed {
+            path: path.clone(),
+            message: e.to_string(),
+        }
+    })
+}
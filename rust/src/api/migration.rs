@@ -0,0 +1,136 @@
+//! Game move API exposed to Flutter via FRB.
+//!
+//! Only one move runs at a time; it is tracked in a module-level slot so
+//! Dart can cancel it. The source folder is locked for the whole move, so
+//! no compression or decompression touches it until the move is done.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use flutter_rust_bridge::frb;
+
+use super::automation_types::FrbAutomationJobStatus;
+use super::types::{FrbCompressionAlgorithm, FrbCompressionError, FrbMoveOutcome, FrbMoveProgress};
+use crate::compression::engine::CancellationToken;
+use crate::compression::library_lock::lock_game_folder;
+use crate::discovery::library_changes;
+use crate::frb_generated::StreamSink;
+use crate::utils::normalize_path_key;
+
+static ACTIVE_MOVE: OnceLock<Mutex<Option<CancellationToken>>> = OnceLock::new();
+
+fn active_move_lock() -> &'static Mutex<Option<CancellationToken>> {
+    ACTIVE_MOVE.get_or_init(|| Mutex::new(None))
+}
+
+fn set_active_move(token: Option<CancellationToken>) -> Result<(), FrbCompressionError> {
+    let mut guard = active_move_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("ACTIVE move lock was poisoned; recovering");
+        poisoned.into_inner()
+    });
+    if token.is_some() && guard.is_some() {
+        return Err(FrbCompressionError::IoError {
+            message: "A game move is already in progress".into(),
+        });
+    }
+    *guard = token;
+    Ok(())
+}
+
+/// Whether an automation job is queued or running, or a manual job is
+/// running, on the game at `path`.
+fn has_job_on(path: &Path) -> bool {
+    let path_key = normalize_path_key(path);
+    let automation = crate::api::automation::get_automation_queue()
+        .into_iter()
+        .any(|job| {
+            matches!(
+                job.status,
+                FrbAutomationJobStatus::Pending
+                    | FrbAutomationJobStatus::WaitingForSettle
+                    | FrbAutomationJobStatus::WaitingForIdle
+                    | FrbAutomationJobStatus::Compressing
+            ) && normalize_path_key(Path::new(&job.game_path)) == path_key
+        });
+    automation
+        || crate::api::compression::manual_jobs()
+            .iter()
+            .any(|job| normalize_path_key(&job.path) == path_key)
+}
+
+/// Move a game folder to `dest_drive`, keeping its folder name.
+///
+/// Streams phase and byte progress to `sink`. The copy is verified before
+/// the source is deleted, and rolled back on failure or cancellation. When
+/// `algorithm` is set the copy is compressed at the destination first.
+/// Fails without starting while a compression job targets the game.
+/// On success the library is updated in place via the library change
+/// stream.
+pub fn move_game(
+    source_path: String,
+    game_name: String,
+    dest_drive: String,
+    algorithm: Option<FrbCompressionAlgorithm>,
+    sink: StreamSink<FrbMoveProgress>,
+) -> Result<FrbMoveOutcome, FrbCompressionError> {
+    let source = PathBuf::from(&source_path);
+    if has_job_on(&source) {
+        return Err(FrbCompressionError::IoError {
+            message: format!(
+                "{} is queued for or being compressed or decompressed",
+                source.display()
+            ),
+        });
+    }
+    let _folder_lock = lock_game_folder(&source)?;
+    let cancel_token = CancellationToken::new();
+    set_active_move(Some(cancel_token.clone()))?;
+
+    let mut sink_is_open = true;
+    let result = crate::migration::move_game(
+        &source,
+        &PathBuf::from(&dest_drive),
+        algorithm.map(Into::into),
+        &cancel_token,
+        |progress| {
+            if sink_is_open && sink.add(FrbMoveProgress::from(progress)).is_err() {
+                sink_is_open = false;
+            }
+        },
+    );
+
+    let _ = set_active_move(None);
+
+    let outcome = result?;
+    log::info!(
+        "[move][summary] game=\"{}\" from=\"{}\" to=\"{}\" files={} bytes={} compressed={} source_removed={}",
+        game_name,
+        source_path,
+        outcome.destination.display(),
+        outcome.files_copied,
+        outcome.bytes_copied,
+        outcome.compression.is_some(),
+        outcome.source_removed
+    );
+
+    if outcome.source_removed {
+        super::discovery::broadcast_library_change(library_changes::game_uninstalled(&source));
+    }
+    if let Some(change) = library_changes::game_installed(&outcome.destination, Some(&game_name)) {
+        super::discovery::broadcast_library_change(change);
+    }
+
+    Ok(outcome.into())
+}
+
+/// Cancel the active game move. The partial copy is removed.
+#[frb(sync)]
+pub fn cancel_move_game() {
+    let guard = active_move_lock().lock().unwrap_or_else(|poisoned| {
+        log::warn!("ACTIVE move lock was poisoned during cancel; recovering");
+        poisoned.into_inner()
+    });
+    if let Some(token) = guard.as_ref() {
+        token.cancel();
+    }
+}
@@ -1,15 +1,15 @@
 #[flutter_rust_bridge::frb(sync)]
 pub fn init_app() -> String {
-    let mut builder =
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"));
-    builder.format_timestamp_millis();
-    builder.try_init().ok();
+    crate::logging::init();
     if let Err(e) = rayon::ThreadPoolBuilder::new()
         .num_threads(num_cpus::get().min(8))
         .build_global()
     {
         log::warn!("Failed to configure global thread pool: {e}");
     }
+    let settings = crate::settings::load();
+    crate::api::launch_lag::apply_settings(&settings);
+    crate::api::crash_rollback::apply_settings(&settings);
     log::info!("Compact Games core initialized");
     String::from("Compact Games core ready")
 }
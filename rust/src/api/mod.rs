@@ -1,10 +1,19 @@
 pub mod automation;
 pub mod automation_types;
 pub mod compression;
+pub mod crash_rollback;
 pub mod discovery;
+pub mod exclusions;
 pub mod icon;
+pub mod launch_lag;
+pub mod logging;
+pub mod migration;
 pub mod minimal;
+pub mod reclaim;
+pub mod settings;
 pub mod shell;
+pub mod startup;
 pub mod types;
 pub mod unsupported;
 pub mod update;
+pub mod user_data;
@@ -0,0 +1,112 @@
+//! Disk reclaim planner API exposed to Flutter via FRB.
+//!
+//! The UI asks for a plan to reach a free-space goal, shows it, and hands
+//! the accepted plan back to be queued in automation; see
+//! [`crate::automation::reclaim`].
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::automation_types::FrbAutomationError;
+use super::types::{system_time_to_millis, FrbDiscoveryError, FrbPlatform};
+use crate::automation::reclaim::{self, ReclaimPlan, ReclaimStep, SavingsModel};
+use crate::discovery::drive_summary::mounted_volumes;
+use crate::discovery::platform::DiscoveryScanMode;
+use crate::discovery::storage::volume_key_for_path;
+use crate::discovery::utils;
+
+/// One game in a reclaim plan.
+#[derive(Debug, Clone)]
+pub struct FrbReclaimStep {
+    pub game_path: String,
+    pub game_name: String,
+    pub platform: FrbPlatform,
+    pub size_bytes: u64,
+    pub estimated_savings_bytes: u64,
+    pub last_played: Option<i64>,
+}
+
+impl From<ReclaimStep> for FrbReclaimStep {
+    fn from(step: ReclaimStep) -> Self {
+        Self {
+            game_path: step.game_path.to_string_lossy().into_owned(),
+            game_name: step.game_name,
+            platform: step.platform.into(),
+            size_bytes: step.size_bytes,
+            estimated_savings_bytes: step.estimated_savings_bytes,
+            last_played: step.last_played.and_then(system_time_to_millis),
+        }
+    }
+}
+
+/// Games to compress, in order, to reach a free-space goal.
+#[derive(Debug, Clone)]
+pub struct FrbReclaimPlan {
+    pub volume: String,
+    pub free_bytes: u64,
+    pub target_free_bytes: u64,
+    pub shortfall_bytes: u64,
+    pub planned_savings_bytes: u64,
+    /// False when compressing every candidate would still fall short.
+    pub reaches_target: bool,
+    pub steps: Vec<FrbReclaimStep>,
+}
+
+impl From<ReclaimPlan> for FrbReclaimPlan {
+    fn from(plan: ReclaimPlan) -> Self {
+        Self {
+            reaches_target: plan.reaches_target(),
+            volume: plan.volume,
+            free_bytes: plan.free_bytes,
+            target_free_bytes: plan.target_free_bytes,
+            shortfall_bytes: plan.shortfall_bytes,
+            planned_savings_bytes: plan.planned_savings_bytes,
+            steps: plan.steps.into_iter().map(FrbReclaimStep::from).collect(),
+        }
+    }
+}
+
+/// Plan which games to compress so `volume` (such as `D:`) has
+/// `target_free_bytes` free. Long-unplayed games with the largest
+/// projected savings come first. Uses a quick discovery pass.
+pub fn plan_disk_reclaim(
+    volume: String,
+    target_free_bytes: u64,
+) -> Result<FrbReclaimPlan, FrbDiscoveryError> {
+    let volume_key = volume_key_for_path(Path::new(&volume));
+    let space = mounted_volumes()
+        .into_iter()
+        .find(|space| space.volume == volume_key)
+        .ok_or_else(|| FrbDiscoveryError::InvalidPath {
+            message: format!("No mounted volume at {volume}"),
+        })?;
+
+    let mut games = utils::scan_all_platforms_with_mode(DiscoveryScanMode::Quick);
+    if !crate::settings::load()
+        .automation
+        .allow_anticheat_compression
+    {
+        games.retain(|game| !game.uses_kernel_anticheat);
+    }
+    let model = SavingsModel::from_history(&crate::compression::history::get_historical_stats());
+    Ok(reclaim::plan_reclaim(
+        &games,
+        &space.volume,
+        space.free_bytes,
+        target_free_bytes,
+        &model,
+        SystemTime::now(),
+    )
+    .into())
+}
+
+/// Queue the steps of an accepted plan in automation. They run once the
+/// user is idle, like any other automation job. Needs automation running.
+pub fn enqueue_reclaim_plan(plan: FrbReclaimPlan) -> Result<(), FrbAutomationError> {
+    let games = plan
+        .steps
+        .into_iter()
+        .map(|step| (PathBuf::from(step.game_path), Some(step.game_name)))
+        .collect();
+    super::automation::enqueue_games(games)
+}
@@ -0,0 +1,150 @@
+//! Shared settings API exposed to Flutter via FRB.
+
+use flutter_rust_bridge::frb;
+use thiserror::Error;
+
+use super::automation_types::FrbAutomationConfig;
+use super::exclusions::FrbExclusionRule;
+use super::user_data::FrbUserDataSettings;
+use crate::settings::{
+    self, AutomationSettings, LibraryReconcileSettings, Settings, UserDataSettings,
+};
+
+#[derive(Debug, Error)]
+pub enum FrbSettingsError {
+    #[error("Saving settings failed: {message}")]
+    SaveFailed { message: String },
+}
+
+/// Mirror of `LibraryReconcileSettings` for FRB.
+#[derive(Debug, Clone)]
+pub struct FrbLibraryReconcileSettings {
+    pub interval_hours: u32,
+    pub min_retained_savings_percent: u8,
+}
+
+impl From<LibraryReconcileSettings> for FrbLibraryReconcileSettings {
+    fn from(s: LibraryReconcileSettings) -> Self {
+        Self {
+            interval_hours: s.interval_hours,
+            min_retained_savings_percent: s.min_retained_savings_percent,
+        }
+    }
+}
+
+impl From<&FrbLibraryReconcileSettings> for LibraryReconcileSettings {
+    fn from(s: &FrbLibraryReconcileSettings) -> Self {
+        Self {
+            interval_hours: s.interval_hours,
+            min_retained_savings_percent: s.min_retained_savings_percent,
+        }
+    }
+}
+
+/// Mirror of `Settings` for FRB.
+#[derive(Debug, Clone)]
+pub struct FrbSettings {
+    pub schema_version: u32,
+    pub auto_compress: bool,
+    pub automation: FrbAutomationConfig,
+    pub compress_admin_only_files: bool,
+    pub detect_launch_lag: bool,
+    pub auto_decompress_on_launch_lag: bool,
+    pub rollback_on_crashes: bool,
+    pub user_data: FrbUserDataSettings,
+    pub exclusions: Vec<FrbExclusionRule>,
+    pub library_reconcile: FrbLibraryReconcileSettings,
+    pub notifications_enabled: bool,
+    pub minimize_to_tray: bool,
+    pub auto_check_updates: bool,
+    pub theme_variant: String,
+    pub locale_tag: Option<String>,
+    pub data_dir: Option<String>,
+}
+
+impl From<Settings> for FrbSettings {
+    fn from(s: Settings) -> Self {
+        Self {
+            schema_version: s.schema_version,
+            auto_compress: s.auto_compress,
+            automation: s.automation.into(),
+            compress_admin_only_files: s.compress_admin_only_files,
+            detect_launch_lag: s.detect_launch_lag,
+            auto_decompress_on_launch_lag: s.auto_decompress_on_launch_lag,
+            rollback_on_crashes: s.rollback_on_crashes,
+            user_data: s.user_data.into(),
+            exclusions: s.exclusions.into_iter().map(Into::into).collect(),
+            library_reconcile: s.library_reconcile.into(),
+            notifications_enabled: s.notifications_enabled,
+            minimize_to_tray: s.minimize_to_tray,
+            auto_check_updates: s.auto_check_updates,
+            theme_variant: s.theme_variant,
+            locale_tag: s.locale_tag,
+            data_dir: s.data_dir,
+        }
+    }
+}
+
+impl From<&FrbSettings> for Settings {
+    fn from(s: &FrbSettings) -> Self {
+        Self {
+            schema_version: s.schema_version,
+            auto_compress: s.auto_compress,
+            automation: AutomationSettings::from(&s.automation),
+            compress_admin_only_files: s.compress_admin_only_files,
+            detect_launch_lag: s.detect_launch_lag,
+            auto_decompress_on_launch_lag: s.auto_decompress_on_launch_lag,
+            rollback_on_crashes: s.rollback_on_crashes,
+            user_data: UserDataSettings::from(&s.user_data),
+            exclusions: s.exclusions.iter().cloned().map(Into::into).collect(),
+            library_reconcile: LibraryReconcileSettings::from(&s.library_reconcile),
+            notifications_enabled: s.notifications_enabled,
+            minimize_to_tray: s.minimize_to_tray,
+            auto_check_updates: s.auto_check_updates,
+            theme_variant: s.theme_variant.clone(),
+            locale_tag: s.locale_tag.clone(),
+            data_dir: s.data_dir.clone(),
+        }
+    }
+}
+
+/// Saved settings, or the defaults on first run.
+#[frb(sync)]
+pub fn get_settings() -> FrbSettings {
+    settings::load().into()
+}
+
+/// The data directory in use this session. A changed `data_dir` setting
+/// shows here after the next start.
+#[frb(sync)]
+pub fn get_data_dir() -> Option<String> {
+    crate::paths::data_dir()
+        .ok()
+        .map(|dir| dir.to_string_lossy().into_owned())
+}
+
+/// Whether the app runs elevated, so admin-only files can be compressed
+/// without relaunching as administrator.
+#[frb(sync)]
+pub fn is_process_elevated() -> bool {
+    crate::compression::privilege::is_process_elevated()
+}
+
+/// Save `settings` for the app, daemon and CLI, and push the automation
+/// section to a running service. Returns the values as saved, after
+/// clamping to safe ranges.
+pub fn update_settings(settings: FrbSettings) -> Result<FrbSettings, FrbSettingsError> {
+    let replacement = Settings::from(&settings);
+    let saved = settings::update(|current| *current = replacement).map_err(|e| {
+        FrbSettingsError::SaveFailed {
+            message: e.to_string(),
+        }
+    })?;
+    if let Err(e) = crate::api::automation::apply_automation_config(saved.automation.clone().into())
+    {
+        log::warn!("Saved settings but failed to update running automation: {e}");
+    }
+    crate::api::launch_lag::apply_settings(&saved);
+    crate::api::crash_rollback::apply_settings(&saved);
+    Ok(saved.into())
+}
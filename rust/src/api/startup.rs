@@ -0,0 +1,64 @@
+//! Launch at login, so automation runs after a reboot without the service.
+
+use thiserror::Error;
+
+use crate::daemon::startup::{self, LaunchTarget};
+
+/// Errors returned when changing launch at login.
+#[derive(Debug, Error)]
+pub enum FrbStartupError {
+    #[error("Launch at login could not be changed: {message}")]
+    RegistrationFailed { message: String },
+}
+
+impl From<std::io::Error> for FrbStartupError {
+    fn from(e: std::io::Error) -> Self {
+        Self::RegistrationFailed {
+            message: e.to_string(),
+        }
+    }
+}
+
+/// What starts at login.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbLaunchTarget {
+    App,
+    /// The background daemon, from the app's install folder.
+    Daemon,
+}
+
+impl From<FrbLaunchTarget> for LaunchTarget {
+    fn from(target: FrbLaunchTarget) -> Self {
+        match target {
+            FrbLaunchTarget::App => Self::App,
+            FrbLaunchTarget::Daemon => Self::Daemon,
+        }
+    }
+}
+
+/// Start the app at login from the user's `Run` key, or stop doing so.
+pub fn set_launch_at_startup(enabled: bool) -> Result<(), FrbStartupError> {
+    if enabled {
+        startup::register(LaunchTarget::App, false)?;
+    } else {
+        startup::unregister()?;
+    }
+    Ok(())
+}
+
+/// Start `target` at login. With `run_elevated` it becomes a Task
+/// Scheduler logon task with highest privileges, which only an elevated
+/// app can register.
+pub fn register_launch_at_startup(
+    target: FrbLaunchTarget,
+    run_elevated: bool,
+) -> Result<(), FrbStartupError> {
+    startup::register(target.into(), run_elevated)?;
+    Ok(())
+}
+
+/// Whether anything is registered to start at login. Not sync: checking
+/// for the scheduled task runs `schtasks`.
+pub fn is_launch_at_startup() -> bool {
+    startup::registration().is_some()
+}
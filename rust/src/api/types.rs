@@ -10,15 +10,34 @@
 // `crate::api::types::FrbAutomation*`) continues to compile.
 pub use super::automation_types::*;
 
-use std::time::UNIX_EPOCH;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::attributes::IncompatibleAttribute;
 use crate::compression::engine::{
-    CompressionEstimate, CompressionEstimateSource, CompressionStats,
+    AlgorithmBenchmark, AttributeSkip, BenchmarkReport, CompressionEstimate,
+    CompressionEstimateSource, CompressionStats, DecompressionStats,
 };
 use crate::compression::error::CompressionError;
+use crate::compression::history::analytics::{
+    HistoryFilter, SavingsBucket, SavingsPoint, SavingsSummary, SavingsTotals,
+};
+use crate::compression::history::retention::HistoryRetention;
+use crate::compression::history::{CompressionHistoryEntry, PruneOutcome};
+use crate::compression::op_journal::{OperationKind, OperationRecord};
+use crate::discovery::cache::CacheStats;
+use crate::discovery::drive_summary::DriveSummary;
+use crate::discovery::duplicates::{DuplicateGroup, DuplicateReason};
+use crate::discovery::library_changes::LibraryChange;
+use crate::discovery::library_export::LibraryExportFormat;
 use crate::discovery::platform::{GameInfo, Platform};
+use crate::discovery::progress::DiscoveryProgress;
+use crate::discovery::storage::{DriveType, StorageClass};
+use crate::migration::{MoveError, MoveOutcome, MovePhase, MoveProgress};
 use crate::progress::tracker::CompressionProgress;
+use crate::safety::directstorage_cache::DirectStorageConfidence;
+use crate::safety::known_games::DatabaseSummary;
 use thiserror::Error;
 
 // ── FRB-compatible game info ──────────────────────────────────────────
@@ -34,9 +53,19 @@ pub struct FrbGameInfo {
     pub is_compressed: bool,
     pub is_directstorage: bool,
     pub is_unsupported: bool,
+    pub is_protected_package: bool,
+    pub is_unsupported_filesystem: bool,
+    pub uses_kernel_anticheat: bool,
+    pub drive_type: FrbDriveType,
+    pub has_cloud_placeholders: bool,
     pub excluded: bool,
     pub steam_app_id: Option<u32>,
     pub last_played: Option<i64>,
+    pub last_compressed: Option<i64>,
+    pub playtime_minutes: Option<u64>,
+    pub epic_catalog_item_id: Option<String>,
+    pub artwork_path: Option<String>,
+    pub has_encrypted_or_sparse_files: bool,
 }
 
 impl From<GameInfo> for FrbGameInfo {
@@ -50,13 +79,228 @@ impl From<GameInfo> for FrbGameInfo {
             is_compressed: g.is_compressed,
             is_directstorage: g.is_directstorage,
             is_unsupported: g.is_unsupported,
+            is_protected_package: g.is_protected_package,
+            is_unsupported_filesystem: g.is_unsupported_filesystem,
+            uses_kernel_anticheat: g.uses_kernel_anticheat,
+            drive_type: g.drive_type.into(),
+            has_cloud_placeholders: g.has_cloud_placeholders,
             excluded: g.excluded,
             steam_app_id: g.steam_app_id,
-            last_played: g.last_played.and_then(|t| {
-                t.duration_since(UNIX_EPOCH)
-                    .ok()
-                    .map(|d| d.as_millis() as i64)
-            }),
+            last_played: g.last_played.and_then(system_time_to_millis),
+            last_compressed: g.last_compressed.and_then(system_time_to_millis),
+            playtime_minutes: g.playtime_minutes,
+            epic_catalog_item_id: g.epic_catalog_item_id,
+            artwork_path: g
+                .artwork_path
+                .map(|path| path.to_string_lossy().into_owned()),
+            has_encrypted_or_sparse_files: g.has_encrypted_or_sparse_files,
+        }
+    }
+}
+
+pub(crate) fn system_time_to_millis(t: SystemTime) -> Option<i64> {
+    t.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as i64)
+}
+
+/// Incremental library delta pushed to Dart by `watch_library_changes`.
+#[derive(Debug, Clone)]
+pub enum FrbLibraryChange {
+    Added { game: FrbGameInfo },
+    Updated { game: FrbGameInfo },
+    Removed { path: String },
+}
+
+impl From<LibraryChange> for FrbLibraryChange {
+    fn from(change: LibraryChange) -> Self {
+        match change {
+            LibraryChange::Added(game) => Self::Added { game: game.into() },
+            LibraryChange::Updated(game) => Self::Updated { game: game.into() },
+            LibraryChange::Removed { path } => Self::Removed {
+                path: path.to_string_lossy().into_owned(),
+            },
+        }
+    }
+}
+
+/// Scan progress pushed to Dart by `watch_discovery_progress`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrbDiscoveryProgress {
+    /// Scanners running right now, in start order.
+    pub active_scanners: Vec<String>,
+    pub scanners_completed: u32,
+    pub scanners_total: u32,
+    pub candidates_found: u64,
+    pub candidates_sized: u64,
+    pub elapsed_ms: u64,
+    pub is_scanning: bool,
+}
+
+impl From<DiscoveryProgress> for FrbDiscoveryProgress {
+    fn from(p: DiscoveryProgress) -> Self {
+        Self {
+            active_scanners: p.active_scanners.into_iter().map(String::from).collect(),
+            scanners_completed: p.scanners_completed as u32,
+            scanners_total: p.scanners_total as u32,
+            candidates_found: p.candidates_found,
+            candidates_sized: p.candidates_sized,
+            elapsed_ms: p.elapsed.as_millis() as u64,
+            is_scanning: p.scanning,
+        }
+    }
+}
+
+// ── Drive summaries ───────────────────────────────────────────────────
+
+/// Mirror of `StorageClass` for FRB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbStorageClass {
+    Hdd,
+    Ssd,
+    Unknown,
+}
+
+impl From<StorageClass> for FrbStorageClass {
+    fn from(class: StorageClass) -> Self {
+        match class {
+            StorageClass::Hdd => Self::Hdd,
+            StorageClass::Ssd => Self::Ssd,
+            StorageClass::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Mirror of `DriveType` for FRB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbDriveType {
+    Fixed,
+    Removable,
+    Network,
+    Unknown,
+}
+
+impl From<DriveType> for FrbDriveType {
+    fn from(drive_type: DriveType) -> Self {
+        match drive_type {
+            DriveType::Fixed => Self::Fixed,
+            DriveType::Removable => Self::Removable,
+            DriveType::Network => Self::Network,
+            DriveType::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Discovery stats cache diagnostics.
+#[derive(Debug, Clone)]
+pub struct FrbDiscoveryCacheStats {
+    pub entries: u32,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+    pub size_on_disk_bytes: u64,
+    /// Unix ms of the last write to disk, if the cache was ever persisted.
+    pub last_persist_ms: Option<i64>,
+}
+
+impl From<CacheStats> for FrbDiscoveryCacheStats {
+    fn from(s: CacheStats) -> Self {
+        Self {
+            entries: s.entries as u32,
+            hits: s.hits,
+            misses: s.misses,
+            hit_rate: s.hit_rate,
+            size_on_disk_bytes: s.size_on_disk_bytes,
+            last_persist_ms: s.last_persist_ms.map(|ms| ms as i64),
+        }
+    }
+}
+
+/// Per-volume space and compression rollup.
+#[derive(Debug, Clone)]
+pub struct FrbDriveSummary {
+    pub volume: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub storage_class: FrbStorageClass,
+    pub game_count: u32,
+    pub compressed_game_count: u32,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    pub bytes_saved: u64,
+    pub projected_savings_bytes: u64,
+}
+
+impl From<DriveSummary> for FrbDriveSummary {
+    fn from(s: DriveSummary) -> Self {
+        Self {
+            volume: s.volume,
+            total_bytes: s.total_bytes,
+            free_bytes: s.free_bytes,
+            storage_class: s.storage_class.into(),
+            game_count: s.game_count,
+            compressed_game_count: s.compressed_game_count,
+            compressed_bytes: s.compressed_bytes,
+            uncompressed_bytes: s.uncompressed_bytes,
+            bytes_saved: s.bytes_saved,
+            projected_savings_bytes: s.projected_savings_bytes,
+        }
+    }
+}
+
+// ── Duplicate installs ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbDuplicateReason {
+    SteamAppId,
+    NameAndSize,
+}
+
+impl From<DuplicateReason> for FrbDuplicateReason {
+    fn from(reason: DuplicateReason) -> Self {
+        match reason {
+            DuplicateReason::SteamAppId => Self::SteamAppId,
+            DuplicateReason::NameAndSize => Self::NameAndSize,
+        }
+    }
+}
+
+/// One game installed in several places. Each copy carries its own size
+/// and compression state.
+#[derive(Debug, Clone)]
+pub struct FrbDuplicateGroup {
+    pub name: String,
+    pub steam_app_id: Option<u32>,
+    pub reason: FrbDuplicateReason,
+    pub copies: Vec<FrbGameInfo>,
+    pub reclaimable_bytes: u64,
+}
+
+impl From<DuplicateGroup> for FrbDuplicateGroup {
+    fn from(g: DuplicateGroup) -> Self {
+        Self {
+            name: g.name,
+            steam_app_id: g.steam_app_id,
+            reason: g.reason.into(),
+            copies: g.copies.into_iter().map(FrbGameInfo::from).collect(),
+            reclaimable_bytes: g.reclaimable_bytes,
+        }
+    }
+}
+
+// ── Library export ────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbLibraryExportFormat {
+    Json,
+    Csv,
+}
+
+impl From<FrbLibraryExportFormat> for LibraryExportFormat {
+    fn from(format: FrbLibraryExportFormat) -> Self {
+        match format {
+            FrbLibraryExportFormat::Json => Self::Json,
+            FrbLibraryExportFormat::Csv => Self::Csv,
         }
     }
 }
@@ -75,6 +319,7 @@ pub enum FrbPlatform {
     XboxGamePass,
     Custom,
     Application,
+    RiotGames,
 }
 
 impl From<Platform> for FrbPlatform {
@@ -89,6 +334,7 @@ impl From<Platform> for FrbPlatform {
             Platform::XboxGamePass => Self::XboxGamePass,
             Platform::Custom => Self::Custom,
             Platform::Application => Self::Application,
+            Platform::RiotGames => Self::RiotGames,
         }
     }
 }
@@ -105,6 +351,7 @@ impl From<FrbPlatform> for Platform {
             FrbPlatform::XboxGamePass => Self::XboxGamePass,
             FrbPlatform::Custom => Self::Custom,
             FrbPlatform::Application => Self::Application,
+            FrbPlatform::RiotGames => Self::RiotGames,
         }
     }
 }
@@ -131,6 +378,17 @@ impl From<FrbCompressionAlgorithm> for CompressionAlgorithm {
     }
 }
 
+impl From<CompressionAlgorithm> for FrbCompressionAlgorithm {
+    fn from(a: CompressionAlgorithm) -> Self {
+        match a {
+            CompressionAlgorithm::Xpress4K => Self::Xpress4K,
+            CompressionAlgorithm::Xpress8K => Self::Xpress8K,
+            CompressionAlgorithm::Xpress16K => Self::Xpress16K,
+            CompressionAlgorithm::Lzx => Self::Lzx,
+        }
+    }
+}
+
 // ── Progress snapshot ─────────────────────────────────────────────────
 
 /// FRB-compatible progress (Duration -> i64 millis).
@@ -143,6 +401,7 @@ pub struct FrbCompressionProgress {
     pub bytes_compressed: u64,
     pub bytes_saved: u64,
     pub estimated_time_remaining_ms: Option<i64>,
+    pub throughput_bytes_per_sec: u64,
     pub is_complete: bool,
 }
 
@@ -156,11 +415,31 @@ impl From<CompressionProgress> for FrbCompressionProgress {
             bytes_compressed: p.bytes_compressed,
             bytes_saved: p.bytes_saved,
             estimated_time_remaining_ms: p.estimated_time_remaining.map(|d| d.as_millis() as i64),
+            throughput_bytes_per_sec: p.throughput_bytes_per_sec,
             is_complete: p.is_complete,
         }
     }
 }
 
+/// What a manual job is doing to its game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbCompressionJobKind {
+    Compress,
+    Decompress,
+    /// A sampled estimate or algorithm benchmark; the game is only read.
+    Sample,
+}
+
+/// A running manual compression, decompression or sampling job.
+#[derive(Debug, Clone)]
+pub struct FrbCompressionJob {
+    pub job_id: u64,
+    pub game_path: String,
+    pub kind: FrbCompressionJobKind,
+    /// `None` until the job reports its first progress.
+    pub progress: Option<FrbCompressionProgress>,
+}
+
 // ── Compression stats ─────────────────────────────────────────────────
 
 /// FRB-compatible compression result stats.
@@ -170,16 +449,88 @@ pub struct FrbCompressionStats {
     pub compressed_bytes: u64,
     pub files_processed: u64,
     pub files_skipped: u64,
+    pub files_skipped_cloud: u64,
+    pub files_skipped_permission: u64,
+    pub files_skipped_encrypted: u64,
+    pub files_skipped_sparse: u64,
+    /// The first skipped encrypted and sparse files; the counts above
+    /// cover the rest.
+    pub skipped_encrypted_paths: Vec<String>,
+    pub skipped_sparse_paths: Vec<String>,
+    /// Bytes in alternate data streams, which WOF does not compress and
+    /// the byte totals leave out.
+    pub alternate_stream_bytes: u64,
+    /// Alternate streams are a large enough share of the folder that the
+    /// savings shown overstate what compression achieved on disk.
+    pub has_significant_alternate_streams: bool,
+    /// Files that were already compressed before this run.
+    pub files_already_compressed: u64,
+    /// Logical size of `files_already_compressed`.
+    pub bytes_already_compressed: u64,
+    /// Savings from this run alone; `original_bytes - compressed_bytes`
+    /// is the total saved on disk.
+    pub bytes_saved_this_run: u64,
+    /// The run was cancelled; the other fields cover the files that
+    /// finished before it stopped.
+    pub was_cancelled: bool,
     pub duration_ms: u64,
 }
 
 impl From<CompressionStats> for FrbCompressionStats {
     fn from(s: CompressionStats) -> Self {
+        let (encrypted, sparse): (Vec<_>, Vec<_>) = s
+            .attribute_skips
+            .iter()
+            .partition(|skip| skip.attribute == IncompatibleAttribute::Encrypted);
+        let paths = |skips: Vec<&AttributeSkip>| {
+            skips
+                .into_iter()
+                .map(|skip| skip.path.to_string_lossy().into_owned())
+                .collect()
+        };
         Self {
             original_bytes: s.original_bytes,
             compressed_bytes: s.compressed_bytes,
             files_processed: s.files_processed,
             files_skipped: s.files_skipped,
+            files_skipped_cloud: s.files_skipped_cloud,
+            files_skipped_permission: s.files_skipped_permission,
+            files_skipped_encrypted: s.files_skipped_encrypted,
+            files_skipped_sparse: s.files_skipped_sparse,
+            skipped_encrypted_paths: paths(encrypted),
+            skipped_sparse_paths: paths(sparse),
+            alternate_stream_bytes: s.alternate_stream_bytes,
+            has_significant_alternate_streams: s.has_significant_alternate_streams(),
+            files_already_compressed: s.files_already_compressed,
+            bytes_already_compressed: s.bytes_already_compressed,
+            bytes_saved_this_run: s.bytes_saved_this_run(),
+            was_cancelled: s.was_cancelled,
+            duration_ms: s.duration_ms,
+        }
+    }
+}
+
+/// FRB-compatible decompression result stats.
+#[derive(Debug, Clone)]
+pub struct FrbDecompressionStats {
+    /// Files whose WOF backing was removed.
+    pub files_decompressed: u64,
+    /// Logical size of `files_decompressed`.
+    pub bytes_restored_logical: u64,
+    /// Growth in space used on disk.
+    pub bytes_grown_physical: u64,
+    /// The run was cancelled; the other fields are zero.
+    pub was_cancelled: bool,
+    pub duration_ms: u64,
+}
+
+impl From<DecompressionStats> for FrbDecompressionStats {
+    fn from(s: DecompressionStats) -> Self {
+        Self {
+            files_decompressed: s.files_decompressed,
+            bytes_restored_logical: s.bytes_restored_logical,
+            bytes_grown_physical: s.bytes_grown_physical,
+            was_cancelled: false,
             duration_ms: s.duration_ms,
         }
     }
@@ -198,12 +549,18 @@ pub struct FrbCompressionEstimate {
     pub adaptive_applied: bool,
     pub community_samples: Option<u32>,
     pub community_lookup_pending: bool,
+    /// Expected savings range around `estimated_saved_bytes`.
+    pub estimated_saved_low_bytes: u64,
+    pub estimated_saved_high_bytes: u64,
+    /// 0.0 (heuristic only) to 1.0 (backed by this game's own history).
+    pub confidence: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrbCompressionEstimateSource {
     Heuristic,
     CommunityDb,
+    Sampled,
 }
 
 /// Optional metadata that helps the estimator look up community-sourced ratios.
@@ -219,6 +576,7 @@ impl From<CompressionEstimateSource> for FrbCompressionEstimateSource {
         match source {
             CompressionEstimateSource::Heuristic => Self::Heuristic,
             CompressionEstimateSource::CommunityDb => Self::CommunityDb,
+            CompressionEstimateSource::Sampled => Self::Sampled,
         }
     }
 }
@@ -239,6 +597,415 @@ impl From<CompressionEstimate> for FrbCompressionEstimate {
             adaptive_applied: e.adaptive_applied,
             community_samples: e.community_samples,
             community_lookup_pending: e.community_lookup_pending,
+            estimated_saved_low_bytes: e.estimated_saved_low_bytes,
+            estimated_saved_high_bytes: e.estimated_saved_high_bytes,
+            confidence: e.confidence,
+        }
+    }
+}
+
+/// One algorithm's row in a benchmark comparison.
+#[derive(Debug, Clone)]
+pub struct FrbAlgorithmBenchmark {
+    pub algorithm: FrbCompressionAlgorithm,
+    pub compressed_bytes: u64,
+    /// Fraction of the sample saved, 0.0 to 1.0.
+    pub savings_ratio: f64,
+    pub throughput_bytes_per_sec: u64,
+    pub duration_ms: u64,
+    /// Savings projected onto the whole game.
+    pub projected_saved_bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbBenchmarkReport {
+    pub sampled_files: u64,
+    pub sample_bytes: u64,
+    pub compressible_bytes: u64,
+    /// Fastest algorithm first.
+    pub results: Vec<FrbAlgorithmBenchmark>,
+    pub recommended: Option<FrbCompressionAlgorithm>,
+}
+
+impl From<AlgorithmBenchmark> for FrbAlgorithmBenchmark {
+    fn from(b: AlgorithmBenchmark) -> Self {
+        Self {
+            algorithm: b.algorithm.into(),
+            compressed_bytes: b.compressed_bytes,
+            savings_ratio: b.savings_ratio,
+            throughput_bytes_per_sec: b.throughput_bytes_per_sec,
+            duration_ms: b.duration_ms,
+            projected_saved_bytes: b.projected_saved_bytes,
+        }
+    }
+}
+
+impl From<BenchmarkReport> for FrbBenchmarkReport {
+    fn from(r: BenchmarkReport) -> Self {
+        Self {
+            sampled_files: r.sampled_files,
+            sample_bytes: r.sample_bytes,
+            compressible_bytes: r.compressible_bytes,
+            results: r.results.into_iter().map(Into::into).collect(),
+            recommended: r.recommended.map(Into::into),
+        }
+    }
+}
+
+// ── Compression history analytics ─────────────────────────────────────
+
+/// Filter for `get_compression_history`; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct FrbHistoryFilter {
+    pub game_path: Option<String>,
+    pub algorithm: Option<FrbCompressionAlgorithm>,
+    /// Inclusive lower bound, Unix ms.
+    pub since_ms: Option<i64>,
+    /// Exclusive upper bound, Unix ms.
+    pub until_ms: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+impl From<FrbHistoryFilter> for HistoryFilter {
+    fn from(f: FrbHistoryFilter) -> Self {
+        Self {
+            game_path: f.game_path.map(PathBuf::from),
+            algorithm: f.algorithm.map(Into::into),
+            since_ms: f.since_ms.map(|ms| ms.max(0) as u64),
+            until_ms: f.until_ms.map(|ms| ms.max(0) as u64),
+            limit: f.limit.map(|limit| limit as usize),
+        }
+    }
+}
+
+/// One recorded compression.
+#[derive(Debug, Clone)]
+pub struct FrbCompressionHistoryEntry {
+    pub game_path: String,
+    pub game_name: String,
+    pub timestamp_ms: i64,
+    pub algorithm: FrbCompressionAlgorithm,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    pub bytes_saved: u64,
+    pub estimated_saved_bytes: u64,
+    pub files_processed: u64,
+    pub duration_ms: u64,
+}
+
+impl From<CompressionHistoryEntry> for FrbCompressionHistoryEntry {
+    fn from(e: CompressionHistoryEntry) -> Self {
+        Self {
+            game_path: e.game_path,
+            game_name: e.game_name,
+            timestamp_ms: e.timestamp_ms as i64,
+            algorithm: e.algorithm.into(),
+            original_bytes: e.actual_stats.original_bytes,
+            compressed_bytes: e.actual_stats.compressed_bytes,
+            bytes_saved: e.actual_stats.actual_saved_bytes,
+            estimated_saved_bytes: e.estimate.estimated_saved_bytes,
+            files_processed: e.actual_stats.files_processed,
+            duration_ms: e.duration_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbSavingsTotals {
+    pub bytes_saved: u64,
+    pub original_bytes: u64,
+    pub games: u32,
+}
+
+impl From<SavingsTotals> for FrbSavingsTotals {
+    fn from(t: SavingsTotals) -> Self {
+        Self {
+            bytes_saved: t.bytes_saved,
+            original_bytes: t.original_bytes,
+            games: t.games as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbPlatformSavings {
+    /// `None` for games no longer in the library.
+    pub platform: Option<FrbPlatform>,
+    pub totals: FrbSavingsTotals,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbAlgorithmSavings {
+    pub algorithm: FrbCompressionAlgorithm,
+    pub totals: FrbSavingsTotals,
+}
+
+/// Lifetime savings; each game counts once, at its latest compression.
+#[derive(Debug, Clone)]
+pub struct FrbSavingsSummary {
+    pub total: FrbSavingsTotals,
+    pub compressions_recorded: u32,
+    pub by_platform: Vec<FrbPlatformSavings>,
+    pub by_algorithm: Vec<FrbAlgorithmSavings>,
+}
+
+impl From<SavingsSummary> for FrbSavingsSummary {
+    fn from(s: SavingsSummary) -> Self {
+        Self {
+            total: s.total.into(),
+            compressions_recorded: s.compressions_recorded as u32,
+            by_platform: s
+                .by_platform
+                .into_iter()
+                .map(|(platform, totals)| FrbPlatformSavings {
+                    platform: platform.map(Into::into),
+                    totals: totals.into(),
+                })
+                .collect(),
+            by_algorithm: s
+                .by_algorithm
+                .into_iter()
+                .map(|(algorithm, totals)| FrbAlgorithmSavings {
+                    algorithm: algorithm.into(),
+                    totals: totals.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbSavingsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+impl From<FrbSavingsBucket> for SavingsBucket {
+    fn from(b: FrbSavingsBucket) -> Self {
+        match b {
+            FrbSavingsBucket::Day => Self::Day,
+            FrbSavingsBucket::Week => Self::Week,
+            FrbSavingsBucket::Month => Self::Month,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbSavingsPoint {
+    /// Start of the bucket, Unix ms (UTC).
+    pub bucket_start_ms: i64,
+    pub bytes_saved: u64,
+    pub compressions: u32,
+}
+
+impl From<SavingsPoint> for FrbSavingsPoint {
+    fn from(p: SavingsPoint) -> Self {
+        Self {
+            bucket_start_ms: p.bucket_start_ms as i64,
+            bytes_saved: p.bytes_saved,
+            compressions: p.compressions as u32,
+        }
+    }
+}
+
+/// Raw-history limits; entries beyond them are compacted, then pruned.
+#[derive(Debug, Clone, Copy)]
+pub struct FrbHistoryRetention {
+    pub max_entries: u32,
+    /// `None` keeps entries regardless of age.
+    pub max_age_days: Option<u32>,
+}
+
+impl From<HistoryRetention> for FrbHistoryRetention {
+    fn from(r: HistoryRetention) -> Self {
+        Self {
+            max_entries: r.max_entries.min(u32::MAX as usize) as u32,
+            max_age_days: r.max_age_days,
+        }
+    }
+}
+
+impl From<FrbHistoryRetention> for HistoryRetention {
+    fn from(r: FrbHistoryRetention) -> Self {
+        Self {
+            max_entries: r.max_entries as usize,
+            max_age_days: r.max_age_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FrbHistoryPruneResult {
+    pub entries_removed: u32,
+    pub entries_kept: u32,
+}
+
+impl From<PruneOutcome> for FrbHistoryPruneResult {
+    fn from(o: PruneOutcome) -> Self {
+        Self {
+            entries_removed: o.entries_removed as u32,
+            entries_kept: o.entries_kept as u32,
+        }
+    }
+}
+
+// ── Interrupted operations ────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbOperationKind {
+    Compress,
+    Decompress,
+}
+
+impl From<OperationKind> for FrbOperationKind {
+    fn from(kind: OperationKind) -> Self {
+        match kind {
+            OperationKind::Compress => Self::Compress,
+            OperationKind::Decompress => Self::Decompress,
+        }
+    }
+}
+
+/// A compress or decompress run the app did not see finish. The game is
+/// left partially compressed until it is resumed or rolled back.
+#[derive(Debug, Clone)]
+pub struct FrbInterruptedOperation {
+    pub id: String,
+    pub kind: FrbOperationKind,
+    pub game_path: String,
+    pub game_name: String,
+    pub algorithm: FrbCompressionAlgorithm,
+    pub started_at_ms: i64,
+    /// Time of the last progress checkpoint before the interruption.
+    pub updated_at_ms: i64,
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+}
+
+impl From<OperationRecord> for FrbInterruptedOperation {
+    fn from(r: OperationRecord) -> Self {
+        Self {
+            game_name: r.display_name(),
+            id: r.id,
+            kind: r.kind.into(),
+            game_path: r.game_path.to_string_lossy().into_owned(),
+            algorithm: r.algorithm.into(),
+            started_at_ms: r.started_at_ms as i64,
+            updated_at_ms: r.updated_at_ms as i64,
+            files_total: r.files_total,
+            files_done: r.files_done,
+            bytes_total: r.bytes_total,
+            bytes_done: r.bytes_done,
+        }
+    }
+}
+
+// ── Game moves ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbMovePhase {
+    Preparing,
+    Copying,
+    Verifying,
+    Compressing,
+    RemovingSource,
+    RollingBack,
+    Complete,
+}
+
+impl From<MovePhase> for FrbMovePhase {
+    fn from(phase: MovePhase) -> Self {
+        match phase {
+            MovePhase::Preparing => Self::Preparing,
+            MovePhase::Copying => Self::Copying,
+            MovePhase::Verifying => Self::Verifying,
+            MovePhase::Compressing => Self::Compressing,
+            MovePhase::RemovingSource => Self::RemovingSource,
+            MovePhase::RollingBack => Self::RollingBack,
+            MovePhase::Complete => Self::Complete,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbMoveProgress {
+    pub phase: FrbMovePhase,
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+}
+
+impl From<&MoveProgress> for FrbMoveProgress {
+    fn from(p: &MoveProgress) -> Self {
+        Self {
+            phase: p.phase.into(),
+            files_total: p.files_total,
+            files_done: p.files_done,
+            bytes_total: p.bytes_total,
+            bytes_done: p.bytes_done,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrbMoveOutcome {
+    pub destination_path: String,
+    pub files_copied: u64,
+    pub bytes_copied: u64,
+    pub compression: Option<FrbCompressionStats>,
+    pub compression_error: Option<String>,
+    pub source_removed: bool,
+}
+
+impl From<MoveOutcome> for FrbMoveOutcome {
+    fn from(o: MoveOutcome) -> Self {
+        Self {
+            destination_path: o.destination.to_string_lossy().into_owned(),
+            files_copied: o.files_copied,
+            bytes_copied: o.bytes_copied,
+            compression: o.compression.map(Into::into),
+            compression_error: o.compression_error,
+            source_removed: o.source_removed,
+        }
+    }
+}
+
+/// Mirror of `DirectStorageConfidence` for FRB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbDirectStorageConfidence {
+    KnownList,
+    DsDllPresent,
+    MetadataHeuristic,
+}
+
+impl From<DirectStorageConfidence> for FrbDirectStorageConfidence {
+    fn from(confidence: DirectStorageConfidence) -> Self {
+        match confidence {
+            DirectStorageConfidence::KnownList => Self::KnownList,
+            DirectStorageConfidence::DsDllPresent => Self::DsDllPresent,
+            DirectStorageConfidence::MetadataHeuristic => Self::MetadataHeuristic,
+        }
+    }
+}
+
+/// Summary of the runtime DirectStorage known-games database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrbKnownGamesDatabase {
+    pub version: u32,
+    pub entries: u32,
+    pub rejected: u32,
+}
+
+impl From<DatabaseSummary> for FrbKnownGamesDatabase {
+    fn from(summary: DatabaseSummary) -> Self {
+        Self {
+            version: summary.version,
+            entries: summary.entries,
+            rejected: summary.rejected,
         }
     }
 }
@@ -248,16 +1015,57 @@ impl From<CompressionEstimate> for FrbCompressionEstimate {
 /// FRB-compatible compression error enum.
 #[derive(Debug)]
 pub enum FrbCompressionError {
-    LockedFile { path: String },
-    PermissionDenied { path: String },
+    LockedFile {
+        path: String,
+    },
+    PermissionDenied {
+        path: String,
+    },
     DiskFull,
-    PathNotFound { path: String },
-    NotADirectory { path: String },
+    PathNotFound {
+        path: String,
+    },
+    NotADirectory {
+        path: String,
+    },
     GameRunning,
     DirectStorageDetected,
-    WofApiError { message: String },
-    IoError { message: String },
+    WofApiError {
+        message: String,
+    },
+    IoError {
+        message: String,
+    },
     Cancelled,
+    ProtectedPackage {
+        path: String,
+    },
+    InsufficientSpace {
+        required_bytes: u64,
+        available_bytes: u64,
+    },
+    UnsupportedFilesystem {
+        path: String,
+        filesystem: String,
+    },
+    CompressionNotSupportedOnVolume {
+        path: String,
+    },
+    FileTooSmallForWof {
+        path: String,
+    },
+    EncryptedFile {
+        path: String,
+    },
+    SparseFile {
+        path: String,
+    },
+    IntegrityStreamFile {
+        path: String,
+    },
+    GameFolderLocked {
+        path: String,
+    },
 }
 
 impl From<CompressionError> for FrbCompressionError {
@@ -270,6 +1078,13 @@ impl From<CompressionError> for FrbCompressionError {
                 path: path.to_string_lossy().into_owned(),
             },
             CompressionError::DiskFull => Self::DiskFull,
+            CompressionError::InsufficientSpace {
+                required,
+                available,
+            } => Self::InsufficientSpace {
+                required_bytes: required,
+                available_bytes: available,
+            },
             CompressionError::PathNotFound(path) => Self::PathNotFound {
                 path: path.to_string_lossy().into_owned(),
             },
@@ -277,7 +1092,36 @@ impl From<CompressionError> for FrbCompressionError {
                 path: path.to_string_lossy().into_owned(),
             },
             CompressionError::GameRunning => Self::GameRunning,
+            CompressionError::GameFolderLocked { path } => Self::GameFolderLocked {
+                path: path.to_string_lossy().into_owned(),
+            },
             CompressionError::DirectStorageDetected => Self::DirectStorageDetected,
+            CompressionError::ProtectedPackage { path } => Self::ProtectedPackage {
+                path: path.to_string_lossy().into_owned(),
+            },
+            CompressionError::UnsupportedFilesystem { path, filesystem } => {
+                Self::UnsupportedFilesystem {
+                    path: path.to_string_lossy().into_owned(),
+                    filesystem,
+                }
+            }
+            CompressionError::CompressionNotSupportedOnVolume { path } => {
+                Self::CompressionNotSupportedOnVolume {
+                    path: path.to_string_lossy().into_owned(),
+                }
+            }
+            CompressionError::FileTooSmallForWof { path } => Self::FileTooSmallForWof {
+                path: path.to_string_lossy().into_owned(),
+            },
+            CompressionError::EncryptedFile { path } => Self::EncryptedFile {
+                path: path.to_string_lossy().into_owned(),
+            },
+            CompressionError::SparseFile { path } => Self::SparseFile {
+                path: path.to_string_lossy().into_owned(),
+            },
+            CompressionError::IntegrityStreamFile { path } => Self::IntegrityStreamFile {
+                path: path.to_string_lossy().into_owned(),
+            },
             CompressionError::WofApiError { message } => Self::WofApiError { message },
             CompressionError::Io { source } => Self::IoError {
                 message: source.to_string(),
@@ -287,6 +1131,38 @@ impl From<CompressionError> for FrbCompressionError {
     }
 }
 
+impl From<MoveError> for FrbCompressionError {
+    fn from(e: MoveError) -> Self {
+        match e {
+            MoveError::PathNotFound(path) => Self::PathNotFound {
+                path: path.to_string_lossy().into_owned(),
+            },
+            MoveError::NotADirectory(path) => Self::NotADirectory {
+                path: path.to_string_lossy().into_owned(),
+            },
+            MoveError::GameRunning => Self::GameRunning,
+            MoveError::InsufficientSpace {
+                required,
+                available,
+            } => Self::InsufficientSpace {
+                required_bytes: required,
+                available_bytes: available,
+            },
+            MoveError::Io { path, source }
+                if source.kind() == std::io::ErrorKind::PermissionDenied =>
+            {
+                Self::PermissionDenied {
+                    path: path.to_string_lossy().into_owned(),
+                }
+            }
+            MoveError::Cancelled => Self::Cancelled,
+            other => Self::IoError {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
 impl std::fmt::Display for FrbCompressionError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -300,6 +1176,27 @@ impl std::fmt::Display for FrbCompressionError {
             Self::WofApiError { message } => write!(f, "WOF error: {message}"),
             Self::IoError { message } => write!(f, "I/O error: {message}"),
             Self::Cancelled => write!(f, "Operation cancelled"),
+            Self::ProtectedPackage { path } => write!(f, "Protected UWP package: {path}"),
+            Self::InsufficientSpace {
+                required_bytes,
+                available_bytes,
+            } => write!(
+                f,
+                "Not enough free space: need {required_bytes} bytes, {available_bytes} available"
+            ),
+            Self::UnsupportedFilesystem { path, filesystem } => {
+                write!(f, "Unsupported filesystem ({filesystem}): {path}")
+            }
+            Self::CompressionNotSupportedOnVolume { path } => {
+                write!(f, "Volume does not support WOF compression: {path}")
+            }
+            Self::FileTooSmallForWof { path } => write!(f, "File too small to compress: {path}"),
+            Self::EncryptedFile { path } => write!(f, "Encrypted file: {path}"),
+            Self::SparseFile { path } => write!(f, "Sparse file: {path}"),
+            Self::IntegrityStreamFile { path } => write!(f, "File has an integrity stream: {path}"),
+            Self::GameFolderLocked { path } => {
+                write!(f, "Another Compact Games instance is working on: {path}")
+            }
         }
     }
 }
@@ -313,4 +1210,6 @@ pub enum FrbDiscoveryError {
     CustomScanFailed { path: String, message: String },
     #[error("Invalid custom scan path: {message}")]
     InvalidPath { message: String },
+    #[error("Library file '{path}' failed: {message}")]
+    LibraryFileFailed { path: String, message: String },
 }
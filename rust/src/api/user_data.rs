@@ -0,0 +1,138 @@
+//! User-data compression API exposed to Flutter via FRB.
+//!
+//! Save and config folders are compressed only after the user approves
+//! them in settings; see [`crate::compression::user_data`] for the
+//! safeguards. Progress and cancellation share the manual compression
+//! controls in [`super::compression`].
+
+use std::path::Path;
+
+use super::types::{FrbCompressionError, FrbCompressionProgress, FrbCompressionStats};
+use crate::compression::user_data::{self, UserDataKind, UserDataLocation, UserDataStats};
+use crate::frb_generated::StreamSink;
+use crate::settings::{self, UserDataSettings};
+
+/// Mirror of `UserDataSettings` for FRB.
+#[derive(Debug, Clone)]
+pub struct FrbUserDataSettings {
+    pub enabled: bool,
+    pub approved_paths: Vec<String>,
+    pub include_in_automation: bool,
+}
+
+impl From<UserDataSettings> for FrbUserDataSettings {
+    fn from(s: UserDataSettings) -> Self {
+        Self {
+            enabled: s.enabled,
+            approved_paths: s.approved_paths,
+            include_in_automation: s.include_in_automation,
+        }
+    }
+}
+
+impl From<&FrbUserDataSettings> for UserDataSettings {
+    fn from(s: &FrbUserDataSettings) -> Self {
+        Self {
+            enabled: s.enabled,
+            approved_paths: s.approved_paths.clone(),
+            include_in_automation: s.include_in_automation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrbUserDataKind {
+    MyGames,
+    SavedGames,
+    RoamingAppData,
+    LocalAppData,
+}
+
+impl From<UserDataKind> for FrbUserDataKind {
+    fn from(kind: UserDataKind) -> Self {
+        match kind {
+            UserDataKind::MyGames => Self::MyGames,
+            UserDataKind::SavedGames => Self::SavedGames,
+            UserDataKind::RoamingAppData => Self::RoamingAppData,
+            UserDataKind::LocalAppData => Self::LocalAppData,
+        }
+    }
+}
+
+/// A save or config folder found for a game.
+#[derive(Debug, Clone)]
+pub struct FrbUserDataLocation {
+    pub kind: FrbUserDataKind,
+    pub path: String,
+    pub approved: bool,
+}
+
+impl From<UserDataLocation> for FrbUserDataLocation {
+    fn from(location: UserDataLocation) -> Self {
+        Self {
+            kind: location.kind.into(),
+            path: location.path.to_string_lossy().into_owned(),
+            approved: location.approved,
+        }
+    }
+}
+
+/// Savings from the last compression of an approved folder.
+#[derive(Debug, Clone)]
+pub struct FrbUserDataStats {
+    pub path: String,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    pub saved_bytes: u64,
+    pub files_processed: u64,
+    pub compressed_at_ms: u64,
+}
+
+impl From<UserDataStats> for FrbUserDataStats {
+    fn from(stats: UserDataStats) -> Self {
+        Self {
+            saved_bytes: stats.saved_bytes(),
+            path: stats.path,
+            original_bytes: stats.original_bytes,
+            compressed_bytes: stats.compressed_bytes,
+            files_processed: stats.files_processed,
+            compressed_at_ms: stats.compressed_at_ms,
+        }
+    }
+}
+
+/// Save and config folders that look like they belong to `game_name`,
+/// each marked with whether the user approved it.
+pub fn find_user_data_locations(game_name: String) -> Vec<FrbUserDataLocation> {
+    let settings = settings::load().user_data;
+    user_data::locations_for_game(&game_name, &settings)
+        .into_iter()
+        .map(FrbUserDataLocation::from)
+        .collect()
+}
+
+/// Compress an approved save or config folder with Xpress4K. Fails when
+/// user data compression is off or the folder was not approved.
+pub fn compress_user_data(
+    path: String,
+    sink: StreamSink<FrbCompressionProgress>,
+) -> Result<FrbCompressionStats, FrbCompressionError> {
+    let path = Path::new(&path);
+    user_data::check_compressible(path, &settings::load().user_data).map_err(|e| {
+        FrbCompressionError::IoError {
+            message: e.to_string(),
+        }
+    })?;
+    super::compression::compress_user_data_with_progress(path, &mut |progress| {
+        sink.add(progress.clone().into()).is_ok()
+    })
+}
+
+/// Savings recorded for compressed user data folders, largest first. Kept
+/// apart from the game savings summary.
+pub fn get_user_data_stats() -> Vec<FrbUserDataStats> {
+    user_data::all_stats()
+        .into_iter()
+        .map(FrbUserDataStats::from)
+        .collect()
+}
@@ -0,0 +1,95 @@
+//! Time source for the automation scheduler.
+//!
+//! Settle windows, backoff and the folder-write hold all measure time
+//! through [`Clock`], so tests can drive them with [`VirtualClock`] instead
+//! of sleeping, and assert backoff deadlines exactly.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+pub trait Clock: Send + Sync {
+    /// Monotonic time, for deadlines within this run.
+    fn now(&self) -> Instant;
+    /// Wall-clock time, for timestamps shown to the user or persisted.
+    fn system_now(&self) -> SystemTime;
+}
+
+/// The real clocks.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    start: Instant,
+    system_start: SystemTime,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            system_start: SystemTime::now(),
+            elapsed: Arc::default(),
+        }
+    }
+
+    /// Move time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut elapsed = self.elapsed.lock().unwrap_or_else(|e| e.into_inner());
+        *elapsed += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.system_start + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_clock_moves_only_when_advanced() {
+        let clock = VirtualClock::new();
+        let shared = clock.clone();
+        let (start, system_start) = (clock.now(), clock.system_now());
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(clock.now(), start);
+
+        shared.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - start, Duration::from_secs(90));
+        assert_eq!(
+            clock.system_now().duration_since(system_start).unwrap(),
+            Duration::from_secs(90)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Estimated compression time for queued automation jobs.
+//!
+//! Rates come from the throughput samples past runs recorded, grouped by
+//! algorithm and storage class. Combinations without enough history fall
+//! back to conservative defaults scaled by how costly the algorithm is.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::history::{get_historical_stats, CompressionHistoryEntry};
+use crate::discovery::storage::{storage_class_for_path, StorageClass};
+
+/// Samples a combination needs before its median replaces the default.
+const MIN_SAMPLES: usize = 2;
+/// Newest samples per combination considered, so estimates follow drift.
+const LOOKBACK: usize = 24;
+
+/// Xpress4K throughput assumed per storage class without history.
+const DEFAULT_HDD_BYTES_PER_SEC: u64 = 80 * 1024 * 1024;
+const DEFAULT_SSD_BYTES_PER_SEC: u64 = 250 * 1024 * 1024;
+const DEFAULT_UNKNOWN_BYTES_PER_SEC: u64 = 150 * 1024 * 1024;
+
+/// Measured throughput per algorithm and storage class.
+#[derive(Debug, Default)]
+pub struct ThroughputTable {
+    rates: HashMap<(CompressionAlgorithm, StorageClass), u64>,
+}
+
+impl ThroughputTable {
+    /// Build the table from the compression history.
+    pub fn from_history() -> Self {
+        Self::from_entries(get_historical_stats(), storage_class_for_path)
+    }
+
+    fn from_entries(
+        mut entries: Vec<CompressionHistoryEntry>,
+        storage_of: impl Fn(&Path) -> StorageClass,
+    ) -> Self {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+        let mut samples: HashMap<(CompressionAlgorithm, StorageClass), Vec<u64>> = HashMap::new();
+        for entry in &entries {
+            let Some(throughput) = entry.throughput else {
+                continue;
+            };
+            let key = (entry.algorithm, storage_of(Path::new(&entry.game_path)));
+            let rates = samples.entry(key).or_default();
+            if rates.len() < LOOKBACK {
+                rates.push(throughput.bytes_per_sec);
+            }
+        }
+        let rates = samples
+            .into_iter()
+            .filter(|(_, rates)| rates.len() >= MIN_SAMPLES)
+            .map(|(key, mut rates)| {
+                rates.sort_unstable();
+                (key, rates[rates.len() / 2])
+            })
+            .collect();
+        Self { rates }
+    }
+
+    /// Expected bytes per second for `algorithm` on `storage`.
+    pub fn bytes_per_sec(&self, algorithm: CompressionAlgorithm, storage: StorageClass) -> u64 {
+        if let Some(&rate) = self.rates.get(&(algorithm, storage)) {
+            return rate.max(1);
+        }
+        let base = match storage {
+            StorageClass::Hdd => DEFAULT_HDD_BYTES_PER_SEC,
+            StorageClass::Ssd => DEFAULT_SSD_BYTES_PER_SEC,
+            StorageClass::Unknown => DEFAULT_UNKNOWN_BYTES_PER_SEC,
+        };
+        let cost = match algorithm {
+            CompressionAlgorithm::Xpress4K => 1.0,
+            CompressionAlgorithm::Xpress8K => 0.9,
+            CompressionAlgorithm::Xpress16K => 0.8,
+            CompressionAlgorithm::Lzx => 0.35,
+        };
+        (base as f64 * cost) as u64
+    }
+}
+
+/// Estimates job durations under the current automation settings.
+#[derive(Debug)]
+pub struct JobDurationEstimator {
+    table: ThroughputTable,
+    algorithm: CompressionAlgorithm,
+    max_bytes_per_sec: Option<u64>,
+}
+
+impl JobDurationEstimator {
+    pub fn new(
+        table: ThroughputTable,
+        algorithm: CompressionAlgorithm,
+        max_bytes_per_sec: Option<u64>,
+    ) -> Self {
+        Self {
+            table,
+            algorithm,
+            max_bytes_per_sec,
+        }
+    }
+
+    /// Expected throughput for a game at `game_path`, honouring the rate cap.
+    pub fn bytes_per_sec(&self, game_path: &Path) -> u64 {
+        let rate = self
+            .table
+            .bytes_per_sec(self.algorithm, storage_class_for_path(game_path));
+        match self.max_bytes_per_sec {
+            Some(cap) if cap > 0 => rate.min(cap),
+            _ => rate,
+        }
+    }
+
+    /// Expected time to compress `bytes` of the game at `game_path`.
+    pub fn estimate(&self, game_path: &Path, bytes: u64) -> Duration {
+        duration_for(bytes, self.bytes_per_sec(game_path))
+    }
+}
+
+/// Time to process `bytes` at `bytes_per_sec`.
+pub fn duration_for(bytes: u64, bytes_per_sec: u64) -> Duration {
+    Duration::from_secs_f64(bytes as f64 / bytes_per_sec.max(1) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::history::{ActualStats, EstimateSnapshot};
+    use crate::compression::thread_policy::ThroughputSample;
+
+    fn entry(
+        timestamp_ms: u64,
+        algorithm: CompressionAlgorithm,
+        bytes_per_sec: Option<u64>,
+    ) -> CompressionHistoryEntry {
+        CompressionHistoryEntry {
+            game_path: r"D:\Games\Sample".to_string(),
+            game_name: "Sample".to_string(),
+            timestamp_ms,
+            estimate: EstimateSnapshot {
+                scanned_files: 0,
+                sampled_bytes: 0,
+                estimated_saved_bytes: 0,
+            },
+            actual_stats: ActualStats {
+                original_bytes: 0,
+                compressed_bytes: 0,
+                actual_saved_bytes: 0,
+                files_processed: 0,
+            },
+            algorithm,
+            duration_ms: 0,
+            extensions: Vec::new(),
+            throughput: bytes_per_sec.map(|bytes_per_sec| ThroughputSample {
+                io_parallelism: 4,
+                bytes_per_sec,
+            }),
+        }
+    }
+
+    #[test]
+    fn median_of_history_replaces_the_default() {
+        let table = ThroughputTable::from_entries(
+            vec![
+                entry(1, CompressionAlgorithm::Lzx, Some(30)),
+                entry(2, CompressionAlgorithm::Lzx, Some(50)),
+                entry(3, CompressionAlgorithm::Lzx, Some(40)),
+                entry(4, CompressionAlgorithm::Lzx, None),
+                entry(5, CompressionAlgorithm::Xpress4K, Some(999)),
+            ],
+            |_| StorageClass::Hdd,
+        );
+
+        assert_eq!(
+            table.bytes_per_sec(CompressionAlgorithm::Lzx, StorageClass::Hdd),
+            40
+        );
+        // One sample is not enough to trust.
+        assert_eq!(
+            table.bytes_per_sec(CompressionAlgorithm::Xpress4K, StorageClass::Hdd),
+            DEFAULT_HDD_BYTES_PER_SEC
+        );
+    }
+
+    #[test]
+    fn defaults_slow_down_for_costlier_algorithms() {
+        let table = ThroughputTable::default();
+        let xpress = table.bytes_per_sec(CompressionAlgorithm::Xpress4K, StorageClass::Ssd);
+        let lzx = table.bytes_per_sec(CompressionAlgorithm::Lzx, StorageClass::Ssd);
+        assert!(lzx < xpress);
+    }
+
+    #[test]
+    fn rate_cap_bounds_the_estimate() {
+        let estimator = JobDurationEstimator::new(
+            ThroughputTable::default(),
+            CompressionAlgorithm::Xpress4K,
+            Some(1024 * 1024),
+        );
+        let estimate = estimator.estimate(Path::new(r"Z:\Games\Capped"), 60 * 1024 * 1024);
+        assert_eq!(estimate, Duration::from_secs(60));
+    }
+}
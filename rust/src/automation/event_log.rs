@@ -0,0 +1,300 @@
+//! Append-only audit log of what the automation service did.
+//!
+//! Unlike the pending-job journal, entries are never removed when a job
+//! finishes: each job start/outcome is appended as one JSON line so users
+//! can review overnight activity. The file is compacted to the newest
+//! entries once it grows past a size threshold.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+const LOG_FILE_NAME: &str = "automation_history.jsonl";
+/// File size that triggers compaction.
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+/// Number of newest entries kept by compaction.
+const COMPACT_RETAIN_EVENTS: usize = 2_000;
+
+/// What happened to an automation job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutomationEventKind {
+    JobStarted,
+    JobCompleted,
+    JobFailed,
+    JobSkipped,
+    /// A dry run estimated the savings without compressing.
+    JobSimulated,
+}
+
+/// A single automation audit record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutomationEvent {
+    pub timestamp_ms: u64,
+    pub kind: AutomationEventKind,
+    pub game_path: PathBuf,
+    pub game_name: Option<String>,
+    #[serde(default)]
+    pub original_bytes: Option<u64>,
+    #[serde(default)]
+    pub compressed_bytes: Option<u64>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Failure or skip reason.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl AutomationEvent {
+    pub fn new(kind: AutomationEventKind, game_path: PathBuf, game_name: Option<String>) -> Self {
+        Self {
+            timestamp_ms: crate::utils::unix_now_ms(),
+            kind,
+            game_path,
+            game_name,
+            original_bytes: None,
+            compressed_bytes: None,
+            duration_ms: None,
+            message: None,
+        }
+    }
+
+    /// Bytes reclaimed by a completed job.
+    pub fn bytes_saved(&self) -> Option<u64> {
+        Some(self.original_bytes?.saturating_sub(self.compressed_bytes?))
+    }
+}
+
+/// Query constraints for [`AutomationEventLog::query`]. Empty fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AutomationEventFilter {
+    pub kinds: Vec<AutomationEventKind>,
+    /// Case-insensitive substring matched against the game path.
+    pub path_contains: Option<String>,
+    pub since_ms: Option<u64>,
+}
+
+impl AutomationEventFilter {
+    fn matches(&self, event: &AutomationEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind) {
+            return false;
+        }
+        if self
+            .since_ms
+            .is_some_and(|since| event.timestamp_ms < since)
+        {
+            return false;
+        }
+        match self.path_contains.as_deref() {
+            Some(needle) if !needle.is_empty() => event
+                .game_path
+                .to_string_lossy()
+                .to_ascii_lowercase()
+                .contains(&needle.to_ascii_lowercase()),
+            _ => true,
+        }
+    }
+}
+
+/// Append-only JSON-lines event log.
+///
+/// Writes are serialized through an interior `Mutex`.
+pub struct AutomationEventLog {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+static DEFAULT_LOG: OnceLock<AutomationEventLog> = OnceLock::new();
+
+impl AutomationEventLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Process-wide log at `automation_history.jsonl` in the data directory.
+    pub fn global() -> &'static AutomationEventLog {
+        DEFAULT_LOG.get_or_init(|| {
+            let dir = crate::paths::data_dir_or_fallback();
+            Self::new(dir.join(LOG_FILE_NAME))
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one event, compacting the file first if it has grown too large.
+    pub fn append(&self, event: &AutomationEvent) -> Result<(), std::io::Error> {
+        let mut line = serde_json::to_string(event)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        line.push('\n');
+
+        let _guard = self.lock_writes();
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(&self.path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+            self.compact_locked()?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    /// Return up to `limit` matching events, newest first.
+    ///
+    /// Malformed lines (e.g. a torn final write) are skipped.
+    pub fn query(&self, limit: usize, filter: &AutomationEventFilter) -> Vec<AutomationEvent> {
+        let mut events = self.read_all();
+        events.retain(|event| filter.matches(event));
+        events.reverse();
+        events.truncate(limit);
+        events
+    }
+
+    /// Delete all recorded events.
+    pub fn clear(&self) -> Result<(), std::io::Error> {
+        let _guard = self.lock_writes();
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read_all(&self) -> Vec<AutomationEvent> {
+        let Ok(contents) = fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    fn compact_locked(&self) -> Result<(), std::io::Error> {
+        let events = self.read_all();
+        let skip = events.len().saturating_sub(COMPACT_RETAIN_EVENTS);
+        let mut contents = String::new();
+        for event in &events[skip..] {
+            if let Ok(line) = serde_json::to_string(event) {
+                contents.push_str(&line);
+                contents.push('\n');
+            }
+        }
+        crate::utils::atomic_write(&self.path, contents.as_bytes())
+    }
+
+    fn lock_writes(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.write_lock.lock().unwrap_or_else(|p| {
+            log::warn!("Automation event log lock poisoned; recovering");
+            p.into_inner()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_log(dir: &TempDir) -> AutomationEventLog {
+        AutomationEventLog::new(dir.path().join("history.jsonl"))
+    }
+
+    fn event_at(kind: AutomationEventKind, path: &str, timestamp_ms: u64) -> AutomationEvent {
+        AutomationEvent {
+            timestamp_ms,
+            ..AutomationEvent::new(kind, PathBuf::from(path), None)
+        }
+    }
+
+    #[test]
+    fn query_returns_newest_first_and_respects_limit() {
+        let dir = TempDir::new().unwrap();
+        let log = test_log(&dir);
+        for i in 0..5 {
+            log.append(&event_at(
+                AutomationEventKind::JobCompleted,
+                r"C:\Games\A",
+                1_000 + i,
+            ))
+            .unwrap();
+        }
+
+        let events = log.query(3, &AutomationEventFilter::default());
+
+        assert_eq!(
+            events.iter().map(|e| e.timestamp_ms).collect::<Vec<_>>(),
+            vec![1_004, 1_003, 1_002]
+        );
+    }
+
+    #[test]
+    fn query_filters_by_kind_path_and_time() {
+        let dir = TempDir::new().unwrap();
+        let log = test_log(&dir);
+        log.append(&event_at(
+            AutomationEventKind::JobStarted,
+            r"C:\Games\Alpha",
+            10,
+        ))
+        .unwrap();
+        log.append(&event_at(
+            AutomationEventKind::JobFailed,
+            r"C:\Games\Alpha",
+            20,
+        ))
+        .unwrap();
+        log.append(&event_at(
+            AutomationEventKind::JobFailed,
+            r"C:\Games\Beta",
+            30,
+        ))
+        .unwrap();
+
+        let filter = AutomationEventFilter {
+            kinds: vec![AutomationEventKind::JobFailed],
+            path_contains: Some("alpha".to_owned()),
+            since_ms: Some(15),
+        };
+        let events = log.query(10, &filter);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].timestamp_ms, 20);
+    }
+
+    #[test]
+    fn torn_trailing_line_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let log = test_log(&dir);
+        log.append(&event_at(AutomationEventKind::JobSkipped, r"C:\Games\A", 1))
+            .unwrap();
+        let mut file = OpenOptions::new().append(true).open(log.path()).unwrap();
+        file.write_all(br#"{"timestamp_ms":2,"kind":"#).unwrap();
+
+        assert_eq!(log.query(10, &AutomationEventFilter::default()).len(), 1);
+    }
+
+    #[test]
+    fn clear_removes_all_events() {
+        let dir = TempDir::new().unwrap();
+        let log = test_log(&dir);
+        log.append(&event_at(AutomationEventKind::JobStarted, r"C:\Games\A", 1))
+            .unwrap();
+
+        log.clear().unwrap();
+
+        assert!(log.query(10, &AutomationEventFilter::default()).is_empty());
+        log.clear().unwrap();
+    }
+}
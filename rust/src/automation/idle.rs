@@ -1,5 +1,6 @@
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
 /// Abstraction over system metrics for testability.
@@ -40,12 +41,67 @@ impl SystemMetricsSource for SysinfoSource {
     }
 }
 
+/// Abstraction over the user's keyboard and mouse activity for testability.
+pub trait InputActivitySource: Send {
+    /// Time since the last keyboard or mouse input, or `None` when it
+    /// cannot be read.
+    fn time_since_last_input(&mut self) -> Option<Duration>;
+}
+
+/// Real input source backed by `GetLastInputInfo`, which covers input to
+/// any window in the current session.
+pub struct LastInputInfoSource;
+
+impl InputActivitySource for LastInputInfoSource {
+    #[cfg(windows)]
+    fn time_since_last_input(&mut self) -> Option<Duration> {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        // SAFETY: `info` is a properly sized LASTINPUTINFO owned by this frame.
+        if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+            return None;
+        }
+        // Both are 32-bit tick counts; wrapping keeps this right across
+        // the 49.7-day rollover.
+        // SAFETY: GetTickCount has no preconditions.
+        let now = unsafe { GetTickCount() };
+        Some(Duration::from_millis(u64::from(
+            now.wrapping_sub(info.dwTime),
+        )))
+    }
+
+    #[cfg(not(windows))]
+    fn time_since_last_input(&mut self) -> Option<Duration> {
+        None
+    }
+}
+
+/// How the CPU and input signals combine when both are enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdlePolicy {
+    /// Idle only when the CPU is quiet and the user has been away.
+    #[default]
+    All,
+    /// Idle when either the CPU is quiet or the user has been away.
+    Any,
+}
+
 /// Configuration for idle detection thresholds.
 pub struct IdleConfig {
     /// CPU usage percentage below which the system is considered idle.
     pub cpu_threshold_percent: f32,
     /// How long CPU must stay below threshold before idle is confirmed.
     pub idle_duration: Duration,
+    /// How long without keyboard or mouse input before the user counts as
+    /// away. `None` ignores input and judges by CPU alone.
+    pub input_idle_duration: Option<Duration>,
+    pub policy: IdlePolicy,
 }
 
 impl Default for IdleConfig {
@@ -53,6 +109,8 @@ impl Default for IdleConfig {
         Self {
             cpu_threshold_percent: 40.0,
             idle_duration: Duration::from_secs(300), // 5 minutes
+            input_idle_duration: None,
+            policy: IdlePolicy::All,
         }
     }
 }
@@ -60,6 +118,7 @@ impl Default for IdleConfig {
 /// Monitors system metrics to determine whether the machine is idle.
 pub struct IdleDetector {
     metrics_source: Box<dyn SystemMetricsSource>,
+    input_source: Box<dyn InputActivitySource>,
     config: IdleConfig,
     idle_since: Option<Instant>,
 }
@@ -68,6 +127,7 @@ impl IdleDetector {
     pub fn new(config: IdleConfig) -> Self {
         Self {
             metrics_source: Box::new(SysinfoSource::new()),
+            input_source: Box::new(LastInputInfoSource),
             config,
             idle_since: None,
         }
@@ -79,12 +139,29 @@ impl IdleDetector {
     ) -> Self {
         Self {
             metrics_source,
+            input_source: Box::new(LastInputInfoSource),
             config,
             idle_since: None,
         }
     }
 
+    pub fn with_input_source(mut self, input_source: Box<dyn InputActivitySource>) -> Self {
+        self.input_source = input_source;
+        self
+    }
+
     pub fn is_idle(&mut self) -> bool {
+        let cpu_idle = self.is_cpu_idle();
+        let Some(input_idle) = self.is_input_idle() else {
+            return cpu_idle;
+        };
+        match self.config.policy {
+            IdlePolicy::All => cpu_idle && input_idle,
+            IdlePolicy::Any => cpu_idle || input_idle,
+        }
+    }
+
+    fn is_cpu_idle(&mut self) -> bool {
         let cpu_usage = self.metrics_source.global_cpu_usage();
 
         if cpu_usage < self.config.cpu_threshold_percent {
@@ -97,6 +174,14 @@ impl IdleDetector {
         }
     }
 
+    /// `None` when input is not part of the policy or cannot be read, so
+    /// the CPU signal decides alone.
+    fn is_input_idle(&mut self) -> Option<bool> {
+        let threshold = self.config.input_idle_duration?;
+        let since_input = self.input_source.time_since_last_input()?;
+        Some(since_input >= threshold)
+    }
+
     pub fn cpu_usage(&mut self) -> f32 {
         self.metrics_source.global_cpu_usage()
     }
@@ -138,10 +223,56 @@ impl SystemMetricsSource for MockMetricsSource {
     }
 }
 
+#[cfg(test)]
+pub struct MockInputSource {
+    pub since_input: Option<Duration>,
+}
+
+#[cfg(test)]
+impl InputActivitySource for MockInputSource {
+    fn time_since_last_input(&mut self) -> Option<Duration> {
+        self.since_input
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn detector_with(policy: IdlePolicy, cpu: f32, since_input: Option<Duration>) -> IdleDetector {
+        let config = IdleConfig {
+            cpu_threshold_percent: 10.0,
+            idle_duration: Duration::ZERO,
+            input_idle_duration: Some(Duration::from_secs(60)),
+            policy,
+        };
+        let mock = MockMetricsSource { cpu, memory: 1024 };
+        IdleDetector::with_metrics_source(config, Box::new(mock))
+            .with_input_source(Box::new(MockInputSource { since_input }))
+    }
+
+    #[test]
+    fn input_policy_combines_cpu_and_input() {
+        let away = Some(Duration::from_secs(120));
+        let present = Some(Duration::from_secs(5));
+
+        // Busy but unattended: only ANY calls it idle.
+        assert!(!detector_with(IdlePolicy::All, 90.0, away).is_idle());
+        assert!(detector_with(IdlePolicy::Any, 90.0, away).is_idle());
+
+        // Quiet CPU while the user is at the keyboard.
+        assert!(!detector_with(IdlePolicy::All, 1.0, present).is_idle());
+        assert!(detector_with(IdlePolicy::Any, 1.0, present).is_idle());
+
+        assert!(detector_with(IdlePolicy::All, 1.0, away).is_idle());
+    }
+
+    #[test]
+    fn unreadable_input_falls_back_to_cpu() {
+        assert!(detector_with(IdlePolicy::All, 1.0, None).is_idle());
+        assert!(!detector_with(IdlePolicy::Any, 90.0, None).is_idle());
+    }
+
     #[test]
     fn default_config_has_expected_values() {
         let config = IdleConfig::default();
@@ -155,6 +286,7 @@ mod tests {
         let config = IdleConfig {
             cpu_threshold_percent: 100.0,
             idle_duration: Duration::from_millis(50),
+            ..IdleConfig::default()
         };
         let mock = MockMetricsSource {
             cpu: 5.0,
@@ -174,6 +306,7 @@ mod tests {
         let config = IdleConfig {
             cpu_threshold_percent: 0.0,
             idle_duration: Duration::from_millis(1),
+            ..IdleConfig::default()
         };
         let mock = MockMetricsSource {
             cpu: 50.0,
@@ -204,6 +337,7 @@ mod tests {
         let config = IdleConfig {
             cpu_threshold_percent: 100.0,
             idle_duration: Duration::from_millis(1),
+            ..IdleConfig::default()
         };
         let mock = MockMetricsSource {
             cpu: 5.0,
@@ -220,6 +354,7 @@ mod tests {
         detector.update_config(IdleConfig {
             cpu_threshold_percent: 100.0,
             idle_duration: Duration::from_secs(999),
+            ..IdleConfig::default()
         });
         assert!(!detector.is_idle());
     }
@@ -237,6 +372,7 @@ mod property_tests {
             let config = IdleConfig {
                 cpu_threshold_percent: 10.0,
                 idle_duration: Duration::from_millis(1),
+                ..IdleConfig::default()
             };
             let mock = MockMetricsSource { cpu, memory: 1024 };
             let mut detector = IdleDetector::with_metrics_source(config, Box::new(mock));
@@ -255,6 +391,7 @@ mod property_tests {
             let config = IdleConfig {
                 cpu_threshold_percent: 10.0,
                 idle_duration: Duration::from_millis(200),
+                ..IdleConfig::default()
             };
             // Use a changeable mock
             struct AlternatingCpu {
@@ -0,0 +1,174 @@
+//! One automation service per data directory.
+//!
+//! Launching the app twice, or the app next to the daemon, would otherwise
+//! start two watchers over the same journal and queue every game twice.
+//! The service holds a named kernel object keyed on the data directory,
+//! visible to every process and session, and records its owner in
+//! `automation.lock` so a refusal can say who is running.
+//!
+//! The OS drops the kernel object when its holder dies. The owner record
+//! does not go away by itself, so a record whose process has exited, or
+//! whose PID now belongs to a newer process, is treated as stale and
+//! replaced. Where named objects are unavailable the record alone guards
+//! the service.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::utils::{normalize_path_key, stable_hash, try_named_lock, NamedLock};
+
+const OWNER_FILE_NAME: &str = "automation.lock";
+
+/// The process running automation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceOwner {
+    pub pid: u32,
+    /// File name of the executable, e.g. `pressplay-daemon.exe`.
+    pub executable: String,
+    pub started_at_ms: u64,
+}
+
+impl InstanceOwner {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            executable: std::env::current_exe()
+                .ok()
+                .and_then(|exe| {
+                    exe.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                })
+                .unwrap_or_default(),
+            started_at_ms: crate::utils::unix_now_ms(),
+        }
+    }
+}
+
+impl std::fmt::Display for InstanceOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.executable.is_empty() {
+            write!(f, "process {}", self.pid)
+        } else {
+            write!(f, "{} (pid {})", self.executable, self.pid)
+        }
+    }
+}
+
+/// Automation already runs in another process for this data directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlreadyRunning {
+    /// `None` when the holder has not recorded itself yet.
+    pub owner: Option<InstanceOwner>,
+}
+
+impl std::fmt::Display for AlreadyRunning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.owner {
+            Some(owner) => write!(f, "automation is already running in {owner}"),
+            None => write!(f, "automation is already running in another process"),
+        }
+    }
+}
+
+/// Held while this process runs automation.
+#[must_use = "the service is unlocked when the guard drops"]
+pub struct InstanceGuard {
+    /// `None` when named objects are unavailable.
+    _lock: Option<NamedLock>,
+    owner_path: Option<PathBuf>,
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        if let Some(path) = &self.owner_path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Claim automation for this process's data directory.
+pub fn acquire() -> Result<InstanceGuard, AlreadyRunning> {
+    let dir = crate::paths::data_dir_or_fallback();
+    let hash = stable_hash(&normalize_path_key(&dir));
+    let name = format!("Global\\CompactGames.Automation.{hash:016x}");
+    let owner_path = dir.join(OWNER_FILE_NAME);
+
+    let lock = match try_named_lock(&name) {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            return Err(AlreadyRunning {
+                owner: read_owner(&owner_path).filter(is_alive),
+            })
+        }
+        Err(e) => {
+            log::warn!("Automation instance lock unavailable; using the owner file alone: {e}");
+            if let Some(owner) = read_owner(&owner_path).filter(is_alive) {
+                if owner.pid != std::process::id() {
+                    return Err(AlreadyRunning { owner: Some(owner) });
+                }
+            }
+            None
+        }
+    };
+
+    if let Some(stale) = read_owner(&owner_path) {
+        log::info!("Recovered stale automation lock left by {stale}");
+    }
+    let written = fs::create_dir_all(&dir).and_then(|()| {
+        let json = serde_json::to_vec(&InstanceOwner::current()).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&owner_path, &json)
+    });
+    if let Err(e) = &written {
+        log::warn!("Failed to record automation owner: {e}");
+    }
+    Ok(InstanceGuard {
+        _lock: lock,
+        owner_path: written.is_ok().then_some(owner_path),
+    })
+}
+
+fn read_owner(path: &Path) -> Option<InstanceOwner> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+/// Whether `owner` is still the process that wrote the record, rather than
+/// an exited one or a newer process that reused its PID.
+fn is_alive(owner: &InstanceOwner) -> bool {
+    let pid = Pid::from_u32(owner.pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    system.process(pid).is_some_and(|process| {
+        // Start times are whole seconds; allow for the rounding.
+        process.start_time().saturating_mul(1_000) <= owner.started_at_ms + 1_000
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_record_is_live_only_for_its_own_process() {
+        let current = InstanceOwner::current();
+        assert!(is_alive(&current));
+
+        let reused_pid = InstanceOwner {
+            started_at_ms: 1_000,
+            ..current.clone()
+        };
+        assert!(!is_alive(&reused_pid), "process started after the record");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(OWNER_FILE_NAME);
+        assert_eq!(read_owner(&path), None);
+        fs::write(&path, serde_json::to_vec(&current).unwrap()).unwrap();
+        assert_eq!(read_owner(&path), Some(current));
+    }
+}
@@ -0,0 +1,68 @@
+//! Recent-write probe for a single game folder.
+//!
+//! A launcher's download can finish long before its post-install steps do:
+//! shader precompilation, redistributable installers and first-run patchers
+//! keep writing into the game folder after the settle window has ended.
+//! Compressing then means fighting those writers for the same files.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use walkdir::WalkDir;
+
+/// Files inspected per probe. Large games stop here rather than walking
+/// every file on each recheck; post-install writers touch shallow paths.
+const MAX_PROBE_ENTRIES: usize = 20_000;
+
+/// Directory depth inspected per probe.
+const MAX_PROBE_DEPTH: usize = 6;
+
+/// True when any file under `game_path` was written within `window`.
+///
+/// Creation time counts as a write: installers that extract archives keep
+/// the archived modification times, so only creation reflects the copy.
+pub fn has_recent_writes(game_path: &Path, window: Duration) -> bool {
+    if window.is_zero() {
+        return false;
+    }
+    let Some(cutoff) = SystemTime::now().checked_sub(window) else {
+        return false;
+    };
+    WalkDir::new(game_path)
+        .max_depth(MAX_PROBE_DEPTH)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .take(MAX_PROBE_ENTRIES)
+        .filter_map(|entry| entry.metadata().ok())
+        .any(|metadata| {
+            let modified = metadata.modified().ok();
+            let created = metadata.created().ok();
+            modified
+                .max(created)
+                .is_some_and(|written| written >= cutoff)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_file_counts_as_recent_write() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("shadercache")).unwrap();
+        std::fs::write(temp.path().join("shadercache").join("0001.bin"), b"x").unwrap();
+
+        assert!(has_recent_writes(temp.path(), Duration::from_secs(60)));
+        assert!(!has_recent_writes(temp.path(), Duration::ZERO));
+    }
+
+    #[test]
+    fn missing_folder_has_no_writes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(!has_recent_writes(
+            &temp.path().join("Gone"),
+            Duration::from_secs(60)
+        ));
+    }
+}
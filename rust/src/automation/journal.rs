@@ -1,16 +1,24 @@
 //! Crash-safe automation journal for pending compression jobs.
 //!
 //! Single serialized writer (Lesson 4): only one `JournalWriter` owns
-//! persistence. Writes use atomic file replace (write .tmp, then rename)
-//! to survive crashes.
+//! persistence. The default writer stores entries in the SQLite database
+//! (see [`crate::storage`]); path-based writers use [`crate::utils::atomic_write`]
+//! (synced temp file, then replace) to survive crashes.
+//!
+//! A journal file that still fails to parse (a torn write on a filesystem
+//! without atomic rename, a disk error) is not discarded: the readable
+//! entries are salvaged and the original is kept as a `.corrupt` backup.
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
+use super::scheduler::SchedulerSnapshot;
+use crate::storage::Database;
+
 /// What triggered this automation job.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JournalEventKind {
@@ -28,7 +36,7 @@ pub struct JournalEntry {
     pub game_path: PathBuf,
     pub game_name: Option<String>,
     pub event_kind: JournalEventKind,
-    /// Deduplication key: `"{canonical_path}:{update_epoch}"`.
+    /// Deduplication key from [`content_idempotency_key`].
     pub idempotency_key: String,
     pub queued_at: SystemTime,
 }
@@ -39,12 +47,7 @@ impl JournalEntry {
         game_name: Option<String>,
         event_kind: JournalEventKind,
     ) -> Self {
-        let epoch = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        let canonical = game_path.to_string_lossy().to_ascii_lowercase();
-        let idempotency_key = format!("{canonical}:{epoch}");
+        let idempotency_key = content_idempotency_key(&game_path);
         Self {
             game_path,
             game_name,
@@ -70,32 +73,83 @@ impl JournalEntry {
     }
 }
 
+/// Deduplication key for a job on `game_path`: `"{lowercase_path}:{digest}"`
+/// where the digest covers the folder's discovery change token. Events for
+/// unchanged content produce the same key, so they are not processed twice.
+pub fn content_idempotency_key(game_path: &Path) -> String {
+    let canonical = game_path.to_string_lossy().to_ascii_lowercase();
+    let token = crate::discovery::cache::compute_change_token(game_path, true);
+    format!("{canonical}:{}", token.digest())
+}
+
+/// What [`JournalWriter::load`] salvaged from an unreadable journal file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalRecovery {
+    pub recovered: usize,
+    pub dropped: usize,
+    /// Copy of the unreadable file; `None` when it could not be written.
+    pub backup_path: Option<PathBuf>,
+}
+
+enum JournalBackend {
+    Json(PathBuf),
+    Sqlite(Arc<Database>),
+}
+
 /// Durable writer for automation journal entries.
 ///
-/// Thread-safe via interior `Mutex`. Uses atomic file replace for
-/// crash safety: serialize to `.tmp`, then `fs::rename` over the real file.
+/// Thread-safe via interior `Mutex`. JSON-backed writers use atomic file
+/// replace for crash safety: serialize to a synced temp file, then replace
+/// the real file. SQLite-backed writers only insert/delete changed rows.
 pub struct JournalWriter {
-    path: PathBuf,
+    backend: JournalBackend,
     pending: Mutex<Vec<JournalEntry>>,
+    last_recovery: Mutex<Option<JournalRecovery>>,
 }
 
 impl JournalWriter {
     /// Create a new writer targeting the given journal file path.
     pub fn new(path: PathBuf) -> Self {
         Self {
-            path,
+            backend: JournalBackend::Json(path),
+            pending: Mutex::new(Vec::new()),
+            last_recovery: Mutex::new(None),
+        }
+    }
+
+    /// Create a writer that persists to the journal table of `database`.
+    pub fn with_database(database: Arc<Database>) -> Self {
+        Self {
+            backend: JournalBackend::Sqlite(database),
             pending: Mutex::new(Vec::new()),
+            last_recovery: Mutex::new(None),
         }
     }
 
-    /// Create a writer using the default `%APPDATA%/compact_games/automation_journal.json` path.
+    /// Create the default writer backed by the app database, importing a
+    /// legacy `automation_journal.json` in the data directory once.
+    ///
+    /// Falls back to that JSON file when the database cannot be opened.
     pub fn default_path() -> Result<Self, std::io::Error> {
-        let config_dir = dirs::config_dir().ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::NotFound, "no config directory found")
-        })?;
-        let compact_games_dir = config_dir.join("compact_games");
+        let compact_games_dir = crate::paths::data_dir()?;
         fs::create_dir_all(&compact_games_dir)?;
-        Ok(Self::new(compact_games_dir.join("automation_journal.json")))
+        let legacy_path = compact_games_dir.join("automation_journal.json");
+
+        let Some(database) = Database::global() else {
+            return Ok(Self::new(legacy_path));
+        };
+        if let Err(e) = database.import_legacy_journal(&legacy_path) {
+            log::warn!("Failed to import legacy automation journal: {e}");
+        }
+        Ok(Self::with_database(database))
+    }
+
+    /// Entries persisted by the default writer, read without touching the
+    /// writer owned by the automation service.
+    pub fn read_default() -> Result<Vec<JournalEntry>, std::io::Error> {
+        let writer = Self::default_path()?;
+        writer.load()?;
+        Ok(writer.snapshot())
     }
 
     /// Insert an entry, deduplicating by idempotency key.
@@ -126,7 +180,7 @@ impl JournalWriter {
 
     /// Remove all entries whose idempotency key starts with the given prefix.
     /// Used for GameUninstalled events where we need to remove all jobs for a path
-    /// regardless of the epoch suffix.
+    /// regardless of the content digest suffix.
     pub fn remove_by_prefix(&self, prefix: &str) {
         let mut pending = self.pending.lock().unwrap_or_else(|p| {
             log::warn!("Journal lock poisoned during remove_by_prefix; recovering");
@@ -157,31 +211,80 @@ impl JournalWriter {
         self.len() == 0
     }
 
-    /// Flush pending entries to disk using atomic file replace.
+    /// Flush pending entries to durable storage.
     ///
-    /// Writes to a `.tmp` file first, then renames over the target path.
+    /// JSON writers write to a synced temp file first, then replace the
+    /// target path.
     pub fn flush(&self) -> Result<(), std::io::Error> {
         let snapshot = self.snapshot();
+        let path = match &self.backend {
+            JournalBackend::Json(path) => path,
+            JournalBackend::Sqlite(database) => {
+                return database
+                    .sync_journal(&snapshot)
+                    .map_err(std::io::Error::other);
+            }
+        };
         let json = serde_json::to_string_pretty(&snapshot)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+        crate::utils::atomic_write(path, json.as_bytes())
+    }
 
-        let tmp_path = self.path.with_extension("json.tmp");
-
-        // Ensure parent directory exists
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent)?;
+    /// Save the scheduler snapshot alongside the entries. JSON writers
+    /// keep it in a sibling `.scheduler.json` file.
+    pub fn save_scheduler_snapshot(
+        &self,
+        snapshot: &SchedulerSnapshot,
+    ) -> Result<(), std::io::Error> {
+        match &self.backend {
+            JournalBackend::Json(path) => {
+                let json = serde_json::to_vec_pretty(snapshot).map_err(|e| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+                })?;
+                crate::utils::atomic_write(&scheduler_snapshot_path(path), &json)
+            }
+            JournalBackend::Sqlite(database) => database
+                .save_scheduler_snapshot(snapshot)
+                .map_err(std::io::Error::other),
         }
+    }
 
-        fs::write(&tmp_path, &json)?;
-        fs::rename(&tmp_path, &self.path)?;
-
-        Ok(())
+    /// The saved scheduler snapshot, or `None` when there is none or it
+    /// cannot be read.
+    pub fn load_scheduler_snapshot(&self) -> Option<SchedulerSnapshot> {
+        match &self.backend {
+            JournalBackend::Json(path) => {
+                let contents = fs::read(scheduler_snapshot_path(path)).ok()?;
+                serde_json::from_slice(&contents)
+                    .map_err(|e| log::warn!("Ignoring unreadable scheduler snapshot: {e}"))
+                    .ok()
+            }
+            JournalBackend::Sqlite(database) => database
+                .scheduler_snapshot()
+                .map_err(|e| log::warn!("Failed to read scheduler snapshot: {e}"))
+                .ok()
+                .flatten(),
+        }
     }
 
     /// Load entries from disk into this writer, deduplicating with any
     /// entries already in memory.
+    ///
+    /// A JSON file that fails to parse is salvaged entry by entry; see
+    /// [`Self::last_recovery`].
     pub fn load(&self) -> Result<usize, std::io::Error> {
-        let loaded = Self::load_from_path(&self.path)?;
+        let loaded = match &self.backend {
+            JournalBackend::Json(path) => {
+                let (entries, recovery) = Self::load_from_path_with_report(path)?;
+                if recovery.is_some() {
+                    *self.last_recovery.lock().unwrap_or_else(|p| p.into_inner()) = recovery;
+                }
+                entries
+            }
+            JournalBackend::Sqlite(database) => {
+                database.journal_entries().map_err(std::io::Error::other)?
+            }
+        };
         let mut pending = self.pending.lock().unwrap_or_else(|p| {
             log::warn!("Journal lock poisoned during load; recovering");
             p.into_inner()
@@ -201,12 +304,50 @@ impl JournalWriter {
         Ok(added)
     }
 
+    /// Report from the last [`Self::load`] that had to salvage entries.
+    pub fn last_recovery(&self) -> Option<JournalRecovery> {
+        self.last_recovery
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .clone()
+    }
+
     /// Load entries from a specific path (static, no lock needed).
     pub fn load_from_path(path: &Path) -> Result<Vec<JournalEntry>, std::io::Error> {
-        let contents = fs::read_to_string(path)?;
-        let entries: Vec<JournalEntry> = serde_json::from_str(&contents)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
-        Ok(entries)
+        Self::load_from_path_with_report(path).map(|(entries, _)| entries)
+    }
+
+    /// Load entries from `path`, salvaging what parses when the file as a
+    /// whole does not. The report is `None` for a clean read.
+    pub fn load_from_path_with_report(
+        path: &Path,
+    ) -> Result<(Vec<JournalEntry>, Option<JournalRecovery>), std::io::Error> {
+        let contents = fs::read(path)?;
+        if let Ok(entries) = serde_json::from_slice::<Vec<JournalEntry>>(&contents) {
+            return Ok((entries, None));
+        }
+
+        let backup = path.with_extension("json.corrupt");
+        let backup_path = match fs::write(&backup, &contents) {
+            Ok(()) => Some(backup),
+            Err(e) => {
+                log::warn!("Failed to back up unreadable automation journal: {e}");
+                None
+            }
+        };
+        let (entries, dropped) = recover_entries(&String::from_utf8_lossy(&contents));
+        log::warn!(
+            "Automation journal {} was unreadable; recovered {} entries, dropped {}",
+            path.display(),
+            entries.len(),
+            dropped
+        );
+        let recovery = JournalRecovery {
+            recovered: entries.len(),
+            dropped,
+            backup_path,
+        };
+        Ok((entries, Some(recovery)))
     }
 
     /// Replace all pending entries (used during restore from journal).
@@ -228,6 +369,54 @@ impl JournalWriter {
     }
 }
 
+/// Parse the entries of a journal array one at a time, skipping any that
+/// do not parse. Returns the readable entries and how many were dropped.
+///
+/// A syntax error resumes at the next entry in the layout `flush` writes
+/// (an object opening on its own line); a truncated tail ends the scan.
+pub(crate) fn recover_entries(contents: &str) -> (Vec<JournalEntry>, usize) {
+    let mut entries = Vec::new();
+    let mut dropped = 0;
+    let Some(start) = contents.find('[') else {
+        return (entries, usize::from(!contents.trim().is_empty()));
+    };
+    let mut rest = &contents[start + 1..];
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() || rest.starts_with(']') {
+            break;
+        }
+        let mut stream = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>();
+        match stream.next() {
+            Some(Ok(value)) => {
+                let consumed = stream.byte_offset();
+                match serde_json::from_value::<JournalEntry>(value) {
+                    Ok(entry) => entries.push(entry),
+                    Err(_) => dropped += 1,
+                }
+                rest = &rest[consumed..];
+            }
+            Some(Err(e)) if e.is_eof() => {
+                dropped += 1;
+                break;
+            }
+            Some(Err(_)) => {
+                dropped += 1;
+                match rest.find("\n  {") {
+                    Some(next) => rest = &rest[next + 1..],
+                    None => break,
+                }
+            }
+            None => break,
+        }
+    }
+    (entries, dropped)
+}
+
+fn scheduler_snapshot_path(journal_path: &Path) -> PathBuf {
+    journal_path.with_extension("scheduler.json")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +515,60 @@ mod tests {
         assert_eq!(loaded[0].idempotency_key, "key_1");
     }
 
+    #[test]
+    fn truncated_journal_keeps_complete_entries_and_a_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test_journal.json");
+        let writer = JournalWriter::new(path.clone());
+        for i in 0..3 {
+            writer.insert(JournalEntry::with_idempotency_key(
+                PathBuf::from(format!(r"C:\Games\Game{i}")),
+                None,
+                JournalEventKind::NewInstall,
+                format!("key_{i}"),
+            ));
+        }
+        writer.flush().unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        let cut = contents.rfind("key_2").unwrap();
+        fs::write(&path, &contents[..cut]).unwrap();
+
+        let restored = JournalWriter::new(path.clone());
+        assert_eq!(restored.load().unwrap(), 2);
+        let recovery = restored.last_recovery().expect("recovery report");
+        assert_eq!((recovery.recovered, recovery.dropped), (2, 1));
+        let backup = recovery.backup_path.expect("backup written");
+        assert_eq!(fs::read_to_string(backup).unwrap(), &contents[..cut]);
+    }
+
+    #[test]
+    fn unreadable_entry_is_skipped_without_losing_its_neighbours() {
+        let contents = r#"[
+  {
+    "game_path": "C:\\Games\\Good",
+    "game_name": null,
+    "event_kind": "NewInstall",
+    "idempotency_key": "good",
+    "queued_at": { "secs_since_epoch": 1, "nanos_since_epoch": 0 }
+  },
+  {
+    "game_path": "C:\\Games\\Bad",
+    "event_kind": "Unheard
+  },
+  {
+    "game_path": "C:\\Games\\Also",
+    "game_name": "Also",
+    "event_kind": "Reconcile",
+    "idempotency_key": "also",
+    "queued_at": { "secs_since_epoch": 2, "nanos_since_epoch": 0 }
+  }
+]"#;
+        let (entries, dropped) = recover_entries(contents);
+        let keys: Vec<_> = entries.iter().map(|e| e.idempotency_key.as_str()).collect();
+        assert_eq!(keys, vec!["good", "also"]);
+        assert_eq!(dropped, 1);
+    }
+
     #[test]
     fn remove_entry() {
         let dir = TempDir::new().unwrap();
@@ -379,4 +622,136 @@ mod tests {
         assert_eq!(added, 0);
         assert_eq!(writer.len(), 2);
     }
+
+    #[test]
+    fn sqlite_backend_roundtrips_inserts_and_removals() {
+        let database = Arc::new(Database::open_in_memory().unwrap());
+        let writer = JournalWriter::with_database(Arc::clone(&database));
+        for key in ["key_1", "key_2", "key_3"] {
+            writer.insert(JournalEntry::with_idempotency_key(
+                PathBuf::from(r"C:\Games\Game"),
+                None,
+                JournalEventKind::NewInstall,
+                key.to_string(),
+            ));
+        }
+        writer.flush().unwrap();
+        writer.remove("key_2");
+        writer.flush().unwrap();
+
+        let restored = JournalWriter::with_database(database);
+        assert_eq!(restored.load().unwrap(), 2);
+        let keys: Vec<_> = restored
+            .snapshot()
+            .into_iter()
+            .map(|e| e.idempotency_key)
+            .collect();
+        assert_eq!(keys, vec!["key_1", "key_3"]);
+    }
+
+    #[test]
+    fn legacy_json_journal_is_imported_into_sqlite() {
+        let dir = TempDir::new().unwrap();
+        let legacy = test_journal(&dir);
+        legacy.insert(JournalEntry::with_idempotency_key(
+            PathBuf::from(r"C:\Games\Game1"),
+            Some("Game 1".to_string()),
+            JournalEventKind::Reconcile,
+            "key_1".to_string(),
+        ));
+        legacy.flush().unwrap();
+
+        let database = Arc::new(Database::open_in_memory().unwrap());
+        let legacy_path = dir.path().join("test_journal.json");
+        assert_eq!(database.import_legacy_journal(&legacy_path).unwrap(), 1);
+
+        let writer = JournalWriter::with_database(database);
+        writer.load().unwrap();
+        assert_eq!(writer.snapshot()[0].game_name.as_deref(), Some("Game 1"));
+        assert!(!legacy_path.exists());
+    }
+}
+
+#[cfg(test)]
+mod property_tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::time::{Duration, UNIX_EPOCH};
+    use tempfile::TempDir;
+
+    fn entry_strategy() -> impl Strategy<Value = JournalEntry> {
+        (
+            "[A-Za-z0-9 _\\-]{1,24}",
+            proptest::option::of("\\PC{0,16}"),
+            prop_oneof![
+                Just(JournalEventKind::NewInstall),
+                Just(JournalEventKind::Reconcile),
+                Just(JournalEventKind::Opportunistic),
+            ],
+            0u64..4_000_000_000,
+        )
+            .prop_map(|(name, game_name, event_kind, secs)| JournalEntry {
+                game_path: PathBuf::from(format!(r"C:\Games\{name}")),
+                game_name,
+                event_kind,
+                idempotency_key: format!("c:\\games\\{}:{secs}", name.to_ascii_lowercase()),
+                queued_at: UNIX_EPOCH + Duration::from_secs(secs),
+            })
+    }
+
+    fn same(a: &JournalEntry, b: &JournalEntry) -> bool {
+        a.game_path == b.game_path
+            && a.game_name == b.game_name
+            && a.event_kind == b.event_kind
+            && a.idempotency_key == b.idempotency_key
+            && a.queued_at == b.queued_at
+    }
+
+    proptest! {
+        #[test]
+        fn flushed_entries_roundtrip(entries in proptest::collection::vec(entry_strategy(), 0..12)) {
+            let dir = TempDir::new().unwrap();
+            let path = dir.path().join("journal.json");
+            let writer = JournalWriter::new(path.clone());
+            writer.replace_all(entries.clone());
+            writer.flush().unwrap();
+
+            let (loaded, recovery) = JournalWriter::load_from_path_with_report(&path).unwrap();
+            prop_assert!(recovery.is_none());
+            prop_assert_eq!(loaded.len(), entries.len());
+            for (a, b) in loaded.iter().zip(&entries) {
+                prop_assert!(same(a, b));
+            }
+        }
+
+        /// Any truncation recovers a prefix of the entries, never panics,
+        /// and only loses entries the cut actually reached.
+        #[test]
+        fn truncation_recovers_a_prefix(
+            entries in proptest::collection::vec(entry_strategy(), 1..8),
+            cut_ratio in 0.0f64..1.0,
+        ) {
+            let json = serde_json::to_string_pretty(&entries).unwrap();
+            let mut cut = (json.len() as f64 * cut_ratio) as usize;
+            while !json.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let truncated = &json[..cut];
+
+            let (recovered, dropped) = recover_entries(truncated);
+            prop_assert!(recovered.len() <= entries.len());
+            for (a, b) in recovered.iter().zip(&entries) {
+                prop_assert!(same(a, b));
+            }
+            let complete = entries
+                .iter()
+                .take_while(|entry| {
+                    let key = serde_json::to_string(&entry.idempotency_key).unwrap();
+                    truncated.contains(&key)
+                })
+                .count();
+            prop_assert!(recovered.len() + 1 >= complete);
+            prop_assert!(dropped <= 1);
+        }
+    }
 }
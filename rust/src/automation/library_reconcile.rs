@@ -0,0 +1,264 @@
+//! Periodic re-check of compressed games for lost savings.
+//!
+//! Compression drifts: a launcher patch rewrites files uncompressed while
+//! the service is stopped, or another tool decompresses a game. Every
+//! interval, each game in the compression history has its on-disk ratio
+//! measured again. A game keeping too little of the savings its last
+//! compression achieved has its discovery metadata dropped, so the library
+//! shows the real size, and is handed back to automation. Games the user
+//! decompressed through the app are left alone.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::compression::engine::CompressionEngine;
+use crate::compression::history::{get_historical_stats, CompressionHistoryEntry};
+use crate::settings::LibraryReconcileSettings;
+use crate::utils::{normalize_path_key, unix_now_ms};
+
+const STATE_FILE_NAME: &str = "library_reconcile.json";
+/// Compressions that saved less than this are not worth re-checking.
+const MIN_EXPECTED_SAVINGS: f64 = 0.02;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReconcileState {
+    /// When the last check started; `None` before the first.
+    #[serde(default)]
+    last_run_ms: Option<u64>,
+    /// When the user last decompressed each game in the app, by path key.
+    #[serde(default)]
+    decompressed_at_ms: HashMap<String, u64>,
+}
+
+#[cfg(not(test))]
+static STATE_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+static STATE: LazyLock<RwLock<ReconcileState>> = LazyLock::new(|| RwLock::new(load_state()));
+
+/// A compressed game that lost part of its savings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftedGame {
+    pub game_path: PathBuf,
+    pub game_name: String,
+    /// Compressed to original size after its last compression.
+    pub expected_ratio: f64,
+    /// Compressed to original size now.
+    pub current_ratio: f64,
+}
+
+/// Whether a check is due under `settings`. The first call only starts the
+/// clock, so enabling the check does not walk the whole library at once.
+pub fn is_due(settings: &LibraryReconcileSettings) -> bool {
+    let Some(interval) = settings.interval() else {
+        return false;
+    };
+    let now_ms = unix_now_ms();
+    let last_run_ms = with_state_read(|state| state.last_run_ms);
+    if last_run_ms.is_none() {
+        with_state_write(|state| state.last_run_ms = Some(now_ms));
+        persist();
+        return false;
+    }
+    due(last_run_ms, interval, now_ms)
+}
+
+fn due(last_run_ms: Option<u64>, interval: Duration, now_ms: u64) -> bool {
+    last_run_ms.is_none_or(|last| now_ms.saturating_sub(last) >= interval.as_millis() as u64)
+}
+
+/// Note that the user decompressed `path`, so the check does not undo it.
+pub fn record_decompression(path: &Path) {
+    let key = normalize_path_key(path);
+    with_state_write(|state| {
+        state.decompressed_at_ms.insert(key, unix_now_ms());
+    });
+    persist();
+}
+
+/// Measure every game in the compression history and return the ones
+/// that kept less than `min_retained_savings` of their savings. Marks the
+/// check as run. Walks every file of every compressed game, so callers
+/// run it off the automation loop.
+pub fn find_drifted_games(min_retained_savings: f64) -> Vec<DriftedGame> {
+    let decompressed = with_state_write(|state| {
+        state.last_run_ms = Some(unix_now_ms());
+        state.decompressed_at_ms.clone()
+    });
+    persist();
+
+    let mut drifted = Vec::new();
+    for entry in latest_entries(get_historical_stats()) {
+        let key = normalize_path_key(Path::new(&entry.game_path));
+        if decompressed
+            .get(&key)
+            .is_some_and(|&at| at >= entry.timestamp_ms)
+        {
+            continue;
+        }
+        let path = PathBuf::from(&entry.game_path);
+        if !path.is_dir() {
+            continue;
+        }
+        let stats = &entry.actual_stats;
+        let expected_ratio = stats.compressed_bytes as f64 / stats.original_bytes.max(1) as f64;
+        let current_ratio = match CompressionEngine::get_compression_ratio(&path) {
+            Ok(ratio) => ratio,
+            Err(e) => {
+                log::debug!("Library reconcile: cannot measure {}: {e}", path.display());
+                continue;
+            }
+        };
+        if !has_drifted(expected_ratio, current_ratio, min_retained_savings) {
+            continue;
+        }
+        log::info!(
+            "Library reconcile: {} saves {:.0}% of its size, down from {:.0}%",
+            path.display(),
+            (1.0 - current_ratio) * 100.0,
+            (1.0 - expected_ratio) * 100.0
+        );
+        crate::discovery::cache::remove(&path);
+        crate::discovery::index::remove(&path);
+        drifted.push(DriftedGame {
+            game_path: path,
+            game_name: entry.game_name,
+            expected_ratio,
+            current_ratio,
+        });
+    }
+    if !drifted.is_empty() {
+        crate::discovery::cache::persist_if_dirty();
+        crate::discovery::index::persist_if_dirty();
+    }
+    drifted
+}
+
+/// Newest entry with real stats for each game.
+fn latest_entries(history: Vec<CompressionHistoryEntry>) -> Vec<CompressionHistoryEntry> {
+    let mut latest: HashMap<String, CompressionHistoryEntry> = HashMap::new();
+    for entry in history
+        .into_iter()
+        .filter(|entry| entry.actual_stats.original_bytes > 0)
+    {
+        let key = normalize_path_key(Path::new(&entry.game_path));
+        match latest.get(&key) {
+            Some(newer) if newer.timestamp_ms >= entry.timestamp_ms => {}
+            _ => {
+                latest.insert(key, entry);
+            }
+        }
+    }
+    latest.into_values().collect()
+}
+
+/// Whether a game compressed to `expected_ratio` and now at
+/// `current_ratio` kept less than `min_retained_savings` of its savings.
+fn has_drifted(expected_ratio: f64, current_ratio: f64, min_retained_savings: f64) -> bool {
+    let expected_savings = 1.0 - expected_ratio;
+    if expected_savings < MIN_EXPECTED_SAVINGS {
+        return false;
+    }
+    (1.0 - current_ratio) / expected_savings < min_retained_savings
+}
+
+fn with_state_read<R>(f: impl FnOnce(&ReconcileState) -> R) -> R {
+    let guard = STATE.read().unwrap_or_else(|poisoned| {
+        log::warn!("Library reconcile lock poisoned (read); recovering");
+        poisoned.into_inner()
+    });
+    f(&guard)
+}
+
+fn with_state_write<R>(f: impl FnOnce(&mut ReconcileState) -> R) -> R {
+    let mut guard = STATE.write().unwrap_or_else(|poisoned| {
+        log::warn!("Library reconcile lock poisoned (write); recovering");
+        poisoned.into_inner()
+    });
+    f(&mut guard)
+}
+
+fn persist() {
+    let snapshot = with_state_read(Clone::clone);
+    let result = state_path().and_then(|path| {
+        let json = serde_json::to_vec(&snapshot).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&path, &json)
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to persist library reconcile state: {e}");
+    }
+}
+
+fn load_state() -> ReconcileState {
+    let Ok(path) = state_path() else {
+        return ReconcileState::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return ReconcileState::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse library reconcile state: {e}");
+        ReconcileState::default()
+    })
+}
+
+fn state_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-library-reconcile-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(STATE_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !STATE_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            STATE_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(STATE_FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_is_measured_against_the_savings_achieved() {
+        // Compressed to 60%: 40% saved. Keeping 36% of it is 90% retained.
+        assert!(!has_drifted(0.6, 0.64, 0.8));
+        // A patch rewrote half the files: 20% saved is 50% retained.
+        assert!(has_drifted(0.6, 0.8, 0.8));
+        // Nothing worth restoring when compression barely helped.
+        assert!(!has_drifted(0.99, 1.0, 0.8));
+    }
+
+    #[test]
+    fn check_is_due_once_the_interval_passes() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let last = 1_000_000;
+        assert!(!due(Some(last), day, last + 1_000));
+        assert!(due(Some(last), day, last + day.as_millis() as u64));
+    }
+}
@@ -0,0 +1,201 @@
+//! Counters and gauges describing what automation has done since launch.
+//!
+//! The app reads a snapshot; homelab users can scrape the same numbers in
+//! Prometheus text format through the IPC server. Counters start at zero
+//! with each process and only grow.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::scheduler::SchedulerState;
+
+/// Every scheduler state, in the order they are exported.
+const ALL_STATES: [SchedulerState; 7] = [
+    SchedulerState::WaitingForEvents,
+    SchedulerState::WaitingForSettle,
+    SchedulerState::WaitingForIdle,
+    SchedulerState::SafetyCheck,
+    SchedulerState::Compressing,
+    SchedulerState::Paused,
+    SchedulerState::Backoff,
+];
+
+static JOBS_COMPLETED: AtomicU64 = AtomicU64::new(0);
+static JOBS_FAILED: AtomicU64 = AtomicU64::new(0);
+static JOBS_SKIPPED: AtomicU64 = AtomicU64::new(0);
+static BYTES_SAVED: AtomicU64 = AtomicU64::new(0);
+static ACTIVITY_CANCELLATIONS: AtomicU64 = AtomicU64::new(0);
+static QUEUE_DEPTH: AtomicU64 = AtomicU64::new(0);
+/// `None` while the service is stopped.
+static STATE: Mutex<Option<SchedulerState>> = Mutex::new(None);
+
+/// A point-in-time copy of the automation metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutomationMetrics {
+    pub jobs_completed: u64,
+    pub jobs_failed: u64,
+    pub jobs_skipped: u64,
+    /// Bytes freed by automation runs, excluding savings earlier runs
+    /// already had.
+    pub bytes_saved: u64,
+    /// Jobs cancelled because a game launched or user activity outlasted
+    /// the pause.
+    pub activity_cancellations: u64,
+    pub queue_depth: u64,
+    /// `None` while the service is stopped.
+    pub state: Option<SchedulerState>,
+}
+
+pub fn record_job_completed(bytes_saved: u64) {
+    JOBS_COMPLETED.fetch_add(1, Ordering::Relaxed);
+    BYTES_SAVED.fetch_add(bytes_saved, Ordering::Relaxed);
+}
+
+pub fn record_job_failed() {
+    JOBS_FAILED.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn record_job_skipped() {
+    JOBS_SKIPPED.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn record_activity_cancellation() {
+    ACTIVITY_CANCELLATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Publish the scheduler's gauges; `None` once the service stops.
+pub fn set_gauges(state: Option<SchedulerState>, queue_depth: usize) {
+    QUEUE_DEPTH.store(queue_depth as u64, Ordering::Relaxed);
+    *STATE.lock().unwrap_or_else(|poisoned| {
+        log::warn!("Automation metrics lock poisoned; recovering");
+        poisoned.into_inner()
+    }) = state;
+}
+
+pub fn snapshot() -> AutomationMetrics {
+    AutomationMetrics {
+        jobs_completed: JOBS_COMPLETED.load(Ordering::Relaxed),
+        jobs_failed: JOBS_FAILED.load(Ordering::Relaxed),
+        jobs_skipped: JOBS_SKIPPED.load(Ordering::Relaxed),
+        bytes_saved: BYTES_SAVED.load(Ordering::Relaxed),
+        activity_cancellations: ACTIVITY_CANCELLATIONS.load(Ordering::Relaxed),
+        queue_depth: QUEUE_DEPTH.load(Ordering::Relaxed),
+        state: *STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    }
+}
+
+impl AutomationMetrics {
+    /// The metrics in the Prometheus text exposition format. The scheduler
+    /// state is exported as one 0/1 series per state.
+    pub fn to_prometheus_text(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, u64)]| {
+            let _ = writeln!(out, "# HELP compact_games_automation_{name} {help}");
+            let _ = writeln!(out, "# TYPE compact_games_automation_{name} {kind}");
+            for (labels, value) in samples {
+                let _ = writeln!(out, "compact_games_automation_{name}{labels} {value}");
+            }
+        };
+
+        metric(
+            "jobs_total",
+            "counter",
+            "Automation jobs finished, by outcome.",
+            &[
+                ("{outcome=\"completed\"}", self.jobs_completed),
+                ("{outcome=\"failed\"}", self.jobs_failed),
+                ("{outcome=\"skipped\"}", self.jobs_skipped),
+            ],
+        );
+        metric(
+            "bytes_saved_total",
+            "counter",
+            "Bytes freed by automation runs.",
+            &[("", self.bytes_saved)],
+        );
+        metric(
+            "activity_cancellations_total",
+            "counter",
+            "Jobs cancelled because the user came back or a game launched.",
+            &[("", self.activity_cancellations)],
+        );
+        metric(
+            "queue_depth",
+            "gauge",
+            "Jobs waiting in the automation queue.",
+            &[("", self.queue_depth)],
+        );
+        metric(
+            "running",
+            "gauge",
+            "Whether the automation service is running.",
+            &[("", u64::from(self.state.is_some()))],
+        );
+        let labels: Vec<String> = ALL_STATES
+            .iter()
+            .map(|state| format!("{{state=\"{}\"}}", state_label(*state)))
+            .collect();
+        let states: Vec<(&str, u64)> = ALL_STATES
+            .iter()
+            .zip(&labels)
+            .map(|(state, label)| (label.as_str(), u64::from(self.state == Some(*state))))
+            .collect();
+        metric(
+            "state",
+            "gauge",
+            "Current scheduler state; 1 for the active state.",
+            &states,
+        );
+        out
+    }
+}
+
+fn state_label(state: SchedulerState) -> &'static str {
+    match state {
+        SchedulerState::WaitingForEvents => "waiting_for_events",
+        SchedulerState::WaitingForSettle => "waiting_for_settle",
+        SchedulerState::WaitingForIdle => "waiting_for_idle",
+        SchedulerState::SafetyCheck => "safety_check",
+        SchedulerState::Compressing => "compressing",
+        SchedulerState::Paused => "paused",
+        SchedulerState::Backoff => "backoff",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_outcomes_show_in_snapshot_and_exposition() {
+        let before = snapshot();
+        record_job_completed(1_000);
+        record_job_failed();
+        record_activity_cancellation();
+        let after = snapshot();
+        // Automation tests in other modules may record outcomes concurrently.
+        assert!(after.jobs_completed > before.jobs_completed);
+        assert!(after.bytes_saved >= before.bytes_saved + 1_000);
+        assert!(after.jobs_failed > before.jobs_failed);
+        assert!(after.activity_cancellations > before.activity_cancellations);
+
+        let text = AutomationMetrics {
+            jobs_completed: 3,
+            bytes_saved: 4_096,
+            queue_depth: 2,
+            state: Some(SchedulerState::Compressing),
+            ..AutomationMetrics::default()
+        }
+        .to_prometheus_text();
+        assert!(text.contains("# TYPE compact_games_automation_jobs_total counter\n"));
+        assert!(text.contains("compact_games_automation_jobs_total{outcome=\"completed\"} 3\n"));
+        assert!(text.contains("compact_games_automation_bytes_saved_total 4096\n"));
+        assert!(text.contains("compact_games_automation_queue_depth 2\n"));
+        assert!(text.contains("compact_games_automation_running 1\n"));
+        assert!(text.contains("compact_games_automation_state{state=\"compressing\"} 1\n"));
+        assert!(text.contains("compact_games_automation_state{state=\"paused\"} 0\n"));
+    }
+}
@@ -1,4 +1,15 @@
+pub mod clock;
+pub mod duration;
+pub mod event_log;
 pub mod idle;
+pub mod instance_lock;
+pub mod io_activity;
 pub mod journal;
+pub mod library_reconcile;
+pub mod metrics;
+pub mod notifications;
+pub mod reclaim;
 pub mod scheduler;
+pub mod thermal;
+pub mod wake;
 pub mod watcher;
@@ -0,0 +1,314 @@
+//! User-facing notifications for automation outcomes.
+//!
+//! Turns job results into short, structured messages (severity plus a
+//! suggested follow-up) that the UI shell can show as toasts.
+
+use std::path::{Path, PathBuf};
+
+/// How prominently a notification should be presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationSeverity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// Follow-up the user can take from a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestedAction {
+    None,
+    /// Open the automation history for details.
+    ViewHistory,
+    /// Close the game so the job can run on the next idle window.
+    CloseGame,
+    /// Re-run compression for the game manually.
+    RetryManually,
+}
+
+/// What triggered a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    JobCompleted,
+    JobFailed,
+    QueueDrained,
+    /// A game was decompressed after crashing repeatedly on startup.
+    CrashRollback,
+    /// A dry-run job estimated what compression would have saved.
+    JobSimulated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutomationNotification {
+    pub kind: NotificationKind,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub suggested_action: SuggestedAction,
+    pub game_path: Option<PathBuf>,
+    pub bytes_saved: Option<u64>,
+    pub timestamp_ms: u64,
+}
+
+/// Failure text reported when the target game was running at job start.
+pub const GAME_RUNNING_ERROR: &str = "Game is currently running";
+/// Failure text reported when user activity interrupted a job.
+pub const CANCELLED_FOR_ACTIVITY_ERROR: &str = "Cancelled due to user activity";
+
+/// Running totals for jobs finished since the queue last drained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrainSummary {
+    pub completed: u32,
+    pub failed: u32,
+    pub bytes_saved: u64,
+    /// Dry-run jobs and the savings they estimated.
+    pub simulated: u32,
+    pub bytes_would_save: u64,
+}
+
+impl DrainSummary {
+    pub fn is_empty(&self) -> bool {
+        self.completed == 0 && self.failed == 0 && self.simulated == 0
+    }
+}
+
+pub fn job_completed(
+    game_path: &Path,
+    game_name: Option<&str>,
+    original_bytes: u64,
+    compressed_bytes: u64,
+) -> AutomationNotification {
+    let saved = original_bytes.saturating_sub(compressed_bytes);
+    AutomationNotification {
+        kind: NotificationKind::JobCompleted,
+        severity: NotificationSeverity::Success,
+        title: format!("Compressed {}", display_name(game_path, game_name)),
+        body: format!("Saved {}", format_size(saved)),
+        suggested_action: SuggestedAction::None,
+        game_path: Some(game_path.to_path_buf()),
+        bytes_saved: Some(saved),
+        timestamp_ms: crate::utils::unix_now_ms(),
+    }
+}
+
+/// A dry-run job estimated `estimated_saved_bytes` of savings.
+pub fn job_simulated(
+    game_path: &Path,
+    game_name: Option<&str>,
+    estimated_saved_bytes: u64,
+) -> AutomationNotification {
+    AutomationNotification {
+        kind: NotificationKind::JobSimulated,
+        severity: NotificationSeverity::Info,
+        title: format!("Dry run: {}", display_name(game_path, game_name)),
+        body: format!("Would have saved {}", format_size(estimated_saved_bytes)),
+        suggested_action: SuggestedAction::None,
+        game_path: Some(game_path.to_path_buf()),
+        bytes_saved: Some(estimated_saved_bytes),
+        timestamp_ms: crate::utils::unix_now_ms(),
+    }
+}
+
+/// Build a failure notification, or `None` for transient interruptions the
+/// scheduler will retry on its own.
+pub fn job_failed(
+    game_path: &Path,
+    game_name: Option<&str>,
+    error: &str,
+) -> Option<AutomationNotification> {
+    if error == CANCELLED_FOR_ACTIVITY_ERROR {
+        return None;
+    }
+
+    let (severity, suggested_action) = if error == GAME_RUNNING_ERROR {
+        (NotificationSeverity::Warning, SuggestedAction::CloseGame)
+    } else {
+        (NotificationSeverity::Error, SuggestedAction::RetryManually)
+    };
+    Some(AutomationNotification {
+        kind: NotificationKind::JobFailed,
+        severity,
+        title: format!("Could not compress {}", display_name(game_path, game_name)),
+        body: error.to_owned(),
+        suggested_action,
+        game_path: Some(game_path.to_path_buf()),
+        bytes_saved: None,
+        timestamp_ms: crate::utils::unix_now_ms(),
+    })
+}
+
+pub fn queue_drained(summary: DrainSummary) -> AutomationNotification {
+    let mut body = if summary.completed == 0 && summary.simulated > 0 {
+        format!(
+            "{} game{} simulated, {} would be saved",
+            summary.simulated,
+            if summary.simulated == 1 { "" } else { "s" },
+            format_size(summary.bytes_would_save)
+        )
+    } else {
+        format!(
+            "{} game{} compressed, {} saved",
+            summary.completed,
+            if summary.completed == 1 { "" } else { "s" },
+            format_size(summary.bytes_saved)
+        )
+    };
+    let (severity, suggested_action) = if summary.failed > 0 {
+        body.push_str(&format!(", {} failed", summary.failed));
+        (NotificationSeverity::Warning, SuggestedAction::ViewHistory)
+    } else {
+        (NotificationSeverity::Info, SuggestedAction::None)
+    };
+    AutomationNotification {
+        kind: NotificationKind::QueueDrained,
+        severity,
+        title: "Automation queue finished".to_owned(),
+        body,
+        suggested_action,
+        game_path: None,
+        bytes_saved: Some(summary.bytes_saved),
+        timestamp_ms: crate::utils::unix_now_ms(),
+    }
+}
+
+/// A compressed game kept crashing on startup and was decompressed, or
+/// the decompression failed with `error`.
+pub fn crash_rollback(
+    game_path: &Path,
+    game_name: Option<&str>,
+    crashes: usize,
+    error: Option<&str>,
+) -> AutomationNotification {
+    let name = display_name(game_path, game_name);
+    let (severity, title, body, suggested_action) = match error {
+        None => (
+            NotificationSeverity::Warning,
+            format!("Decompressed {name}"),
+            format!(
+                "It crashed {crashes} times on startup while compressed and is now excluded from automation"
+            ),
+            SuggestedAction::None,
+        ),
+        Some(error) => (
+            NotificationSeverity::Error,
+            format!("Could not decompress {name}"),
+            format!("It crashed {crashes} times on startup while compressed: {error}"),
+            SuggestedAction::ViewHistory,
+        ),
+    };
+    AutomationNotification {
+        kind: NotificationKind::CrashRollback,
+        severity,
+        title,
+        body,
+        suggested_action,
+        game_path: Some(game_path.to_path_buf()),
+        bytes_saved: None,
+        timestamp_ms: crate::utils::unix_now_ms(),
+    }
+}
+
+fn display_name(game_path: &Path, game_name: Option<&str>) -> String {
+    game_name
+        .map(str::to_owned)
+        .or_else(|| {
+            game_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| game_path.display().to_string())
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GIB {
+        format!("{:.1} GB", bytes / GIB)
+    } else {
+        format!("{:.0} MB", bytes / MIB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completed_reports_savings_in_gigabytes() {
+        let n = job_completed(
+            Path::new(r"C:\Games\Alpha"),
+            None,
+            10 * 1024 * 1024 * 1024,
+            7 * 1024 * 1024 * 1024,
+        );
+        assert_eq!(n.title, "Compressed Alpha");
+        assert_eq!(n.body, "Saved 3.0 GB");
+        assert_eq!(n.bytes_saved, Some(3 * 1024 * 1024 * 1024));
+        assert_eq!(n.severity, NotificationSeverity::Success);
+    }
+
+    #[test]
+    fn running_game_failure_suggests_closing_it() {
+        let n = job_failed(
+            Path::new(r"C:\Games\Alpha"),
+            Some("Alpha"),
+            GAME_RUNNING_ERROR,
+        )
+        .expect("notification");
+        assert_eq!(n.severity, NotificationSeverity::Warning);
+        assert_eq!(n.suggested_action, SuggestedAction::CloseGame);
+    }
+
+    #[test]
+    fn activity_cancellation_is_silent() {
+        assert!(job_failed(
+            Path::new(r"C:\Games\Alpha"),
+            None,
+            CANCELLED_FOR_ACTIVITY_ERROR
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn drained_with_failures_points_to_history() {
+        let n = queue_drained(DrainSummary {
+            completed: 2,
+            failed: 1,
+            bytes_saved: 512 * 1024 * 1024,
+            ..DrainSummary::default()
+        });
+        assert_eq!(n.body, "2 games compressed, 512 MB saved, 1 failed");
+        assert_eq!(n.suggested_action, SuggestedAction::ViewHistory);
+    }
+
+    #[test]
+    fn simulated_job_reports_estimated_savings() {
+        let n = job_simulated(Path::new(r"C:\Games\Alpha"), None, 2 * 1024 * 1024 * 1024);
+        assert_eq!(n.kind, NotificationKind::JobSimulated);
+        assert_eq!(n.title, "Dry run: Alpha");
+        assert_eq!(n.body, "Would have saved 2.0 GB");
+
+        let drained = queue_drained(DrainSummary {
+            simulated: 3,
+            bytes_would_save: 768 * 1024 * 1024,
+            ..DrainSummary::default()
+        });
+        assert_eq!(drained.body, "3 games simulated, 768 MB would be saved");
+    }
+
+    #[test]
+    fn crash_rollback_reports_the_crash_count() {
+        let n = crash_rollback(Path::new(r"C:\Games\Alpha"), Some("Alpha"), 3, None);
+        assert_eq!(n.kind, NotificationKind::CrashRollback);
+        assert_eq!(n.title, "Decompressed Alpha");
+        assert_eq!(
+            n.body,
+            "It crashed 3 times on startup while compressed and is now excluded from automation"
+        );
+
+        let failed = crash_rollback(Path::new(r"C:\Games\Alpha"), None, 3, Some("Access denied"));
+        assert_eq!(failed.severity, NotificationSeverity::Error);
+        assert_eq!(failed.title, "Could not decompress Alpha");
+    }
+}
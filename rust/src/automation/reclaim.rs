@@ -0,0 +1,258 @@
+//! Plans which games to compress to reach a free-space goal on a volume.
+//!
+//! Candidates are the uncompressed games on the volume that compression
+//! could shrink. Each is scored by its projected savings, discounted when
+//! it was played recently: a game in rotation is the one most likely to
+//! notice slower loads. The best-scoring games are taken until their
+//! projected savings cover the shortfall.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::compression::history::CompressionHistoryEntry;
+use crate::discovery::drive_summary::{is_projection_eligible, projected_savings_ratio};
+use crate::discovery::platform::{GameInfo, Platform};
+use crate::discovery::storage::volume_key_for_path;
+use crate::utils::normalize_path_key;
+
+/// Games played this recently are planned only after everything else.
+const RECENTLY_PLAYED: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+const RECENTLY_PLAYED_WEIGHT: f64 = 0.25;
+/// Games played this recently are planned after long-idle ones.
+const LATELY_PLAYED: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+const LATELY_PLAYED_WEIGHT: f64 = 0.5;
+
+/// One game to compress, in plan order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReclaimStep {
+    pub game_path: PathBuf,
+    pub game_name: String,
+    pub platform: Platform,
+    pub size_bytes: u64,
+    pub estimated_savings_bytes: u64,
+    pub last_played: Option<SystemTime>,
+}
+
+/// Games to compress to free `shortfall_bytes` on `volume`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReclaimPlan {
+    /// Volume root, e.g. `d:\`.
+    pub volume: String,
+    pub free_bytes: u64,
+    pub target_free_bytes: u64,
+    /// Space still missing: the target minus what is free now.
+    pub shortfall_bytes: u64,
+    /// Projected savings of every step together.
+    pub planned_savings_bytes: u64,
+    pub steps: Vec<ReclaimStep>,
+}
+
+impl ReclaimPlan {
+    /// Whether the steps are expected to free enough space.
+    pub fn reaches_target(&self) -> bool {
+        self.planned_savings_bytes >= self.shortfall_bytes
+    }
+}
+
+/// What the planner assumes each game will save.
+#[derive(Debug, Clone, Default)]
+pub struct SavingsModel {
+    /// Ratio for games without history of their own.
+    default_ratio: f64,
+    /// Ratio the newest compression of each game achieved, by path key.
+    game_ratios: HashMap<String, f64>,
+}
+
+impl SavingsModel {
+    pub fn from_history(history: &[CompressionHistoryEntry]) -> Self {
+        let mut newest: HashMap<String, &CompressionHistoryEntry> = HashMap::new();
+        for entry in history
+            .iter()
+            .filter(|entry| entry.actual_stats.original_bytes > 0)
+        {
+            let key = normalize_path_key(Path::new(&entry.game_path));
+            let slot = newest.entry(key).or_insert(entry);
+            if entry.timestamp_ms > slot.timestamp_ms {
+                *slot = entry;
+            }
+        }
+        Self {
+            default_ratio: projected_savings_ratio(history),
+            game_ratios: newest
+                .into_iter()
+                .map(|(key, entry)| {
+                    let stats = &entry.actual_stats;
+                    let ratio = stats.actual_saved_bytes as f64 / stats.original_bytes as f64;
+                    (key, ratio)
+                })
+                .collect(),
+        }
+    }
+
+    fn estimated_savings(&self, game: &GameInfo) -> u64 {
+        let ratio = self
+            .game_ratios
+            .get(&normalize_path_key(&game.path))
+            .copied()
+            .unwrap_or(self.default_ratio);
+        (game.size_bytes as f64 * ratio.clamp(0.0, 1.0)) as u64
+    }
+}
+
+/// Plan compressions on `volume` until `target_free_bytes` are free, given
+/// `free_bytes` free now. The plan comes up short when every candidate
+/// together would not save enough.
+pub fn plan_reclaim(
+    games: &[GameInfo],
+    volume: &str,
+    free_bytes: u64,
+    target_free_bytes: u64,
+    model: &SavingsModel,
+    now: SystemTime,
+) -> ReclaimPlan {
+    let volume = volume_key_for_path(Path::new(volume));
+    let shortfall_bytes = target_free_bytes.saturating_sub(free_bytes);
+
+    let mut candidates: Vec<(f64, ReclaimStep)> = games
+        .iter()
+        .filter(|game| !game.is_compressed && is_projection_eligible(game))
+        .filter(|game| volume_key_for_path(&game.path) == volume)
+        .map(|game| {
+            let estimated_savings_bytes = model.estimated_savings(game);
+            let score = estimated_savings_bytes as f64 * recency_weight(game.last_played, now);
+            let step = ReclaimStep {
+                game_path: game.path.clone(),
+                game_name: game.name.clone(),
+                platform: game.platform,
+                size_bytes: game.size_bytes,
+                estimated_savings_bytes,
+                last_played: game.last_played,
+            };
+            (score, step)
+        })
+        .filter(|(_, step)| step.estimated_savings_bytes > 0)
+        .collect();
+    candidates.sort_by(|(a_score, a), (b_score, b)| {
+        b_score
+            .total_cmp(a_score)
+            .then(b.size_bytes.cmp(&a.size_bytes))
+            .then_with(|| a.game_path.cmp(&b.game_path))
+    });
+
+    let mut planned_savings_bytes = 0u64;
+    let mut steps = Vec::new();
+    for (_, step) in candidates {
+        if planned_savings_bytes >= shortfall_bytes {
+            break;
+        }
+        planned_savings_bytes = planned_savings_bytes.saturating_add(step.estimated_savings_bytes);
+        steps.push(step);
+    }
+
+    ReclaimPlan {
+        volume,
+        free_bytes,
+        target_free_bytes,
+        shortfall_bytes,
+        planned_savings_bytes,
+        steps,
+    }
+}
+
+fn recency_weight(last_played: Option<SystemTime>, now: SystemTime) -> f64 {
+    let Some(since) = last_played.and_then(|played| now.duration_since(played).ok()) else {
+        return 1.0;
+    };
+    if since < RECENTLY_PLAYED {
+        RECENTLY_PLAYED_WEIGHT
+    } else if since < LATELY_PLAYED {
+        LATELY_PLAYED_WEIGHT
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::storage::DriveType;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn game(path: &str, size_bytes: u64, played_days_ago: Option<u32>) -> GameInfo {
+        let now = SystemTime::UNIX_EPOCH + 365 * DAY;
+        GameInfo {
+            name: path.rsplit('\\').next().unwrap_or(path).into(),
+            path: PathBuf::from(path),
+            platform: Platform::Steam,
+            size_bytes,
+            compressed_size: None,
+            is_compressed: false,
+            is_directstorage: false,
+            is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
+            excluded: false,
+            steam_app_id: None,
+            last_played: played_days_ago.map(|days| now - days * DAY),
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
+        }
+    }
+
+    fn model(ratio: f64) -> SavingsModel {
+        SavingsModel {
+            default_ratio: ratio,
+            game_ratios: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn idle_games_are_planned_before_recently_played_ones() {
+        let now = SystemTime::UNIX_EPOCH + 365 * DAY;
+        let mut excluded = game(r"D:\Games\Excluded", 500 * GB, None);
+        excluded.excluded = true;
+        let games = vec![
+            game(r"D:\Games\PlayedToday", 100 * GB, Some(0)),
+            game(r"D:\Games\Shelved", 60 * GB, Some(200)),
+            game(r"D:\Games\Untouched", 40 * GB, None),
+            game(r"E:\Games\OtherDrive", 300 * GB, None),
+            excluded,
+        ];
+
+        let plan = plan_reclaim(&games, "D:", 10 * GB, 45 * GB, &model(0.5), now);
+
+        let planned: Vec<&str> = plan
+            .steps
+            .iter()
+            .map(|step| step.game_name.as_str())
+            .collect();
+        assert_eq!(planned, ["Shelved", "Untouched"]);
+        assert_eq!(plan.shortfall_bytes, 35 * GB);
+        assert_eq!(plan.planned_savings_bytes, 50 * GB);
+        assert!(plan.reaches_target());
+    }
+
+    #[test]
+    fn plan_reports_when_the_goal_is_out_of_reach() {
+        let now = SystemTime::UNIX_EPOCH + 365 * DAY;
+        let games = vec![game(r"D:\Games\Only", 10 * GB, None)];
+
+        let plan = plan_reclaim(&games, r"d:\", 0, 150 * GB, &model(0.5), now);
+
+        assert_eq!(plan.steps.len(), 1);
+        assert!(!plan.reaches_target());
+
+        let satisfied = plan_reclaim(&games, r"D:\", 200 * GB, 150 * GB, &model(0.5), now);
+        assert!(satisfied.steps.is_empty());
+        assert!(satisfied.reaches_target());
+    }
+}
@@ -3,6 +3,9 @@
 //! Manages a queue of compression jobs, advancing through states
 //! based on idle detection, safety checks, and watcher events.
 //! Uses a single-owner model on the auto-compression thread.
+//!
+//! Up to `SchedulerConfig::max_concurrent_jobs` jobs may compress at once,
+//! but never two on the same physical disk.
 
 mod types;
 pub use types::*;
@@ -10,10 +13,16 @@ pub use types::*;
 #[cfg(test)]
 mod tests;
 
-use std::collections::VecDeque;
-use std::time::{Instant, SystemTime};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::discovery::install_state::InstallState;
 
-use super::journal::{JournalEntry, JournalEventKind, JournalWriter};
+use super::clock::{Clock, SystemClock};
+use super::io_activity::has_recent_writes;
+use super::journal::{content_idempotency_key, JournalEntry, JournalEventKind, JournalWriter};
 use super::watcher::WatchEvent;
 
 /// The automation scheduler state machine.
@@ -29,10 +38,35 @@ pub struct AutoScheduler {
     consecutive_failures: u32,
     settle_started: Option<Instant>,
     needs_persist: bool,
+    /// Set by an explicit user pause; idle detection cannot resume it.
+    user_paused: bool,
+    /// Queued game paths with a running process; never started while set.
+    running_games: HashSet<PathBuf>,
+    /// Launcher-reported install state of settling games.
+    install_states: HashMap<PathBuf, InstallState>,
+    /// Volumes of watch roots reported offline; their jobs wait.
+    offline_volumes: HashSet<String>,
+    /// Games whose folder was still being written at the last probe, and
+    /// when to probe them again.
+    io_busy_until: HashMap<PathBuf, Instant>,
+    /// Idle flag from the last tick, for status displays.
+    last_idle: bool,
+    /// Set by a stop-after-current or drain command.
+    stop_request: Option<StopRequest>,
+    clock: Arc<dyn Clock>,
 }
 
 impl AutoScheduler {
     pub fn new(config: SchedulerConfig, journal: JournalWriter) -> Self {
+        Self::with_clock(config, journal, Arc::new(SystemClock))
+    }
+
+    /// A scheduler that measures settle, backoff and hold times on `clock`.
+    pub fn with_clock(
+        config: SchedulerConfig,
+        journal: JournalWriter,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
         Self {
             state: SchedulerState::WaitingForEvents,
             queue: VecDeque::new(),
@@ -42,12 +76,29 @@ impl AutoScheduler {
             consecutive_failures: 0,
             settle_started: None,
             needs_persist: false,
+            user_paused: false,
+            running_games: HashSet::new(),
+            install_states: HashMap::new(),
+            offline_volumes: HashSet::new(),
+            io_busy_until: HashMap::new(),
+            last_idle: false,
+            stop_request: None,
+            clock,
         }
     }
 
     /// Restore from journal or create a fresh scheduler.
     pub fn restore_or_new(config: SchedulerConfig, journal: JournalWriter) -> Self {
-        let mut scheduler = Self::new(config, journal);
+        Self::restore_or_new_with_clock(config, journal, Arc::new(SystemClock))
+    }
+
+    /// [`Self::restore_or_new`] on `clock`.
+    pub fn restore_or_new_with_clock(
+        config: SchedulerConfig,
+        journal: JournalWriter,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let mut scheduler = Self::with_clock(config, journal, clock);
         if let Ok(count) = scheduler.journal.load() {
             if count > 0 {
                 log::info!("Restored {count} pending jobs from journal");
@@ -66,6 +117,7 @@ impl AutoScheduler {
                         queued_at: entry.queued_at,
                         started_at: None,
                         error: None,
+                        simulated: false,
                     };
                     scheduler.enqueue_job(job);
                 }
@@ -74,9 +126,68 @@ impl AutoScheduler {
                 }
             }
         }
+        if let Some(snapshot) = scheduler.journal.load_scheduler_snapshot() {
+            scheduler.apply_snapshot(snapshot);
+        }
         scheduler
     }
 
+    /// Restore failure counts, backoff and finished jobs saved by
+    /// [`Self::persist`] before a restart.
+    fn apply_snapshot(&mut self, snapshot: SchedulerSnapshot) {
+        self.consecutive_failures = snapshot.consecutive_failures;
+        // Clamped in case the wall clock jumped while the service was down.
+        self.backoff_until = snapshot
+            .backoff_until
+            .and_then(|until| until.duration_since(self.clock.system_now()).ok())
+            .map(|remaining| self.clock.now() + remaining.min(MAX_BACKOFF));
+        for job in snapshot.finished_jobs.into_iter().rev() {
+            if matches!(
+                job.status,
+                JobStatus::Completed | JobStatus::Failed | JobStatus::Skipped
+            ) && !self
+                .queue
+                .iter()
+                .any(|j| j.idempotency_key == job.idempotency_key)
+            {
+                self.queue.push_front(job);
+            }
+        }
+        self.prune_finished();
+
+        if let Some(until) = self.backoff_until {
+            log::info!(
+                "Restored scheduler backoff: {}s left after {} consecutive failures",
+                until.saturating_duration_since(self.clock.now()).as_secs(),
+                self.consecutive_failures
+            );
+            if self.has_pending_jobs() {
+                self.state = SchedulerState::Backoff;
+            }
+        }
+    }
+
+    /// State to carry across a restart; see [`SchedulerSnapshot`].
+    pub fn snapshot(&self) -> SchedulerSnapshot {
+        SchedulerSnapshot {
+            consecutive_failures: self.consecutive_failures,
+            backoff_until: self.backoff_until.map(|until| {
+                self.clock.system_now() + until.saturating_duration_since(self.clock.now())
+            }),
+            finished_jobs: self
+                .queue
+                .iter()
+                .filter(|j| {
+                    matches!(
+                        j.status,
+                        JobStatus::Completed | JobStatus::Failed | JobStatus::Skipped
+                    )
+                })
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Handle an incoming watcher event.
     pub fn on_event(&mut self, event: WatchEvent) {
         let (path, game_name, kind) = match &event {
@@ -93,19 +204,26 @@ impl AutoScheduler {
                 self.needs_persist = true;
                 return;
             }
+            WatchEvent::DriveOffline { path } => {
+                log::info!("Suspending jobs on offline drive: {}", path.display());
+                self.offline_volumes.insert(volume_key(path));
+                return;
+            }
+            WatchEvent::DriveOnline { path } => {
+                if self.offline_volumes.remove(&volume_key(path))
+                    && self.state == SchedulerState::WaitingForEvents
+                    && self.has_pending_jobs()
+                {
+                    log::info!("Resuming jobs on returned drive: {}", path.display());
+                    self.state = SchedulerState::WaitingForIdle;
+                }
+                return;
+            }
         };
         let event_path = path.clone();
 
-        let path_str = path.to_string_lossy().to_ascii_lowercase();
-
-        // Check exclusion list
-        if self.config.excluded_paths.contains(&path_str)
-            || self
-                .config
-                .excluded_paths
-                .contains(&path.to_string_lossy().to_string())
-        {
-            log::debug!("Skipping excluded path: {}", path.display());
+        if let Some(rule) = self.config.exclusions.matching_install(&path) {
+            log::debug!("Skipping excluded path {} ({rule:?})", path.display());
             return;
         }
 
@@ -114,8 +232,11 @@ impl AutoScheduler {
             SchedulerState::Compressing | SchedulerState::Paused
         );
 
+        let idempotency_key = content_idempotency_key(&path);
+
         // More filesystem activity means the install is still changing. Keep
-        // the existing job but restart its settle window before it can run.
+        // the existing job but restart its settle window before it can run,
+        // and re-key it to the latest content.
         if self.queue.iter().any(|j| {
             j.game_path == path
                 && matches!(
@@ -126,7 +247,7 @@ impl AutoScheduler {
             if !compression_active {
                 self.state = SchedulerState::WaitingForSettle;
             }
-            self.settle_started = Some(Instant::now());
+            self.settle_started = Some(self.clock.now());
             for job in &mut self.queue {
                 if job.game_path == path
                     && matches!(
@@ -137,17 +258,33 @@ impl AutoScheduler {
                     )
                 {
                     job.status = JobStatus::WaitingForSettle;
+                    if job.idempotency_key != idempotency_key {
+                        self.journal.remove(&job.idempotency_key);
+                        self.journal.insert(JournalEntry::with_idempotency_key(
+                            job.game_path.clone(),
+                            job.game_name.clone(),
+                            journal_event_kind(job.kind),
+                            idempotency_key.clone(),
+                        ));
+                        job.idempotency_key = idempotency_key.clone();
+                        self.needs_persist = true;
+                    }
                 }
             }
             log::debug!("Reset settle timer for: {}", path.display());
             return;
         }
 
-        let epoch = SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        let idempotency_key = format!("{path_str}:{epoch}");
+        // A re-fired event for content already compressed (or skipped) is
+        // not worth another pass; only a content change yields a new key.
+        if self.queue.iter().any(|j| {
+            j.idempotency_key == idempotency_key
+                && !j.simulated
+                && matches!(j.status, JobStatus::Completed | JobStatus::Skipped)
+        }) {
+            log::debug!("Content unchanged since last job: {}", path.display());
+            return;
+        }
 
         let job = AutomationJob {
             game_path: path,
@@ -159,9 +296,10 @@ impl AutoScheduler {
                 JobStatus::Pending
             },
             idempotency_key: idempotency_key.clone(),
-            queued_at: SystemTime::now(),
+            queued_at: self.clock.system_now(),
             started_at: None,
             error: None,
+            simulated: false,
         };
 
         self.enqueue_job(job);
@@ -175,35 +313,82 @@ impl AutoScheduler {
         let entry = JournalEntry::with_idempotency_key(
             event_path,
             event.game_name().map(|s| s.to_string()),
-            match kind {
-                JobKind::NewInstall => JournalEventKind::NewInstall,
-                JobKind::Reconcile => JournalEventKind::Reconcile,
-                JobKind::Opportunistic => JournalEventKind::Opportunistic,
-            },
+            journal_event_kind(kind),
             idempotency_key,
         );
         self.journal.insert(entry);
         self.needs_persist = true;
 
-        self.settle_started = Some(Instant::now());
+        self.settle_started = Some(self.clock.now());
         if !compression_active {
             self.state = SchedulerState::WaitingForSettle;
         }
     }
 
+    /// Queue an installed game the user asked to compress, such as a step
+    /// of a reclaim plan. It needs no settle window and starts once the
+    /// user is idle. Returns `false` when the game is excluded or already
+    /// queued.
+    pub fn enqueue_opportunistic(&mut self, path: PathBuf, game_name: Option<String>) -> bool {
+        if let Some(rule) = self.config.exclusions.matching_install(&path) {
+            log::debug!("Not queueing excluded path {} ({rule:?})", path.display());
+            return false;
+        }
+        if self.queue.iter().any(|j| {
+            j.game_path == path
+                && matches!(
+                    j.status,
+                    JobStatus::Pending
+                        | JobStatus::WaitingForSettle
+                        | JobStatus::WaitingForIdle
+                        | JobStatus::Compressing
+                )
+        }) {
+            return false;
+        }
+
+        let idempotency_key = content_idempotency_key(&path);
+        self.journal.insert(JournalEntry::with_idempotency_key(
+            path.clone(),
+            game_name.clone(),
+            JournalEventKind::Opportunistic,
+            idempotency_key.clone(),
+        ));
+        self.enqueue_job(AutomationJob {
+            game_path: path,
+            game_name,
+            kind: JobKind::Opportunistic,
+            status: JobStatus::WaitingForIdle,
+            idempotency_key,
+            queued_at: self.clock.system_now(),
+            started_at: None,
+            error: None,
+            simulated: false,
+        });
+        self.needs_persist = true;
+        if self.state == SchedulerState::WaitingForEvents {
+            self.state = SchedulerState::WaitingForIdle;
+        }
+        true
+    }
+
     /// Advance the state machine. Called periodically from auto_loop.
     pub fn tick(&mut self, is_idle: bool, _process_active: bool) -> Option<SchedulerAction> {
+        self.last_idle = is_idle;
         if self.needs_persist {
             self.needs_persist = false;
             return Some(SchedulerAction::Persist);
         }
+        if self.user_paused {
+            return None;
+        }
 
         match self.state {
             SchedulerState::WaitingForEvents => None,
 
             SchedulerState::WaitingForSettle => {
                 if let Some(start) = self.settle_started {
-                    if start.elapsed() >= self.config.cooldown {
+                    if self.settle_elapsed(start) {
                         self.state = SchedulerState::WaitingForIdle;
                         self.settle_started = None;
                         for job in &mut self.queue {
@@ -226,22 +411,16 @@ impl AutoScheduler {
             }
 
             SchedulerState::SafetyCheck => {
-                if let Some(job) = self.next_pending_job() {
-                    let mut job = job.clone();
-                    job.status = JobStatus::Compressing;
-                    job.started_at = Some(SystemTime::now());
-
-                    if let Some(q) = self
-                        .queue
-                        .iter_mut()
-                        .find(|j| j.idempotency_key == job.idempotency_key)
-                    {
-                        q.status = JobStatus::Compressing;
-                        q.started_at = job.started_at;
-                    }
-
+                if let Some(action) = self.start_next_job() {
+                    self.state = SchedulerState::Compressing;
+                    Some(action)
+                } else if self.has_active_job() {
                     self.state = SchedulerState::Compressing;
-                    Some(SchedulerAction::Compress(job))
+                    None
+                } else if self.has_jobs_waiting_on_running_games() || self.has_jobs_waiting_on_io()
+                {
+                    self.state = SchedulerState::WaitingForIdle;
+                    None
                 } else {
                     self.state = SchedulerState::WaitingForEvents;
                     None
@@ -251,8 +430,19 @@ impl AutoScheduler {
             SchedulerState::Compressing => {
                 if !is_idle {
                     self.state = SchedulerState::Paused;
+                    return None;
                 }
-                None
+                if self
+                    .backoff_until
+                    .is_some_and(|until| self.clock.now() >= until)
+                {
+                    self.backoff_until = None;
+                }
+                if self.config.max_concurrent_jobs <= 1 || self.backoff_until.is_some() {
+                    return None;
+                }
+                self.promote_settled_jobs();
+                self.start_next_job()
             }
 
             SchedulerState::Paused => {
@@ -264,7 +454,7 @@ impl AutoScheduler {
 
             SchedulerState::Backoff => {
                 if let Some(until) = self.backoff_until {
-                    if Instant::now() >= until {
+                    if self.clock.now() >= until {
                         self.backoff_until = None;
                         self.state = SchedulerState::WaitingForIdle;
                     }
@@ -276,7 +466,7 @@ impl AutoScheduler {
         }
     }
 
-    /// Mark the current compression job as completed.
+    /// Mark a compression job as completed.
     pub fn job_completed(&mut self, idempotency_key: &str) {
         if let Some(job) = self
             .queue
@@ -290,22 +480,23 @@ impl AutoScheduler {
         self.needs_persist = true;
         self.prune_finished();
 
-        if self.has_pending_jobs() {
-            self.state = if self
-                .queue
-                .iter()
-                .any(|job| job.status == JobStatus::WaitingForSettle)
-            {
-                SchedulerState::WaitingForSettle
-            } else {
-                SchedulerState::WaitingForIdle
-            };
-        } else {
-            self.state = SchedulerState::WaitingForEvents;
+        self.update_state_after_job();
+    }
+
+    /// Mark a dry-run job as finished. It counts as completed, but the same
+    /// content is still eligible for a real run later.
+    pub fn job_simulated(&mut self, idempotency_key: &str) {
+        if let Some(job) = self
+            .queue
+            .iter_mut()
+            .find(|j| j.idempotency_key == idempotency_key)
+        {
+            job.simulated = true;
         }
+        self.job_completed(idempotency_key);
     }
 
-    /// Mark the current compression job as failed.
+    /// Mark a compression job as failed.
     pub fn job_failed(&mut self, idempotency_key: &str, error: String) {
         if let Some(job) = self
             .queue
@@ -323,13 +514,20 @@ impl AutoScheduler {
         let backoff =
             INITIAL_BACKOFF * 2u32.saturating_pow(self.consecutive_failures.saturating_sub(1));
         let capped = backoff.min(MAX_BACKOFF);
-        self.backoff_until = Some(Instant::now() + capped);
+        self.backoff_until = Some(self.clock.now() + capped);
 
-        if self.has_pending_jobs() {
+        // Other volumes finish their current job; no new jobs start until
+        // the backoff has elapsed.
+        if self.has_active_job() {
+            self.state = SchedulerState::Compressing;
+        } else if self.has_pending_jobs() {
             self.state = SchedulerState::Backoff;
         } else {
             self.state = SchedulerState::WaitingForEvents;
         }
+        if self.user_paused {
+            self.state = SchedulerState::Paused;
+        }
     }
 
     /// Mark a job as skipped (e.g., DirectStorage detected).
@@ -346,8 +544,34 @@ impl AutoScheduler {
         self.needs_persist = true;
         self.prune_finished();
 
-        if self.has_pending_jobs() {
-            self.state = if self
+        self.update_state_after_job();
+    }
+
+    /// Put a job whose game launched mid-run back in the queue. It restarts
+    /// once the game exits; unlike [`Self::job_failed`] this is no failure
+    /// and starts no backoff.
+    pub fn job_deferred(&mut self, idempotency_key: &str) {
+        if let Some(job) = self
+            .queue
+            .iter_mut()
+            .find(|j| j.idempotency_key == idempotency_key)
+        {
+            job.status = JobStatus::WaitingForIdle;
+            job.started_at = None;
+        }
+        self.needs_persist = true;
+        self.update_state_after_job();
+    }
+
+    /// Pick the state once a job stops running. A backoff started by a
+    /// failure while other jobs ran takes over once the last one is done.
+    fn update_state_after_job(&mut self) {
+        if self.has_active_job() {
+            self.state = SchedulerState::Compressing;
+        } else if self.has_pending_jobs() {
+            self.state = if self.backoff_until.is_some() {
+                SchedulerState::Backoff
+            } else if self
                 .queue
                 .iter()
                 .any(|job| job.status == JobStatus::WaitingForSettle)
@@ -359,10 +583,53 @@ impl AutoScheduler {
         } else {
             self.state = SchedulerState::WaitingForEvents;
         }
+        if self.user_paused {
+            self.state = SchedulerState::Paused;
+        }
+    }
+
+    /// Replace the set of queued games that are currently running. Jobs for
+    /// them stay queued and are skipped over until the game exits.
+    pub fn set_running_games(&mut self, running: &[PathBuf]) {
+        self.running_games = running.iter().cloned().collect();
+    }
+
+    /// Replace the launcher-reported install state of settling games. A
+    /// download in progress or an update still to run holds the settle
+    /// open; a confirmed complete install ends it after
+    /// `SchedulerConfig::install_settle`.
+    pub fn set_install_states(&mut self, states: &HashMap<PathBuf, InstallState>) {
+        self.install_states = states.clone();
+    }
+
+    /// Game paths of jobs still settling, for the install-progress monitor.
+    pub fn settling_game_paths(&self) -> Vec<PathBuf> {
+        self.queue
+            .iter()
+            .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::WaitingForSettle))
+            .map(|j| j.game_path.clone())
+            .collect()
+    }
+
+    /// Game paths of jobs not yet finished, for the running-games monitor.
+    pub fn unfinished_game_paths(&self) -> Vec<PathBuf> {
+        self.queue
+            .iter()
+            .filter(|j| {
+                !matches!(
+                    j.status,
+                    JobStatus::Completed | JobStatus::Failed | JobStatus::Skipped
+                )
+            })
+            .map(|j| j.game_path.clone())
+            .collect()
     }
 
     /// Pause the scheduler (external control).
+    ///
+    /// Unlike an activity pause, this holds until [`Self::resume`] is called.
     pub fn pause(&mut self) {
+        self.user_paused = true;
         if self.state != SchedulerState::Paused {
             self.state = SchedulerState::Paused;
         }
@@ -370,6 +637,7 @@ impl AutoScheduler {
 
     /// Resume the scheduler from pause.
     pub fn resume(&mut self) {
+        self.user_paused = false;
         if self.state == SchedulerState::Paused {
             if self.has_pending_jobs() || self.has_active_job() {
                 self.state = SchedulerState::WaitingForIdle;
@@ -379,15 +647,118 @@ impl AutoScheduler {
         }
     }
 
-    /// Persist the journal to disk.
+    /// Ask the service to stop at a safe point; see [`Self::should_stop`].
+    pub fn request_stop(&mut self, request: StopRequest) {
+        self.stop_request = Some(request);
+    }
+
+    pub fn stop_request(&self) -> Option<StopRequest> {
+        self.stop_request
+    }
+
+    /// True once a requested stop can happen without cutting a job short.
+    pub fn should_stop(&self) -> bool {
+        match self.stop_request {
+            None => false,
+            Some(StopRequest::AfterCurrentJob) => !self.has_active_job(),
+            Some(StopRequest::WhenDrained) => !self.has_active_job() && !self.has_pending_jobs(),
+        }
+    }
+
+    /// Persist the journal and the scheduler snapshot to disk.
     pub fn persist(&self) -> Result<(), std::io::Error> {
-        self.journal.flush()
+        self.journal.flush()?;
+        self.journal.save_scheduler_snapshot(&self.snapshot())
     }
 
     pub fn state(&self) -> SchedulerState {
         self.state
     }
 
+    /// True while paused via [`Self::pause`] rather than by user activity.
+    pub fn is_user_paused(&self) -> bool {
+        self.user_paused
+    }
+
+    /// Current state, why nothing is starting, and job counts.
+    pub fn status(&self) -> SchedulerStatus {
+        let count = |status: JobStatus| self.queue.iter().filter(|j| j.status == status).count();
+        // Fresh jobs stay `Pending` until the settle window closes.
+        let settle_open = self.settle_started.is_some();
+        let queued = count(JobStatus::Pending);
+        SchedulerStatus {
+            state: self.state,
+            wait_reason: self.wait_reason(),
+            backoff_remaining: self
+                .backoff_until
+                .map(|until| until.saturating_duration_since(self.clock.now())),
+            pending: count(JobStatus::WaitingForIdle) + if settle_open { 0 } else { queued },
+            settling: count(JobStatus::WaitingForSettle) + if settle_open { queued } else { 0 },
+            active: count(JobStatus::Compressing),
+            completed: count(JobStatus::Completed),
+            failed: count(JobStatus::Failed),
+            skipped: count(JobStatus::Skipped),
+        }
+    }
+
+    fn wait_reason(&self) -> Option<WaitReason> {
+        if self.user_paused {
+            return Some(WaitReason::UserPaused);
+        }
+        if self.stop_request == Some(StopRequest::AfterCurrentJob) {
+            return Some(WaitReason::StoppingAfterCurrent);
+        }
+        match self.state {
+            SchedulerState::WaitingForEvents => Some(WaitReason::NoJobs),
+            SchedulerState::WaitingForSettle => {
+                let installing = self
+                    .queue
+                    .iter()
+                    .filter(|j| {
+                        matches!(j.status, JobStatus::Pending | JobStatus::WaitingForSettle)
+                    })
+                    .any(|j| {
+                        self.install_states
+                            .get(&j.game_path)
+                            .is_some_and(|state| state.awaits_writes())
+                    });
+                Some(if installing {
+                    WaitReason::InstallInProgress
+                } else {
+                    WaitReason::Settling
+                })
+            }
+            SchedulerState::Backoff => Some(WaitReason::Backoff),
+            SchedulerState::Paused => Some(WaitReason::NotIdle),
+            SchedulerState::WaitingForIdle if !self.last_idle => Some(WaitReason::NotIdle),
+            SchedulerState::WaitingForIdle | SchedulerState::SafetyCheck => {
+                let waiting: Vec<&AutomationJob> = self
+                    .queue
+                    .iter()
+                    .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::WaitingForIdle))
+                    .collect();
+                if waiting.is_empty() {
+                    None
+                } else if waiting
+                    .iter()
+                    .all(|j| self.running_games.contains(&j.game_path))
+                {
+                    Some(WaitReason::GamesRunning)
+                } else if waiting
+                    .iter()
+                    .all(|j| self.offline_volumes.contains(&volume_key(&j.game_path)))
+                {
+                    Some(WaitReason::DrivesOffline)
+                } else if waiting.iter().all(|j| self.is_io_busy(&j.game_path)) {
+                    Some(WaitReason::FolderBusy)
+                } else {
+                    None
+                }
+            }
+            SchedulerState::Compressing => None,
+        }
+    }
+
     pub fn pending_queue_len(&self) -> usize {
         self.queue
             .iter()
@@ -406,6 +777,14 @@ impl AutoScheduler {
             .find(|j| j.status == JobStatus::Compressing)
     }
 
+    /// Number of jobs currently compressing.
+    pub fn active_job_count(&self) -> usize {
+        self.queue
+            .iter()
+            .filter(|j| j.status == JobStatus::Compressing)
+            .count()
+    }
+
     pub fn queue_snapshot(&self) -> Vec<AutomationJob> {
         self.queue.iter().cloned().collect()
     }
@@ -434,29 +813,127 @@ impl AutoScheduler {
         self.queue.push_back(job);
     }
 
-    /// Get the next job to process, prioritizing Reconcile > NewInstall > Opportunistic.
-    fn next_pending_job(&self) -> Option<&AutomationJob> {
-        let is_ready =
-            |j: &&AutomationJob| matches!(j.status, JobStatus::Pending | JobStatus::WaitingForIdle);
+    /// Mark the next runnable job as compressing and hand it to the worker.
+    ///
+    /// Returns `None` when the concurrency limit is reached, every ready job
+    /// targets a disk that already has an active job, or a stop after the
+    /// current job was requested.
+    fn start_next_job(&mut self) -> Option<SchedulerAction> {
+        if self.stop_request == Some(StopRequest::AfterCurrentJob) {
+            return None;
+        }
+        let limit = self
+            .config
+            .max_concurrent_jobs
+            .clamp(1, MAX_CONCURRENT_JOBS_LIMIT);
+        if self.active_job_count() >= limit {
+            return None;
+        }
 
-        // Reconcile jobs first (post-update recompression)
-        if let Some(job) = self
+        let busy_disks: HashSet<String> = self
             .queue
             .iter()
-            .find(|j| is_ready(j) && j.kind == JobKind::Reconcile)
+            .filter(|j| j.status == JobStatus::Compressing)
+            .map(|j| disk_key(&j.game_path))
+            .collect();
+        let mut job = loop {
+            let candidate = self.next_pending_job(&busy_disks)?.clone();
+            if !self.still_being_written(&candidate.game_path) {
+                break candidate;
+            }
+        };
+        job.status = JobStatus::Compressing;
+        job.started_at = Some(self.clock.system_now());
+
+        if let Some(q) = self
+            .queue
+            .iter_mut()
+            .find(|j| j.idempotency_key == job.idempotency_key)
         {
-            return Some(job);
+            q.status = JobStatus::Compressing;
+            q.started_at = job.started_at;
         }
-        // Then new installs
-        if let Some(job) = self
+        Some(SchedulerAction::Compress(job))
+    }
+
+    /// Whether the settle window that began at `start` is over. The fixed
+    /// cooldown is only a fallback for games without a launcher signal.
+    fn settle_elapsed(&self, start: Instant) -> bool {
+        let elapsed = self.clock.now().saturating_duration_since(start);
+        let states: Vec<InstallState> = self
             .queue
             .iter()
-            .find(|j| is_ready(j) && j.kind == JobKind::NewInstall)
+            .filter(|j| matches!(j.status, JobStatus::Pending | JobStatus::WaitingForSettle))
+            .map(|j| {
+                self.install_states
+                    .get(&j.game_path)
+                    .copied()
+                    .unwrap_or(InstallState::Unknown)
+            })
+            .collect();
+        if states.iter().any(|state| state.awaits_writes()) {
+            return elapsed >= MAX_INSTALL_SETTLE;
+        }
+        if !states.is_empty() && states.iter().all(|&state| state == InstallState::Complete) {
+            return elapsed >= self.config.install_settle.min(self.config.cooldown);
+        }
+        elapsed >= self.config.cooldown
+    }
+
+    /// Probe `game_path` for writes within `SchedulerConfig::io_quiet_window`.
+    /// A busy game is passed over until [`IO_ACTIVITY_RECHECK`] has elapsed.
+    fn still_being_written(&mut self, game_path: &Path) -> bool {
+        if !has_recent_writes(game_path, self.config.io_quiet_window) {
+            self.io_busy_until.remove(game_path);
+            return false;
+        }
+        log::info!(
+            "Game folder still being written, holding job: {}",
+            game_path.display()
+        );
+        let queued: HashSet<&PathBuf> = self.queue.iter().map(|j| &j.game_path).collect();
+        self.io_busy_until.retain(|path, _| queued.contains(path));
+        self.io_busy_until.insert(
+            game_path.to_path_buf(),
+            self.clock.now() + IO_ACTIVITY_RECHECK,
+        );
+        true
+    }
+
+    /// Move jobs whose settle window elapsed while other jobs were running
+    /// to `WaitingForIdle` so they can start on a free volume.
+    fn promote_settled_jobs(&mut self) {
+        if self
+            .settle_started
+            .is_some_and(|start| !self.settle_elapsed(start))
         {
-            return Some(job);
+            return;
+        }
+        self.settle_started = None;
+        for job in &mut self.queue {
+            if matches!(job.status, JobStatus::Pending | JobStatus::WaitingForSettle) {
+                job.status = JobStatus::WaitingForIdle;
+            }
         }
-        // Finally opportunistic
-        self.queue.iter().find(|j| is_ready(j))
+    }
+
+    /// Get the next job to process, prioritizing Reconcile > NewInstall > Opportunistic.
+    ///
+    /// Jobs on a disk in `busy_disks` are not eligible.
+    fn next_pending_job(&self, busy_disks: &HashSet<String>) -> Option<&AutomationJob> {
+        let is_ready = |j: &&AutomationJob| {
+            matches!(j.status, JobStatus::Pending | JobStatus::WaitingForIdle)
+                && !self.running_games.contains(&j.game_path)
+                && !self.offline_volumes.contains(&volume_key(&j.game_path))
+                && !self.is_io_busy(&j.game_path)
+                && (busy_disks.is_empty() || !busy_disks.contains(&disk_key(&j.game_path)))
+        };
+
+        // Oldest job of the most urgent kind.
+        self.queue
+            .iter()
+            .filter(is_ready)
+            .min_by_key(|j| j.kind.start_rank())
     }
 
     fn has_pending_jobs(&self) -> bool {
@@ -468,6 +945,26 @@ impl AutoScheduler {
         })
     }
 
+    fn has_jobs_waiting_on_running_games(&self) -> bool {
+        self.queue.iter().any(|j| {
+            matches!(j.status, JobStatus::Pending | JobStatus::WaitingForIdle)
+                && self.running_games.contains(&j.game_path)
+        })
+    }
+
+    fn has_jobs_waiting_on_io(&self) -> bool {
+        self.queue.iter().any(|j| {
+            matches!(j.status, JobStatus::Pending | JobStatus::WaitingForIdle)
+                && self.io_busy_until.contains_key(&j.game_path)
+        })
+    }
+
+    fn is_io_busy(&self, game_path: &Path) -> bool {
+        self.io_busy_until
+            .get(game_path)
+            .is_some_and(|&until| self.clock.now() < until)
+    }
+
     fn has_active_job(&self) -> bool {
         self.queue
             .iter()
@@ -508,3 +1005,26 @@ impl AutoScheduler {
         });
     }
 }
+
+fn journal_event_kind(kind: JobKind) -> JournalEventKind {
+    match kind {
+        JobKind::NewInstall => JournalEventKind::NewInstall,
+        JobKind::Reconcile => JournalEventKind::Reconcile,
+        JobKind::Opportunistic => JournalEventKind::Opportunistic,
+    }
+}
+
+fn volume_key(path: &Path) -> String {
+    crate::discovery::storage::volume_key_for_path(path)
+}
+
+#[cfg(not(test))]
+fn disk_key(path: &Path) -> String {
+    crate::discovery::storage::disk_key_for_path(path)
+}
+
+/// Tests stand in drive letters for disks, whatever the host's layout.
+#[cfg(test)]
+fn disk_key(path: &Path) -> String {
+    volume_key(path)
+}
@@ -1,22 +1,29 @@
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock, Mutex};
 
 use tempfile::TempDir;
 
 use super::*;
+use crate::automation::clock::VirtualClock;
 use crate::automation::journal::JournalWriter;
+use crate::discovery::install_state::InstallState;
+use crate::settings::{ExclusionRule, Exclusions};
 
 static TEST_MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
-fn test_scheduler() -> (AutoScheduler, TempDir) {
+fn test_scheduler() -> (AutoScheduler, VirtualClock, TempDir) {
     let dir = TempDir::new().unwrap();
     let journal = JournalWriter::new(dir.path().join("test_journal.json"));
     let config = SchedulerConfig {
         cooldown: std::time::Duration::from_millis(10),
+        // Tests write game folders moments before starting jobs.
+        io_quiet_window: std::time::Duration::ZERO,
         ..Default::default()
     };
-    (AutoScheduler::new(config, journal), dir)
+    let clock = VirtualClock::new();
+    let scheduler = AutoScheduler::with_clock(config, journal, Arc::new(clock.clone()));
+    (scheduler, clock, dir)
 }
 
 fn make_event(path: &str) -> WatchEvent {
@@ -36,14 +43,14 @@ fn make_modify_event(path: &str) -> WatchEvent {
 #[test]
 fn initial_state_is_waiting_for_events() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (scheduler, _dir) = test_scheduler();
+    let (scheduler, _clock, _dir) = test_scheduler();
     assert_eq!(scheduler.state(), SchedulerState::WaitingForEvents);
 }
 
 #[test]
 fn event_transitions_to_waiting_for_settle() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
     assert_eq!(scheduler.state(), SchedulerState::WaitingForSettle);
 }
@@ -51,11 +58,11 @@ fn event_transitions_to_waiting_for_settle() {
 #[test]
 fn duplicate_event_restarts_the_settle_window() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
     let first_settle_start = scheduler.settle_started.expect("initial settle time");
 
-    std::thread::sleep(std::time::Duration::from_millis(2));
+    clock.advance(std::time::Duration::from_millis(2));
     scheduler.on_event(make_modify_event(r"C:\Games\TestGame"));
 
     assert_eq!(scheduler.state(), SchedulerState::WaitingForSettle);
@@ -68,10 +75,10 @@ fn duplicate_event_restarts_the_settle_window() {
 #[test]
 fn event_during_compression_queues_behind_the_active_job() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\Active"));
     let _ = scheduler.tick(false, false); // persist
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false); // settle -> idle
     let _ = scheduler.tick(true, false); // idle -> safety
     let action = scheduler.tick(true, false); // safety -> compressing
@@ -93,13 +100,13 @@ fn event_during_compression_queues_behind_the_active_job() {
 #[test]
 fn settle_complete_transitions_to_waiting_for_idle() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     // Consume persist action
     let _ = scheduler.tick(false, false);
 
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false);
     assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
 }
@@ -107,13 +114,13 @@ fn settle_complete_transitions_to_waiting_for_idle() {
 #[test]
 fn idle_detected_transitions_to_safety_check() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     // Consume persist
     let _ = scheduler.tick(false, false);
 
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     // Settle
     let _ = scheduler.tick(false, false);
     assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
@@ -126,12 +133,12 @@ fn idle_detected_transitions_to_safety_check() {
 #[test]
 fn safety_pass_transitions_to_compressing() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     // Advance through states
     let _ = scheduler.tick(false, false); // persist
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false); // settle -> WaitingForIdle
     let _ = scheduler.tick(true, false); // idle -> SafetyCheck
 
@@ -143,11 +150,11 @@ fn safety_pass_transitions_to_compressing() {
 #[test]
 fn completion_returns_to_waiting_or_next_job() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     let _ = scheduler.tick(false, false);
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false);
     let _ = scheduler.tick(true, false);
 
@@ -158,15 +165,80 @@ fn completion_returns_to_waiting_or_next_job() {
     assert_eq!(scheduler.state(), SchedulerState::WaitingForEvents);
 }
 
+#[test]
+fn refired_event_for_unchanged_content_is_ignored() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, dir) = test_scheduler();
+    let game = dir.path().join("Game");
+    std::fs::create_dir_all(&game).unwrap();
+    std::fs::write(game.join("data.pak"), b"v1").unwrap();
+    let event = || WatchEvent::GameInstalled {
+        path: game.clone(),
+        game_name: None,
+    };
+
+    scheduler.on_event(event());
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    let _ = scheduler.tick(true, false);
+    let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) else {
+        panic!("job should start");
+    };
+    scheduler.job_completed(&job.idempotency_key);
+
+    scheduler.on_event(event());
+    assert_eq!(scheduler.pending_queue_len(), 0);
+
+    std::fs::write(game.join("patch.pak"), b"v2").unwrap();
+    scheduler.on_event(event());
+    assert_eq!(scheduler.pending_queue_len(), 1);
+}
+
+#[test]
+fn simulated_job_completes_but_allows_a_real_run() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, dir) = test_scheduler();
+    let game = dir.path().join("Game");
+    std::fs::create_dir_all(&game).unwrap();
+    std::fs::write(game.join("data.pak"), b"v1").unwrap();
+    let event = || WatchEvent::GameInstalled {
+        path: game.clone(),
+        game_name: None,
+    };
+
+    scheduler.on_event(event());
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    let _ = scheduler.tick(true, false);
+    let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) else {
+        panic!("job should start");
+    };
+    scheduler.job_simulated(&job.idempotency_key);
+
+    let finished = scheduler
+        .queue_snapshot()
+        .into_iter()
+        .find(|j| j.idempotency_key == job.idempotency_key)
+        .expect("finished job stays visible");
+    assert_eq!(finished.status, JobStatus::Completed);
+    assert!(finished.simulated);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForEvents);
+
+    scheduler.on_event(event());
+    assert_eq!(scheduler.pending_queue_len(), 1);
+}
+
 #[test]
 fn safety_fail_transitions_to_backoff() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
     scheduler.on_event(make_event(r"C:\Games\TestGame2"));
 
     let _ = scheduler.tick(false, false);
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false);
     let _ = scheduler.tick(true, false);
 
@@ -180,11 +252,11 @@ fn safety_fail_transitions_to_backoff() {
 #[test]
 fn user_activity_pauses_compression() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     let _ = scheduler.tick(false, false);
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false);
     let _ = scheduler.tick(true, false);
     let _ = scheduler.tick(true, false); // starts compressing
@@ -197,11 +269,11 @@ fn user_activity_pauses_compression() {
 #[test]
 fn resume_from_paused() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
 
     let _ = scheduler.tick(false, false);
-    std::thread::sleep(std::time::Duration::from_millis(20));
+    clock.advance(std::time::Duration::from_millis(20));
     let _ = scheduler.tick(false, false);
     let _ = scheduler.tick(true, false);
     let _ = scheduler.tick(true, false);
@@ -217,11 +289,9 @@ fn excluded_path_is_skipped() {
     let _g = TEST_MUTEX.lock().unwrap();
     let dir = TempDir::new().unwrap();
     let journal = JournalWriter::new(dir.path().join("test.json"));
-    let mut excluded = HashSet::new();
-    excluded.insert(r"c:\games\excluded".to_string());
     let config = SchedulerConfig {
         cooldown: std::time::Duration::from_millis(10),
-        excluded_paths: excluded,
+        exclusions: Exclusions::new(&[ExclusionRule::Path(r"c:\games\excluded".to_string())]),
         ..Default::default()
     };
     let mut scheduler = AutoScheduler::new(config, journal);
@@ -235,10 +305,29 @@ fn excluded_path_is_skipped() {
     assert_eq!(scheduler.state(), SchedulerState::WaitingForEvents);
 }
 
+#[test]
+fn planned_job_skips_the_settle_window() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
+    let path = PathBuf::from(r"C:\Games\Planned");
+
+    assert!(scheduler.enqueue_opportunistic(path.clone(), Some("Planned".to_string())));
+    assert!(!scheduler.enqueue_opportunistic(path, None));
+    assert_eq!(scheduler.pending_queue_len(), 1);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+
+    let _ = scheduler.tick(false, false); // persist
+    let _ = scheduler.tick(true, false); // idle -> SafetyCheck
+    let action = scheduler.tick(true, false);
+    assert!(
+        matches!(action, Some(SchedulerAction::Compress(job)) if job.kind == JobKind::Opportunistic)
+    );
+}
+
 #[test]
 fn duplicate_idempotency_key_rejected() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
 
     scheduler.on_event(make_event(r"C:\Games\TestGame"));
     let initial_len = scheduler.pending_queue_len();
@@ -274,10 +363,63 @@ fn crash_recovery_replays_pending_jobs() {
     }
 }
 
+#[test]
+fn restart_during_backoff_keeps_backoff_and_finished_jobs() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let dir = TempDir::new().unwrap();
+    let journal_path = dir.path().join("test.json");
+    let config = || SchedulerConfig {
+        cooldown: std::time::Duration::from_millis(10),
+        ..Default::default()
+    };
+    let clock = VirtualClock::new();
+
+    {
+        let journal = JournalWriter::new(journal_path.clone());
+        let mut scheduler = AutoScheduler::with_clock(config(), journal, Arc::new(clock.clone()));
+        scheduler.on_event(make_event(r"C:\Games\Failing"));
+        scheduler.on_event(make_event(r"C:\Games\Waiting"));
+        let _ = scheduler.tick(false, false);
+        clock.advance(std::time::Duration::from_millis(20));
+        let _ = scheduler.tick(false, false);
+        let _ = scheduler.tick(true, false);
+        if let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) {
+            scheduler.job_failed(&job.idempotency_key, "test failure".to_string());
+        }
+        assert_eq!(scheduler.state(), SchedulerState::Backoff);
+        scheduler.persist().unwrap();
+    }
+
+    clock.advance(std::time::Duration::from_secs(20));
+    let journal = JournalWriter::new(journal_path);
+    let mut scheduler =
+        AutoScheduler::restore_or_new_with_clock(config(), journal, Arc::new(clock.clone()));
+    assert_eq!(scheduler.state(), SchedulerState::Backoff);
+    assert_eq!(scheduler.consecutive_failures, 1);
+    let remaining = scheduler
+        .backoff_until
+        .expect("backoff restored")
+        .saturating_duration_since(clock.now());
+    assert_eq!(
+        remaining,
+        INITIAL_BACKOFF - std::time::Duration::from_secs(20)
+    );
+    let failed: Vec<_> = scheduler
+        .queue_snapshot()
+        .into_iter()
+        .filter(|j| j.status == JobStatus::Failed)
+        .collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].error.as_deref(), Some("test failure"));
+
+    assert!(scheduler.tick(true, false).is_none());
+    assert_eq!(scheduler.state(), SchedulerState::Backoff);
+}
+
 #[test]
 fn game_modified_creates_reconcile_job() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
 
     scheduler.on_event(make_modify_event(r"C:\Games\TestGame"));
 
@@ -289,7 +431,7 @@ fn game_modified_creates_reconcile_job() {
 #[test]
 fn queue_bounded_at_64() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
 
     for i in 0..70 {
         scheduler.on_event(WatchEvent::GameInstalled {
@@ -304,7 +446,7 @@ fn queue_bounded_at_64() {
 #[test]
 fn backoff_exponential_with_cap() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
 
     // Simulate multiple failures
     for i in 0..10 {
@@ -313,7 +455,7 @@ fn backoff_exponential_with_cap() {
             game_name: None,
         });
         let _ = scheduler.tick(false, false); // persist
-        std::thread::sleep(std::time::Duration::from_millis(20));
+        clock.advance(std::time::Duration::from_millis(20));
         let _ = scheduler.tick(false, false); // settle
         let _ = scheduler.tick(true, false); // idle -> safety
         if let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) {
@@ -323,15 +465,43 @@ fn backoff_exponential_with_cap() {
 
     // Backoff should exist and not exceed MAX_BACKOFF
     if let Some(until) = scheduler.backoff_until {
-        let remaining = until.saturating_duration_since(std::time::Instant::now());
+        let remaining = until.saturating_duration_since(clock.now());
         assert!(remaining <= MAX_BACKOFF);
     }
 }
 
+#[test]
+fn backoff_doubles_per_consecutive_failure() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+
+    for (i, expected) in [1, 2, 4, 8, 16, 30, 30].into_iter().enumerate() {
+        scheduler.on_event(make_event(&format!(r"C:\Games\Failing{i}")));
+        scheduler.on_event(make_event(&format!(r"C:\Games\Waiting{i}")));
+        let Some(SchedulerAction::Compress(job)) =
+            (0..6).find_map(|_| match scheduler.tick(true, false) {
+                Some(SchedulerAction::Persist) | None => {
+                    clock.advance(std::time::Duration::from_millis(20));
+                    None
+                }
+                action => action,
+            })
+        else {
+            panic!("job {i} should start");
+        };
+        scheduler.job_failed(&job.idempotency_key, "test".to_string());
+
+        let backoff = scheduler.status().backoff_remaining.expect("backing off");
+        assert_eq!(backoff, std::time::Duration::from_secs(expected * 60));
+        clock.advance(backoff);
+        let _ = scheduler.tick(true, false); // backoff over
+    }
+}
+
 #[test]
 fn pause_resume_does_not_deadlock() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, _clock, _dir) = test_scheduler();
 
     for _ in 0..100 {
         scheduler.pause();
@@ -384,7 +554,7 @@ fn concurrent_event_ingestion_safe() {
 #[test]
 fn prune_finished_removes_old_completed_jobs() {
     let _g = TEST_MUTEX.lock().unwrap();
-    let (mut scheduler, _dir) = test_scheduler();
+    let (mut scheduler, clock, _dir) = test_scheduler();
 
     // Add and complete many jobs
     for i in 0..20 {
@@ -393,7 +563,7 @@ fn prune_finished_removes_old_completed_jobs() {
             game_name: None,
         });
         let _ = scheduler.tick(false, false); // persist
-        std::thread::sleep(std::time::Duration::from_millis(15));
+        clock.advance(std::time::Duration::from_millis(15));
         let _ = scheduler.tick(false, false); // settle
         let _ = scheduler.tick(true, false); // idle -> safety
         if let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) {
@@ -416,3 +586,431 @@ fn prune_finished_removes_old_completed_jobs() {
         .count();
     assert!(finished <= MAX_FINISHED_JOBS);
 }
+
+fn concurrent_scheduler(max_concurrent_jobs: usize) -> (AutoScheduler, VirtualClock, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let journal = JournalWriter::new(dir.path().join("test_journal.json"));
+    let config = SchedulerConfig {
+        cooldown: std::time::Duration::from_millis(10),
+        max_concurrent_jobs,
+        ..Default::default()
+    };
+    let clock = VirtualClock::new();
+    let scheduler = AutoScheduler::with_clock(config, journal, Arc::new(clock.clone()));
+    (scheduler, clock, dir)
+}
+
+fn drain_compress_actions(scheduler: &mut AutoScheduler, ticks: usize) -> Vec<PathBuf> {
+    let mut started = Vec::new();
+    for _ in 0..ticks {
+        if let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) {
+            started.push(job.game_path);
+        }
+    }
+    started
+}
+
+#[test]
+fn jobs_on_distinct_volumes_run_concurrently() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = concurrent_scheduler(2);
+    scheduler.on_event(make_event(r"C:\Games\Alpha"));
+    scheduler.on_event(make_event(r"D:\Games\Beta"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+
+    let started = drain_compress_actions(&mut scheduler, 6);
+
+    assert_eq!(started.len(), 2);
+    assert_eq!(scheduler.active_job_count(), 2);
+    assert_eq!(scheduler.state(), SchedulerState::Compressing);
+}
+
+#[test]
+fn jobs_on_same_volume_stay_serialized() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = concurrent_scheduler(2);
+    scheduler.on_event(make_event(r"C:\Games\Alpha"));
+    scheduler.on_event(make_event(r"C:\Games\Beta"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+
+    let started = drain_compress_actions(&mut scheduler, 6);
+    assert_eq!(started.len(), 1);
+
+    let key = scheduler.active_job().unwrap().idempotency_key.clone();
+    scheduler.job_completed(&key);
+    let _ = scheduler.tick(true, false); // persist
+    let started = drain_compress_actions(&mut scheduler, 4);
+    assert_eq!(started.len(), 1);
+}
+
+#[test]
+fn completing_one_of_two_jobs_keeps_compressing() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = concurrent_scheduler(2);
+    scheduler.on_event(make_event(r"C:\Games\Alpha"));
+    scheduler.on_event(make_event(r"D:\Games\Beta"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = drain_compress_actions(&mut scheduler, 6);
+
+    let key = scheduler.active_job().unwrap().idempotency_key.clone();
+    scheduler.job_completed(&key);
+
+    assert_eq!(scheduler.state(), SchedulerState::Compressing);
+    assert_eq!(scheduler.active_job_count(), 1);
+}
+
+fn running_job_key(scheduler: &AutoScheduler, path: &str) -> String {
+    scheduler
+        .queue_snapshot()
+        .into_iter()
+        .find(|job| job.status == JobStatus::Compressing && job.game_path == Path::new(path))
+        .map(|job| job.idempotency_key)
+        .expect("job should be running")
+}
+
+#[test]
+fn failure_during_another_job_backs_off_once_it_finishes() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = concurrent_scheduler(2);
+    scheduler.on_event(make_event(r"C:\Games\Alpha"));
+    scheduler.on_event(make_event(r"D:\Games\Beta"));
+    scheduler.on_event(make_event(r"C:\Games\Gamma"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    assert_eq!(drain_compress_actions(&mut scheduler, 6).len(), 2);
+
+    let alpha = running_job_key(&scheduler, r"C:\Games\Alpha");
+    scheduler.job_failed(&alpha, "test failure".to_string());
+    assert_eq!(scheduler.state(), SchedulerState::Compressing);
+    let beta = running_job_key(&scheduler, r"D:\Games\Beta");
+    scheduler.job_completed(&beta);
+
+    assert_eq!(scheduler.state(), SchedulerState::Backoff);
+    assert!(drain_compress_actions(&mut scheduler, 4).is_empty());
+    assert!(scheduler
+        .status()
+        .backoff_remaining
+        .is_some_and(|left| left > std::time::Duration::ZERO));
+
+    clock.advance(INITIAL_BACKOFF);
+    assert_eq!(
+        drain_compress_actions(&mut scheduler, 6),
+        vec![PathBuf::from(r"C:\Games\Gamma")]
+    );
+    assert!(scheduler.backoff_until.is_none());
+}
+
+#[test]
+fn user_pause_survives_idle_ticks_and_job_results() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\TestGame"));
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    let _ = scheduler.tick(true, false);
+    let _ = scheduler.tick(true, false); // starts compressing
+
+    scheduler.pause();
+    let key = scheduler.active_job().unwrap().idempotency_key.clone();
+    scheduler.job_failed(&key, "Cancelled due to user activity".to_string());
+    for _ in 0..4 {
+        let _ = scheduler.tick(true, false);
+    }
+
+    assert!(scheduler.is_user_paused());
+    assert_eq!(scheduler.state(), SchedulerState::Paused);
+
+    scheduler.resume();
+    assert!(!scheduler.is_user_paused());
+    assert_ne!(scheduler.state(), SchedulerState::Paused);
+}
+
+#[test]
+fn running_game_is_passed_over_until_it_exits() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\Playing"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false); // settle -> idle
+
+    scheduler.set_running_games(&[PathBuf::from(r"C:\Games\Playing")]);
+    assert!(drain_compress_actions(&mut scheduler, 4).is_empty());
+    assert_eq!(scheduler.pending_queue_len(), 1);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+
+    scheduler.set_running_games(&[]);
+    assert_eq!(
+        drain_compress_actions(&mut scheduler, 4),
+        vec![PathBuf::from(r"C:\Games\Playing")]
+    );
+}
+
+#[test]
+fn launched_game_is_deferred_without_backoff() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\Playing"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = drain_compress_actions(&mut scheduler, 4);
+    let key = scheduler.active_job().unwrap().idempotency_key.clone();
+
+    scheduler.set_running_games(&[PathBuf::from(r"C:\Games\Playing")]);
+    scheduler.job_deferred(&key);
+
+    assert!(scheduler.backoff_until.is_none());
+    assert_eq!(scheduler.active_job_count(), 0);
+    assert_eq!(scheduler.pending_queue_len(), 1);
+    assert_eq!(
+        scheduler.unfinished_game_paths(),
+        vec![PathBuf::from(r"C:\Games\Playing")]
+    );
+}
+
+fn install_state_scheduler() -> (AutoScheduler, VirtualClock, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let journal = JournalWriter::new(dir.path().join("test_journal.json"));
+    let config = SchedulerConfig {
+        cooldown: std::time::Duration::from_secs(60 * 60),
+        install_settle: std::time::Duration::from_millis(10),
+        ..Default::default()
+    };
+    let clock = VirtualClock::new();
+    let scheduler = AutoScheduler::with_clock(config, journal, Arc::new(clock.clone()));
+    (scheduler, clock, dir)
+}
+
+#[test]
+fn download_in_progress_holds_the_settle() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    let game = PathBuf::from(r"C:\Games\Downloading");
+    scheduler.on_event(make_event(r"C:\Games\Downloading"));
+    scheduler.set_install_states(&HashMap::from([(game.clone(), InstallState::InProgress)]));
+    let _ = scheduler.tick(true, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForSettle);
+
+    scheduler.set_install_states(&HashMap::from([(game, InstallState::Unknown)]));
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+}
+
+#[test]
+fn completed_install_ends_the_settle_before_the_cooldown() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = install_state_scheduler();
+    let game = PathBuf::from(r"C:\Games\Installed");
+    scheduler.on_event(make_event(r"C:\Games\Installed"));
+    let _ = scheduler.tick(true, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForSettle);
+
+    scheduler.set_install_states(&HashMap::from([(game, InstallState::Complete)]));
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+}
+
+#[test]
+fn queued_update_holds_the_settle_until_installed() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = install_state_scheduler();
+    let game = PathBuf::from(r"C:\Games\Patching");
+    scheduler.on_event(make_event(r"C:\Games\Patching"));
+    scheduler.set_install_states(&HashMap::from([(
+        game.clone(),
+        InstallState::UpdatePending,
+    )]));
+    let _ = scheduler.tick(true, false); // persist
+    clock.advance(std::time::Duration::from_secs(2 * 60 * 60));
+
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForSettle);
+
+    scheduler.set_install_states(&HashMap::from([(game, InstallState::Complete)]));
+    let _ = scheduler.tick(true, false);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+}
+
+#[test]
+fn jobs_on_an_offline_drive_wait_until_it_returns() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"E:\Games\External"));
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false); // settle -> idle
+
+    scheduler.on_event(WatchEvent::DriveOffline {
+        path: PathBuf::from(r"E:\Games"),
+    });
+    assert!(drain_compress_actions(&mut scheduler, 4).is_empty());
+    assert_eq!(scheduler.pending_queue_len(), 1);
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForEvents);
+
+    scheduler.on_event(WatchEvent::DriveOnline {
+        path: PathBuf::from(r"E:\Games"),
+    });
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+    assert_eq!(
+        drain_compress_actions(&mut scheduler, 4),
+        vec![PathBuf::from(r"E:\Games\External")]
+    );
+}
+
+#[test]
+fn game_folder_still_being_written_is_held() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let dir = TempDir::new().unwrap();
+    let journal = JournalWriter::new(dir.path().join("test_journal.json"));
+    let config = SchedulerConfig {
+        cooldown: std::time::Duration::from_millis(10),
+        io_quiet_window: std::time::Duration::from_secs(60),
+        ..Default::default()
+    };
+    let clock = VirtualClock::new();
+    let mut scheduler = AutoScheduler::with_clock(config, journal, Arc::new(clock.clone()));
+    let game = dir.path().join("Game");
+    std::fs::create_dir_all(&game).unwrap();
+    std::fs::write(game.join("shaders.bin"), b"compiling").unwrap();
+
+    scheduler.on_event(WatchEvent::GameInstalled {
+        path: game.clone(),
+        game_name: None,
+    });
+    let _ = scheduler.tick(true, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(true, false); // settle elapsed
+    let _ = scheduler.tick(true, false); // idle -> safety check
+
+    assert!(scheduler.tick(true, false).is_none());
+    assert_eq!(scheduler.state(), SchedulerState::WaitingForIdle);
+    assert_eq!(scheduler.pending_queue_len(), 1);
+
+    // Once the hold expires and the folder has gone quiet the job starts.
+    scheduler.config.io_quiet_window = std::time::Duration::ZERO;
+    scheduler.io_busy_until.clear();
+    let _ = scheduler.tick(true, false);
+    assert!(matches!(
+        scheduler.tick(true, false),
+        Some(SchedulerAction::Compress(job)) if job.game_path == game
+    ));
+}
+
+#[test]
+fn status_explains_why_nothing_starts() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    assert_eq!(scheduler.status().wait_reason, Some(WaitReason::NoJobs));
+
+    scheduler.on_event(make_event(r"C:\Games\Playing"));
+    assert_eq!(scheduler.status().wait_reason, Some(WaitReason::Settling));
+    assert_eq!(scheduler.status().settling, 1);
+
+    let _ = scheduler.tick(false, false); // persist
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false); // settle elapsed
+    let _ = scheduler.tick(false, false);
+    assert_eq!(scheduler.status().wait_reason, Some(WaitReason::NotIdle));
+    assert_eq!(scheduler.status().pending, 1);
+
+    scheduler.set_running_games(&[PathBuf::from(r"C:\Games\Playing")]);
+    let _ = scheduler.tick(true, false);
+    assert_eq!(
+        scheduler.status().wait_reason,
+        Some(WaitReason::GamesRunning)
+    );
+
+    scheduler.pause();
+    assert_eq!(scheduler.status().wait_reason, Some(WaitReason::UserPaused));
+}
+
+#[test]
+fn status_reports_backoff_time_left() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\Failing"));
+    scheduler.on_event(make_event(r"C:\Games\Waiting"));
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    let _ = scheduler.tick(true, false);
+    let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) else {
+        panic!("job should start");
+    };
+    scheduler.job_failed(&job.idempotency_key, "test failure".to_string());
+
+    let status = scheduler.status();
+    assert_eq!(status.wait_reason, Some(WaitReason::Backoff));
+    assert!(status
+        .backoff_remaining
+        .is_some_and(|left| left <= INITIAL_BACKOFF));
+    assert_eq!((status.failed, status.pending), (1, 1));
+}
+
+#[test]
+fn stop_after_current_job_holds_the_rest_of_the_queue() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\First"));
+    scheduler.on_event(make_event(r"C:\Games\Second"));
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    let _ = scheduler.tick(true, false);
+    let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) else {
+        panic!("job should start");
+    };
+
+    scheduler.request_stop(StopRequest::AfterCurrentJob);
+    assert!(!scheduler.should_stop(), "the running job finishes first");
+    assert_eq!(
+        scheduler.status().wait_reason,
+        Some(WaitReason::StoppingAfterCurrent)
+    );
+
+    scheduler.job_completed(&job.idempotency_key);
+    for _ in 0..4 {
+        assert!(!matches!(
+            scheduler.tick(true, false),
+            Some(SchedulerAction::Compress(_))
+        ));
+    }
+    assert!(scheduler.should_stop());
+    assert_eq!(scheduler.pending_queue_len(), 1);
+}
+
+#[test]
+fn drain_stops_only_once_the_queue_is_empty() {
+    let _g = TEST_MUTEX.lock().unwrap();
+    let (mut scheduler, clock, _dir) = test_scheduler();
+    scheduler.on_event(make_event(r"C:\Games\First"));
+    scheduler.on_event(make_event(r"C:\Games\Second"));
+    let _ = scheduler.tick(false, false);
+    clock.advance(std::time::Duration::from_millis(20));
+    let _ = scheduler.tick(false, false);
+    scheduler.request_stop(StopRequest::WhenDrained);
+
+    let mut finished = 0;
+    for _ in 0..10 {
+        if let Some(SchedulerAction::Compress(job)) = scheduler.tick(true, false) {
+            assert!(!scheduler.should_stop());
+            scheduler.job_completed(&job.idempotency_key);
+            finished += 1;
+        }
+        if scheduler.should_stop() {
+            break;
+        }
+    }
+    assert_eq!(finished, 2);
+    assert!(scheduler.should_stop());
+}
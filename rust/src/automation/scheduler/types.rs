@@ -1,11 +1,12 @@
 //! Type definitions for the automation scheduler.
 
-use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
+use crate::settings::Exclusions;
+
 /// Maximum number of jobs in the queue. Oldest non-active dropped on overflow.
 pub const MAX_QUEUE_SIZE: usize = 64;
 
@@ -18,6 +19,17 @@ pub const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30 *
 /// Initial backoff duration (1 minute).
 pub const INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
 
+/// Longest a settle is held for a launcher that never reports the install
+/// finished, e.g. a stale partial-download file.
+pub const MAX_INSTALL_SETTLE: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// How long a game with recent writes is passed over before it is probed
+/// again.
+pub const IO_ACTIVITY_RECHECK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Upper bound for `SchedulerConfig::max_concurrent_jobs`.
+pub const MAX_CONCURRENT_JOBS_LIMIT: usize = 8;
+
 /// Scheduler state machine states.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SchedulerState {
@@ -30,6 +42,62 @@ pub enum SchedulerState {
     Backoff,
 }
 
+/// A pending request to stop the automation service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopRequest {
+    /// Start nothing new; stop once the running jobs finish.
+    AfterCurrentJob,
+    /// Keep working through the queue; stop once it is empty.
+    WhenDrained,
+}
+
+/// Why the scheduler is not starting work right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitReason {
+    /// Nothing is queued.
+    NoJobs,
+    /// Waiting out the cooldown after the last file change.
+    Settling,
+    /// The launcher reports a download or patch still running.
+    InstallInProgress,
+    /// The user is active at the machine.
+    NotIdle,
+    /// A do-not-disturb process is running.
+    BlockingProcess,
+    /// Focus Assist, presentation mode or a full-screen game is on.
+    FocusAssist,
+    /// The CPU is running hot or its clock is capped for heat or power.
+    ThermallyConstrained,
+    /// A recent failure started a backoff.
+    Backoff,
+    /// Paused via `AutoScheduler::pause`.
+    UserPaused,
+    /// Every ready job's game is running.
+    GamesRunning,
+    /// Every ready job's drive is unplugged.
+    DrivesOffline,
+    /// Every ready job's folder is still being written.
+    FolderBusy,
+    /// A stop-after-current request holds back the rest of the queue.
+    StoppingAfterCurrent,
+}
+
+/// Point-in-time view of the scheduler for status displays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerStatus {
+    pub state: SchedulerState,
+    /// `None` while work is running or about to start.
+    pub wait_reason: Option<WaitReason>,
+    pub backoff_remaining: Option<std::time::Duration>,
+    /// Past their settle window, waiting to start.
+    pub pending: usize,
+    pub settling: usize,
+    pub active: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
 /// What kind of automation job this is.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JobKind {
@@ -38,6 +106,18 @@ pub enum JobKind {
     Opportunistic,
 }
 
+impl JobKind {
+    /// Start order between kinds, lowest first: post-update recompression
+    /// before new installs before opportunistic work.
+    pub fn start_rank(self) -> u8 {
+        match self {
+            Self::Reconcile => 0,
+            Self::NewInstall => 1,
+            Self::Opportunistic => 2,
+        }
+    }
+}
+
 /// Status of a single automation job.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JobStatus {
@@ -61,6 +141,24 @@ pub struct AutomationJob {
     pub queued_at: SystemTime,
     pub started_at: Option<SystemTime>,
     pub error: Option<String>,
+    /// Completed as a dry run: estimated, not compressed.
+    #[serde(default)]
+    pub simulated: bool,
+}
+
+/// Scheduler state that outlives a restart, saved next to the journal.
+///
+/// The journal keeps pending jobs; this keeps what the scheduler learned
+/// from finished ones, so a restart during backoff does not retry a
+/// failing job straight away.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerSnapshot {
+    pub consecutive_failures: u32,
+    /// Wall-clock end of the current backoff; `Instant`s do not survive a
+    /// restart.
+    pub backoff_until: Option<SystemTime>,
+    /// Recently finished jobs, oldest first.
+    pub finished_jobs: Vec<AutomationJob>,
 }
 
 /// Actions the scheduler wants the auto_loop to perform.
@@ -75,16 +173,29 @@ pub enum SchedulerAction {
 /// Configuration for the scheduler.
 pub struct SchedulerConfig {
     pub cooldown: std::time::Duration,
-    pub excluded_paths: HashSet<String>,
+    /// Games whose events are ignored.
+    pub exclusions: Exclusions,
     pub watch_paths: Vec<PathBuf>,
+    /// Maximum jobs compressing at once. Jobs only run in parallel when
+    /// they target different physical disks.
+    pub max_concurrent_jobs: usize,
+    /// Quiet period that ends the settle early once the launcher reports
+    /// every settling install complete.
+    pub install_settle: std::time::Duration,
+    /// A settled game is only started once nothing in its folder was
+    /// written for this long. Zero disables the probe.
+    pub io_quiet_window: std::time::Duration,
 }
 
 impl Default for SchedulerConfig {
     fn default() -> Self {
         Self {
             cooldown: std::time::Duration::from_secs(300),
-            excluded_paths: HashSet::new(),
+            exclusions: Exclusions::default(),
             watch_paths: Vec::new(),
+            max_concurrent_jobs: 1,
+            install_settle: std::time::Duration::from_secs(30),
+            io_quiet_window: std::time::Duration::from_secs(3 * 60),
         }
     }
 }
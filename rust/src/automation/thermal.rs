@@ -0,0 +1,232 @@
+use std::time::{Duration, Instant};
+
+use sysinfo::Components;
+
+/// Temperature at or above which the CPU counts as thermally constrained.
+const HOT_CELSIUS: f32 = 90.0;
+/// Temperature the CPU must fall back to before jobs resume.
+const COOL_CELSIUS: f32 = 80.0;
+/// Clock limit, as a share of the rated maximum, at or below which the CPU
+/// counts as power- or thermal-throttled.
+const THROTTLED_LIMIT_RATIO: f32 = 0.85;
+/// Clock limit share the CPU must recover to before jobs resume.
+const RECOVERED_LIMIT_RATIO: f32 = 0.95;
+/// Sensors are read at most this often; WMI-backed reads are slow.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// One reading of the CPU's thermal state. Either field is `None` when the
+/// machine does not expose it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThermalSample {
+    /// Hottest CPU or thermal-zone sensor.
+    pub temperature_celsius: Option<f32>,
+    /// Lowest per-core clock limit divided by its rated maximum; below 1.0
+    /// when firmware or Windows caps the clock for heat or power.
+    pub clock_limit_ratio: Option<f32>,
+}
+
+/// Abstraction over thermal sensors for testability.
+pub trait ThermalSource: Send {
+    fn sample(&mut self) -> ThermalSample;
+}
+
+/// Real source: sysinfo's component temperatures and the processor clock
+/// limits from `CallNtPowerInformation`.
+pub struct SystemThermalSource {
+    components: Components,
+}
+
+impl SystemThermalSource {
+    pub fn new() -> Self {
+        Self {
+            components: Components::new_with_refreshed_list(),
+        }
+    }
+
+    fn hottest_sensor(&mut self) -> Option<f32> {
+        self.components.refresh(false);
+        self.components
+            .list()
+            .iter()
+            .filter_map(|component| component.temperature())
+            .filter(|celsius| celsius.is_finite() && *celsius > 0.0)
+            .reduce(f32::max)
+    }
+}
+
+impl Default for SystemThermalSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThermalSource for SystemThermalSource {
+    fn sample(&mut self) -> ThermalSample {
+        ThermalSample {
+            temperature_celsius: self.hottest_sensor(),
+            clock_limit_ratio: clock_limit_ratio(),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn clock_limit_ratio() -> Option<f32> {
+    use windows::Win32::System::Power::{
+        CallNtPowerInformation, ProcessorInformation, PROCESSOR_POWER_INFORMATION,
+    };
+
+    let cores = num_cpus::get();
+    let mut info = vec![PROCESSOR_POWER_INFORMATION::default(); cores];
+    let size = std::mem::size_of_val(info.as_slice()) as u32;
+    // SAFETY: the output buffer holds one PROCESSOR_POWER_INFORMATION per
+    // logical processor and `size` is its exact byte length.
+    let status = unsafe {
+        CallNtPowerInformation(
+            ProcessorInformation,
+            None,
+            0,
+            Some(info.as_mut_ptr().cast()),
+            size,
+        )
+    };
+    if status.is_err() {
+        return None;
+    }
+    info.iter()
+        .filter(|core| core.MaxMhz > 0)
+        .map(|core| core.MhzLimit as f32 / core.MaxMhz as f32)
+        .reduce(f32::min)
+}
+
+#[cfg(not(windows))]
+fn clock_limit_ratio() -> Option<f32> {
+    None
+}
+
+/// Tracks whether the machine is thermally constrained, with hysteresis so
+/// jobs do not flap around a threshold.
+pub struct ThermalMonitor {
+    source: Box<dyn ThermalSource>,
+    constrained: bool,
+    last_sample: Option<Instant>,
+}
+
+impl ThermalMonitor {
+    pub fn new() -> Self {
+        Self::with_source(Box::new(SystemThermalSource::new()))
+    }
+
+    pub fn with_source(source: Box<dyn ThermalSource>) -> Self {
+        Self {
+            source,
+            constrained: false,
+            last_sample: None,
+        }
+    }
+
+    /// Whether the CPU is hot or clock-limited, re-sampled at most every
+    /// [`SAMPLE_INTERVAL`].
+    pub fn is_constrained(&mut self) -> bool {
+        if self
+            .last_sample
+            .is_some_and(|last| last.elapsed() < SAMPLE_INTERVAL)
+        {
+            return self.constrained;
+        }
+        self.last_sample = Some(Instant::now());
+
+        let sample = self.source.sample();
+        let was_constrained = self.constrained;
+        self.constrained = if was_constrained {
+            sample.temperature_celsius.is_some_and(|c| c > COOL_CELSIUS)
+                || sample
+                    .clock_limit_ratio
+                    .is_some_and(|ratio| ratio < RECOVERED_LIMIT_RATIO)
+        } else {
+            sample.temperature_celsius.is_some_and(|c| c >= HOT_CELSIUS)
+                || sample
+                    .clock_limit_ratio
+                    .is_some_and(|ratio| ratio <= THROTTLED_LIMIT_RATIO)
+        };
+        if self.constrained != was_constrained {
+            log::info!(
+                "[automation][thermal] constrained={} temperature_celsius={:?} clock_limit_ratio={:?}",
+                self.constrained,
+                sample.temperature_celsius,
+                sample.clock_limit_ratio
+            );
+        }
+        self.constrained
+    }
+}
+
+impl Default for ThermalMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub struct MockThermalSource {
+    pub samples: std::sync::Arc<std::sync::Mutex<ThermalSample>>,
+}
+
+#[cfg(test)]
+impl ThermalSource for MockThermalSource {
+    fn sample(&mut self) -> ThermalSample {
+        *self.samples.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    fn monitor() -> (ThermalMonitor, Arc<Mutex<ThermalSample>>) {
+        let samples = Arc::new(Mutex::new(ThermalSample::default()));
+        let source = MockThermalSource {
+            samples: samples.clone(),
+        };
+        (ThermalMonitor::with_source(Box::new(source)), samples)
+    }
+
+    fn resample(monitor: &mut ThermalMonitor) -> bool {
+        monitor.last_sample = None;
+        monitor.is_constrained()
+    }
+
+    #[test]
+    fn heat_constrains_until_cooled_below_hysteresis() {
+        let (mut monitor, samples) = monitor();
+        assert!(!monitor.is_constrained());
+
+        samples.lock().unwrap().temperature_celsius = Some(92.0);
+        assert!(resample(&mut monitor));
+
+        samples.lock().unwrap().temperature_celsius = Some(85.0);
+        assert!(resample(&mut monitor), "still above the cool-down mark");
+
+        samples.lock().unwrap().temperature_celsius = Some(75.0);
+        assert!(!resample(&mut monitor));
+    }
+
+    #[test]
+    fn clock_limit_constrains_without_temperature() {
+        let (mut monitor, samples) = monitor();
+        samples.lock().unwrap().clock_limit_ratio = Some(0.6);
+        assert!(resample(&mut monitor));
+
+        samples.lock().unwrap().clock_limit_ratio = Some(1.0);
+        assert!(!resample(&mut monitor));
+    }
+
+    #[test]
+    fn readings_are_cached_between_samples() {
+        let (mut monitor, samples) = monitor();
+        assert!(!monitor.is_constrained());
+        samples.lock().unwrap().temperature_celsius = Some(99.0);
+        assert!(!monitor.is_constrained());
+    }
+}
@@ -0,0 +1,296 @@
+//! Waking the machine from sleep for queued jobs.
+//!
+//! Jobs only run while the machine is awake, so a PC that sleeps through
+//! the night never reaches its queue. When the user picks a wake time,
+//! automation keeps a waking timer armed for it while jobs are waiting and
+//! holds off sleep once it fires and while jobs run, since Windows
+//! otherwise puts a timer-woken machine back to sleep within minutes.
+//!
+//! Machines that cannot resume for timers, or whose power plan disallows
+//! wake timers, fall back to a timer that only fires if the machine
+//! happens to be awake, with a warning logged once.
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1_000;
+/// How long sleep is held off after the wake time while jobs still wait,
+/// long enough for the machine to settle and count as idle.
+const POST_WAKE_HOLD_MS: u64 = 30 * 60 * 1_000;
+/// Wake times are minutes after local midnight, below this.
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Arms the wake timer and holds off sleep from the automation loop
+/// thread; the sleep hold belongs to the thread that takes it.
+pub struct MaintenanceWake {
+    timer: Option<platform::WakeTimer>,
+    /// UTC due time of the armed timer, in Unix milliseconds.
+    armed_for_ms: Option<u64>,
+    /// Wake time the armed timer was computed from.
+    armed_minute: Option<u32>,
+    /// End of the sleep hold after the wake time, in Unix milliseconds.
+    hold_until_ms: Option<u64>,
+    keeping_awake: bool,
+    warned_no_resume: bool,
+}
+
+impl MaintenanceWake {
+    pub fn new() -> Self {
+        Self {
+            timer: None,
+            armed_for_ms: None,
+            armed_minute: None,
+            hold_until_ms: None,
+            keeping_awake: false,
+            warned_no_resume: false,
+        }
+    }
+
+    /// Bring the timer and sleep hold in line with the loop's state.
+    /// `wake_minute_of_day` is `None` when waking is turned off.
+    pub fn update(
+        &mut self,
+        wake_minute_of_day: Option<u32>,
+        jobs_waiting: bool,
+        compressing: bool,
+    ) {
+        let wake_minute = wake_minute_of_day.filter(|minute| *minute < MINUTES_PER_DAY);
+        let now_ms = crate::utils::unix_now_ms();
+        if self.armed_for_ms.is_some_and(|due| due <= now_ms) {
+            // The wake time came; the timer is spent.
+            self.armed_for_ms = None;
+            self.armed_minute = None;
+            self.hold_until_ms = Some(now_ms.saturating_add(POST_WAKE_HOLD_MS));
+        }
+        if !jobs_waiting || self.hold_until_ms.is_some_and(|until| until <= now_ms) {
+            self.hold_until_ms = None;
+        }
+        self.set_keep_awake(wake_minute.is_some() && (compressing || self.hold_until_ms.is_some()));
+
+        let Some(minute) = wake_minute.filter(|_| jobs_waiting) else {
+            self.disarm();
+            return;
+        };
+        if self.armed_minute == Some(minute) && self.armed_for_ms.is_some_and(|due| due > now_ms) {
+            return;
+        }
+        let due_ms = next_wake_ms(now_ms, platform::utc_offset_minutes(), minute);
+        self.arm(minute, due_ms);
+    }
+
+    fn arm(&mut self, minute: u32, due_ms: u64) {
+        if self.timer.is_none() {
+            match platform::WakeTimer::new() {
+                Ok(timer) => self.timer = Some(timer),
+                Err(e) => {
+                    log::warn!("[automation][wake] wake timer unavailable: {e}");
+                    return;
+                }
+            }
+        }
+        let Some(timer) = &self.timer else {
+            return;
+        };
+        match timer.set(due_ms) {
+            Ok(true) => {}
+            Ok(false) => {
+                if !self.warned_no_resume {
+                    log::warn!(
+                        "[automation][wake] this machine cannot wake for timers; queued jobs run at the wake time only if it is awake"
+                    );
+                    self.warned_no_resume = true;
+                }
+            }
+            Err(e) => {
+                log::warn!("[automation][wake] failed to arm wake timer: {e}");
+                return;
+            }
+        }
+        log::info!("[automation][wake] armed for {due_ms} (unix ms)");
+        self.armed_for_ms = Some(due_ms);
+        self.armed_minute = Some(minute);
+    }
+
+    fn disarm(&mut self) {
+        self.armed_minute = None;
+        if self.armed_for_ms.take().is_some() {
+            if let Some(timer) = &self.timer {
+                timer.cancel();
+            }
+            log::info!("[automation][wake] disarmed");
+        }
+    }
+
+    fn set_keep_awake(&mut self, keep_awake: bool) {
+        if keep_awake != self.keeping_awake {
+            platform::keep_system_awake(keep_awake);
+            self.keeping_awake = keep_awake;
+        }
+    }
+}
+
+impl Default for MaintenanceWake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MaintenanceWake {
+    fn drop(&mut self) {
+        // Closing the timer handle cancels the timer.
+        self.set_keep_awake(false);
+    }
+}
+
+/// The next time, in UTC Unix milliseconds, that local time reads
+/// `minute_of_day`, given the local offset from UTC.
+fn next_wake_ms(now_ms: u64, utc_offset_minutes: i32, minute_of_day: u32) -> u64 {
+    let offset_ms = i64::from(utc_offset_minutes) * 60_000;
+    let local_now = now_ms as i64 + offset_ms;
+    let mut local_due = local_now.div_euclid(DAY_MS) * DAY_MS + i64::from(minute_of_day) * 60_000;
+    if local_due <= local_now {
+        local_due += DAY_MS;
+    }
+    (local_due - offset_ms).max(0) as u64
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{
+        CloseHandle, GetLastError, SetLastError, ERROR_NOT_SUPPORTED, HANDLE, SYSTEMTIME,
+        WIN32_ERROR,
+    };
+    use windows::Win32::System::Power::{
+        SetThreadExecutionState, ES_CONTINUOUS, ES_SYSTEM_REQUIRED,
+    };
+    use windows::Win32::System::SystemInformation::{GetLocalTime, GetSystemTime};
+    use windows::Win32::System::Threading::{
+        CancelWaitableTimer, CreateWaitableTimerW, SetWaitableTimer,
+    };
+
+    /// 100 ns intervals between 1601-01-01 and the Unix epoch.
+    const UNIX_EPOCH_AS_FILETIME: i64 = 116_444_736_000_000_000;
+
+    pub(super) struct WakeTimer {
+        handle: HANDLE,
+    }
+
+    // SAFETY: the handle is only used through kernel calls, which accept it
+    // from any thread, and is closed once on drop.
+    unsafe impl Send for WakeTimer {}
+
+    impl WakeTimer {
+        pub(super) fn new() -> io::Result<Self> {
+            // SAFETY: an unnamed timer with default security.
+            let handle = unsafe { CreateWaitableTimerW(None, true, PCWSTR::null()) }
+                .map_err(crate::utils::windows_error_to_io)?;
+            Ok(Self { handle })
+        }
+
+        /// Arm for `due_ms`, replacing any earlier due time. `Ok(false)`
+        /// means it is armed but cannot wake the machine.
+        pub(super) fn set(&self, due_ms: u64) -> io::Result<bool> {
+            let due = (due_ms as i64)
+                .saturating_mul(10_000)
+                .saturating_add(UNIX_EPOCH_AS_FILETIME);
+            // The only sign that resume is unsupported is the last error
+            // left by a successful call, so clear any stale one first.
+            // SAFETY: sets the calling thread's last-error value.
+            unsafe { SetLastError(WIN32_ERROR(0)) };
+            // SAFETY: `due` outlives the call; no completion routine.
+            unsafe { SetWaitableTimer(self.handle, &due, 0, None, None, true) }
+                .map_err(crate::utils::windows_error_to_io)?;
+            // SAFETY: reads the calling thread's last-error value.
+            Ok(unsafe { GetLastError() } != ERROR_NOT_SUPPORTED)
+        }
+
+        pub(super) fn cancel(&self) {
+            // SAFETY: the handle is open until drop.
+            let _ = unsafe { CancelWaitableTimer(self.handle) };
+        }
+    }
+
+    impl Drop for WakeTimer {
+        fn drop(&mut self) {
+            // SAFETY: the handle came from CreateWaitableTimerW and is closed
+            // once.
+            let _ = unsafe { CloseHandle(self.handle) };
+        }
+    }
+
+    /// Hold off idle sleep from this thread, or release the hold.
+    pub(super) fn keep_system_awake(keep_awake: bool) {
+        let flags = if keep_awake {
+            ES_CONTINUOUS | ES_SYSTEM_REQUIRED
+        } else {
+            ES_CONTINUOUS
+        };
+        // SAFETY: only changes this thread's execution state.
+        unsafe { SetThreadExecutionState(flags) };
+    }
+
+    /// Local time minus UTC, from the current clock readings.
+    pub(super) fn utc_offset_minutes() -> i32 {
+        // SAFETY: both calls only fill in a SYSTEMTIME.
+        let (local, utc) = unsafe { (GetLocalTime(), GetSystemTime()) };
+        let minutes = |t: &SYSTEMTIME| i32::from(t.wHour) * 60 + i32::from(t.wMinute);
+        let date = |t: &SYSTEMTIME| (t.wYear, t.wMonth, t.wDay);
+        let day_shift = match date(&local).cmp(&date(&utc)) {
+            std::cmp::Ordering::Greater => 24 * 60,
+            std::cmp::Ordering::Less => -24 * 60,
+            std::cmp::Ordering::Equal => 0,
+        };
+        minutes(&local) - minutes(&utc) + day_shift
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use std::io;
+
+    pub(super) struct WakeTimer;
+
+    impl WakeTimer {
+        pub(super) fn new() -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "wake timers require Windows",
+            ))
+        }
+
+        pub(super) fn set(&self, _due_ms: u64) -> io::Result<bool> {
+            Ok(false)
+        }
+
+        pub(super) fn cancel(&self) {}
+    }
+
+    pub(super) fn keep_system_awake(_keep_awake: bool) {}
+
+    pub(super) fn utc_offset_minutes() -> i32 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 60 * 60 * 1_000;
+
+    #[test]
+    fn next_wake_is_the_coming_local_time_of_day() {
+        // 2024-01-01 22:00 UTC.
+        let now = 1_704_146_400_000;
+        let three_am = 3 * 60;
+
+        // UTC: tomorrow at 03:00.
+        assert_eq!(next_wake_ms(now, 0, three_am), now + 5 * HOUR_MS);
+        // UTC+2 reads 00:00, so 03:00 is three hours out.
+        assert_eq!(next_wake_ms(now, 120, three_am), now + 3 * HOUR_MS);
+        // UTC-5 reads 17:00, so 03:00 is ten hours out.
+        assert_eq!(next_wake_ms(now, -300, three_am), now + 10 * HOUR_MS);
+        // A time that has just passed rolls over to the next day.
+        assert_eq!(next_wake_ms(now, 0, 22 * 60), now + 24 * HOUR_MS);
+    }
+}
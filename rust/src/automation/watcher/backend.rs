@@ -0,0 +1,139 @@
+//! Change-detection backends that feed raw filesystem events to the
+//! watcher worker.
+//!
+//! Every backend publishes `notify::Event` values into the same channel so
+//! noise filtering, game-folder resolution, and coalescing stay shared.
+
+use std::path::{Path, PathBuf};
+
+use crossbeam_channel::Sender;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Channel carrying raw events from a backend to the watcher worker.
+pub(crate) type RawEventSender = Sender<notify::Result<notify::Event>>;
+
+/// Which change-detection mechanism the watcher should prefer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WatcherBackendKind {
+    /// Recursive OS notifications via the `notify` crate.
+    #[default]
+    Notify,
+    /// Poll the NTFS USN change journal. Roots on volumes without an
+    /// accessible journal fall back to `notify`.
+    UsnJournal,
+}
+
+/// A source of raw filesystem change events for one or more watch roots.
+///
+/// Backends stop producing events when dropped.
+pub trait WatcherBackend: Send {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Start reporting changes under `path`.
+    fn watch(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Backend wrapping a recursive `notify` watcher.
+pub(crate) struct NotifyBackend {
+    watcher: RecommendedWatcher,
+}
+
+impl NotifyBackend {
+    pub fn new(raw_tx: RawEventSender) -> notify::Result<Self> {
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let _ = raw_tx.try_send(event);
+        })?;
+        Ok(Self { watcher })
+    }
+}
+
+impl WatcherBackend for NotifyBackend {
+    fn name(&self) -> &'static str {
+        "notify"
+    }
+
+    fn watch(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.watcher.watch(path, RecursiveMode::Recursive)?;
+        Ok(())
+    }
+}
+
+/// Attach every existing watch path to a backend, preferring `kind` and
+/// falling back to `notify` for paths the preferred backend cannot serve.
+///
+/// Returns only the backends that ended up watching at least one path.
+pub(crate) fn start_backends(
+    kind: WatcherBackendKind,
+    watch_paths: &[PathBuf],
+    raw_tx: RawEventSender,
+) -> Result<Vec<Box<dyn WatcherBackend>>, Box<dyn std::error::Error>> {
+    let mut preferred = preferred_backend(kind, &raw_tx);
+    let mut preferred_used = false;
+    let mut fallback = NotifyBackend::new(raw_tx)?;
+    let mut fallback_used = false;
+
+    for path in watch_paths {
+        if !path.exists() {
+            log::warn!("Watch path does not exist: {}", path.display());
+            continue;
+        }
+
+        if let Some(backend) = preferred.as_mut() {
+            match backend.watch(path) {
+                Ok(()) => {
+                    log::info!("Watching ({}): {}", backend.name(), path.display());
+                    preferred_used = true;
+                    continue;
+                }
+                Err(e) => log::info!(
+                    "{} backend unavailable for {}: {e}; falling back to notify",
+                    backend.name(),
+                    path.display()
+                ),
+            }
+        }
+
+        match fallback.watch(path) {
+            Ok(()) => {
+                log::info!("Watching ({}): {}", fallback.name(), path.display());
+                fallback_used = true;
+            }
+            Err(e) => log::warn!("Failed to watch {}: {e}", path.display()),
+        }
+    }
+
+    let mut backends: Vec<Box<dyn WatcherBackend>> = Vec::with_capacity(2);
+    if let Some(backend) = preferred.filter(|_| preferred_used) {
+        backends.push(backend);
+    }
+    if fallback_used {
+        backends.push(Box::new(fallback));
+    }
+    Ok(backends)
+}
+
+#[cfg(windows)]
+fn preferred_backend(
+    kind: WatcherBackendKind,
+    raw_tx: &RawEventSender,
+) -> Option<Box<dyn WatcherBackend>> {
+    match kind {
+        WatcherBackendKind::Notify => None,
+        WatcherBackendKind::UsnJournal => Some(Box::new(super::usn::UsnJournalBackend::new(
+            raw_tx.clone(),
+            super::usn::DEFAULT_POLL_INTERVAL,
+        ))),
+    }
+}
+
+#[cfg(not(windows))]
+fn preferred_backend(
+    kind: WatcherBackendKind,
+    _raw_tx: &RawEventSender,
+) -> Option<Box<dyn WatcherBackend>> {
+    if kind == WatcherBackendKind::UsnJournal {
+        log::info!("USN journal backend is only available on Windows; using notify");
+    }
+    None
+}
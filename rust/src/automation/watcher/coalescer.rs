@@ -15,12 +15,52 @@ pub(crate) enum WatchEventKind {
     Modified,
 }
 
+/// Bursts shorter than this are small patches and settle after the base
+/// cooldown.
+const SMALL_BURST: Duration = Duration::from_secs(30);
+/// A long burst adds this fraction of its length to the cooldown: big
+/// installs pause longer between chunks than small patches do.
+const BURST_COOLDOWN_DIVISOR: u32 = 10;
+/// Upper bound on the adaptive cooldown, unless the base is longer.
+const MAX_ADAPTIVE_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
 /// Internal pending event for coalescing.
 struct PendingEvent {
     path: PathBuf,
     kind: WatchEventKind,
     game_name: Option<String>,
+    first_seen: Instant,
     last_seen: Instant,
+    event_count: u32,
+}
+
+impl PendingEvent {
+    fn burst(&self) -> Duration {
+        self.last_seen.duration_since(self.first_seen)
+    }
+}
+
+/// Settle timer of one pending game, for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSettle {
+    pub path: PathBuf,
+    pub game_name: Option<String>,
+    /// Raw events coalesced so far.
+    pub event_count: u32,
+    /// Time from the first to the latest event of the burst.
+    pub burst: Duration,
+    /// Quiet period chosen for this burst.
+    pub cooldown: Duration,
+    /// Quiet time left before the event is emitted.
+    pub remaining: Duration,
+}
+
+/// Cooldown for a burst that has lasted `burst` so far.
+pub(crate) fn adaptive_cooldown(base: Duration, burst: Duration) -> Duration {
+    if burst < SMALL_BURST {
+        return base;
+    }
+    (base + burst / BURST_COOLDOWN_DIVISOR).min(MAX_ADAPTIVE_COOLDOWN.max(base))
 }
 
 /// Coalesces rapid filesystem events into single events per path.
@@ -45,30 +85,45 @@ impl EventCoalescer {
             Entry::Occupied(mut e) => {
                 let pending = e.get_mut();
                 pending.last_seen = Instant::now();
+                pending.event_count = pending.event_count.saturating_add(1);
                 pending.kind = kind;
                 if game_name.is_some() {
                     pending.game_name = game_name;
                 }
             }
             Entry::Vacant(e) => {
+                let now = Instant::now();
                 e.insert(PendingEvent {
                     path,
                     kind,
                     game_name,
-                    last_seen: Instant::now(),
+                    first_seen: now,
+                    last_seen: now,
+                    event_count: 1,
                 });
             }
         }
     }
 
     /// Drain events whose cooldown has expired, returning settled events.
+    ///
+    /// Each burst's cooldown adapts to how long it has run; see
+    /// [`adaptive_cooldown`].
     pub fn drain_settled(&mut self) -> Vec<WatchEvent> {
         let now = Instant::now();
-        let cooldown = self.cooldown;
+        let base = self.cooldown;
         let mut settled = Vec::new();
 
         self.pending.retain(|_path, pending| {
+            let cooldown = adaptive_cooldown(base, pending.burst());
             if now.duration_since(pending.last_seen) >= cooldown {
+                log::debug!(
+                    "Settled {} after {} events over {}s (cooldown {}s)",
+                    pending.path.display(),
+                    pending.event_count,
+                    pending.burst().as_secs(),
+                    cooldown.as_secs()
+                );
                 // Take ownership of fields without cloning the whole PendingEvent
                 settled.push(match pending.kind {
                     WatchEventKind::Installed => WatchEvent::GameInstalled {
@@ -93,6 +148,28 @@ impl EventCoalescer {
         settled
     }
 
+    /// Settle timers of the events still pending.
+    pub fn pending_settles(&self) -> Vec<PendingSettle> {
+        let now = Instant::now();
+        let mut settles: Vec<PendingSettle> = self
+            .pending
+            .values()
+            .map(|pending| {
+                let cooldown = adaptive_cooldown(self.cooldown, pending.burst());
+                PendingSettle {
+                    path: pending.path.clone(),
+                    game_name: pending.game_name.clone(),
+                    event_count: pending.event_count,
+                    burst: pending.burst(),
+                    cooldown,
+                    remaining: cooldown.saturating_sub(now.duration_since(pending.last_seen)),
+                }
+            })
+            .collect();
+        settles.sort_by_key(|settle| settle.remaining);
+        settles
+    }
+
     #[cfg_attr(not(test), allow(dead_code))]
     pub fn len(&self) -> usize {
         self.pending.len()
@@ -137,6 +214,64 @@ pub(crate) fn is_noise_path(path: &std::path::Path) -> bool {
     false
 }
 
+/// User-configurable noise filtering, applied on top of the built-in lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoiseRules {
+    /// Directory names (case-insensitive) whose contents never count as a
+    /// game change, e.g. a mod manager's staging folder.
+    pub ignored_dirs: Vec<String>,
+    /// File extensions without the dot, e.g. `dmp`.
+    pub ignored_extensions: Vec<String>,
+    /// File name patterns with `*` and `?` wildcards for temporary files a
+    /// launcher writes while patching, e.g. `*.egstmp` or `~patch*`.
+    pub temp_file_patterns: Vec<String>,
+    /// Events more than this many levels below the game folder are
+    /// dropped; `None` has no limit.
+    pub max_event_depth: Option<u32>,
+}
+
+impl NoiseRules {
+    /// Whether `relative_path`, relative to its game folder, matches a
+    /// configured directory, extension or temp-file pattern.
+    pub(crate) fn is_noise(&self, relative_path: &Path) -> bool {
+        let in_ignored_dir = !self.ignored_dirs.is_empty()
+            && relative_path.components().any(|component| {
+                component.as_os_str().to_str().is_some_and(|segment| {
+                    self.ignored_dirs
+                        .iter()
+                        .any(|dir| segment.eq_ignore_ascii_case(dir))
+                })
+            });
+        if in_ignored_dir {
+            return true;
+        }
+
+        let Some(name) = relative_path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        let ignored_extension = relative_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| {
+                self.ignored_extensions
+                    .iter()
+                    .any(|ignored| ext.eq_ignore_ascii_case(ignored.trim_start_matches('.')))
+            });
+        ignored_extension
+            || self
+                .temp_file_patterns
+                .iter()
+                .any(|pattern| crate::utils::wildcard_match(pattern, name))
+    }
+
+    /// Whether `relative_path` lies deeper below its game folder than
+    /// [`Self::max_event_depth`] allows.
+    pub(crate) fn is_too_deep(&self, relative_path: &Path) -> bool {
+        self.max_event_depth
+            .is_some_and(|max| relative_path.components().count() > max as usize)
+    }
+}
+
 pub(crate) fn is_user_state_subpath(path: &std::path::Path) -> bool {
     if path.components().any(|component| {
         component
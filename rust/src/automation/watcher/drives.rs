@@ -0,0 +1,62 @@
+//! Watch-root reachability polling.
+//!
+//! Watches on an unplugged external drive die silently and are not revived
+//! when the drive comes back. Polling whether each root still exists is
+//! cheap and catches both directions without a device-notification window.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often watch roots are checked for reachability.
+pub(crate) const DRIVE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Roots whose reachability changed since the previous poll.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct DriveChanges {
+    pub offline: Vec<PathBuf>,
+    pub online: Vec<PathBuf>,
+}
+
+impl DriveChanges {
+    pub fn is_empty(&self) -> bool {
+        self.offline.is_empty() && self.online.is_empty()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct DriveMonitor {
+    offline: HashSet<PathBuf>,
+    last_poll: Option<Instant>,
+}
+
+impl DriveMonitor {
+    /// Check `roots` at most every [`DRIVE_POLL_INTERVAL`].
+    pub fn poll(&mut self, roots: &[PathBuf]) -> Option<DriveChanges> {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < DRIVE_POLL_INTERVAL)
+        {
+            return None;
+        }
+        self.last_poll = Some(Instant::now());
+        let changes = self.update(roots, Path::exists);
+        (!changes.is_empty()).then_some(changes)
+    }
+
+    /// Record the reachability of `roots` and report transitions. Roots no
+    /// longer configured are forgotten.
+    pub fn update(&mut self, roots: &[PathBuf], is_online: impl Fn(&Path) -> bool) -> DriveChanges {
+        self.offline.retain(|root| roots.contains(root));
+        let mut changes = DriveChanges::default();
+        for root in roots {
+            let online = is_online(root);
+            if !online && self.offline.insert(root.clone()) {
+                changes.offline.push(root.clone());
+            } else if online && self.offline.remove(root) {
+                changes.online.push(root.clone());
+            }
+        }
+        changes
+    }
+}
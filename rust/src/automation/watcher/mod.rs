@@ -1,25 +1,34 @@
 //! File system watcher for game directory changes.
 //!
-//! Uses `notify` crate for OS-level file system notifications.
-//! Events are coalesced with a configurable cooldown to batch rapid
-//! filesystem writes (e.g., during game updates) into single events.
+//! Raw changes come from a pluggable [`WatcherBackend`]: recursive `notify`
+//! watches by default, or the NTFS USN change journal. Events are
+//! coalesced with a configurable cooldown to batch rapid filesystem writes
+//! (e.g., during game updates) into single events.
 
+pub(crate) mod backend;
 pub(crate) mod coalescer;
+mod drives;
+#[cfg(windows)]
+mod usn;
 
 #[cfg(test)]
 mod tests;
 
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 use std::time::Duration;
 
 use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 
+pub use backend::{WatcherBackend, WatcherBackendKind};
 use coalescer::{game_name_from_path, is_noise_path, is_user_state_subpath, resolve_game_folder};
 use coalescer::{EventCoalescer, WatchEventKind};
+pub use coalescer::{NoiseRules, PendingSettle};
+use drives::DriveMonitor;
+
+use crate::safety::ignore_marker::is_ignored_below;
 
 const RECENT_SELF_COMPRESSION_SUPPRESSION_MS: u64 = 30_000;
 
@@ -41,6 +50,11 @@ pub enum WatchEvent {
         path: PathBuf,
         game_name: Option<String>,
     },
+    /// A watch root became unreachable, e.g. its external drive was
+    /// unplugged.
+    DriveOffline { path: PathBuf },
+    /// A watch root is reachable again and its watches were re-established.
+    DriveOnline { path: PathBuf },
 }
 
 impl WatchEvent {
@@ -48,7 +62,9 @@ impl WatchEvent {
         match self {
             WatchEvent::GameInstalled { path, .. }
             | WatchEvent::GameUninstalled { path, .. }
-            | WatchEvent::GameModified { path, .. } => path,
+            | WatchEvent::GameModified { path, .. }
+            | WatchEvent::DriveOffline { path }
+            | WatchEvent::DriveOnline { path } => path,
         }
     }
 
@@ -57,6 +73,7 @@ impl WatchEvent {
             WatchEvent::GameInstalled { game_name, .. }
             | WatchEvent::GameUninstalled { game_name, .. }
             | WatchEvent::GameModified { game_name, .. } => game_name.as_deref(),
+            WatchEvent::DriveOffline { .. } | WatchEvent::DriveOnline { .. } => None,
         }
     }
 }
@@ -68,6 +85,10 @@ pub struct WatcherConfig {
     pub watch_paths: Vec<PathBuf>,
     /// Cooldown after detecting a change before emitting the coalesced event.
     pub cooldown: Duration,
+    /// Preferred change-detection backend.
+    pub backend: WatcherBackendKind,
+    /// Extra noise filtering on top of the built-in rules.
+    pub noise_rules: NoiseRules,
 }
 
 impl Default for WatcherConfig {
@@ -75,33 +96,87 @@ impl Default for WatcherConfig {
         Self {
             watch_paths: Vec::new(),
             cooldown: Duration::from_secs(300), // 5 minutes
+            backend: WatcherBackendKind::default(),
+            noise_rules: NoiseRules::default(),
+        }
+    }
+}
+
+/// Raw events dropped before coalescing, by reason, since the watcher was
+/// created. Lets users see why a change did not trigger automation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilteredEventCounts {
+    /// Built-in or configured noise: temp files, ignored extensions and
+    /// directories.
+    pub noise: u64,
+    /// Saves, configs, logs and caches the game writes itself.
+    pub user_state: u64,
+    /// Below a folder carrying an ignore marker.
+    pub ignore_marker: u64,
+    /// Deeper than [`NoiseRules::max_event_depth`].
+    pub too_deep: u64,
+    /// Echoes of the app's own compression.
+    pub self_compression: u64,
+}
+
+#[derive(Debug, Default)]
+struct FilterCounters {
+    noise: AtomicU64,
+    user_state: AtomicU64,
+    ignore_marker: AtomicU64,
+    too_deep: AtomicU64,
+    self_compression: AtomicU64,
+}
+
+impl FilterCounters {
+    fn bump(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> FilteredEventCounts {
+        FilteredEventCounts {
+            noise: self.noise.load(Ordering::Relaxed),
+            user_state: self.user_state.load(Ordering::Relaxed),
+            ignore_marker: self.ignore_marker.load(Ordering::Relaxed),
+            too_deep: self.too_deep.load(Ordering::Relaxed),
+            self_compression: self.self_compression.load(Ordering::Relaxed),
         }
     }
 }
 
 /// Watches game directories for installation/removal events.
 ///
-/// Uses the `notify` crate for cross-platform file system notifications.
+/// Raw changes come from the configured backend (see [`WatcherBackendKind`]).
 /// Coalesces events and applies a cooldown period to allow installations
 /// to complete before triggering compression.
 pub struct GameWatcher {
     config: WatcherConfig,
-    watcher: Option<RecommendedWatcher>,
+    backends: Vec<Box<dyn WatcherBackend>>,
     stop_flag: Arc<AtomicBool>,
     worker_handle: Option<JoinHandle<()>>,
     event_tx: Option<Sender<WatchEvent>>,
     event_rx: Option<Receiver<WatchEvent>>,
+    filter_counters: Arc<FilterCounters>,
+    /// Published by the worker after each pass over the coalescer.
+    pending_settles: Arc<Mutex<Vec<PendingSettle>>>,
+    /// Kept so backends can be re-attached without restarting the worker.
+    raw_tx: Option<backend::RawEventSender>,
+    drives: DriveMonitor,
 }
 
 impl GameWatcher {
     pub fn new(config: WatcherConfig) -> Self {
         Self {
             config,
-            watcher: None,
+            backends: Vec::new(),
             stop_flag: Arc::new(AtomicBool::new(false)),
             worker_handle: None,
             event_tx: None,
             event_rx: None,
+            filter_counters: Arc::new(FilterCounters::default()),
+            pending_settles: Arc::default(),
+            raw_tx: None,
+            drives: DriveMonitor::default(),
         }
     }
 
@@ -122,38 +197,40 @@ impl GameWatcher {
         self.stop_flag.store(false, Ordering::Relaxed);
 
         let (notify_tx, notify_rx) = bounded::<notify::Result<notify::Event>>(256);
-
-        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
-            let _ = notify_tx.try_send(event);
-        })?;
+        let backends = backend::start_backends(
+            self.config.backend,
+            &self.config.watch_paths,
+            notify_tx.clone(),
+        )?;
+        self.raw_tx = Some(notify_tx);
 
         let (event_tx, event_rx) = bounded::<WatchEvent>(256);
 
-        self.watcher = Some(watcher);
+        self.backends = backends;
         self.event_tx = Some(event_tx.clone());
         self.event_rx = Some(event_rx);
 
-        if let Some(ref mut w) = self.watcher {
-            for path in &self.config.watch_paths {
-                if path.exists() {
-                    match w.watch(path, RecursiveMode::Recursive) {
-                        Ok(()) => log::info!("Watching: {}", path.display()),
-                        Err(e) => log::warn!("Failed to watch {}: {e}", path.display()),
-                    }
-                } else {
-                    log::warn!("Watch path does not exist: {}", path.display());
-                }
-            }
-        }
-
         let stop_flag = self.stop_flag.clone();
         let cooldown = self.config.cooldown;
         let watch_paths = self.config.watch_paths.clone();
+        let filter = EventFilter {
+            rules: self.config.noise_rules.clone(),
+            counters: self.filter_counters.clone(),
+        };
+        let pending_settles = self.pending_settles.clone();
 
         let handle = std::thread::Builder::new()
             .name("compact-games-watcher".to_owned())
             .spawn(move || {
-                watcher_worker(notify_rx, event_tx, stop_flag, cooldown, watch_paths);
+                watcher_worker(
+                    notify_rx,
+                    event_tx,
+                    stop_flag,
+                    cooldown,
+                    watch_paths,
+                    filter,
+                    pending_settles,
+                );
             })?;
 
         self.worker_handle = Some(handle);
@@ -163,7 +240,8 @@ impl GameWatcher {
 
     pub fn stop(&mut self) {
         self.stop_flag.store(true, Ordering::Relaxed);
-        self.watcher.take();
+        self.backends.clear();
+        self.raw_tx.take();
 
         if let Some(handle) = self.worker_handle.take() {
             let _ = handle.join();
@@ -171,6 +249,10 @@ impl GameWatcher {
 
         self.event_tx.take();
         self.event_rx.take();
+        self.pending_settles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
         log::info!("GameWatcher stopped");
     }
 
@@ -182,6 +264,74 @@ impl GameWatcher {
         self.config.watch_paths.len()
     }
 
+    /// Events filtered out so far, kept across config changes.
+    pub fn filtered_event_counts(&self) -> FilteredEventCounts {
+        self.filter_counters.snapshot()
+    }
+
+    /// Coalesced changes still waiting out their cooldown, soonest first.
+    pub fn pending_settles(&self) -> Vec<PendingSettle> {
+        self.pending_settles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Check whether watch roots are still reachable. A root that went away
+    /// yields [`WatchEvent::DriveOffline`]; one that came back has its
+    /// watches re-established and yields [`WatchEvent::DriveOnline`].
+    ///
+    /// Call periodically from the thread that owns the watcher.
+    pub fn poll_drives(&mut self) {
+        if !self.is_running() {
+            return;
+        }
+        let Some(changes) = self.drives.poll(&self.config.watch_paths) else {
+            return;
+        };
+        for root in &changes.offline {
+            log::warn!("Watch root went offline: {}", root.display());
+        }
+        if !changes.online.is_empty() {
+            self.reattach_backends();
+        }
+
+        let events = changes
+            .offline
+            .into_iter()
+            .map(|path| WatchEvent::DriveOffline { path })
+            .chain(
+                changes
+                    .online
+                    .into_iter()
+                    .map(|path| WatchEvent::DriveOnline { path }),
+            );
+        let Some(event_tx) = self.event_tx.as_ref() else {
+            return;
+        };
+        for event in events {
+            if event_tx.try_send(event).is_err() {
+                log::warn!("Watcher output channel full, dropping drive event");
+            }
+        }
+    }
+
+    /// Drop and recreate every backend so roots on a returning drive are
+    /// watched again. The worker and its pending settles are kept.
+    fn reattach_backends(&mut self) {
+        let Some(raw_tx) = self.raw_tx.clone() else {
+            return;
+        };
+        self.backends.clear();
+        match backend::start_backends(self.config.backend, &self.config.watch_paths, raw_tx) {
+            Ok(backends) => {
+                log::info!("Re-established watches after a drive came back");
+                self.backends = backends;
+            }
+            Err(e) => log::error!("Failed to re-establish watches: {e}"),
+        }
+    }
+
     /// Update the watch paths, starting or restarting the watcher as needed.
     pub fn update_config(&mut self, config: WatcherConfig) {
         let was_running = self.is_running();
@@ -214,12 +364,21 @@ impl Drop for GameWatcher {
 
 // ── Worker thread ────────────────────────────────────────────────────
 
+/// Noise rules plus the counters that record what they dropped.
+#[derive(Default)]
+struct EventFilter {
+    rules: NoiseRules,
+    counters: Arc<FilterCounters>,
+}
+
 fn watcher_worker(
     notify_rx: Receiver<notify::Result<notify::Event>>,
     event_tx: Sender<WatchEvent>,
     stop_flag: Arc<AtomicBool>,
     cooldown: Duration,
     watch_paths: Vec<PathBuf>,
+    filter: EventFilter,
+    pending_settles: Arc<Mutex<Vec<PendingSettle>>>,
 ) {
     let mut coalescer = EventCoalescer::new(cooldown);
 
@@ -230,7 +389,7 @@ fn watcher_worker(
 
         match notify_rx.recv_timeout(Duration::from_millis(500)) {
             Ok(Ok(event)) => {
-                process_notify_event(&event, &watch_paths, &mut coalescer);
+                process_notify_event(&event, &watch_paths, &filter, &mut coalescer);
             }
             Ok(Err(e)) => {
                 log::warn!("Watcher error: {e}");
@@ -245,7 +404,7 @@ fn watcher_worker(
         loop {
             match notify_rx.try_recv() {
                 Ok(Ok(event)) => {
-                    process_notify_event(&event, &watch_paths, &mut coalescer);
+                    process_notify_event(&event, &watch_paths, &filter, &mut coalescer);
                 }
                 Ok(Err(e)) => {
                     log::warn!("Watcher error: {e}");
@@ -269,16 +428,20 @@ fn watcher_worker(
                 }
             }
         }
+        *pending_settles.lock().unwrap_or_else(|e| e.into_inner()) = coalescer.pending_settles();
     }
 }
 
 fn process_notify_event(
     event: &notify::Event,
     watch_paths: &[PathBuf],
+    filter: &EventFilter,
     coalescer: &mut EventCoalescer,
 ) {
+    let counters = &filter.counters;
     for path in &event.paths {
         if is_noise_path(path) {
+            FilterCounters::bump(&counters.noise);
             continue;
         }
 
@@ -289,7 +452,22 @@ fn process_notify_event(
         let game_folder = resolved.path;
         let relative_event_path = path.strip_prefix(&game_folder).unwrap_or(path.as_path());
 
+        if filter.rules.is_too_deep(relative_event_path) {
+            FilterCounters::bump(&counters.too_deep);
+            continue;
+        }
+        if filter.rules.is_noise(relative_event_path) {
+            FilterCounters::bump(&counters.noise);
+            continue;
+        }
         if is_user_state_subpath(relative_event_path) {
+            FilterCounters::bump(&counters.user_state);
+            continue;
+        }
+
+        // Changes inside an opted-out subtree never need recompression.
+        if is_ignored_below(&game_folder, path) {
+            FilterCounters::bump(&counters.ignore_marker);
             continue;
         }
 
@@ -322,6 +500,7 @@ fn process_notify_event(
         if kind == WatchEventKind::Modified
             && should_suppress_recent_self_compression_event(&game_folder, path)
         {
+            FilterCounters::bump(&counters.self_compression);
             continue;
         }
 
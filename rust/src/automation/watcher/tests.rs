@@ -31,6 +31,8 @@ fn record_test_compression(game_dir: &Path, timestamp_ms: u64) {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 }
 
@@ -97,6 +99,47 @@ fn coalescer_different_paths_produce_separate_events() {
     assert_eq!(settled.len(), 2);
 }
 
+#[test]
+fn adaptive_cooldown_grows_with_burst_length() {
+    let base = Duration::from_secs(60);
+
+    assert_eq!(adaptive_cooldown(base, Duration::from_secs(5)), base);
+    assert_eq!(
+        adaptive_cooldown(base, Duration::from_secs(20 * 60)),
+        Duration::from_secs(3 * 60)
+    );
+    assert_eq!(
+        adaptive_cooldown(base, Duration::from_secs(5 * 60 * 60)),
+        Duration::from_secs(10 * 60)
+    );
+    // A base above the cap is never shortened.
+    let long_base = Duration::from_secs(15 * 60);
+    assert_eq!(
+        adaptive_cooldown(long_base, Duration::from_secs(60 * 60)),
+        long_base
+    );
+}
+
+#[test]
+fn pending_settles_report_burst_and_remaining_time() {
+    let mut coalescer = EventCoalescer::new(Duration::from_secs(60));
+    for _ in 0..3 {
+        coalescer.ingest(
+            PathBuf::from(r"C:\Games\Patching"),
+            WatchEventKind::Modified,
+            Some("Patching".to_string()),
+        );
+    }
+
+    let settles = coalescer.pending_settles();
+    assert_eq!(settles.len(), 1);
+    assert_eq!(settles[0].path, PathBuf::from(r"C:\Games\Patching"));
+    assert_eq!(settles[0].event_count, 3);
+    assert_eq!(settles[0].cooldown, Duration::from_secs(60));
+    assert!(settles[0].remaining <= settles[0].cooldown);
+    assert!(settles[0].remaining > Duration::from_secs(50));
+}
+
 #[test]
 fn coalescer_reset_timer_on_new_event() {
     let mut coalescer = EventCoalescer::new(Duration::from_millis(100));
@@ -203,6 +246,8 @@ fn resolve_game_folder_returns_known_watch_root_for_child_event() {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 
     let watch_paths = vec![game_dir.clone()];
@@ -233,6 +278,8 @@ fn create_folder_under_known_watch_root_emits_modified_for_game_root() {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 
     let new_folder = game_dir.join("PatchFolder");
@@ -243,7 +290,12 @@ fn create_folder_under_known_watch_root_emits_modified_for_game_root() {
     };
     let mut coalescer = EventCoalescer::new(Duration::ZERO);
 
-    process_notify_event(&event, std::slice::from_ref(&game_dir), &mut coalescer);
+    process_notify_event(
+        &event,
+        std::slice::from_ref(&game_dir),
+        &EventFilter::default(),
+        &mut coalescer,
+    );
     let settled = coalescer.drain_settled();
 
     assert_eq!(
@@ -277,6 +329,8 @@ fn save_file_under_known_watch_root_is_ignored() {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 
     let event = notify::Event {
@@ -288,7 +342,12 @@ fn save_file_under_known_watch_root_is_ignored() {
     };
     let mut coalescer = EventCoalescer::new(Duration::ZERO);
 
-    process_notify_event(&event, std::slice::from_ref(&game_dir), &mut coalescer);
+    process_notify_event(
+        &event,
+        std::slice::from_ref(&game_dir),
+        &EventFilter::default(),
+        &mut coalescer,
+    );
 
     assert!(coalescer.drain_settled().is_empty());
 }
@@ -311,7 +370,12 @@ fn recent_self_compression_echo_is_ignored() {
     };
     let mut coalescer = EventCoalescer::new(Duration::ZERO);
 
-    process_notify_event(&event, std::slice::from_ref(&game_dir), &mut coalescer);
+    process_notify_event(
+        &event,
+        std::slice::from_ref(&game_dir),
+        &EventFilter::default(),
+        &mut coalescer,
+    );
 
     assert!(coalescer.drain_settled().is_empty());
 }
@@ -334,7 +398,12 @@ fn recent_real_content_update_after_compression_is_queued() {
     };
     let mut coalescer = EventCoalescer::new(Duration::ZERO);
 
-    process_notify_event(&event, std::slice::from_ref(&game_dir), &mut coalescer);
+    process_notify_event(
+        &event,
+        std::slice::from_ref(&game_dir),
+        &EventFilter::default(),
+        &mut coalescer,
+    );
 
     assert_eq!(
         coalescer.drain_settled(),
@@ -358,7 +427,12 @@ fn ancestor_named_cache_does_not_filter_content_event() {
     };
     let mut coalescer = EventCoalescer::new(Duration::ZERO);
 
-    process_notify_event(&event, std::slice::from_ref(&watch_root), &mut coalescer);
+    process_notify_event(
+        &event,
+        std::slice::from_ref(&watch_root),
+        &EventFilter::default(),
+        &mut coalescer,
+    );
 
     assert_eq!(
         coalescer.drain_settled(),
@@ -396,6 +470,7 @@ fn update_config_starts_watcher_after_initial_empty_config() {
     watcher.update_config(WatcherConfig {
         watch_paths: vec![temp.path().to_path_buf()],
         cooldown: Duration::from_millis(10),
+        ..WatcherConfig::default()
     });
 
     assert!(watcher.is_running());
@@ -409,6 +484,7 @@ fn update_config_stops_watcher_when_paths_become_empty() {
     let mut watcher = GameWatcher::new(WatcherConfig {
         watch_paths: vec![temp.path().to_path_buf()],
         cooldown: Duration::from_millis(10),
+        ..WatcherConfig::default()
     });
 
     watcher.start().unwrap();
@@ -426,6 +502,7 @@ fn update_config_keeps_running_watcher_when_config_is_unchanged() {
     let config = WatcherConfig {
         watch_paths: vec![temp.path().to_path_buf()],
         cooldown: Duration::from_millis(10),
+        ..WatcherConfig::default()
     };
     let mut watcher = GameWatcher::new(config.clone());
 
@@ -506,3 +583,109 @@ mod property_tests {
         }
     }
 }
+
+#[test]
+fn noise_rules_match_dirs_extensions_and_temp_patterns() {
+    let rules = NoiseRules {
+        ignored_dirs: vec!["ModStaging".into()],
+        ignored_extensions: vec![".dmp".into()],
+        temp_file_patterns: vec!["*.egstmp".into(), "~patch?.bin".into()],
+        max_event_depth: None,
+    };
+
+    assert!(rules.is_noise(Path::new("modstaging/textures/rock.dds")));
+    assert!(rules.is_noise(Path::new("crash/Game.DMP")));
+    assert!(rules.is_noise(Path::new("Content/pak01.pak.EGSTMP")));
+    assert!(rules.is_noise(Path::new("~patch1.bin")));
+    assert!(!rules.is_noise(Path::new("~patch10.bin")));
+    assert!(!rules.is_noise(Path::new("Content/pak01.pak")));
+    assert!(!NoiseRules::default().is_noise(Path::new("Content/pak01.pak.egstmp")));
+}
+
+#[test]
+fn noise_rules_depth_limit_counts_components_below_game_root() {
+    let rules = NoiseRules {
+        max_event_depth: Some(2),
+        ..NoiseRules::default()
+    };
+
+    assert!(!rules.is_too_deep(Path::new("Content/pak01.pak")));
+    assert!(rules.is_too_deep(Path::new("Content/Paks/pak01.pak")));
+    assert!(!NoiseRules::default().is_too_deep(Path::new("a/b/c/d/e/f")));
+}
+
+#[test]
+fn configured_noise_is_dropped_and_counted() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let game_dir = temp.path().join("PatchingGame");
+    std::fs::create_dir_all(game_dir.join("Content").join("Paks")).unwrap();
+
+    let filter = EventFilter {
+        rules: NoiseRules {
+            temp_file_patterns: vec!["*.egstmp".into()],
+            max_event_depth: Some(2),
+            ..NoiseRules::default()
+        },
+        counters: Arc::default(),
+    };
+    let mut coalescer = EventCoalescer::new(Duration::ZERO);
+    for path in [
+        game_dir.join("pak01.pak.egstmp"),
+        game_dir.join("Content").join("Paks").join("pak01.pak"),
+        game_dir.join("Save").join("slot1.sav"),
+    ] {
+        let event = notify::Event {
+            kind: notify::EventKind::Create(notify::event::CreateKind::File),
+            paths: vec![path],
+            attrs: notify::event::EventAttributes::new(),
+        };
+        process_notify_event(
+            &event,
+            std::slice::from_ref(&game_dir),
+            &filter,
+            &mut coalescer,
+        );
+    }
+
+    assert!(coalescer.drain_settled().is_empty());
+    assert_eq!(
+        filter.counters.snapshot(),
+        FilteredEventCounts {
+            noise: 1,
+            user_state: 1,
+            too_deep: 1,
+            ..FilteredEventCounts::default()
+        }
+    );
+}
+
+#[test]
+fn drive_monitor_reports_each_transition_once() {
+    let mut monitor = drives::DriveMonitor::default();
+    let roots = vec![PathBuf::from(r"D:\Games"), PathBuf::from(r"E:\Games")];
+    let e_online = std::cell::Cell::new(true);
+    let is_online = |root: &Path| root.starts_with("D:") || e_online.get();
+
+    assert!(monitor.update(&roots, is_online).is_empty());
+
+    e_online.set(false);
+    let changes = monitor.update(&roots, is_online);
+    assert_eq!(changes.offline, vec![PathBuf::from(r"E:\Games")]);
+    assert!(changes.online.is_empty());
+    assert!(monitor.update(&roots, is_online).is_empty());
+
+    e_online.set(true);
+    let changes = monitor.update(&roots, is_online);
+    assert_eq!(changes.online, vec![PathBuf::from(r"E:\Games")]);
+    assert!(changes.offline.is_empty());
+}
+
+#[test]
+fn drive_monitor_forgets_removed_roots() {
+    let mut monitor = drives::DriveMonitor::default();
+    let roots = vec![PathBuf::from(r"E:\Games")];
+    monitor.update(&roots, |_| false);
+
+    assert!(monitor.update(&[], |_| true).is_empty());
+    assert!(monitor.update(&roots, |_| true).is_empty());
+}
@@ -0,0 +1,674 @@
+//! NTFS USN change journal backend.
+//!
+//! Instead of holding recursive directory handles over large libraries,
+//! one thread per volume polls the change journal and republishes records
+//! that fall under a watch root as `notify` events.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::os::windows::ffi::OsStringExt;
+use std::os::windows::io::{AsRawHandle, FromRawHandle};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::SendTimeoutError;
+use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Storage::FileSystem::{
+    FileIdType, GetFinalPathNameByHandleW, GetVolumeInformationW,
+    GetVolumeNameForVolumeMountPointW, GetVolumePathNameW, OpenFileById, FILE_ATTRIBUTE_DIRECTORY,
+    FILE_FLAG_BACKUP_SEMANTICS, FILE_ID_DESCRIPTOR, FILE_ID_DESCRIPTOR_0, FILE_NAME_NORMALIZED,
+    FILE_READ_ATTRIBUTES, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+};
+use windows::Win32::System::Ioctl::{
+    FSCTL_QUERY_USN_JOURNAL, FSCTL_READ_USN_JOURNAL, READ_USN_JOURNAL_DATA_V0, USN_JOURNAL_DATA_V0,
+    USN_REASON_DATA_EXTEND, USN_REASON_DATA_OVERWRITE, USN_REASON_DATA_TRUNCATION,
+    USN_REASON_FILE_CREATE, USN_REASON_FILE_DELETE, USN_REASON_RENAME_NEW_NAME,
+    USN_REASON_RENAME_OLD_NAME,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+use super::backend::{RawEventSender, WatcherBackend};
+
+/// How often each volume's journal is polled for new records.
+pub(crate) const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const READ_BUFFER_BYTES: usize = 64 * 1024;
+/// Upper bound on journal reads per poll so a busy volume cannot starve stop().
+const MAX_READS_PER_POLL: usize = 64;
+const MAX_CACHED_DIRECTORIES: usize = 16_384;
+const SEND_TIMEOUT: Duration = Duration::from_millis(250);
+
+const ERROR_JOURNAL_DELETE_IN_PROGRESS: u32 = 1178;
+const ERROR_JOURNAL_NOT_ACTIVE: u32 = 1179;
+const ERROR_JOURNAL_ENTRY_DELETED: u32 = 1181;
+
+const WATCHED_REASONS: u32 = USN_REASON_DATA_OVERWRITE
+    | USN_REASON_DATA_EXTEND
+    | USN_REASON_DATA_TRUNCATION
+    | USN_REASON_FILE_CREATE
+    | USN_REASON_FILE_DELETE
+    | USN_REASON_RENAME_OLD_NAME
+    | USN_REASON_RENAME_NEW_NAME;
+
+/// Polls the USN change journal of every NTFS volume hosting a watch root.
+pub(crate) struct UsnJournalBackend {
+    raw_tx: RawEventSender,
+    poll_interval: Duration,
+    volumes: Vec<VolumePoller>,
+}
+
+struct VolumePoller {
+    volume_name: String,
+    roots: Arc<Mutex<Vec<PathBuf>>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl UsnJournalBackend {
+    pub fn new(raw_tx: RawEventSender, poll_interval: Duration) -> Self {
+        Self {
+            raw_tx,
+            poll_interval,
+            volumes: Vec::new(),
+        }
+    }
+}
+
+impl WatcherBackend for UsnJournalBackend {
+    fn name(&self) -> &'static str {
+        "usn-journal"
+    }
+
+    fn watch(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mount_point = volume_mount_point(path)?;
+        let file_system = volume_file_system(&mount_point)?;
+        if !file_system.eq_ignore_ascii_case("NTFS") {
+            return Err(format!("volume {mount_point} is {file_system}, not NTFS").into());
+        }
+
+        let volume_name = volume_guid_name(&mount_point)?;
+        if let Some(poller) = self
+            .volumes
+            .iter()
+            .find(|poller| poller.volume_name == volume_name)
+        {
+            lock_roots(&poller.roots).push(path.to_path_buf());
+            return Ok(());
+        }
+
+        let volume = OpenOptions::new()
+            .read(true)
+            .open(volume_name.trim_end_matches('\\'))?;
+        let journal = query_journal(&volume)?;
+
+        let roots = Arc::new(Mutex::new(vec![path.to_path_buf()]));
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker = JournalPoller {
+            resolver: DirectoryResolver::new(volume),
+            journal_id: journal.UsnJournalID,
+            next_usn: journal.NextUsn,
+            roots: roots.clone(),
+            raw_tx: self.raw_tx.clone(),
+            stop: stop.clone(),
+            poll_interval: self.poll_interval,
+        };
+        let handle = std::thread::Builder::new()
+            .name("compact-games-usn".to_owned())
+            .spawn(move || worker.run())?;
+
+        self.volumes.push(VolumePoller {
+            volume_name,
+            roots,
+            stop,
+            handle: Some(handle),
+        });
+        Ok(())
+    }
+}
+
+impl Drop for UsnJournalBackend {
+    fn drop(&mut self) {
+        for poller in &self.volumes {
+            poller.stop.store(true, Ordering::Relaxed);
+            if let Some(handle) = poller.handle.as_ref() {
+                handle.thread().unpark();
+            }
+        }
+        for poller in &mut self.volumes {
+            if let Some(handle) = poller.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+// ── Poller thread ────────────────────────────────────────────────────
+
+struct JournalPoller {
+    resolver: DirectoryResolver,
+    journal_id: u64,
+    next_usn: i64,
+    roots: Arc<Mutex<Vec<PathBuf>>>,
+    raw_tx: RawEventSender,
+    stop: Arc<AtomicBool>,
+    poll_interval: Duration,
+}
+
+impl JournalPoller {
+    fn run(mut self) {
+        let mut buffer = vec![0_u8; READ_BUFFER_BYTES];
+        loop {
+            let deadline = Instant::now() + self.poll_interval;
+            while !self.stop.load(Ordering::Relaxed) {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                // park_timeout lets Drop wake this thread immediately via unpark().
+                std::thread::park_timeout(deadline - now);
+            }
+            if self.stop.load(Ordering::Relaxed) {
+                return;
+            }
+
+            if !self.poll(&mut buffer) {
+                return;
+            }
+        }
+    }
+
+    /// Drain new journal records. Returns `false` when the journal is gone
+    /// and the poller should exit.
+    fn poll(&mut self, buffer: &mut [u8]) -> bool {
+        for _ in 0..MAX_READS_PER_POLL {
+            if self.stop.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            let returned = match read_journal(
+                self.resolver.volume(),
+                self.journal_id,
+                self.next_usn,
+                buffer,
+            ) {
+                Ok(returned) => returned,
+                Err(code) => return self.handle_read_error(code),
+            };
+
+            let (next_usn, records) = parse_usn_records(&buffer[..returned]);
+            let Some(next_usn) = next_usn else {
+                return true;
+            };
+            let made_progress = next_usn != self.next_usn;
+            self.next_usn = next_usn;
+
+            for record in records {
+                if !self.publish(record) {
+                    return false;
+                }
+            }
+
+            if !made_progress {
+                return true;
+            }
+        }
+        true
+    }
+
+    fn handle_read_error(&mut self, code: u32) -> bool {
+        match code {
+            ERROR_JOURNAL_ENTRY_DELETED => {
+                log::warn!(
+                    "[automation][usn] journal wrapped past last read position; resuming at head"
+                );
+                match query_journal(self.resolver.volume()) {
+                    Ok(journal) => {
+                        self.journal_id = journal.UsnJournalID;
+                        self.next_usn = journal.NextUsn;
+                        self.resolver.clear();
+                        true
+                    }
+                    Err(e) => self.fail(format!("USN journal re-query failed: {e}")),
+                }
+            }
+            ERROR_JOURNAL_NOT_ACTIVE | ERROR_JOURNAL_DELETE_IN_PROGRESS => {
+                self.fail("USN journal was disabled; change detection stopped".to_owned())
+            }
+            _ => {
+                log::debug!("[automation][usn] journal read failed with Win32 error {code}");
+                true
+            }
+        }
+    }
+
+    fn fail(&self, message: String) -> bool {
+        log::warn!("[automation][usn] {message}");
+        let _ = self
+            .raw_tx
+            .send_timeout(Err(notify::Error::generic(&message)), SEND_TIMEOUT);
+        false
+    }
+
+    fn publish(&mut self, record: UsnChange) -> bool {
+        if record.is_directory
+            && record.reason & (USN_REASON_FILE_DELETE | USN_REASON_RENAME_NEW_NAME) != 0
+        {
+            // Cached child paths may now be stale.
+            self.resolver.clear();
+        }
+
+        let Some(parent) = self.resolver.directory_path(record.parent_ref) else {
+            return true;
+        };
+        let changed_path = parent.join(&record.file_name);
+        let rebased = {
+            let roots = lock_roots(&self.roots);
+            rebase_onto_root(&changed_path, &roots)
+        };
+        let Some(path) = rebased else {
+            return true;
+        };
+
+        let event = notify::Event::new(event_kind_for_reason(record.reason, record.is_directory))
+            .add_path(path);
+        match self.raw_tx.send_timeout(Ok(event), SEND_TIMEOUT) {
+            Ok(()) => true,
+            Err(SendTimeoutError::Timeout(_)) => {
+                log::warn!("[automation][usn] watcher channel full, dropping journal record");
+                true
+            }
+            Err(SendTimeoutError::Disconnected(_)) => false,
+        }
+    }
+}
+
+// ── Record parsing ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UsnChange {
+    parent_ref: u64,
+    reason: u32,
+    is_directory: bool,
+    file_name: OsString,
+}
+
+/// Parse the output of `FSCTL_READ_USN_JOURNAL`: a leading next-USN value
+/// followed by packed `USN_RECORD_V2` entries. Non-V2 records are skipped.
+fn parse_usn_records(buffer: &[u8]) -> (Option<i64>, Vec<UsnChange>) {
+    let Some(next_usn) = read_i64(buffer, 0) else {
+        return (None, Vec::new());
+    };
+
+    let mut records = Vec::new();
+    let mut offset = 8usize;
+    while let Some(record_length) = read_u32(buffer, offset).map(|len| len as usize) {
+        if record_length == 0 || offset + record_length > buffer.len() {
+            break;
+        }
+        let record = &buffer[offset..offset + record_length];
+        offset += record_length;
+
+        if read_u16(record, 4) != Some(2) {
+            continue;
+        }
+        let (Some(parent_ref), Some(reason), Some(attributes), Some(name_len), Some(name_off)) = (
+            read_u64(record, 16),
+            read_u32(record, 40),
+            read_u32(record, 52),
+            read_u16(record, 56).map(usize::from),
+            read_u16(record, 58).map(usize::from),
+        ) else {
+            continue;
+        };
+        let Some(name_bytes) = record.get(name_off..name_off + name_len) else {
+            continue;
+        };
+        let wide: Vec<u16> = name_bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+
+        records.push(UsnChange {
+            parent_ref,
+            reason,
+            is_directory: attributes & FILE_ATTRIBUTE_DIRECTORY.0 != 0,
+            file_name: OsString::from_wide(&wide),
+        });
+    }
+
+    (Some(next_usn), records)
+}
+
+fn read_u16(buffer: &[u8], offset: usize) -> Option<u16> {
+    buffer
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Option<u32> {
+    buffer
+        .get(offset..offset + 4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_le_bytes)
+}
+
+fn read_u64(buffer: &[u8], offset: usize) -> Option<u64> {
+    buffer
+        .get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+}
+
+fn read_i64(buffer: &[u8], offset: usize) -> Option<i64> {
+    read_u64(buffer, offset).map(|value| value as i64)
+}
+
+fn event_kind_for_reason(reason: u32, is_directory: bool) -> notify::EventKind {
+    if reason & USN_REASON_FILE_DELETE != 0 {
+        return notify::EventKind::Remove(if is_directory {
+            RemoveKind::Folder
+        } else {
+            RemoveKind::File
+        });
+    }
+    if reason & (USN_REASON_FILE_CREATE | USN_REASON_RENAME_NEW_NAME) != 0 {
+        return notify::EventKind::Create(if is_directory {
+            CreateKind::Folder
+        } else {
+            CreateKind::File
+        });
+    }
+    notify::EventKind::Modify(ModifyKind::Data(DataChange::Any))
+}
+
+/// Re-express `path` under the matching configured watch root so that
+/// downstream prefix matching sees the same spelling the user configured.
+fn rebase_onto_root(path: &Path, roots: &[PathBuf]) -> Option<PathBuf> {
+    let path_str = path.to_string_lossy();
+    let path_key = crate::utils::normalize_path_key(path);
+
+    roots.iter().find_map(|root| {
+        let root_key = crate::utils::normalize_path_key(root);
+        let remainder = path_key.strip_prefix(&root_key)?;
+        if !remainder.is_empty() && !remainder.starts_with('\\') && !root_key.ends_with('\\') {
+            return None;
+        }
+        let suffix = path_str.get(root_key.len()..)?.trim_start_matches('\\');
+        Some(if suffix.is_empty() {
+            root.clone()
+        } else {
+            root.join(suffix)
+        })
+    })
+}
+
+fn lock_roots(roots: &Mutex<Vec<PathBuf>>) -> std::sync::MutexGuard<'_, Vec<PathBuf>> {
+    roots
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// ── Win32 helpers ────────────────────────────────────────────────────
+
+/// Resolves directory file reference numbers to paths, caching results
+/// (including misses) because journal records repeat the same parents.
+struct DirectoryResolver {
+    volume: File,
+    cache: HashMap<u64, Option<PathBuf>>,
+}
+
+impl DirectoryResolver {
+    fn new(volume: File) -> Self {
+        Self {
+            volume,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn volume(&self) -> &File {
+        &self.volume
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn directory_path(&mut self, file_ref: u64) -> Option<PathBuf> {
+        if let Some(cached) = self.cache.get(&file_ref) {
+            return cached.clone();
+        }
+        if self.cache.len() >= MAX_CACHED_DIRECTORIES {
+            self.cache.clear();
+        }
+        let resolved = open_path_by_id(&self.volume, file_ref);
+        self.cache.insert(file_ref, resolved.clone());
+        resolved
+    }
+}
+
+fn open_path_by_id(volume: &File, file_ref: u64) -> Option<PathBuf> {
+    let descriptor = FILE_ID_DESCRIPTOR {
+        dwSize: std::mem::size_of::<FILE_ID_DESCRIPTOR>() as u32,
+        Type: FileIdType,
+        Anonymous: FILE_ID_DESCRIPTOR_0 {
+            FileId: file_ref as i64,
+        },
+    };
+    let handle = unsafe {
+        OpenFileById(
+            file_handle(volume),
+            &descriptor,
+            FILE_READ_ATTRIBUTES.0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+            None,
+            FILE_FLAG_BACKUP_SEMANTICS,
+        )
+    }
+    .ok()?;
+    // Take ownership so the handle is closed on every path.
+    let file = unsafe { File::from_raw_handle(handle.0 as _) };
+
+    let mut buffer = vec![0_u16; 32_768];
+    let length =
+        unsafe { GetFinalPathNameByHandleW(file_handle(&file), &mut buffer, FILE_NAME_NORMALIZED) }
+            as usize;
+    if length == 0 || length >= buffer.len() {
+        return None;
+    }
+    let path = OsString::from_wide(&buffer[..length]);
+    let path = path.to_string_lossy();
+    let path = path.strip_prefix(r"\\?\").unwrap_or(&path);
+    Some(PathBuf::from(path))
+}
+
+fn query_journal(volume: &File) -> Result<USN_JOURNAL_DATA_V0, windows::core::Error> {
+    let mut journal = USN_JOURNAL_DATA_V0::default();
+    let mut returned: u32 = 0;
+    unsafe {
+        DeviceIoControl(
+            file_handle(volume),
+            FSCTL_QUERY_USN_JOURNAL,
+            None,
+            0,
+            Some(&mut journal as *mut _ as *mut _),
+            std::mem::size_of::<USN_JOURNAL_DATA_V0>() as u32,
+            Some(&mut returned),
+            None,
+        )?;
+    }
+    Ok(journal)
+}
+
+fn read_journal(
+    volume: &File,
+    journal_id: u64,
+    start_usn: i64,
+    buffer: &mut [u8],
+) -> Result<usize, u32> {
+    let request = READ_USN_JOURNAL_DATA_V0 {
+        StartUsn: start_usn,
+        ReasonMask: WATCHED_REASONS,
+        // Only report once a handle is closed so writes collapse into one record.
+        ReturnOnlyOnClose: 1,
+        Timeout: 0,
+        BytesToWaitFor: 0,
+        UsnJournalID: journal_id,
+    };
+    let mut returned: u32 = 0;
+    unsafe {
+        DeviceIoControl(
+            file_handle(volume),
+            FSCTL_READ_USN_JOURNAL,
+            Some(&request as *const _ as *const _),
+            std::mem::size_of::<READ_USN_JOURNAL_DATA_V0>() as u32,
+            Some(buffer.as_mut_ptr() as *mut _),
+            buffer.len() as u32,
+            Some(&mut returned),
+            None,
+        )
+    }
+    .map_err(|e| (e.code().0 & 0xFFFF) as u32)?;
+    Ok(returned as usize)
+}
+
+fn volume_mount_point(path: &Path) -> Result<String, windows::core::Error> {
+    let wide = crate::utils::wide_path(path);
+    let mut buffer = vec![0_u16; 1024];
+    unsafe { GetVolumePathNameW(PCWSTR(wide.as_ptr()), &mut buffer)? };
+    // Long paths go in extended-length form and come back that way.
+    let mount_point = wide_buffer_to_string(&buffer);
+    Ok(mount_point
+        .strip_prefix(r"\\?\")
+        .map(str::to_string)
+        .unwrap_or(mount_point))
+}
+
+fn volume_file_system(mount_point: &str) -> Result<String, windows::core::Error> {
+    let wide = crate::utils::wide_null_str(mount_point);
+    let mut name = [0_u16; 64];
+    unsafe {
+        GetVolumeInformationW(
+            PCWSTR(wide.as_ptr()),
+            None,
+            None,
+            None,
+            None,
+            Some(&mut name),
+        )?
+    };
+    Ok(wide_buffer_to_string(&name))
+}
+
+fn volume_guid_name(mount_point: &str) -> Result<String, windows::core::Error> {
+    let wide = crate::utils::wide_null_str(mount_point);
+    let mut buffer = [0_u16; 64];
+    unsafe { GetVolumeNameForVolumeMountPointW(PCWSTR(wide.as_ptr()), &mut buffer)? };
+    Ok(wide_buffer_to_string(&buffer))
+}
+
+fn wide_buffer_to_string(buffer: &[u16]) -> String {
+    let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..end])
+}
+
+fn file_handle(file: &File) -> HANDLE {
+    HANDLE(file.as_raw_handle() as _)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_record(parent_ref: u64, reason: u32, attributes: u32, name: &str) -> Vec<u8> {
+        let name: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let unpadded = 60 + name.len();
+        let record_length = unpadded.div_ceil(8) * 8;
+        let mut record = vec![0_u8; record_length];
+        record[0..4].copy_from_slice(&(record_length as u32).to_le_bytes());
+        record[4..6].copy_from_slice(&2_u16.to_le_bytes());
+        record[16..24].copy_from_slice(&parent_ref.to_le_bytes());
+        record[40..44].copy_from_slice(&reason.to_le_bytes());
+        record[52..56].copy_from_slice(&attributes.to_le_bytes());
+        record[56..58].copy_from_slice(&(name.len() as u16).to_le_bytes());
+        record[58..60].copy_from_slice(&60_u16.to_le_bytes());
+        record[60..60 + name.len()].copy_from_slice(&name);
+        record
+    }
+
+    #[test]
+    fn parse_usn_records_reads_next_usn_and_records() {
+        let mut buffer = 4242_i64.to_le_bytes().to_vec();
+        buffer.extend(encode_record(7, USN_REASON_FILE_CREATE, 0, "game.pak"));
+        buffer.extend(encode_record(
+            9,
+            USN_REASON_FILE_DELETE,
+            FILE_ATTRIBUTE_DIRECTORY.0,
+            "Mods",
+        ));
+
+        let (next_usn, records) = parse_usn_records(&buffer);
+
+        assert_eq!(next_usn, Some(4242));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].parent_ref, 7);
+        assert_eq!(records[0].file_name, OsString::from("game.pak"));
+        assert!(!records[0].is_directory);
+        assert_eq!(records[1].reason, USN_REASON_FILE_DELETE);
+        assert!(records[1].is_directory);
+    }
+
+    #[test]
+    fn parse_usn_records_stops_at_truncated_record() {
+        let mut buffer = 1_i64.to_le_bytes().to_vec();
+        let record = encode_record(7, USN_REASON_FILE_CREATE, 0, "game.pak");
+        buffer.extend(&record[..record.len() - 4]);
+
+        let (next_usn, records) = parse_usn_records(&buffer);
+
+        assert_eq!(next_usn, Some(1));
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn event_kind_prefers_delete_then_create() {
+        assert!(matches!(
+            event_kind_for_reason(USN_REASON_FILE_CREATE | USN_REASON_FILE_DELETE, false),
+            notify::EventKind::Remove(RemoveKind::File)
+        ));
+        assert!(matches!(
+            event_kind_for_reason(USN_REASON_RENAME_NEW_NAME, true),
+            notify::EventKind::Create(CreateKind::Folder)
+        ));
+        assert!(matches!(
+            event_kind_for_reason(USN_REASON_DATA_EXTEND, false),
+            notify::EventKind::Modify(_)
+        ));
+    }
+
+    #[test]
+    fn rebase_onto_root_preserves_configured_root_spelling() {
+        let roots = vec![PathBuf::from(r"D:\SteamLibrary\steamapps\common")];
+
+        let rebased = rebase_onto_root(
+            Path::new(r"D:\STEAMLIBRARY\SteamApps\common\Portal 2\portal2.exe"),
+            &roots,
+        );
+
+        assert_eq!(
+            rebased,
+            Some(PathBuf::from(
+                r"D:\SteamLibrary\steamapps\common\Portal 2\portal2.exe"
+            ))
+        );
+        assert_eq!(
+            rebase_onto_root(Path::new(r"D:\SteamLibrary\steamapps\commonx\a"), &roots),
+            None
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! `pressplay-cli`: scan, compress and inspect games from scripts. See
+//! [`compact_games_core::cli`] for the commands.
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    compact_games_core::logging::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    compact_games_core::cli::run(&args)
+}
@@ -0,0 +1,126 @@
+//! `pressplay-daemon`: runs auto-compression without the app.
+//!
+//! ```text
+//! pressplay-daemon [run]                         run in this console until Ctrl+C
+//! pressplay-daemon install [--account NAME --password PASSWORD]
+//! pressplay-daemon uninstall
+//! pressplay-daemon status
+//! ```
+//!
+//! The service manager starts the installed service with `service`.
+
+use std::process::ExitCode;
+
+#[cfg(windows)]
+fn main() -> ExitCode {
+    use compact_games_core::daemon::{self, service};
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first().map(String::as_str).unwrap_or("run");
+    let result = match command {
+        "run" => {
+            compact_games_core::logging::init();
+            daemon::console::run()
+        }
+        service::SERVICE_ARGUMENT => service::run_dispatcher(),
+        "install" => match parse_account(&args[1..]) {
+            Ok(account) => service::install(account).map(|()| {
+                println!("Installed service '{}'", service::SERVICE_NAME);
+            }),
+            Err(message) => return usage(&message),
+        },
+        "uninstall" => service::uninstall().map(|()| {
+            println!("Removed service '{}'", service::SERVICE_NAME);
+        }),
+        "status" => {
+            print_status();
+            Ok(())
+        }
+        other => return usage(&format!("unknown command '{other}'")),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("pressplay-daemon: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(windows)]
+fn parse_account(
+    args: &[String],
+) -> Result<Option<compact_games_core::daemon::service::ServiceAccount>, String> {
+    let mut name = None;
+    let mut password = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("missing value for {arg}"))?
+            .clone();
+        match arg.as_str() {
+            "--account" => name = Some(value),
+            "--password" => password = Some(value),
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+    match (name, password) {
+        (Some(name), Some(password)) => {
+            Ok(Some(compact_games_core::daemon::service::ServiceAccount {
+                name,
+                password,
+            }))
+        }
+        (None, None) => Ok(None),
+        _ => Err("--account and --password go together".to_string()),
+    }
+}
+
+#[cfg(windows)]
+fn print_status() {
+    use compact_games_core::daemon::status;
+
+    let Some(status) = status::read() else {
+        println!("Daemon has never run for this user");
+        return;
+    };
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    if !status.is_alive(now_ms) {
+        println!("Daemon is not running");
+        return;
+    }
+    println!(
+        "Daemon running (pid {}, {}): {} | queue {} | watching {} paths",
+        status.pid,
+        if status.is_service {
+            "service"
+        } else {
+            "console"
+        },
+        status.scheduler_state,
+        status.queue_depth,
+        status.watched_path_count,
+    );
+    if let Some(error) = status.last_error {
+        println!("Last error: {error}");
+    }
+}
+
+#[cfg(windows)]
+fn usage(message: &str) -> ExitCode {
+    eprintln!("pressplay-daemon: {message}");
+    eprintln!(
+        "usage: pressplay-daemon [run | install [--account NAME --password PASSWORD] | uninstall | status]"
+    );
+    ExitCode::from(2)
+}
+
+#[cfg(not(windows))]
+fn main() -> ExitCode {
+    eprintln!("pressplay-daemon requires Windows");
+    ExitCode::FAILURE
+}
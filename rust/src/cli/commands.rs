@@ -0,0 +1,297 @@
+//! Command bodies. Text goes to stdout, progress to stderr (only on a
+//! terminal), so scripts can capture either output mode.
+
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde_json::{json, Value};
+
+use super::Command;
+use crate::api::compression::{compress_game_with_progress, decompress_game_with_progress};
+use crate::automation::journal::JournalWriter;
+use crate::automation::notifications::format_size;
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::engine::{CompressionEngine, EstimateGameContext};
+use crate::discovery::platform::DiscoveryScanMode;
+use crate::ipc::client::IpcClient;
+use crate::ipc::methods::{decompression_stats_json, folder_name, stats_json};
+use crate::progress::tracker::CompressionProgress;
+
+pub(super) fn execute(command: Command, json: bool) -> Result<(), String> {
+    match command {
+        Command::Scan { quick } => scan(quick, json),
+        Command::Compress {
+            path,
+            name,
+            algorithm,
+            allow_directstorage,
+        } => compress(path, name, algorithm, allow_directstorage, json),
+        Command::Decompress { path, name } => decompress(path, name, json),
+        Command::Estimate {
+            path,
+            algorithm,
+            sampled,
+        } => estimate(&path, algorithm, sampled, json),
+        Command::Status => status(json),
+        Command::Queue => queue(json),
+    }
+}
+
+fn scan(quick: bool, json: bool) -> Result<(), String> {
+    let mode = if quick {
+        DiscoveryScanMode::Quick
+    } else {
+        DiscoveryScanMode::Full
+    };
+    let mut games = crate::discovery::utils::scan_all_platforms_with_mode(mode);
+    games.sort_by_cached_key(|game| game.name.to_lowercase());
+    if json {
+        return print_json(&games);
+    }
+    for game in &games {
+        let state = if game.is_compressed {
+            "compressed"
+        } else {
+            "uncompressed"
+        };
+        println!(
+            "{:<40} {:<16} {:>9}  {:<12} {}",
+            game.name,
+            game.platform.to_string(),
+            format_size(game.size_bytes),
+            state,
+            game.path.display()
+        );
+    }
+    println!("{} games", games.len());
+    Ok(())
+}
+
+fn compress(
+    path: String,
+    name: Option<String>,
+    algorithm: CompressionAlgorithm,
+    allow_directstorage: bool,
+    json: bool,
+) -> Result<(), String> {
+    let name = name.unwrap_or_else(|| folder_name(&path));
+    let result = compress_game_with_progress(
+        path,
+        name.clone(),
+        algorithm.into(),
+        allow_directstorage,
+        None,
+        None,
+        &mut show_progress,
+    );
+    finish_progress();
+    let stats = result.map_err(|e| e.to_string())?;
+    if json {
+        return print_json(&stats_json(&stats));
+    }
+    println!(
+        "Compressed {name} with {algorithm}: {} -> {} ({} saved), {} files in {:.1}s",
+        format_size(stats.original_bytes),
+        format_size(stats.compressed_bytes),
+        format_size(stats.original_bytes.saturating_sub(stats.compressed_bytes)),
+        stats.files_processed,
+        stats.duration_ms as f64 / 1000.0
+    );
+    if stats.files_already_compressed > 0 {
+        println!(
+            "{} files were already compressed; this run saved {}",
+            stats.files_already_compressed,
+            format_size(stats.bytes_saved_this_run)
+        );
+    }
+    if stats.files_skipped > 0 {
+        println!("Skipped {} files", stats.files_skipped);
+    }
+    if stats.files_skipped_permission > 0 {
+        println!(
+            "{} of them are admin-only; enable compress_admin_only_files in settings and run elevated to include them",
+            stats.files_skipped_permission
+        );
+    }
+    Ok(())
+}
+
+fn decompress(path: String, name: Option<String>, json: bool) -> Result<(), String> {
+    let name = name.unwrap_or_else(|| folder_name(&path));
+    let result = decompress_game_with_progress(path, name.clone(), None, &mut show_progress);
+    finish_progress();
+    let stats = result.map_err(|e| e.to_string())?;
+    if json {
+        return print_json(&decompression_stats_json(&stats));
+    }
+    println!(
+        "Decompressed {name}: {} files, {} restored, {} more on disk, in {:.1}s",
+        stats.files_decompressed,
+        format_size(stats.bytes_restored_logical),
+        format_size(stats.bytes_grown_physical),
+        stats.duration_ms as f64 / 1000.0
+    );
+    Ok(())
+}
+
+fn estimate(
+    path: &str,
+    algorithm: CompressionAlgorithm,
+    sampled: bool,
+    json: bool,
+) -> Result<(), String> {
+    let engine = CompressionEngine::new(algorithm);
+    let estimate = if sampled {
+        engine.estimate_folder_savings_sampled(Path::new(path))
+    } else {
+        engine.estimate_folder_savings_with_context(
+            Path::new(path),
+            EstimateGameContext {
+                game_name: None,
+                steam_app_id: None,
+                known_size_bytes: None,
+            },
+        )
+    }
+    .map_err(|e| e.to_string())?;
+    if json {
+        return print_json(&estimate);
+    }
+    println!(
+        "{algorithm}: about {} saved ({} - {}) from {} in {} files, confidence {:.0}%",
+        format_size(estimate.estimated_saved_bytes),
+        format_size(estimate.estimated_saved_low_bytes),
+        format_size(estimate.estimated_saved_high_bytes),
+        format_size(estimate.sampled_bytes),
+        estimate.scanned_files,
+        estimate.confidence * 100.0
+    );
+    Ok(())
+}
+
+fn status(json: bool) -> Result<(), String> {
+    let engine = IpcClient::connect()
+        .and_then(|mut client| client.call("automation.status", Value::Null))
+        .map_err(|e| log::debug!("Engine status unavailable over IPC: {e}"))
+        .ok();
+    let daemon = crate::daemon::status::read();
+    if json {
+        return print_json(&json!({ "engine": engine, "daemon": daemon }));
+    }
+
+    let now_ms = crate::utils::unix_now_ms();
+    match &daemon {
+        Some(daemon) if daemon.is_alive(now_ms) => println!(
+            "Daemon running (pid {}, {})",
+            daemon.pid,
+            if daemon.is_service {
+                "service"
+            } else {
+                "console"
+            }
+        ),
+        _ => println!("Daemon is not running"),
+    }
+    if let Some(engine) = engine {
+        println!(
+            "Automation {}: {} | queue {} | watching {} paths",
+            if engine["running"].as_bool() == Some(true) {
+                "running"
+            } else {
+                "stopped"
+            },
+            engine["state"].as_str().unwrap_or("unknown"),
+            engine["queue_depth"],
+            engine["watched_path_count"],
+        );
+        if let Some(error) = engine["last_error"].as_str() {
+            println!("Last error: {error}");
+        }
+    }
+    Ok(())
+}
+
+/// Live queue from the engine, or the pending journal when none is running.
+fn queue(json: bool) -> Result<(), String> {
+    let live =
+        IpcClient::connect().and_then(|mut client| client.call("automation.queue", Value::Null));
+    let (source, jobs) = match live {
+        Ok(jobs) => ("engine", jobs),
+        Err(e) => {
+            log::debug!("Live queue unavailable over IPC: {e}");
+            let entries = JournalWriter::read_default().map_err(|e| e.to_string())?;
+            let jobs = entries
+                .into_iter()
+                .map(|entry| {
+                    json!({
+                        "game_path": entry.game_path,
+                        "game_name": entry.game_name,
+                        "kind": format!("{:?}", entry.event_kind),
+                        "status": "Pending",
+                        "queued_at_ms": entry
+                            .queued_at
+                            .duration_since(UNIX_EPOCH)
+                            .map(|since| since.as_millis() as u64)
+                            .unwrap_or_default(),
+                    })
+                })
+                .collect();
+            ("journal", Value::Array(jobs))
+        }
+    };
+    if json {
+        return print_json(&json!({ "source": source, "jobs": jobs }));
+    }
+
+    let jobs = jobs.as_array().map(Vec::as_slice).unwrap_or_default();
+    if jobs.is_empty() {
+        println!("Queue is empty");
+        return Ok(());
+    }
+    if source == "journal" {
+        println!("Engine not running; pending jobs from the journal:");
+    }
+    for job in jobs {
+        let path = job["game_path"].as_str().unwrap_or_default();
+        let name = job["game_name"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| folder_name(path));
+        println!(
+            "{:<10} {:<14} {name} ({path})",
+            job["status"].as_str().unwrap_or_default(),
+            job["kind"].as_str().unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
+
+fn show_progress(progress: &CompressionProgress) -> bool {
+    let mut stderr = std::io::stderr();
+    if stderr.is_terminal() {
+        let _ = write!(
+            stderr,
+            "\r{:>3}% | {}/{} files | {} saved   ",
+            progress.percent(),
+            progress.files_processed,
+            progress.files_total,
+            format_size(progress.bytes_saved)
+        );
+        let _ = stderr.flush();
+    }
+    true
+}
+
+fn finish_progress() {
+    let stderr = std::io::stderr();
+    if stderr.is_terminal() {
+        eprintln!();
+    }
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{text}");
+    Ok(())
+}
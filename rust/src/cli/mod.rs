@@ -0,0 +1,197 @@
+//! `pressplay-cli`: the engine for scripts and Task Scheduler.
+//!
+//! ```text
+//! pressplay-cli scan [--quick]
+//! pressplay-cli compress <path> [--algo xpress4k|xpress8k|xpress16k|lzx] [--name NAME] [--allow-directstorage]
+//! pressplay-cli decompress <path> [--name NAME]
+//! pressplay-cli estimate <path> [--algo ALGO] [--sampled]
+//! pressplay-cli status
+//! pressplay-cli queue
+//! ```
+//!
+//! Every command takes `--json` to print one JSON document on stdout
+//! instead of text. `scan`, `compress`, `decompress` and `estimate` run in
+//! this process; `status` and `queue` ask the running daemon over
+//! [`crate::ipc`] and fall back to its heartbeat and journal files.
+//!
+//! Exit codes: 0 success, 1 the command failed, 2 bad arguments.
+
+mod commands;
+
+use std::process::ExitCode;
+
+use crate::compression::algorithm::CompressionAlgorithm;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Scan {
+        quick: bool,
+    },
+    Compress {
+        path: String,
+        name: Option<String>,
+        algorithm: CompressionAlgorithm,
+        allow_directstorage: bool,
+    },
+    Decompress {
+        path: String,
+        name: Option<String>,
+    },
+    Estimate {
+        path: String,
+        algorithm: CompressionAlgorithm,
+        sampled: bool,
+    },
+    Status,
+    Queue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Invocation {
+    command: Command,
+    json: bool,
+}
+
+const USAGE: &str = "usage: pressplay-cli <scan [--quick] | compress <path> [--algo ALGO] [--name NAME] [--allow-directstorage] | decompress <path> [--name NAME] | estimate <path> [--algo ALGO] [--sampled] | status | queue> [--json]";
+
+/// Run one command line (without the program name).
+pub fn run(args: &[String]) -> ExitCode {
+    let invocation = match parse(args) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            eprintln!("pressplay-cli: {message}");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match commands::execute(invocation.command, invocation.json) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("pressplay-cli: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<Invocation, String> {
+    let mut json = false;
+    let mut flags = Vec::new();
+    let mut options: Vec<(&str, &str)> = Vec::new();
+    let mut positionals = Vec::new();
+    let mut iter = args.iter().map(String::as_str);
+    while let Some(arg) = iter.next() {
+        match arg {
+            "--json" => json = true,
+            "--quick" | "--sampled" | "--allow-directstorage" => flags.push(arg),
+            "--algo" | "--name" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("missing value for {arg}"))?;
+                options.push((arg, value));
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ => positionals.push(arg),
+        }
+    }
+
+    let mut positionals = positionals.into_iter();
+    let name = positionals.next().ok_or("missing command")?;
+    let mut path = || {
+        positionals
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| format!("{name} needs a path"))
+    };
+    let command = match name {
+        "scan" => Command::Scan {
+            quick: flags.contains(&"--quick"),
+        },
+        "compress" => Command::Compress {
+            path: path()?,
+            name: option(&options, "--name").map(str::to_string),
+            algorithm: algorithm(&options)?,
+            allow_directstorage: flags.contains(&"--allow-directstorage"),
+        },
+        "decompress" => Command::Decompress {
+            path: path()?,
+            name: option(&options, "--name").map(str::to_string),
+        },
+        "estimate" => Command::Estimate {
+            path: path()?,
+            algorithm: algorithm(&options)?,
+            sampled: flags.contains(&"--sampled"),
+        },
+        "status" => Command::Status,
+        "queue" => Command::Queue,
+        other => return Err(format!("unknown command '{other}'")),
+    };
+    if let Some(extra) = positionals.next() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    Ok(Invocation { command, json })
+}
+
+fn option<'a>(options: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .rev()
+        .find(|(option, _)| *option == name)
+        .map(|(_, value)| *value)
+}
+
+fn algorithm(options: &[(&str, &str)]) -> Result<CompressionAlgorithm, String> {
+    match option(options, "--algo") {
+        None => Ok(CompressionAlgorithm::default()),
+        Some(flag) => CompressionAlgorithm::from_compact_exe_flag(flag)
+            .ok_or_else(|| format!("unknown algorithm '{flag}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line: &str) -> Result<Invocation, String> {
+        let args: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+        parse(&args)
+    }
+
+    #[test]
+    fn parses_commands_and_options_in_any_order() {
+        assert_eq!(
+            parse_line("compress --algo LZX D:\\Games\\Hades --json").unwrap(),
+            Invocation {
+                command: Command::Compress {
+                    path: "D:\\Games\\Hades".into(),
+                    name: None,
+                    algorithm: CompressionAlgorithm::Lzx,
+                    allow_directstorage: false,
+                },
+                json: true,
+            }
+        );
+        assert_eq!(
+            parse_line("scan --quick").unwrap().command,
+            Command::Scan { quick: true }
+        );
+        assert_eq!(
+            parse_line("estimate D:\\Games\\Hades").unwrap().command,
+            Command::Estimate {
+                path: "D:\\Games\\Hades".into(),
+                algorithm: CompressionAlgorithm::Xpress4K,
+                sampled: false,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_command_lines() {
+        assert!(parse_line("").is_err());
+        assert!(parse_line("compress").is_err());
+        assert!(parse_line("compress D:\\Games\\Hades --algo lznt1").is_err());
+        assert!(parse_line("compress D:\\Games\\Hades --algo").is_err());
+        assert!(parse_line("status --verbose").is_err());
+        assert!(parse_line("queue extra").is_err());
+        assert!(parse_line("defrag").is_err());
+    }
+}
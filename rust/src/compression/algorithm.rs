@@ -44,6 +44,13 @@ impl CompressionAlgorithm {
         }
     }
 
+    /// Parse a `compact.exe /EXE:` name such as `xpress8k`, ignoring case.
+    pub fn from_compact_exe_flag(flag: &str) -> Option<Self> {
+        [Self::Xpress4K, Self::Xpress8K, Self::Xpress16K, Self::Lzx]
+            .into_iter()
+            .find(|algo| algo.compact_exe_flag().eq_ignore_ascii_case(flag))
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Self::Xpress4K => "XPRESS 4K (Fast)",
@@ -109,6 +116,26 @@ mod tests {
         assert_eq!(CompressionAlgorithm::from_wof_id(99), None);
     }
 
+    #[test]
+    fn compact_exe_flag_roundtrip() {
+        for algo in [
+            CompressionAlgorithm::Xpress4K,
+            CompressionAlgorithm::Xpress8K,
+            CompressionAlgorithm::Xpress16K,
+            CompressionAlgorithm::Lzx,
+        ] {
+            assert_eq!(
+                CompressionAlgorithm::from_compact_exe_flag(algo.compact_exe_flag()),
+                Some(algo)
+            );
+        }
+        assert_eq!(
+            CompressionAlgorithm::from_compact_exe_flag("LZX"),
+            Some(CompressionAlgorithm::Lzx)
+        );
+        assert_eq!(CompressionAlgorithm::from_compact_exe_flag("lznt1"), None);
+    }
+
     #[test]
     fn serde_roundtrip() {
         let algo = CompressionAlgorithm::Xpress16K;
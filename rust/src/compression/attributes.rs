@@ -0,0 +1,49 @@
+//! File attributes that keep WOF from compressing a file.
+//!
+//! EFS-encrypted files cannot take a WOF backing, and sparse files already
+//! save their unallocated ranges; WOF would allocate them. Compression
+//! skips both and discovery flags games that hold many of them.
+
+use serde::{Deserialize, Serialize};
+
+const FILE_ATTRIBUTE_SPARSE_FILE: u32 = 0x200;
+const FILE_ATTRIBUTE_ENCRYPTED: u32 = 0x4000;
+
+/// Why a file was left out of compression for an attribute of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncompatibleAttribute {
+    Encrypted,
+    Sparse,
+}
+
+/// The attribute in `attributes` that rules out WOF, if any. Encryption
+/// wins when a file is both.
+pub fn incompatible_attribute(attributes: u32) -> Option<IncompatibleAttribute> {
+    if attributes & FILE_ATTRIBUTE_ENCRYPTED != 0 {
+        Some(IncompatibleAttribute::Encrypted)
+    } else if attributes & FILE_ATTRIBUTE_SPARSE_FILE != 0 {
+        Some(IncompatibleAttribute::Sparse)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encryption_and_sparseness_rule_out_wof() {
+        // FILE_ATTRIBUTE_ARCHIVE alone.
+        assert_eq!(incompatible_attribute(0x20), None);
+        assert_eq!(
+            incompatible_attribute(0x20 | FILE_ATTRIBUTE_SPARSE_FILE),
+            Some(IncompatibleAttribute::Sparse)
+        );
+        assert_eq!(
+            incompatible_attribute(FILE_ATTRIBUTE_ENCRYPTED | FILE_ATTRIBUTE_SPARSE_FILE),
+            Some(IncompatibleAttribute::Encrypted)
+        );
+    }
+}
@@ -298,8 +298,7 @@ fn community_db_cache_path() -> Result<PathBuf, String> {
 
     #[cfg(not(test))]
     {
-        let config_dir = dirs::config_dir().ok_or_else(|| "no config dir".to_string())?;
-        let compact_games_dir = config_dir.join("compact_games");
+        let compact_games_dir = crate::paths::data_dir().map_err(|e| e.to_string())?;
         fs::create_dir_all(&compact_games_dir)
             .map_err(|e| format!("Failed to create config dir: {e}"))?;
         Ok(compact_games_dir.join("compression_db.v1.json"))
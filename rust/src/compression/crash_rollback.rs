@@ -0,0 +1,117 @@
+//! Crash tracking for compressed games.
+//!
+//! A small set of titles misbehave under WOF: they crash on startup once
+//! compressed, typically from anti-tamper checks or engines that map their
+//! own executables. A game whose process keeps exiting abnormally shortly
+//! after launch is rolled back (decompressed) when the user opted in.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Exits this soon after launch count as a startup crash; later ones are
+/// ordinary in-game crashes that compression is unlikely to cause.
+pub const STARTUP_CRASH_WINDOW: Duration = Duration::from_secs(2 * 60);
+/// Startup crashes needed before rolling back; one crash is not a pattern.
+pub const CRASHES_BEFORE_ROLLBACK: usize = 3;
+/// Crashes older than this are forgotten.
+const CRASH_MEMORY: Duration = Duration::from_secs(60 * 60);
+
+/// How one run of a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameExit {
+    pub launched_at: Instant,
+    pub exited_at: Instant,
+    /// A process of the game exited with a non-zero code.
+    pub abnormal: bool,
+}
+
+impl GameExit {
+    pub fn is_startup_crash(&self) -> bool {
+        self.abnormal
+            && self.exited_at.saturating_duration_since(self.launched_at) <= STARTUP_CRASH_WINDOW
+    }
+}
+
+/// Recent startup crashes per game.
+#[derive(Debug, Default)]
+pub struct CrashTracker {
+    crashes: HashMap<PathBuf, Vec<Instant>>,
+}
+
+impl CrashTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how a run of `game_path` ended. Returns the number of recent
+    /// startup crashes once it reaches [`CRASHES_BEFORE_ROLLBACK`], and
+    /// forgets them so the same crashes do not trigger twice.
+    pub fn record_exit(&mut self, game_path: &Path, exit: GameExit) -> Option<usize> {
+        if !exit.is_startup_crash() {
+            // A run that got past startup shows the game works compressed.
+            self.crashes.remove(game_path);
+            return None;
+        }
+
+        let crashes = self.crashes.entry(game_path.to_path_buf()).or_default();
+        crashes.retain(|at| exit.exited_at.saturating_duration_since(*at) <= CRASH_MEMORY);
+        crashes.push(exit.exited_at);
+        if crashes.len() < CRASHES_BEFORE_ROLLBACK {
+            return None;
+        }
+        self.crashes.remove(game_path).map(|crashes| crashes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exit(launched_at: Instant, ran_for_secs: u64, abnormal: bool) -> GameExit {
+        GameExit {
+            launched_at,
+            exited_at: launched_at + Duration::from_secs(ran_for_secs),
+            abnormal,
+        }
+    }
+
+    #[test]
+    fn repeated_startup_crashes_trigger_rollback_once() {
+        let game = Path::new(r"C:\Games\Fragile");
+        let start = Instant::now();
+        let mut tracker = CrashTracker::new();
+
+        assert_eq!(tracker.record_exit(game, exit(start, 10, true)), None);
+        assert_eq!(tracker.record_exit(game, exit(start, 20, true)), None);
+        assert_eq!(tracker.record_exit(game, exit(start, 30, true)), Some(3));
+        assert_eq!(tracker.record_exit(game, exit(start, 40, true)), None);
+    }
+
+    #[test]
+    fn clean_runs_and_late_crashes_reset_the_count() {
+        let game = Path::new(r"C:\Games\Fine");
+        let start = Instant::now();
+        let mut tracker = CrashTracker::new();
+
+        tracker.record_exit(game, exit(start, 10, true));
+        tracker.record_exit(game, exit(start, 20, true));
+        assert_eq!(tracker.record_exit(game, exit(start, 3_600, false)), None);
+        tracker.record_exit(game, exit(start, 10, true));
+        tracker.record_exit(game, exit(start, 20, true));
+        assert_eq!(tracker.record_exit(game, exit(start, 600, true)), None);
+        assert_eq!(tracker.record_exit(game, exit(start, 30, true)), None);
+    }
+
+    #[test]
+    fn old_crashes_are_forgotten() {
+        let game = Path::new(r"C:\Games\Occasional");
+        let start = Instant::now();
+        let mut tracker = CrashTracker::new();
+
+        tracker.record_exit(game, exit(start, 10, true));
+        tracker.record_exit(game, exit(start, 20, true));
+        let later = start + CRASH_MEMORY + Duration::from_secs(60);
+        assert_eq!(tracker.record_exit(game, exit(later, 10, true)), None);
+    }
+}
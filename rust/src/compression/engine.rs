@@ -1,29 +1,39 @@
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
 
 use crossbeam_channel::{bounded, Receiver};
 use serde::{Deserialize, Serialize};
 
+mod benchmark;
 mod engine_safety;
 mod estimation;
 mod estimation_runtime;
 mod operation_session;
 mod path_guard;
+mod sampling;
+mod space_preflight;
 #[cfg(windows)]
 mod wof_ops;
 
 use super::algorithm::CompressionAlgorithm;
+use super::attributes::IncompatibleAttribute;
 use super::error::CompressionError;
-use super::thread_policy::ThreadPolicy;
+use super::library_lock::{lock_game_folder, GameFolderLock};
+use super::rate_limit::RateLimiter;
+use super::thread_policy::{compute_thread_policy, ThreadPolicy, ThroughputSample};
 use crate::progress::reporter::{EngineCounters, ProgressReporter};
 use crate::progress::tracker::CompressionProgress;
+use crate::safety::traversal::TraversalPolicy;
 
+pub use self::benchmark::{AlgorithmBenchmark, BenchmarkReport};
 pub use self::engine_safety::SafetyConfig;
 use self::engine_safety::{run_process_safety_check, run_safety_checks, DirectStoragePolicy};
 pub use self::estimation_runtime::EstimateGameContext;
 use self::operation_session::{OperationGuard, OperationLock, OperationSession};
 use self::path_guard::safe_file_iter;
+use self::space_preflight::{reserve_space, SpaceReservation};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompressionStats {
@@ -31,7 +41,52 @@ pub struct CompressionStats {
     pub compressed_bytes: u64,
     pub files_processed: u64,
     pub files_skipped: u64,
+    /// Subset of `files_skipped` that were online-only cloud placeholders.
+    #[serde(default)]
+    pub files_skipped_cloud: u64,
+    /// Subset of `files_skipped` whose ACLs denied this user write access.
+    #[serde(default)]
+    pub files_skipped_permission: u64,
+    /// Subset of `files_skipped` that were EFS-encrypted.
+    #[serde(default)]
+    pub files_skipped_encrypted: u64,
+    /// Subset of `files_skipped` that were sparse.
+    #[serde(default)]
+    pub files_skipped_sparse: u64,
+    /// The encrypted and sparse files skipped, up to
+    /// `MAX_REPORTED_ATTRIBUTE_SKIPS`, so the user can find them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attribute_skips: Vec<AttributeSkip>,
+    /// Files carrying alternate data streams.
+    #[serde(default)]
+    pub alternate_stream_files: u64,
+    /// Total size of those streams. WOF leaves them uncompressed and they
+    /// are not part of `original_bytes` or `compressed_bytes`.
+    #[serde(default)]
+    pub alternate_stream_bytes: u64,
+    /// Files already compressed before this run and left as they were.
+    /// They still count towards `original_bytes` and `compressed_bytes`,
+    /// which describe the whole folder on disk.
+    #[serde(default)]
+    pub files_already_compressed: u64,
+    /// Logical size of `files_already_compressed`.
+    #[serde(default)]
+    pub bytes_already_compressed: u64,
+    /// Physical size of `files_already_compressed`.
+    #[serde(default)]
+    pub bytes_already_compressed_on_disk: u64,
+    /// Set when a graceful cancel stopped the run early; the stats cover
+    /// the files that finished.
+    #[serde(default)]
+    pub was_cancelled: bool,
+    /// Rate achieved at the run's worker count, when the run was long
+    /// enough and uncapped; see `ThroughputSample::measure`.
+    #[serde(default)]
+    pub throughput: Option<ThroughputSample>,
     pub duration_ms: u64,
+    /// Per-extension byte totals, largest original size first.
+    #[serde(default)]
+    pub extensions: Vec<ExtensionStats>,
 }
 
 impl CompressionStats {
@@ -45,12 +100,168 @@ impl CompressionStats {
     pub fn bytes_saved(&self) -> u64 {
         self.original_bytes.saturating_sub(self.compressed_bytes)
     }
+
+    /// Whether alternate data streams hold enough of the folder that the
+    /// savings figures understate what stays on disk.
+    pub fn has_significant_alternate_streams(&self) -> bool {
+        self.alternate_stream_bytes >= SIGNIFICANT_ALTERNATE_STREAM_BYTES
+            && self.alternate_stream_bytes.saturating_mul(100)
+                >= self
+                    .original_bytes
+                    .saturating_mul(SIGNIFICANT_ALTERNATE_STREAM_PERCENT)
+    }
+
+    /// Savings from files this run compressed, excluding what earlier runs
+    /// had already saved.
+    pub fn bytes_saved_this_run(&self) -> u64 {
+        let already_saved = self
+            .bytes_already_compressed
+            .saturating_sub(self.bytes_already_compressed_on_disk);
+        self.bytes_saved().saturating_sub(already_saved)
+    }
+}
+
+/// What a decompression run restored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecompressionStats {
+    /// Files whose WOF backing was removed.
+    pub files_decompressed: u64,
+    /// Logical size of `files_decompressed`.
+    pub bytes_restored_logical: u64,
+    /// How much space on disk `files_decompressed` took up afterwards
+    /// compared to before.
+    pub bytes_grown_physical: u64,
+    pub duration_ms: u64,
+}
+
+/// Bytes one file extension contributed to a compression run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionStats {
+    /// Lowercase, without the dot; empty for files without an extension.
+    pub extension: String,
+    pub files: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// Files WOF rejected as not worth compressing, or skipped because
+    /// an earlier run learned they would be.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub not_beneficial_files: u64,
+}
+
+impl ExtensionStats {
+    pub fn key_for(path: &Path) -> String {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default()
+    }
+}
+
+/// A file compression left alone because of its attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributeSkip {
+    pub path: PathBuf,
+    pub attribute: IncompatibleAttribute,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// Counts encrypted and sparse skips from parallel compression workers
+/// and keeps the first few paths for the report.
+#[derive(Default)]
+struct AttributeSkipTally {
+    encrypted: AtomicU64,
+    sparse: AtomicU64,
+    report: Mutex<Vec<AttributeSkip>>,
+}
+
+impl AttributeSkipTally {
+    fn record(&self, path: &Path, attribute: IncompatibleAttribute) {
+        let counter = match attribute {
+            IncompatibleAttribute::Encrypted => &self.encrypted,
+            IncompatibleAttribute::Sparse => &self.sparse,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        let mut report = self.report.lock().unwrap_or_else(|e| e.into_inner());
+        if report.len() < MAX_REPORTED_ATTRIBUTE_SKIPS {
+            report.push(AttributeSkip {
+                path: path.to_path_buf(),
+                attribute,
+            });
+        }
+    }
+
+    /// Encrypted count, sparse count and the reported files.
+    fn into_parts(self) -> (u64, u64, Vec<AttributeSkip>) {
+        (
+            self.encrypted.into_inner(),
+            self.sparse.into_inner(),
+            self.report.into_inner().unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+}
+
+/// Collects `ExtensionStats` from parallel compression workers.
+#[derive(Default)]
+struct ExtensionTally {
+    by_extension: std::sync::Mutex<std::collections::HashMap<String, ExtensionStats>>,
+}
+
+impl ExtensionTally {
+    fn record(&self, path: &Path, original_bytes: u64, compressed_bytes: u64) {
+        self.record_with(path, original_bytes, compressed_bytes, false);
+    }
+
+    fn record_not_beneficial(&self, path: &Path, original_bytes: u64, compressed_bytes: u64) {
+        self.record_with(path, original_bytes, compressed_bytes, true);
+    }
+
+    fn record_with(
+        &self,
+        path: &Path,
+        original_bytes: u64,
+        compressed_bytes: u64,
+        not_beneficial: bool,
+    ) {
+        let key = ExtensionStats::key_for(path);
+        let mut by_extension = self.by_extension.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = by_extension
+            .entry(key)
+            .or_insert_with_key(|key| ExtensionStats {
+                extension: key.clone(),
+                ..ExtensionStats::default()
+            });
+        stats.files += 1;
+        stats.original_bytes = stats.original_bytes.saturating_add(original_bytes);
+        stats.compressed_bytes = stats.compressed_bytes.saturating_add(compressed_bytes);
+        if not_beneficial {
+            stats.not_beneficial_files += 1;
+        }
+    }
+
+    fn into_sorted(self) -> Vec<ExtensionStats> {
+        let mut stats: Vec<_> = self
+            .by_extension
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+            .into_values()
+            .collect();
+        stats.sort_by(|a, b| {
+            b.original_bytes
+                .cmp(&a.original_bytes)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+        stats
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CompressionEstimateSource {
     Heuristic,
     CommunityDb,
+    /// Ratios measured by compressing a sample of the game's files.
+    Sampled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +274,12 @@ pub struct CompressionEstimate {
     pub adaptive_applied: bool,
     pub community_samples: Option<u32>,
     pub community_lookup_pending: bool,
+    /// Expected savings range; `estimated_saved_bytes` lies within it.
+    pub estimated_saved_low_bytes: u64,
+    pub estimated_saved_high_bytes: u64,
+    /// 0.0 for a heuristic-only guess up to 1.0. Same-game history scores
+    /// above 0.85, algorithm-wide history 0.2-0.8.
+    pub confidence: f64,
 }
 
 impl CompressionEstimate {
@@ -79,9 +296,9 @@ impl CompressionEstimate {
     }
 }
 
-pub struct CompressionProgressHandle {
+pub struct CompressionProgressHandle<T = CompressionStats> {
     pub progress: Receiver<CompressionProgress>,
-    pub result: Receiver<Result<CompressionStats, CompressionError>>,
+    pub result: Receiver<Result<T, CompressionError>>,
 }
 
 #[derive(Debug, Clone)]
@@ -93,12 +310,14 @@ pub struct ManifestFile {
 #[derive(Debug, Clone)]
 pub struct CancellationToken {
     cancelled: Arc<AtomicBool>,
+    graceful: Arc<AtomicBool>,
 }
 
 impl CancellationToken {
     pub fn new() -> Self {
         Self {
             cancelled: Arc::new(AtomicBool::new(false)),
+            graceful: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -106,12 +325,26 @@ impl CancellationToken {
         self.cancelled.store(true, Ordering::Release);
     }
 
+    /// Stop picking up new files, like [`cancel`](Self::cancel), but have
+    /// compression return the stats of the files that finished, with
+    /// `was_cancelled` set, instead of `CompressionError::Cancelled`.
+    /// Files already being compressed run to completion either way.
+    pub fn cancel_gracefully(&self) {
+        self.graceful.store(true, Ordering::Release);
+        self.cancel();
+    }
+
     pub fn is_cancelled(&self) -> bool {
         self.cancelled.load(Ordering::Acquire)
     }
 
+    pub fn is_graceful(&self) -> bool {
+        self.graceful.load(Ordering::Acquire)
+    }
+
     fn reset(&self) {
         self.cancelled.store(false, Ordering::Release);
+        self.graceful.store(false, Ordering::Release);
     }
 }
 
@@ -121,7 +354,58 @@ impl Default for CancellationToken {
     }
 }
 
+/// How often a parked worker re-checks cancellation.
+const PAUSE_CANCEL_POLL: Duration = Duration::from_millis(250);
+
+/// Shared pause switch. While set, compression and decompression workers
+/// park before starting their next file; files in flight finish first.
+#[derive(Debug, Clone, Default)]
+pub struct PauseHandle {
+    paused: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl PauseHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pause(&self) {
+        *self.paused.0.lock().unwrap_or_else(|e| e.into_inner()) = true;
+    }
+
+    pub fn resume(&self) {
+        let (lock, condvar) = &*self.paused;
+        *lock.lock().unwrap_or_else(|e| e.into_inner()) = false;
+        condvar.notify_all();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Block while paused, returning early once `cancel_token` fires so a
+    /// paused operation can still be cancelled.
+    fn wait_while_paused(&self, cancel_token: &CancellationToken) {
+        let (lock, condvar) = &*self.paused;
+        let mut paused = lock.lock().unwrap_or_else(|e| e.into_inner());
+        while *paused && !cancel_token.is_cancelled() {
+            paused = condvar
+                .wait_timeout(paused, PAUSE_CANCEL_POLL)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
 const MIN_COMPRESSIBLE_SIZE: u64 = 4096;
+/// Encrypted and sparse files listed by path in `CompressionStats`; the
+/// counts cover the rest.
+const MAX_REPORTED_ATTRIBUTE_SKIPS: usize = 100;
+/// Alternate streams below this total are not worth flagging.
+const SIGNIFICANT_ALTERNATE_STREAM_BYTES: u64 = 16 * 1024 * 1024;
+/// Share of the folder's logical size, in percent, alternate streams must
+/// reach to be flagged.
+const SIGNIFICANT_ALTERNATE_STREAM_PERCENT: u64 = 5;
 const USE_ADAPTIVE_ESTIMATION: bool = true;
 
 #[derive(Clone, Default)]
@@ -187,9 +471,16 @@ pub struct CompressionEngine {
     files_total: Arc<AtomicU64>,
     bytes_original: Arc<AtomicU64>,
     bytes_compressed: Arc<AtomicU64>,
+    /// Logical size of every manifest file, processed or skipped.
+    bytes_total: Arc<AtomicU64>,
+    bytes_processed: Arc<AtomicU64>,
     safety: Option<SafetyConfig>,
     directstorage_policy: DirectStoragePolicy,
     thread_policy: Option<ThreadPolicy>,
+    traversal_policy: TraversalPolicy,
+    backup_privilege: bool,
+    pause: PauseHandle,
+    rate_limit: Option<Arc<RateLimiter>>,
 }
 
 impl CompressionEngine {
@@ -202,9 +493,15 @@ impl CompressionEngine {
             files_total: Arc::new(AtomicU64::new(0)),
             bytes_original: Arc::new(AtomicU64::new(0)),
             bytes_compressed: Arc::new(AtomicU64::new(0)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
             safety: None,
             directstorage_policy: DirectStoragePolicy::Block,
             thread_policy: None,
+            traversal_policy: TraversalPolicy::default(),
+            backup_privilege: false,
+            pause: PauseHandle::new(),
+            rate_limit: None,
         }
     }
 
@@ -213,15 +510,71 @@ impl CompressionEngine {
         self
     }
 
+    /// Traversal policy for estimates. Compression, decompression and
+    /// ratio queries always skip reparse points so WOF writes never leave
+    /// the selected folder.
+    pub fn with_traversal_policy(mut self, policy: TraversalPolicy) -> Self {
+        self.traversal_policy = policy;
+        self
+    }
+
+    /// Retry files whose ACLs deny access through backup semantics. Takes
+    /// effect only when the process can enable backup and restore
+    /// privileges (an elevated app or the daemon service); otherwise
+    /// those files are skipped and counted in `files_skipped_permission`.
+    pub fn with_backup_privilege(mut self, enabled: bool) -> Self {
+        self.backup_privilege = enabled;
+        self
+    }
+
     pub fn with_cancel_token(mut self, token: CancellationToken) -> Self {
         self.cancel_token = token;
         self
     }
 
+    /// Share a pause switch with whoever needs to pause this engine
+    /// before it exists, such as the automation loop.
+    pub fn with_pause_handle(mut self, pause: PauseHandle) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Cap compression throughput across all workers at `bytes_per_sec`
+    /// (at least `rate_limit::MIN_BYTES_PER_SEC`); `None` runs unthrottled.
+    pub fn with_rate_limit(mut self, bytes_per_sec: Option<u64>) -> Self {
+        self.rate_limit = bytes_per_sec.map(|rate| Arc::new(RateLimiter::new(rate)));
+        self
+    }
+
+    pub fn pause_handle(&self) -> PauseHandle {
+        self.pause.clone()
+    }
+
+    /// Park workers between files until [`resume`](Self::resume). The
+    /// pause is lifted when the operation ends.
+    pub fn pause(&self) {
+        self.pause.pause();
+    }
+
+    pub fn resume(&self) {
+        self.pause.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause.is_paused()
+    }
+
     pub fn thread_policy(&self) -> Option<&ThreadPolicy> {
         self.thread_policy.as_ref()
     }
 
+    /// The configured policy, or the storage-based one for `folder` so an
+    /// engine without a policy still sizes its pool to the disk.
+    fn effective_thread_policy(&self, folder: &Path) -> ThreadPolicy {
+        self.thread_policy
+            .unwrap_or_else(|| compute_thread_policy(folder, false, None, None))
+    }
+
     pub fn cancel_token(&self) -> CancellationToken {
         self.cancel_token.clone()
     }
@@ -241,6 +594,8 @@ impl CompressionEngine {
             files_total: self.files_total.clone(),
             bytes_original: self.bytes_original.clone(),
             bytes_compressed: self.bytes_compressed.clone(),
+            bytes_total: self.bytes_total.clone(),
+            bytes_processed: self.bytes_processed.clone(),
         }
     }
 
@@ -249,6 +604,18 @@ impl CompressionEngine {
         self.files_total.store(0, Ordering::Relaxed);
         self.bytes_original.store(0, Ordering::Relaxed);
         self.bytes_compressed.store(0, Ordering::Relaxed);
+        self.bytes_total.store(0, Ordering::Relaxed);
+        self.bytes_processed.store(0, Ordering::Relaxed);
+    }
+
+    fn set_totals<'a>(&self, manifests: impl IntoIterator<Item = &'a [ManifestFile]>) {
+        let (mut files, mut bytes) = (0_u64, 0_u64);
+        for manifest in manifests {
+            files += manifest.len() as u64;
+            bytes += estimation_runtime::manifest_total_size(manifest).unwrap_or(0);
+        }
+        self.files_total.store(files, Ordering::Relaxed);
+        self.bytes_total.store(bytes, Ordering::Relaxed);
     }
 
     fn operation_guard(&self) -> OperationGuard {
@@ -264,17 +631,38 @@ impl CompressionEngine {
         OperationSession::new(self)
     }
 
+    /// The file attribute behind a skipped file's error, if that is why.
+    fn skipped_attribute(error: &CompressionError) -> Option<IncompatibleAttribute> {
+        match error {
+            CompressionError::EncryptedFile { .. } => Some(IncompatibleAttribute::Encrypted),
+            CompressionError::SparseFile { .. } => Some(IncompatibleAttribute::Sparse),
+            _ => None,
+        }
+    }
+
     fn is_recoverable_file_error(error: &CompressionError) -> bool {
         matches!(
             error,
-            CompressionError::LockedFile { .. } | CompressionError::PermissionDenied { .. }
+            CompressionError::LockedFile { .. }
+                | CompressionError::PermissionDenied { .. }
+                | CompressionError::FileTooSmallForWof { .. }
+                | CompressionError::EncryptedFile { .. }
+                | CompressionError::SparseFile { .. }
+                | CompressionError::IntegrityStreamFile { .. }
         )
     }
 
     pub fn compress_folder(&self, folder: &Path) -> Result<CompressionStats, CompressionError> {
         self.validate_path(folder)?;
         run_safety_checks(folder, self.directstorage_policy, self.safety.as_ref())?;
+        let _folder_lock = lock_game_folder(folder)?;
         let _operation = self.begin_operation();
+        let largest_file = Self::file_iter(folder)?
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .max()
+            .unwrap_or(0);
+        let _reservation = reserve_space(folder, largest_file)?;
         self.compress_impl(folder)
     }
 
@@ -316,19 +704,111 @@ impl CompressionEngine {
         game_name: Arc<str>,
         file_manifest: Vec<ManifestFile>,
     ) -> Result<CompressionProgressHandle, CompressionError> {
-        self.validate_path(folder)?;
-        run_safety_checks(folder, self.directstorage_policy, self.safety.as_ref())?;
-        let engine = self.clone();
+        self.compress_roots_with_progress(vec![(folder.to_path_buf(), file_manifest)], game_name)
+    }
+
+    /// Compress several folders, such as a game and its DLC on another
+    /// drive, as one operation. Stats are combined across folders.
+    pub fn compress_folders(
+        &self,
+        folders: Vec<PathBuf>,
+    ) -> Result<CompressionStats, CompressionError> {
+        let roots = self.folder_manifests(folders)?;
+        let (_folder_locks, _operation, _reservations) = self.prepare_compression(&roots)?;
+        self.compress_impl_from_manifests(roots)
+    }
+
+    /// [`Self::compress_folders`] with one progress stream covering every
+    /// folder.
+    pub fn compress_folders_with_progress(
+        &self,
+        folders: Vec<PathBuf>,
+        game_name: Arc<str>,
+    ) -> Result<CompressionProgressHandle, CompressionError> {
+        let roots = self.folder_manifests(folders)?;
+        self.compress_roots_with_progress(roots, game_name)
+    }
+
+    /// Manifests for `folders`, dropping duplicates and folders nested in
+    /// another listed folder so no file is compressed twice.
+    fn folder_manifests(
+        &self,
+        folders: Vec<PathBuf>,
+    ) -> Result<Vec<(PathBuf, Vec<ManifestFile>)>, CompressionError> {
+        let mut canonical = Vec::with_capacity(folders.len());
+        for folder in folders {
+            self.validate_path(&folder)?;
+            let root =
+                std::fs::canonicalize(&folder).map_err(|source| CompressionError::Io { source })?;
+            canonical.push((folder, root));
+        }
+
+        let mut roots = Vec::with_capacity(canonical.len());
+        for (index, (folder, root)) in canonical.iter().enumerate() {
+            let covered = canonical
+                .iter()
+                .enumerate()
+                .any(|(other_index, (_, other))| {
+                    root.starts_with(other) && (root != other || other_index < index)
+                });
+            if covered {
+                log::info!(
+                    "Skipping {}: already covered by another folder in this run",
+                    folder.display()
+                );
+                continue;
+            }
+            roots.push((folder.clone(), self.build_file_manifest(folder)?));
+        }
+        Ok(roots)
+    }
+
+    /// Safety checks, folder locks, the operation guard and free-space
+    /// reservations for compressing `roots`.
+    fn prepare_compression(
+        &self,
+        roots: &[(PathBuf, Vec<ManifestFile>)],
+    ) -> Result<(Vec<GameFolderLock>, OperationSession, Vec<SpaceReservation>), CompressionError>
+    {
+        for (folder, _) in roots {
+            self.validate_path(folder)?;
+            run_safety_checks(folder, self.directstorage_policy, self.safety.as_ref())?;
+        }
+        let folder_locks = roots
+            .iter()
+            .map(|(folder, _)| lock_game_folder(folder))
+            .collect::<Result<Vec<_>, _>>()?;
         let operation = self.begin_operation();
-        let folder = folder.to_path_buf();
-        let files_total = file_manifest.len() as u64;
+        let reservations = roots
+            .iter()
+            .map(|(folder, files)| {
+                let largest_file = files
+                    .iter()
+                    .filter_map(|file| file.logical_size_hint)
+                    .max()
+                    .unwrap_or(0);
+                reserve_space(folder, largest_file)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((folder_locks, operation, reservations))
+    }
+
+    fn compress_roots_with_progress(
+        &self,
+        roots: Vec<(PathBuf, Vec<ManifestFile>)>,
+        game_name: Arc<str>,
+    ) -> Result<CompressionProgressHandle, CompressionError> {
+        let engine = self.clone();
+        let (folder_locks, operation, reservations) = self.prepare_compression(&roots)?;
 
         let (progress_ready_tx, progress_ready_rx) = bounded(1);
         let (result_tx, result_rx) = bounded(1);
 
-        self.files_total.store(files_total, Ordering::Relaxed);
+        self.set_totals(roots.iter().map(|(_, files)| files.as_slice()));
         std::thread::spawn(move || {
+            let _folder_locks = folder_locks;
             let _operation = operation;
+            let _reservations = reservations;
 
             let counters = engine.engine_counters();
             let (mut reporter, progress_rx) =
@@ -339,7 +819,7 @@ impl CompressionEngine {
                 return;
             }
 
-            let result = engine.compress_impl_from_manifest(&folder, file_manifest);
+            let result = engine.compress_impl_from_manifests(roots);
 
             reporter.mark_done();
             reporter.stop();
@@ -364,7 +844,7 @@ impl CompressionEngine {
         &self,
         folder: &Path,
         game_name: Arc<str>,
-    ) -> Result<CompressionProgressHandle, CompressionError> {
+    ) -> Result<CompressionProgressHandle<DecompressionStats>, CompressionError> {
         let file_manifest = self.build_file_manifest(folder)?;
         self.decompress_folder_with_progress_with_manifest(folder, game_name, file_manifest)
     }
@@ -374,19 +854,20 @@ impl CompressionEngine {
         folder: &Path,
         game_name: Arc<str>,
         file_manifest: Vec<ManifestFile>,
-    ) -> Result<CompressionProgressHandle, CompressionError> {
+    ) -> Result<CompressionProgressHandle<DecompressionStats>, CompressionError> {
         self.validate_path(folder)?;
         run_process_safety_check(folder, self.safety.as_ref())?;
+        let folder_lock = lock_game_folder(folder)?;
         let engine = self.clone();
         let operation = self.begin_operation();
         let folder = folder.to_path_buf();
-        let files_total = file_manifest.len() as u64;
 
         let (progress_ready_tx, progress_ready_rx) = bounded(1);
         let (result_tx, result_rx) = bounded(1);
 
-        self.files_total.store(files_total, Ordering::Relaxed);
+        self.set_totals([file_manifest.as_slice()]);
         std::thread::spawn(move || {
+            let _folder_lock = folder_lock;
             let _operation = operation;
 
             let counters = engine.engine_counters();
@@ -398,10 +879,7 @@ impl CompressionEngine {
                 return;
             }
 
-            let start = std::time::Instant::now();
-            let result = engine
-                .decompress_impl_from_manifest(&folder, file_manifest)
-                .map(|()| engine.current_stats(start.elapsed().as_millis() as u64));
+            let result = engine.decompress_impl_from_manifest(&folder, file_manifest);
 
             reporter.mark_done();
             reporter.stop();
@@ -422,9 +900,10 @@ impl CompressionEngine {
         })
     }
 
-    pub fn decompress_folder(&self, folder: &Path) -> Result<(), CompressionError> {
+    pub fn decompress_folder(&self, folder: &Path) -> Result<DecompressionStats, CompressionError> {
         self.validate_path(folder)?;
         run_process_safety_check(folder, self.safety.as_ref())?;
+        let _folder_lock = lock_game_folder(folder)?;
         let _operation = self.begin_operation();
         self.decompress_impl(folder)
     }
@@ -444,22 +923,19 @@ impl CompressionEngine {
         }
     }
 
-    fn current_stats(&self, duration_ms: u64) -> CompressionStats {
-        CompressionStats {
-            original_bytes: self.bytes_original.load(Ordering::Relaxed),
-            compressed_bytes: self.bytes_compressed.load(Ordering::Relaxed),
-            files_processed: self.files_processed.load(Ordering::Relaxed),
-            files_skipped: 0,
-            duration_ms,
-        }
+    fn file_iter(
+        folder: &Path,
+    ) -> Result<impl Iterator<Item = walkdir::DirEntry> + '_, CompressionError> {
+        Self::file_iter_with_policy(folder, TraversalPolicy::SkipReparsePoints)
     }
 
-    fn file_iter(
+    fn file_iter_with_policy(
         folder: &Path,
+        policy: TraversalPolicy,
     ) -> Result<impl Iterator<Item = walkdir::DirEntry> + '_, CompressionError> {
         let canonical_root =
             std::fs::canonicalize(folder).map_err(|source| CompressionError::Io { source })?;
-        Ok(safe_file_iter(folder, canonical_root))
+        Ok(safe_file_iter(folder, canonical_root, policy))
     }
 
     #[cfg(not(windows))]
@@ -470,10 +946,9 @@ impl CompressionEngine {
     }
 
     #[cfg(not(windows))]
-    fn compress_impl_from_manifest(
+    fn compress_impl_from_manifests(
         &self,
-        _folder: &Path,
-        _files: Vec<ManifestFile>,
+        _roots: Vec<(PathBuf, Vec<ManifestFile>)>,
     ) -> Result<CompressionStats, CompressionError> {
         Err(CompressionError::WofApiError {
             message: "WOF compression requires Windows".into(),
@@ -481,7 +956,7 @@ impl CompressionEngine {
     }
 
     #[cfg(not(windows))]
-    fn decompress_impl(&self, _folder: &Path) -> Result<(), CompressionError> {
+    fn decompress_impl(&self, _folder: &Path) -> Result<DecompressionStats, CompressionError> {
         Err(CompressionError::WofApiError {
             message: "WOF decompression requires Windows".into(),
         })
@@ -492,7 +967,7 @@ impl CompressionEngine {
         &self,
         _folder: &Path,
         _files: Vec<ManifestFile>,
-    ) -> Result<(), CompressionError> {
+    ) -> Result<DecompressionStats, CompressionError> {
         Err(CompressionError::WofApiError {
             message: "WOF decompression requires Windows".into(),
         })
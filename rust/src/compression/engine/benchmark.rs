@@ -0,0 +1,220 @@
+//! Algorithm benchmark: compresses temp copies of one sample of a game
+//! with every WOF algorithm and compares savings against speed. The game's
+//! own files are only read, so nothing needs restoring afterwards.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::sampling::{plan_samples, sample_budget};
+use super::{CompressionEngine, CompressionError, MIN_COMPRESSIBLE_SIZE};
+use crate::compression::algorithm::CompressionAlgorithm;
+
+/// Every algorithm compresses the full sample, so it is capped well below
+/// the sampling estimator's to keep LZX under a minute on slow CPUs.
+const MAX_BENCHMARK_SAMPLE_BYTES: u64 = 128 * 1024 * 1024;
+/// Fastest first; the order `recommend` walks.
+const ALGORITHMS: [CompressionAlgorithm; 4] = [
+    CompressionAlgorithm::Xpress4K,
+    CompressionAlgorithm::Xpress8K,
+    CompressionAlgorithm::Xpress16K,
+    CompressionAlgorithm::Lzx,
+];
+/// A slower algorithm must save at least this much more of the sample to
+/// be worth recommending.
+const MIN_RECOMMENDED_GAIN: f64 = 0.02;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlgorithmBenchmark {
+    pub algorithm: CompressionAlgorithm,
+    pub compressed_bytes: u64,
+    /// Fraction of the sample saved, 0.0 to 1.0.
+    pub savings_ratio: f64,
+    /// Sample bytes compressed per second of WOF time.
+    pub throughput_bytes_per_sec: u64,
+    pub duration_ms: u64,
+    /// `savings_ratio` applied to the game's compressible bytes.
+    pub projected_saved_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub sampled_files: u64,
+    pub sample_bytes: u64,
+    pub compressible_bytes: u64,
+    /// In [`ALGORITHMS`] order.
+    pub results: Vec<AlgorithmBenchmark>,
+    /// `None` when nothing in the sample compressed.
+    pub recommended: Option<CompressionAlgorithm>,
+}
+
+impl CompressionEngine {
+    /// Compress copies of a stratified sample of `folder` with each
+    /// algorithm in turn. Takes seconds to a minute; the engine's cancel
+    /// token stops it between files.
+    pub fn benchmark_folder(&self, folder: &Path) -> Result<BenchmarkReport, CompressionError> {
+        self.validate_path(folder)?;
+
+        let mut candidates: Vec<(PathBuf, u64)> = Vec::new();
+        let mut compressible_bytes = 0_u64;
+        for entry in Self::file_iter_with_policy(folder, self.traversal_policy)? {
+            if self.cancel_token.is_cancelled() {
+                return Err(CompressionError::Cancelled);
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let file_size = metadata.len();
+            if file_size < MIN_COMPRESSIBLE_SIZE {
+                continue;
+            }
+            compressible_bytes = compressible_bytes.saturating_add(file_size);
+            if !crate::safety::cloud::is_cloud_placeholder(entry.path()) {
+                candidates.push((entry.path().to_path_buf(), file_size));
+            }
+        }
+
+        let budget = sample_budget(compressible_bytes).min(MAX_BENCHMARK_SAMPLE_BYTES);
+        let picks = plan_samples(&candidates, budget);
+        let mut results = Vec::with_capacity(ALGORITHMS.len());
+        let mut sample_bytes = 0_u64;
+        for algorithm in ALGORITHMS {
+            let measured = self.measure_samples(algorithm, &picks)?;
+            let (original, compressed) =
+                measured
+                    .extensions
+                    .iter()
+                    .fold((0_u64, 0_u64), |(original, compressed), stats| {
+                        (
+                            original.saturating_add(stats.original_bytes),
+                            compressed.saturating_add(stats.compressed_bytes),
+                        )
+                    });
+            sample_bytes = sample_bytes.max(original);
+            results.push(algorithm_result(
+                algorithm,
+                original,
+                compressed,
+                measured.compress_time,
+                compressible_bytes,
+            ));
+        }
+        log::info!(
+            "Benchmarked {} ({} sample files, {sample_bytes} bytes)",
+            folder.display(),
+            picks.len()
+        );
+
+        Ok(BenchmarkReport {
+            sampled_files: picks.len() as u64,
+            sample_bytes,
+            compressible_bytes,
+            recommended: recommend(&results),
+            results,
+        })
+    }
+}
+
+fn algorithm_result(
+    algorithm: CompressionAlgorithm,
+    original_bytes: u64,
+    compressed_bytes: u64,
+    compress_time: std::time::Duration,
+    compressible_bytes: u64,
+) -> AlgorithmBenchmark {
+    let savings_ratio = if original_bytes == 0 {
+        0.0
+    } else {
+        1.0 - compressed_bytes.min(original_bytes) as f64 / original_bytes as f64
+    };
+    let seconds = compress_time.as_secs_f64();
+    AlgorithmBenchmark {
+        algorithm,
+        compressed_bytes,
+        savings_ratio,
+        throughput_bytes_per_sec: if seconds > 0.0 {
+            (original_bytes as f64 / seconds) as u64
+        } else {
+            0
+        },
+        duration_ms: compress_time.as_millis() as u64,
+        projected_saved_bytes: (compressible_bytes as f64 * savings_ratio) as u64,
+    }
+}
+
+/// The fastest algorithm that no slower one beats by
+/// [`MIN_RECOMMENDED_GAIN`]. Slower algorithms also cost CPU on every
+/// read, so a small extra saving does not justify them.
+fn recommend(results: &[AlgorithmBenchmark]) -> Option<CompressionAlgorithm> {
+    let mut best: Option<&AlgorithmBenchmark> = None;
+    for result in results {
+        if result.savings_ratio <= 0.0 {
+            continue;
+        }
+        match best {
+            Some(current)
+                if result.savings_ratio < current.savings_ratio + MIN_RECOMMENDED_GAIN => {}
+            _ => best = Some(result),
+        }
+    }
+    best.map(|result| result.algorithm)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn result(algorithm: CompressionAlgorithm, savings_ratio: f64) -> AlgorithmBenchmark {
+        AlgorithmBenchmark {
+            algorithm,
+            compressed_bytes: 0,
+            savings_ratio,
+            throughput_bytes_per_sec: 0,
+            duration_ms: 0,
+            projected_saved_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn result_projects_sample_ratio_onto_game() {
+        let result = algorithm_result(
+            CompressionAlgorithm::Xpress8K,
+            100 * MIB,
+            60 * MIB,
+            Duration::from_millis(500),
+            10_000 * MIB,
+        );
+        assert!((result.savings_ratio - 0.4).abs() < 1e-9);
+        assert_eq!(result.throughput_bytes_per_sec, 200 * MIB);
+        assert_eq!(result.projected_saved_bytes, 4_000 * MIB);
+    }
+
+    #[test]
+    fn recommends_slower_algorithm_only_for_a_real_gain() {
+        let close = [
+            result(CompressionAlgorithm::Xpress4K, 0.30),
+            result(CompressionAlgorithm::Xpress8K, 0.31),
+            result(CompressionAlgorithm::Xpress16K, 0.315),
+            result(CompressionAlgorithm::Lzx, 0.317),
+        ];
+        assert_eq!(recommend(&close), Some(CompressionAlgorithm::Xpress4K));
+
+        let lzx_wins = [
+            result(CompressionAlgorithm::Xpress4K, 0.20),
+            result(CompressionAlgorithm::Xpress8K, 0.23),
+            result(CompressionAlgorithm::Xpress16K, 0.24),
+            result(CompressionAlgorithm::Lzx, 0.32),
+        ];
+        assert_eq!(recommend(&lzx_wins), Some(CompressionAlgorithm::Lzx));
+
+        let incompressible = [
+            result(CompressionAlgorithm::Xpress4K, 0.0),
+            result(CompressionAlgorithm::Lzx, 0.0),
+        ];
+        assert_eq!(recommend(&incompressible), None);
+    }
+}
@@ -4,7 +4,9 @@ use std::sync::Arc;
 use super::CompressionEngine;
 use crate::compression::error::CompressionError;
 use crate::safety::directstorage::is_directstorage_game;
+use crate::safety::filesystem::fs_capabilities;
 use crate::safety::process::ProcessChecker;
+use crate::safety::uwp::is_protected_package;
 
 #[derive(Clone)]
 /// Optional runtime safety integrations used before compression starts.
@@ -23,6 +25,22 @@ pub(super) fn run_safety_checks(
     directstorage_policy: DirectStoragePolicy,
     safety: Option<&SafetyConfig>,
 ) -> Result<(), CompressionError> {
+    let capabilities = fs_capabilities(folder);
+    if !capabilities.supports_wof {
+        return Err(CompressionError::UnsupportedFilesystem {
+            path: folder.to_path_buf(),
+            filesystem: capabilities.filesystem.label().to_string(),
+        });
+    }
+
+    // Unlike DirectStorage there is no override: a modified package fails
+    // signature validation and has to be repaired from the Xbox app.
+    if is_protected_package(folder) {
+        return Err(CompressionError::ProtectedPackage {
+            path: folder.to_path_buf(),
+        });
+    }
+
     if is_directstorage_game(folder) {
         if directstorage_policy == DirectStoragePolicy::Block {
             return Err(CompressionError::DirectStorageDetected);
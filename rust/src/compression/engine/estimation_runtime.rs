@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::Path;
 
 use rayon::iter::ParallelBridge;
@@ -6,10 +7,19 @@ use rayon::prelude::*;
 use super::estimation;
 use super::{
     CompressionEngine, CompressionError, CompressionEstimate, CompressionEstimateSource,
-    EstimateCandidate, EstimateTotals, ManifestFile, MIN_COMPRESSIBLE_SIZE,
+    EstimateCandidate, EstimateTotals, ExtensionStats, ManifestFile, MIN_COMPRESSIBLE_SIZE,
     USE_ADAPTIVE_ESTIMATION,
 };
 use crate::compression::community_db::{self, CommunityLookup, GameLookupContext};
+use crate::compression::history::adaptive::HEURISTIC_SPREAD;
+use crate::compression::history::skip_list;
+
+/// Community ratios are measured on the same title, so their range is
+/// tighter than the per-extension heuristic.
+const COMMUNITY_SPREAD: f64 = 0.2;
+/// Sample count at which a community ratio reaches `COMMUNITY_MAX_CONFIDENCE`.
+const COMMUNITY_FULL_CONFIDENCE_SAMPLES: f64 = 50.0;
+const COMMUNITY_MAX_CONFIDENCE: f64 = 0.7;
 
 #[derive(Debug, Clone, Copy)]
 pub struct EstimateGameContext<'a> {
@@ -22,6 +32,8 @@ pub struct EstimateGameContext<'a> {
 struct AdaptiveFactors {
     correction_factor: f64,
     confidence: f64,
+    /// Relative half-width of the savings range.
+    relative_spread: f64,
 }
 
 impl AdaptiveFactors {
@@ -29,6 +41,7 @@ impl AdaptiveFactors {
         Self {
             correction_factor: 1.0,
             confidence: 0.0,
+            relative_spread: HEURISTIC_SPREAD,
         }
     }
 
@@ -78,7 +91,12 @@ impl CompressionEngine {
     ) -> Result<CompressionEstimate, CompressionError> {
         self.validate_path(folder)?;
         let factors = self.compute_adaptive_factors(folder);
-        self.estimate_folder_savings_with_manifest_and_factors(file_manifest, factors, false)
+        self.estimate_folder_savings_with_manifest_and_factors(
+            folder,
+            file_manifest,
+            factors,
+            false,
+        )
     }
 
     pub fn estimate_folder_savings_with_manifest_and_context(
@@ -101,6 +119,7 @@ impl CompressionEngine {
             }
         }
         self.estimate_folder_savings_with_manifest_and_factors(
+            folder,
             file_manifest,
             factors,
             community_lookup_pending,
@@ -113,15 +132,25 @@ impl CompressionEngine {
         }
         let history = crate::compression::history::get_historical_stats();
         let estimator =
-            crate::compression::history::adaptive::AdaptiveEstimator::from_history(history);
+            crate::compression::history::adaptive::AdaptiveEstimator::from_history(history)
+                .with_aggregates(crate::compression::history::retention::aggregates().games);
         let (correction_factor, confidence) =
             estimator.get_correction_factor_for_game(self.algorithm, folder);
         AdaptiveFactors {
             correction_factor,
             confidence,
+            relative_spread: estimator.relative_spread_for_game(self.algorithm, folder),
         }
     }
 
+    /// Extensions this game's history says compression skips.
+    pub(super) fn learned_skip_extensions(&self, folder: &Path) -> HashSet<String> {
+        if !USE_ADAPTIVE_ESTIMATION {
+            return HashSet::new();
+        }
+        skip_list::learned_skip_extensions(folder, self.algorithm)
+    }
+
     fn estimate_folder_savings_with_factors(
         &self,
         folder: &Path,
@@ -129,7 +158,11 @@ impl CompressionEngine {
         community_lookup_pending: bool,
     ) -> Result<CompressionEstimate, CompressionError> {
         let algorithm_scale_num = estimation::algorithm_scale_num(self.algorithm);
+        let skip_extensions = self.learned_skip_extensions(folder);
         let totals = self.estimate_totals_parallel(folder, |path, file_size| {
+            if skip_extensions.contains(&ExtensionStats::key_for(path)) {
+                return 0;
+            }
             saved_for_file_with_factors(
                 self.algorithm,
                 algorithm_scale_num,
@@ -147,12 +180,17 @@ impl CompressionEngine {
 
     fn estimate_folder_savings_with_manifest_and_factors(
         &self,
+        folder: &Path,
         file_manifest: &[ManifestFile],
         factors: AdaptiveFactors,
         community_lookup_pending: bool,
     ) -> Result<CompressionEstimate, CompressionError> {
         let algorithm_scale_num = estimation::algorithm_scale_num(self.algorithm);
+        let skip_extensions = self.learned_skip_extensions(folder);
         let totals = self.estimate_totals_from_manifest(file_manifest, |path, file_size| {
+            if skip_extensions.contains(&ExtensionStats::key_for(path)) {
+                return 0;
+            }
             saved_for_file_with_factors(
                 self.algorithm,
                 algorithm_scale_num,
@@ -202,6 +240,12 @@ impl CompressionEngine {
             factors.confidence,
         );
 
+        let (estimated_saved_low_bytes, estimated_saved_high_bytes) = savings_range(
+            estimated_saved_bytes,
+            size_bytes,
+            factors.relative_spread.min(COMMUNITY_SPREAD),
+        );
+
         CommunityEstimateLookup::Hit(CompressionEstimate {
             scanned_files: 0,
             sampled_bytes: size_bytes,
@@ -211,6 +255,11 @@ impl CompressionEngine {
             adaptive_applied: factors.applied(),
             community_samples: Some(community.samples),
             community_lookup_pending: false,
+            estimated_saved_low_bytes,
+            estimated_saved_high_bytes,
+            confidence: factors
+                .confidence
+                .max(community_confidence(community.samples)),
         })
     }
 
@@ -241,7 +290,7 @@ impl CompressionEngine {
     where
         F: Fn(&Path, u64) -> u64 + Sync,
     {
-        let totals = Self::file_iter(folder)?
+        let totals = Self::file_iter_with_policy(folder, self.traversal_policy)?
             .par_bridge()
             .map(|entry| {
                 if self.cancel_token.is_cancelled() {
@@ -368,6 +417,11 @@ fn heuristic_estimate(
     factors: AdaptiveFactors,
     community_lookup_pending: bool,
 ) -> CompressionEstimate {
+    let (estimated_saved_low_bytes, estimated_saved_high_bytes) = savings_range(
+        totals.estimated_saved_bytes,
+        totals.sampled_bytes,
+        factors.relative_spread,
+    );
     CompressionEstimate {
         scanned_files: totals.scanned_files,
         sampled_bytes: totals.sampled_bytes,
@@ -377,10 +431,25 @@ fn heuristic_estimate(
         adaptive_applied: factors.applied(),
         community_samples: None,
         community_lookup_pending,
+        estimated_saved_low_bytes,
+        estimated_saved_high_bytes,
+        confidence: factors.confidence,
     }
 }
 
-fn manifest_total_size(file_manifest: &[ManifestFile]) -> Option<u64> {
+/// `saved` widened by `relative_spread` either way; the upper bound never
+/// exceeds the bytes that could be saved at all.
+pub(super) fn savings_range(saved: u64, total_bytes: u64, relative_spread: f64) -> (u64, u64) {
+    let low = (saved as f64 * (1.0 - relative_spread)).max(0.0) as u64;
+    let high = ((saved as f64 * (1.0 + relative_spread)) as u64).min(total_bytes.max(saved));
+    (low, high)
+}
+
+fn community_confidence(samples: u32) -> f64 {
+    (f64::from(samples) / COMMUNITY_FULL_CONFIDENCE_SAMPLES).min(1.0) * COMMUNITY_MAX_CONFIDENCE
+}
+
+pub(super) fn manifest_total_size(file_manifest: &[ManifestFile]) -> Option<u64> {
     let mut total = 0_u64;
     let mut known = 0_usize;
     for file in file_manifest {
@@ -397,7 +466,7 @@ fn adaptive_applied(correction_factor: f64, confidence: f64) -> bool {
         && (correction_factor < 1.0 || confidence > f64::EPSILON)
 }
 
-fn executable_score(path: &Path, file_size: u64) -> Option<u16> {
+pub(super) fn executable_score(path: &Path, file_size: u64) -> Option<u16> {
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
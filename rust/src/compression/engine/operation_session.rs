@@ -1,6 +1,6 @@
 use std::sync::{Arc, Condvar, Mutex};
 
-use super::{CancellationToken, CompressionEngine};
+use super::{CancellationToken, CompressionEngine, PauseHandle};
 
 #[derive(Debug)]
 pub(super) struct OperationLock {
@@ -103,6 +103,7 @@ impl Drop for OperationGuard {
 pub(super) struct OperationSession {
     _guard: OperationGuard,
     cancel_token: CancellationToken,
+    pause: PauseHandle,
 }
 
 impl OperationSession {
@@ -112,6 +113,7 @@ impl OperationSession {
         Self {
             _guard: guard,
             cancel_token: engine.cancel_token.clone(),
+            pause: engine.pause.clone(),
         }
     }
 }
@@ -122,5 +124,6 @@ impl Drop for OperationSession {
         // with a clean slate. The OperationGuard serializes operations, so
         // there is no race between Drop and a new caller's cancel().
         self.cancel_token.reset();
+        self.pause.resume();
     }
 }
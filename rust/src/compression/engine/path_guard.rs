@@ -3,10 +3,16 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
-use walkdir::{DirEntry, WalkDir};
+use walkdir::DirEntry;
+
+use crate::safety::traversal::TraversalPolicy;
 
 /// Returns an iterator over safe-to-compress files under `folder`.
 ///
+/// Under [`TraversalPolicy::FollowReparsePoints`] files reached through a
+/// link are yielded even though they live outside `canonical_root`; only
+/// read-only callers (estimation) may opt in to that.
+///
 /// **Thread-safety note:** This iterator is consumed by a single thread
 /// (even when fed to `par_bridge()`, which pulls sequentially). The parent
 /// path cache uses `Mutex` rather than `RefCell` so this remains sound if
@@ -14,12 +20,12 @@ use walkdir::{DirEntry, WalkDir};
 pub(super) fn safe_file_iter(
     folder: &Path,
     canonical_root: PathBuf,
+    policy: TraversalPolicy,
 ) -> impl Iterator<Item = DirEntry> + '_ {
     let parent_path_safety_cache = Mutex::new(HashMap::<PathBuf, bool>::new());
 
-    WalkDir::new(folder)
-        .follow_links(false)
-        .into_iter()
+    policy
+        .walk(folder)
         .filter_map(|entry| match entry {
             Ok(entry) => Some(entry),
             Err(e) => {
@@ -31,14 +37,21 @@ pub(super) fn safe_file_iter(
             }
         })
         .filter(|entry| entry.file_type().is_file())
-        .filter(move |entry| is_safe_file_entry(entry, &canonical_root, &parent_path_safety_cache))
+        .filter(move |entry| {
+            is_safe_file_entry(entry, &canonical_root, &parent_path_safety_cache, policy)
+        })
 }
 
 fn is_safe_file_entry(
     entry: &DirEntry,
     canonical_root: &Path,
     parent_cache: &Mutex<HashMap<PathBuf, bool>>,
+    policy: TraversalPolicy,
 ) -> bool {
+    if policy.follows_reparse_points() {
+        return entry.metadata().is_ok();
+    }
+
     if entry.path_is_symlink() {
         log::warn!(
             "Skipping symlink entry in compression path scan: {}",
@@ -0,0 +1,437 @@
+//! Sampling estimator: WOF-compresses temp copies of a stratified sample of
+//! a game's files and extrapolates the measured per-extension ratios to the
+//! whole folder.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::estimation;
+use super::estimation_runtime::{executable_score, savings_range};
+use super::{
+    select_best_candidate, CompressionEngine, CompressionError, CompressionEstimate,
+    CompressionEstimateSource, EstimateCandidate, ExtensionStats, MIN_COMPRESSIBLE_SIZE,
+};
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::history::adaptive::HEURISTIC_SPREAD;
+
+/// The sample is 1% of the compressible bytes, within the bounds below.
+const SAMPLE_FRACTION_DENOMINATOR: u64 = 100;
+const MIN_SAMPLE_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_SAMPLE_BYTES: u64 = 500 * 1024 * 1024;
+/// Large files contribute a prefix so one archive can't use the whole budget.
+const MAX_BYTES_PER_FILE: u64 = 32 * 1024 * 1024;
+/// Every extension gets at least this much while budget remains, so small
+/// strata are measured instead of falling back to the heuristic.
+const MIN_STRATUM_BYTES: u64 = 1024 * 1024;
+/// Measured ratios still vary between the sample and the rest of the game.
+const SAMPLED_SPREAD: f64 = 0.1;
+const SAMPLED_CONFIDENCE: f64 = 0.75;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SamplePick {
+    pub(super) path: PathBuf,
+    /// Bytes to copy from the start of the file.
+    pub(super) bytes: u64,
+}
+
+/// Result of compressing one set of sample copies.
+#[derive(Debug, Clone, Default)]
+pub(super) struct SampleMeasurement {
+    pub(super) extensions: Vec<ExtensionStats>,
+    /// Time spent inside WOF compression, excluding the copies.
+    pub(super) compress_time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct StratumTotals {
+    bytes: u64,
+    heuristic_saved_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Extrapolation {
+    saved_bytes: u64,
+    /// Bytes whose savings come from a measured ratio.
+    measured_bytes: u64,
+}
+
+impl CompressionEngine {
+    /// Estimate by WOF-compressing temp copies of a stratified sample (1% of
+    /// the folder, 16-500 MiB) and extrapolating the measured ratio of each
+    /// extension. Slower than the heuristic estimators but measures this
+    /// game's data, which matters most for games without history.
+    pub fn estimate_folder_savings_sampled(
+        &self,
+        folder: &Path,
+    ) -> Result<CompressionEstimate, CompressionError> {
+        self.validate_path(folder)?;
+        let algorithm_scale_num = estimation::algorithm_scale_num(self.algorithm);
+        let skip_extensions = self.learned_skip_extensions(folder);
+
+        let mut scanned_files = 0_u64;
+        let mut sampled_bytes = 0_u64;
+        let mut executable_candidate = None;
+        let mut strata: BTreeMap<String, StratumTotals> = BTreeMap::new();
+        let mut candidates = Vec::new();
+        for entry in Self::file_iter_with_policy(folder, self.traversal_policy)? {
+            if self.cancel_token.is_cancelled() {
+                return Err(CompressionError::Cancelled);
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let file_size = metadata.len();
+            let path = entry.path();
+
+            scanned_files += 1;
+            sampled_bytes = sampled_bytes.saturating_add(file_size);
+            if let Some(score) = executable_score(path, file_size) {
+                executable_candidate = select_best_candidate(
+                    executable_candidate,
+                    Some(EstimateCandidate {
+                        score,
+                        path: path.to_path_buf(),
+                        path_len: path.as_os_str().len(),
+                    }),
+                );
+            }
+            if file_size < MIN_COMPRESSIBLE_SIZE {
+                continue;
+            }
+
+            let extension = ExtensionStats::key_for(path);
+            let skipped = skip_extensions.contains(&extension);
+            let (ratio_num, ratio_den) = estimation::compression_ratio_parts(path);
+            let stratum = strata.entry(extension).or_default();
+            stratum.bytes = stratum.bytes.saturating_add(file_size);
+            // Compression will skip these, so they save nothing and are
+            // not worth sampling.
+            if skipped {
+                continue;
+            }
+            stratum.heuristic_saved_bytes = stratum.heuristic_saved_bytes.saturating_add(
+                file_size
+                    .saturating_mul(ratio_num)
+                    .saturating_mul(algorithm_scale_num)
+                    / ratio_den.saturating_mul(100),
+            );
+            // Copying a placeholder would download it; it keeps the
+            // heuristic ratio instead.
+            if !crate::safety::cloud::is_cloud_placeholder(path) {
+                candidates.push((path.to_path_buf(), file_size));
+            }
+        }
+
+        let compressible_bytes = strata
+            .values()
+            .fold(0_u64, |sum, stratum| sum.saturating_add(stratum.bytes));
+        let picks = plan_samples(&candidates, sample_budget(compressible_bytes));
+        let measured = self.measure_samples(self.algorithm, &picks)?;
+        let extrapolation = extrapolate(&strata, &measured.extensions);
+
+        let measured_share = if compressible_bytes == 0 {
+            0.0
+        } else {
+            extrapolation.measured_bytes as f64 / compressible_bytes as f64
+        };
+        let relative_spread =
+            SAMPLED_SPREAD * measured_share + HEURISTIC_SPREAD * (1.0 - measured_share);
+        let (estimated_saved_low_bytes, estimated_saved_high_bytes) =
+            savings_range(extrapolation.saved_bytes, sampled_bytes, relative_spread);
+
+        Ok(CompressionEstimate {
+            scanned_files,
+            sampled_bytes,
+            estimated_saved_bytes: extrapolation.saved_bytes,
+            executable_candidate_path: executable_candidate.map(|c| c.path),
+            base_source: CompressionEstimateSource::Sampled,
+            adaptive_applied: false,
+            community_samples: None,
+            community_lookup_pending: false,
+            estimated_saved_low_bytes,
+            estimated_saved_high_bytes,
+            confidence: SAMPLED_CONFIDENCE * measured_share,
+        })
+    }
+
+    /// Compress each pick's copy with `algorithm` and tally logical and
+    /// physical bytes per extension. Unreadable sources are skipped;
+    /// scratch or WOF failures abort the measurement.
+    #[cfg(windows)]
+    pub(super) fn measure_samples(
+        &self,
+        algorithm: CompressionAlgorithm,
+        picks: &[SamplePick],
+    ) -> Result<SampleMeasurement, CompressionError> {
+        use std::fs::{File, OpenOptions};
+        use std::io::Read;
+        use std::time::Instant;
+
+        use super::ExtensionTally;
+        use crate::compression::wof::{self, CompressFileResult};
+
+        let scratch = ScratchDir::create()?;
+        let tally = ExtensionTally::default();
+        let mut compress_time = Duration::ZERO;
+        for (index, pick) in picks.iter().enumerate() {
+            if self.cancel_token.is_cancelled() {
+                return Err(CompressionError::Cancelled);
+            }
+            let source = match File::open(&pick.path) {
+                Ok(source) => source,
+                Err(error) => {
+                    log::debug!("Skipping sample {}: {error}", pick.path.display());
+                    continue;
+                }
+            };
+
+            let copy_path = scratch.path.join(format!("{index}.sample"));
+            let mut copy = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&copy_path)
+                .map_err(|error| wof::map_io(error, &copy_path))?;
+            let copied = std::io::copy(&mut source.take(pick.bytes), &mut copy)
+                .map_err(|error| wof::map_io(error, &copy_path))?;
+            if copied < MIN_COMPRESSIBLE_SIZE {
+                continue;
+            }
+
+            let started = Instant::now();
+            let outcome = wof::wof_compress_open_file(&copy, &copy_path, algorithm)?;
+            compress_time += started.elapsed();
+            let compressed = match outcome {
+                CompressFileResult::NotBeneficial => copied,
+                CompressFileResult::Compressed => {
+                    drop(copy);
+                    wof::get_physical_size(&copy_path)?
+                }
+            };
+            tally.record(&pick.path, copied, compressed.min(copied));
+            let _ = std::fs::remove_file(&copy_path);
+        }
+        Ok(SampleMeasurement {
+            extensions: tally.into_sorted(),
+            compress_time,
+        })
+    }
+
+    #[cfg(not(windows))]
+    pub(super) fn measure_samples(
+        &self,
+        _algorithm: CompressionAlgorithm,
+        _picks: &[SamplePick],
+    ) -> Result<SampleMeasurement, CompressionError> {
+        Err(CompressionError::WofApiError {
+            message: "WOF compression requires Windows".into(),
+        })
+    }
+}
+
+/// Temp directory for sample copies, removed with its contents on drop.
+#[cfg(windows)]
+struct ScratchDir {
+    path: PathBuf,
+}
+
+#[cfg(windows)]
+impl ScratchDir {
+    fn create() -> Result<Self, CompressionError> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!(
+            "compact-games-sample-{}-{nanos}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&path).map_err(|source| CompressionError::Io { source })?;
+        Ok(Self { path })
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+pub(super) fn sample_budget(compressible_bytes: u64) -> u64 {
+    (compressible_bytes / SAMPLE_FRACTION_DENOMINATOR)
+        .clamp(MIN_SAMPLE_BYTES, MAX_SAMPLE_BYTES)
+        .min(compressible_bytes)
+}
+
+/// Split `budget` across extensions in proportion to their bytes and pick
+/// files spread evenly through each extension's path-sorted list. Smaller
+/// extensions are planned first so their `MIN_STRATUM_BYTES` floor comes out
+/// of the budget before the large ones share the rest; the total never
+/// exceeds `budget`.
+pub(super) fn plan_samples(files: &[(PathBuf, u64)], budget: u64) -> Vec<SamplePick> {
+    let mut strata: BTreeMap<String, Vec<&(PathBuf, u64)>> = BTreeMap::new();
+    for file in files {
+        strata
+            .entry(ExtensionStats::key_for(&file.0))
+            .or_default()
+            .push(file);
+    }
+    let mut strata: Vec<(u64, Vec<&(PathBuf, u64)>)> = strata
+        .into_values()
+        .map(|stratum| {
+            let bytes = stratum
+                .iter()
+                .fold(0_u64, |sum, (_, size)| sum.saturating_add(*size));
+            (bytes, stratum)
+        })
+        .collect();
+    strata.sort_by_key(|(bytes, _)| *bytes);
+
+    let mut picks = Vec::new();
+    let mut budget_left = budget;
+    let mut bytes_left = strata
+        .iter()
+        .fold(0_u64, |sum, (bytes, _)| sum.saturating_add(*bytes));
+    for (stratum_bytes, mut stratum) in strata {
+        if budget_left < MIN_COMPRESSIBLE_SIZE {
+            break;
+        }
+        stratum.sort_by(|a, b| a.0.cmp(&b.0));
+        let share = (u128::from(budget_left) * u128::from(stratum_bytes)
+            / u128::from(bytes_left.max(1))) as u64;
+        bytes_left = bytes_left.saturating_sub(stratum_bytes);
+        let mut remaining = share
+            .max(MIN_STRATUM_BYTES)
+            .min(stratum_bytes)
+            .min(budget_left);
+
+        let average_pick = (stratum_bytes / stratum.len() as u64).clamp(1, MAX_BYTES_PER_FILE);
+        let wanted = (remaining / average_pick).max(1) as usize;
+        let stride = (stratum.len() / wanted).max(1);
+        for (path, size) in stratum.into_iter().step_by(stride) {
+            let bytes = (*size).min(MAX_BYTES_PER_FILE).min(remaining);
+            if bytes < MIN_COMPRESSIBLE_SIZE {
+                break;
+            }
+            picks.push(SamplePick {
+                path: path.clone(),
+                bytes,
+            });
+            remaining -= bytes;
+            budget_left -= bytes;
+        }
+    }
+    picks
+}
+
+/// Apply each extension's measured savings ratio to all of its bytes;
+/// extensions without a measurement keep their heuristic savings.
+fn extrapolate(
+    strata: &BTreeMap<String, StratumTotals>,
+    measured: &[ExtensionStats],
+) -> Extrapolation {
+    let mut result = Extrapolation::default();
+    for (extension, stratum) in strata {
+        let sample = measured
+            .iter()
+            .find(|stats| &stats.extension == extension && stats.original_bytes > 0);
+        let saved = match sample {
+            Some(sample) => {
+                let saved_ratio =
+                    1.0 - sample.compressed_bytes as f64 / sample.original_bytes as f64;
+                result.measured_bytes = result.measured_bytes.saturating_add(stratum.bytes);
+                (stratum.bytes as f64 * saved_ratio.clamp(0.0, 1.0)) as u64
+            }
+            None => stratum.heuristic_saved_bytes,
+        };
+        result.saved_bytes = result.saved_bytes.saturating_add(saved);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn file(path: &str, size: u64) -> (PathBuf, u64) {
+        (PathBuf::from(path), size)
+    }
+
+    #[test]
+    fn budget_is_one_percent_within_bounds() {
+        assert_eq!(sample_budget(100 * 1024 * MIB), MAX_SAMPLE_BYTES);
+        assert_eq!(sample_budget(10 * 1024 * MIB), 10 * 1024 * MIB / 100);
+        assert_eq!(sample_budget(1024 * MIB), MIN_SAMPLE_BYTES);
+        assert_eq!(sample_budget(8 * MIB), 8 * MIB);
+    }
+
+    #[test]
+    fn plan_covers_every_extension_within_budget() {
+        let mut files: Vec<_> = (0..100)
+            .map(|i| file(&format!("data/{i:03}.pak"), 64 * MIB))
+            .collect();
+        files.push(file("bin/game.exe", 40 * MIB));
+        files.push(file("cfg/settings.ini", 64 * 1024));
+        let budget = 100 * MIB;
+
+        let picks = plan_samples(&files, budget);
+        let total: u64 = picks.iter().map(|pick| pick.bytes).sum();
+        assert!(total <= budget);
+        for extension in ["pak", "exe", "ini"] {
+            assert!(
+                picks
+                    .iter()
+                    .any(|pick| ExtensionStats::key_for(&pick.path) == extension),
+                "{extension} should be sampled"
+            );
+        }
+        assert!(picks.iter().all(|pick| pick.bytes <= MAX_BYTES_PER_FILE));
+    }
+
+    #[test]
+    fn plan_spreads_picks_across_a_stratum() {
+        let files: Vec<_> = (0..100)
+            .map(|i| file(&format!("data/{i:03}.pak"), 8 * MIB))
+            .collect();
+
+        let picks = plan_samples(&files, 32 * MIB);
+        assert_eq!(picks.len(), 4);
+        assert_eq!(picks[0].path, PathBuf::from("data/000.pak"));
+        assert_eq!(picks[1].path, PathBuf::from("data/025.pak"));
+        assert_eq!(picks[3].path, PathBuf::from("data/075.pak"));
+    }
+
+    #[test]
+    fn extrapolation_prefers_measured_ratios() {
+        let mut strata = BTreeMap::new();
+        strata.insert(
+            "pak".to_string(),
+            StratumTotals {
+                bytes: 1000 * MIB,
+                heuristic_saved_bytes: 50 * MIB,
+            },
+        );
+        strata.insert(
+            "dll".to_string(),
+            StratumTotals {
+                bytes: 100 * MIB,
+                heuristic_saved_bytes: 40 * MIB,
+            },
+        );
+        let measured = vec![ExtensionStats {
+            extension: "pak".into(),
+            files: 3,
+            original_bytes: 10 * MIB,
+            compressed_bytes: 6 * MIB,
+            ..ExtensionStats::default()
+        }];
+
+        let result = extrapolate(&strata, &measured);
+        assert_eq!(result.measured_bytes, 1000 * MIB);
+        assert_eq!(result.saved_bytes, 400 * MIB + 40 * MIB);
+    }
+}
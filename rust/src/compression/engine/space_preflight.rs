@@ -0,0 +1,123 @@
+//! Free-space preflight for compression.
+//!
+//! WOF writes a file's compressed stream before it frees the original
+//! extents, so while a file is being compressed the volume holds both.
+//! On a nearly full drive that used to surface as `DiskFull` halfway
+//! through a game. The preflight refuses up front when the largest file
+//! plus a margin does not fit, and reserves that much for the run so two
+//! operations on one volume (the app and the automation worker) do not
+//! both count the same free bytes.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use sysinfo::Disks;
+
+use super::CompressionError;
+use crate::discovery::storage::volume_key_for_path;
+
+/// Headroom for NTFS metadata, the WOF reparse data and whatever else
+/// writes to the volume during the run.
+const SPACE_SAFETY_MARGIN: u64 = 256 * 1024 * 1024;
+
+/// Bytes reserved by running compressions, by volume key.
+static RESERVATIONS: LazyLock<Mutex<HashMap<String, u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Free space held for one compression run; released on drop.
+#[derive(Debug)]
+pub(super) struct SpaceReservation {
+    volume: String,
+    bytes: u64,
+}
+
+impl Drop for SpaceReservation {
+    fn drop(&mut self) {
+        let mut reservations = RESERVATIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(reserved) = reservations.get_mut(&self.volume) {
+            *reserved = reserved.saturating_sub(self.bytes);
+            if *reserved == 0 {
+                reservations.remove(&self.volume);
+            }
+        }
+    }
+}
+
+/// Scratch space compressing files up to `largest_file` bytes needs.
+fn required_space(largest_file: u64) -> u64 {
+    largest_file.saturating_add(SPACE_SAFETY_MARGIN)
+}
+
+fn check_space(required: u64, free: u64, reserved: u64) -> Result<(), CompressionError> {
+    let available = free.saturating_sub(reserved);
+    if available < required {
+        return Err(CompressionError::InsufficientSpace {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Check that the volume holding `folder` has room to compress a file of
+/// `largest_file` bytes after other runs' reservations, and reserve it.
+/// When free space cannot be read the check is skipped but the
+/// reservation is still recorded.
+pub(super) fn reserve_space(
+    folder: &Path,
+    largest_file: u64,
+) -> Result<SpaceReservation, CompressionError> {
+    let volume = volume_key_for_path(folder);
+    let required = required_space(largest_file);
+    let free = free_space(&volume);
+
+    let mut reservations = RESERVATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let reserved = reservations.get(&volume).copied().unwrap_or(0);
+    match free {
+        Some(free) => check_space(required, free, reserved)?,
+        None => log::debug!("Free space unknown for {volume}; skipping space preflight"),
+    }
+    let total = reservations.entry(volume.clone()).or_insert(0);
+    *total = total.saturating_add(required);
+
+    Ok(SpaceReservation {
+        volume,
+        bytes: required,
+    })
+}
+
+/// Free bytes on the mounted volume with this key, if known.
+fn free_space(volume: &str) -> Option<u64> {
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .find(|disk| volume_key_for_path(disk.mount_point()) == volume)
+        .map(|disk| disk.available_space())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn requirement_covers_largest_file_and_margin() {
+        let required = required_space(4_096 * MIB);
+        assert_eq!(required, 4_096 * MIB + SPACE_SAFETY_MARGIN);
+        assert_eq!(required_space(u64::MAX), u64::MAX);
+
+        assert!(check_space(required, required, 0).is_ok());
+        match check_space(required, required + MIB, 2 * MIB) {
+            Err(CompressionError::InsufficientSpace {
+                required: needed,
+                available,
+            }) => {
+                assert_eq!(needed, required);
+                assert_eq!(available, required - MIB);
+            }
+            other => panic!("expected InsufficientSpace, got {other:?}"),
+        }
+    }
+}
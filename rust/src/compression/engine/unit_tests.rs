@@ -23,6 +23,29 @@ fn reset_counters_zeroes_all() {
     assert_eq!((fp, ft, bo, bc), (0, 0, 0, 0));
 }
 
+#[test]
+fn paused_workers_wait_for_resume_or_cancel() {
+    let engine = CompressionEngine::new(CompressionAlgorithm::default());
+    engine.pause();
+    assert!(engine.is_paused());
+
+    let pause = engine.pause_handle();
+    let token = engine.cancel_token();
+    let waiter = std::thread::spawn(move || pause.wait_while_paused(&token));
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!waiter.is_finished());
+    engine.resume();
+    waiter.join().unwrap();
+
+    engine.pause();
+    let pause = engine.pause_handle();
+    let token = engine.cancel_token();
+    let waiter = std::thread::spawn(move || pause.wait_while_paused(&token));
+    engine.cancel_token().cancel();
+    waiter.join().unwrap();
+    assert!(engine.is_paused());
+}
+
 #[test]
 fn operation_guard_prevents_parallel_entry() {
     let engine = CompressionEngine::new(CompressionAlgorithm::default());
@@ -49,6 +72,17 @@ fn recoverable_error_classification_is_strict() {
     assert!(CompressionEngine::is_recoverable_file_error(&locked));
     assert!(CompressionEngine::is_recoverable_file_error(&denied));
     assert!(!CompressionEngine::is_recoverable_file_error(&wof));
+
+    let encrypted = CompressionError::EncryptedFile {
+        path: Path::new("C:\\test").to_path_buf(),
+    };
+    let unsupported_volume = CompressionError::CompressionNotSupportedOnVolume {
+        path: Path::new("C:\\test").to_path_buf(),
+    };
+    assert!(CompressionEngine::is_recoverable_file_error(&encrypted));
+    assert!(!CompressionEngine::is_recoverable_file_error(
+        &unsupported_volume
+    ));
 }
 
 #[test]
@@ -72,6 +106,9 @@ fn estimate_savings_uses_extension_buckets() {
         "0.5% of 10_000 + 35% of 10_000 should be predicted for XPRESS8K"
     );
     assert_eq!(estimate.base_source, CompressionEstimateSource::Heuristic);
+    assert!(estimate.estimated_saved_low_bytes < estimate.estimated_saved_bytes);
+    assert!(estimate.estimated_saved_high_bytes > estimate.estimated_saved_bytes);
+    assert!(estimate.estimated_saved_high_bytes <= estimate.sampled_bytes);
 }
 
 #[test]
@@ -137,6 +174,9 @@ fn estimate_with_context_uses_community_db_before_file_walk() {
     assert_eq!(estimate.base_source, CompressionEstimateSource::CommunityDb);
     assert_eq!(estimate.community_samples, Some(20));
     assert!(!estimate.community_lookup_pending);
+    assert!(estimate.estimated_saved_low_bytes >= 20_000);
+    assert!(estimate.estimated_saved_high_bytes <= 30_000);
+    assert!(estimate.confidence > 0.0);
 }
 
 #[test]
@@ -1,6 +1,7 @@
 //! Windows-specific compress/decompress/ratio implementations using WOF API.
 
-use std::path::Path;
+use std::os::windows::fs::MetadataExt;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -43,8 +44,16 @@ fn get_or_create_thread_pool(
 }
 
 use super::super::error::CompressionError;
+use super::super::op_journal::{OperationJournal, OperationKind};
+use super::super::privilege;
+use super::super::thread_policy::ThroughputSample;
 use super::super::wof::{self, CompressFileResult};
-use super::{CompressionEngine, CompressionStats, ManifestFile, MIN_COMPRESSIBLE_SIZE};
+use super::{
+    AttributeSkipTally, CompressionEngine, CompressionStats, DecompressionStats, ExtensionStats,
+    ExtensionTally, ManifestFile, MIN_COMPRESSIBLE_SIZE,
+};
+use crate::compression::attributes::incompatible_attribute;
+use crate::compression::history::skip_list::learned_skip_extensions;
 
 impl CompressionEngine {
     pub(super) fn compress_impl(
@@ -60,165 +69,304 @@ impl CompressionEngine {
                 }
             })
             .collect();
-        self.compress_impl_from_manifest(folder, files)
+        self.compress_impl_from_manifests(vec![(folder.to_path_buf(), files)])
     }
 
-    pub(super) fn compress_impl_from_manifest(
+    /// Compress every `(folder, manifest)` root as one run: counters,
+    /// progress and the returned stats cover all of them, while the path
+    /// guard, journal, skip list and thread pool stay per folder.
+    pub(super) fn compress_impl_from_manifests(
         &self,
-        folder: &Path,
-        files: Vec<ManifestFile>,
+        roots: Vec<(PathBuf, Vec<ManifestFile>)>,
     ) -> Result<CompressionStats, CompressionError> {
         let start = std::time::Instant::now();
         let disk_full = Arc::new(AtomicBool::new(false));
         let skipped = Arc::new(AtomicU64::new(0));
+        let skipped_cloud = Arc::new(AtomicU64::new(0));
+        let skipped_permission = Arc::new(AtomicU64::new(0));
+        let already_compressed_files = Arc::new(AtomicU64::new(0));
+        let already_compressed_bytes = Arc::new(AtomicU64::new(0));
+        let already_compressed_on_disk = Arc::new(AtomicU64::new(0));
+        let extensions = ExtensionTally::default();
+        let attribute_skips = AttributeSkipTally::default();
+        let alternate_stream_files = Arc::new(AtomicU64::new(0));
+        let alternate_stream_bytes = Arc::new(AtomicU64::new(0));
         let algorithm = self.algorithm;
-        let canonical_root =
-            std::fs::canonicalize(folder).map_err(|source| CompressionError::Io { source })?;
+        let roots = roots
+            .into_iter()
+            .map(|(folder, files)| {
+                let canonical_root = std::fs::canonicalize(&folder)
+                    .map_err(|source| CompressionError::Io { source })?;
+                Ok((folder, canonical_root, files))
+            })
+            .collect::<Result<Vec<_>, CompressionError>>()?;
 
         // Reset counters before starting to avoid stale accumulation from
         // a previous run when the engine instance is reused.
         self.reset_counters();
-        self.files_total
-            .store(files.len() as u64, Ordering::Relaxed);
+        self.set_totals(roots.iter().map(|(_, _, files)| files.as_slice()));
 
-        let compress_body = |manifest_file: &ManifestFile| -> Result<(), CompressionError> {
-            let path = manifest_file.path.as_path();
-            if self.cancel_token.is_cancelled() {
-                return Err(CompressionError::Cancelled);
-            }
-            if disk_full.load(Ordering::Relaxed) {
-                return Err(CompressionError::DiskFull);
+        let record_already_compressed = |file_size: u64, physical: u64| {
+            already_compressed_files.fetch_add(1, Ordering::Relaxed);
+            already_compressed_bytes.fetch_add(file_size, Ordering::Relaxed);
+            already_compressed_on_disk.fetch_add(physical, Ordering::Relaxed);
+        };
+
+        let mut was_cancelled = false;
+        let mut io_parallelism = None;
+        for (folder, canonical_root, files) in &roots {
+            let folder = folder.as_path();
+            let journal = self.begin_journal(OperationKind::Compress, canonical_root);
+            let skip_extensions = learned_skip_extensions(folder, algorithm);
+            if !skip_extensions.is_empty() {
+                let mut listed: Vec<_> = skip_extensions.iter().map(String::as_str).collect();
+                listed.sort_unstable();
+                log::info!(
+                    "Skipping extensions {} in {}: not beneficial on the last run",
+                    listed.join(", "),
+                    folder.display()
+                );
             }
 
-            let file = match wof::open_verified_file(path, &canonical_root) {
-                Ok(file) => file,
-                Err(error) if Self::is_recoverable_file_error(&error) => {
+            let compress_body = |manifest_file: &ManifestFile| -> Result<(), CompressionError> {
+                let path = manifest_file.path.as_path();
+                self.pause.wait_while_paused(&self.cancel_token);
+                if self.cancel_token.is_cancelled() {
+                    return Err(CompressionError::Cancelled);
+                }
+                if disk_full.load(Ordering::Relaxed) {
+                    return Err(CompressionError::DiskFull);
+                }
+
+                // Opening an online-only placeholder for write would download it.
+                if crate::safety::cloud::is_cloud_placeholder(path) {
+                    log::debug!("Skipping cloud placeholder: {}", path.display());
+                    skipped_cloud.fetch_add(1, Ordering::Relaxed);
                     skipped.fetch_add(1, Ordering::Relaxed);
                     self.files_processed.fetch_add(1, Ordering::Relaxed);
                     return Ok(());
                 }
-                Err(error) => {
+
+                let file = match self.open_for_compression(path, canonical_root) {
+                    Ok(file) => file,
+                    Err(error) if Self::is_recoverable_file_error(&error) => {
+                        if is_acl_denial(&error) {
+                            log::debug!("Skipping {}: access denied by ACL", path.display());
+                            skipped_permission.fetch_add(1, Ordering::Relaxed);
+                        }
+                        skipped.fetch_add(1, Ordering::Relaxed);
+                        self.files_processed.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                    Err(error) => {
+                        log::warn!(
+                            "Skipping unsafe compression path {}: {error}",
+                            path.display()
+                        );
+                        skipped.fetch_add(1, Ordering::Relaxed);
+                        self.files_processed.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                };
+
+                if wof::link_count(&file).is_some_and(|count| count > 1) {
                     log::warn!(
-                        "Skipping unsafe compression path {}: {error}",
+                        "Skipping multi-linked file during compression: {}",
                         path.display()
                     );
                     skipped.fetch_add(1, Ordering::Relaxed);
                     self.files_processed.fetch_add(1, Ordering::Relaxed);
                     return Ok(());
                 }
-            };
 
-            if wof::link_count(&file).is_some_and(|count| count > 1) {
-                log::warn!(
-                    "Skipping multi-linked file during compression: {}",
-                    path.display()
-                );
-                skipped.fetch_add(1, Ordering::Relaxed);
-                self.files_processed.fetch_add(1, Ordering::Relaxed);
-                return Ok(());
-            }
+                // Counted for every file, even ones skipped below, because
+                // none of them get their streams compressed.
+                let stream_bytes = wof::alternate_stream_bytes(path);
+                if stream_bytes > 0 {
+                    alternate_stream_files.fetch_add(1, Ordering::Relaxed);
+                    alternate_stream_bytes.fetch_add(stream_bytes, Ordering::Relaxed);
+                }
 
-            let file_size = file
-                .metadata()
-                .map(|metadata| metadata.len())
-                .unwrap_or_default();
-            if file_size == 0 {
-                skipped.fetch_add(1, Ordering::Relaxed);
-                self.files_processed.fetch_add(1, Ordering::Relaxed);
-                return Ok(());
-            }
+                let metadata = file.metadata().ok();
+                let file_size = metadata.as_ref().map(|m| m.len()).unwrap_or_default();
+                if file_size == 0 {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    self.files_processed.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
 
-            if file_size < MIN_COMPRESSIBLE_SIZE {
-                skipped.fetch_add(1, Ordering::Relaxed);
-                self.files_processed.fetch_add(1, Ordering::Relaxed);
-                return Ok(());
-            }
+                if file_size < MIN_COMPRESSIBLE_SIZE {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    self.files_processed.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
 
-            // WOF does not overlay a second backing on an already-backed file,
-            // so recompression with a different algorithm must clear the old
-            // backing before applying the new one.
-            match wof::wof_get_compression_open_file(&file, path) {
-                Ok(Some(current_algo)) if current_algo == algorithm => {
+                if let Some(attribute) = metadata
+                    .as_ref()
+                    .and_then(|m| incompatible_attribute(m.file_attributes()))
+                {
+                    log::debug!("Skipping {attribute:?} file: {}", path.display());
                     let physical = wof::get_physical_size(path).unwrap_or(file_size);
                     self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
                     self.bytes_compressed.fetch_add(physical, Ordering::Relaxed);
+                    extensions.record(path, file_size, physical);
+                    attribute_skips.record(path, attribute);
+                    skipped.fetch_add(1, Ordering::Relaxed);
                     self.files_processed.fetch_add(1, Ordering::Relaxed);
                     return Ok(());
                 }
-                Ok(Some(_)) => {
-                    if let Err(e) = wof::wof_decompress_open_file(&file, path) {
-                        log::warn!(
-                            "Skipping {} during re-apply: could not clear WOF backing: {e}",
-                            path.display()
-                        );
-                        self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
-                        self.bytes_compressed
-                            .fetch_add(file_size, Ordering::Relaxed);
-                        skipped.fetch_add(1, Ordering::Relaxed);
-                        self.files_processed.fetch_add(1, Ordering::Relaxed);
-                        return Ok(());
-                    }
-                }
-                _ => {
-                    // Not WOF-backed (or query failed). Legacy heuristic: if
-                    // physical < logical the file is NTFS LZNT1 or sparse, so
-                    // leave it alone rather than layering WOF on top.
+
+                // Tallied as not beneficial again so the next run keeps
+                // skipping the extension.
+                if skip_extensions.contains(&ExtensionStats::key_for(path)) {
                     let physical = wof::get_physical_size(path).unwrap_or(file_size);
                     if physical < file_size {
+                        record_already_compressed(file_size, physical);
+                    }
+                    self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                    self.bytes_compressed.fetch_add(physical, Ordering::Relaxed);
+                    extensions.record_not_beneficial(path, file_size, physical);
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    self.files_processed.fetch_add(1, Ordering::Relaxed);
+                    return Ok(());
+                }
+
+                // WOF does not overlay a second backing on an already-backed file,
+                // so recompression with a different algorithm must clear the old
+                // backing before applying the new one.
+                match wof::wof_get_compression_open_file(&file, path) {
+                    Ok(Some(current_algo)) if current_algo == algorithm => {
+                        let physical = wof::get_physical_size(path).unwrap_or(file_size);
+                        record_already_compressed(file_size, physical);
                         self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
                         self.bytes_compressed.fetch_add(physical, Ordering::Relaxed);
+                        extensions.record(path, file_size, physical);
                         self.files_processed.fetch_add(1, Ordering::Relaxed);
                         return Ok(());
                     }
+                    Ok(Some(_)) => {
+                        if let Err(e) = wof::wof_decompress_open_file(&file, path) {
+                            log::warn!(
+                                "Skipping {} during re-apply: could not clear WOF backing: {e}",
+                                path.display()
+                            );
+                            self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                            self.bytes_compressed
+                                .fetch_add(file_size, Ordering::Relaxed);
+                            extensions.record(path, file_size, file_size);
+                            skipped.fetch_add(1, Ordering::Relaxed);
+                            self.files_processed.fetch_add(1, Ordering::Relaxed);
+                            return Ok(());
+                        }
+                    }
+                    _ => {
+                        // Not WOF-backed (or query failed). NTFS LZNT1 files from
+                        // `compact /c` are upgraded: their compression is cleared
+                        // and WOF applied below. Anything else smaller on disk is
+                        // sparse, or could not be cleared, and is left alone
+                        // rather than layering WOF on top.
+                        let physical = wof::get_physical_size(path).unwrap_or(file_size);
+                        let upgrade_ntfs = physical < file_size
+                            && wof::is_ntfs_compressed_open_file(&file)
+                            && match wof::clear_ntfs_compression_open_file(&file, path) {
+                                Ok(()) => true,
+                                Err(e) => {
+                                    log::warn!(
+                                        "Leaving NTFS compression on {}: could not clear it: {e}",
+                                        path.display()
+                                    );
+                                    false
+                                }
+                            };
+                        if physical < file_size && !upgrade_ntfs {
+                            record_already_compressed(file_size, physical);
+                            self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                            self.bytes_compressed.fetch_add(physical, Ordering::Relaxed);
+                            extensions.record(path, file_size, physical);
+                            self.files_processed.fetch_add(1, Ordering::Relaxed);
+                            return Ok(());
+                        }
+                    }
                 }
-            }
 
-            match wof::wof_compress_open_file(&file, path, algorithm) {
-                Ok(CompressFileResult::Compressed) => {
-                    self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
-                    let phys = wof::get_physical_size(path).unwrap_or(file_size);
-                    self.bytes_compressed.fetch_add(phys, Ordering::Relaxed);
-                }
-                Ok(CompressFileResult::NotBeneficial) => {
-                    self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
-                    self.bytes_compressed
-                        .fetch_add(file_size, Ordering::Relaxed);
-                    skipped.fetch_add(1, Ordering::Relaxed);
-                }
-                Err(CompressionError::DiskFull) => {
-                    disk_full.store(true, Ordering::Relaxed);
-                    return Err(CompressionError::DiskFull);
-                }
-                Err(e) if Self::is_recoverable_file_error(&e) => {
-                    log::debug!("Skipping {}: locked or permission denied", path.display());
-                    self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
-                    self.bytes_compressed
-                        .fetch_add(file_size, Ordering::Relaxed);
-                    skipped.fetch_add(1, Ordering::Relaxed);
+                if let Some(limiter) = &self.rate_limit {
+                    if !limiter.acquire(file_size, || self.cancel_token.is_cancelled()) {
+                        return Err(CompressionError::Cancelled);
+                    }
                 }
-                Err(e) => {
-                    log::warn!("Aborting compression for {}: {e}", path.display());
-                    return Err(e);
+
+                match wof::wof_compress_open_file(&file, path, algorithm) {
+                    Ok(CompressFileResult::Compressed) => {
+                        self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                        let phys = wof::get_physical_size(path).unwrap_or(file_size);
+                        self.bytes_compressed.fetch_add(phys, Ordering::Relaxed);
+                        extensions.record(path, file_size, phys);
+                    }
+                    Ok(CompressFileResult::NotBeneficial) => {
+                        self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                        self.bytes_compressed
+                            .fetch_add(file_size, Ordering::Relaxed);
+                        extensions.record_not_beneficial(path, file_size, file_size);
+                        skipped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(CompressionError::DiskFull) => {
+                        disk_full.store(true, Ordering::Relaxed);
+                        return Err(CompressionError::DiskFull);
+                    }
+                    Err(e) if Self::is_recoverable_file_error(&e) => {
+                        log::debug!("Skipping {}: {e}", path.display());
+                        if is_acl_denial(&e) {
+                            skipped_permission.fetch_add(1, Ordering::Relaxed);
+                        }
+                        if let Some(attribute) = Self::skipped_attribute(&e) {
+                            attribute_skips.record(path, attribute);
+                        }
+                        self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
+                        self.bytes_compressed
+                            .fetch_add(file_size, Ordering::Relaxed);
+                        extensions.record(path, file_size, file_size);
+                        skipped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        log::warn!("Aborting compression for {}: {e}", path.display());
+                        return Err(e);
+                    }
                 }
-            }
 
-            self.files_processed.fetch_add(1, Ordering::Relaxed);
-            Ok(())
-        };
+                self.files_processed.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            };
 
-        let result = if let Some(policy) = &self.thread_policy {
+            let policy = self.effective_thread_policy(folder);
+            io_parallelism = Some(policy.io_parallelism);
             let pool = get_or_create_thread_pool(policy.io_parallelism)?;
             log::info!(
                 "[compression][thread_policy] io_parallelism={} background={}",
                 policy.io_parallelism,
                 policy.is_background,
             );
-            pool.install(|| files.par_iter().try_for_each(compress_body))
-        } else {
-            files.par_iter().try_for_each(compress_body)
-        };
-
-        result?;
+            let result = pool.install(|| {
+                files
+                    .par_iter()
+                    .try_for_each(|file| self.track_bytes(file, journal.as_ref(), &compress_body))
+            });
+
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            match result {
+                Ok(()) => {}
+                Err(CompressionError::Cancelled) if self.cancel_token.is_graceful() => {
+                    log::info!(
+                        "Compression of {} stopped early; reporting partial stats",
+                        folder.display()
+                    );
+                    was_cancelled = true;
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
 
         let duration = start.elapsed();
         let original = self.bytes_original.load(Ordering::Relaxed);
@@ -234,16 +382,81 @@ impl CompressionEngine {
             );
         }
 
+        // A capped run measures the cap, not the disk, and a multi-folder
+        // run may span several disks.
+        let throughput = io_parallelism
+            .filter(|_| roots.len() == 1 && self.rate_limit.is_none())
+            .and_then(|io_parallelism| {
+                let already_compressed = already_compressed_bytes.load(Ordering::Relaxed);
+                ThroughputSample::measure(
+                    io_parallelism,
+                    original.saturating_sub(already_compressed),
+                    duration,
+                )
+            });
+
+        let (files_skipped_encrypted, files_skipped_sparse, attribute_skips) =
+            attribute_skips.into_parts();
+        if files_skipped_encrypted + files_skipped_sparse > 0 {
+            log::info!(
+                "[compression][attributes] skipped encrypted={files_skipped_encrypted} sparse={files_skipped_sparse}"
+            );
+        }
+
+        let alternate_stream_bytes = alternate_stream_bytes.load(Ordering::Relaxed);
+        if alternate_stream_bytes > 0 {
+            log::info!(
+                "[compression][streams] {} files carry {alternate_stream_bytes} bytes of alternate data streams",
+                alternate_stream_files.load(Ordering::Relaxed)
+            );
+        }
+
         Ok(CompressionStats {
             original_bytes: self.bytes_original.load(Ordering::Relaxed),
             compressed_bytes: self.bytes_compressed.load(Ordering::Relaxed),
             files_processed: self.files_processed.load(Ordering::Relaxed),
             files_skipped: skipped.load(Ordering::Relaxed),
+            files_skipped_cloud: skipped_cloud.load(Ordering::Relaxed),
+            files_skipped_permission: skipped_permission.load(Ordering::Relaxed),
+            files_skipped_encrypted,
+            files_skipped_sparse,
+            attribute_skips,
+            alternate_stream_files: alternate_stream_files.load(Ordering::Relaxed),
+            alternate_stream_bytes,
+            files_already_compressed: already_compressed_files.load(Ordering::Relaxed),
+            bytes_already_compressed: already_compressed_bytes.load(Ordering::Relaxed),
+            bytes_already_compressed_on_disk: already_compressed_on_disk.load(Ordering::Relaxed),
+            was_cancelled,
+            throughput,
             duration_ms: start.elapsed().as_millis() as u64,
+            extensions: extensions.into_sorted(),
         })
     }
 
-    pub(super) fn decompress_impl(&self, folder: &Path) -> Result<(), CompressionError> {
+    /// Open `path` for WOF, retrying an ACL denial with backup semantics
+    /// when the engine allows it and the privileges could be enabled.
+    fn open_for_compression(
+        &self,
+        path: &Path,
+        canonical_root: &Path,
+    ) -> Result<std::fs::File, CompressionError> {
+        match wof::open_verified_file(path, canonical_root) {
+            Err(error)
+                if self.backup_privilege
+                    && is_acl_denial(&error)
+                    && privilege::enable_backup_privileges() =>
+            {
+                log::debug!("Retrying {} with backup semantics", path.display());
+                wof::open_verified_file_for_backup(path, canonical_root)
+            }
+            result => result,
+        }
+    }
+
+    pub(super) fn decompress_impl(
+        &self,
+        folder: &Path,
+    ) -> Result<DecompressionStats, CompressionError> {
         let files: Vec<ManifestFile> = Self::file_iter(folder)?
             .map(|entry| {
                 let logical_size_hint = entry.metadata().ok().map(|m| m.len());
@@ -260,21 +473,31 @@ impl CompressionEngine {
         &self,
         folder: &Path,
         files: Vec<ManifestFile>,
-    ) -> Result<(), CompressionError> {
+    ) -> Result<DecompressionStats, CompressionError> {
+        let start = std::time::Instant::now();
         self.reset_counters();
+        let files_decompressed = Arc::new(AtomicU64::new(0));
+        let bytes_restored_logical = Arc::new(AtomicU64::new(0));
+        let bytes_grown_physical = Arc::new(AtomicU64::new(0));
         let decompression_candidates = Arc::new(AtomicU64::new(0));
         let likely_uncompressed = Arc::new(AtomicU64::new(0));
         let canonical_root =
             std::fs::canonicalize(folder).map_err(|source| CompressionError::Io { source })?;
-        self.files_total
-            .store(files.len() as u64, Ordering::Relaxed);
+        self.set_totals([files.as_slice()]);
+        let journal = self.begin_journal(OperationKind::Decompress, &canonical_root);
 
         let decompress_body = |manifest_file: &ManifestFile| -> Result<(), CompressionError> {
+            self.pause.wait_while_paused(&self.cancel_token);
             if self.cancel_token.is_cancelled() {
                 return Err(CompressionError::Cancelled);
             }
 
             let path = manifest_file.path.as_path();
+            // Placeholders are never WOF-backed, and opening one downloads it.
+            if crate::safety::cloud::is_cloud_placeholder(path) {
+                self.files_processed.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
             let file = match wof::open_verified_file(path, &canonical_root) {
                 Ok(file) => file,
                 Err(error) if Self::is_recoverable_file_error(&error) => {
@@ -341,6 +564,13 @@ impl CompressionEngine {
                     self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
                     self.bytes_compressed
                         .fetch_add(physical_size, Ordering::Relaxed);
+                    let restored_physical = wof::get_physical_size(path).unwrap_or(file_size);
+                    files_decompressed.fetch_add(1, Ordering::Relaxed);
+                    bytes_restored_logical.fetch_add(file_size, Ordering::Relaxed);
+                    bytes_grown_physical.fetch_add(
+                        restored_physical.saturating_sub(physical_size),
+                        Ordering::Relaxed,
+                    );
                 }
                 Err(e) if Self::is_recoverable_file_error(&e) => {
                     self.bytes_original.fetch_add(file_size, Ordering::Relaxed);
@@ -364,23 +594,35 @@ impl CompressionEngine {
             Ok(())
         };
 
-        let result = if let Some(policy) = &self.thread_policy {
-            let pool = get_or_create_thread_pool(policy.io_parallelism)?;
-            pool.install(|| files.par_iter().try_for_each(decompress_body))
-        } else {
-            files.par_iter().try_for_each(decompress_body)
-        };
+        let policy = self.effective_thread_policy(folder);
+        let pool = get_or_create_thread_pool(policy.io_parallelism)?;
+        let result = pool.install(|| {
+            files
+                .par_iter()
+                .try_for_each(|file| self.track_bytes(file, journal.as_ref(), &decompress_body))
+        });
 
+        if let Some(journal) = journal {
+            journal.finish();
+        }
         result?;
+        let stats = DecompressionStats {
+            files_decompressed: files_decompressed.load(Ordering::Relaxed),
+            bytes_restored_logical: bytes_restored_logical.load(Ordering::Relaxed),
+            bytes_grown_physical: bytes_grown_physical.load(Ordering::Relaxed),
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
         log::info!(
-            "[decompression][summary] path=\"{}\" files={} candidates={} skipped_likely_uncompressed={}",
+            "[decompression][summary] path=\"{}\" files={} candidates={} decompressed={} grown={} skipped_likely_uncompressed={}",
             folder.display(),
             self.files_total.load(Ordering::Relaxed),
             decompression_candidates.load(Ordering::Relaxed),
+            stats.files_decompressed,
+            stats.bytes_grown_physical,
             likely_uncompressed.load(Ordering::Relaxed),
         );
 
-        Ok(())
+        Ok(stats)
     }
 
     pub(super) fn ratio_impl(folder: &Path) -> Result<f64, CompressionError> {
@@ -404,4 +646,48 @@ impl CompressionEngine {
 
         Ok(physical_total.load(Ordering::Relaxed) as f64 / logical as f64)
     }
+
+    fn begin_journal(&self, kind: OperationKind, root: &Path) -> Option<OperationJournal> {
+        OperationJournal::begin(
+            kind,
+            root,
+            self.algorithm,
+            self.files_total.load(Ordering::Relaxed),
+            self.bytes_total.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Run `body` for one file, then count its logical size as processed
+    /// whatever the outcome so the bytes-based ETA also covers skips.
+    fn track_bytes<F>(
+        &self,
+        file: &ManifestFile,
+        journal: Option<&OperationJournal>,
+        body: &F,
+    ) -> Result<(), CompressionError>
+    where
+        F: Fn(&ManifestFile) -> Result<(), CompressionError>,
+    {
+        let result = body(file);
+        let size = file.logical_size_hint.unwrap_or(0);
+        let bytes_processed = self.bytes_processed.fetch_add(size, Ordering::Relaxed) + size;
+        if let Some(journal) = journal {
+            journal.checkpoint(
+                self.files_processed.load(Ordering::Relaxed),
+                bytes_processed,
+            );
+        }
+        result
+    }
+}
+
+/// Access denied by the file's ACL rather than its read-only attribute,
+/// which backup semantics cannot get past either.
+fn is_acl_denial(error: &CompressionError) -> bool {
+    match error {
+        CompressionError::PermissionDenied { path, .. } => {
+            !std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.permissions().readonly())
+        }
+        _ => false,
+    }
 }
@@ -16,6 +16,9 @@ pub enum CompressionError {
     #[error("not enough disk space to complete operation")]
     DiskFull,
 
+    #[error("not enough free space to compress: need {required} bytes, {available} available")]
+    InsufficientSpace { required: u64, available: u64 },
+
     #[error("path does not exist: {0}")]
     PathNotFound(PathBuf),
 
@@ -25,9 +28,33 @@ pub enum CompressionError {
     #[error("compression aborted: game is running")]
     GameRunning,
 
+    #[error("another Compact Games instance is working on {path}")]
+    GameFolderLocked { path: PathBuf },
+
     #[error("compression aborted: DirectStorage game detected")]
     DirectStorageDetected,
 
+    #[error("compression refused: protected UWP package at {path}")]
+    ProtectedPackage { path: PathBuf },
+
+    #[error("compression unsupported: {path} is on a {filesystem} volume; WOF requires NTFS")]
+    UnsupportedFilesystem { path: PathBuf, filesystem: String },
+
+    #[error("WOF compression is not supported on the volume holding {path}")]
+    CompressionNotSupportedOnVolume { path: PathBuf },
+
+    #[error("file is too small for WOF compression: {path}")]
+    FileTooSmallForWof { path: PathBuf },
+
+    #[error("file is EFS-encrypted and cannot be compressed: {path}")]
+    EncryptedFile { path: PathBuf },
+
+    #[error("sparse file cannot be compressed: {path}")]
+    SparseFile { path: PathBuf },
+
+    #[error("file has an integrity stream and cannot be compressed: {path}")]
+    IntegrityStreamFile { path: PathBuf },
+
     #[error("WOF API error: {message}")]
     WofApiError { message: String },
 
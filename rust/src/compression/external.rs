@@ -0,0 +1,146 @@
+//! Folders compressed outside the app, e.g. with `compact /c /exe:lzx`.
+//!
+//! `compact /exe` applies the same WOF backing the engine does, so such
+//! files already count as compressed in size scans and the engine can
+//! recompress them with another algorithm in place. What they lack is
+//! history: the import here backfills one entry with the savings measured
+//! on disk so the game shows as compressed with a last-compressed time.
+
+use std::path::Path;
+
+use super::algorithm::CompressionAlgorithm;
+use super::history::{
+    history_for_game, record_compression, ActualStats, CompressionHistoryEntry, EstimateSnapshot,
+};
+
+/// WOF-compressed files found under a folder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExternalCompressionScan {
+    pub files_compressed: u64,
+    /// Logical size of `files_compressed`.
+    pub logical_bytes: u64,
+    /// Physical size of `files_compressed`.
+    pub physical_bytes: u64,
+    /// The algorithm covering the most logical bytes.
+    pub algorithm: Option<CompressionAlgorithm>,
+}
+
+/// Count the WOF-compressed files under `folder`, by whatever tool.
+#[cfg(windows)]
+pub fn scan_external_compression(folder: &Path) -> ExternalCompressionScan {
+    use super::wof;
+    use crate::safety::traversal::TraversalPolicy;
+
+    let mut scan = ExternalCompressionScan::default();
+    let mut bytes_by_algorithm: Vec<(CompressionAlgorithm, u64)> = Vec::new();
+    for entry in TraversalPolicy::default()
+        .walk(folder)
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let path = entry.path();
+        let Ok(Some(algorithm)) = wof::wof_get_compression(path) else {
+            continue;
+        };
+        let logical = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let physical = wof::get_physical_size(path).unwrap_or(logical);
+
+        scan.files_compressed += 1;
+        scan.logical_bytes += logical;
+        scan.physical_bytes += physical;
+        match bytes_by_algorithm.iter_mut().find(|(a, _)| *a == algorithm) {
+            Some((_, bytes)) => *bytes += logical,
+            None => bytes_by_algorithm.push((algorithm, logical)),
+        }
+    }
+    scan.algorithm = bytes_by_algorithm
+        .into_iter()
+        .max_by_key(|&(_, bytes)| bytes)
+        .map(|(algorithm, _)| algorithm);
+    scan
+}
+
+#[cfg(not(windows))]
+pub fn scan_external_compression(_folder: &Path) -> ExternalCompressionScan {
+    ExternalCompressionScan::default()
+}
+
+/// Record `game_path`'s external compression in history.
+///
+/// Returns `None` when the game already has history, which the app's own
+/// runs keep accurate, or when nothing under it is WOF-compressed.
+pub fn import_external_compression(
+    game_path: &Path,
+    game_name: &str,
+) -> Option<CompressionHistoryEntry> {
+    if !history_for_game(game_path, 1).is_empty() {
+        return None;
+    }
+    let scan = scan_external_compression(game_path);
+    let entry = history_entry_for_scan(game_path, game_name, &scan)?;
+    log::info!(
+        "Imported external compression of {}: {} files, {} bytes saved",
+        game_path.display(),
+        scan.files_compressed,
+        entry.actual_stats.actual_saved_bytes
+    );
+    record_compression(entry.clone());
+    Some(entry)
+}
+
+/// History entry for `scan`. The estimate is zeroed so the import does
+/// not teach the adaptive estimator anything.
+fn history_entry_for_scan(
+    game_path: &Path,
+    game_name: &str,
+    scan: &ExternalCompressionScan,
+) -> Option<CompressionHistoryEntry> {
+    let algorithm = scan.algorithm.filter(|_| scan.files_compressed > 0)?;
+    Some(CompressionHistoryEntry {
+        game_path: game_path.to_string_lossy().into_owned(),
+        game_name: game_name.to_owned(),
+        timestamp_ms: crate::utils::unix_now_ms(),
+        estimate: EstimateSnapshot {
+            scanned_files: 0,
+            sampled_bytes: 0,
+            estimated_saved_bytes: 0,
+        },
+        actual_stats: ActualStats {
+            original_bytes: scan.logical_bytes,
+            compressed_bytes: scan.physical_bytes,
+            actual_saved_bytes: scan.logical_bytes.saturating_sub(scan.physical_bytes),
+            files_processed: scan.files_compressed,
+        },
+        algorithm,
+        duration_ms: 0,
+        extensions: Vec::new(),
+        throughput: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_becomes_a_history_entry_with_measured_savings() {
+        let scan = ExternalCompressionScan {
+            files_compressed: 3,
+            logical_bytes: 9_000,
+            physical_bytes: 4_000,
+            algorithm: Some(CompressionAlgorithm::Lzx),
+        };
+        let entry = history_entry_for_scan(Path::new("D:\\Games\\Foo"), "Foo", &scan).unwrap();
+
+        assert_eq!(entry.algorithm, CompressionAlgorithm::Lzx);
+        assert_eq!(entry.actual_stats.actual_saved_bytes, 5_000);
+        assert_eq!(entry.actual_stats.files_processed, 3);
+        assert_eq!(entry.estimate.estimated_saved_bytes, 0);
+    }
+
+    #[test]
+    fn folders_without_compressed_files_are_not_imported() {
+        let scan = ExternalCompressionScan::default();
+        assert!(history_entry_for_scan(Path::new("D:\\Games\\Foo"), "Foo", &scan).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use super::retention::GameAggregate;
 use super::CompressionHistoryEntry;
 use crate::compression::algorithm::CompressionAlgorithm;
 use std::path::Path;
@@ -9,65 +10,120 @@ const CONFIDENCE_MAX: f64 = 0.8;
 const MIN_MULTIPLIER: f64 = 0.2;
 const MAX_MULTIPLIER: f64 = 2.0;
 const CONSERVATIVE_Z: f64 = 1.96;
+/// Range half-width, relative to the estimate, when history says nothing.
+pub const HEURISTIC_SPREAD: f64 = 0.35;
+const MIN_SPREAD: f64 = 0.05;
+const MAX_SPREAD: f64 = 0.6;
 
 /// Adaptive estimator that learns from compression history.
 pub struct AdaptiveEstimator {
     history: Vec<CompressionHistoryEntry>,
+    aggregates: Vec<GameAggregate>,
     min_samples_for_confidence: usize,
 }
 
+/// Decay-weighted moments of estimate accuracy ratios.
+#[derive(Default)]
+struct AccuracyMoments {
+    weight_sum: f64,
+    weighted_sum: f64,
+    weighted_sq_sum: f64,
+    samples: usize,
+}
+
+impl AccuracyMoments {
+    fn add_entry(&mut self, entry: &CompressionHistoryEntry, now_ms: u64) {
+        let ratio = entry.estimate_accuracy_ratio();
+        let weight = decay_weight(entry.timestamp_ms, now_ms);
+        self.weight_sum += weight;
+        self.weighted_sum += ratio * weight;
+        self.weighted_sq_sum += ratio * ratio * weight;
+        self.samples += 1;
+    }
+
+    /// Compacted samples all take the weight of the newest one, which
+    /// slightly favours them over their true age.
+    fn add_aggregate(&mut self, aggregate: &GameAggregate, now_ms: u64) {
+        let weight = decay_weight(aggregate.latest.timestamp_ms, now_ms);
+        self.weight_sum += weight * aggregate.accuracy_samples as f64;
+        self.weighted_sum += weight * aggregate.accuracy_sum;
+        self.weighted_sq_sum += weight * aggregate.accuracy_sq_sum;
+        self.samples += aggregate.accuracy_samples as usize;
+    }
+
+    fn mean(&self) -> f64 {
+        self.weighted_sum / self.weight_sum
+    }
+
+    fn variance(&self) -> f64 {
+        (self.weighted_sq_sum / self.weight_sum - self.mean().powi(2)).max(0.0)
+    }
+
+    /// Coefficient of variation: how far a corrected estimate typically
+    /// lands from the actual savings, as a fraction of the estimate.
+    fn relative_spread(&self) -> f64 {
+        (self.variance().sqrt() / self.mean().max(MIN_MULTIPLIER)).clamp(MIN_SPREAD, MAX_SPREAD)
+    }
+}
+
 impl AdaptiveEstimator {
     pub fn from_history(history: Vec<CompressionHistoryEntry>) -> Self {
         Self {
             history,
+            aggregates: Vec::new(),
             min_samples_for_confidence: 10,
         }
     }
 
+    /// Include samples from entries that retention compacted away.
+    pub fn with_aggregates(mut self, aggregates: Vec<GameAggregate>) -> Self {
+        self.aggregates = aggregates;
+        self
+    }
+
+    fn moments(&self, algorithm: CompressionAlgorithm, game_key: Option<&str>) -> AccuracyMoments {
+        let now_ms = current_epoch_ms();
+        let mut moments = AccuracyMoments::default();
+        self.history
+            .iter()
+            .filter(|entry| entry.algorithm == algorithm)
+            .filter(|entry| entry.estimate.estimated_saved_bytes > 0)
+            .filter(|entry| {
+                game_key.is_none_or(|key| normalize_game_path(Path::new(&entry.game_path)) == key)
+            })
+            .for_each(|entry| moments.add_entry(entry, now_ms));
+        self.aggregates
+            .iter()
+            .filter(|aggregate| aggregate.latest.algorithm == algorithm)
+            .filter(|aggregate| aggregate.accuracy_samples > 0)
+            .filter(|aggregate| {
+                game_key.is_none_or(|key| {
+                    normalize_game_path(Path::new(&aggregate.latest.game_path)) == key
+                })
+            })
+            .for_each(|aggregate| moments.add_aggregate(aggregate, now_ms));
+        moments
+    }
+
     /// Get adaptive correction factor for estimates.
     ///
     /// Returns `(multiplier, confidence)`:
     /// - `multiplier`: scales default estimate (1.0 = no change)
     /// - `confidence`: blending weight to adaptive value (0.0-0.8)
     pub fn get_correction_factor(&self, algorithm: CompressionAlgorithm) -> (f64, f64) {
-        let relevant: Vec<_> = self
-            .history
-            .iter()
-            .filter(|entry| entry.algorithm == algorithm)
-            .filter(|entry| entry.estimate.estimated_saved_bytes > 0)
-            .collect();
-
-        if relevant.len() < self.min_samples_for_confidence {
+        let moments = self.moments(algorithm, None);
+        if moments.samples < self.min_samples_for_confidence {
             return (1.0, 0.0);
         }
-
-        let now_ms = current_epoch_ms();
-        let weighted: Vec<(f64, f64)> = relevant
-            .iter()
-            .map(|entry| {
-                let ratio = entry.estimate_accuracy_ratio();
-                let age_days = now_ms.saturating_sub(entry.timestamp_ms) as f64 / MILLIS_PER_DAY;
-                let weight = (-age_days / DECAY_DAYS).exp();
-                (ratio, weight)
-            })
-            .collect();
-
-        let weight_sum: f64 = weighted.iter().map(|(_, weight)| *weight).sum();
-        if weight_sum <= f64::EPSILON {
+        if moments.weight_sum <= f64::EPSILON {
             return (1.0, 0.0);
         }
 
-        let weighted_sum: f64 = weighted.iter().map(|(ratio, weight)| ratio * weight).sum();
-        let mean_ratio = weighted_sum / weight_sum;
+        let mean_ratio = moments.mean();
 
         // Use weighted variance so recent entries influence uncertainty more.
-        let weighted_variance: f64 = weighted
-            .iter()
-            .map(|(ratio, weight)| weight * (ratio - mean_ratio).powi(2))
-            .sum::<f64>()
-            / weight_sum;
-        let std_dev = weighted_variance.sqrt();
-        let sample_count = relevant.len() as f64;
+        let std_dev = moments.variance().sqrt();
+        let sample_count = moments.samples as f64;
         let std_error = std_dev / sample_count.sqrt();
 
         // Conservative lower confidence bound to reduce over-promising.
@@ -91,44 +147,49 @@ impl AdaptiveEstimator {
         game_path: &Path,
     ) -> (f64, f64) {
         let game_key = normalize_game_path(game_path);
-        let relevant_for_game: Vec<_> = self
-            .history
-            .iter()
-            .filter(|entry| entry.algorithm == algorithm)
-            .filter(|entry| entry.estimate.estimated_saved_bytes > 0)
-            .filter(|entry| normalize_game_path(Path::new(&entry.game_path)) == game_key)
-            .collect();
-
-        if relevant_for_game.is_empty() {
-            return self.get_correction_factor(algorithm);
-        }
-
-        let now_ms = current_epoch_ms();
-        let weighted: Vec<(f64, f64)> = relevant_for_game
-            .iter()
-            .map(|entry| {
-                let ratio = entry.estimate_accuracy_ratio();
-                let age_days = now_ms.saturating_sub(entry.timestamp_ms) as f64 / MILLIS_PER_DAY;
-                let weight = (-age_days / DECAY_DAYS).exp();
-                (ratio, weight)
-            })
-            .collect();
+        let moments = self.moments(algorithm, Some(&game_key));
 
-        let weight_sum: f64 = weighted.iter().map(|(_, weight)| *weight).sum();
-        if weight_sum <= f64::EPSILON {
+        if moments.samples == 0 || moments.weight_sum <= f64::EPSILON {
             return self.get_correction_factor(algorithm);
         }
 
-        let weighted_sum: f64 = weighted.iter().map(|(ratio, weight)| ratio * weight).sum();
-        let mean_ratio = weighted_sum / weight_sum;
+        let mean_ratio = moments.mean();
 
         // Same-game fast path only lowers estimates; never raises them.
         let multiplier = mean_ratio.clamp(MIN_MULTIPLIER, 1.0);
-        let sample_count = relevant_for_game.len();
+        let sample_count = moments.samples;
         let confidence = (0.85 + 0.05 * (sample_count.saturating_sub(1)) as f64).clamp(0.85, 0.95);
 
         (multiplier, confidence)
     }
+
+    /// Relative half-width of the expected-savings range for `game_path`.
+    ///
+    /// Uses the spread of past accuracy ratios for the same game when there
+    /// are any, then the algorithm-wide spread once it has enough samples,
+    /// and `HEURISTIC_SPREAD` otherwise.
+    pub fn relative_spread_for_game(
+        &self,
+        algorithm: CompressionAlgorithm,
+        game_path: &Path,
+    ) -> f64 {
+        let game_key = normalize_game_path(game_path);
+        let game = self.moments(algorithm, Some(&game_key));
+        if game.samples > 0 && game.weight_sum > f64::EPSILON {
+            return game.relative_spread();
+        }
+
+        let all = self.moments(algorithm, None);
+        if all.samples < self.min_samples_for_confidence || all.weight_sum <= f64::EPSILON {
+            return HEURISTIC_SPREAD;
+        }
+        all.relative_spread()
+    }
+}
+
+fn decay_weight(timestamp_ms: u64, now_ms: u64) -> f64 {
+    let age_days = now_ms.saturating_sub(timestamp_ms) as f64 / MILLIS_PER_DAY;
+    (-age_days / DECAY_DAYS).exp()
 }
 
 fn current_epoch_ms() -> u64 {
@@ -171,6 +232,8 @@ mod tests {
             },
             algorithm,
             duration_ms: 1_000,
+            extensions: Vec::new(),
+            throughput: None,
         }
     }
 
@@ -261,6 +324,8 @@ mod tests {
             },
             algorithm: CompressionAlgorithm::Xpress8K,
             duration_ms: 1_000,
+            extensions: Vec::new(),
+            throughput: None,
         }];
 
         let estimator = AdaptiveEstimator::from_history(history);
@@ -292,6 +357,8 @@ mod tests {
             },
             algorithm: CompressionAlgorithm::Xpress8K,
             duration_ms: 500,
+            extensions: Vec::new(),
+            throughput: None,
         }];
 
         let estimator = AdaptiveEstimator::from_history(history);
@@ -316,4 +383,55 @@ mod tests {
         assert_eq!(multiplier, 1.0);
         assert_eq!(confidence, 0.0);
     }
+
+    #[test]
+    fn compacted_samples_count_toward_correction() {
+        let history: Vec<_> = (0..4)
+            .map(|i| entry(CompressionAlgorithm::Xpress8K, 0.5, i, 100_000))
+            .collect();
+        let aggregate = GameAggregate {
+            latest: entry(CompressionAlgorithm::Xpress8K, 0.5, 200, 100_000),
+            compressions: 8,
+            accuracy_samples: 8,
+            accuracy_sum: 4.0,
+            accuracy_sq_sum: 2.0,
+        };
+
+        let without = AdaptiveEstimator::from_history(history.clone());
+        assert_eq!(
+            without.get_correction_factor(CompressionAlgorithm::Xpress8K),
+            (1.0, 0.0)
+        );
+
+        let with = AdaptiveEstimator::from_history(history).with_aggregates(vec![aggregate]);
+        let (multiplier, confidence) = with.get_correction_factor(CompressionAlgorithm::Xpress8K);
+        assert!((multiplier - 0.5).abs() < 1e-6);
+        assert!(confidence >= CONFIDENCE_MIN);
+    }
+
+    #[test]
+    fn spread_narrows_from_heuristic_to_noisy_to_same_game_history() {
+        let ratios = [0.7, 0.9, 1.0, 1.1, 1.4, 1.8, 0.8, 1.3, 1.6, 0.95];
+        let history: Vec<_> = ratios
+            .iter()
+            .enumerate()
+            .map(|(i, ratio)| entry(CompressionAlgorithm::Xpress8K, *ratio, i as u64, 200_000))
+            .collect();
+        let unknown = Path::new(r"C:\Games\Unknown");
+
+        let empty = AdaptiveEstimator::from_history(Vec::new());
+        assert_eq!(
+            empty.relative_spread_for_game(CompressionAlgorithm::Xpress8K, unknown),
+            HEURISTIC_SPREAD
+        );
+
+        let noisy = AdaptiveEstimator::from_history(history.clone());
+        let noisy_spread = noisy.relative_spread_for_game(CompressionAlgorithm::Xpress8K, unknown);
+        assert!(noisy_spread > MIN_SPREAD && noisy_spread <= MAX_SPREAD);
+
+        let same_game = Path::new(&history[0].game_path).to_path_buf();
+        let game_spread =
+            noisy.relative_spread_for_game(CompressionAlgorithm::Xpress8K, &same_game);
+        assert_eq!(game_spread, MIN_SPREAD);
+    }
 }
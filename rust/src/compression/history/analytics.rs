@@ -0,0 +1,380 @@
+//! Read-side aggregates over compression history for the savings dashboard.
+//!
+//! Re-compressing a game records a new entry whose stats already include
+//! the earlier savings, so totals count only the newest entry per game.
+//! Time series are the exception: each bucket reports what the
+//! compressions in that period saved.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::{get_historical_stats, CompressionHistoryEntry};
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::discovery::platform::Platform;
+
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+/// 1970-01-01 was a Thursday; weeks start on Monday.
+const EPOCH_WEEKDAY_OFFSET: u64 = 3;
+
+/// Narrows `history`; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    pub game_path: Option<PathBuf>,
+    pub algorithm: Option<CompressionAlgorithm>,
+    /// Inclusive lower bound, Unix ms.
+    pub since_ms: Option<u64>,
+    /// Exclusive upper bound, Unix ms.
+    pub until_ms: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, entry: &CompressionHistoryEntry, game_key: Option<&str>) -> bool {
+        game_key.is_none_or(|key| normalize(&entry.game_path) == key)
+            && self.algorithm.is_none_or(|algo| entry.algorithm == algo)
+            && self
+                .since_ms
+                .is_none_or(|since| entry.timestamp_ms >= since)
+            && self.until_ms.is_none_or(|until| entry.timestamp_ms < until)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SavingsTotals {
+    pub bytes_saved: u64,
+    pub original_bytes: u64,
+    pub games: u64,
+}
+
+impl SavingsTotals {
+    fn add(&mut self, entry: &CompressionHistoryEntry) {
+        self.bytes_saved = self
+            .bytes_saved
+            .saturating_add(entry.actual_stats.actual_saved_bytes);
+        self.original_bytes = self
+            .original_bytes
+            .saturating_add(entry.actual_stats.original_bytes);
+        self.games += 1;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SavingsSummary {
+    pub total: SavingsTotals,
+    /// Every recorded compression, including re-compressions.
+    pub compressions_recorded: u64,
+    /// `None` for games no longer in the discovery index.
+    pub by_platform: Vec<(Option<Platform>, SavingsTotals)>,
+    pub by_algorithm: Vec<(CompressionAlgorithm, SavingsTotals)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavingsBucket {
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavingsPoint {
+    /// Start of the bucket, Unix ms (UTC).
+    pub bucket_start_ms: u64,
+    pub bytes_saved: u64,
+    pub compressions: u64,
+}
+
+/// Newest-first history entries matching `filter`.
+pub fn history(filter: &HistoryFilter) -> Vec<CompressionHistoryEntry> {
+    filter_entries(get_historical_stats(), filter)
+}
+
+/// Lifetime savings, split by platform and algorithm.
+///
+/// Games whose raw entries were pruned by retention still count through
+/// the newest compacted entry.
+pub fn total_savings_summary() -> SavingsSummary {
+    let platforms = crate::discovery::index::platforms_by_game_path();
+    let mut entries = get_historical_stats();
+    let compacted = super::retention::aggregates().games;
+    let compacted_compressions: u64 = compacted.iter().map(|game| game.compressions).sum();
+    let compacted_latest = compacted.len() as u64;
+    entries.extend(compacted.into_iter().map(|game| game.latest));
+
+    let mut summary = summarize(&entries, |key| platforms.get(key).copied());
+    summary.compressions_recorded =
+        summary.compressions_recorded - compacted_latest + compacted_compressions;
+    summary
+}
+
+/// Bytes saved per `bucket`, oldest first. Empty buckets are omitted.
+pub fn savings_over_time(bucket: SavingsBucket) -> Vec<SavingsPoint> {
+    bucket_savings(&get_historical_stats(), bucket)
+}
+
+fn normalize(game_path: &str) -> String {
+    crate::utils::normalize_path_key(Path::new(game_path))
+}
+
+fn filter_entries(
+    entries: Vec<CompressionHistoryEntry>,
+    filter: &HistoryFilter,
+) -> Vec<CompressionHistoryEntry> {
+    let game_key = filter
+        .game_path
+        .as_deref()
+        .map(crate::utils::normalize_path_key);
+    let mut matching: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| filter.matches(entry, game_key.as_deref()))
+        .collect();
+    matching.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+    if let Some(limit) = filter.limit {
+        matching.truncate(limit);
+    }
+    matching
+}
+
+fn summarize(
+    entries: &[CompressionHistoryEntry],
+    platform_of: impl Fn(&str) -> Option<Platform>,
+) -> SavingsSummary {
+    let mut latest: HashMap<String, &CompressionHistoryEntry> = HashMap::new();
+    for entry in entries {
+        latest
+            .entry(normalize(&entry.game_path))
+            .and_modify(|current| {
+                if entry.timestamp_ms > current.timestamp_ms {
+                    *current = entry;
+                }
+            })
+            .or_insert(entry);
+    }
+
+    let mut total = SavingsTotals::default();
+    let mut by_platform: HashMap<Option<Platform>, SavingsTotals> = HashMap::new();
+    let mut by_algorithm: HashMap<CompressionAlgorithm, SavingsTotals> = HashMap::new();
+    for (key, entry) in &latest {
+        total.add(entry);
+        by_platform.entry(platform_of(key)).or_default().add(entry);
+        by_algorithm.entry(entry.algorithm).or_default().add(entry);
+    }
+
+    let mut by_platform: Vec<_> = by_platform.into_iter().collect();
+    by_platform.sort_by_key(|(_, totals)| std::cmp::Reverse(totals.bytes_saved));
+    let mut by_algorithm: Vec<_> = by_algorithm.into_iter().collect();
+    by_algorithm.sort_by_key(|(_, totals)| std::cmp::Reverse(totals.bytes_saved));
+
+    SavingsSummary {
+        total,
+        compressions_recorded: entries.len() as u64,
+        by_platform,
+        by_algorithm,
+    }
+}
+
+fn bucket_savings(entries: &[CompressionHistoryEntry], bucket: SavingsBucket) -> Vec<SavingsPoint> {
+    let mut points: HashMap<u64, SavingsPoint> = HashMap::new();
+    for entry in entries {
+        let start = bucket_start_ms(entry.timestamp_ms, bucket);
+        let point = points.entry(start).or_insert(SavingsPoint {
+            bucket_start_ms: start,
+            bytes_saved: 0,
+            compressions: 0,
+        });
+        point.bytes_saved = point
+            .bytes_saved
+            .saturating_add(entry.actual_stats.actual_saved_bytes);
+        point.compressions += 1;
+    }
+    let mut points: Vec<_> = points.into_values().collect();
+    points.sort_by_key(|point| point.bucket_start_ms);
+    points
+}
+
+fn bucket_start_ms(timestamp_ms: u64, bucket: SavingsBucket) -> u64 {
+    let day = timestamp_ms / MS_PER_DAY;
+    let start_day = match bucket {
+        SavingsBucket::Day => day,
+        SavingsBucket::Week => day - (day + EPOCH_WEEKDAY_OFFSET) % 7,
+        SavingsBucket::Month => {
+            let (year, month, _) = civil_from_days(day);
+            days_from_civil(year, month, 1)
+        }
+    };
+    start_day * MS_PER_DAY
+}
+
+/// Days since 1970-01-01 to (year, month, day), proleptic Gregorian.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of [`civil_from_days`] for dates on or after 1970-01-01.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::history::{ActualStats, EstimateSnapshot};
+
+    /// 2024-03-14T12:00:00Z, a Thursday.
+    const MARCH_14_2024_NOON_MS: u64 = 1_710_417_600_000;
+
+    fn entry(
+        path: &str,
+        timestamp_ms: u64,
+        saved: u64,
+        algorithm: CompressionAlgorithm,
+    ) -> CompressionHistoryEntry {
+        CompressionHistoryEntry {
+            game_path: path.to_string(),
+            game_name: path.to_string(),
+            timestamp_ms,
+            estimate: EstimateSnapshot {
+                scanned_files: 0,
+                sampled_bytes: 0,
+                estimated_saved_bytes: 0,
+            },
+            actual_stats: ActualStats {
+                original_bytes: saved * 2,
+                compressed_bytes: saved,
+                actual_saved_bytes: saved,
+                files_processed: 1,
+            },
+            algorithm,
+            duration_ms: 1,
+            extensions: Vec::new(),
+            throughput: None,
+        }
+    }
+
+    #[test]
+    fn summary_counts_only_latest_entry_per_game() {
+        let entries = vec![
+            entry("C:/Games/A", 1_000, 100, CompressionAlgorithm::Xpress4K),
+            entry("C:/Games/A", 2_000, 150, CompressionAlgorithm::Lzx),
+            entry("C:/Games/B", 1_500, 50, CompressionAlgorithm::Xpress4K),
+        ];
+        let summary = summarize(&entries, |key| {
+            key.ends_with(['a', 'A']).then_some(Platform::Steam)
+        });
+
+        assert_eq!(summary.total.bytes_saved, 200);
+        assert_eq!(summary.total.games, 2);
+        assert_eq!(summary.compressions_recorded, 3);
+        assert_eq!(
+            summary.by_algorithm,
+            vec![
+                (
+                    CompressionAlgorithm::Lzx,
+                    SavingsTotals {
+                        bytes_saved: 150,
+                        original_bytes: 300,
+                        games: 1
+                    }
+                ),
+                (
+                    CompressionAlgorithm::Xpress4K,
+                    SavingsTotals {
+                        bytes_saved: 50,
+                        original_bytes: 100,
+                        games: 1
+                    }
+                ),
+            ]
+        );
+        assert_eq!(summary.by_platform[0].0, Some(Platform::Steam));
+        assert_eq!(summary.by_platform[1].0, None);
+    }
+
+    #[test]
+    fn filter_applies_bounds_algorithm_and_limit_newest_first() {
+        let entries = vec![
+            entry("C:/Games/A", 1_000, 1, CompressionAlgorithm::Xpress4K),
+            entry("C:/Games/A", 2_000, 2, CompressionAlgorithm::Xpress4K),
+            entry("C:/Games/A", 3_000, 3, CompressionAlgorithm::Lzx),
+            entry("C:/Games/B", 2_500, 4, CompressionAlgorithm::Xpress4K),
+        ];
+        let filtered = filter_entries(
+            entries,
+            &HistoryFilter {
+                game_path: Some(PathBuf::from("C:/Games/A")),
+                algorithm: Some(CompressionAlgorithm::Xpress4K),
+                since_ms: Some(1_000),
+                until_ms: Some(3_000),
+                limit: Some(1),
+            },
+        );
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].timestamp_ms, 2_000);
+    }
+
+    #[test]
+    fn bucket_starts_align_to_day_monday_and_month() {
+        let day_start = MARCH_14_2024_NOON_MS - 12 * 60 * 60 * 1000;
+        assert_eq!(
+            bucket_start_ms(MARCH_14_2024_NOON_MS, SavingsBucket::Day),
+            day_start
+        );
+        // Monday 2024-03-11.
+        assert_eq!(
+            bucket_start_ms(MARCH_14_2024_NOON_MS, SavingsBucket::Week),
+            day_start - 3 * MS_PER_DAY
+        );
+        // 2024-03-01.
+        assert_eq!(
+            bucket_start_ms(MARCH_14_2024_NOON_MS, SavingsBucket::Month),
+            day_start - 13 * MS_PER_DAY
+        );
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+    }
+
+    #[test]
+    fn time_series_sums_every_compression_in_bucket() {
+        let entries = vec![
+            entry(
+                "C:/Games/A",
+                MARCH_14_2024_NOON_MS,
+                10,
+                CompressionAlgorithm::Xpress4K,
+            ),
+            entry(
+                "C:/Games/A",
+                MARCH_14_2024_NOON_MS + 1,
+                5,
+                CompressionAlgorithm::Lzx,
+            ),
+            entry(
+                "C:/Games/B",
+                MARCH_14_2024_NOON_MS + 40 * MS_PER_DAY,
+                7,
+                CompressionAlgorithm::Lzx,
+            ),
+        ];
+        let points = bucket_savings(&entries, SavingsBucket::Month);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].bytes_saved, 15);
+        assert_eq!(points[0].compressions, 2);
+        assert_eq!(points[1].bytes_saved, 7);
+    }
+}
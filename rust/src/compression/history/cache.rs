@@ -1,12 +1,19 @@
+use super::retention;
 use super::CompressionHistoryEntry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::{LazyLock, Mutex, RwLock};
+use std::sync::{Condvar, LazyLock, Mutex, Once, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::storage::Database;
 
-const MAX_HISTORY_ENTRIES: usize = 1000;
 const PENDING_FLUSH_THRESHOLD: usize = 32;
 const CACHE_VERSION: u32 = 1;
+/// Quiet period after the last recorded entry before history is written.
+const PERSIST_DEBOUNCE: Duration = Duration::from_secs(2);
+/// Longest a recorded entry waits to be written while entries keep arriving.
+const MAX_PERSIST_DELAY: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryCache {
@@ -20,6 +27,35 @@ static LATEST_TIMESTAMP_INDEX: LazyLock<RwLock<Option<HashMap<String, u64>>>> =
 static PENDING_UPDATES: LazyLock<Mutex<Vec<CompressionHistoryEntry>>> =
     LazyLock::new(|| Mutex::new(Vec::new()));
 static CACHE_DIRTY: LazyLock<Mutex<bool>> = LazyLock::new(|| Mutex::new(false));
+/// Entries merged into the cache but not yet written to the database.
+static UNPERSISTED: LazyLock<Mutex<Vec<CompressionHistoryEntry>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+/// Pending background write, woken by [`schedule_persist`].
+static PERSIST_SCHEDULE: LazyLock<(Mutex<PersistSchedule>, Condvar)> =
+    LazyLock::new(|| (Mutex::new(PersistSchedule::default()), Condvar::new()));
+static PERSIST_FLUSHER: Once = Once::new();
+
+/// When the background flusher was asked to write.
+#[derive(Debug, Default, Clone, Copy)]
+struct PersistSchedule {
+    first_request: Option<Instant>,
+    last_request: Option<Instant>,
+}
+
+impl PersistSchedule {
+    /// Write once requests go quiet, but no later than the bound after the
+    /// first one.
+    fn deadline(&self) -> Option<Instant> {
+        let first = self.first_request?;
+        let last = self.last_request.unwrap_or(first);
+        Some((last + PERSIST_DEBOUNCE).min(first + MAX_PERSIST_DELAY))
+    }
+
+    fn request(&mut self, now: Instant) {
+        self.first_request.get_or_insert(now);
+        self.last_request = Some(now);
+    }
+}
 
 fn default_cache() -> HistoryCache {
     HistoryCache {
@@ -29,20 +65,18 @@ fn default_cache() -> HistoryCache {
 }
 
 fn cache_path() -> PathBuf {
-    let config_dir = dirs::config_dir()
-        .or_else(|| std::env::current_dir().ok())
-        .unwrap_or_else(|| PathBuf::from("."));
-    config_dir
-        .join("compact_games")
-        .join("compression_history.json")
+    crate::paths::data_dir_or_fallback().join("compression_history.json")
 }
 
 fn normalize_game_path(path: &Path) -> String {
     crate::utils::normalize_path_key(path)
 }
 
+/// Latest timestamp per game, including games whose raw entries were
+/// compacted away.
 fn build_latest_timestamp_index(entries: &[CompressionHistoryEntry]) -> HashMap<String, u64> {
-    let mut latest_by_path = HashMap::with_capacity(entries.len());
+    let mut latest_by_path: HashMap<String, u64> =
+        retention::compacted_timestamps().into_iter().collect();
     for entry in entries {
         let key = normalize_game_path(Path::new(&entry.game_path));
         latest_by_path
@@ -74,7 +108,12 @@ fn ensure_loaded() {
     }
 
     let path = cache_path();
-    let cache = if path.exists() {
+    let cache = if let Some(entries) = load_from_database(&path) {
+        HistoryCache {
+            entries,
+            version: CACHE_VERSION,
+        }
+    } else if path.exists() {
         std::fs::read_to_string(&path)
             .ok()
             .and_then(|s| serde_json::from_str(&s).ok())
@@ -118,6 +157,56 @@ pub fn record_compression(entry: CompressionHistoryEntry) {
         drop(pending);
         flush_pending();
     }
+    schedule_persist();
+}
+
+/// Write history in the background once recording goes quiet, so callers
+/// on the UI thread never serialize a large history themselves.
+pub fn schedule_persist() {
+    PERSIST_FLUSHER.call_once(|| {
+        if let Err(e) = std::thread::Builder::new()
+            .name("compact-games-history-flush".to_owned())
+            .spawn(run_persist_flusher)
+        {
+            log::warn!("Failed to start history flusher; writes stay manual: {e}");
+        }
+    });
+    let (lock, wake) = &*PERSIST_SCHEDULE;
+    lock.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .request(Instant::now());
+    wake.notify_one();
+}
+
+/// Write history now, dropping any scheduled background write. For
+/// shutdown, where a pending debounce would otherwise be lost.
+pub fn flush_now() {
+    *PERSIST_SCHEDULE.0.lock().unwrap_or_else(|e| e.into_inner()) = PersistSchedule::default();
+    persist_if_dirty();
+}
+
+fn run_persist_flusher() {
+    let (lock, wake) = &*PERSIST_SCHEDULE;
+    loop {
+        let mut schedule = lock.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let Some(deadline) = schedule.deadline() else {
+                schedule = wake.wait(schedule).unwrap_or_else(|e| e.into_inner());
+                continue;
+            };
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            schedule = wake
+                .wait_timeout(schedule, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        *schedule = PersistSchedule::default();
+        drop(schedule);
+        persist_if_dirty();
+    }
 }
 
 /// Flush pending entries into the in-memory cache.
@@ -133,20 +222,63 @@ fn flush_pending() {
         return;
     };
 
-    cache.entries.extend(pending.drain(..));
-
-    // Keep only newest entries by timestamp.
-    if cache.entries.len() > MAX_HISTORY_ENTRIES {
-        cache
-            .entries
-            .sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
-        cache.entries.truncate(MAX_HISTORY_ENTRIES);
+    if Database::global().is_some() {
+        UNPERSISTED.lock().unwrap().extend(pending.iter().cloned());
     }
+    cache.entries.extend(pending.drain(..));
+    apply_retention(cache);
 
     *LATEST_TIMESTAMP_INDEX.write().unwrap() = Some(build_latest_timestamp_index(&cache.entries));
     *CACHE_DIRTY.lock().unwrap() = true;
 }
 
+/// Evict entries outside the retention policy, compacting them first.
+/// Returns how many were evicted.
+fn apply_retention(cache: &mut HistoryCache) -> usize {
+    let policy = retention::retention();
+    let entries = std::mem::take(&mut cache.entries);
+    let (kept, evicted) = policy.split(entries, crate::utils::unix_now_ms());
+    cache.entries = kept;
+    let evicted_count = evicted.len();
+    retention::compact(evicted);
+    evicted_count
+}
+
+/// Result of an explicit `prune_history` pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PruneOutcome {
+    pub entries_removed: usize,
+    pub entries_kept: usize,
+}
+
+/// Apply the retention policy now rather than on the next flush, and
+/// persist the result.
+pub fn prune_history() -> PruneOutcome {
+    flush_pending();
+    ensure_loaded();
+    let outcome = {
+        let mut cache_guard = HISTORY_CACHE.write().unwrap();
+        let Some(cache) = cache_guard.as_mut() else {
+            return PruneOutcome {
+                entries_removed: 0,
+                entries_kept: 0,
+            };
+        };
+        let entries_removed = apply_retention(cache);
+        *LATEST_TIMESTAMP_INDEX.write().unwrap() =
+            Some(build_latest_timestamp_index(&cache.entries));
+        PruneOutcome {
+            entries_removed,
+            entries_kept: cache.entries.len(),
+        }
+    };
+    // The database prunes by the same policy on every append, so a write
+    // is needed even when nothing new was recorded.
+    *CACHE_DIRTY.lock().unwrap() = true;
+    persist_if_dirty();
+    outcome
+}
+
 /// Get historical compression statistics.
 pub fn get_historical_stats() -> Vec<CompressionHistoryEntry> {
     flush_pending();
@@ -202,7 +334,32 @@ pub fn with_latest_compression_timestamps_by_path<R>(
     }
 }
 
+/// Newest-first history for one game, at most `limit` entries.
+///
+/// Served from the database path index when available.
+pub fn history_for_game(game_path: &Path, limit: usize) -> Vec<CompressionHistoryEntry> {
+    persist_if_dirty();
+    if let Some(database) = Database::global() {
+        match database.history_for_path(game_path, limit) {
+            Ok(entries) => return entries,
+            Err(e) => log::warn!("Compression history query failed: {e}"),
+        }
+    }
+
+    let target = normalize_game_path(game_path);
+    let mut entries: Vec<_> = get_historical_stats()
+        .into_iter()
+        .filter(|entry| normalize_game_path(Path::new(&entry.game_path)) == target)
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+    entries.truncate(limit);
+    entries
+}
+
 /// Persist cache to disk.
+///
+/// With the database available only entries recorded since the last
+/// persist are written; otherwise the whole cache is rewritten as JSON.
 pub fn persist_if_dirty() {
     // Pending entries can exist below threshold; flush first to avoid data loss.
     flush_pending();
@@ -210,6 +367,25 @@ pub fn persist_if_dirty() {
         return;
     }
 
+    if let Some(database) = Database::global() {
+        let unpersisted = std::mem::take(&mut *UNPERSISTED.lock().unwrap());
+        let policy = retention::retention();
+        let min_timestamp_ms = policy.min_timestamp_ms(crate::utils::unix_now_ms());
+        match database.append_history(&unpersisted, policy.max_entries, min_timestamp_ms) {
+            Ok(()) => {
+                *CACHE_DIRTY.lock().unwrap() = false;
+                persist_discovery_metadata_if_dirty();
+            }
+            Err(e) => {
+                log::warn!("Failed to persist compression history: {e}");
+                let mut guard = UNPERSISTED.lock().unwrap();
+                let newer = std::mem::replace(&mut *guard, unpersisted);
+                guard.extend(newer);
+            }
+        }
+        return;
+    }
+
     ensure_loaded();
     let guard = HISTORY_CACHE.read().unwrap();
     let Some(cache) = guard.as_ref() else {
@@ -229,6 +405,29 @@ pub fn persist_if_dirty() {
     }
 }
 
+/// Load history from the database, importing the legacy JSON cache first.
+///
+/// Returns `None` when the database is unavailable so callers fall back
+/// to the JSON file.
+fn load_from_database(legacy_path: &Path) -> Option<Vec<CompressionHistoryEntry>> {
+    let database = Database::global()?;
+    let max_entries = retention::retention().max_entries;
+    if let Err(e) = database.import_legacy_history(legacy_path, max_entries) {
+        log::warn!("Failed to import legacy compression history: {e}");
+    }
+    match database.history_entries(max_entries) {
+        Ok(mut entries) => {
+            // Cache order is oldest-first, matching append order.
+            entries.reverse();
+            Some(entries)
+        }
+        Err(e) => {
+            log::warn!("Failed to load compression history from database: {e}");
+            None
+        }
+    }
+}
+
 fn evict_stale_discovery_metadata(game_path: &str) {
     let path = Path::new(game_path);
     evict_stale_discovery_metadata_for_path(path);
@@ -259,6 +458,7 @@ mod tests {
     use crate::discovery::cache::{self as discovery_cache, CachedGameStats};
     use crate::discovery::index as discovery_index;
     use crate::discovery::platform::{GameInfo, Platform};
+    use crate::discovery::storage::DriveType;
     use crate::discovery::test_sync::lock_discovery_test;
 
     fn unique_test_path(prefix: &str) -> PathBuf {
@@ -287,9 +487,28 @@ mod tests {
             },
             algorithm: CompressionAlgorithm::Xpress8K,
             duration_ms: 100,
+            extensions: Vec::new(),
+            throughput: None,
         }
     }
 
+    #[test]
+    fn persist_waits_for_quiet_but_not_past_the_bound() {
+        let start = Instant::now();
+        let mut schedule = PersistSchedule::default();
+        assert_eq!(schedule.deadline(), None);
+
+        schedule.request(start);
+        assert_eq!(schedule.deadline(), Some(start + PERSIST_DEBOUNCE));
+
+        let later = start + Duration::from_secs(1);
+        schedule.request(later);
+        assert_eq!(schedule.deadline(), Some(later + PERSIST_DEBOUNCE));
+
+        schedule.request(start + Duration::from_secs(9));
+        assert_eq!(schedule.deadline(), Some(start + MAX_PERSIST_DELAY));
+    }
+
     #[test]
     fn latest_compression_timestamp_returns_none_for_unknown_path() {
         let path = unique_test_path("NoHistory");
@@ -346,9 +565,19 @@ mod tests {
                 is_compressed: true,
                 is_directstorage: false,
                 is_unsupported: false,
+                is_protected_package: false,
+                is_unsupported_filesystem: false,
+                uses_kernel_anticheat: false,
+                drive_type: DriveType::Unknown,
+                has_cloud_placeholders: false,
+                has_encrypted_or_sparse_files: false,
                 excluded: false,
                 steam_app_id: None,
                 last_played: None,
+                last_compressed: None,
+                playtime_minutes: None,
+                epic_catalog_item_id: None,
+                artwork_path: None,
             },
         );
 
@@ -0,0 +1,166 @@
+//! Decompressions run through the app.
+//!
+//! Kept apart from the compression history: its entries feed savings
+//! totals, estimate learning and "last compressed" lookups, none of which
+//! a decompression should move.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::compression::engine::DecompressionStats;
+use crate::utils::normalize_path_key;
+
+const HISTORY_FILE_NAME: &str = "decompression_history.json";
+/// Oldest entries beyond this are dropped.
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecompressionHistoryEntry {
+    pub game_path: String,
+    pub game_name: String,
+    pub timestamp_ms: u64,
+    pub stats: DecompressionStats,
+}
+
+#[cfg(not(test))]
+static HISTORY_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+/// Oldest first, matching append order.
+static HISTORY: LazyLock<RwLock<Vec<DecompressionHistoryEntry>>> =
+    LazyLock::new(|| RwLock::new(load_history()));
+
+/// Record a finished decompression and persist the history.
+pub fn record_decompression(entry: DecompressionHistoryEntry) {
+    with_history_write(|history| {
+        history.push(entry);
+        let excess = history.len().saturating_sub(MAX_ENTRIES);
+        history.drain(..excess);
+    });
+    persist();
+}
+
+/// Newest-first decompressions of one game, at most `limit` entries.
+pub fn decompression_history_for_game(
+    game_path: &Path,
+    limit: usize,
+) -> Vec<DecompressionHistoryEntry> {
+    let target = normalize_path_key(game_path);
+    with_history_read(|history| {
+        history
+            .iter()
+            .rev()
+            .filter(|entry| normalize_path_key(Path::new(&entry.game_path)) == target)
+            .take(limit)
+            .cloned()
+            .collect()
+    })
+}
+
+fn with_history_read<R>(f: impl FnOnce(&Vec<DecompressionHistoryEntry>) -> R) -> R {
+    let guard = HISTORY.read().unwrap_or_else(|poisoned| {
+        log::warn!("Decompression history lock poisoned (read); recovering");
+        poisoned.into_inner()
+    });
+    f(&guard)
+}
+
+fn with_history_write<R>(f: impl FnOnce(&mut Vec<DecompressionHistoryEntry>) -> R) -> R {
+    let mut guard = HISTORY.write().unwrap_or_else(|poisoned| {
+        log::warn!("Decompression history lock poisoned (write); recovering");
+        poisoned.into_inner()
+    });
+    f(&mut guard)
+}
+
+fn persist() {
+    let snapshot = with_history_read(Clone::clone);
+    let result = history_path().and_then(|path| {
+        let json = serde_json::to_vec(&snapshot).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&path, &json)
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to persist decompression history: {e}");
+    }
+}
+
+fn load_history() -> Vec<DecompressionHistoryEntry> {
+    let Ok(path) = history_path() else {
+        return Vec::new();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse decompression history: {e}");
+        Vec::new()
+    })
+}
+
+fn history_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-decompression-history-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(HISTORY_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !HISTORY_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            HISTORY_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(HISTORY_FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(game_path: &str, timestamp_ms: u64) -> DecompressionHistoryEntry {
+        DecompressionHistoryEntry {
+            game_path: game_path.to_owned(),
+            game_name: "Game".to_owned(),
+            timestamp_ms,
+            stats: DecompressionStats {
+                files_decompressed: 10,
+                bytes_restored_logical: 1_000,
+                bytes_grown_physical: 400,
+                duration_ms: 50,
+            },
+        }
+    }
+
+    #[test]
+    fn history_for_game_is_newest_first() {
+        let game = r"C:\Games\DecompressionHistoryOrder";
+        record_decompression(entry(game, 1_000));
+        record_decompression(entry(r"C:\Games\Other", 1_500));
+        record_decompression(entry(game, 2_000));
+
+        let history = decompression_history_for_game(Path::new(game), 10);
+        let timestamps: Vec<_> = history.iter().map(|entry| entry.timestamp_ms).collect();
+        assert_eq!(timestamps, vec![2_000, 1_000]);
+        assert_eq!(decompression_history_for_game(Path::new(game), 1).len(), 1);
+    }
+}
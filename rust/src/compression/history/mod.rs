@@ -1,15 +1,25 @@
 use serde::{Deserialize, Serialize};
 
 pub mod adaptive;
+pub mod analytics;
 pub mod cache;
+pub mod decompression;
+pub mod retention;
+pub mod skip_list;
 
 pub use cache::{
-    get_historical_stats, is_newer_than, latest_compression_timestamp_ms,
-    latest_compression_timestamps_by_path, persist_if_dirty, record_compression,
-    with_latest_compression_timestamps_by_path,
+    flush_now, get_historical_stats, history_for_game, is_newer_than,
+    latest_compression_timestamp_ms, latest_compression_timestamps_by_path, persist_if_dirty,
+    prune_history, record_compression, schedule_persist,
+    with_latest_compression_timestamps_by_path, PruneOutcome,
 };
 
 use super::algorithm::CompressionAlgorithm;
+use super::engine::ExtensionStats;
+use super::thread_policy::ThroughputSample;
+
+/// Keeps history rows small; the tail of tiny extensions carries no signal.
+const MAX_EXTENSIONS_PER_ENTRY: usize = 16;
 
 /// Single compression history entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +37,17 @@ pub struct CompressionHistoryEntry {
     // Algorithm used
     pub algorithm: CompressionAlgorithm,
     pub duration_ms: u64,
+
+    /// Largest extensions by original size, capped at
+    /// `MAX_EXTENSIONS_PER_ENTRY`. Empty for entries recorded before
+    /// per-extension tallies existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<ExtensionStats>,
+
+    /// Rate the run achieved and the worker count it used; feeds
+    /// per-volume parallelism tuning. `None` for short or throttled runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput: Option<ThroughputSample>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,6 +103,13 @@ impl CompressionHistoryEntry {
             },
             algorithm,
             duration_ms: stats.duration_ms,
+            extensions: stats
+                .extensions
+                .iter()
+                .take(MAX_EXTENSIONS_PER_ENTRY)
+                .cloned()
+                .collect(),
+            throughput: stats.throughput,
         }
     }
 }
@@ -0,0 +1,387 @@
+//! History retention and compaction.
+//!
+//! Raw entries are kept up to a count and an age limit. Entries that fall
+//! outside either limit are first folded into per-game and per-extension
+//! aggregates, so adaptive estimation, lifetime savings and "last
+//! compressed" timestamps survive the pruning of the rows they came from.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use super::CompressionHistoryEntry;
+use crate::compression::algorithm::CompressionAlgorithm;
+
+const RETENTION_FILE_NAME: &str = "history_retention.json";
+const AGGREGATES_FILE_NAME: &str = "compression_history_aggregates.json";
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+const DEFAULT_MAX_AGE_DAYS: u32 = 365;
+/// Adaptive estimation needs a few dozen raw samples per algorithm.
+pub const MIN_RETAINED_ENTRIES: usize = 50;
+pub const MIN_RETENTION_DAYS: u32 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryRetention {
+    pub max_entries: usize,
+    /// `None` keeps entries regardless of age.
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_age_days: Some(DEFAULT_MAX_AGE_DAYS),
+        }
+    }
+}
+
+impl HistoryRetention {
+    /// Raise limits that would starve adaptive estimation to their floors.
+    pub fn sanitized(self) -> Self {
+        Self {
+            max_entries: self.max_entries.max(MIN_RETAINED_ENTRIES),
+            max_age_days: self.max_age_days.map(|days| days.max(MIN_RETENTION_DAYS)),
+        }
+    }
+
+    /// Oldest timestamp still retained at `now_ms`; 0 when age is unbounded.
+    pub fn min_timestamp_ms(&self, now_ms: u64) -> u64 {
+        self.max_age_days.map_or(0, |days| {
+            now_ms.saturating_sub(u64::from(days).saturating_mul(MS_PER_DAY))
+        })
+    }
+
+    /// Split `entries` into the retained and the evicted ones.
+    ///
+    /// Entries are returned untouched when nothing needs evicting;
+    /// otherwise both halves come back newest-first.
+    pub fn split(
+        &self,
+        mut entries: Vec<CompressionHistoryEntry>,
+        now_ms: u64,
+    ) -> (Vec<CompressionHistoryEntry>, Vec<CompressionHistoryEntry>) {
+        let min_timestamp_ms = self.min_timestamp_ms(now_ms);
+        if entries.len() <= self.max_entries
+            && entries
+                .iter()
+                .all(|entry| entry.timestamp_ms >= min_timestamp_ms)
+        {
+            return (entries, Vec::new());
+        }
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+        let keep = entries
+            .iter()
+            .position(|entry| entry.timestamp_ms < min_timestamp_ms)
+            .unwrap_or(entries.len())
+            .min(self.max_entries);
+        let evicted = entries.split_off(keep);
+        (entries, evicted)
+    }
+}
+
+/// Everything known about one game's pruned entries for one algorithm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameAggregate {
+    /// Newest compacted entry, without its extension breakdown.
+    pub latest: CompressionHistoryEntry,
+    pub compressions: u64,
+    /// Compacted entries that carried an estimate; only these feed
+    /// adaptive estimation.
+    pub accuracy_samples: u64,
+    pub accuracy_sum: f64,
+    pub accuracy_sq_sum: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionAggregate {
+    pub extension: String,
+    pub algorithm: CompressionAlgorithm,
+    pub files: u64,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryAggregates {
+    #[serde(default)]
+    pub games: Vec<GameAggregate>,
+    #[serde(default)]
+    pub extensions: Vec<ExtensionAggregate>,
+}
+
+impl HistoryAggregates {
+    /// Fold one raw entry into the aggregates.
+    pub fn absorb(&mut self, mut entry: CompressionHistoryEntry) {
+        for stats in std::mem::take(&mut entry.extensions) {
+            let position = self.extensions.iter().position(|aggregate| {
+                aggregate.algorithm == entry.algorithm && aggregate.extension == stats.extension
+            });
+            let aggregate = match position {
+                Some(index) => &mut self.extensions[index],
+                None => {
+                    self.extensions.push(ExtensionAggregate {
+                        extension: stats.extension.clone(),
+                        algorithm: entry.algorithm,
+                        files: 0,
+                        original_bytes: 0,
+                        compressed_bytes: 0,
+                    });
+                    self.extensions.last_mut().expect("just pushed")
+                }
+            };
+            aggregate.files = aggregate.files.saturating_add(stats.files);
+            aggregate.original_bytes = aggregate
+                .original_bytes
+                .saturating_add(stats.original_bytes);
+            aggregate.compressed_bytes = aggregate
+                .compressed_bytes
+                .saturating_add(stats.compressed_bytes);
+        }
+
+        let has_estimate = entry.estimate.estimated_saved_bytes > 0;
+        let ratio = entry.estimate_accuracy_ratio();
+        let key = normalize(&entry.game_path);
+        let position = self.games.iter().position(|game| {
+            game.latest.algorithm == entry.algorithm && normalize(&game.latest.game_path) == key
+        });
+        let game = match position {
+            Some(index) => {
+                let game = &mut self.games[index];
+                if entry.timestamp_ms >= game.latest.timestamp_ms {
+                    game.latest = entry;
+                }
+                game
+            }
+            None => {
+                self.games.push(GameAggregate {
+                    latest: entry,
+                    compressions: 0,
+                    accuracy_samples: 0,
+                    accuracy_sum: 0.0,
+                    accuracy_sq_sum: 0.0,
+                });
+                self.games.last_mut().expect("just pushed")
+            }
+        };
+        game.compressions += 1;
+        if has_estimate {
+            game.accuracy_samples += 1;
+            game.accuracy_sum += ratio;
+            game.accuracy_sq_sum += ratio * ratio;
+        }
+    }
+}
+
+static RETENTION: LazyLock<RwLock<HistoryRetention>> =
+    LazyLock::new(|| RwLock::new(load_json(RETENTION_FILE_NAME).unwrap_or_default()));
+static AGGREGATES: LazyLock<RwLock<HistoryAggregates>> =
+    LazyLock::new(|| RwLock::new(load_json(AGGREGATES_FILE_NAME).unwrap_or_default()));
+
+/// Current retention policy.
+pub fn retention() -> HistoryRetention {
+    *RETENTION.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Replace and persist the retention policy. Returns the policy actually
+/// applied after limits are raised to their floors. Takes effect on the
+/// next flush or `prune`.
+pub fn set_retention(retention: HistoryRetention) -> std::io::Result<HistoryRetention> {
+    let retention = retention.sanitized();
+    save_json(RETENTION_FILE_NAME, &retention)?;
+    *RETENTION.write().unwrap_or_else(|e| e.into_inner()) = retention;
+    Ok(retention)
+}
+
+/// Snapshot of everything compacted so far.
+pub fn aggregates() -> HistoryAggregates {
+    AGGREGATES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Newest compacted timestamp per normalized game path.
+pub fn compacted_timestamps() -> Vec<(String, u64)> {
+    let guard = AGGREGATES.read().unwrap_or_else(|e| e.into_inner());
+    guard
+        .games
+        .iter()
+        .map(|game| (normalize(&game.latest.game_path), game.latest.timestamp_ms))
+        .collect()
+}
+
+/// Fold `evicted` into the aggregates and persist them.
+pub(super) fn compact(evicted: Vec<CompressionHistoryEntry>) {
+    if evicted.is_empty() {
+        return;
+    }
+    let count = evicted.len();
+    let snapshot = {
+        let mut guard = AGGREGATES.write().unwrap_or_else(|e| e.into_inner());
+        for entry in evicted {
+            guard.absorb(entry);
+        }
+        guard.clone()
+    };
+    log::info!("Compacted {count} compression history entries");
+    if let Err(e) = save_json(AGGREGATES_FILE_NAME, &snapshot) {
+        log::warn!("Failed to persist compression history aggregates: {e}");
+    }
+}
+
+fn normalize(game_path: &str) -> String {
+    crate::utils::normalize_path_key(Path::new(game_path))
+}
+
+fn load_json<T: for<'de> Deserialize<'de>>(file_name: &str) -> Option<T> {
+    let contents = fs::read_to_string(config_path(file_name).ok()?).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| log::warn!("Failed to parse {file_name}: {e}"))
+        .ok()
+}
+
+fn save_json<T: Serialize>(file_name: &str, value: &T) -> std::io::Result<()> {
+    let path = config_path(file_name)?;
+    let json = serde_json::to_string(value).map_err(std::io::Error::other)?;
+    crate::utils::atomic_write(&path, json.as_bytes())
+}
+
+fn config_path(file_name: &str) -> std::io::Result<PathBuf> {
+    #[cfg(test)]
+    let dir = {
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            std::env::temp_dir().join(format!(
+                "compact-games-history-retention-tests-{}",
+                std::process::id()
+            ))
+        });
+        TEST_CONFIG_DIR.clone()
+    };
+
+    #[cfg(not(test))]
+    let dir = crate::paths::data_dir()?;
+
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::engine::ExtensionStats;
+    use crate::compression::history::{ActualStats, EstimateSnapshot};
+
+    fn entry(
+        game_path: &str,
+        timestamp_ms: u64,
+        estimated: u64,
+        saved: u64,
+    ) -> CompressionHistoryEntry {
+        CompressionHistoryEntry {
+            game_path: game_path.to_owned(),
+            game_name: "Test Game".to_owned(),
+            timestamp_ms,
+            estimate: EstimateSnapshot {
+                scanned_files: 10,
+                sampled_bytes: 10_000,
+                estimated_saved_bytes: estimated,
+            },
+            actual_stats: ActualStats {
+                original_bytes: 10_000,
+                compressed_bytes: 10_000 - saved,
+                actual_saved_bytes: saved,
+                files_processed: 10,
+            },
+            algorithm: CompressionAlgorithm::Xpress8K,
+            duration_ms: 100,
+            extensions: vec![ExtensionStats {
+                extension: "pak".to_owned(),
+                files: 2,
+                original_bytes: 8_000,
+                compressed_bytes: 6_000,
+                ..ExtensionStats::default()
+            }],
+            throughput: None,
+        }
+    }
+
+    #[test]
+    fn split_evicts_past_count_and_age_limits() {
+        let now = 400 * MS_PER_DAY;
+        let retention = HistoryRetention {
+            max_entries: 2,
+            max_age_days: Some(30),
+        };
+        let entries = vec![
+            entry(r"C:\Games\A", now - 40 * MS_PER_DAY, 0, 1),
+            entry(r"C:\Games\B", now - MS_PER_DAY, 0, 1),
+            entry(r"C:\Games\C", now - 2 * MS_PER_DAY, 0, 1),
+            entry(r"C:\Games\D", now - 3 * MS_PER_DAY, 0, 1),
+        ];
+
+        let (kept, evicted) = retention.split(entries, now);
+        let paths = |entries: &[CompressionHistoryEntry]| {
+            entries
+                .iter()
+                .map(|entry| entry.game_path.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&kept), vec![r"C:\Games\B", r"C:\Games\C"]);
+        assert_eq!(paths(&evicted), vec![r"C:\Games\D", r"C:\Games\A"]);
+
+        let (kept, evicted) = HistoryRetention::default().split(kept, now);
+        assert_eq!(kept.len(), 2);
+        assert!(evicted.is_empty());
+    }
+
+    #[test]
+    fn sanitized_raises_limits_to_floors() {
+        let retention = HistoryRetention {
+            max_entries: 1,
+            max_age_days: Some(1),
+        }
+        .sanitized();
+        assert_eq!(retention.max_entries, MIN_RETAINED_ENTRIES);
+        assert_eq!(retention.max_age_days, Some(MIN_RETENTION_DAYS));
+        assert_eq!(
+            HistoryRetention {
+                max_age_days: None,
+                ..retention
+            }
+            .min_timestamp_ms(u64::MAX),
+            0
+        );
+    }
+
+    #[test]
+    fn absorb_keeps_latest_entry_and_accuracy_moments_per_game() {
+        let mut aggregates = HistoryAggregates::default();
+        aggregates.absorb(entry(r"C:\Games\A", 2, 1_000, 2_000));
+        aggregates.absorb(entry(r"c:\games\a\", 1, 1_000, 1_000));
+        aggregates.absorb(entry(r"C:\Games\A", 3, 0, 3_000));
+        aggregates.absorb(entry(r"C:\Games\B", 1, 1_000, 500));
+
+        assert_eq!(aggregates.games.len(), 2);
+        let game = &aggregates.games[0];
+        assert_eq!(game.compressions, 3);
+        assert_eq!(game.latest.timestamp_ms, 3);
+        assert!(game.latest.extensions.is_empty());
+        assert_eq!(game.accuracy_samples, 2);
+        assert!((game.accuracy_sum - 3.0).abs() < 1e-9);
+        assert!((game.accuracy_sq_sum - 5.0).abs() < 1e-9);
+
+        assert_eq!(
+            aggregates.extensions,
+            vec![ExtensionAggregate {
+                extension: "pak".to_owned(),
+                algorithm: CompressionAlgorithm::Xpress8K,
+                files: 8,
+                original_bytes: 32_000,
+                compressed_bytes: 24_000,
+            }]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Extensions a game's own history shows are not worth compressing.
+//!
+//! Some titles ship pre-compressed assets (Bink video, Opus audio,
+//! Oodle-packed archives) that WOF rejects file after file. Once a run
+//! finds an extension almost entirely not beneficial, later runs and
+//! estimates for that game skip it instead of reading and compressing
+//! every file again only to throw the result away.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::CompressionHistoryEntry;
+use crate::compression::algorithm::CompressionAlgorithm;
+
+/// Fewer files than this is too little evidence to stop trying.
+const MIN_SKIP_FILES: u64 = 8;
+/// Share of an extension's files that must have been not beneficial.
+const MIN_NOT_BENEFICIAL_SHARE: f64 = 0.9;
+/// Recent entries searched for a run with the same algorithm.
+const HISTORY_LOOKBACK: usize = 8;
+
+/// Extensions the latest `algorithm` run on `game_path` found not
+/// beneficial. Empty when the game has no such run.
+pub fn learned_skip_extensions(
+    game_path: &Path,
+    algorithm: CompressionAlgorithm,
+) -> HashSet<String> {
+    super::history_for_game(game_path, HISTORY_LOOKBACK)
+        .iter()
+        .find(|entry| entry.algorithm == algorithm)
+        .map(skip_extensions)
+        .unwrap_or_default()
+}
+
+/// Extensions that `entry` recorded as not beneficial.
+pub fn skip_extensions(entry: &CompressionHistoryEntry) -> HashSet<String> {
+    entry
+        .extensions
+        .iter()
+        .filter(|stats| {
+            stats.files >= MIN_SKIP_FILES
+                && stats.not_beneficial_files as f64
+                    >= stats.files as f64 * MIN_NOT_BENEFICIAL_SHARE
+        })
+        .map(|stats| stats.extension.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::engine::ExtensionStats;
+    use crate::compression::history::{ActualStats, EstimateSnapshot};
+
+    fn stats(extension: &str, files: u64, not_beneficial_files: u64) -> ExtensionStats {
+        ExtensionStats {
+            extension: extension.to_owned(),
+            files,
+            original_bytes: files * 1_000_000,
+            compressed_bytes: files * 900_000,
+            not_beneficial_files,
+        }
+    }
+
+    fn entry(
+        algorithm: CompressionAlgorithm,
+        extensions: Vec<ExtensionStats>,
+    ) -> CompressionHistoryEntry {
+        CompressionHistoryEntry {
+            game_path: r"C:\Games\SkipList".to_owned(),
+            game_name: "SkipList".to_owned(),
+            timestamp_ms: 1,
+            estimate: EstimateSnapshot {
+                scanned_files: 0,
+                sampled_bytes: 0,
+                estimated_saved_bytes: 0,
+            },
+            actual_stats: ActualStats {
+                original_bytes: 0,
+                compressed_bytes: 0,
+                actual_saved_bytes: 0,
+                files_processed: 0,
+            },
+            algorithm,
+            duration_ms: 0,
+            extensions,
+            throughput: None,
+        }
+    }
+
+    #[test]
+    fn skips_extensions_that_were_mostly_not_beneficial() {
+        let entry = entry(
+            CompressionAlgorithm::Xpress8K,
+            vec![
+                stats("bk2", 40, 40),
+                stats("ogg", 20, 19),
+                stats("pak", 30, 12),
+                stats("webm", 4, 4),
+            ],
+        );
+
+        let skipped = skip_extensions(&entry);
+        assert!(skipped.contains("bk2"));
+        assert!(skipped.contains("ogg"));
+        assert!(!skipped.contains("pak"));
+        assert!(!skipped.contains("webm"), "too few files to learn from");
+    }
+
+    #[test]
+    fn entries_without_counts_skip_nothing() {
+        let legacy: CompressionHistoryEntry = serde_json::from_str(
+            r#"{"game_path":"C:\\Games\\Old","game_name":"Old","timestamp_ms":1,
+                "estimate":{"scanned_files":0,"sampled_bytes":0,"estimated_saved_bytes":0},
+                "actual_stats":{"original_bytes":0,"compressed_bytes":0,"actual_saved_bytes":0,"files_processed":0},
+                "algorithm":"Xpress8K","duration_ms":0,
+                "extensions":[{"extension":"bk2","files":40,"original_bytes":40,"compressed_bytes":40}]}"#,
+        )
+        .unwrap();
+        assert!(skip_extensions(&legacy).is_empty());
+    }
+}
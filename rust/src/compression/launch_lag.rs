@@ -0,0 +1,365 @@
+//! Launch-time tracking for compressed games.
+//!
+//! WOF decompresses on read, so a game that streams a lot at startup can
+//! launch slower once compressed. Launch times are measured from the
+//! moment the game's process appears until its disk reads settle, stored
+//! per game split by whether the install was compressed, and compared:
+//! a game whose compressed launches are clearly slower than its earlier
+//! uncompressed ones is worth decompressing.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::{normalize_path_key, unix_now_ms};
+
+const TIMINGS_FILE_NAME: &str = "launch_timings.json";
+/// Recent launches kept per game and state; older ones drop off.
+const MAX_SAMPLES: usize = 5;
+const MAX_TRACKED_GAMES: usize = 2048;
+/// Launches needed on each side before comparing; one slow cold boot is
+/// not a trend.
+const MIN_SAMPLES: usize = 2;
+/// Compressed launches must be this much slower than the baseline...
+const SIGNIFICANT_SLOWDOWN_RATIO: f64 = 1.25;
+/// ...and by at least this long, so short launches do not trip on noise.
+const MIN_SLOWDOWN_MS: u64 = 3_000;
+
+/// Reads below this rate count as idle.
+const STEADY_READ_BYTES_PER_SEC: f64 = 4.0 * 1024.0 * 1024.0;
+/// How long reads must stay idle before the launch counts as finished.
+const STEADY_WINDOW: Duration = Duration::from_secs(5);
+/// Launches still loading after this are not measured.
+const MAX_LAUNCH_DURATION: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GameLaunches {
+    #[serde(default)]
+    uncompressed_ms: Vec<u64>,
+    #[serde(default)]
+    compressed_ms: Vec<u64>,
+    updated_at_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TimingsFile {
+    #[serde(default)]
+    entries: HashMap<String, GameLaunches>,
+}
+
+#[cfg(not(test))]
+static TIMINGS_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+static TIMINGS: LazyLock<RwLock<TimingsFile>> = LazyLock::new(|| RwLock::new(load_timings()));
+
+/// A game whose compressed launches are significantly slower than its
+/// uncompressed baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchLagReport {
+    pub game_path: String,
+    /// Median uncompressed launch.
+    pub baseline_ms: u64,
+    /// Median compressed launch.
+    pub compressed_ms: u64,
+    /// `compressed_ms / baseline_ms`.
+    pub slowdown_ratio: f64,
+}
+
+/// Store one measured launch of the game at `game_path`.
+pub fn record_launch(game_path: &Path, duration: Duration, compressed: bool) {
+    let key = normalize_path_key(game_path);
+    let duration_ms = duration.as_millis() as u64;
+    with_timings_write(|timings| {
+        if timings.entries.len() >= MAX_TRACKED_GAMES && !timings.entries.contains_key(&key) {
+            evict_oldest(timings);
+        }
+        let entry = timings.entries.entry(key).or_default();
+        let samples = if compressed {
+            &mut entry.compressed_ms
+        } else {
+            &mut entry.uncompressed_ms
+        };
+        samples.push(duration_ms);
+        if samples.len() > MAX_SAMPLES {
+            samples.remove(0);
+        }
+        entry.updated_at_ms = unix_now_ms();
+    });
+    log::info!(
+        "Launch of {} took {duration_ms} ms ({})",
+        game_path.display(),
+        if compressed {
+            "compressed"
+        } else {
+            "uncompressed"
+        }
+    );
+    persist();
+}
+
+/// Drop compressed launches after the game is decompressed, so it is not
+/// flagged again from stale samples.
+pub fn forget_compressed_launches(game_path: &Path) {
+    let key = normalize_path_key(game_path);
+    let changed = with_timings_write(|timings| {
+        timings
+            .entries
+            .get_mut(&key)
+            .is_some_and(|entry| !std::mem::take(&mut entry.compressed_ms).is_empty())
+    });
+    if changed {
+        persist();
+    }
+}
+
+/// Launch-lag verdict for one game, if its compressed launches are
+/// significantly slower than its baseline.
+pub fn assess(game_path: &Path) -> Option<LaunchLagReport> {
+    let key = normalize_path_key(game_path);
+    let entry = with_timings_read(|timings| timings.entries.get(&key).cloned())?;
+    compare(game_path.to_string_lossy().into_owned(), &entry)
+}
+
+/// Every tracked game that launches significantly slower compressed,
+/// worst first.
+pub fn lagging_games() -> Vec<LaunchLagReport> {
+    let entries = with_timings_read(|timings| timings.entries.clone());
+    let mut reports: Vec<LaunchLagReport> = entries
+        .into_iter()
+        .filter_map(|(path, entry)| compare(path, &entry))
+        .collect();
+    reports.sort_by(|a, b| b.slowdown_ratio.total_cmp(&a.slowdown_ratio));
+    reports
+}
+
+fn compare(game_path: String, entry: &GameLaunches) -> Option<LaunchLagReport> {
+    if entry.uncompressed_ms.len() < MIN_SAMPLES || entry.compressed_ms.len() < MIN_SAMPLES {
+        return None;
+    }
+    let baseline_ms = median(&entry.uncompressed_ms)?.max(1);
+    let compressed_ms = median(&entry.compressed_ms)?;
+    let slowdown_ratio = compressed_ms as f64 / baseline_ms as f64;
+    let significant = slowdown_ratio >= SIGNIFICANT_SLOWDOWN_RATIO
+        && compressed_ms.saturating_sub(baseline_ms) >= MIN_SLOWDOWN_MS;
+    significant.then_some(LaunchLagReport {
+        game_path,
+        baseline_ms,
+        compressed_ms,
+        slowdown_ratio,
+    })
+}
+
+fn median(samples: &[u64]) -> Option<u64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[mid]),
+        _ => Some((sorted[mid - 1] + sorted[mid]) / 2),
+    }
+}
+
+/// Where a launch being timed stands after a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchProgress {
+    Loading,
+    /// Reads settled; the launch took this long.
+    Settled(Duration),
+    /// Not measurable: still loading after [`MAX_LAUNCH_DURATION`], or it
+    /// settled without ever reading much (a launcher stub, a warm cache).
+    Abandoned,
+}
+
+/// Times one launch from the cumulative read bytes of the game's
+/// processes, sampled every second or so.
+#[derive(Debug)]
+pub struct LaunchTimer {
+    started: Instant,
+    last_sample: Option<(Instant, u64)>,
+    quiet_since: Option<Instant>,
+    saw_reads: bool,
+}
+
+impl LaunchTimer {
+    pub fn start(now: Instant) -> Self {
+        Self {
+            started: now,
+            last_sample: None,
+            quiet_since: None,
+            saw_reads: false,
+        }
+    }
+
+    pub fn observe(&mut self, total_read_bytes: u64, now: Instant) -> LaunchProgress {
+        let previous = self.last_sample.replace((now, total_read_bytes));
+        if let Some((at, bytes)) = previous {
+            let seconds = now.saturating_duration_since(at).as_secs_f64();
+            if seconds > 0.0 {
+                let rate = total_read_bytes.saturating_sub(bytes) as f64 / seconds;
+                if rate < STEADY_READ_BYTES_PER_SEC {
+                    self.quiet_since.get_or_insert(at);
+                } else {
+                    self.saw_reads = true;
+                    self.quiet_since = None;
+                }
+            }
+        }
+
+        if let Some(quiet_since) = self.quiet_since {
+            if now.saturating_duration_since(quiet_since) >= STEADY_WINDOW {
+                return if self.saw_reads {
+                    LaunchProgress::Settled(quiet_since.saturating_duration_since(self.started))
+                } else {
+                    LaunchProgress::Abandoned
+                };
+            }
+        }
+        if now.saturating_duration_since(self.started) >= MAX_LAUNCH_DURATION {
+            return LaunchProgress::Abandoned;
+        }
+        LaunchProgress::Loading
+    }
+}
+
+fn evict_oldest(timings: &mut TimingsFile) {
+    if let Some(oldest) = timings
+        .entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.updated_at_ms)
+        .map(|(key, _)| key.clone())
+    {
+        timings.entries.remove(&oldest);
+    }
+}
+
+fn with_timings_read<R>(f: impl FnOnce(&TimingsFile) -> R) -> R {
+    let guard = TIMINGS.read().unwrap_or_else(|poisoned| {
+        log::warn!("Launch timings lock poisoned (read); recovering");
+        poisoned.into_inner()
+    });
+    f(&guard)
+}
+
+fn with_timings_write<R>(f: impl FnOnce(&mut TimingsFile) -> R) -> R {
+    let mut guard = TIMINGS.write().unwrap_or_else(|poisoned| {
+        log::warn!("Launch timings lock poisoned (write); recovering");
+        poisoned.into_inner()
+    });
+    f(&mut guard)
+}
+
+/// Launches are rare, so every change is written straight away.
+fn persist() {
+    let snapshot = with_timings_read(Clone::clone);
+    let result = timings_path().and_then(|path| {
+        let json = serde_json::to_vec(&snapshot).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&path, &json)
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to persist launch timings: {e}");
+    }
+}
+
+fn load_timings() -> TimingsFile {
+    let Ok(path) = timings_path() else {
+        return TimingsFile::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return TimingsFile::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse launch timings: {e}");
+        TimingsFile::default()
+    })
+}
+
+fn timings_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-launch-timings-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(TIMINGS_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !TIMINGS_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            TIMINGS_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(TIMINGS_FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn timer_settles_once_reads_stay_quiet() {
+        let start = Instant::now();
+        let mut timer = LaunchTimer::start(start);
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        assert_eq!(timer.observe(0, at(1)), LaunchProgress::Loading);
+        assert_eq!(timer.observe(200 * MIB, at(2)), LaunchProgress::Loading);
+        assert_eq!(timer.observe(400 * MIB, at(3)), LaunchProgress::Loading);
+        for secs in 4..8 {
+            assert_eq!(timer.observe(401 * MIB, at(secs)), LaunchProgress::Loading);
+        }
+        assert_eq!(
+            timer.observe(401 * MIB, at(8)),
+            LaunchProgress::Settled(Duration::from_secs(3))
+        );
+
+        let mut idle = LaunchTimer::start(start);
+        for secs in 1..7 {
+            idle.observe(0, at(secs));
+        }
+        assert_eq!(idle.observe(0, at(7)), LaunchProgress::Abandoned);
+    }
+
+    #[test]
+    fn flags_only_a_clear_slowdown_with_enough_samples() {
+        let entry = |uncompressed: &[u64], compressed: &[u64]| GameLaunches {
+            uncompressed_ms: uncompressed.to_vec(),
+            compressed_ms: compressed.to_vec(),
+            updated_at_ms: 0,
+        };
+
+        let slow = compare(
+            "game".into(),
+            &entry(&[10_000, 12_000], &[18_000, 16_000, 40_000]),
+        )
+        .expect("slowdown should be flagged");
+        assert_eq!(slow.baseline_ms, 11_000);
+        assert_eq!(slow.compressed_ms, 18_000);
+
+        assert!(compare("game".into(), &entry(&[10_000], &[30_000, 30_000])).is_none());
+        assert!(compare("game".into(), &entry(&[10_000, 10_000], &[11_000, 12_000])).is_none());
+        assert!(compare("game".into(), &entry(&[2_000, 2_000], &[4_000, 4_000])).is_none());
+    }
+}
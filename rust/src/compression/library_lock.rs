@@ -0,0 +1,71 @@
+//! Cross-process lock on a game folder.
+//!
+//! Several Windows accounts can share one Steam library, each running its
+//! own app or daemon. Before compressing or decompressing, the engine takes
+//! a named kernel object for the game folder in the `Global\` namespace,
+//! which every session sees, so two instances never work on the same game
+//! at once. A crashed instance releases its games with its handles.
+
+use std::path::Path;
+
+use super::error::CompressionError;
+use crate::utils::{normalize_path_key, stable_hash, try_named_lock, NamedLock};
+
+/// Held for the length of an operation on one game folder.
+#[must_use = "the folder is unlocked when the guard drops"]
+pub struct GameFolderLock {
+    /// `None` when named objects are unavailable and the run goes ahead
+    /// unlocked.
+    _lock: Option<NamedLock>,
+}
+
+/// Lock `folder` against other instances, including other Windows
+/// accounts, and other engines in this process.
+///
+/// Fails with [`CompressionError::GameFolderLocked`] while someone else
+/// holds it. When the lock cannot be created at all, the operation goes
+/// ahead unlocked rather than failing.
+pub fn lock_game_folder(folder: &Path) -> Result<GameFolderLock, CompressionError> {
+    match try_named_lock(&object_name(folder)) {
+        Ok(Some(lock)) => Ok(GameFolderLock { _lock: Some(lock) }),
+        Ok(None) => {
+            log::info!(
+                "{} is locked by another Compact Games instance",
+                folder.display()
+            );
+            Err(CompressionError::GameFolderLocked {
+                path: folder.to_path_buf(),
+            })
+        }
+        Err(e) => {
+            log::warn!("Game folder lock unavailable; continuing unlocked: {e}");
+            Ok(GameFolderLock { _lock: None })
+        }
+    }
+}
+
+/// `Global\CompactGames.Game.<hash>`; object names cannot hold a path, so
+/// the canonical folder key is hashed.
+fn object_name(folder: &Path) -> String {
+    let canonical = std::fs::canonicalize(folder).unwrap_or_else(|_| folder.to_path_buf());
+    let hash = stable_hash(&normalize_path_key(&canonical));
+    format!("Global\\CompactGames.Game.{hash:016x}")
+}
+
+#[cfg(all(test, windows))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_lock_fails_until_the_first_drops() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = lock_game_folder(dir.path()).expect("first lock");
+        assert!(matches!(
+            lock_game_folder(dir.path()),
+            Err(CompressionError::GameFolderLocked { .. })
+        ));
+
+        drop(first);
+        assert!(lock_game_folder(dir.path()).is_ok());
+    }
+}
@@ -1,9 +1,18 @@
 pub mod algorithm;
+pub mod attributes;
 pub mod community_db;
+pub mod crash_rollback;
 pub mod engine;
 pub mod error;
+pub mod external;
 pub mod history;
+pub mod launch_lag;
+pub mod library_lock;
+pub mod op_journal;
+pub mod privilege;
+pub mod rate_limit;
 pub mod thread_policy;
+pub mod user_data;
 #[cfg(windows)]
 pub mod wof;
 
@@ -0,0 +1,314 @@
+//! Crash-safe record of in-flight compression operations.
+//!
+//! Every compress or decompress run writes a small JSON record next to the
+//! automation journal when it starts, rewrites it with progress at
+//! checkpoints, and removes it when the engine returns. A record still on
+//! disk at startup means the process died mid-run and the game is left
+//! partially compressed; the UI offers to resume (compress again) or roll
+//! back (decompress).
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::algorithm::CompressionAlgorithm;
+
+const OPERATIONS_DIR_NAME: &str = "operations";
+/// Minimum time between checkpoint writes.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Records written by this process; they are live, not interrupted.
+static ACTIVE_IDS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationKind {
+    Compress,
+    Decompress,
+}
+
+/// On-disk shape of one journaled operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationRecord {
+    pub id: String,
+    pub kind: OperationKind,
+    pub game_path: PathBuf,
+    pub algorithm: CompressionAlgorithm,
+    pub started_at_ms: u64,
+    /// Time of the last checkpoint.
+    pub updated_at_ms: u64,
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+}
+
+impl OperationRecord {
+    /// Folder name, which is the game name for nearly every launcher.
+    pub fn display_name(&self) -> String {
+        self.game_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.game_path.to_string_lossy().into_owned())
+    }
+}
+
+/// Handle for one running operation. Dropping it without `finish` (a
+/// panic unwinding the worker) leaves the record behind, like a crash.
+pub struct OperationJournal {
+    path: PathBuf,
+    record: Mutex<OperationRecord>,
+    last_checkpoint: Mutex<Instant>,
+}
+
+impl OperationJournal {
+    /// Write the start record. Failure is logged and yields `None`: a
+    /// missing journal must never block compression.
+    pub fn begin(
+        kind: OperationKind,
+        game_path: &Path,
+        algorithm: CompressionAlgorithm,
+        files_total: u64,
+        bytes_total: u64,
+    ) -> Option<Self> {
+        let dir = match operations_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                log::warn!("Operation journal disabled: {e}");
+                return None;
+            }
+        };
+        Self::begin_in(&dir, kind, game_path, algorithm, files_total, bytes_total)
+    }
+
+    fn begin_in(
+        dir: &Path,
+        kind: OperationKind,
+        game_path: &Path,
+        algorithm: CompressionAlgorithm,
+        files_total: u64,
+        bytes_total: u64,
+    ) -> Option<Self> {
+        static OPERATION_SEQ: AtomicU64 = AtomicU64::new(0);
+
+        let now_ms = crate::utils::unix_now_ms();
+        let id = format!(
+            "{now_ms}-{}-{}",
+            std::process::id(),
+            OPERATION_SEQ.fetch_add(1, Ordering::Relaxed)
+        );
+        let record = OperationRecord {
+            id: id.clone(),
+            kind,
+            game_path: game_path.to_path_buf(),
+            algorithm,
+            started_at_ms: now_ms,
+            updated_at_ms: now_ms,
+            files_total,
+            files_done: 0,
+            bytes_total,
+            bytes_done: 0,
+        };
+        let path = dir.join(format!("{id}.json"));
+        if let Err(e) = write_record(&path, &record) {
+            log::warn!("Failed to journal {kind:?} of {}: {e}", game_path.display());
+            return None;
+        }
+        ACTIVE_IDS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id);
+        Some(Self {
+            path,
+            record: Mutex::new(record),
+            last_checkpoint: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Record progress if the checkpoint interval has passed. Cheap to
+    /// call after every file; concurrent callers skip while one writes.
+    pub fn checkpoint(&self, files_done: u64, bytes_done: u64) {
+        let Ok(mut last) = self.last_checkpoint.try_lock() else {
+            return;
+        };
+        if last.elapsed() < CHECKPOINT_INTERVAL {
+            return;
+        }
+        *last = Instant::now();
+        let mut record = self.record.lock().unwrap_or_else(|e| e.into_inner());
+        record.files_done = files_done;
+        record.bytes_done = bytes_done;
+        record.updated_at_ms = crate::utils::unix_now_ms();
+        if let Err(e) = write_record(&self.path, &record) {
+            log::debug!("Operation checkpoint failed: {e}");
+        }
+    }
+
+    /// The engine returned, successfully or not; drop the record.
+    pub fn finish(self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!(
+                    "Failed to remove operation record {}: {e}",
+                    self.path.display()
+                );
+            }
+        }
+        let id = &self.record.lock().unwrap_or_else(|e| e.into_inner()).id;
+        ACTIVE_IDS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+    }
+}
+
+/// Operations left behind by an earlier process, oldest first.
+pub fn interrupted_operations() -> Vec<OperationRecord> {
+    operations_dir()
+        .map(|dir| interrupted_in(&dir))
+        .unwrap_or_default()
+}
+
+/// Forget an interrupted operation once the user has resumed, rolled back
+/// or dismissed it. Returns `false` if no such record exists.
+pub fn dismiss(id: &str) -> bool {
+    operations_dir().is_ok_and(|dir| dismiss_in(&dir, id))
+}
+
+fn interrupted_in(dir: &Path) -> Vec<OperationRecord> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let active = ACTIVE_IDS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let mut records: Vec<OperationRecord> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| match read_record(&path) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring unreadable operation record {}: {e}",
+                    path.display()
+                );
+                None
+            }
+        })
+        .filter(|record| !active.contains(&record.id))
+        .collect();
+    records.sort_by_key(|record| record.started_at_ms);
+    records
+}
+
+fn dismiss_in(dir: &Path, id: &str) -> bool {
+    // Ids are generated by `begin_in`; anything else is not ours.
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return false;
+    }
+    if ACTIVE_IDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(id)
+    {
+        return false;
+    }
+    fs::remove_file(dir.join(format!("{id}.json"))).is_ok()
+}
+
+fn write_record(path: &Path, record: &OperationRecord) -> std::io::Result<()> {
+    let json = serde_json::to_vec_pretty(record).map_err(std::io::Error::other)?;
+    crate::utils::atomic_write(path, &json)
+}
+
+fn read_record(path: &Path) -> std::io::Result<OperationRecord> {
+    let contents = fs::read(path)?;
+    serde_json::from_slice(&contents)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn operations_dir() -> std::io::Result<PathBuf> {
+    #[cfg(test)]
+    {
+        static TEST_OPERATIONS_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir()
+                .join(format!(
+                    "compact-games-op-journal-tests-{}-{now}",
+                    std::process::id()
+                ))
+                .join(OPERATIONS_DIR_NAME)
+        });
+        Ok(TEST_OPERATIONS_DIR.clone())
+    }
+
+    #[cfg(not(test))]
+    {
+        Ok(crate::paths::data_dir()?.join(OPERATIONS_DIR_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn begin(dir: &Path) -> OperationJournal {
+        OperationJournal::begin_in(
+            dir,
+            OperationKind::Compress,
+            &Path::new("C:\\Games").join("Cyberpunk 2077"),
+            CompressionAlgorithm::Xpress8K,
+            10,
+            1_000,
+        )
+        .expect("journal")
+    }
+
+    #[test]
+    fn live_operation_is_not_reported_until_abandoned() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = begin(dir.path());
+        assert!(interrupted_in(dir.path()).is_empty());
+
+        // Simulate the process dying: the record stays, the live set is
+        // what a fresh process would see.
+        let id = journal.record.lock().unwrap().id.clone();
+        std::mem::forget(journal);
+        ACTIVE_IDS.lock().unwrap().remove(&id);
+
+        let interrupted = interrupted_in(dir.path());
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].display_name(), "Cyberpunk 2077");
+        assert_eq!(interrupted[0].files_total, 10);
+
+        assert!(dismiss_in(dir.path(), &id));
+        assert!(interrupted_in(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn checkpoint_records_progress_and_finish_removes_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = begin(dir.path());
+        *journal.last_checkpoint.lock().unwrap() -= CHECKPOINT_INTERVAL;
+        journal.checkpoint(4, 400);
+
+        let on_disk = read_record(&journal.path).unwrap();
+        assert_eq!((on_disk.files_done, on_disk.bytes_done), (4, 400));
+
+        journal.finish();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn dismiss_rejects_foreign_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!dismiss_in(dir.path(), "../settings"));
+        assert!(!dismiss_in(dir.path(), ""));
+    }
+}
@@ -0,0 +1,122 @@
+//! Backup and restore privileges for files whose ACLs deny the user.
+//!
+//! Some launchers (EA, Ubisoft) install files only administrators can
+//! write. An elevated process, or the daemon running as a service, holds
+//! `SeBackupPrivilege` and `SeRestorePrivilege` disabled; once enabled, a
+//! handle opened with `FILE_FLAG_BACKUP_SEMANTICS` bypasses the file's
+//! ACL. A standard user token does not hold them at all, so enabling
+//! fails and those files stay skipped.
+
+use std::sync::OnceLock;
+
+static BACKUP_PRIVILEGES: OnceLock<bool> = OnceLock::new();
+
+/// Enable backup and restore privileges for this process, once. Returns
+/// whether both are now enabled.
+pub fn enable_backup_privileges() -> bool {
+    *BACKUP_PRIVILEGES.get_or_init(|| {
+        let enabled = enable_privileges();
+        if enabled {
+            log::info!("Backup and restore privileges enabled for admin-only files");
+        } else {
+            log::info!("Backup and restore privileges unavailable; process is not elevated");
+        }
+        enabled
+    })
+}
+
+/// Whether this process runs with an elevated (administrator) token.
+#[cfg(windows)]
+pub fn is_process_elevated() -> bool {
+    use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION};
+
+    let Some(token) = ProcessToken::open(windows::Win32::Security::TOKEN_QUERY) else {
+        return false;
+    };
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut returned = 0_u32;
+    unsafe {
+        GetTokenInformation(
+            token.0,
+            TokenElevation,
+            Some((&mut elevation as *mut TOKEN_ELEVATION).cast()),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned,
+        )
+    }
+    .is_ok_and(|()| elevation.TokenIsElevated != 0)
+}
+
+#[cfg(not(windows))]
+pub fn is_process_elevated() -> bool {
+    false
+}
+
+#[cfg(windows)]
+struct ProcessToken(windows::Win32::Foundation::HANDLE);
+
+#[cfg(windows)]
+impl ProcessToken {
+    fn open(access: windows::Win32::Security::TOKEN_ACCESS_MASK) -> Option<Self> {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+        let mut token = HANDLE::default();
+        unsafe { OpenProcessToken(GetCurrentProcess(), access, &mut token) }.ok()?;
+        Some(Self(token))
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ProcessToken {
+    fn drop(&mut self) {
+        let _ = unsafe { windows::Win32::Foundation::CloseHandle(self.0) };
+    }
+}
+
+#[cfg(windows)]
+fn enable_privileges() -> bool {
+    use windows::Win32::Security::{
+        SE_BACKUP_NAME, SE_RESTORE_NAME, TOKEN_ADJUST_PRIVILEGES, TOKEN_QUERY,
+    };
+
+    let Some(token) = ProcessToken::open(TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY) else {
+        return false;
+    };
+    [SE_BACKUP_NAME, SE_RESTORE_NAME]
+        .into_iter()
+        .all(|name| enable_privilege(&token, name))
+}
+
+#[cfg(windows)]
+fn enable_privilege(token: &ProcessToken, name: windows::core::PCWSTR) -> bool {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{GetLastError, ERROR_NOT_ALL_ASSIGNED, LUID};
+    use windows::Win32::Security::{
+        AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED,
+        TOKEN_PRIVILEGES,
+    };
+
+    let mut luid = LUID::default();
+    if unsafe { LookupPrivilegeValueW(PCWSTR::null(), name, &mut luid) }.is_err() {
+        return false;
+    }
+    let privileges = TOKEN_PRIVILEGES {
+        PrivilegeCount: 1,
+        Privileges: [LUID_AND_ATTRIBUTES {
+            Luid: luid,
+            Attributes: SE_PRIVILEGE_ENABLED,
+        }],
+    };
+    if unsafe { AdjustTokenPrivileges(token.0, false, Some(&privileges), 0, None, None) }.is_err() {
+        return false;
+    }
+    // Succeeds without assigning anything when the token lacks the privilege.
+    let last_error = unsafe { GetLastError() };
+    last_error != ERROR_NOT_ALL_ASSIGNED
+}
+
+#[cfg(not(windows))]
+fn enable_privileges() -> bool {
+    false
+}
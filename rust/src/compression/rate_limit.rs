@@ -0,0 +1,127 @@
+//! Aggregate throughput cap for compression workers.
+//!
+//! Every worker draws a file's logical size from one shared token bucket
+//! before compressing it, so the cap holds however many rayon threads
+//! run. A file larger than the bucket is let through and puts the bucket
+//! into debt, which the following files wait out; the long-run rate
+//! stays at the cap without splitting WOF calls.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Slowest cap accepted; anything lower would stall on the first file.
+pub const MIN_BYTES_PER_SEC: u64 = 1024 * 1024;
+/// Burst allowance, in seconds of throughput.
+const BURST_SECONDS: f64 = 1.0;
+/// Longest single sleep, so cancellation is noticed promptly.
+const MAX_WAIT_SLICE: Duration = Duration::from_millis(250);
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    bytes_per_sec: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Bytes available now; negative while in debt.
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Limiter for `bytes_per_sec`, raised to `MIN_BYTES_PER_SEC`.
+    pub fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(MIN_BYTES_PER_SEC) as f64;
+        Self {
+            bytes_per_sec,
+            bucket: Mutex::new(Bucket {
+                tokens: bytes_per_sec * BURST_SECONDS,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec as u64
+    }
+
+    /// Take `bytes` from the bucket and return how long the caller must
+    /// wait before using them.
+    fn reserve(&self, bytes: u64, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now
+            .saturating_duration_since(bucket.refilled_at)
+            .as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * self.bytes_per_sec).min(self.bytes_per_sec * BURST_SECONDS);
+        bucket.refilled_at = now;
+
+        let debt = bytes as f64 - bucket.tokens;
+        bucket.tokens -= bytes as f64;
+        if debt <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(debt / self.bytes_per_sec)
+        }
+    }
+
+    /// Block until `bytes` may be processed. Returns `false` if
+    /// `is_cancelled` fired while waiting.
+    pub fn acquire(&self, bytes: u64, is_cancelled: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + self.reserve(bytes, Instant::now());
+        loop {
+            if is_cancelled() {
+                return false;
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return true;
+            }
+            std::thread::sleep(remaining.min(MAX_WAIT_SLICE));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn bucket_allows_a_burst_then_paces_to_the_cap() {
+        let limiter = RateLimiter::new(4 * MIB);
+        let start = Instant::now();
+
+        assert_eq!(limiter.reserve(4 * MIB, start), Duration::ZERO);
+        assert_eq!(limiter.reserve(2 * MIB, start), Duration::from_millis(500));
+        // Half a second later the debt is repaid and another 2 MiB waits
+        // another half second.
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.reserve(2 * MIB, later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn idle_time_refills_at_most_one_burst() {
+        let limiter = RateLimiter::new(2 * MIB);
+        let start = Instant::now();
+        assert_eq!(limiter.reserve(2 * MIB, start), Duration::ZERO);
+
+        let much_later = start + Duration::from_secs(60);
+        assert_eq!(limiter.reserve(2 * MIB, much_later), Duration::ZERO);
+        assert_eq!(limiter.reserve(MIB, much_later), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn caps_below_the_minimum_are_raised() {
+        assert_eq!(RateLimiter::new(1).bytes_per_sec(), MIN_BYTES_PER_SEC);
+    }
+
+    #[test]
+    fn acquire_stops_waiting_when_cancelled() {
+        let limiter = RateLimiter::new(MIB);
+        limiter.reserve(100 * MIB, Instant::now());
+        assert!(!limiter.acquire(MIB, || true));
+    }
+}
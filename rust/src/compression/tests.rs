@@ -61,7 +61,20 @@ fn stats_savings_ratio_zero_when_empty() {
         compressed_bytes: 0,
         files_processed: 0,
         files_skipped: 0,
+        files_skipped_cloud: 0,
+        files_skipped_permission: 0,
+        files_skipped_encrypted: 0,
+        files_skipped_sparse: 0,
+        attribute_skips: Vec::new(),
+        alternate_stream_files: 0,
+        alternate_stream_bytes: 0,
+        files_already_compressed: 0,
+        bytes_already_compressed: 0,
+        bytes_already_compressed_on_disk: 0,
+        was_cancelled: false,
+        throughput: None,
         duration_ms: 0,
+        extensions: Vec::new(),
     };
     assert_eq!(stats.savings_ratio(), 0.0);
 }
@@ -73,12 +86,85 @@ fn stats_savings_ratio_calculated_correctly() {
         compressed_bytes: 600,
         files_processed: 10,
         files_skipped: 0,
+        files_skipped_cloud: 0,
+        files_skipped_permission: 0,
+        files_skipped_encrypted: 0,
+        files_skipped_sparse: 0,
+        attribute_skips: Vec::new(),
+        alternate_stream_files: 0,
+        alternate_stream_bytes: 0,
+        files_already_compressed: 0,
+        bytes_already_compressed: 0,
+        bytes_already_compressed_on_disk: 0,
+        was_cancelled: false,
+        throughput: None,
         duration_ms: 100,
+        extensions: Vec::new(),
     };
     assert!((stats.savings_ratio() - 0.4).abs() < f64::EPSILON);
     assert_eq!(stats.bytes_saved(), 400);
 }
 
+#[test]
+fn stats_separate_savings_from_already_compressed_files() {
+    let stats = CompressionStats {
+        original_bytes: 1000,
+        compressed_bytes: 500,
+        files_processed: 10,
+        files_skipped: 0,
+        files_skipped_cloud: 0,
+        files_skipped_permission: 0,
+        files_skipped_encrypted: 0,
+        files_skipped_sparse: 0,
+        attribute_skips: Vec::new(),
+        alternate_stream_files: 0,
+        alternate_stream_bytes: 0,
+        files_already_compressed: 4,
+        bytes_already_compressed: 400,
+        bytes_already_compressed_on_disk: 100,
+        was_cancelled: false,
+        throughput: None,
+        duration_ms: 100,
+        extensions: Vec::new(),
+    };
+    assert_eq!(stats.bytes_saved(), 500);
+    assert_eq!(stats.bytes_saved_this_run(), 200);
+}
+
+#[test]
+fn stats_flag_alternate_streams_by_share_and_size() {
+    let gib = 1024 * 1024 * 1024;
+    let mut stats = CompressionStats {
+        original_bytes: gib,
+        compressed_bytes: gib / 2,
+        files_processed: 10,
+        files_skipped: 0,
+        files_skipped_cloud: 0,
+        files_skipped_permission: 0,
+        files_skipped_encrypted: 0,
+        files_skipped_sparse: 0,
+        attribute_skips: Vec::new(),
+        alternate_stream_files: 3,
+        alternate_stream_bytes: gib / 10,
+        files_already_compressed: 0,
+        bytes_already_compressed: 0,
+        bytes_already_compressed_on_disk: 0,
+        was_cancelled: false,
+        throughput: None,
+        duration_ms: 100,
+        extensions: Vec::new(),
+    };
+    assert!(stats.has_significant_alternate_streams());
+
+    stats.alternate_stream_bytes = gib / 100;
+    assert!(!stats.has_significant_alternate_streams());
+
+    // A large share of a tiny folder is still only a few bytes.
+    stats.original_bytes = 1024;
+    stats.alternate_stream_bytes = 512;
+    assert!(!stats.has_significant_alternate_streams());
+}
+
 #[test]
 fn cancellation_token_works() {
     let token = CancellationToken::new();
@@ -117,7 +203,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let ratio = stats.savings_ratio();
@@ -136,7 +235,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let expected = original.saturating_sub(compressed);
@@ -157,7 +269,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let expected_ratio = 1.0 - (compressed as f64 / original as f64);
@@ -180,7 +305,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let first_call = stats.savings_ratio();
@@ -199,7 +337,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 0,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 0,
+                extensions: Vec::new(),
             };
 
             prop_assert_eq!(stats.savings_ratio(), 0.0,
@@ -214,7 +365,20 @@ mod stats_properties {
                 compressed_bytes: 0,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let ratio = stats.savings_ratio();
@@ -230,7 +394,20 @@ mod stats_properties {
                 compressed_bytes: size,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             let ratio = stats.savings_ratio();
@@ -249,7 +426,20 @@ mod stats_properties {
                 compressed_bytes: compressed,
                 files_processed: 1,
                 files_skipped: 0,
+                files_skipped_cloud: 0,
+                files_skipped_permission: 0,
+                files_skipped_encrypted: 0,
+                files_skipped_sparse: 0,
+                attribute_skips: Vec::new(),
+                alternate_stream_files: 0,
+                alternate_stream_bytes: 0,
+                files_already_compressed: 0,
+                bytes_already_compressed: 0,
+                bytes_already_compressed_on_disk: 0,
+                was_cancelled: false,
+                throughput: None,
                 duration_ms: 100,
+                extensions: Vec::new(),
             };
 
             prop_assert!(stats.bytes_saved() <= original,
@@ -75,6 +75,29 @@ fn compress_reduces_or_maintains_size_lzx() {
     );
 }
 
+#[test]
+fn compress_folders_combines_stats_and_skips_nested_folders() {
+    let game = TempDir::new().unwrap();
+    let dlc = TempDir::new().unwrap();
+    create_compressible_file(game.path(), "base.dat", 1_048_576);
+    std::fs::create_dir(game.path().join("sub")).unwrap();
+    create_compressible_file(&game.path().join("sub"), "patch.dat", 1_048_576);
+    create_compressible_file(dlc.path(), "dlc.dat", 1_048_576);
+
+    let engine = CompressionEngine::new(CompressionAlgorithm::Xpress4K);
+    let stats = engine
+        .compress_folders(vec![
+            game.path().to_path_buf(),
+            dlc.path().to_path_buf(),
+            game.path().join("sub"),
+        ])
+        .unwrap();
+
+    assert_eq!(stats.files_processed, 3);
+    assert_eq!(stats.original_bytes, 3 * 1_048_576);
+    assert_eq!(engine.progress().1, 3);
+}
+
 #[test]
 fn roundtrip_preserves_file_content() {
     let dir = TempDir::new().unwrap();
@@ -105,10 +128,12 @@ fn decompression_restores_logical_size() {
     engine.compress_folder(dir.path()).unwrap();
 
     let engine2 = CompressionEngine::new(CompressionAlgorithm::Xpress4K);
-    engine2.decompress_folder(dir.path()).unwrap();
+    let stats = engine2.decompress_folder(dir.path()).unwrap();
 
     let restored_size = fs::metadata(&path).unwrap().len();
     assert_eq!(original_size, restored_size);
+    assert_eq!(stats.files_decompressed, 1);
+    assert_eq!(stats.bytes_restored_logical, original_size);
 }
 
 #[test]
@@ -126,6 +151,24 @@ fn cancellation_stops_compression() {
     assert!(matches!(result, Err(CompressionError::Cancelled)));
 }
 
+#[test]
+fn graceful_cancellation_returns_partial_stats() {
+    let dir = TempDir::new().unwrap();
+    for i in 0..50 {
+        create_compressible_file(dir.path(), &format!("file_{i}.dat"), 8192);
+    }
+
+    let engine = CompressionEngine::new(CompressionAlgorithm::default());
+    engine.cancel_token().cancel_gracefully();
+
+    let stats = engine.compress_folder(dir.path()).unwrap();
+    assert!(stats.was_cancelled);
+    assert!(stats.files_processed < 50);
+
+    let second_run = engine.compress_folder(dir.path()).unwrap();
+    assert!(!second_run.was_cancelled, "graceful flag should reset");
+}
+
 #[test]
 fn cancellation_stops_decompression() {
     let dir = TempDir::new().unwrap();
@@ -334,7 +377,8 @@ fn decompress_with_progress_reports_stable_total_count() {
         .recv_timeout(Duration::from_secs(60))
         .unwrap()
         .expect("decompression should succeed");
-    assert_eq!(result.files_processed, FILE_COUNT as u64);
+    assert_eq!(result.files_decompressed, FILE_COUNT as u64);
+    assert!(result.bytes_grown_physical > 0);
 }
 
 #[test]
@@ -358,7 +402,7 @@ fn decompress_with_progress_can_complete_without_progress_consumer() {
         .recv_timeout(Duration::from_secs(60))
         .unwrap()
         .expect("decompression should still complete when progress stream is dropped");
-    assert!(stats.files_processed > 0);
+    assert!(stats.files_decompressed > 0);
 }
 
 #[test]
@@ -407,6 +451,42 @@ fn already_compressed_files_handled_gracefully() {
     assert!(result.is_ok(), "re-compressing should not error");
 }
 
+#[test]
+fn externally_compressed_files_are_upgraded_in_place() {
+    let dir = TempDir::new().unwrap();
+    let file = create_compressible_file(dir.path(), "data.dat", 1_048_576);
+    // What `compact /c /exe:xpress4k` leaves behind.
+    wof::wof_compress_file(&file, CompressionAlgorithm::Xpress4K).unwrap();
+
+    let scan = crate::compression::external::scan_external_compression(dir.path());
+    assert_eq!(scan.files_compressed, 1);
+    assert_eq!(scan.algorithm, Some(CompressionAlgorithm::Xpress4K));
+
+    let engine = CompressionEngine::new(CompressionAlgorithm::Lzx);
+    let stats = engine.compress_folder(dir.path()).unwrap();
+    assert_eq!(stats.files_already_compressed, 0);
+    assert_eq!(
+        wof::wof_get_compression(&file).unwrap(),
+        Some(CompressionAlgorithm::Lzx)
+    );
+}
+
+#[test]
+fn external_compression_is_imported_once() {
+    let dir = TempDir::new().unwrap();
+    let file = create_compressible_file(dir.path(), "data.dat", 1_048_576);
+    wof::wof_compress_file(&file, CompressionAlgorithm::Xpress8K).unwrap();
+
+    let imported =
+        crate::compression::external::import_external_compression(dir.path(), "External")
+            .expect("first import records history");
+    assert_eq!(imported.algorithm, CompressionAlgorithm::Xpress8K);
+    assert!(imported.actual_stats.actual_saved_bytes > 0);
+    assert!(
+        crate::compression::external::import_external_compression(dir.path(), "External").is_none()
+    );
+}
+
 #[test]
 fn get_compression_ratio_reflects_compression() {
     let dir = TempDir::new().unwrap();
@@ -477,3 +557,21 @@ fn wof_get_compression_uncompressed_returns_none() {
     let result = wof::wof_get_compression(&path).unwrap();
     assert_eq!(result, None);
 }
+
+#[test]
+fn compress_reaches_files_beyond_max_path() {
+    let dir = TempDir::new().unwrap();
+    let nested = (0..12).fold(dir.path().to_path_buf(), |path, depth| {
+        path.join(format!("deeply_nested_mod_folder_{depth:02}"))
+    });
+    fs::create_dir_all(&nested).unwrap();
+    let path = create_compressible_file(&nested, "texture.dds", 1_048_576);
+    assert!(path.as_os_str().len() > 260);
+
+    let engine = CompressionEngine::new(CompressionAlgorithm::Xpress4K);
+    let stats = engine.compress_folder(dir.path()).unwrap();
+
+    assert_eq!(stats.files_processed, 1);
+    assert_eq!(stats.files_skipped, 0);
+    assert!(wof::get_physical_size(&path).unwrap() < 1_048_576);
+}
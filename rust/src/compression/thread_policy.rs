@@ -1,8 +1,23 @@
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::time::Duration;
 
-use crate::discovery::storage::{storage_class_for_path, StorageClass};
+use serde::{Deserialize, Serialize};
+
+use crate::compression::history::get_historical_stats;
+use crate::discovery::storage::{storage_class_for_path, volume_key_for_path, StorageClass};
 
 const EXPERT_OVERRIDE_MAX_THREADS: usize = 16;
+/// HDD seeks thrash beyond two workers, so tuning never goes higher.
+const HDD_MAX_THREADS: usize = 2;
+/// Runs shorter or smaller than this are dominated by setup and say
+/// little about the disk.
+const MIN_SAMPLE_DURATION: Duration = Duration::from_secs(10);
+const MIN_SAMPLE_BYTES: u64 = 256 * 1024 * 1024;
+/// Samples a worker count needs before its mean throughput is trusted.
+const MIN_SAMPLES_PER_LEVEL: usize = 2;
+/// Newest samples per volume considered, so tuning follows drift.
+const TUNING_LOOKBACK: usize = 24;
 
 /// Controls how many parallel I/O threads the compression engine uses.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,10 +26,32 @@ pub struct ThreadPolicy {
     pub is_background: bool,
 }
 
+/// Throughput one run achieved at a given worker count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThroughputSample {
+    pub io_parallelism: u32,
+    pub bytes_per_sec: u64,
+}
+
+impl ThroughputSample {
+    /// Sample for `bytes` processed in `elapsed` by `io_parallelism`
+    /// workers, or `None` when the run was too short to judge the disk.
+    pub fn measure(io_parallelism: usize, bytes: u64, elapsed: Duration) -> Option<Self> {
+        if elapsed < MIN_SAMPLE_DURATION || bytes < MIN_SAMPLE_BYTES {
+            return None;
+        }
+        Some(Self {
+            io_parallelism: io_parallelism as u32,
+            bytes_per_sec: (bytes as f64 / elapsed.as_secs_f64()) as u64,
+        })
+    }
+}
+
 /// Compute the optimal thread policy for a game path.
 ///
 /// - HDD: cap at 2 threads (sequential I/O is faster than random)
 /// - SSD/Unknown: up to `num_cpus` capped at 8
+/// - Storage default tuned from past throughput on the same volume
 /// - High CPU pressure reduces foreground parallelism
 /// - Background mode: halve parallelism (minimum 1)
 /// - Expert override (if provided) wins after safety clamp
@@ -25,24 +62,85 @@ pub fn compute_thread_policy(
     io_parallelism_override: Option<usize>,
 ) -> ThreadPolicy {
     let storage = storage_class_for_path(game_path);
+    let samples = if io_parallelism_override.is_some() {
+        Vec::new()
+    } else {
+        volume_samples(game_path)
+    };
     compute_thread_policy_for_storage(
         storage,
         is_background,
         cpu_usage_percent,
         io_parallelism_override,
+        &samples,
     )
 }
 
+/// Newest throughput samples recorded for games on `game_path`'s volume.
+fn volume_samples(game_path: &Path) -> Vec<ThroughputSample> {
+    let volume = volume_key_for_path(game_path);
+    let mut entries = get_historical_stats();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp_ms));
+    entries
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .throughput
+                .filter(|_| volume_key_for_path(Path::new(&entry.game_path)) == volume)
+        })
+        .take(TUNING_LOOKBACK)
+        .collect()
+}
+
+/// Hill-climb over worker counts: settle on the count with the best mean
+/// throughput, after first trying its neighbours (half and double) so a
+/// better count gets found. With no trusted count yet, keep `default`.
+fn tuned_parallelism(samples: &[ThroughputSample], default: usize, max: usize) -> usize {
+    let mut levels: BTreeMap<usize, (usize, f64)> = BTreeMap::new();
+    for sample in samples {
+        let level = sample.io_parallelism as usize;
+        if (1..=max).contains(&level) {
+            let (count, total) = levels.entry(level).or_default();
+            *count += 1;
+            *total += sample.bytes_per_sec as f64;
+        }
+    }
+
+    let trusted = |level: usize| {
+        levels
+            .get(&level)
+            .is_some_and(|&(count, _)| count >= MIN_SAMPLES_PER_LEVEL)
+    };
+    let best = levels
+        .iter()
+        .filter(|&(&level, _)| trusted(level))
+        .max_by(|(_, a), (_, b)| (a.1 / a.0 as f64).total_cmp(&(b.1 / b.0 as f64)))
+        .map(|(&level, _)| level);
+    let Some(best) = best else {
+        return default.clamp(1, max);
+    };
+
+    [(best * 2).min(max), (best / 2).max(1)]
+        .into_iter()
+        .find(|&neighbour| neighbour != best && !trusted(neighbour))
+        .unwrap_or(best)
+}
+
 fn compute_thread_policy_for_storage(
     storage: StorageClass,
     is_background: bool,
     cpu_usage_percent: Option<f32>,
     io_parallelism_override: Option<usize>,
+    samples: &[ThroughputSample],
 ) -> ThreadPolicy {
-    let storage_base = match storage {
-        StorageClass::Hdd => 2,
-        StorageClass::Ssd | StorageClass::Unknown => num_cpus::get().min(8),
+    let (storage_default, storage_max) = match storage {
+        StorageClass::Hdd => (2, HDD_MAX_THREADS),
+        StorageClass::Ssd | StorageClass::Unknown => (
+            num_cpus::get().min(8),
+            num_cpus::get().min(EXPERT_OVERRIDE_MAX_THREADS),
+        ),
     };
+    let storage_base = tuned_parallelism(samples, storage_default, storage_max);
 
     // When CPU is already busy, reduce foreground pressure.
     let cpu_adjusted = match cpu_usage_percent {
@@ -139,6 +237,71 @@ mod tests {
             is_background,
             cpu_usage_percent,
             io_parallelism_override,
+            &[],
         )
     }
+
+    fn samples(runs: &[(u32, u64)]) -> Vec<ThroughputSample> {
+        runs.iter()
+            .map(|&(io_parallelism, bytes_per_sec)| ThroughputSample {
+                io_parallelism,
+                bytes_per_sec,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tuning_keeps_default_until_a_level_is_trusted() {
+        assert_eq!(tuned_parallelism(&[], 8, 16), 8);
+        assert_eq!(tuned_parallelism(&samples(&[(8, 100)]), 8, 16), 8);
+    }
+
+    #[test]
+    fn tuning_probes_neighbours_then_settles_on_the_fastest() {
+        let mut runs = vec![(8, 100), (8, 110)];
+        assert_eq!(tuned_parallelism(&samples(&runs), 8, 16), 16);
+
+        runs.extend([(16, 90), (16, 95)]);
+        assert_eq!(tuned_parallelism(&samples(&runs), 8, 16), 4);
+
+        runs.extend([(4, 150), (4, 140)]);
+        assert_eq!(tuned_parallelism(&samples(&runs), 8, 16), 2);
+
+        runs.extend([(2, 60), (2, 70)]);
+        assert_eq!(tuned_parallelism(&samples(&runs), 8, 16), 4);
+    }
+
+    #[test]
+    fn tuning_stays_within_the_storage_limit() {
+        let runs = samples(&[(2, 50), (2, 50), (8, 500), (8, 500)]);
+        assert_eq!(tuned_parallelism(&runs, 2, HDD_MAX_THREADS), 1);
+
+        let hdd = compute_thread_policy_for_storage(StorageClass::Hdd, false, None, None, &runs);
+        assert!(hdd.io_parallelism <= HDD_MAX_THREADS);
+    }
+
+    #[test]
+    fn tuned_level_is_still_halved_for_background() {
+        let runs = samples(&[(2, 100), (2, 100), (1, 50), (1, 50)]);
+        let fg = compute_thread_policy_for_storage(StorageClass::Hdd, false, None, None, &runs);
+        let bg = compute_thread_policy_for_storage(StorageClass::Hdd, true, None, None, &runs);
+        assert_eq!(fg.io_parallelism, 2);
+        assert_eq!(bg.io_parallelism, 1);
+    }
+
+    #[test]
+    fn short_runs_are_not_sampled() {
+        let gib = 1024 * 1024 * 1024;
+        assert_eq!(
+            ThroughputSample::measure(4, gib, Duration::from_secs(2)),
+            None
+        );
+        assert_eq!(
+            ThroughputSample::measure(4, gib, Duration::from_secs(16)),
+            Some(ThroughputSample {
+                io_parallelism: 4,
+                bytes_per_sec: gib / 16,
+            })
+        );
+    }
 }
@@ -0,0 +1,431 @@
+//! Compression of save-game and config folders outside install directories.
+//!
+//! Games keep saves, shader caches and configs under the user profile
+//! (`Documents\My Games`, `Saved Games`, `AppData`), which can grow large.
+//! Those folders are written while games run and hold data that cannot be
+//! re-downloaded, so this mode is opt-in and guarded:
+//!
+//! - only folders the user approved in settings are compressed, and only
+//!   when they sit strictly inside one of the known profile roots;
+//! - compression always uses [`USER_DATA_ALGORITHM`], the cheapest to read
+//!   and write back;
+//! - automation leaves these folders alone unless the user opts in;
+//! - savings are tracked here, apart from the game compression history.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use super::algorithm::CompressionAlgorithm;
+use super::engine::CompressionStats;
+use crate::settings::UserDataSettings;
+use crate::utils::{normalize_path_key, unix_now_ms};
+
+/// Algorithm used for user data regardless of the game setting: saves are
+/// small and rewritten often, so decompression cost matters more than ratio.
+pub const USER_DATA_ALGORITHM: CompressionAlgorithm = CompressionAlgorithm::Xpress4K;
+
+const STATS_FILE_NAME: &str = "user_data_stats.json";
+/// Folder under the roaming root that holds this app's settings, and its
+/// other state unless the data directory was moved.
+const OWN_DATA_DIR_NAME: &str = "compact_games";
+
+/// Where under the profile a location was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UserDataKind {
+    /// `Documents\My Games`.
+    MyGames,
+    /// `%USERPROFILE%\Saved Games`.
+    SavedGames,
+    /// `%APPDATA%`.
+    RoamingAppData,
+    /// `%LOCALAPPDATA%`.
+    LocalAppData,
+}
+
+/// A save or config folder that looks like it belongs to a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDataLocation {
+    pub kind: UserDataKind,
+    pub path: PathBuf,
+    pub approved: bool,
+}
+
+/// Why a folder may not be compressed as user data.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UserDataError {
+    #[error("user data compression is turned off")]
+    Disabled,
+    #[error("{path} is not inside a known save or config folder")]
+    OutsideUserData { path: String },
+    #[error("{path} has not been approved for compression")]
+    NotApproved { path: String },
+}
+
+/// Savings from the last compression of one approved folder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserDataStats {
+    pub path: String,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    pub files_processed: u64,
+    pub compressed_at_ms: u64,
+}
+
+impl UserDataStats {
+    pub fn saved_bytes(&self) -> u64 {
+        self.original_bytes.saturating_sub(self.compressed_bytes)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StatsFile {
+    #[serde(default)]
+    entries: HashMap<String, UserDataStats>,
+}
+
+#[cfg(not(test))]
+static STATS_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+static STATS: LazyLock<RwLock<StatsFile>> = LazyLock::new(|| RwLock::new(load_stats()));
+
+/// The profile folders games keep saves and configs in, where present.
+pub fn known_roots() -> Vec<(UserDataKind, PathBuf)> {
+    [
+        (
+            UserDataKind::MyGames,
+            dirs::document_dir().map(|dir| dir.join("My Games")),
+        ),
+        (
+            UserDataKind::SavedGames,
+            dirs::home_dir().map(|dir| dir.join("Saved Games")),
+        ),
+        (UserDataKind::RoamingAppData, dirs::config_dir()),
+        (UserDataKind::LocalAppData, dirs::data_local_dir()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, root)| Some((kind, root?)))
+    .collect()
+}
+
+/// Folders under the known roots whose name matches `game_name`.
+pub fn locations_for_game(game_name: &str, settings: &UserDataSettings) -> Vec<UserDataLocation> {
+    locations_in(&known_roots(), game_name, settings)
+}
+
+fn locations_in(
+    roots: &[(UserDataKind, PathBuf)],
+    game_name: &str,
+    settings: &UserDataSettings,
+) -> Vec<UserDataLocation> {
+    let wanted = folder_match_key(game_name);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let mut locations = Vec::new();
+    for (kind, root) in roots {
+        let Ok(entries) = fs::read_dir(root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+            let name = entry.file_name();
+            if !is_dir || folder_match_key(&name.to_string_lossy()) != wanted {
+                continue;
+            }
+            let path = entry.path();
+            locations.push(UserDataLocation {
+                kind: *kind,
+                approved: is_approved(&path, settings),
+                path,
+            });
+        }
+    }
+    locations
+}
+
+/// Check the safeguards before compressing `path` as user data.
+pub fn check_compressible(path: &Path, settings: &UserDataSettings) -> Result<(), UserDataError> {
+    check_compressible_in(&known_roots(), path, settings)
+}
+
+fn check_compressible_in(
+    roots: &[(UserDataKind, PathBuf)],
+    path: &Path,
+    settings: &UserDataSettings,
+) -> Result<(), UserDataError> {
+    if !settings.enabled {
+        return Err(UserDataError::Disabled);
+    }
+    let display = path.display().to_string();
+    if root_containing(roots, path).is_none() || is_own_data(roots, path) {
+        return Err(UserDataError::OutsideUserData { path: display });
+    }
+    if !is_approved(path, settings) {
+        return Err(UserDataError::NotApproved { path: display });
+    }
+    Ok(())
+}
+
+/// True when automation should treat `path` as user data: an approved
+/// folder, or anything under the save-game roots. `AppData` as a whole is
+/// not, since some launchers install games there.
+pub fn is_user_data_path(path: &Path, settings: &UserDataSettings) -> bool {
+    is_user_data_path_in(&known_roots(), path, settings)
+}
+
+fn is_user_data_path_in(
+    roots: &[(UserDataKind, PathBuf)],
+    path: &Path,
+    settings: &UserDataSettings,
+) -> bool {
+    is_approved(path, settings)
+        || matches!(
+            root_containing(roots, path),
+            Some(UserDataKind::MyGames | UserDataKind::SavedGames)
+        )
+}
+
+/// Remember the savings of a finished run on `path`.
+pub fn record(path: &Path, stats: &CompressionStats) {
+    let entry = UserDataStats {
+        path: path.to_string_lossy().into_owned(),
+        original_bytes: stats.original_bytes,
+        compressed_bytes: stats.compressed_bytes,
+        files_processed: stats.files_processed,
+        compressed_at_ms: unix_now_ms(),
+    };
+    let snapshot = with_stats_write(|file| {
+        file.entries.insert(normalize_path_key(path), entry);
+        file.clone()
+    });
+    save_stats(&snapshot);
+}
+
+/// Drop the savings recorded for `path`, e.g. after decompressing it.
+pub fn forget(path: &Path) {
+    let snapshot = with_stats_write(|file| {
+        file.entries
+            .remove(&normalize_path_key(path))
+            .map(|_| file.clone())
+    });
+    if let Some(snapshot) = snapshot {
+        save_stats(&snapshot);
+    }
+}
+
+/// Recorded savings per folder, largest first.
+pub fn all_stats() -> Vec<UserDataStats> {
+    let mut stats: Vec<UserDataStats> =
+        with_stats_read(|file| file.entries.values().cloned().collect());
+    stats.sort_by_key(|entry| std::cmp::Reverse(entry.saved_bytes()));
+    stats
+}
+
+/// The root among `roots` that strictly contains `path`.
+fn root_containing(roots: &[(UserDataKind, PathBuf)], path: &Path) -> Option<UserDataKind> {
+    let key = normalize_path_key(path);
+    roots.iter().find_map(|(kind, root)| {
+        let root_key = normalize_path_key(root);
+        (key != root_key && Path::new(&key).starts_with(&root_key)).then_some(*kind)
+    })
+}
+
+fn is_own_data(roots: &[(UserDataKind, PathBuf)], path: &Path) -> bool {
+    let key = normalize_path_key(path);
+    let in_roaming_dir = roots
+        .iter()
+        .filter(|(kind, _)| *kind == UserDataKind::RoamingAppData)
+        .any(|(_, root)| {
+            Path::new(&key).starts_with(normalize_path_key(&root.join(OWN_DATA_DIR_NAME)))
+        });
+    in_roaming_dir
+        || crate::paths::data_dir()
+            .is_ok_and(|dir| Path::new(&key).starts_with(normalize_path_key(&dir)))
+}
+
+fn is_approved(path: &Path, settings: &UserDataSettings) -> bool {
+    let key = normalize_path_key(path);
+    settings
+        .approved_paths
+        .iter()
+        .any(|approved| Path::new(&key).starts_with(normalize_path_key(Path::new(approved))))
+}
+
+/// Folder names match game names ignoring case, spaces and punctuation,
+/// so `Elden Ring` finds `EldenRing`.
+fn folder_match_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn save_stats(snapshot: &StatsFile) {
+    let result = stats_path().and_then(|path| {
+        let json = serde_json::to_vec(snapshot).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&path, &json)
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to persist user data stats: {e}");
+    }
+}
+
+fn load_stats() -> StatsFile {
+    let Ok(path) = stats_path() else {
+        return StatsFile::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return StatsFile::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse user data stats: {e}");
+        StatsFile::default()
+    })
+}
+
+fn with_stats_read<R>(f: impl FnOnce(&StatsFile) -> R) -> R {
+    let guard = STATS.read().unwrap_or_else(|e| e.into_inner());
+    f(&guard)
+}
+
+fn with_stats_write<R>(f: impl FnOnce(&mut StatsFile) -> R) -> R {
+    let mut guard = STATS.write().unwrap_or_else(|e| e.into_inner());
+    f(&mut guard)
+}
+
+fn stats_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-user-data-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(STATS_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !STATS_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            STATS_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(STATS_FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(approved: &[&Path]) -> UserDataSettings {
+        UserDataSettings {
+            enabled: true,
+            approved_paths: approved
+                .iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect(),
+            include_in_automation: false,
+        }
+    }
+
+    #[test]
+    fn finds_game_folders_by_loose_name() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let my_games = temp.path().join("My Games");
+        let local = temp.path().join("Local");
+        fs::create_dir_all(my_games.join("EldenRing")).unwrap();
+        fs::create_dir_all(local.join("Elden-Ring")).unwrap();
+        fs::create_dir_all(local.join("Other Game")).unwrap();
+        let roots = [
+            (UserDataKind::MyGames, my_games.clone()),
+            (UserDataKind::LocalAppData, local.clone()),
+        ];
+
+        let mut found = locations_in(&roots, "Elden Ring", &enabled(&[&my_games]));
+        found.sort_by(|a, b| a.path.cmp(&b.path));
+
+        assert_eq!(found.len(), 2);
+        assert!(found
+            .iter()
+            .any(|location| location.kind == UserDataKind::MyGames && location.approved));
+        assert!(found
+            .iter()
+            .any(|location| location.kind == UserDataKind::LocalAppData && !location.approved));
+    }
+
+    #[test]
+    fn only_approved_folders_inside_known_roots_pass() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let roaming = temp.path().join("Roaming");
+        let saves = roaming.join("Studio").join("Game");
+        let roots = [(UserDataKind::RoamingAppData, roaming.clone())];
+
+        assert_eq!(
+            check_compressible_in(&roots, &saves, &UserDataSettings::default()),
+            Err(UserDataError::Disabled)
+        );
+        assert!(matches!(
+            check_compressible_in(&roots, &saves, &enabled(&[])),
+            Err(UserDataError::NotApproved { .. })
+        ));
+        assert_eq!(
+            check_compressible_in(&roots, &saves, &enabled(&[&roaming.join("Studio")])),
+            Ok(())
+        );
+        // Approving a root does not open the whole root, nor anything outside.
+        assert!(matches!(
+            check_compressible_in(&roots, &roaming, &enabled(&[&roaming])),
+            Err(UserDataError::OutsideUserData { .. })
+        ));
+        let elsewhere = temp.path().join("Games").join("Game");
+        assert!(matches!(
+            check_compressible_in(&roots, &elsewhere, &enabled(&[&elsewhere])),
+            Err(UserDataError::OutsideUserData { .. })
+        ));
+        let own = roaming.join(OWN_DATA_DIR_NAME);
+        assert!(matches!(
+            check_compressible_in(&roots, &own, &enabled(&[&own])),
+            Err(UserDataError::OutsideUserData { .. })
+        ));
+    }
+
+    #[test]
+    fn automation_skips_save_roots_and_approvals_but_not_all_of_appdata() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let my_games = temp.path().join("My Games");
+        let roaming = temp.path().join("Roaming");
+        let roots = [
+            (UserDataKind::MyGames, my_games.clone()),
+            (UserDataKind::RoamingAppData, roaming.clone()),
+        ];
+        let approved = roaming.join("Studio");
+        let settings = enabled(&[&approved]);
+
+        assert!(is_user_data_path_in(
+            &roots,
+            &my_games.join("Game"),
+            &settings
+        ));
+        assert!(is_user_data_path_in(&roots, &approved, &settings));
+        let launcher_install = roaming.join("itch").join("apps").join("Game");
+        assert!(!is_user_data_path_in(&roots, &launcher_install, &settings));
+    }
+}
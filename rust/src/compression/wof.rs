@@ -4,7 +4,7 @@
 //! Every public function returns `Result<T, CompressionError>`.
 
 use std::fs::{File, OpenOptions};
-use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::os::windows::ffi::OsStringExt;
 use std::os::windows::fs::OpenOptionsExt;
 use std::os::windows::io::AsRawHandle;
 use std::path::Path;
@@ -12,12 +12,15 @@ use std::path::Path;
 use windows::core::PCWSTR;
 use windows::Win32::Foundation::HANDLE;
 use windows::Win32::Storage::FileSystem::{
-    GetCompressedFileSizeW, GetFileInformationByHandle, GetFinalPathNameByHandleW,
-    BY_HANDLE_FILE_INFORMATION, FILE_ATTRIBUTE_REPARSE_POINT, FILE_NAME_NORMALIZED,
-    FILE_SHARE_DELETE, FILE_SHARE_READ,
+    FindClose, FindFirstStreamW, FindNextStreamW, FindStreamInfoStandard, GetCompressedFileSizeW,
+    GetFileInformationByHandle, GetFinalPathNameByHandleW, BY_HANDLE_FILE_INFORMATION,
+    FILE_ATTRIBUTE_COMPRESSED, FILE_ATTRIBUTE_ENCRYPTED, FILE_ATTRIBUTE_INTEGRITY_STREAM,
+    FILE_ATTRIBUTE_REPARSE_POINT, FILE_ATTRIBUTE_SPARSE_FILE, FILE_NAME_NORMALIZED,
+    FILE_SHARE_DELETE, FILE_SHARE_READ, WIN32_FIND_STREAM_DATA,
 };
 use windows::Win32::System::Ioctl::{
-    FSCTL_DELETE_EXTERNAL_BACKING, FSCTL_GET_EXTERNAL_BACKING, FSCTL_SET_EXTERNAL_BACKING,
+    FSCTL_DELETE_EXTERNAL_BACKING, FSCTL_GET_EXTERNAL_BACKING, FSCTL_SET_COMPRESSION,
+    FSCTL_SET_EXTERNAL_BACKING,
 };
 use windows::Win32::System::IO::DeviceIoControl;
 
@@ -27,12 +30,36 @@ use super::error::CompressionError;
 const WOF_CURRENT_VERSION: u32 = 1;
 const WOF_PROVIDER_FILE: u32 = 2;
 const FILE_PROVIDER_CURRENT_VERSION: u32 = 1;
+/// `COMPRESSION_FORMAT_NONE` for `FSCTL_SET_COMPRESSION`.
+const COMPRESSION_FORMAT_NONE: u16 = 0;
 
+const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+
+const ERROR_INVALID_FUNCTION: u32 = 1;
 const ERROR_ACCESS_DENIED: u32 = 5;
 const ERROR_SHARING_VIOLATION: u32 = 32;
+const ERROR_NOT_SUPPORTED: u32 = 50;
+const ERROR_INVALID_PARAMETER: u32 = 87;
 const ERROR_DISK_FULL: u32 = 112;
 const ERROR_COMPRESSION_NOT_BENEFICIAL: u32 = 344;
 const ERROR_OBJECT_NOT_EXTERNALLY_BACKED: u32 = 342;
+const ERROR_FILE_ENCRYPTED: u32 = 6002;
+
+/// Set on an HRESULT that wraps an NTSTATUS rather than a Win32 code.
+const FACILITY_NT_BIT: i32 = 0x1000_0000;
+/// NTSTATUS codes the WOF driver surfaces, with their Win32 equivalents.
+const NTSTATUS_TO_WIN32: &[(u32, u32)] = &[
+    (0xC000_000D, ERROR_INVALID_PARAMETER),
+    (0xC000_0010, ERROR_INVALID_FUNCTION), // STATUS_INVALID_DEVICE_REQUEST
+    (0xC000_0022, ERROR_ACCESS_DENIED),
+    (0xC000_0043, ERROR_SHARING_VIOLATION),
+    (0xC000_007F, ERROR_DISK_FULL),
+    (0xC000_00BB, ERROR_NOT_SUPPORTED),
+    (0xC000_0293, ERROR_FILE_ENCRYPTED),
+];
+/// An invalid-parameter failure on a file below one cluster means WOF
+/// had nothing to back: the data lives in the file record itself.
+const WOF_MIN_FILE_BYTES: u64 = 4096;
 
 // ── FFI structs ──────────────────────────────────────────────────────
 
@@ -73,7 +100,7 @@ pub fn wof_compress_file(
     path: &Path,
     algorithm: CompressionAlgorithm,
 ) -> Result<CompressFileResult, CompressionError> {
-    let file = open_for_wof(path)?;
+    let file = open_for_wof(path, false)?;
     wof_compress_open_file(&file, path, algorithm)
 }
 
@@ -118,14 +145,14 @@ pub(crate) fn wof_compress_open_file(
             if code == ERROR_COMPRESSION_NOT_BENEFICIAL {
                 Ok(CompressFileResult::NotBeneficial)
             } else {
-                Err(map_win32(code, path))
+                Err(map_compress_failure(code, file, path))
             }
         }
     }
 }
 
 pub fn wof_decompress_file(path: &Path) -> Result<(), CompressionError> {
-    let file = open_for_wof(path)?;
+    let file = open_for_wof(path, false)?;
     wof_decompress_open_file(&file, path)
 }
 
@@ -213,12 +240,45 @@ pub(crate) fn wof_get_compression_open_file(
     }
 }
 
+/// Whether `file` carries legacy NTFS (LZNT1) compression, as applied by
+/// `compact /c` without `/exe`.
+pub(crate) fn is_ntfs_compressed_open_file(file: &File) -> bool {
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    let queried = unsafe { GetFileInformationByHandle(file_handle(file), &mut info).is_ok() };
+    queried && info.dwFileAttributes & FILE_ATTRIBUTE_COMPRESSED.0 != 0
+}
+
+/// Clear legacy NTFS compression so WOF can back the file instead. The
+/// file is written out uncompressed first, so it needs its full logical
+/// size free on the volume for a moment.
+pub(crate) fn clear_ntfs_compression_open_file(
+    file: &File,
+    path: &Path,
+) -> Result<(), CompressionError> {
+    let handle = file_handle(file);
+    let format = COMPRESSION_FORMAT_NONE;
+    let mut returned: u32 = 0;
+
+    let result = unsafe {
+        DeviceIoControl(
+            handle,
+            FSCTL_SET_COMPRESSION,
+            Some(std::ptr::addr_of!(format).cast()),
+            std::mem::size_of::<u16>() as u32,
+            None,
+            0,
+            Some(&mut returned),
+            None,
+        )
+    };
+
+    result.map_err(|e| map_win32(win32_code(&e), path))
+}
+
+/// On-disk size of the unnamed data stream. Alternate data streams are
+/// not included; see [`alternate_stream_bytes`].
 pub fn get_physical_size(path: &Path) -> Result<u64, CompressionError> {
-    let wide: Vec<u16> = path
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
+    let wide = crate::utils::wide_path(path);
     let mut high: u32 = 0;
 
     let low = unsafe { GetCompressedFileSizeW(PCWSTR(wide.as_ptr()), Some(&mut high)) };
@@ -233,17 +293,74 @@ pub fn get_physical_size(path: &Path) -> Result<u64, CompressionError> {
     Ok(((high as u64) << 32) | (low as u64))
 }
 
-fn open_for_wof(path: &Path) -> Result<File, CompressionError> {
-    OpenOptions::new()
+/// Total size of the named data streams on `path`, 0 when it has none or
+/// they cannot be listed. WOF only compresses the unnamed stream, so these
+/// bytes stay on disk as they are.
+pub fn alternate_stream_bytes(path: &Path) -> u64 {
+    let wide = crate::utils::wide_path(path);
+    let mut data = WIN32_FIND_STREAM_DATA::default();
+    let Ok(handle) = (unsafe {
+        FindFirstStreamW(
+            PCWSTR(wide.as_ptr()),
+            FindStreamInfoStandard,
+            std::ptr::addr_of_mut!(data).cast(),
+            None,
+        )
+    }) else {
+        return 0;
+    };
+
+    let mut total = 0u64;
+    loop {
+        if !is_unnamed_stream(&data.cStreamName) {
+            total = total.saturating_add(data.StreamSize.max(0) as u64);
+        }
+        if unsafe { FindNextStreamW(handle, std::ptr::addr_of_mut!(data).cast()) }.is_err() {
+            break;
+        }
+    }
+    let _ = unsafe { FindClose(handle) };
+    total
+}
+
+/// Whether a `FindFirstStreamW` name is the unnamed `::$DATA` stream.
+fn is_unnamed_stream(name: &[u16]) -> bool {
+    let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+    String::from_utf16_lossy(&name[..len]) == "::$DATA"
+}
+
+fn open_for_wof(path: &Path, backup_semantics: bool) -> Result<File, CompressionError> {
+    let mut options = OpenOptions::new();
+    options
         .access_mode(0x0001 | 0x0002)
-        .share_mode((FILE_SHARE_READ | FILE_SHARE_DELETE).0)
-        .open(path)
-        .map_err(|e| map_io(e, path))
+        .share_mode((FILE_SHARE_READ | FILE_SHARE_DELETE).0);
+    if backup_semantics {
+        options.custom_flags(FILE_FLAG_BACKUP_SEMANTICS);
+    }
+    options.open(path).map_err(|e| map_io(e, path))
 }
 
 pub(crate) fn open_verified_file(
     path: &Path,
     canonical_root: &Path,
+) -> Result<File, CompressionError> {
+    open_verified(path, canonical_root, false)
+}
+
+/// [`open_verified_file`] with `FILE_FLAG_BACKUP_SEMANTICS`, which skips
+/// the ACL check once [`super::privilege::enable_backup_privileges`] has
+/// succeeded.
+pub(crate) fn open_verified_file_for_backup(
+    path: &Path,
+    canonical_root: &Path,
+) -> Result<File, CompressionError> {
+    open_verified(path, canonical_root, true)
+}
+
+fn open_verified(
+    path: &Path,
+    canonical_root: &Path,
+    backup_semantics: bool,
 ) -> Result<File, CompressionError> {
     let metadata = std::fs::symlink_metadata(path).map_err(|error| map_io(error, path))?;
     if !metadata.is_file() || metadata.file_type().is_symlink() {
@@ -251,7 +368,7 @@ pub(crate) fn open_verified_file(
             message: format!("reparse point rejected: {}", path.display()),
         });
     }
-    let file = open_for_wof(path)?;
+    let file = open_for_wof(path, backup_semantics)?;
     let mut info = BY_HANDLE_FILE_INFORMATION::default();
     unsafe {
         GetFileInformationByHandle(file_handle(&file), &mut info)
@@ -310,7 +427,57 @@ fn file_handle(file: &File) -> HANDLE {
 }
 
 fn win32_code(e: &windows::core::Error) -> u32 {
-    (e.code().0 & 0xFFFF) as u32
+    decode_error_code(e.code().0)
+}
+
+/// Win32 code behind an HRESULT, translating the NTSTATUS codes WOF
+/// reports through `FACILITY_NT`.
+fn decode_error_code(hresult: i32) -> u32 {
+    if hresult & FACILITY_NT_BIT != 0 {
+        let status = (hresult & !FACILITY_NT_BIT) as u32;
+        if let Some(&(_, code)) = NTSTATUS_TO_WIN32.iter().find(|(nt, _)| *nt == status) {
+            return code;
+        }
+    }
+    (hresult & 0xFFFF) as u32
+}
+
+/// [`map_win32`] for a failed compression, naming the file property that
+/// stopped WOF when the code alone does not say.
+fn map_compress_failure(code: u32, file: &File, path: &Path) -> CompressionError {
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    let known = unsafe { GetFileInformationByHandle(file_handle(file), &mut info) }.is_ok();
+    if !known {
+        return map_win32(code, path);
+    }
+    let len = (u64::from(info.nFileSizeHigh) << 32) | u64::from(info.nFileSizeLow);
+    classify_compress_failure(code, info.dwFileAttributes, len, path)
+}
+
+fn classify_compress_failure(
+    code: u32,
+    attributes: u32,
+    len: u64,
+    path: &Path,
+) -> CompressionError {
+    let path_buf = || path.to_path_buf();
+    match code {
+        // Transient whatever the file is; keep the retryable error.
+        ERROR_SHARING_VIOLATION | ERROR_DISK_FULL => map_win32(code, path),
+        _ if code == ERROR_FILE_ENCRYPTED || attributes & FILE_ATTRIBUTE_ENCRYPTED.0 != 0 => {
+            CompressionError::EncryptedFile { path: path_buf() }
+        }
+        _ if attributes & FILE_ATTRIBUTE_INTEGRITY_STREAM.0 != 0 => {
+            CompressionError::IntegrityStreamFile { path: path_buf() }
+        }
+        _ if attributes & FILE_ATTRIBUTE_SPARSE_FILE.0 != 0 => {
+            CompressionError::SparseFile { path: path_buf() }
+        }
+        ERROR_INVALID_PARAMETER if len < WOF_MIN_FILE_BYTES => {
+            CompressionError::FileTooSmallForWof { path: path_buf() }
+        }
+        _ => map_win32(code, path),
+    }
 }
 
 fn map_win32(code: u32, path: &Path) -> CompressionError {
@@ -323,13 +490,21 @@ fn map_win32(code: u32, path: &Path) -> CompressionError {
             path: path.to_path_buf(),
         },
         ERROR_DISK_FULL => CompressionError::DiskFull,
+        ERROR_INVALID_FUNCTION | ERROR_NOT_SUPPORTED => {
+            CompressionError::CompressionNotSupportedOnVolume {
+                path: path.to_path_buf(),
+            }
+        }
+        ERROR_FILE_ENCRYPTED => CompressionError::EncryptedFile {
+            path: path.to_path_buf(),
+        },
         _ => CompressionError::WofApiError {
             message: format!("Win32 error {code} on {}", path.display()),
         },
     }
 }
 
-fn map_io(e: std::io::Error, path: &Path) -> CompressionError {
+pub(crate) fn map_io(e: std::io::Error, path: &Path) -> CompressionError {
     match e.raw_os_error() {
         Some(raw) if raw == ERROR_ACCESS_DENIED as i32 => CompressionError::PermissionDenied {
             path: path.to_path_buf(),
@@ -368,4 +543,68 @@ mod tests {
     fn struct_alignment() {
         assert_eq!(std::mem::align_of::<WofBackingBuffer>(), 4);
     }
+
+    #[test]
+    fn ntstatus_and_win32_codes_decode_alike() {
+        // HRESULT_FROM_WIN32(ERROR_NOT_SUPPORTED)
+        assert_eq!(
+            decode_error_code(0x8007_0032_u32 as i32),
+            ERROR_NOT_SUPPORTED
+        );
+        // HRESULT_FROM_NT(STATUS_NOT_SUPPORTED)
+        assert_eq!(
+            decode_error_code(0xD000_00BB_u32 as i32),
+            ERROR_NOT_SUPPORTED
+        );
+        assert_eq!(
+            decode_error_code(0xD000_0293_u32 as i32),
+            ERROR_FILE_ENCRYPTED
+        );
+    }
+
+    #[test]
+    fn compress_failures_name_the_file_property() {
+        let path = Path::new(r"C:\Games\Game\data.bin");
+        let classify =
+            |code, attributes, len| classify_compress_failure(code, attributes, len, path);
+
+        assert!(matches!(
+            classify(ERROR_ACCESS_DENIED, FILE_ATTRIBUTE_ENCRYPTED.0, 1 << 20),
+            CompressionError::EncryptedFile { .. }
+        ));
+        assert!(matches!(
+            classify(
+                ERROR_INVALID_PARAMETER,
+                FILE_ATTRIBUTE_SPARSE_FILE.0,
+                1 << 20
+            ),
+            CompressionError::SparseFile { .. }
+        ));
+        assert!(matches!(
+            classify(ERROR_INVALID_PARAMETER, 0, 512),
+            CompressionError::FileTooSmallForWof { .. }
+        ));
+        assert!(matches!(
+            classify(ERROR_INVALID_FUNCTION, 0, 1 << 20),
+            CompressionError::CompressionNotSupportedOnVolume { .. }
+        ));
+        assert!(matches!(
+            classify(ERROR_SHARING_VIOLATION, FILE_ATTRIBUTE_ENCRYPTED.0, 1 << 20),
+            CompressionError::LockedFile { .. }
+        ));
+    }
+
+    #[test]
+    fn alternate_streams_are_summed_apart_from_the_main_stream() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("data.bin");
+        std::fs::write(&file, vec![0u8; 4096]).unwrap();
+        assert_eq!(alternate_stream_bytes(&file), 0);
+
+        let mut stream = file.clone().into_os_string();
+        stream.push(":launcher.meta");
+        std::fs::write(&stream, vec![0u8; 1000]).unwrap();
+        assert_eq!(alternate_stream_bytes(&file), 1000);
+        assert_eq!(std::fs::metadata(&file).unwrap().len(), 4096);
+    }
 }
@@ -0,0 +1,30 @@
+//! Foreground mode: automation runs until Ctrl+C or the console closes.
+
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Mutex, OnceLock};
+
+use windows::core::BOOL;
+use windows::Win32::System::Console::SetConsoleCtrlHandler;
+
+use super::DaemonError;
+
+static STOP_TX: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+
+pub fn run() -> Result<(), DaemonError> {
+    let (stop_tx, stop_rx) = channel();
+    if STOP_TX.set(Mutex::new(stop_tx)).is_ok() {
+        // SAFETY: `on_console_ctrl` only touches the static sender.
+        if let Err(e) = unsafe { SetConsoleCtrlHandler(Some(on_console_ctrl), true) } {
+            log::warn!("Ctrl+C handler unavailable: {e}");
+        }
+    }
+    super::run(&stop_rx, false)
+}
+
+unsafe extern "system" fn on_console_ctrl(_ctrl_type: u32) -> BOOL {
+    if let Some(stop_tx) = STOP_TX.get() {
+        let _ = stop_tx.lock().unwrap_or_else(|e| e.into_inner()).send(());
+    }
+    // Handled: give `run` time to stop automation cleanly.
+    BOOL(1)
+}
@@ -0,0 +1,55 @@
+//! Mirrors daemon warnings and errors into the Windows Application event
+//! log, where administrators look for service problems.
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::System::EventLog::{
+    RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE, EVENTLOG_WARNING_TYPE,
+};
+
+use crate::logging::{self, LogLevel};
+use crate::utils::wide_null_str;
+
+/// Generic event id; the message text carries the detail.
+const EVENT_ID: u32 = 1;
+
+/// Forward every captured warning and error to the event log under
+/// `source`. Failing to register the source only loses the mirror.
+pub(super) fn install(source: &str) {
+    let source = wide_null_str(source);
+    // SAFETY: `source` is a live null-terminated UTF-16 buffer.
+    let handle = match unsafe { RegisterEventSourceW(PCWSTR::null(), PCWSTR(source.as_ptr())) } {
+        Ok(handle) => handle,
+        Err(e) => {
+            log::warn!("Event log unavailable: {e}");
+            return;
+        }
+    };
+    // The source stays registered for the life of the process; HANDLE is
+    // not Send, so the listener keeps the raw value.
+    let raw = handle.0 as isize;
+    logging::add_listener(Box::new(move |event| {
+        let event_type = match event.level {
+            LogLevel::Error => EVENTLOG_ERROR_TYPE,
+            LogLevel::Warn => EVENTLOG_WARNING_TYPE,
+            LogLevel::Info | LogLevel::Debug | LogLevel::Trace => return true,
+        };
+        let message = wide_null_str(&format!("[{}] {}", event.target, event.message));
+        let strings = [PCWSTR(message.as_ptr())];
+        // SAFETY: the handle came from RegisterEventSourceW and is never
+        // deregistered; `message` outlives the call.
+        let _ = unsafe {
+            ReportEventW(
+                HANDLE(raw as *mut core::ffi::c_void),
+                event_type,
+                0,
+                EVENT_ID,
+                None,
+                0,
+                Some(&strings),
+                None,
+            )
+        };
+        true
+    }));
+}
@@ -0,0 +1,149 @@
+//! Headless automation host behind the `pressplay-daemon` binary.
+//!
+//! Runs the same automation loop the app starts, configured from the
+//! shared [`crate::settings`] file, so auto-compression keeps working with
+//! the app closed. Runs either as a Windows service (`service`) or from a console
+//! (`run`). The app sees it through the heartbeat in [`status`]; scripts
+//! and the CLI drive it over the [`crate::ipc`] server it hosts.
+//!
+//! A service installed without an account runs as LocalSystem and so reads
+//! that profile's config directory; install it under the user's account
+//! to share settings, journal and history with the app.
+
+#[cfg(windows)]
+pub mod console;
+#[cfg(windows)]
+mod event_log;
+#[cfg(windows)]
+pub mod service;
+pub mod startup;
+pub mod status;
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::api::automation::{
+    apply_automation_config, get_scheduler_state, get_watcher_diagnostics,
+    is_auto_compression_running, start_auto_compression, stop_auto_compression,
+    stop_auto_compression_with_timeout,
+};
+use crate::api::automation_types::FrbAutomationError;
+use crate::ipc::server::IpcServer;
+
+use crate::settings::{self, Settings};
+
+use self::status::DaemonStatus;
+
+/// How often the heartbeat is written and the saved settings re-checked.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// Time automation gets to stop on exit; the service manager's default
+/// stop wait is 20 seconds.
+const STOP_TIMEOUT_MS: u64 = 15_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonError {
+    #[error("automation failed: {0}")]
+    Automation(#[from] FrbAutomationError),
+    #[cfg(windows)]
+    #[error("service control failed: {0}")]
+    Service(#[from] windows_service::Error),
+}
+
+/// Run automation until `stop_rx` fires or disconnects.
+pub fn run(stop_rx: &Receiver<()>, is_service: bool) -> Result<(), DaemonError> {
+    let started_at_ms = crate::utils::unix_now_ms();
+    if settings::load_saved().is_none() {
+        log::warn!("No saved settings yet; running with defaults until the app saves some");
+    }
+    let mut applied: Option<Settings> = None;
+    apply_settings(&mut applied)?;
+    let ipc = match IpcServer::start() {
+        Ok(server) => Some(server),
+        Err(e) => {
+            log::warn!("IPC server disabled: {e}");
+            None
+        }
+    };
+    log::info!("Daemon started (pid {})", std::process::id());
+
+    loop {
+        write_heartbeat(started_at_ms, is_service, true);
+        match stop_rx.recv_timeout(HEARTBEAT_INTERVAL) {
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                if let Err(e) = apply_settings(&mut applied) {
+                    log::warn!("Failed to apply saved settings: {e}");
+                }
+            }
+        }
+    }
+
+    log::info!("Daemon stopping");
+    if let Some(ipc) = ipc {
+        ipc.stop();
+    }
+    let stopped = if is_auto_compression_running() {
+        stop_auto_compression_with_timeout(STOP_TIMEOUT_MS).map(|outcome| {
+            log::info!(
+                "Automation stopped at {:?} after {} ms",
+                outcome.stopped_at,
+                outcome.elapsed_ms
+            );
+        })
+    } else {
+        Ok(())
+    };
+    write_heartbeat(started_at_ms, is_service, false);
+    stopped.map_err(Into::into)
+}
+
+/// Bring automation in line with the saved settings when they differ from
+/// the ones last applied, so changes made in the app reach a running
+/// daemon.
+fn apply_settings(applied: &mut Option<Settings>) -> Result<(), FrbAutomationError> {
+    let saved = settings::load();
+    if applied.as_ref() == Some(&saved) {
+        return Ok(());
+    }
+    if saved.auto_compress {
+        if !is_auto_compression_running() {
+            match start_auto_compression() {
+                Ok(()) => {}
+                // The app runs automation itself; take over once it stops.
+                Err(FrbAutomationError::RunningElsewhere { owner }) => {
+                    log::debug!("Automation runs in {owner}; standing by");
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        apply_automation_config(saved.automation.clone().into())?;
+    } else if is_auto_compression_running() {
+        log::info!("Auto-compression is off in settings; stopping automation");
+        stop_auto_compression()?;
+    }
+    crate::api::launch_lag::apply_settings(&saved);
+    crate::api::crash_rollback::apply_settings(&saved);
+    log::info!("Applied saved settings");
+    *applied = Some(saved);
+    Ok(())
+}
+
+fn write_heartbeat(started_at_ms: u64, is_service: bool, running: bool) {
+    let diagnostics = get_watcher_diagnostics();
+    let status = DaemonStatus {
+        pid: std::process::id(),
+        is_service,
+        started_at_ms,
+        updated_at_ms: crate::utils::unix_now_ms(),
+        running,
+        automation_running: is_auto_compression_running(),
+        scheduler_state: format!("{:?}", get_scheduler_state()),
+        queue_depth: diagnostics.queue_depth,
+        watched_path_count: diagnostics.watched_path_count,
+        last_error: diagnostics.last_error,
+    };
+    if let Err(e) = status::write(&status) {
+        log::debug!("Failed to write daemon heartbeat: {e}");
+    }
+}
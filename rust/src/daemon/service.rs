@@ -0,0 +1,159 @@
+//! Windows service plumbing: dispatcher entry, control handler and
+//! install/uninstall with restart-on-failure recovery actions.
+
+use std::ffi::OsString;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use windows_service::service::{
+    ServiceAccess, ServiceAction, ServiceActionType, ServiceControl, ServiceControlAccept,
+    ServiceErrorControl, ServiceExitCode, ServiceFailureActions, ServiceFailureResetPeriod,
+    ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+};
+use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+use windows_service::{define_windows_service, service_dispatcher};
+
+use super::DaemonError;
+
+pub const SERVICE_NAME: &str = "pressplay-daemon";
+const SERVICE_DISPLAY_NAME: &str = "Compact Games Auto-Compression";
+const SERVICE_DESCRIPTION: &str =
+    "Compresses newly installed and updated games in the background while the Compact Games app is closed.";
+const SERVICE_TYPE: ServiceType = ServiceType::OWN_PROCESS;
+/// Argument the service manager passes back to the binary.
+pub const SERVICE_ARGUMENT: &str = "service";
+/// Restart delays after the first, second and later failures.
+const RESTART_DELAYS: [Duration; 3] = [
+    Duration::from_secs(10),
+    Duration::from_secs(60),
+    Duration::from_secs(300),
+];
+/// Failure count resets after a day without failures.
+const FAILURE_RESET_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Account to run the service under instead of LocalSystem.
+pub struct ServiceAccount {
+    /// `.\user` or `DOMAIN\user`.
+    pub name: String,
+    pub password: String,
+}
+
+define_windows_service!(ffi_service_main, service_main);
+
+/// Hand the calling thread to the service control manager. Only works
+/// when the process was started by it.
+pub fn run_dispatcher() -> Result<(), DaemonError> {
+    service_dispatcher::start(SERVICE_NAME, ffi_service_main)?;
+    Ok(())
+}
+
+/// Register the current executable as an auto-start service that the
+/// service manager restarts when it crashes or exits with an error.
+pub fn install(account: Option<ServiceAccount>) -> Result<(), DaemonError> {
+    let manager = ServiceManager::local_computer(
+        None::<&str>,
+        ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+    )?;
+    let executable_path = std::env::current_exe().map_err(windows_service::Error::Winapi)?;
+    let (account_name, account_password) = match account {
+        Some(account) => (
+            Some(OsString::from(account.name)),
+            Some(OsString::from(account.password)),
+        ),
+        None => (None, None),
+    };
+    let info = ServiceInfo {
+        name: OsString::from(SERVICE_NAME),
+        display_name: OsString::from(SERVICE_DISPLAY_NAME),
+        service_type: SERVICE_TYPE,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path,
+        launch_arguments: vec![OsString::from(SERVICE_ARGUMENT)],
+        dependencies: Vec::new(),
+        account_name,
+        account_password,
+    };
+    let service = manager.create_service(&info, ServiceAccess::CHANGE_CONFIG)?;
+    service.set_description(SERVICE_DESCRIPTION)?;
+    service.update_failure_actions(ServiceFailureActions {
+        reset_period: ServiceFailureResetPeriod::After(FAILURE_RESET_PERIOD),
+        reboot_msg: None,
+        command: None,
+        actions: Some(
+            RESTART_DELAYS
+                .iter()
+                .map(|&delay| ServiceAction {
+                    action_type: ServiceActionType::Restart,
+                    delay,
+                })
+                .collect(),
+        ),
+    })?;
+    // Also restart after a clean exit with a non-zero code, which is how
+    // `service_main` reports an automation failure.
+    service.set_failure_actions_on_non_crash_failures(true)?;
+    Ok(())
+}
+
+/// Stop the service if it is running and mark it for deletion.
+pub fn uninstall() -> Result<(), DaemonError> {
+    let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(
+        SERVICE_NAME,
+        ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+    )?;
+    service.delete()?;
+    if service.query_status()?.current_state != ServiceState::Stopped {
+        service.stop()?;
+    }
+    Ok(())
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    crate::logging::init();
+    super::event_log::install(SERVICE_NAME);
+    if let Err(e) = run_service() {
+        log::error!("Service failed: {e}");
+    }
+}
+
+fn run_service() -> Result<(), DaemonError> {
+    let (stop_tx, stop_rx) = channel();
+    let status_handle =
+        service_control_handler::register(SERVICE_NAME, move |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                let _ = stop_tx.send(());
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        })?;
+    let report = |state: ServiceState, controls: ServiceControlAccept, exit_code: u32| {
+        status_handle.set_service_status(ServiceStatus {
+            service_type: SERVICE_TYPE,
+            current_state: state,
+            controls_accepted: controls,
+            exit_code: ServiceExitCode::Win32(exit_code),
+            checkpoint: 0,
+            wait_hint: Duration::default(),
+            process_id: None,
+        })
+    };
+
+    report(
+        ServiceState::Running,
+        ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        0,
+    )?;
+    let result = super::run(&stop_rx, true);
+    // Any non-zero exit code counts as a failure for the recovery actions.
+    let exit_code = if result.is_ok() { 0 } else { 1 };
+    report(
+        ServiceState::Stopped,
+        ServiceControlAccept::empty(),
+        exit_code,
+    )?;
+    result
+}
@@ -0,0 +1,243 @@
+//! Launch at login, for machines without the service installed.
+//!
+//! Installing the service needs an administrator, so most users have the
+//! app or the daemon start when they sign in instead. The entry normally
+//! goes in the user's `Run` key, which needs no rights. One that must run
+//! with highest privileges becomes a Task Scheduler logon task instead,
+//! which only an elevated caller can register. Registering one way removes
+//! the other, so the program never starts twice.
+
+use std::io;
+use std::path::Path;
+
+/// Task Scheduler path of the elevated entry.
+pub const TASK_NAME: &str = r"Compact Games\Launch at login";
+/// Value name of the entry under the `Run` key.
+const RUN_VALUE_NAME: &str = "Compact Games";
+const DAEMON_EXE_NAME: &str = "pressplay-daemon.exe";
+
+/// What starts at login.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LaunchTarget {
+    /// The executable calling [`register`], normally the app.
+    #[default]
+    App,
+    /// `pressplay-daemon run`, from the same folder as the caller.
+    Daemon,
+}
+
+/// Where the login entry is registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registration {
+    RunKey,
+    /// A Task Scheduler logon task running with highest privileges.
+    ScheduledTask,
+}
+
+/// Start `target` at login, replacing any earlier registration.
+/// `run_elevated` needs the caller to be elevated.
+pub fn register(target: LaunchTarget, run_elevated: bool) -> io::Result<()> {
+    let command = command_line(target)?;
+    if run_elevated {
+        platform::create_task(&command)?;
+        platform::delete_run_value()?;
+    } else {
+        platform::set_run_value(&command)?;
+        if platform::task_exists() {
+            platform::delete_task()?;
+        }
+    }
+    log::info!(
+        "Registered launch at login{}: {command}",
+        if run_elevated {
+            " with highest privileges"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+/// Stop launching at login. Succeeds when nothing was registered.
+pub fn unregister() -> io::Result<()> {
+    platform::delete_run_value()?;
+    if platform::task_exists() {
+        platform::delete_task()?;
+    }
+    log::info!("Removed launch at login");
+    Ok(())
+}
+
+/// The current login entry, if any.
+pub fn registration() -> Option<Registration> {
+    if platform::task_exists() {
+        Some(Registration::ScheduledTask)
+    } else if platform::run_value_exists() {
+        Some(Registration::RunKey)
+    } else {
+        None
+    }
+}
+
+fn command_line(target: LaunchTarget) -> io::Result<String> {
+    let exe = std::env::current_exe()?;
+    match target {
+        LaunchTarget::App => Ok(quote_command(&exe, &[])),
+        LaunchTarget::Daemon => {
+            let daemon = exe.with_file_name(DAEMON_EXE_NAME);
+            if !daemon.is_file() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} not found", daemon.display()),
+                ));
+            }
+            Ok(quote_command(&daemon, &["run"]))
+        }
+    }
+}
+
+/// `"<exe>" args…`; the path is quoted since install folders often hold
+/// spaces.
+fn quote_command(exe: &Path, args: &[&str]) -> String {
+    let mut command = format!("\"{}\"", exe.display());
+    for arg in args {
+        command.push(' ');
+        command.push_str(arg);
+    }
+    command
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::io;
+    use std::os::windows::process::CommandExt;
+    use std::process::{Command, Output};
+
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_SET_VALUE};
+    use winreg::RegKey;
+
+    use super::{RUN_VALUE_NAME, TASK_NAME};
+
+    const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+    /// Keeps `schtasks` from flashing a console over the app.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    pub(super) fn set_run_value(command: &str) -> io::Result<()> {
+        let (key, _) = RegKey::predef(HKEY_CURRENT_USER).create_subkey(RUN_KEY)?;
+        key.set_value(RUN_VALUE_NAME, &command)
+    }
+
+    pub(super) fn delete_run_value() -> io::Result<()> {
+        let key = match RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey_with_flags(RUN_KEY, KEY_SET_VALUE)
+        {
+            Ok(key) => key,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        match key.delete_value(RUN_VALUE_NAME) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub(super) fn run_value_exists() -> bool {
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(RUN_KEY)
+            .and_then(|key| key.get_value::<String, _>(RUN_VALUE_NAME))
+            .is_ok()
+    }
+
+    pub(super) fn create_task(command: &str) -> io::Result<()> {
+        schtasks(&[
+            "/Create", "/TN", TASK_NAME, "/TR", command, "/SC", "ONLOGON", "/RL", "HIGHEST", "/F",
+        ])
+        .map(drop)
+    }
+
+    pub(super) fn delete_task() -> io::Result<()> {
+        schtasks(&["/Delete", "/TN", TASK_NAME, "/F"]).map(drop)
+    }
+
+    pub(super) fn task_exists() -> bool {
+        schtasks(&["/Query", "/TN", TASK_NAME]).is_ok()
+    }
+
+    /// Run `schtasks`, turning a failure into an error carrying its message,
+    /// e.g. "Access is denied." when an unelevated caller creates the task.
+    fn schtasks(args: &[&str]) -> io::Result<Output> {
+        let output = Command::new("schtasks")
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()?;
+        if output.status.success() {
+            return Ok(output);
+        }
+        let message = String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .trim_start_matches("ERROR:")
+            .trim()
+            .to_owned();
+        Err(io::Error::other(if message.is_empty() {
+            format!("schtasks exited with {}", output.status)
+        } else {
+            message
+        }))
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use std::io;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "launch at login requires Windows",
+        )
+    }
+
+    pub(super) fn set_run_value(_command: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub(super) fn delete_run_value() -> io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn run_value_exists() -> bool {
+        false
+    }
+
+    pub(super) fn create_task(_command: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub(super) fn delete_task() -> io::Result<()> {
+        Ok(())
+    }
+
+    pub(super) fn task_exists() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_quotes_the_executable_only() {
+        assert_eq!(
+            quote_command(
+                Path::new(r"C:\Program Files\Compact Games\pressplay-daemon.exe"),
+                &["run"]
+            ),
+            r#""C:\Program Files\Compact Games\pressplay-daemon.exe" run"#
+        );
+        assert_eq!(
+            quote_command(Path::new(r"C:\Apps\compact_games.exe"), &[]),
+            r#""C:\Apps\compact_games.exe""#
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! Heartbeat file the daemon rewrites while it runs; the app reads it to
+//! show whether background automation is active.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+const STATUS_FILE_NAME: &str = "daemon_status.json";
+/// A heartbeat older than this means the daemon is gone, even if it did
+/// not get to mark itself stopped.
+pub const STALE_AFTER_MS: u64 = 30_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    /// Running as a Windows service rather than from a console.
+    pub is_service: bool,
+    pub started_at_ms: u64,
+    pub updated_at_ms: u64,
+    /// Cleared on a clean shutdown.
+    pub running: bool,
+    pub automation_running: bool,
+    /// `Debug` name of the scheduler state.
+    pub scheduler_state: String,
+    pub queue_depth: u32,
+    pub watched_path_count: u32,
+    pub last_error: Option<String>,
+}
+
+impl DaemonStatus {
+    pub fn is_alive(&self, now_ms: u64) -> bool {
+        self.running && now_ms.saturating_sub(self.updated_at_ms) < STALE_AFTER_MS
+    }
+}
+
+pub fn write(status: &DaemonStatus) -> std::io::Result<()> {
+    let json = serde_json::to_vec_pretty(status).map_err(std::io::Error::other)?;
+    crate::utils::atomic_write(&status_path()?, &json)
+}
+
+/// The last heartbeat, or `None` if no daemon has ever run.
+pub fn read() -> Option<DaemonStatus> {
+    let contents = std::fs::read(status_path().ok()?).ok()?;
+    serde_json::from_slice(&contents).ok()
+}
+
+fn status_path() -> std::io::Result<PathBuf> {
+    Ok(crate::paths::data_dir()?.join(STATUS_FILE_NAME))
+}
@@ -0,0 +1,176 @@
+//! Diagnostics bundle for support requests.
+//!
+//! One zip file holding the rotated log files, the pending automation
+//! journal, the scheduler snapshot, discovery cache stats, a savings
+//! summary and basic system info. A section that cannot be collected is
+//! written as `{"error": ...}` so the rest of the bundle still ships.
+
+use std::io::{self, Cursor, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use sysinfo::{Disks, System};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::automation::journal::JournalWriter;
+
+/// One file inside the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleEntry {
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+impl BundleEntry {
+    fn json(name: &str, section: Result<Value, String>) -> Self {
+        let value = section.unwrap_or_else(|message| json!({ "error": message }));
+        Self {
+            name: name.to_string(),
+            contents: serde_json::to_vec_pretty(&value).unwrap_or_default(),
+        }
+    }
+}
+
+/// Collect every section and write the bundle to `path`.
+///
+/// `scheduler` is the automation service's view of its queue, which only
+/// the API layer can see.
+pub fn export_bundle(path: &Path, scheduler: Value) -> io::Result<()> {
+    let mut entries = vec![
+        BundleEntry::json("system.json", Ok(system_info())),
+        BundleEntry::json("automation_journal.json", journal_section()),
+        BundleEntry::json("scheduler.json", Ok(scheduler)),
+        BundleEntry::json(
+            "discovery_cache.json",
+            to_value(&crate::discovery::cache::stats()),
+        ),
+        BundleEntry::json(
+            "history_summary.json",
+            to_value(&crate::compression::history::analytics::total_savings_summary()),
+        ),
+    ];
+    entries.extend(log_entries());
+    write_bundle(path, &entries)?;
+    log::info!(
+        "Exported diagnostics bundle ({} files) to {}",
+        entries.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Zip `entries` into `path`, replacing any existing file.
+pub fn write_bundle(path: &Path, entries: &[BundleEntry]) -> io::Result<()> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for entry in entries {
+        zip.start_file(entry.name.as_str(), options)
+            .map_err(io::Error::other)?;
+        zip.write_all(&entry.contents)?;
+    }
+    let bytes = zip.finish().map_err(io::Error::other)?.into_inner();
+    crate::utils::atomic_write(path, &bytes)
+}
+
+fn to_value(section: &impl Serialize) -> Result<Value, String> {
+    serde_json::to_value(section).map_err(|e| e.to_string())
+}
+
+fn journal_section() -> Result<Value, String> {
+    let entries = JournalWriter::read_default().map_err(|e| e.to_string())?;
+    to_value(&entries)
+}
+
+/// Log files oldest first, so the bundle reads in order.
+fn log_entries() -> Vec<BundleEntry> {
+    log::logger().flush();
+    let mut entries = Vec::new();
+    for path in crate::logging::log_files().into_iter().rev() {
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        match std::fs::read(&path) {
+            Ok(contents) => entries.push(BundleEntry {
+                name: format!("logs/{}", file_name.to_string_lossy()),
+                contents,
+            }),
+            Err(e) => log::warn!("Skipping log file {} in bundle: {e}", path.display()),
+        }
+    }
+    entries
+}
+
+fn system_info() -> Value {
+    let mut system = System::new();
+    system.refresh_memory();
+    let disks: Vec<Value> = Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|disk| {
+            json!({
+                "name": disk.name().to_string_lossy(),
+                "mount_point": disk.mount_point().to_string_lossy(),
+                "file_system": disk.file_system().to_string_lossy(),
+                "kind": format!("{:?}", disk.kind()),
+                "removable": disk.is_removable(),
+                "total_bytes": disk.total_space(),
+                "available_bytes": disk.available_space(),
+            })
+        })
+        .collect();
+    json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "created_at_ms": crate::utils::unix_now_ms(),
+        "os": System::long_os_version(),
+        "os_version": System::os_version(),
+        "kernel_version": System::kernel_version(),
+        "cpu_count": num_cpus::get(),
+        "total_memory_bytes": system.total_memory(),
+        "disks": disks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn bundle_round_trips_through_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("diagnostics.zip");
+        let entries = vec![
+            BundleEntry::json("system.json", Ok(json!({ "cpu_count": 8 }))),
+            BundleEntry::json("automation_journal.json", Err("no config dir".into())),
+            BundleEntry {
+                name: "logs/compact_games.jsonl".into(),
+                contents: b"{\"message\":\"hello\"}\n".to_vec(),
+            },
+        ];
+
+        write_bundle(&path, &entries).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<_> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "automation_journal.json",
+                "logs/compact_games.jsonl",
+                "system.json"
+            ]
+        );
+        let mut journal = String::new();
+        archive
+            .by_name("automation_journal.json")
+            .unwrap()
+            .read_to_string(&mut journal)
+            .unwrap();
+        let journal: Value = serde_json::from_str(&journal).unwrap();
+        assert_eq!(journal["error"], "no config dir");
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{LazyLock, Mutex, RwLock};
 use std::time::UNIX_EPOCH;
 
 use walkdir::WalkDir;
 
+use crate::utils::Durability;
+
 const CACHE_FILE_NAME: &str = "discovery_stats_cache.json";
 const CACHE_SCHEMA_VERSION: u32 = 2;
 const MAX_CACHE_ENTRIES: usize = 8_192;
@@ -30,6 +32,20 @@ pub struct ChangeToken {
     pub probe_max_mtime_ms: Option<u64>,
 }
 
+impl ChangeToken {
+    /// Short stable digest of the token, for keys that outlive the process.
+    pub fn digest(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        Sha256::digest(&bytes)
+            .iter()
+            .take(8)
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CachedGameStats {
     pub logical_size: u64,
@@ -37,6 +53,10 @@ pub struct CachedGameStats {
     pub is_compressed: bool,
     pub is_directstorage: bool,
     pub updated_at_ms: u64,
+    #[serde(default)]
+    pub has_cloud_placeholders: bool,
+    #[serde(default)]
+    pub has_encrypted_or_sparse_files: bool,
 }
 
 impl CachedGameStats {
@@ -52,8 +72,20 @@ impl CachedGameStats {
             is_compressed,
             is_directstorage,
             updated_at_ms: unix_now_ms(),
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
         }
     }
+
+    pub fn with_cloud_placeholders(mut self, has_cloud_placeholders: bool) -> Self {
+        self.has_cloud_placeholders = has_cloud_placeholders;
+        self
+    }
+
+    pub fn with_encrypted_or_sparse_files(mut self, has_encrypted_or_sparse_files: bool) -> Self {
+        self.has_encrypted_or_sparse_files = has_encrypted_or_sparse_files;
+        self
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -79,6 +111,22 @@ impl Default for CacheFile {
     }
 }
 
+/// Snapshot of the stats cache for diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct CacheStats {
+    /// Entries in memory, including ones not yet persisted.
+    pub entries: usize,
+    /// Lookups served from the cache since the app started.
+    pub hits: u64,
+    pub misses: u64,
+    /// `hits / (hits + misses)`, or 0 before the first lookup.
+    pub hit_rate: f64,
+    /// Size of the persisted JSON file; 0 if it has not been written.
+    pub size_on_disk_bytes: u64,
+    /// When the JSON file was last written.
+    pub last_persist_ms: Option<u64>,
+}
+
 #[derive(Debug, Default)]
 struct PendingUpdates {
     entries: HashMap<String, CacheEntry>,
@@ -86,6 +134,8 @@ struct PendingUpdates {
 
 static CACHE_DIR_CREATED: AtomicBool = AtomicBool::new(false);
 static CACHE_DIRTY: AtomicBool = AtomicBool::new(false);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
 static CACHE: LazyLock<RwLock<CacheFile>> = LazyLock::new(|| RwLock::new(load_cache_file()));
 static PENDING_UPDATES: LazyLock<Mutex<PendingUpdates>> =
     LazyLock::new(|| Mutex::new(PendingUpdates::default()));
@@ -175,6 +225,21 @@ fn lookup_inner(
     path: &Path,
     token: &ChangeToken,
     max_age_ms: Option<u64>,
+) -> Option<CachedGameStats> {
+    let stats = lookup_entry(path, token, max_age_ms);
+    let counter = if stats.is_some() {
+        &CACHE_HITS
+    } else {
+        &CACHE_MISSES
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    stats
+}
+
+fn lookup_entry(
+    path: &Path,
+    token: &ChangeToken,
+    max_age_ms: Option<u64>,
 ) -> Option<CachedGameStats> {
     let key = normalize_path_key(path);
     let now = unix_now_ms();
@@ -231,6 +296,35 @@ pub fn lookup_stale(path: &Path) -> Option<CachedGameStats> {
     })
 }
 
+pub fn stats() -> CacheStats {
+    let pending_only =
+        with_pending_read(|pending| pending.entries.keys().cloned().collect::<Vec<_>>());
+    let entries = with_cache_read(|cache| {
+        cache.entries.len()
+            + pending_only
+                .iter()
+                .filter(|key| !cache.entries.contains_key(*key))
+                .count()
+    });
+    let hits = CACHE_HITS.load(Ordering::Relaxed);
+    let misses = CACHE_MISSES.load(Ordering::Relaxed);
+    let lookups = hits + misses;
+    let file_metadata = cache_path().and_then(fs::metadata).ok();
+
+    CacheStats {
+        entries,
+        hits,
+        misses,
+        hit_rate: if lookups == 0 {
+            0.0
+        } else {
+            hits as f64 / lookups as f64
+        },
+        size_on_disk_bytes: file_metadata.as_ref().map_or(0, |m| m.len()),
+        last_persist_ms: file_metadata.as_ref().and_then(metadata_modified_ms),
+    }
+}
+
 pub fn remove(path: &Path) {
     let key = normalize_path_key(path);
     let removed_pending = with_pending_write(|pending| pending.entries.remove(&key).is_some());
@@ -388,14 +482,12 @@ fn load_cache_file() -> CacheFile {
 fn save_cache_file(cache: &CacheFile) -> Result<(), Box<dyn std::error::Error>> {
     let path = cache_path()?;
     let json = serde_json::to_string(cache)?;
-    crate::utils::atomic_write(&path, json.as_bytes())?;
+    crate::utils::atomic_write_with(&path, json.as_bytes(), Durability::Buffered)?;
     Ok(())
 }
 
 fn cache_path() -> Result<PathBuf, std::io::Error> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-    let compact_games_dir = config_dir.join("compact_games");
+    let compact_games_dir = crate::paths::data_dir()?;
 
     if !CACHE_DIR_CREATED.load(Ordering::Relaxed) {
         fs::create_dir_all(&compact_games_dir)?;
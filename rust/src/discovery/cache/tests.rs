@@ -24,6 +24,8 @@ fn history_entry(path: &Path, timestamp_ms: u64) -> CompressionHistoryEntry {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 100,
+        extensions: Vec::new(),
+        throughput: None,
     }
 }
 
@@ -63,6 +65,27 @@ fn upsert_is_visible_before_persist_via_pending_map() {
     assert!(hit.is_some());
 }
 
+#[test]
+fn stats_count_hits_misses_and_pending_entries() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let token = compute_change_token(dir.path(), false);
+    let before = stats();
+
+    assert!(lookup(dir.path(), &token).is_none());
+    upsert(
+        dir.path(),
+        token.clone(),
+        CachedGameStats::from_parts(10, 10, false, false),
+    );
+    assert!(lookup(dir.path(), &token).is_some());
+
+    let after = stats();
+    assert!(after.hits > before.hits);
+    assert!(after.misses > before.misses);
+    assert!(after.entries >= 1);
+    assert!(after.hit_rate > 0.0 && after.hit_rate < 1.0);
+}
+
 #[test]
 fn cache_file_default_uses_current_schema() {
     assert_eq!(CacheFile::default().schema_version, CACHE_SCHEMA_VERSION);
@@ -86,6 +109,8 @@ fn lookup_with_ttl_rejects_expired_entries() {
         is_compressed: false,
         is_directstorage: false,
         updated_at_ms: 1_000, // ancient timestamp
+        has_cloud_placeholders: false,
+        has_encrypted_or_sparse_files: false,
     };
     upsert(dir.path(), token.clone(), old_stats);
 
@@ -126,6 +151,8 @@ fn lookup_rejects_entries_older_than_compression_history() {
             is_compressed: false,
             is_directstorage: false,
             updated_at_ms: 1_000,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
         },
     );
 
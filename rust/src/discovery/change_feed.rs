@@ -445,9 +445,7 @@ fn save_change_feed_json(json: &str) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 fn change_feed_path() -> Result<PathBuf, std::io::Error> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-    let compact_games_dir = config_dir.join("compact_games");
+    let compact_games_dir = crate::paths::data_dir()?;
 
     if !CHANGE_FEED_DIR_CREATED.load(Ordering::Relaxed) {
         fs::create_dir_all(&compact_games_dir)?;
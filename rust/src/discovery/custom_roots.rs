@@ -0,0 +1,217 @@
+//! User-added library roots, persisted across sessions.
+//!
+//! Roots are scanned by every full or quick discovery pass after the
+//! launcher scanners, and are added to the automation watcher's paths.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+
+use crate::discovery::cache::normalize_path_key;
+
+const CUSTOM_ROOTS_FILE_NAME: &str = "custom_scan_roots.json";
+const MAX_CUSTOM_ROOTS: usize = 64;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct CustomRootsFile {
+    #[serde(default)]
+    roots: Vec<PathBuf>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CustomRootError {
+    #[error("not a directory: {0}")]
+    NotADirectory(PathBuf),
+    #[error("at most {MAX_CUSTOM_ROOTS} custom roots are supported")]
+    TooManyRoots,
+    #[error("failed to save custom roots: {0}")]
+    Persist(String),
+}
+
+#[cfg(not(test))]
+static CUSTOM_ROOTS_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+static CUSTOM_ROOTS: LazyLock<RwLock<CustomRootsFile>> =
+    LazyLock::new(|| RwLock::new(load_custom_roots_file()));
+
+/// Add `path` as a library root. Returns `false` if it was already present.
+pub fn add(path: &Path) -> Result<bool, CustomRootError> {
+    if !path.is_dir() {
+        return Err(CustomRootError::NotADirectory(path.to_path_buf()));
+    }
+    let key = normalize_path_key(path);
+    let snapshot = with_custom_roots_write(|file| {
+        if file
+            .roots
+            .iter()
+            .any(|root| normalize_path_key(root) == key)
+        {
+            return Ok(None);
+        }
+        if file.roots.len() >= MAX_CUSTOM_ROOTS {
+            return Err(CustomRootError::TooManyRoots);
+        }
+        file.roots.push(path.to_path_buf());
+        Ok(Some(file.clone()))
+    })?;
+
+    let Some(snapshot) = snapshot else {
+        return Ok(false);
+    };
+    save_custom_roots_file(&snapshot).map_err(|e| CustomRootError::Persist(e.to_string()))?;
+    log::info!("Added custom library root {}", path.display());
+    Ok(true)
+}
+
+/// Remove `path` from the library roots. Returns `false` if it was absent.
+pub fn remove(path: &Path) -> Result<bool, CustomRootError> {
+    let key = normalize_path_key(path);
+    let snapshot = with_custom_roots_write(|file| {
+        let before = file.roots.len();
+        file.roots.retain(|root| normalize_path_key(root) != key);
+        (file.roots.len() != before).then(|| file.clone())
+    });
+
+    let Some(snapshot) = snapshot else {
+        return Ok(false);
+    };
+    save_custom_roots_file(&snapshot).map_err(|e| CustomRootError::Persist(e.to_string()))?;
+    log::info!("Removed custom library root {}", path.display());
+    Ok(true)
+}
+
+/// All persisted roots, in the order they were added.
+pub fn list() -> Vec<PathBuf> {
+    with_custom_roots_read(|file| file.roots.clone())
+}
+
+/// `paths` followed by every persisted root not already covered by them.
+pub fn with_custom_roots(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen: HashSet<String> = paths.iter().map(|p| normalize_path_key(p)).collect();
+    for root in list() {
+        if seen.insert(normalize_path_key(&root)) {
+            paths.push(root);
+        }
+    }
+    paths
+}
+
+fn load_custom_roots_file() -> CustomRootsFile {
+    let Ok(path) = custom_roots_path() else {
+        return CustomRootsFile::default();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return CustomRootsFile::default();
+    };
+
+    serde_json::from_str::<CustomRootsFile>(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse custom library roots: {e}");
+        CustomRootsFile::default()
+    })
+}
+
+fn save_custom_roots_file(file: &CustomRootsFile) -> Result<(), Box<dyn std::error::Error>> {
+    let path = custom_roots_path()?;
+    let json = serde_json::to_string(file)?;
+    crate::utils::atomic_write(&path, json.as_bytes())?;
+    Ok(())
+}
+
+fn custom_roots_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-custom-roots-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(CUSTOM_ROOTS_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !CUSTOM_ROOTS_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            CUSTOM_ROOTS_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(CUSTOM_ROOTS_FILE_NAME))
+    }
+}
+
+fn with_custom_roots_read<R>(f: impl FnOnce(&CustomRootsFile) -> R) -> R {
+    match CUSTOM_ROOTS.read() {
+        Ok(guard) => f(&guard),
+        Err(poisoned) => {
+            log::warn!("Custom roots lock poisoned (read); recovering");
+            let guard = poisoned.into_inner();
+            f(&guard)
+        }
+    }
+}
+
+fn with_custom_roots_write<R>(f: impl FnOnce(&mut CustomRootsFile) -> R) -> R {
+    match CUSTOM_ROOTS.write() {
+        Ok(mut guard) => f(&mut guard),
+        Err(poisoned) => {
+            log::warn!("Custom roots lock poisoned (write); recovering");
+            let mut guard = poisoned.into_inner();
+            f(&mut guard)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::test_sync::lock_discovery_test;
+
+    #[test]
+    fn roots_are_deduplicated_persisted_and_removed() {
+        let _guard = lock_discovery_test();
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("Library");
+        fs::create_dir_all(&root).unwrap();
+
+        assert!(add(&root).unwrap());
+        assert!(!add(&root).unwrap());
+        assert!(list().contains(&root));
+        assert_eq!(
+            load_custom_roots_file()
+                .roots
+                .iter()
+                .filter(|r| **r == root)
+                .count(),
+            1
+        );
+
+        let merged = with_custom_roots(vec![root.clone()]);
+        assert_eq!(merged.iter().filter(|r| **r == root).count(), 1);
+
+        assert!(remove(&root).unwrap());
+        assert!(!remove(&root).unwrap());
+        assert!(!list().contains(&root));
+    }
+
+    #[test]
+    fn missing_directory_is_rejected() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(matches!(
+            add(&temp.path().join("missing")),
+            Err(CustomRootError::NotADirectory(_))
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+//! Per-volume rollup of discovered games, space and compression savings.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use sysinfo::Disks;
+
+use super::platform::GameInfo;
+use super::storage::{storage_class_for_path, volume_key_for_path, StorageClass};
+use crate::compression::history::CompressionHistoryEntry;
+
+/// Savings ratio assumed for uncompressed games before any history exists.
+/// Matches the heuristic estimator's "moderately compressible" bucket.
+const DEFAULT_PROJECTED_SAVINGS_RATIO: f64 = 0.18;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveSummary {
+    /// Volume root, e.g. `c:\`.
+    pub volume: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub storage_class: StorageClass,
+    pub game_count: u32,
+    pub compressed_game_count: u32,
+    /// On-disk size of games that are already compressed.
+    pub compressed_bytes: u64,
+    /// Size of games that are not compressed yet.
+    pub uncompressed_bytes: u64,
+    /// Space already reclaimed by compression.
+    pub bytes_saved: u64,
+    /// Expected additional savings from compressing the eligible
+    /// uncompressed games on this volume.
+    pub projected_savings_bytes: u64,
+}
+
+/// Capacity of one mounted volume.
+#[derive(Debug, Clone)]
+pub struct VolumeSpace {
+    pub volume: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub storage_class: StorageClass,
+}
+
+/// Summaries for every mounted volume plus any volume holding `games`.
+pub fn drive_summaries(games: &[GameInfo]) -> Vec<DriveSummary> {
+    let history = crate::compression::history::get_historical_stats();
+    summarize(games, mounted_volumes(), projected_savings_ratio(&history))
+}
+
+/// Savings ratio expected from compressing a game nothing is known about:
+/// the ratio past compressions achieved, or a conservative default.
+pub(crate) fn projected_savings_ratio(history: &[CompressionHistoryEntry]) -> f64 {
+    historical_savings_ratio(history).unwrap_or(DEFAULT_PROJECTED_SAVINGS_RATIO)
+}
+
+pub(crate) fn mounted_volumes() -> Vec<VolumeSpace> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .map(|disk| {
+            let mount_point = disk.mount_point();
+            VolumeSpace {
+                volume: volume_key_for_path(mount_point),
+                total_bytes: disk.total_space(),
+                free_bytes: disk.available_space(),
+                storage_class: storage_class_for_path(mount_point),
+            }
+        })
+        .collect()
+}
+
+/// Byte-weighted savings ratio achieved across past compressions.
+fn historical_savings_ratio(history: &[CompressionHistoryEntry]) -> Option<f64> {
+    let (saved, original) = history
+        .iter()
+        .filter(|entry| entry.actual_stats.original_bytes > 0)
+        .fold((0u64, 0u64), |(saved, original), entry| {
+            (
+                saved.saturating_add(entry.actual_stats.actual_saved_bytes),
+                original.saturating_add(entry.actual_stats.original_bytes),
+            )
+        });
+    (original > 0).then(|| saved as f64 / original as f64)
+}
+
+fn summarize(
+    games: &[GameInfo],
+    volumes: Vec<VolumeSpace>,
+    projected_ratio: f64,
+) -> Vec<DriveSummary> {
+    let mut summaries: BTreeMap<String, DriveSummary> = BTreeMap::new();
+    for space in volumes {
+        summaries
+            .entry(space.volume.clone())
+            .or_insert_with(|| DriveSummary {
+                volume: space.volume,
+                total_bytes: space.total_bytes,
+                free_bytes: space.free_bytes,
+                storage_class: space.storage_class,
+                ..empty_summary(String::new())
+            });
+    }
+
+    for game in games {
+        let volume = volume_key_for_path(&game.path);
+        let summary = summaries
+            .entry(volume.clone())
+            .or_insert_with(|| DriveSummary {
+                storage_class: storage_class_for_path(Path::new(&volume)),
+                ..empty_summary(volume)
+            });
+
+        summary.game_count = summary.game_count.saturating_add(1);
+        if game.is_compressed {
+            summary.compressed_game_count = summary.compressed_game_count.saturating_add(1);
+            summary.compressed_bytes = summary
+                .compressed_bytes
+                .saturating_add(game.compressed_size.unwrap_or(game.size_bytes));
+            summary.bytes_saved = summary.bytes_saved.saturating_add(game.bytes_saved());
+        } else {
+            summary.uncompressed_bytes = summary.uncompressed_bytes.saturating_add(game.size_bytes);
+            if is_projection_eligible(game) {
+                let projected = (game.size_bytes as f64 * projected_ratio) as u64;
+                summary.projected_savings_bytes =
+                    summary.projected_savings_bytes.saturating_add(projected);
+            }
+        }
+    }
+
+    summaries.into_values().collect()
+}
+
+/// Whether compressing `game` could save anything.
+pub(crate) fn is_projection_eligible(game: &GameInfo) -> bool {
+    !game.excluded
+        && !game.is_directstorage
+        && !game.is_unsupported
+        && !game.is_protected_package
+        && !game.is_unsupported_filesystem
+}
+
+fn empty_summary(volume: String) -> DriveSummary {
+    DriveSummary {
+        volume,
+        total_bytes: 0,
+        free_bytes: 0,
+        storage_class: StorageClass::Unknown,
+        game_count: 0,
+        compressed_game_count: 0,
+        compressed_bytes: 0,
+        uncompressed_bytes: 0,
+        bytes_saved: 0,
+        projected_savings_bytes: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::storage::DriveType;
+    use std::path::PathBuf;
+
+    use crate::discovery::platform::Platform;
+
+    fn game(path: &str, size_bytes: u64, compressed_size: Option<u64>) -> GameInfo {
+        GameInfo {
+            name: "Game".into(),
+            path: PathBuf::from(path),
+            platform: Platform::Steam,
+            size_bytes,
+            compressed_size,
+            is_compressed: compressed_size.is_some(),
+            is_directstorage: false,
+            is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
+            excluded: false,
+            steam_app_id: None,
+            last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
+        }
+    }
+
+    #[test]
+    fn games_are_rolled_up_per_volume() {
+        let mut directstorage = game(r"D:\Games\Forspoken", 1_000, None);
+        directstorage.is_directstorage = true;
+        let games = vec![
+            game(r"D:\Games\Alpha", 1_000, Some(600)),
+            game(r"D:\Games\Beta", 2_000, None),
+            directstorage,
+        ];
+        let volumes = vec![VolumeSpace {
+            volume: volume_key_for_path(Path::new(r"D:\")),
+            total_bytes: 10_000,
+            free_bytes: 4_000,
+            storage_class: StorageClass::Ssd,
+        }];
+
+        let summaries = summarize(&games, volumes, 0.25);
+        assert_eq!(summaries.len(), 1);
+        let d = &summaries[0];
+        assert_eq!(d.total_bytes, 10_000);
+        assert_eq!(d.game_count, 3);
+        assert_eq!(d.compressed_game_count, 1);
+        assert_eq!(d.compressed_bytes, 600);
+        assert_eq!(d.bytes_saved, 400);
+        assert_eq!(d.uncompressed_bytes, 3_000);
+        assert_eq!(d.projected_savings_bytes, 500);
+    }
+
+    #[test]
+    fn historical_ratio_is_byte_weighted() {
+        use crate::compression::algorithm::CompressionAlgorithm;
+        use crate::compression::history::{ActualStats, EstimateSnapshot};
+
+        let entry = |original, saved| CompressionHistoryEntry {
+            game_path: r"C:\Games\X".into(),
+            game_name: "X".into(),
+            timestamp_ms: 0,
+            estimate: EstimateSnapshot {
+                scanned_files: 0,
+                sampled_bytes: 0,
+                estimated_saved_bytes: 0,
+            },
+            actual_stats: ActualStats {
+                original_bytes: original,
+                compressed_bytes: original - saved,
+                actual_saved_bytes: saved,
+                files_processed: 1,
+            },
+            algorithm: CompressionAlgorithm::Xpress8K,
+            duration_ms: 0,
+            extensions: Vec::new(),
+            throughput: None,
+        };
+
+        assert_eq!(historical_savings_ratio(&[]), None);
+        let ratio = historical_savings_ratio(&[entry(1_000, 500), entry(3_000, 500)]).unwrap();
+        assert!((ratio - 0.25).abs() < f64::EPSILON);
+    }
+}
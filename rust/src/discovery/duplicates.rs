@@ -0,0 +1,240 @@
+//! Detection of the same game installed in more than one library.
+//!
+//! Copies are matched by Steam app id first. Games without an app id (or
+//! installed through different launchers) are matched by normalized name,
+//! but only when their logical sizes are close, so a demo or a stripped
+//! server build does not pair with the full game.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use super::platform::GameInfo;
+
+/// Maximum relative difference in logical size for a name-only match.
+const NAME_MATCH_SIZE_TOLERANCE: f64 = 0.10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    /// Copies share a Steam app id.
+    SteamAppId,
+    /// Copies share a normalized name and have similar sizes.
+    NameAndSize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub name: String,
+    pub steam_app_id: Option<u32>,
+    pub reason: DuplicateReason,
+    /// Largest copy first.
+    pub copies: Vec<GameInfo>,
+    /// On-disk bytes freed by keeping only the largest copy.
+    pub reclaimable_bytes: u64,
+}
+
+/// Find every game installed more than once in `games`.
+pub fn find_duplicate_installs(games: &[GameInfo]) -> Vec<DuplicateGroup> {
+    let mut seen_paths = HashSet::new();
+    let games: Vec<&GameInfo> = games
+        .iter()
+        .filter(|game| seen_paths.insert(crate::utils::normalize_path_key(&game.path)))
+        .collect();
+
+    let mut groups = Vec::new();
+    let mut matched_paths = HashSet::new();
+
+    let mut by_app_id: HashMap<u32, Vec<&GameInfo>> = HashMap::new();
+    for game in &games {
+        if let Some(app_id) = game.steam_app_id {
+            by_app_id.entry(app_id).or_default().push(game);
+        }
+    }
+    for (app_id, copies) in by_app_id {
+        if copies.len() < 2 {
+            continue;
+        }
+        matched_paths.extend(copies.iter().map(|game| game.path.clone()));
+        groups.push(build_group(
+            copies,
+            Some(app_id),
+            DuplicateReason::SteamAppId,
+        ));
+    }
+
+    let mut by_name: HashMap<String, Vec<&GameInfo>> = HashMap::new();
+    for game in games
+        .iter()
+        .filter(|game| !matched_paths.contains(&game.path))
+    {
+        let key = name_key(&game.name);
+        if !key.is_empty() {
+            by_name.entry(key).or_default().push(game);
+        }
+    }
+    for copies in by_name.into_values() {
+        for cluster in cluster_by_size(copies) {
+            groups.push(build_group(cluster, None, DuplicateReason::NameAndSize));
+        }
+    }
+
+    groups.sort_by(|a, b| {
+        b.reclaimable_bytes
+            .cmp(&a.reclaimable_bytes)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    groups
+}
+
+/// Lowercased alphanumerics only, so `DOOM Eternal™` and `Doom Eternal`
+/// share a key.
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Split same-name copies into clusters of similar logical size, keeping
+/// only clusters with at least two copies.
+fn cluster_by_size(mut copies: Vec<&GameInfo>) -> Vec<Vec<&GameInfo>> {
+    copies.sort_by_key(|game| Reverse(game.size_bytes));
+    let mut clusters: Vec<Vec<&GameInfo>> = Vec::new();
+    for game in copies {
+        let fits_last = clusters.last().is_some_and(|cluster| {
+            let anchor = cluster[0].size_bytes as f64;
+            anchor - game.size_bytes as f64 <= anchor * NAME_MATCH_SIZE_TOLERANCE
+        });
+        match clusters.last_mut() {
+            Some(cluster) if fits_last => cluster.push(game),
+            _ => clusters.push(vec![game]),
+        }
+    }
+    clusters.retain(|cluster| cluster.len() >= 2);
+    clusters
+}
+
+fn build_group(
+    copies: Vec<&GameInfo>,
+    steam_app_id: Option<u32>,
+    reason: DuplicateReason,
+) -> DuplicateGroup {
+    let mut copies: Vec<GameInfo> = copies.into_iter().cloned().collect();
+    copies.sort_by_key(|game| Reverse(game.size_bytes));
+
+    let on_disk: Vec<u64> = copies.iter().map(on_disk_bytes).collect();
+    let total: u64 = on_disk.iter().sum();
+    let largest = on_disk.iter().copied().max().unwrap_or(0);
+
+    DuplicateGroup {
+        name: copies[0].name.clone(),
+        steam_app_id,
+        reason,
+        copies,
+        reclaimable_bytes: total.saturating_sub(largest),
+    }
+}
+
+fn on_disk_bytes(game: &GameInfo) -> u64 {
+    if game.is_compressed {
+        game.compressed_size.unwrap_or(game.size_bytes)
+    } else {
+        game.size_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::platform::Platform;
+    use crate::discovery::storage::DriveType;
+    use std::path::PathBuf;
+
+    fn game(name: &str, path: &str, size_bytes: u64, steam_app_id: Option<u32>) -> GameInfo {
+        GameInfo {
+            name: name.into(),
+            path: PathBuf::from(path),
+            platform: if steam_app_id.is_some() {
+                Platform::Steam
+            } else {
+                Platform::EpicGames
+            },
+            size_bytes,
+            compressed_size: None,
+            is_compressed: false,
+            is_directstorage: false,
+            is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
+            excluded: false,
+            steam_app_id,
+            last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
+        }
+    }
+
+    #[test]
+    fn steam_copies_are_matched_by_app_id() {
+        let mut compressed = game("Portal 2", r"E:\SteamLibrary\Portal 2", 10_000, Some(620));
+        compressed.is_compressed = true;
+        compressed.compressed_size = Some(6_000);
+        let games = vec![
+            game(
+                "Portal 2",
+                r"C:\Steam\steamapps\common\Portal 2",
+                10_000,
+                Some(620),
+            ),
+            compressed,
+            game(
+                "Half-Life 2",
+                r"C:\Steam\steamapps\common\Half-Life 2",
+                5_000,
+                Some(220),
+            ),
+        ];
+
+        let groups = find_duplicate_installs(&games);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::SteamAppId);
+        assert_eq!(groups[0].steam_app_id, Some(620));
+        assert_eq!(groups[0].copies.len(), 2);
+        assert_eq!(groups[0].reclaimable_bytes, 6_000);
+    }
+
+    #[test]
+    fn name_matches_require_similar_size() {
+        let games = vec![
+            game("DOOM Eternal™", r"C:\Games\DOOMEternal", 100_000, None),
+            game("Doom Eternal", r"D:\Epic\DoomEternal", 95_000, None),
+            game("Doom Eternal", r"D:\Demos\DoomEternal", 20_000, None),
+        ];
+
+        let groups = find_duplicate_installs(&games);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::NameAndSize);
+        let paths: Vec<_> = groups[0].copies.iter().map(|g| g.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from(r"C:\Games\DOOMEternal"),
+                PathBuf::from(r"D:\Epic\DoomEternal")
+            ]
+        );
+    }
+
+    #[test]
+    fn same_path_reported_twice_is_not_a_duplicate() {
+        let games = vec![
+            game("Hades", r"C:\Games\Hades", 1_000, None),
+            game("Hades", r"C:\Games\Hades", 1_000, None),
+        ];
+        assert!(find_duplicate_installs(&games).is_empty());
+    }
+}
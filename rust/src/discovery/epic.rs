@@ -133,7 +133,13 @@ fn parse_epic_manifest_item(content: &str, mode: DiscoveryScanMode) -> Option<Ga
         return None;
     }
 
-    utils::build_game_info_with_mode(name, game_path, Platform::EpicGames, mode)
+    let mut game = utils::build_game_info_with_mode(name, game_path, Platform::EpicGames, mode)?;
+    game.epic_catalog_item_id = json
+        .get("CatalogItemId")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty())
+        .map(str::to_owned);
+    Some(game)
 }
 
 fn is_epic_system_folder(name: &str) -> bool {
@@ -251,6 +257,7 @@ mod tests {
         let manifest = serde_json::json!({
             "DisplayName": "Rocket League",
             "InstallLocation": game_dir.display().to_string(),
+            "CatalogItemId": "9773aa1aa54f4f7b80e44bef04986cea",
         })
         .to_string();
 
@@ -264,5 +271,9 @@ mod tests {
         assert_eq!(games.len(), 1);
         assert_eq!(games[0].name, "Rocket League");
         assert_eq!(games[0].path, game_dir);
+        assert_eq!(
+            games[0].epic_catalog_item_id.as_deref(),
+            Some("9773aa1aa54f4f7b80e44bef04986cea")
+        );
     }
 }
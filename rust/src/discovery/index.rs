@@ -1,5 +1,6 @@
 use crate::discovery::cache::{normalize_path_key, ChangeToken};
-use crate::discovery::platform::GameInfo;
+use crate::discovery::platform::{GameInfo, Platform};
+use crate::utils::Durability;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -7,7 +8,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{LazyLock, RwLock};
 
 const INDEX_FILE_NAME: &str = "discovery_index.json";
-const INDEX_SCHEMA_VERSION: u32 = 2;
+const INDEX_SCHEMA_VERSION: u32 = 3;
 const MAX_INDEX_ENTRIES: usize = 16_384;
 const MAX_INDEX_AGE_MS: u64 = 5 * 60 * 1000; // 5 minutes — safe because incremental scans are cheap
 
@@ -82,6 +83,50 @@ pub fn lookup_recent(path: &Path) -> Option<GameInfo> {
     })
 }
 
+/// Platform of every indexed game, keyed by normalized game path (not the
+/// stats path, so Xbox `Content` folders map back to their game folder).
+///
+/// Ignores token and age: platform never changes for a given install.
+pub fn platforms_by_game_path() -> HashMap<String, Platform> {
+    with_index_read(|index| {
+        index
+            .entries
+            .values()
+            .map(|entry| (normalize_path_key(&entry.game.path), entry.game.platform))
+            .collect()
+    })
+}
+
+/// Platform discovery last recorded for the game at `path`.
+///
+/// Ignores token and age like [`platforms_by_game_path`].
+pub fn platform_of(path: &Path) -> Option<Platform> {
+    let key = normalize_path_key(path);
+    with_index_read(|index| {
+        index
+            .entries
+            .get(&key)
+            .or_else(|| {
+                index
+                    .entries
+                    .values()
+                    .find(|entry| normalize_path_key(&entry.game.path) == key)
+            })
+            .map(|entry| entry.game.platform)
+    })
+}
+
+/// Install folder of every indexed game.
+pub fn game_paths() -> Vec<PathBuf> {
+    with_index_read(|index| {
+        index
+            .entries
+            .values()
+            .map(|entry| entry.game.path.clone())
+            .collect()
+    })
+}
+
 pub fn upsert(path: &Path, token: ChangeToken, game: &GameInfo) {
     let key = normalize_path_key(path);
     with_index_write(|index| {
@@ -194,14 +239,12 @@ fn load_index_file() -> IndexFile {
 fn save_index_file(index: &IndexFile) -> Result<(), Box<dyn std::error::Error>> {
     let path = index_path()?;
     let json = serde_json::to_string(index)?;
-    fs::write(path, json)?;
+    crate::utils::atomic_write_with(&path, json.as_bytes(), Durability::Buffered)?;
     Ok(())
 }
 
 fn index_path() -> Result<PathBuf, std::io::Error> {
-    let config_dir = dirs::config_dir()
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-    let compact_games_dir = config_dir.join("compact_games");
+    let compact_games_dir = crate::paths::data_dir()?;
 
     if !INDEX_DIR_CREATED.load(Ordering::Relaxed) {
         fs::create_dir_all(&compact_games_dir)?;
@@ -245,6 +288,7 @@ mod tests {
     };
     use crate::discovery::cache::{compute_change_token, normalize_path_key};
     use crate::discovery::platform::Platform;
+    use crate::discovery::storage::DriveType;
     use std::path::Path;
 
     fn history_entry(path: &Path, timestamp_ms: u64) -> CompressionHistoryEntry {
@@ -265,6 +309,8 @@ mod tests {
             },
             algorithm: CompressionAlgorithm::Xpress8K,
             duration_ms: 100,
+            extensions: Vec::new(),
+            throughput: None,
         }
     }
 
@@ -281,9 +327,19 @@ mod tests {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
 
         upsert(dir.path(), token.clone(), &game);
@@ -318,9 +374,19 @@ mod tests {
                         is_compressed: false,
                         is_directstorage: false,
                         is_unsupported: false,
+                        is_protected_package: false,
+                        is_unsupported_filesystem: false,
+                        uses_kernel_anticheat: false,
+                        drive_type: DriveType::Unknown,
+                        has_cloud_placeholders: false,
+                        has_encrypted_or_sparse_files: false,
                         excluded: false,
                         steam_app_id: None,
                         last_played: None,
+                        last_compressed: None,
+                        playtime_minutes: None,
+                        epic_catalog_item_id: None,
+                        artwork_path: None,
                     },
                     updated_at_ms: 1_000,
                 },
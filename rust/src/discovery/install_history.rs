@@ -171,9 +171,7 @@ fn history_path() -> Result<PathBuf, std::io::Error> {
 
     #[cfg(not(test))]
     {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-        let compact_games_dir = config_dir.join("compact_games");
+        let compact_games_dir = crate::paths::data_dir()?;
 
         if !HISTORY_DIR_CREATED.load(Ordering::Relaxed) {
             fs::create_dir_all(&compact_games_dir)?;
@@ -0,0 +1,156 @@
+//! Launcher-aware install progress.
+//!
+//! Filesystem events alone cannot say when a download has finished: Steam
+//! stages updates under `steamapps/downloading` and may go quiet for minutes
+//! between chunks. The probe asks the launcher where it can and falls back
+//! to partial-download markers in the game folder.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::steam;
+
+/// How often the monitor re-probes settling games. Each probe reads the
+/// library's app manifests, so this is kept well above the loop interval.
+pub const INSTALL_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// File extensions browsers and download tools use for unfinished files.
+const PARTIAL_DOWNLOAD_EXTENSIONS: &[&str] = &["crdownload", "partial", "part"];
+
+/// Whether a launcher is still writing a game's files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallState {
+    /// Downloading, patching or staging; compressing now would be undone.
+    InProgress,
+    /// An update is queued or paused but not yet writing; compressing now
+    /// would be undone once it runs.
+    UpdatePending,
+    /// The launcher reports the install fully applied.
+    Complete,
+    /// No launcher signal either way.
+    Unknown,
+}
+
+impl InstallState {
+    /// Whether the launcher still has writes to make to the game.
+    pub fn awaits_writes(self) -> bool {
+        matches!(self, Self::InProgress | Self::UpdatePending)
+    }
+}
+
+/// Probe the install state of the game at `game_path`.
+pub fn probe_install_state(game_path: &Path) -> InstallState {
+    if let Some(state) = steam::steam_install_state(game_path) {
+        return state;
+    }
+    if has_partial_download_markers(game_path) {
+        InstallState::InProgress
+    } else {
+        InstallState::Unknown
+    }
+}
+
+/// Unfinished download files directly inside the game folder.
+fn has_partial_download_markers(game_path: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(game_path) else {
+        return false;
+    };
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        entry
+            .path()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                PARTIAL_DOWNLOAD_EXTENSIONS
+                    .iter()
+                    .any(|marker| ext.eq_ignore_ascii_case(marker))
+            })
+    })
+}
+
+/// Keeps the install state of settling games current across polls.
+pub struct InstallProgressMonitor {
+    states: HashMap<PathBuf, InstallState>,
+    last_poll: Option<Instant>,
+}
+
+impl InstallProgressMonitor {
+    pub fn new() -> Self {
+        Self {
+            states: HashMap::new(),
+            last_poll: None,
+        }
+    }
+
+    /// Re-probe `game_paths` at most every [`INSTALL_POLL_INTERVAL`].
+    /// Returns the new states when they differ from the previous poll.
+    pub fn poll(&mut self, game_paths: &[PathBuf]) -> Option<&HashMap<PathBuf, InstallState>> {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < INSTALL_POLL_INTERVAL)
+        {
+            return None;
+        }
+        self.last_poll = Some(Instant::now());
+
+        let states: HashMap<PathBuf, InstallState> = game_paths
+            .iter()
+            .map(|path| (path.clone(), probe_install_state(path)))
+            .collect();
+        if states == self.states {
+            return None;
+        }
+        for (path, state) in &states {
+            if self
+                .states
+                .get(path)
+                .is_some_and(|previous| previous.awaits_writes())
+                && !state.awaits_writes()
+            {
+                log::info!("Install finished: {}", path.display());
+            }
+        }
+        self.states = states;
+        Some(&self.states)
+    }
+}
+
+impl Default for InstallProgressMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_download_marker_means_in_progress() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game = temp.path().join("Game");
+        std::fs::create_dir_all(&game).unwrap();
+        std::fs::write(game.join("game.exe"), b"x").unwrap();
+        assert_eq!(probe_install_state(&game), InstallState::Unknown);
+
+        std::fs::write(game.join("data.pak.crdownload"), b"x").unwrap();
+        assert_eq!(probe_install_state(&game), InstallState::InProgress);
+    }
+
+    #[test]
+    fn monitor_reports_only_changes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game = temp.path().join("Game");
+        std::fs::create_dir_all(&game).unwrap();
+        let mut monitor = InstallProgressMonitor::new();
+
+        let states = monitor.poll(std::slice::from_ref(&game)).cloned();
+        assert_eq!(
+            states.and_then(|s| s.get(&game).copied()),
+            Some(InstallState::Unknown)
+        );
+        monitor.last_poll = None;
+        assert!(monitor.poll(std::slice::from_ref(&game)).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! Epic titles installed through Legendary or Heroic Games Launcher.
+//!
+//! Neither tool writes the official launcher's `.item` manifests, so these
+//! installs are invisible to `EpicScanner`. Both keep Legendary's
+//! `installed.json`, keyed by Epic app name.
+
+use std::path::{Path, PathBuf};
+
+use super::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
+use super::scan_error::ScanError;
+use super::utils;
+
+const INSTALLED_FILE_NAME: &str = "installed.json";
+
+#[derive(Default)]
+pub struct LegendaryScanner {}
+
+impl PlatformScanner for LegendaryScanner {
+    fn scan(&self, mode: DiscoveryScanMode) -> Result<Vec<GameInfo>, ScanError> {
+        let mut games = Vec::new();
+
+        for config_dir in legendary_config_dirs() {
+            let installed_path = config_dir.join(INSTALLED_FILE_NAME);
+            let Ok(content) = std::fs::read_to_string(&installed_path) else {
+                continue;
+            };
+            log::debug!("Legendary: reading {}", installed_path.display());
+            utils::merge_games(&mut games, build_legendary_games(&content, mode));
+        }
+
+        log::info!("Legendary/Heroic: found {} games", games.len());
+        Ok(games)
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "Legendary"
+    }
+}
+
+/// Candidate Legendary config directories, most specific first.
+///
+/// - `LEGENDARY_CONFIG_PATH` overrides everything for standalone Legendary.
+/// - Standalone Legendary defaults to `~/.config/legendary` on Windows too.
+/// - Heroic bundles its own Legendary under `%APPDATA%\heroic\legendaryConfig`.
+fn legendary_config_dirs() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some(path) = std::env::var_os("LEGENDARY_CONFIG_PATH") {
+        candidates.push(PathBuf::from(path));
+    }
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".config").join("legendary"));
+    }
+    if let Some(config) = dirs::config_dir() {
+        candidates.push(
+            config
+                .join("heroic")
+                .join("legendaryConfig")
+                .join("legendary"),
+        );
+    }
+
+    candidates.dedup();
+    candidates
+}
+
+fn build_legendary_games(content: &str, mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    parse_installed_json(content)
+        .into_iter()
+        .filter(|(_, path)| path.is_dir())
+        .filter_map(|(title, path)| {
+            utils::build_game_info_with_mode(title, path, Platform::EpicGames, mode)
+        })
+        .collect()
+}
+
+/// Parse Legendary's `installed.json` into `(title, install_path)` pairs.
+///
+/// DLC entries share their base game's install path and are skipped.
+fn parse_installed_json(content: &str) -> Vec<(String, PathBuf)> {
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(e) => {
+            log::debug!("Failed to parse Legendary installed.json: {e}");
+            return Vec::new();
+        }
+    };
+
+    let Some(installed) = json.as_object() else {
+        return Vec::new();
+    };
+
+    installed
+        .iter()
+        .filter_map(|(app_name, entry)| {
+            if entry.get("is_dlc").and_then(|v| v.as_bool()) == Some(true) {
+                return None;
+            }
+            let install_path = entry.get("install_path").and_then(|v| v.as_str())?;
+            let title = entry
+                .get("title")
+                .and_then(|v| v.as_str())
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or(app_name);
+            Some((title.to_owned(), normalize_install_path(install_path)))
+        })
+        .collect()
+}
+
+/// Legendary stores paths with forward slashes on Windows.
+fn normalize_install_path(raw: &str) -> PathBuf {
+    Path::new(raw).components().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::test_sync::lock_discovery_test;
+
+    #[test]
+    fn legendary_scanner_returns_ok() {
+        let _guard = lock_discovery_test();
+        let scanner = LegendaryScanner {};
+        let result = scanner.scan(DiscoveryScanMode::Full);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn installed_json_skips_dlc_and_uses_title() {
+        let json = r#"{
+            "Sugar": {
+                "app_name": "Sugar",
+                "title": "Rocket League",
+                "install_path": "D:/Games/Heroic/rocketleague",
+                "is_dlc": false
+            },
+            "SugarDlc": {
+                "app_name": "SugarDlc",
+                "title": "Rocket League DLC",
+                "install_path": "D:/Games/Heroic/rocketleague",
+                "is_dlc": true
+            }
+        }"#;
+        let entries = parse_installed_json(json);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, "Rocket League");
+    }
+
+    #[test]
+    fn installed_json_falls_back_to_app_name() {
+        let json = r#"{"Fortnite": {"install_path": "C:/Games/Fortnite"}}"#;
+        let entries = parse_installed_json(json);
+        assert_eq!(entries[0].0, "Fortnite");
+    }
+
+    #[test]
+    fn installed_json_invalid_returns_empty() {
+        assert!(parse_installed_json("not json").is_empty());
+        assert!(parse_installed_json("[]").is_empty());
+    }
+
+    #[test]
+    fn installed_game_is_reported_as_epic() {
+        let _guard = lock_discovery_test();
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path().join("Hades");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::File::create(game_dir.join("Hades.exe"))
+            .unwrap()
+            .set_len(4 * 1024 * 1024)
+            .unwrap();
+        std::fs::File::create(game_dir.join("Content.pkg"))
+            .unwrap()
+            .set_len(700 * 1024 * 1024)
+            .unwrap();
+
+        let json = serde_json::json!({
+            "Min": {
+                "title": "Hades",
+                "install_path": game_dir.display().to_string(),
+                "is_dlc": false
+            }
+        })
+        .to_string();
+
+        let games = build_legendary_games(&json, DiscoveryScanMode::Full);
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Hades");
+        assert_eq!(games[0].platform, Platform::EpicGames);
+    }
+}
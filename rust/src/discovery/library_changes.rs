@@ -0,0 +1,175 @@
+//! Incremental library updates driven by filesystem watcher events.
+//!
+//! Instead of a full rescan, a single install/uninstall/modify event
+//! refreshes just that folder's discovery cache entries and yields the
+//! delta the library view needs to apply.
+
+use std::path::{Path, PathBuf};
+
+use super::platform::{DiscoveryScanMode, GameInfo, Platform};
+use super::utils;
+
+/// A single change to the discovered library.
+#[derive(Debug, Clone)]
+pub enum LibraryChange {
+    /// A new game folder passed discovery checks.
+    Added(GameInfo),
+    /// An existing game's metadata (size, compression state) changed.
+    Updated(GameInfo),
+    /// A game folder is gone or no longer looks like a game.
+    Removed { path: PathBuf },
+}
+
+impl LibraryChange {
+    pub fn path(&self) -> &Path {
+        match self {
+            LibraryChange::Added(game) | LibraryChange::Updated(game) => &game.path,
+            LibraryChange::Removed { path } => path,
+        }
+    }
+}
+
+/// Refresh discovery state for a newly installed folder.
+pub fn game_installed(path: &Path, game_name: Option<&str>) -> Option<LibraryChange> {
+    rebuild(path, game_name).map(LibraryChange::Added)
+}
+
+/// Refresh discovery state after files inside a game folder changed.
+///
+/// Returns `Removed` when the folder no longer qualifies as a game (e.g. an
+/// update left only a stub behind).
+pub fn game_modified(path: &Path, game_name: Option<&str>) -> Option<LibraryChange> {
+    if !path.is_dir() {
+        return Some(game_uninstalled(path));
+    }
+    Some(match rebuild(path, game_name) {
+        Some(game) => LibraryChange::Updated(game),
+        None => LibraryChange::Removed {
+            path: path.to_path_buf(),
+        },
+    })
+}
+
+/// Drop all discovery state for a removed folder.
+pub fn game_uninstalled(path: &Path) -> LibraryChange {
+    utils::evict_discovery_entry(path);
+    persist();
+    LibraryChange::Removed {
+        path: path.to_path_buf(),
+    }
+}
+
+fn rebuild(path: &Path, game_name: Option<&str>) -> Option<GameInfo> {
+    let name = game_name
+        .map(str::to_owned)
+        .or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()))?;
+    let platform = infer_platform(path);
+
+    let stats_path = if platform == Platform::XboxGamePass && path.join("Content").is_dir() {
+        path.join("Content")
+    } else {
+        path.to_path_buf()
+    };
+    // Stale stats would otherwise be served from the cache until the
+    // change token catches up with the write burst.
+    utils::evict_discovery_entry(&stats_path);
+    let mut game = utils::build_game_info_with_mode_and_stats_path(
+        name,
+        path.to_path_buf(),
+        stats_path,
+        platform,
+        DiscoveryScanMode::Full,
+    );
+    if let Some(game) = game.as_mut() {
+        if game.platform == Platform::Steam {
+            game.steam_app_id = super::steam::lookup_steam_app_id_for_path(&game.path);
+        }
+    }
+    persist();
+    game
+}
+
+/// Best-effort platform for a folder seen only through the watcher, based
+/// on the launcher's conventional library layout.
+fn infer_platform(path: &Path) -> Platform {
+    let parent_names: Vec<String> = path
+        .ancestors()
+        .skip(1)
+        .filter_map(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .collect();
+    let has_parent = |name: &str| parent_names.iter().any(|n| n == name);
+
+    if parent_names.first().is_some_and(|n| n == "common") && has_parent("steamapps") {
+        Platform::Steam
+    } else if has_parent("epic games") {
+        Platform::EpicGames
+    } else if has_parent("gog galaxy") || has_parent("gog games") {
+        Platform::GogGalaxy
+    } else if has_parent("xboxgames") || has_parent("windowsapps") {
+        Platform::XboxGamePass
+    } else if has_parent("ea games") {
+        Platform::EaApp
+    } else if has_parent("riot games") {
+        Platform::RiotGames
+    } else {
+        Platform::Custom
+    }
+}
+
+fn persist() {
+    super::cache::persist_if_dirty();
+    super::index::persist_if_dirty();
+    super::change_feed::persist_if_dirty();
+    super::install_history::persist_if_dirty();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::test_sync::lock_discovery_test;
+
+    #[test]
+    fn infer_platform_from_library_layout() {
+        assert_eq!(
+            infer_platform(Path::new(r"D:\SteamLibrary\steamapps\common\Portal 2")),
+            Platform::Steam
+        );
+        assert_eq!(
+            infer_platform(Path::new(r"C:\Program Files\Epic Games\Fortnite")),
+            Platform::EpicGames
+        );
+        assert_eq!(
+            infer_platform(Path::new(r"C:\XboxGames\Halo Infinite")),
+            Platform::XboxGamePass
+        );
+        assert_eq!(
+            infer_platform(Path::new(r"E:\Games\SomeGame")),
+            Platform::Custom
+        );
+    }
+
+    #[test]
+    fn installed_then_uninstalled_yields_add_and_remove() {
+        let _guard = lock_discovery_test();
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path().join("WatchedGame");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::File::create(game_dir.join("game.exe"))
+            .unwrap()
+            .set_len(4 * 1024 * 1024)
+            .unwrap();
+        std::fs::File::create(game_dir.join("content.bin"))
+            .unwrap()
+            .set_len(700 * 1024 * 1024)
+            .unwrap();
+
+        let added = game_installed(&game_dir, None).expect("game should be discovered");
+        assert!(matches!(&added, LibraryChange::Added(game) if game.name == "WatchedGame"));
+
+        std::fs::remove_dir_all(&game_dir).unwrap();
+        let removed = game_modified(&game_dir, None).expect("missing folder is a removal");
+        assert!(matches!(removed, LibraryChange::Removed { .. }));
+        assert_eq!(removed.path(), game_dir.as_path());
+    }
+}
@@ -0,0 +1,362 @@
+//! Export of the discovered library to JSON or CSV, and import of
+//! exclusion lists edited outside the app.
+//!
+//! Both formats share one row shape, so an exported file can be edited
+//! (flip the `excluded` column) and fed straight back to
+//! [`parse_exclusions`].
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::platform::GameInfo;
+
+const CSV_HEADER: [&str; 11] = [
+    "name",
+    "platform",
+    "path",
+    "size_bytes",
+    "compressed_size_bytes",
+    "is_compressed",
+    "bytes_saved",
+    "savings_percent",
+    "excluded",
+    "steam_app_id",
+    "last_played_ms",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryExportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LibraryFileError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("CSV has no `path` column")]
+    MissingPathColumn,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportRow {
+    name: String,
+    platform: String,
+    path: String,
+    size_bytes: u64,
+    compressed_size_bytes: Option<u64>,
+    is_compressed: bool,
+    bytes_saved: u64,
+    savings_percent: f64,
+    excluded: bool,
+    steam_app_id: Option<u32>,
+    last_played_ms: Option<u64>,
+}
+
+impl ExportRow {
+    fn new(game: &GameInfo, excluded_keys: &HashSet<String>) -> Self {
+        let bytes_saved = game.bytes_saved();
+        let savings_percent = if game.size_bytes > 0 {
+            (bytes_saved as f64 / game.size_bytes as f64 * 1000.0).round() / 10.0
+        } else {
+            0.0
+        };
+        Self {
+            name: game.name.clone(),
+            platform: game.platform.to_string(),
+            path: game.path.to_string_lossy().into_owned(),
+            size_bytes: game.size_bytes,
+            compressed_size_bytes: game.compressed_size,
+            is_compressed: game.is_compressed,
+            bytes_saved,
+            savings_percent,
+            excluded: game.excluded
+                || excluded_keys.contains(&crate::utils::normalize_path_key(&game.path)),
+            steam_app_id: game.steam_app_id,
+            last_played_ms: game.last_played.and_then(|t| {
+                t.duration_since(std::time::UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_millis() as u64)
+            }),
+        }
+    }
+
+    fn csv_fields(&self) -> [String; 11] {
+        let opt = |v: Option<u64>| v.map(|v| v.to_string()).unwrap_or_default();
+        [
+            self.name.clone(),
+            self.platform.clone(),
+            self.path.clone(),
+            self.size_bytes.to_string(),
+            opt(self.compressed_size_bytes),
+            self.is_compressed.to_string(),
+            self.bytes_saved.to_string(),
+            format!("{:.1}", self.savings_percent),
+            self.excluded.to_string(),
+            opt(self.steam_app_id.map(u64::from)),
+            opt(self.last_played_ms),
+        ]
+    }
+}
+
+/// Write `games` to `path`, marking anything in `excluded_paths` (the
+/// user's exclusion list) as excluded.
+pub fn export_library(
+    games: &[GameInfo],
+    excluded_paths: &[String],
+    format: LibraryExportFormat,
+    path: &Path,
+) -> Result<(), LibraryFileError> {
+    let contents = render_library(games, excluded_paths, format)?;
+    crate::utils::atomic_write(path, contents.as_bytes())?;
+    log::info!(
+        "Exported {} games as {:?} to {}",
+        games.len(),
+        format,
+        path.display()
+    );
+    Ok(())
+}
+
+fn render_library(
+    games: &[GameInfo],
+    excluded_paths: &[String],
+    format: LibraryExportFormat,
+) -> Result<String, LibraryFileError> {
+    let excluded_keys: HashSet<String> = excluded_paths
+        .iter()
+        .map(|p| crate::utils::normalize_path_key(Path::new(p)))
+        .collect();
+    let rows: Vec<ExportRow> = games
+        .iter()
+        .map(|game| ExportRow::new(game, &excluded_keys))
+        .collect();
+
+    match format {
+        LibraryExportFormat::Json => Ok(serde_json::to_string_pretty(&rows)?),
+        LibraryExportFormat::Csv => {
+            let mut out = CSV_HEADER.join(",");
+            out.push_str("\r\n");
+            for row in &rows {
+                let fields = row.csv_fields();
+                let line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+                out.push_str(&line.join(","));
+                out.push_str("\r\n");
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// Read an exclusion list from `path`.
+pub fn import_exclusions(path: &Path) -> Result<Vec<String>, LibraryFileError> {
+    let contents = std::fs::read_to_string(path)?;
+    parse_exclusions(&contents)
+}
+
+/// Paths to exclude from a JSON or CSV file.
+///
+/// Accepted shapes:
+/// - a JSON array of path strings;
+/// - a JSON array of objects with `path` and optional `excluded` (as
+///   written by [`export_library`]); rows default to excluded;
+/// - CSV with a `path` column and optional `excluded` column.
+pub fn parse_exclusions(contents: &str) -> Result<Vec<String>, LibraryFileError> {
+    let trimmed = contents.trim_start_matches('\u{feff}').trim();
+    let mut paths = if trimmed.starts_with('[') {
+        parse_json_exclusions(trimmed)?
+    } else {
+        parse_csv_exclusions(trimmed)?
+    };
+
+    let mut seen = HashSet::new();
+    paths.retain(|p| !p.is_empty() && seen.insert(crate::utils::normalize_path_key(Path::new(p))));
+    Ok(paths)
+}
+
+fn parse_json_exclusions(contents: &str) -> Result<Vec<String>, LibraryFileError> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(contents)?;
+    Ok(values
+        .iter()
+        .filter_map(|value| match value {
+            serde_json::Value::String(path) => Some(path.trim().to_owned()),
+            serde_json::Value::Object(row) => {
+                let excluded = row.get("excluded").is_none_or(is_truthy_json);
+                let path = row.get("path")?.as_str()?;
+                excluded.then(|| path.trim().to_owned())
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+fn is_truthy_json(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::String(s) => is_truthy(s),
+        serde_json::Value::Number(n) => n.as_u64().is_some_and(|n| n != 0),
+        _ => false,
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "y" | "1" | "x"
+    )
+}
+
+fn parse_csv_exclusions(contents: &str) -> Result<Vec<String>, LibraryFileError> {
+    let mut records = parse_csv(contents).into_iter();
+    let Some(header) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let path_col = column("path").ok_or(LibraryFileError::MissingPathColumn)?;
+    let excluded_col = column("excluded");
+
+    Ok(records
+        .filter(|record| match excluded_col {
+            Some(col) => record.get(col).is_some_and(|v| is_truthy(v)),
+            None => true,
+        })
+        .filter_map(|record| record.get(path_col).map(|p| p.trim().to_owned()))
+        .collect())
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Minimal RFC 4180 reader: quoted fields, doubled quotes, CRLF or LF.
+fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::platform::Platform;
+    use crate::discovery::storage::DriveType;
+    use std::path::PathBuf;
+
+    fn game(name: &str, path: &str) -> GameInfo {
+        GameInfo {
+            name: name.into(),
+            path: PathBuf::from(path),
+            platform: Platform::Steam,
+            size_bytes: 1_000,
+            compressed_size: Some(750),
+            is_compressed: true,
+            is_directstorage: false,
+            is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
+            excluded: false,
+            steam_app_id: Some(620),
+            last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
+        }
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_and_round_trips_exclusions() {
+        let games = vec![
+            game("Portal 2", r"C:\Games\Portal 2"),
+            game("Game, \"The\"", r"D:\Games\Odd"),
+        ];
+        let csv =
+            render_library(&games, &[r"D:\Games\Odd".into()], LibraryExportFormat::Csv).unwrap();
+
+        assert!(csv.starts_with("name,platform,path,"));
+        assert!(csv.contains("\"Game, \"\"The\"\"\""));
+        assert!(csv.contains(",25.0,"));
+        assert_eq!(parse_exclusions(&csv).unwrap(), vec![r"D:\Games\Odd"]);
+    }
+
+    #[test]
+    fn json_export_round_trips_exclusions() {
+        let mut riot = game("VALORANT", r"C:\Riot Games\VALORANT");
+        riot.excluded = true;
+        let games = vec![game("Portal 2", r"C:\Games\Portal 2"), riot];
+        let json = render_library(&games, &[], LibraryExportFormat::Json).unwrap();
+        assert_eq!(
+            parse_exclusions(&json).unwrap(),
+            vec![r"C:\Riot Games\VALORANT"]
+        );
+    }
+
+    #[test]
+    fn plain_lists_are_accepted() {
+        assert_eq!(
+            parse_exclusions(r#"["C:\\Games\\A", "C:\\Games\\B", "C:\\Games\\A"]"#).unwrap(),
+            vec![r"C:\Games\A", r"C:\Games\B"]
+        );
+        assert_eq!(
+            parse_exclusions("path\nC:\\Games\\A\nC:\\Games\\B\n").unwrap(),
+            vec![r"C:\Games\A", r"C:\Games\B"]
+        );
+    }
+
+    #[test]
+    fn csv_without_path_column_is_rejected() {
+        assert!(matches!(
+            parse_exclusions("name,excluded\nPortal 2,true\n"),
+            Err(LibraryFileError::MissingPathColumn)
+        ));
+    }
+}
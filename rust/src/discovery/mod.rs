@@ -2,18 +2,29 @@ pub mod battlenet;
 pub mod cache;
 pub mod change_feed;
 pub mod custom;
+pub mod custom_roots;
+pub mod drive_summary;
+pub mod duplicates;
 pub mod ea;
 pub mod epic;
 pub mod gog;
 pub mod hidden_paths;
 pub mod index;
 pub mod install_history;
+pub mod install_state;
+pub mod legendary;
+pub mod library_changes;
+pub mod library_export;
 pub mod platform;
+pub mod progress;
+pub mod riot;
 pub mod scan_error;
+pub mod scanner_registry;
 pub mod steam;
 pub mod storage;
 #[cfg(test)]
 pub(crate) mod test_sync;
+pub mod tools;
 pub mod ubisoft;
 pub mod utils;
 pub mod xbox;
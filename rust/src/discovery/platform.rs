@@ -3,6 +3,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use super::storage::DriveType;
+
 /// Supported game distribution platforms.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +18,7 @@ pub enum Platform {
     XboxGamePass,
     Custom,
     Application,
+    RiotGames,
 }
 
 /// Discovery scan strategy.
@@ -41,6 +44,7 @@ impl std::fmt::Display for Platform {
             Self::XboxGamePass => write!(f, "Xbox Game Pass"),
             Self::Custom => write!(f, "Custom"),
             Self::Application => write!(f, "Application"),
+            Self::RiotGames => write!(f, "Riot Games"),
         }
     }
 }
@@ -104,6 +108,28 @@ pub struct GameInfo {
     pub is_directstorage: bool,
     #[serde(default)]
     pub is_unsupported: bool,
+    /// Signed UWP package (Xbox app / Store install); never compressed.
+    #[serde(default)]
+    pub is_protected_package: bool,
+    /// Volume filesystem cannot hold WOF-compressed files (ReFS, FAT32,
+    /// exFAT); see [`crate::safety::filesystem`].
+    #[serde(default)]
+    pub is_unsupported_filesystem: bool,
+    /// Ships kernel anti-cheat (EAC, BattlEye, Vanguard); see
+    /// [`crate::safety::anticheat`].
+    #[serde(default)]
+    pub uses_kernel_anticheat: bool,
+    /// Fixed, removable or network volume; see [`DriveType`].
+    #[serde(default)]
+    pub drive_type: DriveType,
+    /// Folder holds online-only cloud files (OneDrive and similar); those
+    /// are skipped during compression so they are not downloaded.
+    #[serde(default)]
+    pub has_cloud_placeholders: bool,
+    /// Folder holds many EFS-encrypted or sparse files. Compression skips
+    /// them, so savings fall short of the estimate.
+    #[serde(default)]
+    pub has_encrypted_or_sparse_files: bool,
     #[serde(default)]
     pub excluded: bool,
     #[serde(default)]
@@ -114,6 +140,24 @@ pub struct GameInfo {
         deserialize_with = "deserialize_systemtime_millis"
     )]
     pub last_played: Option<SystemTime>,
+    /// Completion time of the newest compression recorded in history.
+    #[serde(
+        default,
+        serialize_with = "serialize_systemtime_millis",
+        deserialize_with = "deserialize_systemtime_millis"
+    )]
+    pub last_compressed: Option<SystemTime>,
+    /// Total playtime reported by the launcher, in minutes.
+    #[serde(default)]
+    pub playtime_minutes: Option<u64>,
+    /// Epic Games Store catalog item id from the launcher manifest.
+    #[serde(default)]
+    pub epic_catalog_item_id: Option<String>,
+    /// Cover art the launcher cached locally, such as Steam's
+    /// `librarycache` capsule or header. Preferred over art guessed from
+    /// the install.
+    #[serde(default)]
+    pub artwork_path: Option<PathBuf>,
 }
 
 impl GameInfo {
@@ -162,9 +206,19 @@ mod tests {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
         assert_eq!(game.bytes_saved(), 0);
         assert_eq!(game.savings_display(), "Not compressed");
@@ -181,9 +235,19 @@ mod tests {
             is_compressed: true,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
         assert_eq!(game.bytes_saved(), 4_000);
         assert_eq!(game.savings_display(), "40.0%");
@@ -200,9 +264,19 @@ mod tests {
             is_compressed: true,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: Some(620),
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
         let json = serde_json::to_string(&game).unwrap();
         let parsed: GameInfo = serde_json::from_str(&json).unwrap();
@@ -222,9 +296,19 @@ mod tests {
             is_compressed: true,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
         assert_eq!(game.bytes_saved(), 0);
         assert_eq!(game.savings_display(), "Not compressed");
@@ -242,9 +326,19 @@ mod tests {
             is_compressed: true,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
         // saturating_sub should prevent underflow, returning 0
         assert_eq!(game.bytes_saved(), 0);
@@ -265,9 +359,19 @@ mod tests {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: Some(timestamp),
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
 
         // Serialize to JSON
@@ -293,9 +397,19 @@ mod tests {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         };
 
         let json = serde_json::to_string(&game).unwrap();
@@ -0,0 +1,174 @@
+//! Live progress of platform scans.
+//!
+//! Scans report which scanners are running and how many game folders they
+//! have found and sized, so a long full scan can show real progress instead
+//! of a spinner. Overlapping scans (say a quick refresh during a full scan)
+//! share one set of counters until the last of them finishes.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Where the running scans are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryProgress {
+    /// Scanners currently running, in start order.
+    pub active_scanners: Vec<&'static str>,
+    pub scanners_completed: usize,
+    pub scanners_total: usize,
+    /// Game folders handed to sizing, including cache hits.
+    pub candidates_found: u64,
+    /// Candidates whose size is known or that were rejected.
+    pub candidates_sized: u64,
+    /// Since the first of the running scans started, or the length of the
+    /// last scan once none is running.
+    pub elapsed: Duration,
+    pub scanning: bool,
+}
+
+#[derive(Default)]
+struct ScanState {
+    started: Option<Instant>,
+    last_elapsed: Duration,
+    active_scanners: Vec<&'static str>,
+    scanners_completed: usize,
+    scanners_total: usize,
+}
+
+static ACTIVE_SCANS: AtomicUsize = AtomicUsize::new(0);
+static CANDIDATES_FOUND: AtomicU64 = AtomicU64::new(0);
+static CANDIDATES_SIZED: AtomicU64 = AtomicU64::new(0);
+static STATE: LazyLock<Mutex<ScanState>> = LazyLock::new(|| Mutex::new(ScanState::default()));
+
+/// Ends a scan's share of the progress when dropped.
+#[must_use = "the scan counts as running until the guard drops"]
+pub struct ScanProgressGuard(());
+
+impl Drop for ScanProgressGuard {
+    fn drop(&mut self) {
+        let mut state = lock_state();
+        if ACTIVE_SCANS.fetch_sub(1, Ordering::AcqRel) == 1 {
+            state.last_elapsed = state
+                .started
+                .take()
+                .map(|started| started.elapsed())
+                .unwrap_or_default();
+            state.active_scanners.clear();
+        }
+    }
+}
+
+/// Start tracking a scan that will run `scanner_count` scanners. The first
+/// scan to start resets the counters.
+pub(crate) fn begin_scan(scanner_count: usize) -> ScanProgressGuard {
+    let mut state = lock_state();
+    if ACTIVE_SCANS.fetch_add(1, Ordering::AcqRel) == 0 {
+        *state = ScanState {
+            started: Some(Instant::now()),
+            ..ScanState::default()
+        };
+        CANDIDATES_FOUND.store(0, Ordering::Relaxed);
+        CANDIDATES_SIZED.store(0, Ordering::Relaxed);
+    }
+    state.scanners_total += scanner_count;
+    ScanProgressGuard(())
+}
+
+pub(crate) fn scanner_started(name: &'static str) {
+    if is_scanning() {
+        lock_state().active_scanners.push(name);
+    }
+}
+
+pub(crate) fn scanner_finished(name: &'static str) {
+    if !is_scanning() {
+        return;
+    }
+    let mut state = lock_state();
+    if let Some(index) = state.active_scanners.iter().position(|&n| n == name) {
+        state.active_scanners.remove(index);
+    }
+    state.scanners_completed += 1;
+}
+
+/// Count candidates about to be sized. Ignored outside a scan, e.g. when
+/// the directory watcher rebuilds a single game.
+pub(crate) fn record_candidates_found(count: usize) {
+    if is_scanning() {
+        CANDIDATES_FOUND.fetch_add(count as u64, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_candidate_sized() {
+    if is_scanning() {
+        CANDIDATES_SIZED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The current progress, or the final counts of the last scan.
+pub fn snapshot() -> DiscoveryProgress {
+    let state = lock_state();
+    let scanning = is_scanning();
+    let elapsed = match state.started {
+        Some(started) if scanning => started.elapsed(),
+        _ => state.last_elapsed,
+    };
+    DiscoveryProgress {
+        active_scanners: state.active_scanners.clone(),
+        scanners_completed: state.scanners_completed,
+        scanners_total: state.scanners_total,
+        candidates_found: CANDIDATES_FOUND.load(Ordering::Relaxed),
+        candidates_sized: CANDIDATES_SIZED.load(Ordering::Relaxed),
+        elapsed,
+        scanning,
+    }
+}
+
+fn is_scanning() -> bool {
+    ACTIVE_SCANS.load(Ordering::Acquire) > 0
+}
+
+fn lock_state() -> MutexGuard<'static, ScanState> {
+    STATE.lock().unwrap_or_else(|poisoned| {
+        log::warn!("Discovery progress lock poisoned; recovering");
+        poisoned.into_inner()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_scans_share_counters_until_the_last_ends() {
+        let _guard = crate::discovery::test_sync::lock_discovery_test();
+        let first = begin_scan(2);
+        scanner_started("Steam");
+        record_candidates_found(3);
+        record_candidate_sized();
+
+        let second = begin_scan(1);
+        scanner_started("GOG Galaxy");
+        let progress = snapshot();
+        assert!(progress.scanning);
+        assert_eq!(progress.scanners_total, 3);
+        assert_eq!(progress.active_scanners, vec!["Steam", "GOG Galaxy"]);
+        // Other tests may size candidates while this scan is open.
+        assert!(progress.candidates_found >= 3);
+        assert!(progress.candidates_sized >= 1);
+
+        scanner_finished("Steam");
+        drop(first);
+        assert!(snapshot().scanning);
+
+        scanner_finished("GOG Galaxy");
+        drop(second);
+        let done = snapshot();
+        assert!(!done.scanning);
+        assert!(done.active_scanners.is_empty());
+        assert_eq!(done.scanners_completed, 2);
+
+        record_candidates_found(5);
+        assert_eq!(snapshot().candidates_found, done.candidates_found);
+    }
+}
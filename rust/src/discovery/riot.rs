@@ -0,0 +1,249 @@
+use std::path::{Path, PathBuf};
+
+use super::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
+use super::scan_error::ScanError;
+use super::utils;
+
+/// A Riot Client product.
+struct RiotProduct {
+    /// Product id used in `Metadata\<id>.<patchline>` folder names.
+    id: &'static str,
+    /// Install folder name created by the Riot Client installer.
+    folder: &'static str,
+    display_name: &'static str,
+    /// Protected by the Riot Vanguard kernel anti-cheat. Vanguard verifies
+    /// game files at launch, so these titles are excluded from compression
+    /// by default.
+    vanguard: bool,
+}
+
+const KNOWN_RIOT_PRODUCTS: &[RiotProduct] = &[
+    RiotProduct {
+        id: "league_of_legends",
+        folder: "League of Legends",
+        display_name: "League of Legends",
+        vanguard: true,
+    },
+    RiotProduct {
+        id: "valorant",
+        folder: "VALORANT",
+        display_name: "VALORANT",
+        vanguard: true,
+    },
+    RiotProduct {
+        id: "bacon",
+        folder: "LoR",
+        display_name: "Legends of Runeterra",
+        vanguard: false,
+    },
+];
+
+const PRODUCT_SETTINGS_INSTALL_KEY: &str = "product_install_full_path";
+
+#[derive(Default)]
+pub struct RiotScanner {}
+
+impl PlatformScanner for RiotScanner {
+    fn scan(&self, mode: DiscoveryScanMode) -> Result<Vec<GameInfo>, ScanError> {
+        let Some(riot_data) = riot_program_data_dir() else {
+            log::info!("Riot Games: PROGRAMDATA not set");
+            return Ok(Vec::new());
+        };
+
+        let mut games = scan_product_settings(&riot_data.join("Metadata"), mode);
+
+        let installs_path = riot_data.join("RiotClientInstalls.json");
+        if let Ok(content) = std::fs::read_to_string(&installs_path) {
+            let candidates = parse_client_installs(&content);
+            utils::merge_games(&mut games, build_riot_games(candidates, mode));
+        }
+
+        log::info!("Riot Games: found {} games", games.len());
+        Ok(games)
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "Riot Games"
+    }
+}
+
+fn riot_program_data_dir() -> Option<PathBuf> {
+    let program_data = std::env::var("PROGRAMDATA").ok()?;
+    Some(PathBuf::from(program_data).join("Riot Games"))
+}
+
+/// Read `<id>.<patchline>.product_settings.yaml` files under the Riot
+/// Client metadata folder.
+fn scan_product_settings(metadata_dir: &Path, mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let Ok(entries) = std::fs::read_dir(metadata_dir) else {
+        return Vec::new();
+    };
+
+    let candidates: Vec<(&'static RiotProduct, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let folder_name = e.file_name().to_string_lossy().into_owned();
+            let product = product_for_metadata_folder(&folder_name)?;
+            let settings_path = e
+                .path()
+                .join(format!("{folder_name}.product_settings.yaml"));
+            let content = std::fs::read_to_string(&settings_path).ok()?;
+            let install_path = parse_product_settings_install_path(&content)?;
+            Some((product, PathBuf::from(install_path)))
+        })
+        .collect();
+
+    build_riot_games(candidates, mode)
+}
+
+/// Map a metadata folder such as `valorant.live` to its product.
+fn product_for_metadata_folder(folder_name: &str) -> Option<&'static RiotProduct> {
+    let product_id = folder_name.split('.').next()?;
+    KNOWN_RIOT_PRODUCTS
+        .iter()
+        .find(|p| p.id.eq_ignore_ascii_case(product_id))
+}
+
+/// Extract `product_install_full_path` from a product settings YAML file.
+///
+/// The file is flat `key: "value"` YAML, so a line scan is enough.
+fn parse_product_settings_install_path(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once(':')?;
+        if key.trim() != PRODUCT_SETTINGS_INSTALL_KEY {
+            return None;
+        }
+        let value = value.trim().trim_matches(['"', '\'']);
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_owned())
+        }
+    })
+}
+
+/// Parse `RiotClientInstalls.json`, whose `associated_client` object is
+/// keyed by game install directory.
+fn parse_client_installs(content: &str) -> Vec<(&'static RiotProduct, PathBuf)> {
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(e) => {
+            log::debug!("Failed to parse RiotClientInstalls.json: {e}");
+            return Vec::new();
+        }
+    };
+
+    let Some(associated) = json.get("associated_client").and_then(|v| v.as_object()) else {
+        return Vec::new();
+    };
+
+    associated
+        .keys()
+        .filter_map(|install_dir| {
+            let path = PathBuf::from(install_dir.trim_end_matches(['/', '\\']));
+            let folder_name = path.file_name()?.to_string_lossy().into_owned();
+            let product = KNOWN_RIOT_PRODUCTS
+                .iter()
+                .find(|p| p.folder.eq_ignore_ascii_case(&folder_name))?;
+            Some((product, path))
+        })
+        .collect()
+}
+
+fn build_riot_games(
+    candidates: Vec<(&'static RiotProduct, PathBuf)>,
+    mode: DiscoveryScanMode,
+) -> Vec<GameInfo> {
+    candidates
+        .into_iter()
+        .filter(|(_, path)| path.is_dir())
+        .filter_map(|(product, path)| {
+            let mut game = utils::build_game_info_with_mode(
+                product.display_name.to_owned(),
+                path,
+                Platform::RiotGames,
+                mode,
+            )?;
+            if product.vanguard {
+                log::debug!(
+                    "Riot Games: excluding Vanguard-protected title {} by default",
+                    product.display_name
+                );
+                game.excluded = true;
+            }
+            Some(game)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn riot_scanner_returns_ok() {
+        let scanner = RiotScanner {};
+        let result = scanner.scan(DiscoveryScanMode::Full);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn product_settings_install_path_is_unquoted() {
+        let yaml = "product_install_full_path: \"C:/Riot Games/VALORANT/live\"\n\
+                    product_install_root: \"C:/Riot Games\"\n";
+        assert_eq!(
+            parse_product_settings_install_path(yaml).as_deref(),
+            Some("C:/Riot Games/VALORANT/live")
+        );
+    }
+
+    #[test]
+    fn product_settings_without_install_path() {
+        assert_eq!(
+            parse_product_settings_install_path("product_install_root: \"C:/Riot Games\""),
+            None
+        );
+    }
+
+    #[test]
+    fn metadata_folder_maps_to_product() {
+        assert_eq!(
+            product_for_metadata_folder("bacon.live").map(|p| p.display_name),
+            Some("Legends of Runeterra")
+        );
+        assert_eq!(
+            product_for_metadata_folder("Riot Client.live").map(|p| p.id),
+            None
+        );
+    }
+
+    #[test]
+    fn client_installs_skip_unknown_folders() {
+        let json = r#"{
+            "associated_client": {
+                "C:/Riot Games/League of Legends/": "C:/Riot Games/Riot Client/RiotClientServices.exe",
+                "C:/Riot Games/VALORANT/live/": "C:/Riot Games/Riot Client/RiotClientServices.exe",
+                "C:/Riot Games/VALORANT/": "C:/Riot Games/Riot Client/RiotClientServices.exe"
+            },
+            "rc_default": "C:/Riot Games/Riot Client/RiotClientServices.exe"
+        }"#;
+        let mut ids: Vec<&str> = parse_client_installs(json)
+            .into_iter()
+            .map(|(product, _)| product.id)
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["league_of_legends", "valorant"]);
+    }
+
+    #[test]
+    fn vanguard_titles_are_flagged() {
+        let vanguard: Vec<&str> = KNOWN_RIOT_PRODUCTS
+            .iter()
+            .filter(|p| p.vanguard)
+            .map(|p| p.id)
+            .collect();
+        assert!(vanguard.contains(&"valorant"));
+        assert!(!vanguard.contains(&"bacon"));
+    }
+}
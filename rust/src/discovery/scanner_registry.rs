@@ -0,0 +1,119 @@
+//! Scanners added by embedders, such as a corporate game-deployment
+//! system, run alongside the built-in launcher scanners.
+//!
+//! Registered scanners take part in [`crate::discovery::utils::scan_all_platforms`]
+//! and its streaming and per-platform variants, and their games are
+//! deduplicated against launcher games like any other. Scanners that build
+//! games through [`crate::discovery::utils::build_game_info_with_mode`] or
+//! [`crate::discovery::utils::scan_game_subdirs`] share the stats cache
+//! and index with the built-in ones.
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+use super::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
+use super::scan_error::ScanError;
+
+/// A scanner shared between the registry and running scans.
+pub type RegisteredScanner = Arc<dyn PlatformScanner + Send + Sync>;
+
+struct Registration {
+    platform: Platform,
+    scanner: RegisteredScanner,
+}
+
+static SCANNERS: LazyLock<RwLock<Vec<Registration>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+impl<T: PlatformScanner + ?Sized> PlatformScanner for Arc<T> {
+    fn scan(&self, mode: DiscoveryScanMode) -> Result<Vec<GameInfo>, ScanError> {
+        (**self).scan(mode)
+    }
+
+    fn platform_name(&self) -> &'static str {
+        (**self).platform_name()
+    }
+}
+
+/// Add `scanner` to every discovery scan. Its games should carry
+/// `platform`, and a scan of just that platform runs it too. Replaces a
+/// scanner registered earlier under the same `platform_name`.
+pub fn register_scanner(platform: Platform, scanner: RegisteredScanner) {
+    let name = scanner.platform_name();
+    with_scanners_write(|scanners| {
+        scanners.retain(|registration| registration.scanner.platform_name() != name);
+        scanners.push(Registration { platform, scanner });
+    });
+    log::info!("Registered discovery scanner {name} for {platform}");
+}
+
+/// Remove the scanner registered under `platform_name`. Returns whether
+/// one was registered.
+pub fn unregister_scanner(platform_name: &str) -> bool {
+    with_scanners_write(|scanners| {
+        let before = scanners.len();
+        scanners.retain(|registration| registration.scanner.platform_name() != platform_name);
+        scanners.len() != before
+    })
+}
+
+/// Registered scanners in registration order, limited to `platform` when
+/// given.
+pub(crate) fn registered_scanners(platform: Option<Platform>) -> Vec<RegisteredScanner> {
+    let guard = SCANNERS.read().unwrap_or_else(|poisoned| {
+        log::warn!("Scanner registry lock poisoned (read); recovering");
+        poisoned.into_inner()
+    });
+    guard
+        .iter()
+        .filter(|registration| platform.is_none_or(|platform| registration.platform == platform))
+        .map(|registration| Arc::clone(&registration.scanner))
+        .collect()
+}
+
+fn with_scanners_write<R>(f: impl FnOnce(&mut Vec<Registration>) -> R) -> R {
+    let mut guard = SCANNERS.write().unwrap_or_else(|poisoned| {
+        log::warn!("Scanner registry lock poisoned (write); recovering");
+        poisoned.into_inner()
+    });
+    f(&mut guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedScanner(&'static str);
+
+    impl PlatformScanner for FixedScanner {
+        fn scan(&self, _mode: DiscoveryScanMode) -> Result<Vec<GameInfo>, ScanError> {
+            Ok(Vec::new())
+        }
+
+        fn platform_name(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    fn registered_names(platform: Option<Platform>) -> Vec<&'static str> {
+        registered_scanners(platform)
+            .iter()
+            .map(|scanner| scanner.platform_name())
+            .collect()
+    }
+
+    #[test]
+    fn registering_again_under_a_name_replaces_the_scanner() {
+        let _guard = crate::discovery::test_sync::lock_discovery_test();
+        let name = "Registry Replace Test";
+        register_scanner(Platform::Custom, Arc::new(FixedScanner(name)));
+        register_scanner(Platform::Application, Arc::new(FixedScanner(name)));
+
+        let all = registered_names(None);
+        assert_eq!(all.iter().filter(|&&n| n == name).count(), 1);
+        assert!(registered_names(Some(Platform::Application)).contains(&name));
+        assert!(!registered_names(Some(Platform::Custom)).contains(&name));
+
+        assert!(unregister_scanner(name));
+        assert!(!unregister_scanner(name));
+        assert!(!registered_names(None).contains(&name));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use super::install_state::InstallState;
 use super::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
 use super::scan_error::ScanError;
 use super::utils;
@@ -14,7 +16,7 @@ pub struct SteamScanner {
 impl SteamScanner {
     pub fn new() -> Self {
         Self {
-            steam_path: PathBuf::from(DEFAULT_STEAM_PATH),
+            steam_path: steam_install_path(),
         }
     }
 
@@ -42,10 +44,12 @@ impl PlatformScanner for SteamScanner {
             return Ok(Vec::new());
         }
 
+        let activity = load_user_app_activity(&self.steam_path);
+
         let games: Vec<GameInfo> = library_paths
             .iter()
             .flat_map(|lib_path| {
-                scan_library(lib_path, mode)
+                scan_library(&self.steam_path, lib_path, &activity, mode)
                     .inspect_err(|e| {
                         log::warn!("Failed to scan Steam library {}: {e}", lib_path.display())
                     })
@@ -62,11 +66,102 @@ impl PlatformScanner for SteamScanner {
     }
 }
 
+/// Steam install directory as registered by the Steam client, falling back
+/// to the default location.
+pub fn steam_install_path() -> PathBuf {
+    registry_steam_path().unwrap_or_else(|| PathBuf::from(DEFAULT_STEAM_PATH))
+}
+
+#[cfg(windows)]
+fn registry_steam_path() -> Option<PathBuf> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    // The client keeps `SteamPath` current for the signed-in user; the
+    // installer's HKLM entry covers machines where it never ran.
+    let from_user = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Software\Valve\Steam")
+        .and_then(|key| key.get_value::<String, _>("SteamPath"));
+    let from_machine = || {
+        RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey(r"SOFTWARE\WOW6432Node\Valve\Steam")
+            .and_then(|key| key.get_value::<String, _>("InstallPath"))
+    };
+    let path = PathBuf::from(
+        from_user
+            .or_else(|_| from_machine())
+            .ok()?
+            .replace('/', "\\"),
+    );
+    path.is_dir().then_some(path)
+}
+
+#[cfg(not(windows))]
+fn registry_steam_path() -> Option<PathBuf> {
+    None
+}
+
+fn library_folders_vdf(steam_path: &Path) -> PathBuf {
+    steam_path.join("steamapps").join("libraryfolders.vdf")
+}
+
+/// `steamapps\common` of every Steam library, on any drive. Reads
+/// libraryfolders.vdf on each call so libraries added in the Steam client
+/// while the app runs are included.
+pub fn steam_library_roots() -> Vec<PathBuf> {
+    library_roots_for(&steam_install_path())
+}
+
+fn library_roots_for(steam_path: &Path) -> Vec<PathBuf> {
+    discover_library_paths(steam_path)
+        .into_iter()
+        .map(|steamapps| steamapps.join("common"))
+        .filter(|common| common.is_dir())
+        .collect()
+}
+
+/// Detects edits to libraryfolders.vdf, which Steam rewrites whenever a
+/// library folder is added or removed.
+pub struct LibraryFoldersWatch {
+    vdf_path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl LibraryFoldersWatch {
+    pub fn new() -> Self {
+        Self::for_steam_path(&steam_install_path())
+    }
+
+    fn for_steam_path(steam_path: &Path) -> Self {
+        let vdf_path = library_folders_vdf(steam_path);
+        let modified = modified_time(&vdf_path);
+        Self { vdf_path, modified }
+    }
+
+    /// Whether the file changed since the last call (or construction).
+    pub fn changed(&mut self) -> bool {
+        let modified = modified_time(&self.vdf_path);
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+}
+
+impl Default for LibraryFoldersWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
 /// Discover all Steam library folders from libraryfolders.vdf.
 fn discover_library_paths(steam_path: &Path) -> Vec<PathBuf> {
-    let vdf_path = steam_path.join("steamapps").join("libraryfolders.vdf");
-
-    let Ok(content) = std::fs::read_to_string(&vdf_path) else {
+    let Ok(content) = std::fs::read_to_string(library_folders_vdf(steam_path)) else {
         let default = steam_path.join("steamapps");
         if default.is_dir() {
             return vec![default];
@@ -114,7 +209,9 @@ fn extract_quoted_value(s: &str) -> Option<&str> {
 
 /// Scan a single Steam library folder for games.
 fn scan_library(
+    steam_path: &Path,
     steamapps_path: &Path,
+    activity: &HashMap<u32, AppActivity>,
     mode: DiscoveryScanMode,
 ) -> Result<Vec<GameInfo>, ScanError> {
     let common_path = steamapps_path.join("common");
@@ -158,6 +255,8 @@ fn scan_library(
         };
         if let Some(manifest) = manifests.get(&folder_key) {
             game.steam_app_id = Some(manifest.app_id);
+            game.artwork_path = library_artwork(steam_path, manifest.app_id);
+            apply_app_activity(game, manifest, activity.get(&manifest.app_id));
         }
     }
     Ok(games)
@@ -167,6 +266,147 @@ struct AppManifest {
     app_id: u32,
     name: String,
     install_dir: String,
+    /// `LastPlayed` from the manifest, Unix seconds. Newer Steam clients
+    /// write it here as well as in `localconfig.vdf`.
+    last_played_secs: Option<u64>,
+    /// `StateFlags` bitmask describing install and update progress.
+    state_flags: Option<u32>,
+    /// `BytesToDownload` and `BytesDownloaded` of the current or last
+    /// update.
+    bytes_to_download: u64,
+    bytes_downloaded: u64,
+}
+
+/// Per-app activity from a Steam user's `localconfig.vdf`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct AppActivity {
+    last_played_secs: Option<u64>,
+    playtime_minutes: Option<u64>,
+}
+
+impl AppActivity {
+    /// Combine two accounts' activity: newest launch, most playtime.
+    fn merge(&mut self, other: AppActivity) {
+        self.last_played_secs = self.last_played_secs.max(other.last_played_secs);
+        self.playtime_minutes = self.playtime_minutes.max(other.playtime_minutes);
+    }
+}
+
+fn apply_app_activity(game: &mut GameInfo, manifest: &AppManifest, activity: Option<&AppActivity>) {
+    let activity = activity.copied().unwrap_or_default();
+    game.last_played = activity
+        .last_played_secs
+        .max(manifest.last_played_secs)
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)));
+    game.playtime_minutes = activity.playtime_minutes;
+}
+
+/// Read app activity for every Steam account that has signed in on this
+/// machine (`userdata/<account id>/config/localconfig.vdf`).
+fn load_user_app_activity(steam_path: &Path) -> HashMap<u32, AppActivity> {
+    let mut activity: HashMap<u32, AppActivity> = HashMap::new();
+    let Ok(users) = std::fs::read_dir(steam_path.join("userdata")) else {
+        return activity;
+    };
+
+    for user in users.filter_map(|e| e.ok()) {
+        let config_path = user.path().join("config").join("localconfig.vdf");
+        let Ok(content) = std::fs::read_to_string(&config_path) else {
+            continue;
+        };
+        for (app_id, app) in parse_localconfig_activity(&content) {
+            activity.entry(app_id).or_default().merge(app);
+        }
+    }
+
+    activity
+}
+
+/// Extract `LastPlayed` / `Playtime` from the
+/// `UserLocalConfigStore/Software/Valve/Steam/apps/<appid>` blocks.
+fn parse_localconfig_activity(content: &str) -> HashMap<u32, AppActivity> {
+    let mut activity: HashMap<u32, AppActivity> = HashMap::new();
+    let mut sections: Vec<String> = Vec::new();
+    let mut pending_key: Option<String> = None;
+
+    for token in vdf_tokens(content) {
+        match token {
+            VdfToken::Open => {
+                sections.push(pending_key.take().unwrap_or_default());
+            }
+            VdfToken::Close => {
+                pending_key = None;
+                sections.pop();
+            }
+            VdfToken::Str(text) => {
+                let Some(key) = pending_key.take() else {
+                    pending_key = Some(text);
+                    continue;
+                };
+                let [.., parent, app] = sections.as_slice() else {
+                    continue;
+                };
+                if !parent.eq_ignore_ascii_case("apps") {
+                    continue;
+                }
+                let (Ok(app_id), Ok(value)) = (app.parse::<u32>(), text.parse::<u64>()) else {
+                    continue;
+                };
+                if value == 0 {
+                    continue;
+                }
+                let entry = activity.entry(app_id).or_default();
+                if key.eq_ignore_ascii_case("LastPlayed") {
+                    entry.last_played_secs = Some(value);
+                } else if key.eq_ignore_ascii_case("Playtime") {
+                    entry.playtime_minutes = Some(value);
+                }
+            }
+        }
+    }
+
+    activity.retain(|_, app| *app != AppActivity::default());
+    activity
+}
+
+enum VdfToken {
+    Open,
+    Close,
+    Str(String),
+}
+
+/// Tokenize Valve KeyValues text into quoted strings and braces.
+fn vdf_tokens(content: &str) -> impl Iterator<Item = VdfToken> + '_ {
+    let mut chars = content.chars().peekable();
+    std::iter::from_fn(move || loop {
+        match chars.next()? {
+            '{' => return Some(VdfToken::Open),
+            '}' => return Some(VdfToken::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                text.push(escaped);
+                            }
+                        }
+                        _ => text.push(c),
+                    }
+                }
+                return Some(VdfToken::Str(text));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    })
 }
 
 fn parse_app_manifests(steamapps_path: &Path) -> HashMap<String, AppManifest> {
@@ -205,11 +445,48 @@ fn parse_app_manifests(steamapps_path: &Path) -> HashMap<String, AppManifest> {
     manifests
 }
 
+/// Cover art the Steam client cached for `app_id`.
+pub(crate) fn steam_library_artwork(app_id: u32) -> Option<PathBuf> {
+    library_artwork(&steam_install_path(), app_id)
+}
+
+/// Art in `appcache/librarycache`, portrait capsule first, then the header.
+/// Newer clients keep `<appid>/<name>`, sometimes one hashed folder deeper;
+/// older ones a flat `<appid>_<name>`.
+fn library_artwork(steam_path: &Path, app_id: u32) -> Option<PathBuf> {
+    const ART_NAMES: [&str; 3] = ["library_600x900.jpg", "library_capsule.jpg", "header.jpg"];
+
+    let cache = steam_path.join("appcache").join("librarycache");
+    let app_dir = cache.join(app_id.to_string());
+    let mut dirs = vec![app_dir.clone()];
+    if let Ok(entries) = std::fs::read_dir(&app_dir) {
+        dirs.extend(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir()),
+        );
+    }
+    let nested = ART_NAMES
+        .iter()
+        .flat_map(|name| dirs.iter().map(move |dir| dir.join(name)));
+    let flat = ART_NAMES
+        .iter()
+        .map(|name| cache.join(format!("{app_id}_{name}")));
+    nested.chain(flat).find(|path| path.is_file())
+}
+
 /// Look up the Steam app ID for an installed game by walking from the game
 /// path up to the surrounding `steamapps/` directory and matching the folder
 /// name against any `appmanifest_*.acf` `installdir`. Returns `None` if the
 /// path isn't a conventional Steam install or no manifest matches.
 pub(crate) fn lookup_steam_app_id_for_path(game_path: &Path) -> Option<u32> {
+    manifest_for_path(game_path).map(|(_, manifest)| manifest.app_id)
+}
+
+/// The surrounding `steamapps/` directory and the app manifest whose
+/// `installdir` matches `game_path`.
+fn manifest_for_path(game_path: &Path) -> Option<(&Path, AppManifest)> {
     let folder_name = game_path.file_name()?.to_str()?.to_ascii_lowercase();
     let common = game_path.parent()?;
     let steamapps = common.parent()?;
@@ -220,8 +497,56 @@ pub(crate) fn lookup_steam_app_id_for_path(game_path: &Path) -> Option<u32> {
     {
         return None;
     }
-    let manifests = parse_app_manifests(steamapps);
-    manifests.get(&folder_name).map(|m| m.app_id)
+    let mut manifests = parse_app_manifests(steamapps);
+    manifests
+        .remove(&folder_name)
+        .map(|manifest| (steamapps, manifest))
+}
+
+const STATE_UPDATE_REQUIRED: u32 = 0x2;
+const STATE_FULLY_INSTALLED: u32 = 0x4;
+const STATE_UPDATE_PAUSED: u32 = 0x200;
+/// Update running or started, uninstalling, reconfiguring, validating,
+/// adding files, preallocating, downloading, staging and committing.
+const STATE_BUSY: u32 = 0x100
+    | 0x400
+    | 0x800
+    | 0x1_0000
+    | 0x2_0000
+    | 0x4_0000
+    | 0x8_0000
+    | 0x10_0000
+    | 0x20_0000
+    | 0x40_0000;
+
+/// Whether Steam is still writing the game at `game_path`, from its app
+/// manifest `StateFlags` and `steamapps/downloading/<appid>`. `None` when
+/// the path is not a Steam install.
+pub(crate) fn steam_install_state(game_path: &Path) -> Option<InstallState> {
+    let (steamapps, manifest) = manifest_for_path(game_path)?;
+    let Some(flags) = manifest.state_flags else {
+        return Some(InstallState::Unknown);
+    };
+    let downloading = steamapps
+        .join("downloading")
+        .join(manifest.app_id.to_string())
+        .exists();
+    let update_unfinished = manifest.bytes_to_download > manifest.bytes_downloaded;
+    Some(state_from_flags(flags, downloading, update_unfinished))
+}
+
+fn state_from_flags(flags: u32, downloading: bool, update_unfinished: bool) -> InstallState {
+    if flags & STATE_UPDATE_PAUSED != 0 {
+        // Nothing is written while paused, but the rest of the update is.
+        InstallState::UpdatePending
+    } else if downloading || flags & STATE_BUSY != 0 || flags & STATE_FULLY_INSTALLED == 0 {
+        InstallState::InProgress
+    } else if flags & STATE_UPDATE_REQUIRED != 0 || update_unfinished {
+        // Queued but not started; the game files are rewritten once it runs.
+        InstallState::UpdatePending
+    } else {
+        InstallState::Complete
+    }
 }
 
 fn parse_app_id_from_manifest_filename(name: &str) -> Option<u32> {
@@ -234,6 +559,10 @@ fn parse_app_id_from_manifest_filename(name: &str) -> Option<u32> {
 fn parse_acf_manifest(content: &str) -> Option<AppManifest> {
     let mut name = None;
     let mut install_dir = None;
+    let mut last_played_secs = None;
+    let mut state_flags = None;
+    let mut bytes_to_download = 0;
+    let mut bytes_downloaded = 0;
 
     for line in content.lines() {
         let trimmed = line.trim();
@@ -245,6 +574,16 @@ fn parse_acf_manifest(content: &str) -> Option<AppManifest> {
             if let Some(val) = extract_quoted_value(rest) {
                 install_dir = Some(val.to_owned());
             }
+        } else if let Some(rest) = trimmed.strip_prefix("\"LastPlayed\"") {
+            last_played_secs = extract_quoted_value(rest)
+                .and_then(|val| val.parse::<u64>().ok())
+                .filter(|secs| *secs > 0);
+        } else if let Some(rest) = trimmed.strip_prefix("\"StateFlags\"") {
+            state_flags = extract_quoted_value(rest).and_then(|val| val.parse::<u32>().ok());
+        } else if let Some(rest) = trimmed.strip_prefix("\"BytesToDownload\"") {
+            bytes_to_download = parse_quoted_u64(rest);
+        } else if let Some(rest) = trimmed.strip_prefix("\"BytesDownloaded\"") {
+            bytes_downloaded = parse_quoted_u64(rest);
         }
     }
 
@@ -252,9 +591,19 @@ fn parse_acf_manifest(content: &str) -> Option<AppManifest> {
         app_id: 0,
         name: name?,
         install_dir: install_dir?,
+        last_played_secs,
+        state_flags,
+        bytes_to_download,
+        bytes_downloaded,
     })
 }
 
+fn parse_quoted_u64(rest: &str) -> u64 {
+    extract_quoted_value(rest)
+        .and_then(|val| val.parse().ok())
+        .unwrap_or(0)
+}
+
 fn is_steam_tool(folder_name: &str) -> bool {
     let lower = folder_name.to_ascii_lowercase();
     lower.contains("steamworks")
@@ -328,11 +677,65 @@ mod tests {
     "name"		"Portal"
     "StateFlags"		"4"
     "installdir"		"Portal"
+    "BytesToDownload"		"1048576"
+    "BytesDownloaded"		"524288"
 }
 "#;
         let manifest = parse_acf_manifest(acf).unwrap();
         assert_eq!(manifest.name, "Portal");
         assert_eq!(manifest.install_dir, "Portal");
+        assert_eq!(manifest.state_flags, Some(4));
+        assert_eq!(manifest.bytes_to_download, 1_048_576);
+        assert_eq!(manifest.bytes_downloaded, 524_288);
+    }
+
+    #[test]
+    fn state_flags_map_to_install_state() {
+        assert_eq!(state_from_flags(4, false, false), InstallState::Complete);
+        // Update downloading and committing.
+        assert_eq!(
+            state_from_flags(0x10_0504, false, true),
+            InstallState::InProgress
+        );
+        // Fresh install that has not finished.
+        assert_eq!(
+            state_from_flags(0x402, false, false),
+            InstallState::InProgress
+        );
+        assert_eq!(state_from_flags(4, true, false), InstallState::InProgress);
+        // Update queued, or downloaded only in part, but not running.
+        assert_eq!(
+            state_from_flags(6, false, false),
+            InstallState::UpdatePending
+        );
+        assert_eq!(
+            state_from_flags(4, false, true),
+            InstallState::UpdatePending
+        );
+        assert_eq!(
+            state_from_flags(0x204, true, true),
+            InstallState::UpdatePending
+        );
+    }
+
+    #[test]
+    fn steam_install_state_reads_manifest_and_downloading_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let steamapps = temp.path().join("steamapps");
+        let game = steamapps.join("common").join("Portal");
+        std::fs::create_dir_all(&game).unwrap();
+        std::fs::write(
+            steamapps.join("appmanifest_400.acf"),
+            "\"AppState\"\n{\n\t\"name\"\t\"Portal\"\n\t\"StateFlags\"\t\"4\"\n\t\"installdir\"\t\"Portal\"\n}\n",
+        )
+        .unwrap();
+
+        assert_eq!(steam_install_state(&game), Some(InstallState::Complete));
+
+        std::fs::create_dir_all(steamapps.join("downloading").join("400")).unwrap();
+        assert_eq!(steam_install_state(&game), Some(InstallState::InProgress));
+
+        assert_eq!(steam_install_state(&temp.path().join("Other")), None);
     }
 
     #[test]
@@ -358,6 +761,86 @@ mod tests {
         assert!(parse_acf_manifest(acf).is_none());
     }
 
+    #[test]
+    fn parse_acf_manifest_reads_last_played() {
+        let acf = r#"
+"AppState"
+{
+    "appid"		"400"
+    "name"		"Portal"
+    "installdir"		"Portal"
+    "LastUpdated"		"1700000000"
+    "LastPlayed"		"1710000000"
+}
+"#;
+        let manifest = parse_acf_manifest(acf).unwrap();
+        assert_eq!(manifest.last_played_secs, Some(1_710_000_000));
+    }
+
+    #[test]
+    fn parse_localconfig_activity_reads_apps_block() {
+        let vdf = r#"
+"UserLocalConfigStore"
+{
+    "Software"
+    {
+        "Valve"
+        {
+            "Steam"
+            {
+                "apps"
+                {
+                    "400"
+                    {
+                        "LastPlayed"		"1710000000"
+                        "Playtime"		"125"
+                        "cloud"
+                        {
+                            "last_sync_state"		"synchronized"
+                        }
+                    }
+                    "620"
+                    {
+                        "LastPlayed"		"0"
+                    }
+                }
+            }
+        }
+    }
+    "friends"
+    {
+        "400"
+        {
+            "Playtime"		"999"
+        }
+    }
+}
+"#;
+        let activity = parse_localconfig_activity(vdf);
+        assert_eq!(activity.len(), 1);
+        assert_eq!(
+            activity[&400],
+            AppActivity {
+                last_played_secs: Some(1_710_000_000),
+                playtime_minutes: Some(125),
+            }
+        );
+    }
+
+    #[test]
+    fn app_activity_merge_keeps_newest_and_largest() {
+        let mut a = AppActivity {
+            last_played_secs: Some(100),
+            playtime_minutes: Some(50),
+        };
+        a.merge(AppActivity {
+            last_played_secs: Some(200),
+            playtime_minutes: None,
+        });
+        assert_eq!(a.last_played_secs, Some(200));
+        assert_eq!(a.playtime_minutes, Some(50));
+    }
+
     #[test]
     fn extract_quoted_value_simple() {
         assert_eq!(
@@ -393,6 +876,53 @@ mod tests {
         assert!(!is_steam_tool("Portal 2"));
     }
 
+    #[test]
+    fn library_roots_follow_libraryfolders_vdf() {
+        let steam = tempfile::TempDir::new().unwrap();
+        let second = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(steam.path().join("steamapps").join("common")).unwrap();
+        std::fs::create_dir_all(second.path().join("steamapps").join("common")).unwrap();
+
+        let mut watch = LibraryFoldersWatch::for_steam_path(steam.path());
+        assert_eq!(
+            library_roots_for(steam.path()),
+            vec![steam.path().join("steamapps").join("common")]
+        );
+
+        let vdf = format!(
+            "\"libraryfolders\"\n{{\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+            second.path().display().to_string().replace('\\', "\\\\")
+        );
+        std::fs::write(library_folders_vdf(steam.path()), vdf).unwrap();
+        assert!(watch.changed());
+        assert!(!watch.changed());
+        assert_eq!(
+            library_roots_for(steam.path()),
+            vec![
+                steam.path().join("steamapps").join("common"),
+                second.path().join("steamapps").join("common"),
+            ]
+        );
+    }
+
+    #[test]
+    fn library_artwork_prefers_capsule_across_cache_layouts() {
+        let steam = tempfile::TempDir::new().unwrap();
+        let cache = steam.path().join("appcache").join("librarycache");
+        std::fs::create_dir_all(cache.join("620").join("3f9a")).unwrap();
+        std::fs::write(cache.join("400_header.jpg"), b"jpg").unwrap();
+        std::fs::write(cache.join("620").join("header.jpg"), b"jpg").unwrap();
+        let capsule = cache.join("620").join("3f9a").join("library_capsule.jpg");
+        std::fs::write(&capsule, b"jpg").unwrap();
+
+        assert_eq!(
+            library_artwork(steam.path(), 400),
+            Some(cache.join("400_header.jpg"))
+        );
+        assert_eq!(library_artwork(steam.path(), 620), Some(capsule));
+        assert_eq!(library_artwork(steam.path(), 730), None);
+    }
+
     #[test]
     fn steam_scanner_nonexistent_path_returns_empty() {
         let scanner = SteamScanner::with_path(PathBuf::from(r"C:\NonExistent\Steam"));
@@ -4,15 +4,40 @@ use std::sync::{LazyLock, OnceLock, RwLock};
 
 use sysinfo::{DiskKind, Disks};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StorageClass {
     Hdd,
     Ssd,
     Unknown,
 }
 
+/// How the volume holding a path is attached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DriveType {
+    Fixed,
+    /// USB sticks, SD cards and optical media.
+    Removable,
+    /// SMB shares, by UNC path or mapped drive letter.
+    Network,
+    #[default]
+    Unknown,
+}
+
+impl DriveType {
+    /// Removable and network volumes are skipped by automation unless the
+    /// user opts in: they come and go, and WOF over SMB does not work.
+    pub fn is_removable_or_network(self) -> bool {
+        matches!(self, Self::Removable | Self::Network)
+    }
+}
+
 static STORAGE_CACHE: LazyLock<RwLock<HashMap<String, StorageClass>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
+static DRIVE_TYPE_CACHE: LazyLock<RwLock<HashMap<String, DriveType>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+/// Physical disk number per drive root; `None` when it could not be read.
+static DISK_NUMBER_CACHE: LazyLock<RwLock<HashMap<String, Option<u32>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
 static DISK_KIND_SUMMARY_CACHE: OnceLock<(bool, bool)> = OnceLock::new();
 
 pub fn storage_class_for_path(path: &Path) -> StorageClass {
@@ -29,6 +54,218 @@ pub fn storage_class_for_path(path: &Path) -> StorageClass {
     class
 }
 
+/// Drive type of the volume containing `path`, cached per volume.
+pub fn drive_type_for_path(path: &Path) -> DriveType {
+    let root = match drive_root(path) {
+        DriveRoot::Unc => return DriveType::Network,
+        DriveRoot::Letter(root) => root,
+        DriveRoot::Other => return DriveType::Unknown,
+    };
+
+    let cached = DRIVE_TYPE_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Drive type cache lock poisoned (read); recovering");
+            poisoned.into_inner()
+        })
+        .get(&root)
+        .copied();
+    if let Some(drive_type) = cached {
+        return drive_type;
+    }
+
+    let drive_type = detect_drive_type(&root);
+    DRIVE_TYPE_CACHE
+        .write()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Drive type cache lock poisoned (write); recovering");
+            poisoned.into_inner()
+        })
+        .insert(root, drive_type);
+    drive_type
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DriveRoot {
+    /// `\\server\share\...` or `\\?\UNC\server\share\...`.
+    Unc,
+    /// Lowercase drive root such as `d:\`.
+    Letter(String),
+    Other,
+}
+
+fn drive_root(path: &Path) -> DriveRoot {
+    let raw = path.as_os_str().to_string_lossy().replace('/', "\\");
+    if let Some(rest) = raw.strip_prefix(r"\\?\") {
+        if rest
+            .get(..4)
+            .is_some_and(|p| p.eq_ignore_ascii_case(r"UNC\"))
+        {
+            return DriveRoot::Unc;
+        }
+        return letter_root(rest);
+    }
+    if raw.starts_with(r"\\.\") {
+        return DriveRoot::Other;
+    }
+    if raw.starts_with(r"\\") {
+        return DriveRoot::Unc;
+    }
+    letter_root(&raw)
+}
+
+fn letter_root(path: &str) -> DriveRoot {
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        return DriveRoot::Letter(format!("{}:\\", (bytes[0] as char).to_ascii_lowercase()));
+    }
+    DriveRoot::Other
+}
+
+#[cfg(windows)]
+fn detect_drive_type(root: &str) -> DriveType {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    // WindowsProgramming constants; that feature is not otherwise needed.
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_FIXED: u32 = 3;
+    const DRIVE_REMOTE: u32 = 4;
+    const DRIVE_CDROM: u32 = 5;
+    const DRIVE_RAMDISK: u32 = 6;
+
+    let wide: Vec<u16> = std::ffi::OsStr::new(root)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    match unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) } {
+        DRIVE_FIXED | DRIVE_RAMDISK => DriveType::Fixed,
+        DRIVE_REMOVABLE | DRIVE_CDROM => DriveType::Removable,
+        DRIVE_REMOTE => DriveType::Network,
+        _ => DriveType::Unknown,
+    }
+}
+
+#[cfg(not(windows))]
+fn detect_drive_type(_root: &str) -> DriveType {
+    DriveType::Unknown
+}
+
+/// Stable identifier for the volume containing `path` (e.g. `c:\`).
+///
+/// Paths on the same volume share one key, so callers can avoid running
+/// parallel disk-heavy work against a single device.
+pub fn volume_key_for_path(path: &Path) -> String {
+    volume_cache_key(path)
+}
+
+/// Stable identifier for the physical disk holding `path` (e.g. `disk0`).
+///
+/// Volumes that are partitions of one drive share a key. Falls back to
+/// [`volume_key_for_path`] when the disk cannot be resolved, such as for
+/// network shares or volumes the OS will not describe.
+pub fn disk_key_for_path(path: &Path) -> String {
+    let DriveRoot::Letter(root) = drive_root(path) else {
+        return volume_key_for_path(path);
+    };
+
+    let cached = DISK_NUMBER_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Disk number cache lock poisoned (read); recovering");
+            poisoned.into_inner()
+        })
+        .get(&root)
+        .copied();
+    let disk_number = match cached {
+        Some(disk_number) => disk_number,
+        None => {
+            let disk_number = detect_disk_number(&root);
+            DISK_NUMBER_CACHE
+                .write()
+                .unwrap_or_else(|poisoned| {
+                    log::warn!("Disk number cache lock poisoned (write); recovering");
+                    poisoned.into_inner()
+                })
+                .insert(root.clone(), disk_number);
+            disk_number
+        }
+    };
+
+    match disk_number {
+        Some(number) => format!("disk{number}"),
+        None => root,
+    }
+}
+
+/// Number of the disk backing the volume at `root`. A volume spanning
+/// several disks is keyed by the first one.
+#[cfg(windows)]
+fn detect_disk_number(root: &str) -> Option<u32> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::Storage::FileSystem::{
+        CreateFileW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+        IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS, OPEN_EXISTING,
+    };
+    use windows::Win32::System::Ioctl::VOLUME_DISK_EXTENTS;
+    use windows::Win32::System::IO::DeviceIoControl;
+
+    // `d:\` -> `\\.\d:`; the volume device, not its root directory.
+    let device = format!(r"\\.\{}", root.trim_end_matches('\\'));
+    let wide: Vec<u16> = std::ffi::OsStr::new(&device)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    // No access rights are needed to query extents, so this works unelevated.
+    let handle = unsafe {
+        CreateFileW(
+            PCWSTR(wide.as_ptr()),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            None,
+        )
+    }
+    .ok()?;
+
+    // Room for a handful of extents; spanned volumes report more than one.
+    let mut buffer = [VOLUME_DISK_EXTENTS::default(); 4];
+    let mut returned = 0u32;
+    let result = unsafe {
+        DeviceIoControl(
+            handle,
+            IOCTL_VOLUME_GET_VOLUME_DISK_EXTENTS,
+            None,
+            0,
+            Some(buffer.as_mut_ptr().cast()),
+            std::mem::size_of_val(&buffer) as u32,
+            Some(&mut returned),
+            None,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+    if let Err(e) = result {
+        log::debug!("Disk extents unavailable for {root}: {e}");
+        return None;
+    }
+
+    let extents = &buffer[0];
+    if extents.NumberOfDiskExtents == 0 {
+        return None;
+    }
+    Some(extents.Extents[0].DiskNumber)
+}
+
+#[cfg(not(windows))]
+fn detect_disk_number(_root: &str) -> Option<u32> {
+    None
+}
+
 pub fn has_any_hdd_disk() -> bool {
     disk_kind_summary().0
 }
@@ -150,4 +387,32 @@ mod tests {
         let second = storage_class_for_path(&cwd);
         assert_eq!(first, second);
     }
+
+    #[test]
+    fn drive_roots_are_parsed() {
+        assert_eq!(
+            drive_root(Path::new(r"D:\Games\Hades")),
+            DriveRoot::Letter(r"d:\".to_owned())
+        );
+        assert_eq!(
+            drive_root(Path::new(r"\\?\E:\Games")),
+            DriveRoot::Letter(r"e:\".to_owned())
+        );
+        assert_eq!(drive_root(Path::new(r"\\nas\games\Hades")), DriveRoot::Unc);
+        assert_eq!(drive_root(Path::new(r"\\?\UNC\nas\games")), DriveRoot::Unc);
+        assert_eq!(
+            drive_root(Path::new(r"\\.\PhysicalDrive0")),
+            DriveRoot::Other
+        );
+    }
+
+    #[test]
+    fn unc_paths_are_network_drives() {
+        assert_eq!(
+            drive_type_for_path(Path::new(r"\\nas\games\Hades")),
+            DriveType::Network
+        );
+        assert!(DriveType::Network.is_removable_or_network());
+        assert!(!DriveType::Fixed.is_removable_or_network());
+    }
 }
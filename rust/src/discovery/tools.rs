@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+
+use super::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
+use super::scan_error::ScanError;
+use super::utils;
+
+/// A development or 3D tool that installs one folder per version.
+struct KnownTool {
+    /// Folder holding the versions, relative to a Program Files root.
+    parent: &'static [&'static str],
+    /// Prefix of each version folder; the rest is the version.
+    folder_prefix: &'static str,
+    display_name: &'static str,
+}
+
+const KNOWN_TOOLS: &[KnownTool] = &[
+    KnownTool {
+        parent: &["Epic Games"],
+        folder_prefix: "UE_",
+        display_name: "Unreal Engine",
+    },
+    KnownTool {
+        parent: &["Unity", "Hub", "Editor"],
+        folder_prefix: "",
+        display_name: "Unity",
+    },
+    KnownTool {
+        parent: &["Blender Foundation"],
+        folder_prefix: "Blender",
+        display_name: "Blender",
+    },
+    KnownTool {
+        parent: &["Side Effects Software"],
+        folder_prefix: "Houdini",
+        display_name: "Houdini",
+    },
+    KnownTool {
+        parent: &["Autodesk"],
+        folder_prefix: "Maya",
+        display_name: "Maya",
+    },
+    KnownTool {
+        parent: &["Autodesk"],
+        folder_prefix: "3ds Max",
+        display_name: "3ds Max",
+    },
+    KnownTool {
+        parent: &["Adobe"],
+        folder_prefix: "Adobe Substance 3D Painter",
+        display_name: "Substance 3D Painter",
+    },
+];
+
+/// Finds engine, SDK and content tools in their default install folders.
+/// They are listed as applications, not games.
+#[derive(Default)]
+pub struct ToolsScanner {}
+
+impl PlatformScanner for ToolsScanner {
+    fn scan(&self, mode: DiscoveryScanMode) -> Result<Vec<GameInfo>, ScanError> {
+        let tools = scan_tool_roots(&program_files_roots(), mode);
+        log::info!("Tools: found {} installs", tools.len());
+        Ok(tools)
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "Tools"
+    }
+}
+
+fn program_files_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = ["ProgramFiles", "ProgramFiles(x86)"]
+        .into_iter()
+        .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+        .collect();
+    roots.dedup();
+    roots
+}
+
+fn scan_tool_roots(roots: &[PathBuf], mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let mut tools = Vec::new();
+    for root in roots {
+        for tool in KNOWN_TOOLS {
+            let parent = tool
+                .parent
+                .iter()
+                .fold(root.clone(), |path, part| path.join(part));
+            utils::merge_games(&mut tools, scan_tool_versions(tool, &parent, mode));
+        }
+    }
+    tools
+}
+
+fn scan_tool_versions(tool: &KnownTool, parent: &Path, mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let folder_name = e.file_name().to_string_lossy().into_owned();
+            let name = tool_display_name(tool, &folder_name)?;
+            utils::build_game_info_with_mode(name, e.path(), Platform::Application, mode)
+        })
+        .collect()
+}
+
+/// `Unreal Engine 5.3` for `UE_5.3`, or `None` when the folder is not a
+/// version of `tool`.
+fn tool_display_name(tool: &KnownTool, folder_name: &str) -> Option<String> {
+    let prefix_len = tool.folder_prefix.len();
+    let (prefix, version) = (
+        folder_name.get(..prefix_len)?,
+        folder_name.get(prefix_len..)?,
+    );
+    if !prefix.eq_ignore_ascii_case(tool.folder_prefix) {
+        return None;
+    }
+    let version = version.trim_start_matches(['_', ' ', '-']);
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{} {version}", tool.display_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_folders_get_tool_names() {
+        let unreal = &KNOWN_TOOLS[0];
+        assert_eq!(
+            tool_display_name(unreal, "UE_5.3").as_deref(),
+            Some("Unreal Engine 5.3")
+        );
+        assert_eq!(tool_display_name(unreal, "Launcher"), None);
+        assert_eq!(tool_display_name(unreal, "UE_Binaries"), None);
+
+        let unity = &KNOWN_TOOLS[1];
+        assert_eq!(
+            tool_display_name(unity, "2022.3.1f1").as_deref(),
+            Some("Unity 2022.3.1f1")
+        );
+    }
+
+    #[test]
+    fn scans_tool_versions_under_program_files() {
+        let root = tempfile::TempDir::new().unwrap();
+        let engine = root.path().join("Epic Games").join("UE_5.4");
+        std::fs::create_dir_all(engine.join("Engine")).unwrap();
+        std::fs::write(engine.join("Engine").join("Build.version"), b"{}").unwrap();
+        std::fs::create_dir_all(root.path().join("Epic Games").join("Launcher")).unwrap();
+
+        let tools = scan_tool_roots(&[root.path().to_path_buf()], DiscoveryScanMode::Full);
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "Unreal Engine 5.4");
+        assert_eq!(tools[0].platform, Platform::Application);
+    }
+}
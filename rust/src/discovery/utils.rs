@@ -7,10 +7,11 @@ pub use dedupe::merge_games;
 pub(crate) use game_info::is_non_game_exe;
 pub use game_info::{
     build_game_info, build_game_info_with_mode, build_game_info_with_mode_and_stats_path,
+    refresh_cached_stats,
 };
 pub use scanning::{
     build_games_from_candidates, evict_discovery_entry, scan_all_platforms,
-    scan_all_platforms_with_mode, scan_custom_paths, scan_custom_paths_with_mode,
-    scan_game_subdirs,
+    scan_all_platforms_streaming, scan_all_platforms_with_mode, scan_custom_paths,
+    scan_custom_paths_with_mode, scan_game_subdirs, scan_platform_with_mode,
 };
-pub use stats::{dir_stats, dir_stats_quick, DirStats};
+pub use stats::{dir_stats, dir_stats_quick, dir_stats_with_policy, DirStats};
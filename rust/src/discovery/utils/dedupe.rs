@@ -39,6 +39,7 @@ mod tests {
     use std::path::PathBuf;
 
     use crate::discovery::platform::Platform;
+    use crate::discovery::storage::DriveType;
 
     fn make_game(name: &str, path: PathBuf) -> GameInfo {
         GameInfo {
@@ -50,9 +51,19 @@ mod tests {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         }
     }
 
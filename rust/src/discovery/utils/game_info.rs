@@ -8,6 +8,8 @@ use crate::discovery::hidden_paths;
 use crate::discovery::index;
 use crate::discovery::install_history;
 use crate::discovery::platform::{DiscoveryScanMode, GameInfo, Platform};
+use crate::discovery::progress;
+use crate::discovery::storage::{self, DriveType};
 
 use super::stats::{dir_stats, dir_stats_quick};
 
@@ -58,6 +60,30 @@ pub fn build_game_info_with_mode_and_stats_path(
     stats_path: PathBuf,
     platform: Platform,
     mode: DiscoveryScanMode,
+) -> Option<GameInfo> {
+    progress::record_candidates_found(1);
+    build_found_candidate(name, game_path, stats_path, platform, mode)
+}
+
+/// Build a candidate already counted as found in the scan progress.
+pub(super) fn build_found_candidate(
+    name: String,
+    game_path: PathBuf,
+    stats_path: PathBuf,
+    platform: Platform,
+    mode: DiscoveryScanMode,
+) -> Option<GameInfo> {
+    let game = build_candidate(name, game_path, stats_path, platform, mode);
+    progress::record_candidate_sized();
+    game
+}
+
+fn build_candidate(
+    name: String,
+    game_path: PathBuf,
+    stats_path: PathBuf,
+    platform: Platform,
+    mode: DiscoveryScanMode,
 ) -> Option<GameInfo> {
     if !stats_path.exists() {
         evict_candidate(&stats_path);
@@ -210,7 +236,7 @@ pub fn build_game_info_with_mode_and_stats_path(
             mode,
             "quick stats sampled (not persisted)",
         );
-        return Some(game_info_from_parts(
+        let mut game = game_info_from_parts(
             name,
             game_path,
             platform,
@@ -218,7 +244,10 @@ pub fn build_game_info_with_mode_and_stats_path(
             stats.physical_size,
             stats.is_compressed,
             is_directstorage,
-        ));
+        );
+        game.has_cloud_placeholders = stats.has_cloud_placeholders;
+        game.has_encrypted_or_sparse_files = stats.has_many_incompatible_files();
+        return Some(game);
     }
 
     if let Some(mut indexed_game) = index::lookup(&stats_path, &token) {
@@ -345,7 +374,9 @@ pub fn build_game_info_with_mode_and_stats_path(
             stats.physical_size,
             stats.is_compressed,
             is_directstorage,
-        ),
+        )
+        .with_cloud_placeholders(stats.has_cloud_placeholders)
+        .with_encrypted_or_sparse_files(stats.has_many_incompatible_files()),
     );
 
     log_candidate_decision(
@@ -357,7 +388,7 @@ pub fn build_game_info_with_mode_and_stats_path(
         "full stats refreshed cache",
     );
 
-    let game = game_info_from_parts(
+    let mut game = game_info_from_parts(
         name,
         game_path,
         platform,
@@ -366,6 +397,8 @@ pub fn build_game_info_with_mode_and_stats_path(
         stats.is_compressed,
         is_directstorage,
     );
+    game.has_cloud_placeholders = stats.has_cloud_placeholders;
+    game.has_encrypted_or_sparse_files = stats.has_many_incompatible_files();
     index::upsert(&stats_path, token, &game);
     Some(game)
 }
@@ -379,7 +412,7 @@ fn game_info_from_cached(
     if cached.logical_size == 0 {
         return None;
     }
-    Some(game_info_from_parts(
+    let mut game = game_info_from_parts(
         name,
         game_path,
         platform,
@@ -387,7 +420,46 @@ fn game_info_from_cached(
         cached.physical_size,
         cached.is_compressed,
         cached.is_directstorage,
-    ))
+    );
+    game.has_cloud_placeholders = cached.has_cloud_placeholders;
+    game.has_encrypted_or_sparse_files = cached.has_encrypted_or_sparse_files;
+    Some(game)
+}
+
+/// Recompute the cached stats of `game_path` after compression or
+/// decompression changed its size on disk, so the library shows the new
+/// size without waiting for a rescan. Xbox installs keep their stats on
+/// the `Content` folder, which is refreshed as well.
+pub fn refresh_cached_stats(game_path: &Path) {
+    refresh_cached_stats_for_path(game_path);
+    let content_path = game_path.join("Content");
+    if content_path.is_dir() {
+        refresh_cached_stats_for_path(&content_path);
+    }
+}
+
+fn refresh_cached_stats_for_path(stats_path: &Path) {
+    let stats = dir_stats(stats_path);
+    if stats.scan_limit_reached || stats.logical_size == 0 {
+        evict_candidate(stats_path);
+        return;
+    }
+    // Probed like every lookup after the first scan, so the next one hits.
+    let token = cache::compute_change_token(stats_path, true);
+    let is_directstorage = crate::safety::directstorage::is_directstorage_game(stats_path);
+    cache::upsert(
+        stats_path,
+        token,
+        CachedGameStats::from_parts(
+            stats.logical_size,
+            stats.physical_size,
+            stats.is_compressed,
+            is_directstorage,
+        )
+        .with_cloud_placeholders(stats.has_cloud_placeholders)
+        .with_encrypted_or_sparse_files(stats.has_many_incompatible_files()),
+    );
+    cache::persist_if_dirty();
 }
 
 fn game_info_from_parts(
@@ -408,9 +480,19 @@ fn game_info_from_parts(
         is_compressed,
         is_directstorage,
         is_unsupported: false,
+        is_protected_package: false,
+        is_unsupported_filesystem: false,
+        uses_kernel_anticheat: false,
+        drive_type: DriveType::Unknown,
+        has_cloud_placeholders: false,
+        has_encrypted_or_sparse_files: false,
         excluded: false,
         steam_app_id: None,
         last_played: None,
+        last_compressed: None,
+        playtime_minutes: None,
+        epic_catalog_item_id: None,
+        artwork_path: None,
     };
     refresh_dynamic_game_metadata(&mut game);
     game
@@ -420,10 +502,16 @@ pub(crate) fn refresh_dynamic_game_metadata(game: &mut GameInfo) {
     if crate::safety::known_games::is_known_directstorage_game(&game.path) {
         game.is_directstorage = true;
     }
+    if let Some(allow) = crate::safety::directstorage_cache::override_for(&game.path) {
+        game.is_directstorage = !allow;
+    }
     game.is_unsupported = crate::safety::unsupported_games::is_unsupported_game(&game.path);
-    // Reuse `last_played` transport slot as "last compressed" timestamp.
-    // The old last-played source is not currently populated.
-    game.last_played = compression_timestamp_for_game_path(&game.path, game.is_compressed);
+    game.is_protected_package = crate::safety::uwp::is_protected_package(&game.path);
+    game.is_unsupported_filesystem =
+        crate::safety::filesystem::is_unsupported_filesystem(&game.path);
+    game.uses_kernel_anticheat = crate::safety::anticheat::uses_kernel_anticheat(&game.path);
+    game.drive_type = storage::drive_type_for_path(&game.path);
+    game.last_compressed = compression_timestamp_for_game_path(&game.path, game.is_compressed);
 }
 
 fn compression_timestamp_for_game_path(path: &Path, is_compressed: bool) -> Option<SystemTime> {
@@ -10,9 +10,13 @@ use crate::discovery::cache;
 use crate::discovery::hidden_paths;
 use crate::discovery::install_history;
 use crate::discovery::platform::{DiscoveryScanMode, GameInfo, Platform};
+use crate::discovery::storage::DriveType;
 use crate::discovery::test_sync::lock_discovery_test;
 
-use super::{build_game_info_with_mode_and_stats_path, compression_timestamp_for_game_path};
+use super::{
+    build_game_info_with_mode_and_stats_path, compression_timestamp_for_game_path,
+    refresh_cached_stats,
+};
 
 #[test]
 fn quick_scan_ignores_stale_cache_for_deleted_path() {
@@ -536,6 +540,8 @@ fn compression_timestamp_only_exposed_for_compressed_games() {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 
     assert!(
@@ -578,6 +584,8 @@ fn indexed_game_refreshes_last_compressed_timestamp_from_history() {
         },
         algorithm: CompressionAlgorithm::Xpress8K,
         duration_ms: 10,
+        extensions: Vec::new(),
+        throughput: None,
     });
 
     let token = cache::compute_change_token(&game_dir, false);
@@ -593,9 +601,19 @@ fn indexed_game_refreshes_last_compressed_timestamp_from_history() {
             is_compressed: true,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: None,
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         },
     );
 
@@ -609,7 +627,7 @@ fn indexed_game_refreshes_last_compressed_timestamp_from_history() {
     .expect("indexed application should hydrate");
 
     let expected = UNIX_EPOCH + Duration::from_millis(timestamp_ms);
-    assert_eq!(game.last_played, Some(expected));
+    assert_eq!(game.last_compressed, Some(expected));
 }
 
 #[test]
@@ -683,9 +701,19 @@ fn known_directstorage_game_refreshes_false_index_hit() {
             is_compressed: false,
             is_directstorage: false,
             is_unsupported: false,
+            is_protected_package: false,
+            is_unsupported_filesystem: false,
+            uses_kernel_anticheat: false,
+            drive_type: DriveType::Unknown,
+            has_cloud_placeholders: false,
+            has_encrypted_or_sparse_files: false,
             excluded: false,
             steam_app_id: Some(2_483_190),
             last_played: None,
+            last_compressed: None,
+            playtime_minutes: None,
+            epic_catalog_item_id: None,
+            artwork_path: None,
         },
     );
 
@@ -703,3 +731,27 @@ fn known_directstorage_game_refreshes_false_index_hit() {
         "known-game database should override stale false DirectStorage index metadata",
     );
 }
+
+#[test]
+fn refresh_replaces_cached_stats_with_current_sizes() {
+    let _guard = lock_discovery_test();
+    let temp = tempfile::TempDir::new().unwrap();
+    let game_dir = temp.path().join("RefreshedAfterCompression");
+    fs::create_dir_all(&game_dir).unwrap();
+    File::create(game_dir.join("payload.bin"))
+        .unwrap()
+        .set_len(10_000)
+        .unwrap();
+    cache::upsert(
+        &game_dir,
+        cache::compute_change_token(&game_dir, false),
+        cache::CachedGameStats::from_parts(1, 1, true, false),
+    );
+
+    refresh_cached_stats(&game_dir);
+
+    let token = cache::compute_change_token(&game_dir, cache::has_entry(&game_dir));
+    let cached = cache::lookup(&game_dir, &token).expect("refresh should leave a usable entry");
+    assert_eq!(cached.logical_size, 10_000);
+    assert!(!cached.is_compressed);
+}
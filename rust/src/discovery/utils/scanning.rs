@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use rayon::prelude::*;
 
@@ -9,12 +10,15 @@ use crate::discovery::hidden_paths;
 use crate::discovery::index;
 use crate::discovery::install_history;
 use crate::discovery::platform::{DiscoveryScanMode, GameInfo, Platform, PlatformScanner};
+use crate::discovery::progress;
+use crate::discovery::scanner_registry::{self, RegisteredScanner};
 use crate::discovery::storage::{
     has_any_hdd_disk, has_any_ssd_disk, storage_class_for_path, StorageClass,
 };
+use crate::settings::Exclusions;
 
 use super::dedupe::merge_games;
-use super::game_info::{build_game_info_with_mode, refresh_dynamic_game_metadata};
+use super::game_info::{build_found_candidate, refresh_dynamic_game_metadata};
 
 const PARALLEL_MIN_CANDIDATES: usize = 3;
 const PARALLEL_UNKNOWN_MIN_CANDIDATES: usize = 8;
@@ -74,6 +78,8 @@ pub fn scan_game_subdirs(
             cached.platform = platform;
             refresh_dynamic_game_metadata(&mut cached);
             results.push(cached);
+            progress::record_candidates_found(1);
+            progress::record_candidate_sized();
         } else {
             index_miss_count = index_miss_count.saturating_add(1);
             rebuild_candidates.push((name.clone(), path.clone()));
@@ -132,6 +138,7 @@ pub fn build_games_from_candidates(
     if candidates.is_empty() {
         return Vec::new();
     }
+    progress::record_candidates_found(candidates.len());
 
     if should_parallelize_subdir_scan(scan_root, mode, candidates.len()) {
         log::debug!(
@@ -141,13 +148,19 @@ pub fn build_games_from_candidates(
         );
         return candidates
             .into_par_iter()
-            .filter_map(|(name, path)| build_game_info_with_mode(name, path, platform, mode))
+            .filter_map(|(name, path)| {
+                let stats_path = path.clone();
+                build_found_candidate(name, path, stats_path, platform, mode)
+            })
             .collect();
     }
 
     candidates
         .into_iter()
-        .filter_map(|(name, path)| build_game_info_with_mode(name, path, platform, mode))
+        .filter_map(|(name, path)| {
+            let stats_path = path.clone();
+            build_found_candidate(name, path, stats_path, platform, mode)
+        })
         .collect()
 }
 
@@ -172,6 +185,7 @@ fn should_parallelize_subdir_scan(
 /// Run all platform scanners and return merged, deduplicated results.
 ///
 /// Individual scanner failures are logged but do not abort the scan.
+/// Games matching the user's exclusion rules come back marked `excluded`.
 /// This function encapsulates scanner instantiation so callers don't
 /// need to depend on individual scanner types.
 pub fn scan_all_platforms() -> Vec<GameInfo> {
@@ -179,7 +193,9 @@ pub fn scan_all_platforms() -> Vec<GameInfo> {
 }
 
 pub fn scan_all_platforms_with_mode(mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let exclusions = Exclusions::load();
     let tasks = scanner_tasks();
+    let _progress = progress::begin_scan(tasks.len());
     let mut all_games = Vec::new();
 
     if should_parallelize_platform_scanners(mode) {
@@ -197,16 +213,96 @@ pub fn scan_all_platforms_with_mode(mode: DiscoveryScanMode) -> Vec<GameInfo> {
         }
     }
 
+    persist_scan_state(mode);
+    exclusions.mark(&mut all_games);
+    all_games
+}
+
+/// Run only the scanners that produce `platform` games.
+///
+/// Lets the UI refresh one launcher's tab (e.g. after a Steam update)
+/// without re-running every scanner. `Custom` covers user and common
+/// custom roots; `Application` covers the known tool installs, not
+/// folders the user added by hand.
+pub fn scan_platform_with_mode(platform: Platform, mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let tasks = scanner_tasks_for_platform(platform);
+    let _progress = progress::begin_scan(tasks.len());
+    let mut games = Vec::new();
+    for task in tasks {
+        merge_games(&mut games, run_scanner_task(task, mode));
+    }
+    games.retain(|game| game.platform == platform);
+    Exclusions::load().mark(&mut games);
+
+    persist_scan_state(mode);
+    games
+}
+
+/// Scan all platforms, reporting each scanner's newly found games through
+/// `on_games` as soon as that scanner finishes.
+///
+/// Every game is reported once: paths already reported by an earlier
+/// scanner are deduplicated exactly like the batch scan. User and common
+/// custom roots run after the launcher scanners so launcher-owned installs
+/// keep their platform regardless of which scanner finishes first.
+pub fn scan_all_platforms_streaming<F>(mode: DiscoveryScanMode, on_games: F) -> Vec<GameInfo>
+where
+    F: Fn(&[GameInfo]) + Sync,
+{
+    let (fallback_tasks, platform_tasks): (Vec<_>, Vec<_>) =
+        scanner_tasks().into_iter().partition(|task| {
+            matches!(
+                task,
+                ScannerTask::UserCustomRoots | ScannerTask::CommonCustomRoots
+            )
+        });
+    let _progress = progress::begin_scan(platform_tasks.len() + fallback_tasks.len());
+    let all_games = Mutex::new(Vec::new());
+    let exclusions = Exclusions::load();
+
+    let publish = |mut games: Vec<GameInfo>| {
+        exclusions.mark(&mut games);
+        let mut all = all_games.lock().unwrap_or_else(|poisoned| {
+            log::warn!("Discovery stream lock poisoned; recovering");
+            poisoned.into_inner()
+        });
+        let before = all.len();
+        merge_games(&mut all, games);
+        if all.len() > before {
+            on_games(&all[before..]);
+        }
+    };
+
+    if should_parallelize_platform_scanners(mode) {
+        platform_tasks
+            .into_par_iter()
+            .for_each(|task| publish(run_scanner_task(task, mode)));
+    } else {
+        for task in platform_tasks {
+            publish(run_scanner_task(task, mode));
+        }
+    }
+    for task in fallback_tasks {
+        publish(run_scanner_task(task, mode));
+    }
+
+    persist_scan_state(mode);
+    all_games
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn persist_scan_state(mode: DiscoveryScanMode) {
     cache::persist_if_dirty();
     hidden_paths::persist_if_dirty();
     install_history::persist_if_dirty();
+    crate::safety::directstorage_cache::persist_if_dirty();
     if mode == DiscoveryScanMode::Full {
         index::mark_full_scan_success();
         change_feed::mark_full_scan_success();
     }
     index::persist_if_dirty();
     change_feed::persist_if_dirty();
-    all_games
 }
 
 fn should_parallelize_platform_scanners(mode: DiscoveryScanMode) -> bool {
@@ -243,52 +339,157 @@ fn should_parallelize_platform_scanners(mode: DiscoveryScanMode) -> bool {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 enum ScannerTask {
     Steam,
     Epic,
+    Legendary,
     Gog,
     Ubisoft,
     Ea,
     BattleNet,
+    Riot,
     Xbox,
+    Tools,
+    /// Added through [`scanner_registry::register_scanner`].
+    Registered(RegisteredScanner),
+    UserCustomRoots,
     CommonCustomRoots,
 }
 
+impl ScannerTask {
+    /// Name shown in scan progress: the scanner's `platform_name`, except
+    /// that the two custom-root passes are told apart.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Steam => "Steam",
+            Self::Epic => "Epic Games",
+            Self::Legendary => "Legendary",
+            Self::Gog => "GOG Galaxy",
+            Self::Ubisoft => "Ubisoft Connect",
+            Self::Ea => "EA App",
+            Self::BattleNet => "Battle.net",
+            Self::Riot => "Riot Games",
+            Self::Xbox => "Xbox Game Pass",
+            Self::Tools => "Tools",
+            Self::Registered(scanner) => scanner.platform_name(),
+            Self::UserCustomRoots => "Custom Folders",
+            Self::CommonCustomRoots => "Common Game Folders",
+        }
+    }
+}
+
+impl PartialEq for ScannerTask {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Registered(a), Self::Registered(b)) => Arc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Every scanner task. Registered scanners run after the launcher
+/// scanners and before the custom roots, so a generic folder scan never
+/// claims an install an embedder's scanner reports.
 fn scanner_tasks() -> Vec<ScannerTask> {
-    vec![
+    let mut tasks = vec![
         ScannerTask::Steam,
         ScannerTask::Epic,
+        ScannerTask::Legendary,
         ScannerTask::Gog,
         ScannerTask::Ubisoft,
         ScannerTask::Ea,
         ScannerTask::BattleNet,
+        ScannerTask::Riot,
         ScannerTask::Xbox,
-        ScannerTask::CommonCustomRoots,
-    ]
+        ScannerTask::Tools,
+    ];
+    tasks.extend(
+        scanner_registry::registered_scanners(None)
+            .into_iter()
+            .map(ScannerTask::Registered),
+    );
+    tasks.extend([ScannerTask::UserCustomRoots, ScannerTask::CommonCustomRoots]);
+    tasks
+}
+
+fn scanner_tasks_for_platform(platform: Platform) -> Vec<ScannerTask> {
+    let mut tasks = built_in_scanner_tasks_for_platform(platform);
+    tasks.extend(
+        scanner_registry::registered_scanners(Some(platform))
+            .into_iter()
+            .map(ScannerTask::Registered),
+    );
+    tasks
+}
+
+fn built_in_scanner_tasks_for_platform(platform: Platform) -> Vec<ScannerTask> {
+    match platform {
+        Platform::Steam => vec![ScannerTask::Steam],
+        Platform::EpicGames => vec![ScannerTask::Epic, ScannerTask::Legendary],
+        Platform::GogGalaxy => vec![ScannerTask::Gog],
+        Platform::UbisoftConnect => vec![ScannerTask::Ubisoft],
+        Platform::EaApp => vec![ScannerTask::Ea],
+        Platform::BattleNet => vec![ScannerTask::BattleNet],
+        Platform::XboxGamePass => vec![ScannerTask::Xbox],
+        Platform::RiotGames => vec![ScannerTask::Riot],
+        Platform::Custom => vec![ScannerTask::UserCustomRoots, ScannerTask::CommonCustomRoots],
+        Platform::Application => vec![ScannerTask::Tools],
+    }
 }
 
 fn run_scanner_task(task: ScannerTask, mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    let name = task.name();
+    progress::scanner_started(name);
+    let games = run_scanner(task, mode);
+    progress::scanner_finished(name);
+    games
+}
+
+fn run_scanner(task: ScannerTask, mode: DiscoveryScanMode) -> Vec<GameInfo> {
     use crate::discovery::battlenet::BattleNetScanner;
     use crate::discovery::ea::EaScanner;
     use crate::discovery::epic::EpicScanner;
     use crate::discovery::gog::GogScanner;
+    use crate::discovery::legendary::LegendaryScanner;
+    use crate::discovery::riot::RiotScanner;
     use crate::discovery::steam::SteamScanner;
+    use crate::discovery::tools::ToolsScanner;
     use crate::discovery::ubisoft::UbisoftScanner;
     use crate::discovery::xbox::XboxScanner;
 
     match task {
         ScannerTask::Steam => collect_scanner_results(SteamScanner::new(), mode),
         ScannerTask::Epic => collect_scanner_results(EpicScanner::new(), mode),
+        ScannerTask::Legendary => collect_scanner_results(LegendaryScanner {}, mode),
         ScannerTask::Gog => collect_scanner_results(GogScanner {}, mode),
         ScannerTask::Ubisoft => collect_scanner_results(UbisoftScanner {}, mode),
         ScannerTask::Ea => collect_scanner_results(EaScanner {}, mode),
         ScannerTask::BattleNet => collect_scanner_results(BattleNetScanner {}, mode),
+        ScannerTask::Riot => collect_scanner_results(RiotScanner {}, mode),
         ScannerTask::Xbox => collect_scanner_results(XboxScanner::new(), mode),
+        ScannerTask::Tools => collect_scanner_results(ToolsScanner {}, mode),
+        ScannerTask::Registered(scanner) => collect_scanner_results(scanner, mode),
+        ScannerTask::UserCustomRoots => run_user_custom_roots(mode),
         ScannerTask::CommonCustomRoots => run_common_custom_roots(mode),
     }
 }
 
+fn run_user_custom_roots(mode: DiscoveryScanMode) -> Vec<GameInfo> {
+    use crate::discovery::custom::CustomScanner;
+
+    let roots: Vec<PathBuf> = crate::discovery::custom_roots::list()
+        .into_iter()
+        .filter(|root| root.is_dir())
+        .collect();
+    if roots.is_empty() {
+        return Vec::new();
+    }
+
+    log::info!("User custom roots: scanning {} root(s)", roots.len());
+    collect_scanner_results(CustomScanner::new(roots), mode)
+}
+
 fn run_common_custom_roots(mode: DiscoveryScanMode) -> Vec<GameInfo> {
     use crate::discovery::custom::CustomScanner;
 
@@ -407,6 +608,7 @@ pub fn scan_custom_paths_with_mode(
     cache::persist_if_dirty();
     hidden_paths::persist_if_dirty();
     install_history::persist_if_dirty();
+    crate::safety::directstorage_cache::persist_if_dirty();
     if mode == DiscoveryScanMode::Full {
         index::mark_full_scan_success();
         change_feed::mark_full_scan_success();
@@ -423,6 +625,31 @@ mod tests {
     use super::*;
     use crate::discovery::install_history;
 
+    #[test]
+    fn every_scanner_task_belongs_to_one_platform() {
+        let _guard = crate::discovery::test_sync::lock_discovery_test();
+        let platforms = [
+            Platform::Steam,
+            Platform::EpicGames,
+            Platform::GogGalaxy,
+            Platform::UbisoftConnect,
+            Platform::EaApp,
+            Platform::BattleNet,
+            Platform::XboxGamePass,
+            Platform::Custom,
+            Platform::Application,
+            Platform::RiotGames,
+        ];
+        let mut mapped: Vec<ScannerTask> = platforms
+            .into_iter()
+            .flat_map(scanner_tasks_for_platform)
+            .collect();
+        let all = scanner_tasks();
+        assert_eq!(mapped.len(), all.len());
+        mapped.retain(|task| !all.contains(task));
+        assert!(mapped.is_empty());
+    }
+
     #[test]
     fn common_custom_roots_detect_existing_games_folder() {
         let mount = TempDir::new().unwrap();
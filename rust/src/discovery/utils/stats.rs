@@ -1,10 +1,16 @@
+#[cfg(any(windows, test))]
+mod dir_batch;
+
 use std::path::Path;
 
-use walkdir::WalkDir;
+use crate::safety::traversal::TraversalPolicy;
 
 const QUICK_SCAN_MAX_DEPTH: usize = 3;
 const QUICK_SCAN_MAX_FILES: usize = 256;
 const FULL_SCAN_MAX_FILES: usize = 250_000;
+/// Encrypted or sparse files it takes to flag a game; a handful of them
+/// barely moves its savings.
+const MANY_INCOMPATIBLE_FILES: u64 = 32;
 
 /// Directory size statistics collected in a single walk.
 pub struct DirStats {
@@ -12,24 +18,56 @@ pub struct DirStats {
     pub physical_size: u64,
     pub is_compressed: bool,
     pub scan_limit_reached: bool,
+    /// At least one visited file is an online-only cloud placeholder.
+    pub has_cloud_placeholders: bool,
+    /// Visited files that are EFS-encrypted or sparse, which compression
+    /// skips.
+    pub incompatible_files: u64,
+}
+
+impl DirStats {
+    /// Whether enough files are encrypted or sparse to flag the game.
+    pub fn has_many_incompatible_files(&self) -> bool {
+        self.incompatible_files >= MANY_INCOMPATIBLE_FILES
+    }
 }
 
 /// Collect logical size, physical (compressed) size, and compression status
 /// in a single directory walk. Avoids the 3-pass pattern of calling
 /// dir_size + is_dir_compressed + dir_compressed_size separately.
-#[cfg(windows)]
+///
+/// Symlinks, junctions and mount points are not counted; see
+/// [`dir_stats_with_policy`] to follow them.
 pub fn dir_stats(path: &Path) -> DirStats {
+    dir_stats_with_policy(path, TraversalPolicy::default())
+}
+
+/// [`dir_stats`] with an explicit reparse-point policy.
+///
+/// NTFS folders are enumerated in batches (see `dir_batch`) when links are
+/// skipped; anything else, or a root that cannot be enumerated that way,
+/// falls back to a `WalkDir` walk.
+#[cfg(windows)]
+pub fn dir_stats_with_policy(path: &Path, policy: TraversalPolicy) -> DirStats {
+    if !policy.follows_reparse_points() {
+        if let Some(stats) = dir_batch::dir_stats(path, FULL_SCAN_MAX_FILES) {
+            return stats;
+        }
+    }
+    walk_dir_stats(path, policy)
+}
+
+#[cfg(windows)]
+fn walk_dir_stats(path: &Path, policy: TraversalPolicy) -> DirStats {
     let mut logical_size: u64 = 0;
     let mut physical_size: u64 = 0;
     let mut found_compressed = false;
     let mut files_seen: usize = 0;
     let mut scan_limit_reached = false;
+    let mut has_cloud_placeholders = false;
+    let mut incompatible_files: u64 = 0;
 
-    for entry in WalkDir::new(path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in policy.walk(path).filter_map(|e| e.ok()) {
         // Query metadata once and reuse (avoids double query: file_type() + metadata())
         let Ok(metadata) = entry.metadata() else {
             continue;
@@ -43,6 +81,8 @@ pub fn dir_stats(path: &Path) -> DirStats {
             break;
         }
         files_seen += 1;
+        has_cloud_placeholders |= is_cloud_placeholder_metadata(&metadata);
+        incompatible_files += u64::from(is_incompatible_metadata(&metadata));
 
         let logical = metadata.len();
         logical_size += logical;
@@ -65,17 +105,20 @@ pub fn dir_stats(path: &Path) -> DirStats {
         physical_size,
         is_compressed,
         scan_limit_reached,
+        has_cloud_placeholders,
+        incompatible_files,
     }
 }
 
 #[cfg(not(windows))]
-pub fn dir_stats(path: &Path) -> DirStats {
+pub fn dir_stats_with_policy(path: &Path, policy: TraversalPolicy) -> DirStats {
     let mut logical_size: u64 = 0;
     let mut files_seen: usize = 0;
     let mut scan_limit_reached = false;
-    for entry in WalkDir::new(path)
-        .follow_links(false)
-        .into_iter()
+    let mut has_cloud_placeholders = false;
+    let mut incompatible_files: u64 = 0;
+    for entry in policy
+        .walk(path)
         .filter_map(|entry| entry.ok())
         .filter(|entry| entry.file_type().is_file())
     {
@@ -86,6 +129,8 @@ pub fn dir_stats(path: &Path) -> DirStats {
         if let Ok(metadata) = entry.metadata() {
             logical_size = logical_size.saturating_add(metadata.len());
             files_seen += 1;
+            has_cloud_placeholders |= is_cloud_placeholder_metadata(&metadata);
+            incompatible_files += u64::from(is_incompatible_metadata(&metadata));
         }
     }
 
@@ -94,6 +139,8 @@ pub fn dir_stats(path: &Path) -> DirStats {
         physical_size: logical_size,
         is_compressed: false,
         scan_limit_reached,
+        has_cloud_placeholders,
+        incompatible_files,
     }
 }
 
@@ -102,11 +149,11 @@ pub fn dir_stats(path: &Path) -> DirStats {
 pub fn dir_stats_quick(path: &Path) -> DirStats {
     let mut logical_size: u64 = 0;
     let mut files_seen: usize = 0;
+    let mut has_cloud_placeholders = false;
+    let mut incompatible_files: u64 = 0;
 
-    for entry in WalkDir::new(path)
-        .max_depth(QUICK_SCAN_MAX_DEPTH)
-        .follow_links(false)
-        .into_iter()
+    for entry in TraversalPolicy::default()
+        .walk_to_depth(path, QUICK_SCAN_MAX_DEPTH)
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
     {
@@ -116,6 +163,8 @@ pub fn dir_stats_quick(path: &Path) -> DirStats {
         if let Ok(metadata) = entry.metadata() {
             logical_size = logical_size.saturating_add(metadata.len());
             files_seen += 1;
+            has_cloud_placeholders |= is_cloud_placeholder_metadata(&metadata);
+            incompatible_files += u64::from(is_incompatible_metadata(&metadata));
         }
     }
 
@@ -124,9 +173,35 @@ pub fn dir_stats_quick(path: &Path) -> DirStats {
         physical_size: logical_size,
         is_compressed: false,
         scan_limit_reached: false,
+        has_cloud_placeholders,
+        incompatible_files,
     }
 }
 
+/// Placeholder check from metadata a walk already holds. Only the recall
+/// attributes are visible here, which is enough for online-only files.
+#[cfg(windows)]
+fn is_cloud_placeholder_metadata(metadata: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    crate::safety::cloud::is_placeholder(metadata.file_attributes(), 0)
+}
+
+#[cfg(not(windows))]
+fn is_cloud_placeholder_metadata(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn is_incompatible_metadata(metadata: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    crate::compression::attributes::incompatible_attribute(metadata.file_attributes()).is_some()
+}
+
+#[cfg(not(windows))]
+fn is_incompatible_metadata(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +253,85 @@ mod tests {
         );
         assert!(stats.is_compressed);
     }
+
+    #[cfg(windows)]
+    #[test]
+    fn dir_stats_measures_files_beyond_max_path() {
+        use crate::compression::algorithm::CompressionAlgorithm;
+        use crate::compression::wof;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let nested = (0..12).fold(dir.path().to_path_buf(), |path, depth| {
+            path.join(format!("deeply_nested_mod_folder_{depth:02}"))
+        });
+        std::fs::create_dir_all(&nested).unwrap();
+        let path = nested.join("texture.dds");
+        std::fs::write(&path, vec![0_u8; 1024 * 1024]).unwrap();
+        assert!(path.as_os_str().len() > 260);
+        wof::wof_compress_file(&path, CompressionAlgorithm::Xpress4K).unwrap();
+
+        for stats in [
+            dir_stats(dir.path()),
+            walk_dir_stats(dir.path(), TraversalPolicy::SkipReparsePoints),
+        ] {
+            assert_eq!(stats.logical_size, 1024 * 1024);
+            assert!(stats.physical_size < stats.logical_size);
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn batched_stats_match_walk() {
+        use crate::compression::algorithm::CompressionAlgorithm;
+        use crate::compression::wof;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let nested = dir.path().join("data").join("paks");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.path().join("game.exe"), vec![1_u8; 12 * 1024]).unwrap();
+        std::fs::write(nested.join("a.pak"), vec![0_u8; 512 * 1024]).unwrap();
+        std::fs::write(nested.join("b.pak"), vec![7_u8; 3]).unwrap();
+        let _ = wof::wof_compress_file(&nested.join("a.pak"), CompressionAlgorithm::Xpress4K);
+
+        let Some(batched) = dir_batch::dir_stats(dir.path(), FULL_SCAN_MAX_FILES) else {
+            // Temp dir is not on NTFS; nothing to compare.
+            return;
+        };
+        let walked = walk_dir_stats(dir.path(), TraversalPolicy::SkipReparsePoints);
+        assert_eq!(batched.logical_size, walked.logical_size);
+        assert_eq!(batched.physical_size, walked.physical_size);
+        assert_eq!(batched.is_compressed, walked.is_compressed);
+        assert_eq!(batched.scan_limit_reached, walked.scan_limit_reached);
+
+        let limited = dir_batch::dir_stats(dir.path(), 1).unwrap();
+        assert!(limited.scan_limit_reached);
+    }
+
+    /// Timing comparison on a real library:
+    /// `COMPACT_GAMES_BENCH_DIR=D:\Games cargo test --release -- --ignored dir_stats_speedup --nocapture`
+    #[cfg(windows)]
+    #[test]
+    #[ignore]
+    fn dir_stats_speedup() {
+        let Some(root) = std::env::var_os("COMPACT_GAMES_BENCH_DIR") else {
+            return;
+        };
+        let root = std::path::PathBuf::from(root);
+
+        let started = std::time::Instant::now();
+        let walked = walk_dir_stats(&root, TraversalPolicy::SkipReparsePoints);
+        let walk_time = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let batched = dir_batch::dir_stats(&root, FULL_SCAN_MAX_FILES).expect("NTFS volume");
+        let batch_time = started.elapsed();
+
+        println!(
+            "walk: {walk_time:?} ({} bytes), batched: {batch_time:?} ({} bytes), speedup {:.1}x",
+            walked.logical_size,
+            batched.logical_size,
+            walk_time.as_secs_f64() / batch_time.as_secs_f64().max(f64::EPSILON)
+        );
+        assert_eq!(batched.logical_size, walked.logical_size);
+    }
 }
@@ -0,0 +1,376 @@
+//! Batched directory enumeration for full-scan sizing on NTFS.
+//!
+//! `GetFileInformationByHandleEx(FileFullDirectoryInfo)` is a thin wrapper
+//! over `NtQueryDirectoryFile`: one call returns sizes and attributes for
+//! every entry that fits in the buffer, straight from the directory index.
+//! Plain files therefore need no per-file open or metadata query. Only
+//! files whose attributes say the on-disk size may differ from the logical
+//! size (WOF reparse points, NTFS-compressed or sparse files) still get a
+//! `GetCompressedFileSizeW` call.
+//!
+//! Sizes come from the directory index, which NTFS may update lazily for
+//! files that are open for writing; a scan is a point-in-time view either
+//! way. Traversal matches `TraversalPolicy::SkipReparsePoints`: symlinks
+//! and junctions are not followed, unreadable and ignore-marked
+//! subdirectories are skipped, and the same file limit applies.
+
+#[cfg(windows)]
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use super::DirStats;
+#[cfg(windows)]
+use crate::safety::ignore_marker::has_ignore_marker;
+
+const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x0000_0010;
+const FILE_ATTRIBUTE_SPARSE_FILE: u32 = 0x0000_0200;
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0000_0400;
+const FILE_ATTRIBUTE_COMPRESSED: u32 = 0x0000_0800;
+/// Set on reparse tags that redirect to another path (symlinks,
+/// junctions). WOF and cloud-file tags do not have it.
+const REPARSE_TAG_NAME_SURROGATE: u32 = 0x2000_0000;
+
+/// Offsets into `FILE_FULL_DIR_INFO`.
+const NEXT_ENTRY_OFFSET: usize = 0;
+const END_OF_FILE_OFFSET: usize = 40;
+const ATTRIBUTES_OFFSET: usize = 56;
+const NAME_LENGTH_OFFSET: usize = 60;
+/// Holds the reparse tag when `FILE_ATTRIBUTE_REPARSE_POINT` is set.
+const EA_SIZE_OFFSET: usize = 64;
+const NAME_OFFSET: usize = 68;
+
+#[cfg(windows)]
+const ENUM_BUFFER_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DirRecord {
+    pub name: String,
+    pub end_of_file: u64,
+    pub attributes: u32,
+    pub reparse_tag: u32,
+}
+
+impl DirRecord {
+    fn is_dot_entry(&self) -> bool {
+        self.name == "." || self.name == ".."
+    }
+
+    fn is_directory(&self) -> bool {
+        self.attributes & FILE_ATTRIBUTE_DIRECTORY != 0
+    }
+
+    /// Symlink or junction; `WalkDir` with `follow_links(false)` neither
+    /// descends into these nor counts them as files.
+    fn is_link(&self) -> bool {
+        self.attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0
+            && self.reparse_tag & REPARSE_TAG_NAME_SURROGATE != 0
+    }
+
+    /// Whether the directory index size may not match the on-disk size.
+    fn needs_physical_query(&self) -> bool {
+        self.attributes
+            & (FILE_ATTRIBUTE_REPARSE_POINT
+                | FILE_ATTRIBUTE_COMPRESSED
+                | FILE_ATTRIBUTE_SPARSE_FILE)
+            != 0
+    }
+}
+
+/// Decode one buffer of `FILE_FULL_DIR_INFO` records.
+///
+/// Stops at the first record that does not fit, so a short or corrupt
+/// buffer yields the records before it rather than panicking.
+pub(super) fn parse_full_dir_info(buffer: &[u8]) -> Vec<DirRecord> {
+    let read_u32 = |at: usize| -> Option<u32> {
+        buffer
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let read_u64 = |at: usize| -> Option<u64> {
+        let b = buffer.get(at..at + 8)?;
+        Some(u64::from_le_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    };
+
+    let mut records = Vec::new();
+    let mut offset = 0usize;
+    while let Some(next) = read_u32(offset + NEXT_ENTRY_OFFSET) {
+        let (Some(end_of_file), Some(attributes), Some(name_len), Some(ea_size)) = (
+            read_u64(offset + END_OF_FILE_OFFSET),
+            read_u32(offset + ATTRIBUTES_OFFSET),
+            read_u32(offset + NAME_LENGTH_OFFSET),
+            read_u32(offset + EA_SIZE_OFFSET),
+        ) else {
+            break;
+        };
+        let name_start = offset + NAME_OFFSET;
+        let Some(name_bytes) = buffer.get(name_start..name_start + name_len as usize) else {
+            break;
+        };
+        let name_units: Vec<u16> = name_bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+
+        records.push(DirRecord {
+            name: String::from_utf16_lossy(&name_units),
+            end_of_file,
+            attributes,
+            reparse_tag: if attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+                ea_size
+            } else {
+                0
+            },
+        });
+
+        if next == 0 {
+            break;
+        }
+        offset += next as usize;
+    }
+    records
+}
+
+/// Full-scan stats via batched enumeration, or `None` when `path` is not
+/// on NTFS or its root cannot be enumerated, so the caller can fall back
+/// to a plain walk.
+#[cfg(windows)]
+pub(super) fn dir_stats(path: &Path, max_files: usize) -> Option<DirStats> {
+    let root = open_directory(path).ok()?;
+    if !is_ntfs(&root) {
+        return None;
+    }
+
+    let mut logical_size: u64 = 0;
+    let mut physical_size: u64 = 0;
+    let mut found_compressed = false;
+    let mut files_seen: usize = 0;
+    let mut scan_limit_reached = false;
+    let mut has_cloud_placeholders = false;
+    let mut incompatible_files: u64 = 0;
+    let mut buffer = vec![0u8; ENUM_BUFFER_BYTES];
+
+    let mut pending: Vec<(PathBuf, Option<std::fs::File>)> = vec![(path.to_path_buf(), Some(root))];
+    'dirs: while let Some((dir, handle)) = pending.pop() {
+        if has_ignore_marker(&dir) {
+            log::debug!("Skipping ignored directory {}", dir.display());
+            continue;
+        }
+        let handle = match handle {
+            Some(handle) => handle,
+            None => match open_directory(&dir) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    log::debug!("Skipping unreadable directory {}: {e}", dir.display());
+                    continue;
+                }
+            },
+        };
+
+        loop {
+            let records = match next_batch(&handle, &mut buffer) {
+                Ok(Some(records)) => records,
+                Ok(None) => break,
+                Err(e) if dir == path => {
+                    log::debug!(
+                        "Batched enumeration failed for {}; falling back: {e}",
+                        path.display()
+                    );
+                    return None;
+                }
+                Err(e) => {
+                    log::debug!("Stopped enumerating {}: {e}", dir.display());
+                    break;
+                }
+            };
+
+            for record in records {
+                if record.is_dot_entry() || record.is_link() {
+                    continue;
+                }
+                if record.is_directory() {
+                    pending.push((dir.join(&record.name), None));
+                    continue;
+                }
+                if files_seen >= max_files {
+                    scan_limit_reached = true;
+                    break 'dirs;
+                }
+                files_seen += 1;
+                has_cloud_placeholders |=
+                    crate::safety::cloud::is_placeholder(record.attributes, record.reparse_tag);
+                incompatible_files += u64::from(
+                    crate::compression::attributes::incompatible_attribute(record.attributes)
+                        .is_some(),
+                );
+
+                let logical = record.end_of_file;
+                logical_size += logical;
+                let physical = if record.needs_physical_query() {
+                    crate::compression::wof::get_physical_size(&dir.join(&record.name))
+                        .unwrap_or(logical)
+                } else {
+                    logical
+                };
+                physical_size += physical;
+
+                if !found_compressed && logical >= 4096 && physical < logical {
+                    found_compressed = true;
+                }
+            }
+        }
+    }
+
+    let is_compressed = found_compressed || (logical_size > 0 && physical_size < logical_size);
+    Some(DirStats {
+        logical_size,
+        physical_size,
+        is_compressed,
+        scan_limit_reached,
+        has_cloud_placeholders,
+        incompatible_files,
+    })
+}
+
+#[cfg(windows)]
+fn open_directory(path: &Path) -> std::io::Result<std::fs::File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    // FILE_FLAG_BACKUP_SEMANTICS is required to open a directory handle.
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)
+}
+
+#[cfg(windows)]
+fn is_ntfs(handle: &std::fs::File) -> bool {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::GetVolumeInformationByHandleW;
+
+    let mut name = [0u16; 64];
+    let ok = unsafe {
+        GetVolumeInformationByHandleW(
+            HANDLE(handle.as_raw_handle() as _),
+            None,
+            None,
+            None,
+            None,
+            Some(&mut name),
+        )
+    }
+    .is_ok();
+    let end = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+    ok && String::from_utf16_lossy(&name[..end]).eq_ignore_ascii_case("NTFS")
+}
+
+/// Next batch of records, or `None` once the directory is exhausted.
+#[cfg(windows)]
+fn next_batch(
+    handle: &std::fs::File,
+    buffer: &mut [u8],
+) -> std::io::Result<Option<Vec<DirRecord>>> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::{ERROR_NO_MORE_FILES, HANDLE};
+    use windows::Win32::Storage::FileSystem::{
+        FileFullDirectoryInfo, GetFileInformationByHandleEx,
+    };
+
+    let result = unsafe {
+        GetFileInformationByHandleEx(
+            HANDLE(handle.as_raw_handle() as _),
+            FileFullDirectoryInfo,
+            buffer.as_mut_ptr().cast(),
+            buffer.len() as u32,
+        )
+    };
+    match result {
+        Ok(()) => Ok(Some(parse_full_dir_info(buffer))),
+        Err(e) if e.code() == ERROR_NO_MORE_FILES.to_hresult() => Ok(None),
+        Err(e) => Err(std::io::Error::other(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_record(name: &str, end_of_file: u64, attributes: u32, tag: u32) -> Vec<u8> {
+        let name: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let len = (NAME_OFFSET + name.len()).div_ceil(8) * 8;
+        let mut record = vec![0u8; len];
+        record[END_OF_FILE_OFFSET..END_OF_FILE_OFFSET + 8]
+            .copy_from_slice(&end_of_file.to_le_bytes());
+        record[ATTRIBUTES_OFFSET..ATTRIBUTES_OFFSET + 4].copy_from_slice(&attributes.to_le_bytes());
+        record[NAME_LENGTH_OFFSET..NAME_LENGTH_OFFSET + 4]
+            .copy_from_slice(&(name.len() as u32).to_le_bytes());
+        record[EA_SIZE_OFFSET..EA_SIZE_OFFSET + 4].copy_from_slice(&tag.to_le_bytes());
+        record[NAME_OFFSET..NAME_OFFSET + name.len()].copy_from_slice(&name);
+        record
+    }
+
+    fn encode_batch(records: &[Vec<u8>]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        for (i, record) in records.iter().enumerate() {
+            let mut record = record.clone();
+            if i + 1 < records.len() {
+                let next = record.len() as u32;
+                record[..4].copy_from_slice(&next.to_le_bytes());
+            }
+            buffer.extend_from_slice(&record);
+        }
+        buffer
+    }
+
+    #[test]
+    fn batch_records_are_decoded_in_order() {
+        const IO_REPARSE_TAG_WOF: u32 = 0x8000_0017;
+        const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000_000C;
+        let buffer = encode_batch(&[
+            encode_record(".", 0, FILE_ATTRIBUTE_DIRECTORY, 0),
+            encode_record("game.exe", 4_096, 0x20, 0),
+            encode_record(
+                "data.pak",
+                1 << 33,
+                FILE_ATTRIBUTE_REPARSE_POINT,
+                IO_REPARSE_TAG_WOF,
+            ),
+            encode_record(
+                "Saves",
+                0,
+                FILE_ATTRIBUTE_DIRECTORY | FILE_ATTRIBUTE_REPARSE_POINT,
+                IO_REPARSE_TAG_SYMLINK,
+            ),
+        ]);
+
+        let records = parse_full_dir_info(&buffer);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, [".", "game.exe", "data.pak", "Saves"]);
+        assert!(records[0].is_dot_entry());
+        assert!(!records[1].needs_physical_query());
+        assert_eq!(records[2].end_of_file, 1 << 33);
+        assert!(records[2].needs_physical_query());
+        assert!(!records[2].is_link());
+        assert!(records[3].is_directory() && records[3].is_link());
+    }
+
+    #[test]
+    fn truncated_buffer_keeps_complete_records() {
+        let mut buffer = encode_batch(&[
+            encode_record("a.bin", 1, 0, 0),
+            encode_record("b.bin", 2, 0, 0),
+        ]);
+        buffer.truncate(buffer.len() - 4);
+        let records = parse_full_dir_info(&buffer);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name, "a.bin");
+    }
+
+    #[test]
+    fn reparse_tag_is_ignored_without_reparse_attribute() {
+        let buffer = encode_batch(&[encode_record("plain.bin", 10, 0, 0x1234)]);
+        assert_eq!(parse_full_dir_info(&buffer)[0].reparse_tag, 0);
+    }
+}
@@ -237,6 +237,7 @@ fn wire__crate__api__compression__compress_game_impl(
                 <crate::api::types::FrbCompressionAlgorithm>::sse_decode(&mut deserializer);
             let api_allow_directstorage_override = <bool>::sse_decode(&mut deserializer);
             let api_io_parallelism_override = <Option<u64>>::sse_decode(&mut deserializer);
+            let api_max_bytes_per_sec = <Option<u64>>::sse_decode(&mut deserializer);
             let api_sink = <StreamSink<
                 crate::api::types::FrbCompressionProgress,
                 flutter_rust_bridge::for_generated::SseCodec,
@@ -250,6 +251,7 @@ fn wire__crate__api__compression__compress_game_impl(
                         api_algorithm,
                         api_allow_directstorage_override,
                         api_io_parallelism_override,
+                        api_max_bytes_per_sec,
                         api_sink,
                     )?;
                     Ok(output_ok)
@@ -1567,6 +1569,22 @@ impl SseDecode for crate::api::automation_types::FrbAutomationConfig {
             <crate::api::types::FrbCompressionAlgorithm>::sse_decode(deserializer);
         let mut var_allowDirectstorageOverride = <bool>::sse_decode(deserializer);
         let mut var_ioParallelismOverride = <Option<u64>>::sse_decode(deserializer);
+        let mut var_maxBytesPerSec = <Option<u64>>::sse_decode(deserializer);
+        let mut var_maxConcurrentJobs = <Option<u32>>::sse_decode(deserializer);
+        let mut var_blockingProcesses = <Vec<String>>::sse_decode(deserializer);
+        let mut var_includeRemovableDrives = <bool>::sse_decode(deserializer);
+        let mut var_allowAnticheatCompression = <bool>::sse_decode(deserializer);
+        let mut var_watcherIgnoredDirs = <Vec<String>>::sse_decode(deserializer);
+        let mut var_watcherIgnoredExtensions = <Vec<String>>::sse_decode(deserializer);
+        let mut var_watcherTempFilePatterns = <Vec<String>>::sse_decode(deserializer);
+        let mut var_watcherMaxEventDepth = <Option<u32>>::sse_decode(deserializer);
+        let mut var_dryRun = <bool>::sse_decode(deserializer);
+        let mut var_inputIdleSeconds = <Option<u64>>::sse_decode(deserializer);
+        let mut var_idlePolicy =
+            <crate::api::automation_types::FrbIdlePolicy>::sse_decode(deserializer);
+        let mut var_ignoreFocusAssist = <bool>::sse_decode(deserializer);
+        let mut var_pauseWhenThermallyConstrained = <bool>::sse_decode(deserializer);
+        let mut var_maintenanceWakeMinuteOfDay = <Option<u32>>::sse_decode(deserializer);
         return crate::api::automation_types::FrbAutomationConfig {
             cpu_threshold_percent: var_cpuThresholdPercent,
             idle_duration_seconds: var_idleDurationSeconds,
@@ -1576,6 +1594,21 @@ impl SseDecode for crate::api::automation_types::FrbAutomationConfig {
             algorithm: var_algorithm,
             allow_directstorage_override: var_allowDirectstorageOverride,
             io_parallelism_override: var_ioParallelismOverride,
+            max_bytes_per_sec: var_maxBytesPerSec,
+            max_concurrent_jobs: var_maxConcurrentJobs,
+            blocking_processes: var_blockingProcesses,
+            include_removable_drives: var_includeRemovableDrives,
+            allow_anticheat_compression: var_allowAnticheatCompression,
+            watcher_ignored_dirs: var_watcherIgnoredDirs,
+            watcher_ignored_extensions: var_watcherIgnoredExtensions,
+            watcher_temp_file_patterns: var_watcherTempFilePatterns,
+            watcher_max_event_depth: var_watcherMaxEventDepth,
+            dry_run: var_dryRun,
+            input_idle_seconds: var_inputIdleSeconds,
+            idle_policy: var_idlePolicy,
+            ignore_focus_assist: var_ignoreFocusAssist,
+            pause_when_thermally_constrained: var_pauseWhenThermallyConstrained,
+            maintenance_wake_minute_of_day: var_maintenanceWakeMinuteOfDay,
         };
     }
 }
@@ -1628,6 +1661,11 @@ impl SseDecode for crate::api::automation_types::FrbAutomationJob {
         let mut var_queuedAtMs = <i64>::sse_decode(deserializer);
         let mut var_startedAtMs = <Option<i64>>::sse_decode(deserializer);
         let mut var_error = <Option<String>>::sse_decode(deserializer);
+        let mut var_simulated = <bool>::sse_decode(deserializer);
+        let mut var_estimatedDurationMs = <Option<u64>>::sse_decode(deserializer);
+        let mut var_queuePosition = <Option<u32>>::sse_decode(deserializer);
+        let mut var_progress =
+            <Option<crate::api::types::FrbCompressionProgress>>::sse_decode(deserializer);
         return crate::api::automation_types::FrbAutomationJob {
             game_path: var_gamePath,
             game_name: var_gameName,
@@ -1636,6 +1674,10 @@ impl SseDecode for crate::api::automation_types::FrbAutomationJob {
             queued_at_ms: var_queuedAtMs,
             started_at_ms: var_startedAtMs,
             error: var_error,
+            simulated: var_simulated,
+            estimated_duration_ms: var_estimatedDurationMs,
+            queue_position: var_queuePosition,
+            progress: var_progress,
         };
     }
 }
@@ -1729,6 +1771,56 @@ impl SseDecode for crate::api::types::FrbCompressionError {
             9 => {
                 return crate::api::types::FrbCompressionError::Cancelled;
             }
+            10 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::ProtectedPackage { path: var_path };
+            }
+            11 => {
+                let mut var_requiredBytes = <u64>::sse_decode(deserializer);
+                let mut var_availableBytes = <u64>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::InsufficientSpace {
+                    required_bytes: var_requiredBytes,
+                    available_bytes: var_availableBytes,
+                };
+            }
+            12 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_filesystem = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::UnsupportedFilesystem {
+                    path: var_path,
+                    filesystem: var_filesystem,
+                };
+            }
+            13 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::CompressionNotSupportedOnVolume {
+                    path: var_path,
+                };
+            }
+            14 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::FileTooSmallForWof {
+                    path: var_path,
+                };
+            }
+            15 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::EncryptedFile { path: var_path };
+            }
+            16 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::SparseFile { path: var_path };
+            }
+            17 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::IntegrityStreamFile {
+                    path: var_path,
+                };
+            }
+            18 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbCompressionError::GameFolderLocked { path: var_path };
+            }
             _ => {
                 unimplemented!("");
             }
@@ -1750,6 +1842,9 @@ impl SseDecode for crate::api::types::FrbCompressionEstimate {
         let mut var_adaptiveApplied = <bool>::sse_decode(deserializer);
         let mut var_communitySamples = <Option<u32>>::sse_decode(deserializer);
         let mut var_communityLookupPending = <bool>::sse_decode(deserializer);
+        let mut var_estimatedSavedLowBytes = <u64>::sse_decode(deserializer);
+        let mut var_estimatedSavedHighBytes = <u64>::sse_decode(deserializer);
+        let mut var_confidence = <f64>::sse_decode(deserializer);
         return crate::api::types::FrbCompressionEstimate {
             scanned_files: var_scannedFiles,
             sampled_bytes: var_sampledBytes,
@@ -1761,6 +1856,9 @@ impl SseDecode for crate::api::types::FrbCompressionEstimate {
             adaptive_applied: var_adaptiveApplied,
             community_samples: var_communitySamples,
             community_lookup_pending: var_communityLookupPending,
+            estimated_saved_low_bytes: var_estimatedSavedLowBytes,
+            estimated_saved_high_bytes: var_estimatedSavedHighBytes,
+            confidence: var_confidence,
         };
     }
 }
@@ -1772,6 +1870,7 @@ impl SseDecode for crate::api::types::FrbCompressionEstimateSource {
         return match inner {
             0 => crate::api::types::FrbCompressionEstimateSource::Heuristic,
             1 => crate::api::types::FrbCompressionEstimateSource::CommunityDb,
+            2 => crate::api::types::FrbCompressionEstimateSource::Sampled,
             _ => unreachable!(
                 "Invalid variant for FrbCompressionEstimateSource: {}",
                 inner
@@ -1790,6 +1889,7 @@ impl SseDecode for crate::api::types::FrbCompressionProgress {
         let mut var_bytesCompressed = <u64>::sse_decode(deserializer);
         let mut var_bytesSaved = <u64>::sse_decode(deserializer);
         let mut var_estimatedTimeRemainingMs = <Option<i64>>::sse_decode(deserializer);
+        let mut var_throughputBytesPerSec = <u64>::sse_decode(deserializer);
         let mut var_isComplete = <bool>::sse_decode(deserializer);
         return crate::api::types::FrbCompressionProgress {
             game_name: var_gameName,
@@ -1799,6 +1899,7 @@ impl SseDecode for crate::api::types::FrbCompressionProgress {
             bytes_compressed: var_bytesCompressed,
             bytes_saved: var_bytesSaved,
             estimated_time_remaining_ms: var_estimatedTimeRemainingMs,
+            throughput_bytes_per_sec: var_throughputBytesPerSec,
             is_complete: var_isComplete,
         };
     }
@@ -1811,12 +1912,54 @@ impl SseDecode for crate::api::types::FrbCompressionStats {
         let mut var_compressedBytes = <u64>::sse_decode(deserializer);
         let mut var_filesProcessed = <u64>::sse_decode(deserializer);
         let mut var_filesSkipped = <u64>::sse_decode(deserializer);
+        let mut var_filesSkippedCloud = <u64>::sse_decode(deserializer);
+        let mut var_filesSkippedPermission = <u64>::sse_decode(deserializer);
+        let mut var_filesSkippedEncrypted = <u64>::sse_decode(deserializer);
+        let mut var_filesSkippedSparse = <u64>::sse_decode(deserializer);
+        let mut var_skippedEncryptedPaths = <Vec<String>>::sse_decode(deserializer);
+        let mut var_skippedSparsePaths = <Vec<String>>::sse_decode(deserializer);
+        let mut var_alternateStreamBytes = <u64>::sse_decode(deserializer);
+        let mut var_hasSignificantAlternateStreams = <bool>::sse_decode(deserializer);
+        let mut var_filesAlreadyCompressed = <u64>::sse_decode(deserializer);
+        let mut var_bytesAlreadyCompressed = <u64>::sse_decode(deserializer);
+        let mut var_bytesSavedThisRun = <u64>::sse_decode(deserializer);
+        let mut var_wasCancelled = <bool>::sse_decode(deserializer);
         let mut var_durationMs = <u64>::sse_decode(deserializer);
         return crate::api::types::FrbCompressionStats {
             original_bytes: var_originalBytes,
             compressed_bytes: var_compressedBytes,
             files_processed: var_filesProcessed,
             files_skipped: var_filesSkipped,
+            files_skipped_cloud: var_filesSkippedCloud,
+            files_skipped_permission: var_filesSkippedPermission,
+            files_skipped_encrypted: var_filesSkippedEncrypted,
+            files_skipped_sparse: var_filesSkippedSparse,
+            skipped_encrypted_paths: var_skippedEncryptedPaths,
+            skipped_sparse_paths: var_skippedSparsePaths,
+            alternate_stream_bytes: var_alternateStreamBytes,
+            has_significant_alternate_streams: var_hasSignificantAlternateStreams,
+            files_already_compressed: var_filesAlreadyCompressed,
+            bytes_already_compressed: var_bytesAlreadyCompressed,
+            bytes_saved_this_run: var_bytesSavedThisRun,
+            was_cancelled: var_wasCancelled,
+            duration_ms: var_durationMs,
+        };
+    }
+}
+
+impl SseDecode for crate::api::types::FrbDecompressionStats {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_filesDecompressed = <u64>::sse_decode(deserializer);
+        let mut var_bytesRestoredLogical = <u64>::sse_decode(deserializer);
+        let mut var_bytesGrownPhysical = <u64>::sse_decode(deserializer);
+        let mut var_wasCancelled = <bool>::sse_decode(deserializer);
+        let mut var_durationMs = <u64>::sse_decode(deserializer);
+        return crate::api::types::FrbDecompressionStats {
+            files_decompressed: var_filesDecompressed,
+            bytes_restored_logical: var_bytesRestoredLogical,
+            bytes_grown_physical: var_bytesGrownPhysical,
+            was_cancelled: var_wasCancelled,
             duration_ms: var_durationMs,
         };
     }
@@ -1847,6 +1990,14 @@ impl SseDecode for crate::api::types::FrbDiscoveryError {
                     message: var_message,
                 };
             }
+            3 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::api::types::FrbDiscoveryError::LibraryFileFailed {
+                    path: var_path,
+                    message: var_message,
+                };
+            }
             _ => {
                 unimplemented!("");
             }
@@ -1854,6 +2005,20 @@ impl SseDecode for crate::api::types::FrbDiscoveryError {
     }
 }
 
+impl SseDecode for crate::api::types::FrbDriveType {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::api::types::FrbDriveType::Fixed,
+            1 => crate::api::types::FrbDriveType::Removable,
+            2 => crate::api::types::FrbDriveType::Network,
+            3 => crate::api::types::FrbDriveType::Unknown,
+            _ => unreachable!("Invalid variant for FrbDriveType: {}", inner),
+        };
+    }
+}
+
 impl SseDecode for crate::api::types::FrbEstimateContext {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -1879,9 +2044,19 @@ impl SseDecode for crate::api::types::FrbGameInfo {
         let mut var_isCompressed = <bool>::sse_decode(deserializer);
         let mut var_isDirectstorage = <bool>::sse_decode(deserializer);
         let mut var_isUnsupported = <bool>::sse_decode(deserializer);
+        let mut var_isProtectedPackage = <bool>::sse_decode(deserializer);
+        let mut var_isUnsupportedFilesystem = <bool>::sse_decode(deserializer);
+        let mut var_usesKernelAnticheat = <bool>::sse_decode(deserializer);
+        let mut var_driveType = <crate::api::types::FrbDriveType>::sse_decode(deserializer);
+        let mut var_hasCloudPlaceholders = <bool>::sse_decode(deserializer);
         let mut var_excluded = <bool>::sse_decode(deserializer);
         let mut var_steamAppId = <Option<u32>>::sse_decode(deserializer);
         let mut var_lastPlayed = <Option<i64>>::sse_decode(deserializer);
+        let mut var_lastCompressed = <Option<i64>>::sse_decode(deserializer);
+        let mut var_playtimeMinutes = <Option<u64>>::sse_decode(deserializer);
+        let mut var_epicCatalogItemId = <Option<String>>::sse_decode(deserializer);
+        let mut var_artworkPath = <Option<String>>::sse_decode(deserializer);
+        let mut var_hasEncryptedOrSparseFiles = <bool>::sse_decode(deserializer);
         return crate::api::types::FrbGameInfo {
             name: var_name,
             path: var_path,
@@ -1891,9 +2066,31 @@ impl SseDecode for crate::api::types::FrbGameInfo {
             is_compressed: var_isCompressed,
             is_directstorage: var_isDirectstorage,
             is_unsupported: var_isUnsupported,
+            is_protected_package: var_isProtectedPackage,
+            is_unsupported_filesystem: var_isUnsupportedFilesystem,
+            uses_kernel_anticheat: var_usesKernelAnticheat,
+            drive_type: var_driveType,
+            has_cloud_placeholders: var_hasCloudPlaceholders,
             excluded: var_excluded,
             steam_app_id: var_steamAppId,
             last_played: var_lastPlayed,
+            last_compressed: var_lastCompressed,
+            playtime_minutes: var_playtimeMinutes,
+            epic_catalog_item_id: var_epicCatalogItemId,
+            artwork_path: var_artworkPath,
+            has_encrypted_or_sparse_files: var_hasEncryptedOrSparseFiles,
+        };
+    }
+}
+
+impl SseDecode for crate::api::types::FrbLibraryExportFormat {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::api::types::FrbLibraryExportFormat::Json,
+            1 => crate::api::types::FrbLibraryExportFormat::Csv,
+            _ => unreachable!("Invalid variant for FrbLibraryExportFormat: {}", inner),
         };
     }
 }
@@ -1912,11 +2109,44 @@ impl SseDecode for crate::api::types::FrbPlatform {
             6 => crate::api::types::FrbPlatform::XboxGamePass,
             7 => crate::api::types::FrbPlatform::Custom,
             8 => crate::api::types::FrbPlatform::Application,
+            9 => crate::api::types::FrbPlatform::RiotGames,
             _ => unreachable!("Invalid variant for FrbPlatform: {}", inner),
         };
     }
 }
 
+impl SseDecode for crate::api::automation_types::FrbIdlePolicy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::api::automation_types::FrbIdlePolicy::All,
+            1 => crate::api::automation_types::FrbIdlePolicy::Any,
+            _ => unreachable!("Invalid variant for FrbIdlePolicy: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::api::automation_types::FrbPendingSettle {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_gamePath = <String>::sse_decode(deserializer);
+        let mut var_gameName = <Option<String>>::sse_decode(deserializer);
+        let mut var_eventCount = <u32>::sse_decode(deserializer);
+        let mut var_burstMs = <u64>::sse_decode(deserializer);
+        let mut var_cooldownMs = <u64>::sse_decode(deserializer);
+        let mut var_remainingMs = <u64>::sse_decode(deserializer);
+        return crate::api::automation_types::FrbPendingSettle {
+            game_path: var_gamePath,
+            game_name: var_gameName,
+            event_count: var_eventCount,
+            burst_ms: var_burstMs,
+            cooldown_ms: var_cooldownMs,
+            remaining_ms: var_remainingMs,
+        };
+    }
+}
+
 impl SseDecode for crate::api::automation_types::FrbSchedulerState {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -1929,6 +2159,7 @@ impl SseDecode for crate::api::automation_types::FrbSchedulerState {
             4 => crate::api::automation_types::FrbSchedulerState::Compressing,
             5 => crate::api::automation_types::FrbSchedulerState::Paused,
             6 => crate::api::automation_types::FrbSchedulerState::Backoff,
+            7 => crate::api::automation_types::FrbSchedulerState::PausedByUser,
             _ => unreachable!("Invalid variant for FrbSchedulerState: {}", inner),
         };
     }
@@ -1967,11 +2198,24 @@ impl SseDecode for crate::api::automation_types::FrbWatcherDiagnostics {
         let mut var_watchedPathCount = <u32>::sse_decode(deserializer);
         let mut var_queueDepth = <u32>::sse_decode(deserializer);
         let mut var_lastError = <Option<String>>::sse_decode(deserializer);
+        let mut var_filteredNoiseEvents = <u64>::sse_decode(deserializer);
+        let mut var_filteredUserStateEvents = <u64>::sse_decode(deserializer);
+        let mut var_filteredIgnoreMarkerEvents = <u64>::sse_decode(deserializer);
+        let mut var_filteredTooDeepEvents = <u64>::sse_decode(deserializer);
+        let mut var_filteredSelfCompressionEvents = <u64>::sse_decode(deserializer);
+        let mut var_pendingSettles =
+            <Vec<crate::api::automation_types::FrbPendingSettle>>::sse_decode(deserializer);
         return crate::api::automation_types::FrbWatcherDiagnostics {
             is_watching: var_isWatching,
             watched_path_count: var_watchedPathCount,
             queue_depth: var_queueDepth,
             last_error: var_lastError,
+            filtered_noise_events: var_filteredNoiseEvents,
+            filtered_user_state_events: var_filteredUserStateEvents,
+            filtered_ignore_marker_events: var_filteredIgnoreMarkerEvents,
+            filtered_too_deep_events: var_filteredTooDeepEvents,
+            filtered_self_compression_events: var_filteredSelfCompressionEvents,
+            pending_settles: var_pendingSettles,
         };
     }
 }
@@ -2005,6 +2249,18 @@ impl SseDecode for crate::api::automation_types::FrbWatcherEvent {
                     game_name: var_gameName,
                 };
             }
+            3 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::automation_types::FrbWatcherEvent::DriveOffline {
+                    path: var_path,
+                };
+            }
+            4 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                return crate::api::automation_types::FrbWatcherEvent::DriveOnline {
+                    path: var_path,
+                };
+            }
             _ => {
                 unimplemented!("");
             }
@@ -2050,6 +2306,18 @@ impl SseDecode for Vec<crate::api::automation_types::FrbAutomationJob> {
     }
 }
 
+impl SseDecode for Vec<crate::api::automation_types::FrbPendingSettle> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<crate::api::automation_types::FrbPendingSettle>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
 impl SseDecode for Vec<crate::api::types::FrbGameInfo> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -2401,6 +2669,27 @@ impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbAutomati
                 .into_into_dart()
                 .into_dart(),
             self.io_parallelism_override.into_into_dart().into_dart(),
+            self.max_bytes_per_sec.into_into_dart().into_dart(),
+            self.max_concurrent_jobs.into_into_dart().into_dart(),
+            self.blocking_processes.into_into_dart().into_dart(),
+            self.include_removable_drives.into_into_dart().into_dart(),
+            self.allow_anticheat_compression
+                .into_into_dart()
+                .into_dart(),
+            self.watcher_ignored_dirs.into_into_dart().into_dart(),
+            self.watcher_ignored_extensions.into_into_dart().into_dart(),
+            self.watcher_temp_file_patterns.into_into_dart().into_dart(),
+            self.watcher_max_event_depth.into_into_dart().into_dart(),
+            self.dry_run.into_into_dart().into_dart(),
+            self.input_idle_seconds.into_into_dart().into_dart(),
+            self.idle_policy.into_into_dart().into_dart(),
+            self.ignore_focus_assist.into_into_dart().into_dart(),
+            self.pause_when_thermally_constrained
+                .into_into_dart()
+                .into_dart(),
+            self.maintenance_wake_minute_of_day
+                .into_into_dart()
+                .into_dart(),
         ]
         .into_dart()
     }
@@ -2463,6 +2752,10 @@ impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbAutomati
             self.queued_at_ms.into_into_dart().into_dart(),
             self.started_at_ms.into_into_dart().into_dart(),
             self.error.into_into_dart().into_dart(),
+            self.simulated.into_into_dart().into_dart(),
+            self.estimated_duration_ms.into_into_dart().into_dart(),
+            self.queue_position.into_into_dart().into_dart(),
+            self.progress.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -2577,6 +2870,42 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbCompressionError {
                 [8.into_dart(), message.into_into_dart().into_dart()].into_dart()
             }
             crate::api::types::FrbCompressionError::Cancelled => [9.into_dart()].into_dart(),
+            crate::api::types::FrbCompressionError::ProtectedPackage { path } => {
+                [10.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::InsufficientSpace {
+                required_bytes,
+                available_bytes,
+            } => [
+                11.into_dart(),
+                required_bytes.into_into_dart().into_dart(),
+                available_bytes.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::types::FrbCompressionError::UnsupportedFilesystem { path, filesystem } => [
+                12.into_dart(),
+                path.into_into_dart().into_dart(),
+                filesystem.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::api::types::FrbCompressionError::CompressionNotSupportedOnVolume { path } => {
+                [13.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::FileTooSmallForWof { path } => {
+                [14.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::EncryptedFile { path } => {
+                [15.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::SparseFile { path } => {
+                [16.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::IntegrityStreamFile { path } => {
+                [17.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::types::FrbCompressionError::GameFolderLocked { path } => {
+                [18.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
             _ => {
                 unimplemented!("");
             }
@@ -2608,6 +2937,9 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbCompressionEstimate
             self.adaptive_applied.into_into_dart().into_dart(),
             self.community_samples.into_into_dart().into_dart(),
             self.community_lookup_pending.into_into_dart().into_dart(),
+            self.estimated_saved_low_bytes.into_into_dart().into_dart(),
+            self.estimated_saved_high_bytes.into_into_dart().into_dart(),
+            self.confidence.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -2629,6 +2961,7 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbCompressionEstimate
         match self {
             Self::Heuristic => 0.into_dart(),
             Self::CommunityDb => 1.into_dart(),
+            Self::Sampled => 2.into_dart(),
             _ => unreachable!(),
         }
     }
@@ -2657,6 +2990,7 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbCompressionProgress
             self.estimated_time_remaining_ms
                 .into_into_dart()
                 .into_dart(),
+            self.throughput_bytes_per_sec.into_into_dart().into_dart(),
             self.is_complete.into_into_dart().into_dart(),
         ]
         .into_dart()
@@ -2681,6 +3015,20 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbCompressionStats {
             self.compressed_bytes.into_into_dart().into_dart(),
             self.files_processed.into_into_dart().into_dart(),
             self.files_skipped.into_into_dart().into_dart(),
+            self.files_skipped_cloud.into_into_dart().into_dart(),
+            self.files_skipped_permission.into_into_dart().into_dart(),
+            self.files_skipped_encrypted.into_into_dart().into_dart(),
+            self.files_skipped_sparse.into_into_dart().into_dart(),
+            self.skipped_encrypted_paths.into_into_dart().into_dart(),
+            self.skipped_sparse_paths.into_into_dart().into_dart(),
+            self.alternate_stream_bytes.into_into_dart().into_dart(),
+            self.has_significant_alternate_streams
+                .into_into_dart()
+                .into_dart(),
+            self.files_already_compressed.into_into_dart().into_dart(),
+            self.bytes_already_compressed.into_into_dart().into_dart(),
+            self.bytes_saved_this_run.into_into_dart().into_dart(),
+            self.was_cancelled.into_into_dart().into_dart(),
             self.duration_ms.into_into_dart().into_dart(),
         ]
         .into_dart()
@@ -2698,6 +3046,30 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::types::FrbCompressionStats>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::types::FrbDecompressionStats {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.files_decompressed.into_into_dart().into_dart(),
+            self.bytes_restored_logical.into_into_dart().into_dart(),
+            self.bytes_grown_physical.into_into_dart().into_dart(),
+            self.was_cancelled.into_into_dart().into_dart(),
+            self.duration_ms.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::types::FrbDecompressionStats
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::types::FrbDecompressionStats>
+    for crate::api::types::FrbDecompressionStats
+{
+    fn into_into_dart(self) -> crate::api::types::FrbDecompressionStats {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::api::types::FrbDiscoveryError {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         match self {
@@ -2713,6 +3085,12 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbDiscoveryError {
             crate::api::types::FrbDiscoveryError::InvalidPath { message } => {
                 [2.into_dart(), message.into_into_dart().into_dart()].into_dart()
             }
+            crate::api::types::FrbDiscoveryError::LibraryFileFailed { path, message } => [
+                3.into_dart(),
+                path.into_into_dart().into_dart(),
+                message.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
             _ => {
                 unimplemented!("");
             }
@@ -2731,6 +3109,29 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::types::FrbDiscoveryError>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::types::FrbDriveType {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Fixed => 0.into_dart(),
+            Self::Removable => 1.into_dart(),
+            Self::Network => 2.into_dart(),
+            Self::Unknown => 3.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::types::FrbDriveType
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::types::FrbDriveType>
+    for crate::api::types::FrbDriveType
+{
+    fn into_into_dart(self) -> crate::api::types::FrbDriveType {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::api::types::FrbEstimateContext {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         [
@@ -2764,9 +3165,21 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbGameInfo {
             self.is_compressed.into_into_dart().into_dart(),
             self.is_directstorage.into_into_dart().into_dart(),
             self.is_unsupported.into_into_dart().into_dart(),
+            self.is_protected_package.into_into_dart().into_dart(),
+            self.is_unsupported_filesystem.into_into_dart().into_dart(),
+            self.uses_kernel_anticheat.into_into_dart().into_dart(),
+            self.drive_type.into_into_dart().into_dart(),
+            self.has_cloud_placeholders.into_into_dart().into_dart(),
             self.excluded.into_into_dart().into_dart(),
             self.steam_app_id.into_into_dart().into_dart(),
             self.last_played.into_into_dart().into_dart(),
+            self.last_compressed.into_into_dart().into_dart(),
+            self.playtime_minutes.into_into_dart().into_dart(),
+            self.epic_catalog_item_id.into_into_dart().into_dart(),
+            self.artwork_path.into_into_dart().into_dart(),
+            self.has_encrypted_or_sparse_files
+                .into_into_dart()
+                .into_dart(),
         ]
         .into_dart()
     }
@@ -2795,6 +3208,7 @@ impl flutter_rust_bridge::IntoDart for crate::api::types::FrbPlatform {
             Self::XboxGamePass => 6.into_dart(),
             Self::Custom => 7.into_dart(),
             Self::Application => 8.into_dart(),
+            Self::RiotGames => 9.into_dart(),
             _ => unreachable!(),
         }
     }
@@ -2811,6 +3225,52 @@ impl flutter_rust_bridge::IntoIntoDart<crate::api::types::FrbPlatform>
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbIdlePolicy {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::All => 0.into_dart(),
+            Self::Any => 1.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::automation_types::FrbIdlePolicy
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::automation_types::FrbIdlePolicy>
+    for crate::api::automation_types::FrbIdlePolicy
+{
+    fn into_into_dart(self) -> crate::api::automation_types::FrbIdlePolicy {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbPendingSettle {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.game_path.into_into_dart().into_dart(),
+            self.game_name.into_into_dart().into_dart(),
+            self.event_count.into_into_dart().into_dart(),
+            self.burst_ms.into_into_dart().into_dart(),
+            self.cooldown_ms.into_into_dart().into_dart(),
+            self.remaining_ms.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::api::automation_types::FrbPendingSettle
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::api::automation_types::FrbPendingSettle>
+    for crate::api::automation_types::FrbPendingSettle
+{
+    fn into_into_dart(self) -> crate::api::automation_types::FrbPendingSettle {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
 impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbSchedulerState {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         match self {
@@ -2821,6 +3281,7 @@ impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbSchedule
             Self::Compressing => 4.into_dart(),
             Self::Paused => 5.into_dart(),
             Self::Backoff => 6.into_dart(),
+            Self::PausedByUser => 7.into_dart(),
             _ => unreachable!(),
         }
     }
@@ -2874,6 +3335,16 @@ impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbWatcherD
             self.watched_path_count.into_into_dart().into_dart(),
             self.queue_depth.into_into_dart().into_dart(),
             self.last_error.into_into_dart().into_dart(),
+            self.filtered_noise_events.into_into_dart().into_dart(),
+            self.filtered_user_state_events.into_into_dart().into_dart(),
+            self.filtered_ignore_marker_events
+                .into_into_dart()
+                .into_dart(),
+            self.filtered_too_deep_events.into_into_dart().into_dart(),
+            self.filtered_self_compression_events
+                .into_into_dart()
+                .into_dart(),
+            self.pending_settles.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -2911,6 +3382,12 @@ impl flutter_rust_bridge::IntoDart for crate::api::automation_types::FrbWatcherE
                 game_name.into_into_dart().into_dart(),
             ]
             .into_dart(),
+            crate::api::automation_types::FrbWatcherEvent::DriveOffline { path } => {
+                [3.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
+            crate::api::automation_types::FrbWatcherEvent::DriveOnline { path } => {
+                [4.into_dart(), path.into_into_dart().into_dart()].into_dart()
+            }
             _ => {
                 unimplemented!("");
             }
@@ -3079,6 +3556,21 @@ impl SseEncode for crate::api::automation_types::FrbAutomationConfig {
         <crate::api::types::FrbCompressionAlgorithm>::sse_encode(self.algorithm, serializer);
         <bool>::sse_encode(self.allow_directstorage_override, serializer);
         <Option<u64>>::sse_encode(self.io_parallelism_override, serializer);
+        <Option<u64>>::sse_encode(self.max_bytes_per_sec, serializer);
+        <Option<u32>>::sse_encode(self.max_concurrent_jobs, serializer);
+        <Vec<String>>::sse_encode(self.blocking_processes, serializer);
+        <bool>::sse_encode(self.include_removable_drives, serializer);
+        <bool>::sse_encode(self.allow_anticheat_compression, serializer);
+        <Vec<String>>::sse_encode(self.watcher_ignored_dirs, serializer);
+        <Vec<String>>::sse_encode(self.watcher_ignored_extensions, serializer);
+        <Vec<String>>::sse_encode(self.watcher_temp_file_patterns, serializer);
+        <Option<u32>>::sse_encode(self.watcher_max_event_depth, serializer);
+        <bool>::sse_encode(self.dry_run, serializer);
+        <Option<u64>>::sse_encode(self.input_idle_seconds, serializer);
+        <crate::api::automation_types::FrbIdlePolicy>::sse_encode(self.idle_policy, serializer);
+        <bool>::sse_encode(self.ignore_focus_assist, serializer);
+        <bool>::sse_encode(self.pause_when_thermally_constrained, serializer);
+        <Option<u32>>::sse_encode(self.maintenance_wake_minute_of_day, serializer);
     }
 }
 
@@ -3121,6 +3613,10 @@ impl SseEncode for crate::api::automation_types::FrbAutomationJob {
         <i64>::sse_encode(self.queued_at_ms, serializer);
         <Option<i64>>::sse_encode(self.started_at_ms, serializer);
         <Option<String>>::sse_encode(self.error, serializer);
+        <bool>::sse_encode(self.simulated, serializer);
+        <Option<u64>>::sse_encode(self.estimated_duration_ms, serializer);
+        <Option<u32>>::sse_encode(self.queue_position, serializer);
+        <Option<crate::api::types::FrbCompressionProgress>>::sse_encode(self.progress, serializer);
     }
 }
 
@@ -3162,6 +3658,68 @@ impl SseEncode for crate::api::automation_types::FrbAutomationJobStatus {
     }
 }
 
+impl SseEncode for crate::api::automation_types::FrbAutomationNotification {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::api::automation_types::FrbNotificationKind>::sse_encode(self.kind, serializer);
+        <crate::api::automation_types::FrbNotificationSeverity>::sse_encode(
+            self.severity,
+            serializer,
+        );
+        <String>::sse_encode(self.title, serializer);
+        <String>::sse_encode(self.body, serializer);
+        <crate::api::automation_types::FrbSuggestedAction>::sse_encode(
+            self.suggested_action,
+            serializer,
+        );
+        <Option<String>>::sse_encode(self.game_path, serializer);
+        <Option<u64>>::sse_encode(self.bytes_saved, serializer);
+        <i64>::sse_encode(self.timestamp_ms, serializer);
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbAutomationOverallProgress {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.games_total, serializer);
+        <u32>::sse_encode(self.games_finished, serializer);
+        <u64>::sse_encode(self.bytes_total, serializer);
+        <u64>::sse_encode(self.bytes_processed, serializer);
+        <Vec<String>>::sse_encode(self.current_games, serializer);
+        <bool>::sse_encode(self.is_complete, serializer);
+        <u64>::sse_encode(self.estimated_remaining_ms, serializer);
+    }
+}
+
+impl SseEncode for crate::api::logging::FrbLogEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i64>::sse_encode(self.timestamp_ms, serializer);
+        <crate::api::logging::FrbLogLevel>::sse_encode(self.level, serializer);
+        <String>::sse_encode(self.target, serializer);
+        <String>::sse_encode(self.message, serializer);
+    }
+}
+
+impl SseEncode for crate::api::logging::FrbLogLevel {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::logging::FrbLogLevel::Error => 0,
+                crate::api::logging::FrbLogLevel::Warn => 1,
+                crate::api::logging::FrbLogLevel::Info => 2,
+                crate::api::logging::FrbLogLevel::Debug => 3,
+                crate::api::logging::FrbLogLevel::Trace => 4,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
 impl SseEncode for crate::api::types::FrbCompressionAlgorithm {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -3220,6 +3778,47 @@ impl SseEncode for crate::api::types::FrbCompressionError {
             crate::api::types::FrbCompressionError::Cancelled => {
                 <i32>::sse_encode(9, serializer);
             }
+            crate::api::types::FrbCompressionError::ProtectedPackage { path } => {
+                <i32>::sse_encode(10, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::InsufficientSpace {
+                required_bytes,
+                available_bytes,
+            } => {
+                <i32>::sse_encode(11, serializer);
+                <u64>::sse_encode(required_bytes, serializer);
+                <u64>::sse_encode(available_bytes, serializer);
+            }
+            crate::api::types::FrbCompressionError::UnsupportedFilesystem { path, filesystem } => {
+                <i32>::sse_encode(12, serializer);
+                <String>::sse_encode(path, serializer);
+                <String>::sse_encode(filesystem, serializer);
+            }
+            crate::api::types::FrbCompressionError::CompressionNotSupportedOnVolume { path } => {
+                <i32>::sse_encode(13, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::FileTooSmallForWof { path } => {
+                <i32>::sse_encode(14, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::EncryptedFile { path } => {
+                <i32>::sse_encode(15, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::SparseFile { path } => {
+                <i32>::sse_encode(16, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::IntegrityStreamFile { path } => {
+                <i32>::sse_encode(17, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::types::FrbCompressionError::GameFolderLocked { path } => {
+                <i32>::sse_encode(18, serializer);
+                <String>::sse_encode(path, serializer);
+            }
             _ => {
                 unimplemented!("");
             }
@@ -3240,6 +3839,9 @@ impl SseEncode for crate::api::types::FrbCompressionEstimate {
         <bool>::sse_encode(self.adaptive_applied, serializer);
         <Option<u32>>::sse_encode(self.community_samples, serializer);
         <bool>::sse_encode(self.community_lookup_pending, serializer);
+        <u64>::sse_encode(self.estimated_saved_low_bytes, serializer);
+        <u64>::sse_encode(self.estimated_saved_high_bytes, serializer);
+        <f64>::sse_encode(self.confidence, serializer);
     }
 }
 
@@ -3250,6 +3852,7 @@ impl SseEncode for crate::api::types::FrbCompressionEstimateSource {
             match self {
                 crate::api::types::FrbCompressionEstimateSource::Heuristic => 0,
                 crate::api::types::FrbCompressionEstimateSource::CommunityDb => 1,
+                crate::api::types::FrbCompressionEstimateSource::Sampled => 2,
                 _ => {
                     unimplemented!("");
                 }
@@ -3269,6 +3872,7 @@ impl SseEncode for crate::api::types::FrbCompressionProgress {
         <u64>::sse_encode(self.bytes_compressed, serializer);
         <u64>::sse_encode(self.bytes_saved, serializer);
         <Option<i64>>::sse_encode(self.estimated_time_remaining_ms, serializer);
+        <u64>::sse_encode(self.throughput_bytes_per_sec, serializer);
         <bool>::sse_encode(self.is_complete, serializer);
     }
 }
@@ -3280,6 +3884,29 @@ impl SseEncode for crate::api::types::FrbCompressionStats {
         <u64>::sse_encode(self.compressed_bytes, serializer);
         <u64>::sse_encode(self.files_processed, serializer);
         <u64>::sse_encode(self.files_skipped, serializer);
+        <u64>::sse_encode(self.files_skipped_cloud, serializer);
+        <u64>::sse_encode(self.files_skipped_permission, serializer);
+        <u64>::sse_encode(self.files_skipped_encrypted, serializer);
+        <u64>::sse_encode(self.files_skipped_sparse, serializer);
+        <Vec<String>>::sse_encode(self.skipped_encrypted_paths, serializer);
+        <Vec<String>>::sse_encode(self.skipped_sparse_paths, serializer);
+        <u64>::sse_encode(self.alternate_stream_bytes, serializer);
+        <bool>::sse_encode(self.has_significant_alternate_streams, serializer);
+        <u64>::sse_encode(self.files_already_compressed, serializer);
+        <u64>::sse_encode(self.bytes_already_compressed, serializer);
+        <u64>::sse_encode(self.bytes_saved_this_run, serializer);
+        <bool>::sse_encode(self.was_cancelled, serializer);
+        <u64>::sse_encode(self.duration_ms, serializer);
+    }
+}
+
+impl SseEncode for crate::api::types::FrbDecompressionStats {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u64>::sse_encode(self.files_decompressed, serializer);
+        <u64>::sse_encode(self.bytes_restored_logical, serializer);
+        <u64>::sse_encode(self.bytes_grown_physical, serializer);
+        <bool>::sse_encode(self.was_cancelled, serializer);
         <u64>::sse_encode(self.duration_ms, serializer);
     }
 }
@@ -3301,6 +3928,11 @@ impl SseEncode for crate::api::types::FrbDiscoveryError {
                 <i32>::sse_encode(2, serializer);
                 <String>::sse_encode(message, serializer);
             }
+            crate::api::types::FrbDiscoveryError::LibraryFileFailed { path, message } => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(path, serializer);
+                <String>::sse_encode(message, serializer);
+            }
             _ => {
                 unimplemented!("");
             }
@@ -3308,6 +3940,37 @@ impl SseEncode for crate::api::types::FrbDiscoveryError {
     }
 }
 
+impl SseEncode for crate::api::types::FrbDiscoveryProgress {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<String>>::sse_encode(self.active_scanners, serializer);
+        <u32>::sse_encode(self.scanners_completed, serializer);
+        <u32>::sse_encode(self.scanners_total, serializer);
+        <u64>::sse_encode(self.candidates_found, serializer);
+        <u64>::sse_encode(self.candidates_sized, serializer);
+        <u64>::sse_encode(self.elapsed_ms, serializer);
+        <bool>::sse_encode(self.is_scanning, serializer);
+    }
+}
+
+impl SseEncode for crate::api::types::FrbDriveType {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::types::FrbDriveType::Fixed => 0,
+                crate::api::types::FrbDriveType::Removable => 1,
+                crate::api::types::FrbDriveType::Network => 2,
+                crate::api::types::FrbDriveType::Unknown => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
 impl SseEncode for crate::api::types::FrbEstimateContext {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -3328,9 +3991,74 @@ impl SseEncode for crate::api::types::FrbGameInfo {
         <bool>::sse_encode(self.is_compressed, serializer);
         <bool>::sse_encode(self.is_directstorage, serializer);
         <bool>::sse_encode(self.is_unsupported, serializer);
+        <bool>::sse_encode(self.is_protected_package, serializer);
+        <bool>::sse_encode(self.is_unsupported_filesystem, serializer);
+        <bool>::sse_encode(self.uses_kernel_anticheat, serializer);
+        <crate::api::types::FrbDriveType>::sse_encode(self.drive_type, serializer);
+        <bool>::sse_encode(self.has_cloud_placeholders, serializer);
         <bool>::sse_encode(self.excluded, serializer);
         <Option<u32>>::sse_encode(self.steam_app_id, serializer);
         <Option<i64>>::sse_encode(self.last_played, serializer);
+        <Option<i64>>::sse_encode(self.last_compressed, serializer);
+        <Option<u64>>::sse_encode(self.playtime_minutes, serializer);
+        <Option<String>>::sse_encode(self.epic_catalog_item_id, serializer);
+        <Option<String>>::sse_encode(self.artwork_path, serializer);
+        <bool>::sse_encode(self.has_encrypted_or_sparse_files, serializer);
+    }
+}
+
+impl SseEncode for crate::api::types::FrbLibraryChange {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::api::types::FrbLibraryChange::Added { game } => {
+                <i32>::sse_encode(0, serializer);
+                <crate::api::types::FrbGameInfo>::sse_encode(game, serializer);
+            }
+            crate::api::types::FrbLibraryChange::Updated { game } => {
+                <i32>::sse_encode(1, serializer);
+                <crate::api::types::FrbGameInfo>::sse_encode(game, serializer);
+            }
+            crate::api::types::FrbLibraryChange::Removed { path } => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::api::types::FrbMovePhase {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::types::FrbMovePhase::Preparing => 0,
+                crate::api::types::FrbMovePhase::Copying => 1,
+                crate::api::types::FrbMovePhase::Verifying => 2,
+                crate::api::types::FrbMovePhase::Compressing => 3,
+                crate::api::types::FrbMovePhase::RemovingSource => 4,
+                crate::api::types::FrbMovePhase::RollingBack => 5,
+                crate::api::types::FrbMovePhase::Complete => 6,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::types::FrbMoveProgress {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::api::types::FrbMovePhase>::sse_encode(self.phase, serializer);
+        <u64>::sse_encode(self.files_total, serializer);
+        <u64>::sse_encode(self.files_done, serializer);
+        <u64>::sse_encode(self.bytes_total, serializer);
+        <u64>::sse_encode(self.bytes_done, serializer);
     }
 }
 
@@ -3348,6 +4076,7 @@ impl SseEncode for crate::api::types::FrbPlatform {
                 crate::api::types::FrbPlatform::XboxGamePass => 6,
                 crate::api::types::FrbPlatform::Custom => 7,
                 crate::api::types::FrbPlatform::Application => 8,
+                crate::api::types::FrbPlatform::RiotGames => 9,
                 _ => {
                     unimplemented!("");
                 }
@@ -3357,6 +4086,71 @@ impl SseEncode for crate::api::types::FrbPlatform {
     }
 }
 
+impl SseEncode for crate::api::automation_types::FrbNotificationKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::automation_types::FrbNotificationKind::JobCompleted => 0,
+                crate::api::automation_types::FrbNotificationKind::JobFailed => 1,
+                crate::api::automation_types::FrbNotificationKind::QueueDrained => 2,
+                crate::api::automation_types::FrbNotificationKind::CrashRollback => 3,
+                crate::api::automation_types::FrbNotificationKind::JobSimulated => 4,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbNotificationSeverity {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::automation_types::FrbNotificationSeverity::Info => 0,
+                crate::api::automation_types::FrbNotificationSeverity::Success => 1,
+                crate::api::automation_types::FrbNotificationSeverity::Warning => 2,
+                crate::api::automation_types::FrbNotificationSeverity::Error => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbIdlePolicy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::automation_types::FrbIdlePolicy::All => 0,
+                crate::api::automation_types::FrbIdlePolicy::Any => 1,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbPendingSettle {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.game_path, serializer);
+        <Option<String>>::sse_encode(self.game_name, serializer);
+        <u32>::sse_encode(self.event_count, serializer);
+        <u64>::sse_encode(self.burst_ms, serializer);
+        <u64>::sse_encode(self.cooldown_ms, serializer);
+        <u64>::sse_encode(self.remaining_ms, serializer);
+    }
+}
+
 impl SseEncode for crate::api::automation_types::FrbSchedulerState {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -3369,6 +4163,33 @@ impl SseEncode for crate::api::automation_types::FrbSchedulerState {
                 crate::api::automation_types::FrbSchedulerState::Compressing => 4,
                 crate::api::automation_types::FrbSchedulerState::Paused => 5,
                 crate::api::automation_types::FrbSchedulerState::Backoff => 6,
+                crate::api::automation_types::FrbSchedulerState::PausedByUser => 7,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbShutdownProgress {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::api::automation_types::FrbShutdownStage>::sse_encode(self.stage, serializer);
+        <u32>::sse_encode(self.jobs_remaining, serializer);
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbShutdownStage {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::automation_types::FrbShutdownStage::SoftCancel => 0,
+                crate::api::automation_types::FrbShutdownStage::HardCancel => 1,
+                crate::api::automation_types::FrbShutdownStage::Detached => 2,
+                crate::api::automation_types::FrbShutdownStage::Stopped => 3,
                 _ => {
                     unimplemented!("");
                 }
@@ -3405,6 +4226,33 @@ impl SseEncode for crate::api::automation_types::FrbWatcherDiagnostics {
         <u32>::sse_encode(self.watched_path_count, serializer);
         <u32>::sse_encode(self.queue_depth, serializer);
         <Option<String>>::sse_encode(self.last_error, serializer);
+        <u64>::sse_encode(self.filtered_noise_events, serializer);
+        <u64>::sse_encode(self.filtered_user_state_events, serializer);
+        <u64>::sse_encode(self.filtered_ignore_marker_events, serializer);
+        <u64>::sse_encode(self.filtered_too_deep_events, serializer);
+        <u64>::sse_encode(self.filtered_self_compression_events, serializer);
+        <Vec<crate::api::automation_types::FrbPendingSettle>>::sse_encode(
+            self.pending_settles,
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::api::automation_types::FrbSuggestedAction {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::api::automation_types::FrbSuggestedAction::None => 0,
+                crate::api::automation_types::FrbSuggestedAction::ViewHistory => 1,
+                crate::api::automation_types::FrbSuggestedAction::CloseGame => 2,
+                crate::api::automation_types::FrbSuggestedAction::RetryManually => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
     }
 }
 
@@ -3427,6 +4275,14 @@ impl SseEncode for crate::api::automation_types::FrbWatcherEvent {
                 <String>::sse_encode(path, serializer);
                 <Option<String>>::sse_encode(game_name, serializer);
             }
+            crate::api::automation_types::FrbWatcherEvent::DriveOffline { path } => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(path, serializer);
+            }
+            crate::api::automation_types::FrbWatcherEvent::DriveOnline { path } => {
+                <i32>::sse_encode(4, serializer);
+                <String>::sse_encode(path, serializer);
+            }
             _ => {
                 unimplemented!("");
             }
@@ -3468,6 +4324,16 @@ impl SseEncode for Vec<crate::api::automation_types::FrbAutomationJob> {
     }
 }
 
+impl SseEncode for Vec<crate::api::automation_types::FrbPendingSettle> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::api::automation_types::FrbPendingSettle>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<crate::api::types::FrbGameInfo> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -0,0 +1,95 @@
+//! Blocking client for the local control protocol.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpStream};
+
+use serde_json::{json, Value};
+
+use super::protocol::{Notification, Request, RpcError, ServerMessage};
+use super::Endpoint;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IpcError {
+    #[error("no running engine found: {0}")]
+    NotRunning(io::Error),
+    #[error("connection failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid message from server: {0}")]
+    Protocol(String),
+    #[error("{0}")]
+    Rpc(RpcError),
+}
+
+pub struct IpcClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: u64,
+}
+
+impl IpcClient {
+    /// Connect to the server named in the endpoint file.
+    pub fn connect() -> Result<Self, IpcError> {
+        let endpoint = Endpoint::read().map_err(IpcError::NotRunning)?;
+        Self::connect_to(&endpoint)
+    }
+
+    /// Connect and authenticate against a known endpoint.
+    pub fn connect_to(endpoint: &Endpoint) -> Result<Self, IpcError> {
+        let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, endpoint.port)).map_err(|e| {
+            if e.kind() == io::ErrorKind::ConnectionRefused {
+                IpcError::NotRunning(e)
+            } else {
+                IpcError::Io(e)
+            }
+        })?;
+        let mut client = Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            next_id: 1,
+        };
+        client.call("auth", json!({ "token": endpoint.token }))?;
+        Ok(client)
+    }
+
+    pub fn call(&mut self, method: &str, params: Value) -> Result<Value, IpcError> {
+        self.call_with_notifications(method, params, |_| {})
+    }
+
+    /// Call `method`, passing each notification the server pushes before
+    /// the response to `on_notification`.
+    pub fn call_with_notifications(
+        &mut self,
+        method: &str,
+        params: Value,
+        mut on_notification: impl FnMut(&Notification),
+    ) -> Result<Value, IpcError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut line = serde_json::to_vec(&Request::new(id, method, params))
+            .map_err(|e| IpcError::Protocol(e.to_string()))?;
+        line.push(b'\n');
+        self.writer.write_all(&line)?;
+
+        let mut incoming = String::new();
+        loop {
+            incoming.clear();
+            if self.reader.read_line(&mut incoming)? == 0 {
+                return Err(IpcError::Io(io::ErrorKind::UnexpectedEof.into()));
+            }
+            match ServerMessage::parse(incoming.trim())
+                .map_err(|e| IpcError::Protocol(e.to_string()))?
+            {
+                ServerMessage::Notification(note) => on_notification(&note),
+                ServerMessage::Response(response) if response.id == id => {
+                    return response.into_result().map_err(IpcError::Rpc);
+                }
+                ServerMessage::Response(response) => {
+                    return Err(IpcError::Protocol(format!(
+                        "response for unexpected id {}",
+                        response.id
+                    )));
+                }
+            }
+        }
+    }
+}
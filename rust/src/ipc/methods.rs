@@ -0,0 +1,242 @@
+//! Method table: thin wrappers over the same API functions the app calls
+//! through FRB, so every client drives the one engine state.
+//!
+//! | method                   | params                                   |
+//! |--------------------------|------------------------------------------|
+//! | `automation.start`       |                                          |
+//! | `automation.stop`        |                                          |
+//! | `automation.status`      |                                          |
+//! | `automation.queue`       |                                          |
+//! | `automation.metrics`     | `format?` (`json` or `prometheus`)       |
+//! | `compression.compress`   | `path`, `name?`, `algorithm?`, `allow_directstorage?`, `io_parallelism?`, `max_bytes_per_sec?` |
+//! | `compression.decompress` | `path`, `name?`, `io_parallelism?`       |
+//! | `compression.cancel`     |                                          |
+//! | `compression.cancel_job` | `job_id`                                 |
+//! | `compression.progress`   |                                          |
+//! | `compression.jobs`       |                                          |
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::protocol::{Notification, Request, RpcError, METHOD_NOT_FOUND};
+use crate::api::automation::{start_auto_compression, status_snapshot, stop_auto_compression};
+use crate::api::compression::{
+    active_compression_progress, cancel_compression, cancel_compression_job,
+    compress_game_with_progress, decompress_game_with_progress, manual_jobs, ManualJobInfo,
+};
+use crate::api::types::{FrbCompressionJobKind, FrbCompressionStats, FrbDecompressionStats};
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::progress::tracker::CompressionProgress;
+
+#[derive(Debug, Deserialize)]
+struct CompressParams {
+    path: String,
+    #[serde(default)]
+    name: Option<String>,
+    /// A `compact.exe` name such as `xpress8k`.
+    #[serde(default)]
+    algorithm: Option<String>,
+    #[serde(default)]
+    allow_directstorage: bool,
+    #[serde(default)]
+    io_parallelism: Option<u64>,
+    #[serde(default)]
+    max_bytes_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecompressParams {
+    path: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    io_parallelism: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MetricsParams {
+    #[serde(default)]
+    format: MetricsFormat,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MetricsFormat {
+    #[default]
+    Json,
+    /// Prometheus text exposition, returned as one string.
+    Prometheus,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobParams {
+    job_id: u64,
+}
+
+pub(super) fn handle(
+    request: &Request,
+    notify: &mut dyn FnMut(Notification) -> bool,
+) -> Result<Value, RpcError> {
+    match request.method.as_str() {
+        "automation.start" => start_auto_compression()
+            .map(|()| Value::Null)
+            .map_err(RpcError::failed),
+        "automation.stop" => stop_auto_compression()
+            .map(|()| Value::Null)
+            .map_err(RpcError::failed),
+        "automation.status" => Ok(status_snapshot()),
+        "automation.queue" => Ok(status_snapshot()["queue"].take()),
+        "automation.metrics" => metrics(request),
+        "compression.compress" => compress(params(request)?, notify),
+        "compression.decompress" => decompress(params(request)?, notify),
+        "compression.cancel" => {
+            cancel_compression();
+            Ok(Value::Null)
+        }
+        "compression.cancel_job" => {
+            let params: JobParams = params(request)?;
+            Ok(Value::Bool(cancel_compression_job(params.job_id)))
+        }
+        "compression.progress" => Ok(active_compression_progress()
+            .map(|progress| progress_json(&progress))
+            .unwrap_or(Value::Null)),
+        "compression.jobs" => Ok(Value::Array(manual_jobs().iter().map(job_json).collect())),
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method {other}"),
+        )),
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(request: &Request) -> Result<T, RpcError> {
+    serde_json::from_value(request.params.clone())
+        .map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+fn metrics(request: &Request) -> Result<Value, RpcError> {
+    // Scrapers commonly send no params at all.
+    let params: MetricsParams = if request.params.is_null() {
+        MetricsParams::default()
+    } else {
+        params(request)?
+    };
+    let snapshot = crate::automation::metrics::snapshot();
+    Ok(match params.format {
+        MetricsFormat::Prometheus => Value::String(snapshot.to_prometheus_text()),
+        MetricsFormat::Json => json!({
+            "jobs_completed": snapshot.jobs_completed,
+            "jobs_failed": snapshot.jobs_failed,
+            "jobs_skipped": snapshot.jobs_skipped,
+            "bytes_saved": snapshot.bytes_saved,
+            "activity_cancellations": snapshot.activity_cancellations,
+            "queue_depth": snapshot.queue_depth,
+            "running": snapshot.state.is_some(),
+            "state": snapshot.state.map(|state| format!("{state:?}")),
+        }),
+    })
+}
+
+fn compress(
+    params: CompressParams,
+    notify: &mut dyn FnMut(Notification) -> bool,
+) -> Result<Value, RpcError> {
+    let algorithm = match params.algorithm.as_deref() {
+        None => CompressionAlgorithm::default(),
+        Some(flag) => CompressionAlgorithm::from_compact_exe_flag(flag)
+            .ok_or_else(|| RpcError::invalid_params(format!("unknown algorithm {flag}")))?,
+    };
+    let name = params.name.unwrap_or_else(|| folder_name(&params.path));
+    let stats = compress_game_with_progress(
+        params.path,
+        name,
+        algorithm.into(),
+        params.allow_directstorage,
+        params.io_parallelism,
+        params.max_bytes_per_sec,
+        &mut |progress| notify(progress_notification(progress)),
+    )
+    .map_err(RpcError::failed)?;
+    Ok(stats_json(&stats))
+}
+
+fn decompress(
+    params: DecompressParams,
+    notify: &mut dyn FnMut(Notification) -> bool,
+) -> Result<Value, RpcError> {
+    let name = params.name.unwrap_or_else(|| folder_name(&params.path));
+    decompress_game_with_progress(params.path, name, params.io_parallelism, &mut |progress| {
+        notify(progress_notification(progress))
+    })
+    .map(|stats| decompression_stats_json(&stats))
+    .map_err(RpcError::failed)
+}
+
+/// Game name for a bare path: the folder name, as launchers use.
+pub(crate) fn folder_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn progress_notification(progress: &CompressionProgress) -> Notification {
+    Notification::new("compression.progress", progress_json(progress))
+}
+
+pub(crate) fn stats_json(stats: &FrbCompressionStats) -> Value {
+    json!({
+        "original_bytes": stats.original_bytes,
+        "compressed_bytes": stats.compressed_bytes,
+        "files_processed": stats.files_processed,
+        "files_skipped": stats.files_skipped,
+        "files_skipped_cloud": stats.files_skipped_cloud,
+        "files_skipped_permission": stats.files_skipped_permission,
+        "files_already_compressed": stats.files_already_compressed,
+        "bytes_already_compressed": stats.bytes_already_compressed,
+        "bytes_saved_this_run": stats.bytes_saved_this_run,
+        "was_cancelled": stats.was_cancelled,
+        "duration_ms": stats.duration_ms,
+    })
+}
+
+pub(crate) fn decompression_stats_json(stats: &FrbDecompressionStats) -> Value {
+    json!({
+        "files_decompressed": stats.files_decompressed,
+        "bytes_restored_logical": stats.bytes_restored_logical,
+        "bytes_grown_physical": stats.bytes_grown_physical,
+        "was_cancelled": stats.was_cancelled,
+        "duration_ms": stats.duration_ms,
+    })
+}
+
+fn job_json(job: &ManualJobInfo) -> Value {
+    let kind = match job.kind {
+        FrbCompressionJobKind::Compress => "compress",
+        FrbCompressionJobKind::Decompress => "decompress",
+        FrbCompressionJobKind::Sample => "sample",
+    };
+    json!({
+        "job_id": job.id,
+        "path": job.path.to_string_lossy(),
+        "kind": kind,
+        "progress": job.progress.as_ref().map(progress_json),
+    })
+}
+
+fn progress_json(progress: &CompressionProgress) -> Value {
+    json!({
+        "game_name": &*progress.game_name,
+        "files_total": progress.files_total,
+        "files_processed": progress.files_processed,
+        "bytes_original": progress.bytes_original,
+        "bytes_compressed": progress.bytes_compressed,
+        "bytes_saved": progress.bytes_saved,
+        "estimated_time_remaining_ms": progress
+            .estimated_time_remaining
+            .map(|remaining| remaining.as_millis() as u64),
+        "throughput_bytes_per_sec": progress.throughput_bytes_per_sec,
+        "is_complete": progress.is_complete,
+    })
+}
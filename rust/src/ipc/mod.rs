@@ -0,0 +1,88 @@
+//! Local control protocol shared by the app, the daemon and scripts.
+//!
+//! JSON-RPC 2.0 over loopback TCP, one message per line. The server writes
+//! its port and a random token to `ipc_endpoint.json` in the per-user
+//! config directory; a connection must send `auth` with that token before
+//! anything else, so only processes that can read the user's profile get
+//! in. Long calls such as `compression.compress` push
+//! `compression.progress` notifications on the same connection before
+//! their response.
+
+pub mod client;
+pub(crate) mod methods;
+pub mod protocol;
+pub mod server;
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+const ENDPOINT_FILE_NAME: &str = "ipc_endpoint.json";
+
+/// Where a running server listens, as written to the endpoint file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub port: u16,
+    pub token: String,
+    pub pid: u32,
+}
+
+impl Endpoint {
+    pub fn read() -> std::io::Result<Self> {
+        let contents = std::fs::read(endpoint_path()?)?;
+        serde_json::from_slice(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        crate::utils::atomic_write(&endpoint_path()?, &json)
+    }
+
+    /// Remove the endpoint file if it still points at this server.
+    fn remove_if_current(&self) {
+        if Self::read().is_ok_and(|current| current == *self) {
+            if let Ok(path) = endpoint_path() {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// 128 random bits as hex. `RandomState` is seeded from the OS RNG, which
+/// is all a same-user access token needs.
+fn new_token() -> String {
+    (0..2)
+        .map(|_| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(crate::utils::unix_now_ms());
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+fn endpoint_path() -> std::io::Result<PathBuf> {
+    #[cfg(test)]
+    {
+        static TEST_ENDPOINT_PATH: std::sync::LazyLock<PathBuf> = std::sync::LazyLock::new(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir()
+                .join(format!(
+                    "compact-games-ipc-tests-{}-{now}",
+                    std::process::id()
+                ))
+                .join(ENDPOINT_FILE_NAME)
+        });
+        Ok(TEST_ENDPOINT_PATH.clone())
+    }
+
+    #[cfg(not(test))]
+    {
+        Ok(crate::paths::data_dir()?.join(ENDPOINT_FILE_NAME))
+    }
+}
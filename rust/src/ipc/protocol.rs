@@ -0,0 +1,196 @@
+//! JSON-RPC 2.0 messages, one per line.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined: the connection has not sent a valid `auth` request.
+pub const UNAUTHORIZED: i64 = -32001;
+/// Server-defined: the engine reported an error.
+pub const OPERATION_FAILED: i64 = -32002;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    /// Absent for notifications, which get no response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl Request {
+    pub fn new(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: Some(Value::from(id)),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(INVALID_PARAMS, message)
+    }
+
+    pub fn failed(error: impl std::fmt::Display) -> Self {
+        Self::new(OPERATION_FAILED, error.to_string())
+    }
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Response {
+    pub fn from_result(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+
+    pub fn into_result(self) -> Result<Value, RpcError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.result.unwrap_or(Value::Null)),
+        }
+    }
+}
+
+/// Server-to-client push, such as progress for a running call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
+impl Notification {
+    pub fn new(method: &str, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            method: method.to_string(),
+            params,
+        }
+    }
+}
+
+/// Anything a client can read off the wire.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMessage {
+    Response(Response),
+    Notification(Notification),
+}
+
+impl ServerMessage {
+    pub fn parse(line: &str) -> serde_json::Result<Self> {
+        let value: Value = serde_json::from_str(line)?;
+        if value.get("id").is_some() {
+            serde_json::from_value(value).map(Self::Response)
+        } else {
+            serde_json::from_value(value).map(Self::Notification)
+        }
+    }
+}
+
+/// Parse one request line, or the error response to send back.
+pub fn parse_request(line: &str) -> Result<Request, Response> {
+    let value: Value = serde_json::from_str(line).map_err(|e| {
+        Response::from_result(Value::Null, Err(RpcError::new(PARSE_ERROR, e.to_string())))
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: Request = serde_json::from_value(value).map_err(|e| {
+        Response::from_result(
+            id.clone(),
+            Err(RpcError::new(INVALID_REQUEST, e.to_string())),
+        )
+    })?;
+    if request.jsonrpc != JSONRPC_VERSION {
+        return Err(Response::from_result(
+            id,
+            Err(RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\"")),
+        ));
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_requests_and_reports_bad_lines() {
+        let request =
+            parse_request(r#"{"jsonrpc":"2.0","id":7,"method":"automation.queue"}"#).unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.params, Value::Null);
+
+        let garbage = parse_request("{not json").unwrap_err();
+        assert_eq!(garbage.error.unwrap().code, PARSE_ERROR);
+
+        let old_version = parse_request(r#"{"jsonrpc":"1.0","id":"a","method":"x"}"#).unwrap_err();
+        assert_eq!(old_version.id, json!("a"));
+        assert_eq!(old_version.error.unwrap().code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn server_messages_round_trip() {
+        let response = Response::from_result(json!(1), Ok(json!({ "running": true })));
+        let line = serde_json::to_string(&response).unwrap();
+        assert!(!line.contains("error"));
+        assert_eq!(
+            ServerMessage::parse(&line).unwrap(),
+            ServerMessage::Response(response)
+        );
+
+        let note = Notification::new("compression.progress", json!({ "files_processed": 3 }));
+        let line = serde_json::to_string(&note).unwrap();
+        assert_eq!(
+            ServerMessage::parse(&line).unwrap(),
+            ServerMessage::Notification(note)
+        );
+    }
+}
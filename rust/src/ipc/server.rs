@@ -0,0 +1,281 @@
+//! Loopback JSON-RPC server.
+//!
+//! One thread accepts connections and one thread serves each connection.
+//! Requests on a connection are handled in order, so a client wanting
+//! progress for one call while issuing another opens a second connection.
+
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::protocol::{parse_request, Notification, Request, Response, RpcError, UNAUTHORIZED};
+use super::Endpoint;
+
+/// How often blocked accepts and reads look at the shutdown flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_CONNECTIONS: usize = 16;
+/// Longest accepted request line; real requests are a few hundred bytes.
+const MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Answers one authenticated request. `notify` pushes a notification to
+/// the caller and returns `false` once the connection is gone.
+pub type Handler = Arc<
+    dyn Fn(&Request, &mut dyn FnMut(Notification) -> bool) -> Result<Value, RpcError> + Send + Sync,
+>;
+
+pub struct IpcServer {
+    endpoint: Endpoint,
+    published: bool,
+    shutdown: Arc<AtomicBool>,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl IpcServer {
+    /// Serve the engine API and publish the endpoint file for clients.
+    pub fn start() -> io::Result<Self> {
+        let mut server = Self::bind(Arc::new(super::methods::handle))?;
+        server.endpoint.write()?;
+        server.published = true;
+        log::info!("IPC server listening on 127.0.0.1:{}", server.endpoint.port);
+        Ok(server)
+    }
+
+    /// Listen on an ephemeral loopback port without publishing it.
+    pub fn bind(handler: Handler) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        listener.set_nonblocking(true)?;
+        let endpoint = Endpoint {
+            port: listener.local_addr()?.port(),
+            token: super::new_token(),
+            pid: std::process::id(),
+        };
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let accept_thread = {
+            let token: Arc<str> = endpoint.token.as_str().into();
+            let shutdown = shutdown.clone();
+            std::thread::Builder::new()
+                .name("ipc-accept".into())
+                .spawn(move || accept_loop(listener, token, handler, shutdown))?
+        };
+        Ok(Self {
+            endpoint,
+            published: false,
+            shutdown,
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Stop accepting and close idle connections. A connection in the
+    /// middle of a long call closes once the call returns.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.accept_thread.take() {
+            let _ = thread.join();
+        }
+        if self.published {
+            self.endpoint.remove_if_current();
+            self.published = false;
+        }
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    token: Arc<str>,
+    handler: Handler,
+    shutdown: Arc<AtomicBool>,
+) {
+    let connections = Arc::new(AtomicUsize::new(0));
+    while !shutdown.load(Ordering::Relaxed) {
+        let (stream, peer) = match listener.accept() {
+            Ok(accepted) => accepted,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(e) => {
+                log::warn!("IPC accept failed: {e}");
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+        if connections.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+            log::warn!("Rejecting IPC connection from {peer}: too many connections");
+            continue;
+        }
+        connections.fetch_add(1, Ordering::Relaxed);
+        let token = token.clone();
+        let handler = handler.clone();
+        let shutdown = shutdown.clone();
+        let worker_connections = connections.clone();
+        let spawned = std::thread::Builder::new()
+            .name("ipc-connection".into())
+            .spawn(move || {
+                if let Err(e) = serve_connection(stream, &token, &handler, &shutdown) {
+                    log::debug!("IPC connection from {peer} closed: {e}");
+                }
+                worker_connections.fetch_sub(1, Ordering::Relaxed);
+            });
+        if let Err(e) = spawned {
+            log::warn!("Failed to spawn IPC connection thread: {e}");
+            connections.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn serve_connection(
+    stream: TcpStream,
+    token: &str,
+    handler: &Handler,
+    shutdown: &AtomicBool,
+) -> io::Result<()> {
+    // Accepted sockets can inherit the listener's non-blocking mode.
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(POLL_INTERVAL))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut authenticated = false;
+    let mut line = Vec::new();
+
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // A timed-out read keeps what it got in `line`; the next pass
+        // appends the rest.
+        let limit = (MAX_LINE_BYTES + 1 - line.len()) as u64;
+        let read = (&mut reader).take(limit).read_until(b'\n', &mut line);
+        if line.len() > MAX_LINE_BYTES {
+            return Err(io::Error::new(ErrorKind::InvalidData, "request too long"));
+        }
+        match read {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => continue,
+            Err(e) => return Err(e),
+        }
+        let bytes = std::mem::take(&mut line);
+        let text = String::from_utf8_lossy(&bytes);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let response = match parse_request(text) {
+            Err(response) => Some(response),
+            Ok(request) => {
+                let result = if request.method == "auth" {
+                    authenticated = check_token(&request, token);
+                    if authenticated {
+                        Ok(Value::Bool(true))
+                    } else {
+                        Err(RpcError::new(UNAUTHORIZED, "invalid token"))
+                    }
+                } else if !authenticated {
+                    Err(RpcError::new(UNAUTHORIZED, "send auth first"))
+                } else {
+                    handler(&request, &mut |note| {
+                        write_message(&mut writer, &note).is_ok()
+                    })
+                };
+                request.id.map(|id| Response::from_result(id, result))
+            }
+        };
+        if let Some(response) = response {
+            write_message(&mut writer, &response)?;
+        }
+    }
+}
+
+fn check_token(request: &Request, token: &str) -> bool {
+    request
+        .params
+        .get("token")
+        .and_then(Value::as_str)
+        .is_some_and(|sent| sent == token)
+}
+
+fn write_message(writer: &mut TcpStream, message: &impl Serialize) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message).map_err(io::Error::other)?;
+    line.push(b'\n');
+    writer.write_all(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::ipc::client::{IpcClient, IpcError};
+
+    fn echo_server() -> IpcServer {
+        IpcServer::bind(Arc::new(
+            |request: &Request, notify: &mut dyn FnMut(Notification) -> bool| match request
+                .method
+                .as_str()
+            {
+                "echo" => {
+                    notify(Notification::new("echo.progress", json!({ "step": 1 })));
+                    Ok(request.params.clone())
+                }
+                _ => Err(RpcError::new(
+                    crate::ipc::protocol::METHOD_NOT_FOUND,
+                    "unknown",
+                )),
+            },
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn authenticated_client_gets_notifications_then_result() {
+        let server = echo_server();
+        let mut client = IpcClient::connect_to(server.endpoint()).unwrap();
+
+        let mut notes = Vec::new();
+        let result = client
+            .call_with_notifications("echo", json!({ "x": 1 }), |note| {
+                notes.push(note.method.clone())
+            })
+            .unwrap();
+        assert_eq!(result, json!({ "x": 1 }));
+        assert_eq!(notes, ["echo.progress"]);
+
+        let missing = client.call("nope", Value::Null).unwrap_err();
+        assert!(
+            matches!(missing, IpcError::Rpc(e) if e.code == crate::ipc::protocol::METHOD_NOT_FOUND)
+        );
+        server.stop();
+    }
+
+    #[test]
+    fn wrong_token_is_rejected() {
+        let server = echo_server();
+        let mut endpoint = server.endpoint().clone();
+        endpoint.token = "not-the-token".into();
+        let err = IpcClient::connect_to(&endpoint)
+            .err()
+            .expect("auth must fail");
+        assert!(matches!(err, IpcError::Rpc(e) if e.code == UNAUTHORIZED));
+    }
+}
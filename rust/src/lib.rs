@@ -1,9 +1,18 @@
 pub mod api;
 pub mod automation;
+pub mod cli;
 pub mod compression;
+pub mod daemon;
+pub mod diagnostics;
 pub mod discovery;
 mod frb_generated; /* AUTO INJECTED BY flutter_rust_bridge. This line may not be accurate, and you can change it according to your needs. */
+pub mod ipc;
+pub mod logging;
+pub mod migration;
 pub mod net;
+pub mod paths;
 pub mod progress;
 pub mod safety;
+pub mod settings;
+pub mod storage;
 pub(crate) mod utils;
@@ -0,0 +1,297 @@
+//! Application logger.
+//!
+//! Call sites keep using the `log` macros; `init` installs a logger that
+//! prints through `env_logger` as before and also captures warnings from
+//! every crate plus info from this one into rotating JSON-lines files, an
+//! in-memory ring of recent events and live listeners such as the UI log
+//! console.
+
+mod file;
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+
+use self::file::RotatingFile;
+
+const LOG_DIR_NAME: &str = "logs";
+/// Events kept in memory for `recent`.
+const MAX_RECENT_EVENTS: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => Self::Error,
+            Level::Warn => Self::Warn,
+            Level::Info => Self::Info,
+            Level::Debug => Self::Debug,
+            Level::Trace => Self::Trace,
+        }
+    }
+}
+
+/// One captured log record; also the JSON shape of a log file line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEvent {
+    pub timestamp_ms: u64,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+}
+
+/// Called for every captured event; returning `false` unsubscribes.
+pub type LogListener = Box<dyn Fn(&LogEvent) -> bool + Send>;
+
+struct AppLogger {
+    console: env_logger::Logger,
+    file: Mutex<Option<RotatingFile>>,
+    recent: Mutex<VecDeque<LogEvent>>,
+    listeners: Mutex<Vec<LogListener>>,
+}
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+
+thread_local! {
+    /// Set while an event is being captured so a listener or the file
+    /// writer that logs cannot recurse into the logger.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install the application logger. Later calls are no-ops.
+pub fn init() {
+    let logger = app_logger();
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(logger.console.filter().max(LevelFilter::Info));
+    }
+}
+
+/// Up to `limit` of the most recent captured events, oldest first.
+pub fn recent(limit: usize) -> Vec<LogEvent> {
+    let recent = app_logger()
+        .recent
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    recent
+        .iter()
+        .skip(recent.len().saturating_sub(limit))
+        .cloned()
+        .collect()
+}
+
+/// Receive every event captured from now on.
+pub fn add_listener(listener: LogListener) {
+    app_logger()
+        .listeners
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(listener);
+}
+
+/// Directory holding the rotated log files.
+pub fn log_dir() -> std::io::Result<PathBuf> {
+    #[cfg(test)]
+    {
+        static TEST_LOG_DIR: std::sync::LazyLock<PathBuf> = std::sync::LazyLock::new(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir()
+                .join(format!(
+                    "compact-games-logging-tests-{}-{now}",
+                    std::process::id()
+                ))
+                .join(LOG_DIR_NAME)
+        });
+        Ok(TEST_LOG_DIR.clone())
+    }
+
+    #[cfg(not(test))]
+    {
+        Ok(crate::paths::data_dir()?.join(LOG_DIR_NAME))
+    }
+}
+
+/// Existing log files, newest first.
+pub fn log_files() -> Vec<PathBuf> {
+    log_dir()
+        .map(|dir| file::log_files_in(&dir))
+        .unwrap_or_default()
+}
+
+fn app_logger() -> &'static AppLogger {
+    LOGGER.get_or_init(|| {
+        let mut builder =
+            env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"));
+        builder.format_timestamp_millis();
+        let file = match log_dir().and_then(RotatingFile::open) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                eprintln!("Log files disabled: {e}");
+                None
+            }
+        };
+        AppLogger {
+            console: builder.build(),
+            file: Mutex::new(file),
+            recent: Mutex::new(VecDeque::with_capacity(MAX_RECENT_EVENTS)),
+            listeners: Mutex::new(Vec::new()),
+        }
+    })
+}
+
+/// Warnings from any crate, info and above from this one. Dependencies'
+/// info output is noise in a bug report.
+fn captures(metadata: &Metadata<'_>) -> bool {
+    match metadata.level() {
+        Level::Error | Level::Warn => true,
+        Level::Info => metadata.target().starts_with(env!("CARGO_CRATE_NAME")),
+        Level::Debug | Level::Trace => false,
+    }
+}
+
+impl AppLogger {
+    fn capture(&self, event: LogEvent) {
+        if let Ok(mut line) = serde_json::to_vec(&event) {
+            line.push(b'\n');
+            let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(writer) = file.as_mut() {
+                if let Err(e) = writer.write_line(&line) {
+                    eprintln!("Log file write failed; disabling log files: {e}");
+                    *file = None;
+                }
+            }
+        }
+
+        self.listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|listener| listener(&event));
+
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() >= MAX_RECENT_EVENTS {
+            recent.pop_front();
+        }
+        recent.push_back(event);
+    }
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.console.enabled(metadata) || captures(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.console.matches(record) {
+            self.console.log(record);
+        }
+        if !captures(record.metadata()) || CAPTURING.with(|flag| flag.replace(true)) {
+            return;
+        }
+        self.capture(LogEvent {
+            timestamp_ms: crate::utils::unix_now_ms(),
+            level: record.level().into(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+        CAPTURING.with(|flag| flag.set(false));
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+        if let Some(file) = self.file.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    fn event(message: &str) -> LogEvent {
+        LogEvent {
+            timestamp_ms: 1,
+            level: LogLevel::Warn,
+            target: "compact_games_core::tests".into(),
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn captures_warnings_everywhere_but_info_only_from_this_crate() {
+        let own = Metadata::builder()
+            .level(Level::Info)
+            .target("compact_games_core::compression")
+            .build();
+        let dependency_info = Metadata::builder()
+            .level(Level::Info)
+            .target("ureq::unit")
+            .build();
+        let dependency_warn = Metadata::builder()
+            .level(Level::Warn)
+            .target("ureq::unit")
+            .build();
+        let own_debug = Metadata::builder()
+            .level(Level::Debug)
+            .target("compact_games_core::compression")
+            .build();
+
+        assert!(captures(&own));
+        assert!(!captures(&dependency_info));
+        assert!(captures(&dependency_warn));
+        assert!(!captures(&own_debug));
+    }
+
+    #[test]
+    fn captured_events_reach_ring_listeners_and_file() {
+        let logger = app_logger();
+        let seen = Arc::new(AtomicUsize::new(0));
+        let listener_seen = seen.clone();
+        add_listener(Box::new(move |event| {
+            if event.message.starts_with("ring-test") {
+                listener_seen.fetch_add(1, Ordering::Relaxed);
+            }
+            true
+        }));
+
+        logger.capture(event("ring-test one"));
+        logger.capture(event("ring-test two"));
+        logger.flush();
+
+        let recent: Vec<_> = recent(MAX_RECENT_EVENTS)
+            .into_iter()
+            .filter(|event| event.message.starts_with("ring-test"))
+            .map(|event| event.message)
+            .collect();
+        assert_eq!(recent, vec!["ring-test one", "ring-test two"]);
+        assert_eq!(seen.load(Ordering::Relaxed), 2);
+
+        let contents = std::fs::read_to_string(&log_files()[0]).expect("log file");
+        let parsed: LogEvent = serde_json::from_str(
+            contents
+                .lines()
+                .find(|line| line.contains("ring-test two"))
+                .expect("event line"),
+        )
+        .expect("json line");
+        assert_eq!(parsed, event("ring-test two"));
+    }
+}
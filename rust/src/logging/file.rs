@@ -0,0 +1,112 @@
+//! Size-rotated JSON-lines log files.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const LOG_FILE_STEM: &str = "compact_games";
+/// The active file rotates once it would grow past this.
+const MAX_LOG_FILE_BYTES: u64 = 4 * 1024 * 1024;
+/// Active file plus rotated ones; the oldest is deleted on rotation.
+const MAX_LOG_FILES: usize = 5;
+
+pub(super) struct RotatingFile {
+    dir: PathBuf,
+    file: Option<File>,
+    len: u64,
+}
+
+impl RotatingFile {
+    pub(super) fn open(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = open_append(&file_path(&dir, 0))?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            dir,
+            file: Some(file),
+            len,
+        })
+    }
+
+    pub(super) fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.len > 0 && self.len.saturating_add(line.len() as u64) > MAX_LOG_FILE_BYTES {
+            self.rotate()?;
+        }
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(open_append(&file_path(&self.dir, 0))?),
+        };
+        file.write_all(line)?;
+        self.len = self.len.saturating_add(line.len() as u64);
+        Ok(())
+    }
+
+    pub(super) fn flush(&mut self) {
+        if let Some(file) = &mut self.file {
+            let _ = file.flush();
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        // Windows cannot rename a file that is still open.
+        self.file = None;
+        for index in (1..MAX_LOG_FILES).rev() {
+            let from = file_path(&self.dir, index - 1);
+            if !from.exists() {
+                continue;
+            }
+            let to = file_path(&self.dir, index);
+            if to.exists() {
+                fs::remove_file(&to)?;
+            }
+            fs::rename(&from, &to)?;
+        }
+        self.file = Some(open_append(&file_path(&self.dir, 0))?);
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// Existing log files in `dir`, newest first.
+pub(super) fn log_files_in(dir: &Path) -> Vec<PathBuf> {
+    (0..MAX_LOG_FILES)
+        .map(|index| file_path(dir, index))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn file_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(format!("{LOG_FILE_STEM}.jsonl"))
+    } else {
+        dir.join(format!("{LOG_FILE_STEM}.{index}.jsonl"))
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_keeps_a_bounded_number_of_files() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let mut file = RotatingFile::open(dir.path().to_path_buf()).expect("open log");
+        let line = vec![b'x'; (MAX_LOG_FILE_BYTES / 2) as usize];
+        for _ in 0..(MAX_LOG_FILES * 2 + 1) {
+            file.write_line(&line).expect("write line");
+        }
+        file.flush();
+
+        let files = log_files_in(dir.path());
+        assert_eq!(files.len(), MAX_LOG_FILES);
+        assert_eq!(files[0], file_path(dir.path(), 0));
+        for path in &files {
+            let len = fs::metadata(path).expect("metadata").len();
+            assert!(len <= MAX_LOG_FILE_BYTES);
+        }
+    }
+}
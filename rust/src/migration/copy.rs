@@ -0,0 +1,276 @@
+//! Tree copy with metadata preservation and hash verification.
+
+use std::fs::{self, File, FileTimes};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use super::error::MoveError;
+use crate::compression::engine::CancellationToken;
+
+const COPY_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// Everything under the source folder, in walk order.
+#[derive(Debug, Default)]
+pub(crate) struct CopyPlan {
+    /// Directories relative to the root, parents before children.
+    pub dirs: Vec<PathBuf>,
+    /// Regular files relative to the root.
+    pub files: Vec<PathBuf>,
+    pub total_bytes: u64,
+}
+
+/// SHA-256 of each copied file, recorded while reading the source so the
+/// source is only read once.
+pub(crate) type SourceHashes = Vec<(PathBuf, Vec<u8>)>;
+
+/// Walk `source` and collect directories and files.
+///
+/// Symlinks and other reparse points are rejected: copying their targets
+/// would duplicate data and silently change the layout once the source is
+/// deleted.
+pub(crate) fn plan_copy(source: &Path) -> Result<CopyPlan, MoveError> {
+    let mut plan = CopyPlan::default();
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .min_depth(1)
+    {
+        let entry = entry.map_err(|e| {
+            let path = e.path().unwrap_or(source).to_path_buf();
+            MoveError::io(path, e.into())
+        })?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir yields children of the root")
+            .to_path_buf();
+        let file_type = entry.file_type();
+        if file_type.is_symlink() {
+            return Err(MoveError::UnsupportedEntry(entry.path().to_path_buf()));
+        }
+        if file_type.is_dir() {
+            plan.dirs.push(relative);
+        } else {
+            let len = entry
+                .metadata()
+                .map_err(|e| MoveError::io(entry.path(), e.into()))?
+                .len();
+            plan.total_bytes = plan.total_bytes.saturating_add(len);
+            plan.files.push(relative);
+        }
+    }
+    Ok(plan)
+}
+
+/// Create the destination tree, copy every file and preserve metadata.
+///
+/// `on_bytes` is called after each chunk with the number of bytes written.
+pub(crate) fn copy_tree(
+    source: &Path,
+    dest: &Path,
+    plan: &CopyPlan,
+    cancel: &CancellationToken,
+    mut on_bytes: impl FnMut(u64, bool),
+) -> Result<SourceHashes, MoveError> {
+    fs::create_dir(dest).map_err(|e| MoveError::io(dest, e))?;
+    for dir in &plan.dirs {
+        let path = dest.join(dir);
+        fs::create_dir(&path).map_err(|e| MoveError::io(&path, e))?;
+    }
+
+    let mut hashes = Vec::with_capacity(plan.files.len());
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    for relative in &plan.files {
+        let src_path = source.join(relative);
+        let dst_path = dest.join(relative);
+        let digest = copy_file(&src_path, &dst_path, &mut buffer, cancel, |n| {
+            on_bytes(n, false)
+        })?;
+        copy_metadata(&src_path, &dst_path)?;
+        hashes.push((relative.clone(), digest));
+        on_bytes(0, true);
+    }
+
+    // Children are done, so directory timestamps will not be bumped again.
+    for dir in plan.dirs.iter().rev() {
+        copy_metadata(&source.join(dir), &dest.join(dir))?;
+    }
+    copy_metadata(source, dest)?;
+    Ok(hashes)
+}
+
+/// Re-read every destination file and compare against the source hashes.
+pub(crate) fn verify_tree(
+    dest: &Path,
+    hashes: &SourceHashes,
+    cancel: &CancellationToken,
+    mut on_bytes: impl FnMut(u64, bool),
+) -> Result<(), MoveError> {
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    for (relative, expected) in hashes {
+        let path = dest.join(relative);
+        let actual = hash_file(&path, &mut buffer, cancel, |n| on_bytes(n, false))?;
+        if &actual != expected {
+            return Err(MoveError::VerificationFailed(path));
+        }
+        on_bytes(0, true);
+    }
+    Ok(())
+}
+
+fn copy_file(
+    src: &Path,
+    dst: &Path,
+    buffer: &mut [u8],
+    cancel: &CancellationToken,
+    mut on_bytes: impl FnMut(u64),
+) -> Result<Vec<u8>, MoveError> {
+    let mut reader = File::open(src).map_err(|e| MoveError::io(src, e))?;
+    let mut writer = File::create_new(dst).map_err(|e| MoveError::io(dst, e))?;
+    let mut hasher = Sha256::new();
+    loop {
+        if cancel.is_cancelled() {
+            return Err(MoveError::Cancelled);
+        }
+        let n = reader.read(buffer).map_err(|e| MoveError::io(src, e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        writer
+            .write_all(&buffer[..n])
+            .map_err(|e| MoveError::io(dst, e))?;
+        on_bytes(n as u64);
+    }
+    writer.sync_all().map_err(|e| MoveError::io(dst, e))?;
+    Ok(hasher.finalize().to_vec())
+}
+
+fn hash_file(
+    path: &Path,
+    buffer: &mut [u8],
+    cancel: &CancellationToken,
+    mut on_bytes: impl FnMut(u64),
+) -> Result<Vec<u8>, MoveError> {
+    let mut reader = File::open(path).map_err(|e| MoveError::io(path, e))?;
+    let mut hasher = Sha256::new();
+    loop {
+        if cancel.is_cancelled() {
+            return Err(MoveError::Cancelled);
+        }
+        let n = reader.read(buffer).map_err(|e| MoveError::io(path, e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        on_bytes(n as u64);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Copy timestamps, the read-only attribute and (on Windows) the DACL.
+fn copy_metadata(src: &Path, dst: &Path) -> Result<(), MoveError> {
+    let meta = fs::metadata(src).map_err(|e| MoveError::io(src, e))?;
+
+    let mut times = FileTimes::new();
+    if let Ok(modified) = meta.modified() {
+        times = times.set_modified(modified);
+    }
+    if let Ok(accessed) = meta.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileTimesExt;
+        if let Ok(created) = meta.created() {
+            times = times.set_created(created);
+        }
+    }
+    open_for_metadata(dst, meta.is_dir())
+        .and_then(|handle| handle.set_times(times))
+        .map_err(|e| MoveError::io(dst, e))?;
+
+    #[cfg(windows)]
+    if let Err(e) = copy_dacl(src, dst) {
+        // Inherited permissions from the destination parent still apply.
+        log::debug!("Failed to copy ACL to {}: {e}", dst.display());
+    }
+
+    if meta.permissions().readonly() {
+        fs::set_permissions(dst, meta.permissions()).map_err(|e| MoveError::io(dst, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn open_for_metadata(path: &Path, is_dir: bool) -> std::io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    // FILE_FLAG_BACKUP_SEMANTICS is required to open a directory handle.
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if is_dir {
+        options.custom_flags(FILE_FLAG_BACKUP_SEMANTICS);
+    }
+    options.open(path)
+}
+
+#[cfg(not(windows))]
+fn open_for_metadata(path: &Path, is_dir: bool) -> std::io::Result<File> {
+    if is_dir {
+        File::open(path)
+    } else {
+        fs::OpenOptions::new().write(true).open(path)
+    }
+}
+
+#[cfg(windows)]
+fn copy_dacl(src: &Path, dst: &Path) -> std::io::Result<()> {
+    use crate::utils::wide_path;
+    use windows::core::PCWSTR;
+    use windows::Win32::Security::{
+        GetFileSecurityW, SetFileSecurityW, DACL_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR,
+    };
+
+    let src_wide = wide_path(src);
+    let dst_wide = wide_path(dst);
+
+    let mut needed = 0u32;
+    // First call only reports the descriptor size.
+    let _ = unsafe {
+        GetFileSecurityW(
+            PCWSTR(src_wide.as_ptr()),
+            DACL_SECURITY_INFORMATION.0,
+            None,
+            0,
+            &mut needed,
+        )
+    };
+    if needed == 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut descriptor = vec![0u8; needed as usize];
+    let descriptor_ptr = PSECURITY_DESCRIPTOR(descriptor.as_mut_ptr().cast());
+    unsafe {
+        GetFileSecurityW(
+            PCWSTR(src_wide.as_ptr()),
+            DACL_SECURITY_INFORMATION.0,
+            Some(descriptor_ptr),
+            needed,
+            &mut needed,
+        )
+    }
+    .ok()
+    .map_err(std::io::Error::other)?;
+    unsafe {
+        SetFileSecurityW(
+            PCWSTR(dst_wide.as_ptr()),
+            DACL_SECURITY_INFORMATION,
+            descriptor_ptr,
+        )
+    }
+    .ok()
+    .map_err(std::io::Error::other)
+}
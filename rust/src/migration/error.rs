@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Errors that can abort a game move.
+///
+/// Every variant is returned only after the partial destination copy has
+/// been rolled back; the source folder is never touched on failure.
+#[derive(Debug, Error)]
+pub enum MoveError {
+    #[error("path does not exist: {0}")]
+    PathNotFound(PathBuf),
+
+    #[error("path is not a directory: {0}")]
+    NotADirectory(PathBuf),
+
+    #[error("destination already exists: {0}")]
+    DestinationExists(PathBuf),
+
+    #[error("source and destination are on the same volume")]
+    SameVolume,
+
+    #[error("move aborted: game is running")]
+    GameRunning,
+
+    #[error("not enough space on destination: need {required} bytes, {available} available")]
+    InsufficientSpace { required: u64, available: u64 },
+
+    #[error("unsupported entry (symbolic link or reparse point): {0}")]
+    UnsupportedEntry(PathBuf),
+
+    #[error("verification failed: {0} does not match the source")]
+    VerificationFailed(PathBuf),
+
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("operation cancelled by user")]
+    Cancelled,
+}
+
+impl MoveError {
+    pub(crate) fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Self::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! Moving an installed game to another volume.
+//!
+//! A move copies the folder (timestamps, read-only attribute and ACLs
+//! preserved), re-reads the copy to verify it against hashes taken from the
+//! source, optionally compresses the copy, and only then deletes the source.
+//! Any failure or cancellation before the source is deleted removes the
+//! partial copy, so the game is always left intact in one place.
+
+mod copy;
+pub mod error;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use sysinfo::Disks;
+
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::engine::{CancellationToken, CompressionEngine, CompressionStats};
+use crate::discovery::storage::volume_key_for_path;
+use crate::safety::process::ProcessChecker;
+use copy::CopyPlan;
+pub use error::MoveError;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovePhase {
+    Preparing,
+    Copying,
+    Verifying,
+    Compressing,
+    RemovingSource,
+    RollingBack,
+    Complete,
+}
+
+/// Snapshot emitted while a move runs. Byte and file counters are for the
+/// current phase; compression reports only the phase change.
+#[derive(Debug, Clone)]
+pub struct MoveProgress {
+    pub phase: MovePhase,
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveOutcome {
+    pub destination: PathBuf,
+    pub files_copied: u64,
+    pub bytes_copied: u64,
+    /// Present when compression at the destination was requested and ran.
+    pub compression: Option<CompressionStats>,
+    /// Compression failure is not fatal: the verified copy is kept
+    /// uncompressed and the reason is reported here.
+    pub compression_error: Option<String>,
+    /// False when the copy succeeded but the source could not be fully
+    /// deleted (e.g. a file was locked). The game runs from `destination`.
+    pub source_removed: bool,
+}
+
+/// Move `source` into `dest_drive` (a volume root or folder on it).
+///
+/// The game keeps its folder name. When `algorithm` is set the copy is
+/// compressed before the source is deleted.
+pub fn move_game(
+    source: &Path,
+    dest_drive: &Path,
+    algorithm: Option<CompressionAlgorithm>,
+    cancel: &CancellationToken,
+    on_progress: impl FnMut(&MoveProgress),
+) -> Result<MoveOutcome, MoveError> {
+    let Some(folder_name) = source.file_name() else {
+        return Err(MoveError::NotADirectory(source.to_path_buf()));
+    };
+    let dest = dest_drive.join(folder_name);
+
+    if volume_key_for_path(source) == volume_key_for_path(dest_drive) {
+        return Err(MoveError::SameVolume);
+    }
+    if ProcessChecker::new().is_game_running(source) {
+        return Err(MoveError::GameRunning);
+    }
+
+    relocate(
+        source,
+        &dest,
+        algorithm,
+        cancel,
+        on_progress,
+        |required| match available_space(dest_drive) {
+            Some(available) if available < required => Err(MoveError::InsufficientSpace {
+                required,
+                available,
+            }),
+            _ => Ok(()),
+        },
+    )
+}
+
+/// Copy, verify, optionally compress, then delete the source.
+///
+/// `check_space` receives the bytes the copy will write.
+fn relocate(
+    source: &Path,
+    dest: &Path,
+    algorithm: Option<CompressionAlgorithm>,
+    cancel: &CancellationToken,
+    on_progress: impl FnMut(&MoveProgress),
+    check_space: impl FnOnce(u64) -> Result<(), MoveError>,
+) -> Result<MoveOutcome, MoveError> {
+    let mut reporter = ProgressReporter::new(on_progress);
+    reporter.phase(MovePhase::Preparing, 0, 0);
+
+    if !source.exists() {
+        return Err(MoveError::PathNotFound(source.to_path_buf()));
+    }
+    if !source.is_dir() {
+        return Err(MoveError::NotADirectory(source.to_path_buf()));
+    }
+    if dest.exists() {
+        return Err(MoveError::DestinationExists(dest.to_path_buf()));
+    }
+    let plan = copy::plan_copy(source)?;
+    check_space(plan.total_bytes)?;
+
+    let compression = match copy_and_verify(source, dest, &plan, algorithm, cancel, &mut reporter) {
+        Ok(compression) => compression,
+        Err(e) => {
+            log::warn!(
+                "Moving {} to {} failed, rolling back: {e}",
+                source.display(),
+                dest.display()
+            );
+            reporter.phase(MovePhase::RollingBack, 0, 0);
+            if let Err(cleanup) = std::fs::remove_dir_all(dest) {
+                if dest.exists() {
+                    log::warn!(
+                        "Failed to remove partial copy {}: {cleanup}",
+                        dest.display()
+                    );
+                }
+            }
+            return Err(e);
+        }
+    };
+    let (compression, compression_error) = match compression {
+        Some(Ok(stats)) => (Some(stats), None),
+        Some(Err(message)) => (None, Some(message)),
+        None => (None, None),
+    };
+
+    reporter.phase(MovePhase::RemovingSource, 0, 0);
+    let source_removed = match std::fs::remove_dir_all(source) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!(
+                "Moved {} but could not remove the source: {e}",
+                source.display()
+            );
+            false
+        }
+    };
+
+    reporter.phase(
+        MovePhase::Complete,
+        plan.files.len() as u64,
+        plan.total_bytes,
+    );
+    Ok(MoveOutcome {
+        destination: dest.to_path_buf(),
+        files_copied: plan.files.len() as u64,
+        bytes_copied: plan.total_bytes,
+        compression,
+        compression_error,
+        source_removed,
+    })
+}
+
+/// The rollback-covered part of a move. Returns the compression result, if
+/// compression was requested.
+fn copy_and_verify<F: FnMut(&MoveProgress)>(
+    source: &Path,
+    dest: &Path,
+    plan: &CopyPlan,
+    algorithm: Option<CompressionAlgorithm>,
+    cancel: &CancellationToken,
+    reporter: &mut ProgressReporter<F>,
+) -> Result<Option<Result<CompressionStats, String>>, MoveError> {
+    let files_total = plan.files.len() as u64;
+
+    reporter.phase(MovePhase::Copying, files_total, plan.total_bytes);
+    let hashes = copy::copy_tree(source, dest, plan, cancel, |bytes, file_done| {
+        reporter.advance(bytes, file_done)
+    })?;
+
+    reporter.phase(MovePhase::Verifying, files_total, plan.total_bytes);
+    copy::verify_tree(dest, &hashes, cancel, |bytes, file_done| {
+        reporter.advance(bytes, file_done)
+    })?;
+
+    let Some(algorithm) = algorithm else {
+        return Ok(None);
+    };
+    reporter.phase(MovePhase::Compressing, files_total, plan.total_bytes);
+    let engine = CompressionEngine::new(algorithm).with_cancel_token(cancel.clone());
+    match engine.compress_folder(dest) {
+        Ok(stats) => Ok(Some(Ok(stats))),
+        Err(crate::compression::error::CompressionError::Cancelled) => Err(MoveError::Cancelled),
+        Err(e) => {
+            log::warn!("Compression after move failed for {}: {e}", dest.display());
+            Ok(Some(Err(e.to_string())))
+        }
+    }
+}
+
+/// Free bytes on the mounted volume containing `path`, if known.
+fn available_space(path: &Path) -> Option<u64> {
+    let volume = volume_key_for_path(path);
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .find(|disk| volume_key_for_path(disk.mount_point()) == volume)
+        .map(|disk| disk.available_space())
+}
+
+/// Throttles byte-level progress; phase changes are always reported.
+struct ProgressReporter<F: FnMut(&MoveProgress)> {
+    on_progress: F,
+    progress: MoveProgress,
+    last_emit: Instant,
+}
+
+impl<F: FnMut(&MoveProgress)> ProgressReporter<F> {
+    fn new(on_progress: F) -> Self {
+        Self {
+            on_progress,
+            progress: MoveProgress {
+                phase: MovePhase::Preparing,
+                files_total: 0,
+                files_done: 0,
+                bytes_total: 0,
+                bytes_done: 0,
+            },
+            last_emit: Instant::now(),
+        }
+    }
+
+    fn phase(&mut self, phase: MovePhase, files_total: u64, bytes_total: u64) {
+        let done = phase == MovePhase::Complete;
+        self.progress = MoveProgress {
+            phase,
+            files_total,
+            files_done: if done { files_total } else { 0 },
+            bytes_total,
+            bytes_done: if done { bytes_total } else { 0 },
+        };
+        self.emit();
+    }
+
+    fn advance(&mut self, bytes: u64, file_done: bool) {
+        self.progress.bytes_done = self.progress.bytes_done.saturating_add(bytes);
+        if file_done {
+            self.progress.files_done = self.progress.files_done.saturating_add(1);
+        }
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Instant::now();
+        (self.on_progress)(&self.progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_game(root: &Path) {
+        std::fs::create_dir_all(root.join("bin")).unwrap();
+        std::fs::write(root.join("bin").join("game.exe"), vec![7u8; 64 * 1024]).unwrap();
+        std::fs::write(root.join("data.pak"), b"level data".repeat(1000)).unwrap();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+    }
+
+    #[test]
+    fn relocate_copies_verifies_and_removes_source() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("src").join("Game");
+        let dest = temp.path().join("dst").join("Game");
+        write_game(&source);
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let mut phases = Vec::new();
+        let outcome = relocate(
+            &source,
+            &dest,
+            None,
+            &CancellationToken::new(),
+            |p| {
+                if phases.last() != Some(&p.phase) {
+                    phases.push(p.phase);
+                }
+            },
+            |_| Ok(()),
+        )
+        .unwrap();
+
+        assert!(outcome.source_removed);
+        assert!(!source.exists());
+        assert_eq!(outcome.files_copied, 2);
+        assert_eq!(
+            std::fs::read(dest.join("data.pak")).unwrap(),
+            b"level data".repeat(1000)
+        );
+        assert!(dest.join("empty").is_dir());
+        assert_eq!(
+            phases,
+            vec![
+                MovePhase::Preparing,
+                MovePhase::Copying,
+                MovePhase::Verifying,
+                MovePhase::RemovingSource,
+                MovePhase::Complete,
+            ]
+        );
+    }
+
+    #[test]
+    fn cancelled_move_rolls_back_and_keeps_source() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("src").join("Game");
+        let dest = temp.path().join("dst").join("Game");
+        write_game(&source);
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let cancel = CancellationToken::new();
+        let result = relocate(
+            &source,
+            &dest,
+            None,
+            &cancel,
+            |p| {
+                if p.phase == MovePhase::Copying {
+                    cancel.cancel();
+                }
+            },
+            |_| Ok(()),
+        );
+
+        assert!(matches!(result, Err(MoveError::Cancelled)));
+        assert!(!dest.exists());
+        assert!(source.join("data.pak").is_file());
+    }
+
+    #[test]
+    fn existing_destination_and_space_are_checked_before_copying() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let source = temp.path().join("Game");
+        write_game(&source);
+
+        let occupied = temp.path().join("Occupied");
+        std::fs::create_dir_all(&occupied).unwrap();
+        let result = relocate(
+            &source,
+            &occupied,
+            None,
+            &CancellationToken::new(),
+            |_| {},
+            |_| Ok(()),
+        );
+        assert!(matches!(result, Err(MoveError::DestinationExists(_))));
+
+        let dest = temp.path().join("Elsewhere");
+        let result = relocate(
+            &source,
+            &dest,
+            None,
+            &CancellationToken::new(),
+            |_| {},
+            |required| {
+                Err(MoveError::InsufficientSpace {
+                    required,
+                    available: 0,
+                })
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(MoveError::InsufficientSpace { required, .. }) if required == 64 * 1024 + 10_000
+        ));
+        assert!(!dest.exists());
+        assert!(source.is_dir());
+    }
+}
@@ -0,0 +1,153 @@
+//! Where the engine keeps its files.
+//!
+//! Journals, caches, history, logs and the rest live in one data
+//! directory, `%APPDATA%\compact_games` unless overridden. Portable
+//! installs and machines whose roaming profile is locked down can move it
+//! with the `COMPACT_GAMES_DATA_DIR` environment variable or the
+//! `data_dir` setting; the variable wins. A relative override is resolved
+//! against the executable's folder, so a portable install can use `data`.
+//!
+//! `settings.json` itself always stays in the default directory, since it
+//! is what names the override. The override is read once per process, so
+//! a changed setting takes effect on the next start.
+
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::OnceLock;
+
+/// Environment variable naming the data directory.
+pub const DATA_DIR_ENV: &str = "COMPACT_GAMES_DATA_DIR";
+const APP_DIR_NAME: &str = "compact_games";
+
+#[cfg(not(test))]
+static DATA_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// The directory holding everything but `settings.json`. Not created here;
+/// callers create it before their first write.
+pub fn data_dir() -> io::Result<PathBuf> {
+    #[cfg(test)]
+    {
+        static TEST_DATA_DIR: std::sync::LazyLock<PathBuf> = std::sync::LazyLock::new(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-data-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+        Ok(TEST_DATA_DIR.clone())
+    }
+
+    #[cfg(not(test))]
+    {
+        DATA_DIR
+            .get_or_init(|| {
+                // No logging here: the log directory is resolved through
+                // this function and would re-enter the initializer.
+                resolve(
+                    std::env::var_os(DATA_DIR_ENV),
+                    crate::settings::saved_data_dir(),
+                    std::env::current_exe()
+                        .ok()
+                        .as_deref()
+                        .and_then(Path::parent),
+                    default_data_dir().ok(),
+                )
+            })
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory found"))
+    }
+}
+
+/// [`data_dir`], or `compact_games` under the working directory when the
+/// profile has no config directory, for stores that must open somewhere.
+pub fn data_dir_or_fallback() -> PathBuf {
+    data_dir().unwrap_or_else(|_| {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(APP_DIR_NAME)
+    })
+}
+
+/// The per-user default, `%APPDATA%\compact_games`. Settings always live
+/// here.
+pub fn default_data_dir() -> io::Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory found"))?;
+    Ok(config_dir.join(APP_DIR_NAME))
+}
+
+/// Whether the data directory was moved from the default.
+pub fn is_data_dir_overridden() -> bool {
+    match (data_dir(), default_data_dir()) {
+        (Ok(current), Ok(default)) => current != default,
+        _ => false,
+    }
+}
+
+fn resolve(
+    env_override: Option<OsString>,
+    setting_override: Option<String>,
+    exe_dir: Option<&Path>,
+    default: Option<PathBuf>,
+) -> Option<PathBuf> {
+    let chosen = env_override
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            setting_override
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        });
+    match chosen {
+        Some(path) if path.is_absolute() => Some(path),
+        Some(path) => match exe_dir {
+            Some(exe_dir) => Some(exe_dir.join(path)),
+            None => default,
+        },
+        None => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_beats_setting_and_relative_paths_follow_the_exe() {
+        let exe_dir = Path::new(r"C:\Portable\CompactGames");
+        let default = Some(PathBuf::from(r"C:\Users\u\AppData\Roaming\compact_games"));
+
+        assert_eq!(
+            resolve(
+                Some(r"D:\Env".into()),
+                Some(r"E:\Setting".into()),
+                Some(exe_dir),
+                default.clone()
+            ),
+            Some(PathBuf::from(r"D:\Env"))
+        );
+        assert_eq!(
+            resolve(
+                Some(OsString::new()),
+                Some(r"E:\Setting".into()),
+                Some(exe_dir),
+                default.clone()
+            ),
+            Some(PathBuf::from(r"E:\Setting"))
+        );
+        assert_eq!(
+            resolve(None, Some("data".into()), Some(exe_dir), default.clone()),
+            Some(exe_dir.join("data"))
+        );
+        assert_eq!(
+            resolve(None, Some("  ".into()), Some(exe_dir), default.clone()),
+            default
+        );
+    }
+}
@@ -10,11 +10,16 @@ use crossbeam_channel::{bounded, Receiver, Sender, TryRecvError, TrySendError};
 
 use super::tracker::CompressionProgress;
 
+#[derive(Clone)]
 pub struct EngineCounters {
     pub files_processed: Arc<AtomicU64>,
     pub files_total: Arc<AtomicU64>,
     pub bytes_original: Arc<AtomicU64>,
     pub bytes_compressed: Arc<AtomicU64>,
+    /// Logical bytes of all files in the operation; 0 when unknown.
+    pub bytes_total: Arc<AtomicU64>,
+    /// Logical bytes of files finished so far, including skipped ones.
+    pub bytes_processed: Arc<AtomicU64>,
 }
 
 pub struct ProgressReporter {
@@ -94,10 +99,7 @@ fn reporter_loop(
     done: Arc<AtomicBool>,
     emit_baseline_first: bool,
 ) {
-    let mut speed_samples: VecDeque<f64> = VecDeque::with_capacity(10);
-    let mut speed_sum = 0.0;
-    let mut last_files: u64 = 0;
-    let mut last_tick = Instant::now();
+    let mut rates = RateWindow::default();
     let mut last_emitted: Option<(u64, u64, u64, u64, bool)> = None;
     let mut baseline_pending = emit_baseline_first;
 
@@ -122,33 +124,19 @@ fn reporter_loop(
         let files_processed = files_processed_raw.min(files_total);
         let bytes_original = counters.bytes_original.load(Ordering::Relaxed);
         let bytes_compressed = counters.bytes_compressed.load(Ordering::Relaxed);
-        let now = Instant::now();
-        let dt = now.duration_since(last_tick).as_secs_f64();
-        if dt > 0.0 {
-            let files_delta = files_processed.saturating_sub(last_files);
-            let speed = files_delta as f64 / dt;
-            if speed_samples.len() >= 10 {
-                if let Some(oldest) = speed_samples.pop_front() {
-                    speed_sum -= oldest;
-                }
-            }
-            speed_samples.push_back(speed);
-            speed_sum += speed;
-        }
-        last_files = files_processed;
-        last_tick = now;
-
-        let avg_speed = if speed_samples.is_empty() {
-            0.0
-        } else {
-            speed_sum / speed_samples.len() as f64
-        };
-        let remaining = files_total.saturating_sub(files_processed);
-        let eta = if avg_speed > 0.1 {
-            Some(Duration::from_secs_f64(remaining as f64 / avg_speed))
-        } else {
-            None
-        };
+        let bytes_total = counters.bytes_total.load(Ordering::Relaxed);
+        let bytes_processed = counters.bytes_processed.load(Ordering::Relaxed);
+        rates.push(Instant::now(), files_processed, bytes_processed);
+        let (files_per_sec, bytes_per_sec) = rates.rates();
+        let eta = estimate_remaining(
+            Remaining {
+                files: files_total.saturating_sub(files_processed),
+                bytes: bytes_total.saturating_sub(bytes_processed),
+                bytes_known: bytes_total > 0,
+            },
+            files_per_sec,
+            bytes_per_sec,
+        );
 
         if baseline_pending {
             if files_total == 0 && !done_now && !stopping {
@@ -164,6 +152,7 @@ fn reporter_loop(
                 bytes_compressed: 0,
                 bytes_saved: 0,
                 estimated_time_remaining: None,
+                throughput_bytes_per_sec: 0,
                 is_complete: baseline_is_complete,
             };
             if !send_latest(&tx, &latest_rx, baseline) {
@@ -196,6 +185,7 @@ fn reporter_loop(
                 bytes_compressed,
                 bytes_saved: bytes_original.saturating_sub(bytes_compressed),
                 estimated_time_remaining: eta,
+                throughput_bytes_per_sec: bytes_per_sec as u64,
                 is_complete,
             };
 
@@ -211,6 +201,71 @@ fn reporter_loop(
     }
 }
 
+/// How far back throughput is averaged. Files finish in bursts, and a large
+/// file adds all of its bytes at once, so a short window swings wildly.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+/// Below this the file rate says too little to extrapolate from.
+const MIN_FILES_PER_SEC: f64 = 0.1;
+
+/// Processed-counter samples covering the last `RATE_WINDOW`.
+#[derive(Default)]
+struct RateWindow {
+    samples: VecDeque<(Instant, u64, u64)>,
+}
+
+impl RateWindow {
+    fn push(&mut self, at: Instant, files: u64, bytes: u64) {
+        self.samples.push_back((at, files, bytes));
+        // Keep the newest sample at or before the cutoff so the window
+        // spans the full duration once enough time has passed.
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .is_some_and(|(time, _, _)| at.duration_since(*time) >= RATE_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Files and bytes per second across the window.
+    fn rates(&self) -> (f64, f64) {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return (0.0, 0.0);
+        };
+        let elapsed = last.0.duration_since(first.0).as_secs_f64();
+        if elapsed <= 0.0 {
+            return (0.0, 0.0);
+        }
+        (
+            last.1.saturating_sub(first.1) as f64 / elapsed,
+            last.2.saturating_sub(first.2) as f64 / elapsed,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Remaining {
+    files: u64,
+    bytes: u64,
+    bytes_known: bool,
+}
+
+/// Bytes-weighted ETA; the file rate is only used when sizes are unknown,
+/// since it is badly skewed when file sizes vary.
+fn estimate_remaining(
+    remaining: Remaining,
+    files_per_sec: f64,
+    bytes_per_sec: f64,
+) -> Option<Duration> {
+    if remaining.bytes_known {
+        return (bytes_per_sec >= 1.0)
+            .then(|| Duration::from_secs_f64(remaining.bytes as f64 / bytes_per_sec));
+    }
+    (files_per_sec > MIN_FILES_PER_SEC)
+        .then(|| Duration::from_secs_f64(remaining.files as f64 / files_per_sec))
+}
+
 fn send_latest(
     tx: &Sender<CompressionProgress>,
     latest_rx: &Receiver<CompressionProgress>,
@@ -240,6 +295,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(10)),
             bytes_original: Arc::new(AtomicU64::new(0)),
             bytes_compressed: Arc::new(AtomicU64::new(0)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
         let (mut reporter, _rx) = ProgressReporter::new(counters, Arc::from("Test"));
         reporter.stop();
@@ -252,6 +309,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(10)),
             bytes_original: Arc::new(AtomicU64::new(1000)),
             bytes_compressed: Arc::new(AtomicU64::new(600)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
         let fp = counters.files_processed.clone();
         let (mut reporter, rx) = ProgressReporter::new(counters, Arc::from("Test"));
@@ -273,6 +332,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(10)),
             bytes_original: Arc::new(AtomicU64::new(1000)),
             bytes_compressed: Arc::new(AtomicU64::new(600)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
         let (mut reporter, rx) = ProgressReporter::new(counters, Arc::from("Test"));
         reporter.mark_done();
@@ -293,6 +354,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(0)),
             bytes_original: Arc::new(AtomicU64::new(0)),
             bytes_compressed: Arc::new(AtomicU64::new(0)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
         let (mut reporter, rx) = ProgressReporter::new(counters, Arc::from("Empty"));
         reporter.mark_done();
@@ -309,6 +372,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(1)),
             bytes_original: Arc::new(AtomicU64::new(1000)),
             bytes_compressed: Arc::new(AtomicU64::new(600)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
 
         let (mut reporter, rx) = ProgressReporter::new(counters, Arc::from("Clamp"));
@@ -328,6 +393,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(1)),
             bytes_original: Arc::new(AtomicU64::new(1000)),
             bytes_compressed: Arc::new(AtomicU64::new(600)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
 
         let (mut reporter, rx) = ProgressReporter::new(counters, Arc::from("CompleteGate"));
@@ -353,6 +420,8 @@ mod tests {
             files_total: Arc::new(AtomicU64::new(10)),
             bytes_original: Arc::new(AtomicU64::new(1_000)),
             bytes_compressed: Arc::new(AtomicU64::new(600)),
+            bytes_total: Arc::new(AtomicU64::new(0)),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
         };
 
         let (mut reporter, rx) =
@@ -367,4 +436,46 @@ mod tests {
 
         reporter.stop();
     }
+
+    #[test]
+    fn rate_window_averages_over_the_window() {
+        let start = Instant::now();
+        let mut rates = RateWindow::default();
+        rates.push(start, 0, 0);
+        rates.push(start + Duration::from_secs(1), 1, 100);
+        rates.push(start + Duration::from_secs(2), 1, 400);
+        let (files_per_sec, bytes_per_sec) = rates.rates();
+        assert!((files_per_sec - 0.5).abs() < 1e-9);
+        assert!((bytes_per_sec - 200.0).abs() < 1e-9);
+
+        // Samples older than the window stop counting.
+        rates.push(start + Duration::from_secs(7), 1, 400);
+        rates.push(start + Duration::from_secs(8), 2, 1_000);
+        let (_, bytes_per_sec) = rates.rates();
+        assert!((bytes_per_sec - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn eta_is_weighted_by_remaining_bytes() {
+        // Few large files left: the file rate would claim 1s.
+        let remaining = Remaining {
+            files: 10,
+            bytes: 1_000_000,
+            bytes_known: true,
+        };
+        assert_eq!(
+            estimate_remaining(remaining, 10.0, 10_000.0),
+            Some(Duration::from_secs(100))
+        );
+        assert_eq!(estimate_remaining(remaining, 10.0, 0.0), None);
+
+        let unknown_sizes = Remaining {
+            bytes_known: false,
+            ..remaining
+        };
+        assert_eq!(
+            estimate_remaining(unknown_sizes, 10.0, 0.0),
+            Some(Duration::from_secs(1))
+        );
+    }
 }
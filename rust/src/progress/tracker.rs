@@ -17,6 +17,9 @@ pub struct CompressionProgress {
     pub bytes_compressed: u64,
     pub bytes_saved: u64,
     pub estimated_time_remaining: Option<Duration>,
+    /// Logical bytes finished per second, averaged over recent seconds.
+    #[serde(default)]
+    pub throughput_bytes_per_sec: u64,
     pub is_complete: bool,
 }
 
@@ -57,6 +60,7 @@ mod tests {
             bytes_compressed: 0,
             bytes_saved: 0,
             estimated_time_remaining: None,
+            throughput_bytes_per_sec: 0,
             is_complete: false,
         };
         assert_eq!(p.fraction(), 0.0);
@@ -73,6 +77,7 @@ mod tests {
             bytes_compressed: 600,
             bytes_saved: 400,
             estimated_time_remaining: Some(Duration::from_secs(30)),
+            throughput_bytes_per_sec: 0,
             is_complete: false,
         };
         assert!((p.fraction() - 0.5).abs() < f64::EPSILON);
@@ -0,0 +1,182 @@
+//! Kernel anti-cheat detection for game directories.
+//!
+//! Easy Anti-Cheat, BattlEye and Riot Vanguard load kernel drivers that
+//! hash-check the game's files. A WOF-compressed file reads back the same
+//! bytes, but some builds compare on-disk attributes or sizes as well and
+//! refuse to launch after compression. Automation therefore treats these
+//! games with caution and the UI flags them.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+use walkdir::WalkDir;
+
+use crate::utils::normalize_path_key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiCheat {
+    EasyAntiCheat,
+    BattlEye,
+    Vanguard,
+}
+
+impl AntiCheat {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::EasyAntiCheat => "Easy Anti-Cheat",
+            Self::BattlEye => "BattlEye",
+            Self::Vanguard => "Riot Vanguard",
+        }
+    }
+}
+
+/// What unattended compression does with a game that ships kernel
+/// anti-cheat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AntiCheatPolicy {
+    /// Log a warning and compress anyway.
+    Warn,
+    /// Leave the game alone.
+    #[default]
+    Skip,
+}
+
+impl AntiCheatPolicy {
+    pub fn from_allow_compression(allow: bool) -> Self {
+        if allow {
+            Self::Warn
+        } else {
+            Self::Skip
+        }
+    }
+}
+
+const EAC_DIRS: &[&str] = &["EasyAntiCheat", "EasyAntiCheat_EOS"];
+const EAC_FILES: &[&str] = &[
+    "EasyAntiCheat_x64.dll",
+    "EasyAntiCheat_x86.dll",
+    "EasyAntiCheat_EOS_Setup.exe",
+    "EasyAntiCheat_Setup.exe",
+    "start_protected_game.exe",
+];
+const BATTLEYE_DIRS: &[&str] = &["BattlEye"];
+const BATTLEYE_FILES: &[&str] = &[
+    "BEService.exe",
+    "BEService_x64.exe",
+    "BEClient.dll",
+    "BEClient_x64.dll",
+    "BEDaisy.sys",
+];
+const VANGUARD_DIRS: &[&str] = &["Riot Vanguard"];
+const VANGUARD_FILES: &[&str] = &[
+    "vgc.exe",
+    "vgk.sys",
+    "VALORANT-Win64-Shipping.exe",
+    "League of Legends.exe",
+];
+
+/// Matches the markers usually sit in: the install root, `Binaries/Win64`
+/// and the anti-cheat's own folder.
+const MAX_SCAN_DEPTH: usize = 3;
+
+static DETECTION_CACHE: LazyLock<RwLock<HashMap<String, Option<AntiCheat>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Kernel anti-cheat shipped with the game at `game_path`, if any. Cached
+/// per path; anti-cheat is part of the install and rarely changes.
+pub fn detect_anticheat(game_path: &Path) -> Option<AntiCheat> {
+    let key = normalize_path_key(game_path);
+    let cached = DETECTION_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Anti-cheat detection cache lock poisoned (read); recovering");
+            poisoned.into_inner()
+        })
+        .get(&key)
+        .copied();
+    if let Some(detected) = cached {
+        return detected;
+    }
+
+    let detected = scan_for_anticheat(game_path);
+    if let Some(anticheat) = detected {
+        log::info!("{} detected in {}", anticheat.label(), game_path.display());
+    }
+    DETECTION_CACHE
+        .write()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Anti-cheat detection cache lock poisoned (write); recovering");
+            poisoned.into_inner()
+        })
+        .insert(key, detected);
+    detected
+}
+
+pub fn uses_kernel_anticheat(game_path: &Path) -> bool {
+    detect_anticheat(game_path).is_some()
+}
+
+fn scan_for_anticheat(game_path: &Path) -> Option<AntiCheat> {
+    if !game_path.is_dir() {
+        return None;
+    }
+
+    WalkDir::new(game_path)
+        .min_depth(1)
+        .max_depth(MAX_SCAN_DEPTH)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| !entry.path_is_symlink())
+        .find_map(|entry| {
+            let name = entry.file_name().to_str()?;
+            fingerprint(name, entry.file_type().is_dir())
+        })
+}
+
+fn fingerprint(name: &str, is_dir: bool) -> Option<AntiCheat> {
+    let matches = |names: &[&str]| names.iter().any(|n| name.eq_ignore_ascii_case(n));
+    let (eac, battleye, vanguard) = if is_dir {
+        (EAC_DIRS, BATTLEYE_DIRS, VANGUARD_DIRS)
+    } else {
+        (EAC_FILES, BATTLEYE_FILES, VANGUARD_FILES)
+    };
+    if matches(eac) {
+        Some(AntiCheat::EasyAntiCheat)
+    } else if matches(battleye) {
+        Some(AntiCheat::BattlEye)
+    } else if matches(vanguard) {
+        Some(AntiCheat::Vanguard)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fingerprints_match_known_anticheat_markers() {
+        let eac = TempDir::new().unwrap();
+        std::fs::create_dir_all(eac.path().join("EasyAntiCheat")).unwrap();
+        assert_eq!(detect_anticheat(eac.path()), Some(AntiCheat::EasyAntiCheat));
+
+        let battleye = TempDir::new().unwrap();
+        let binaries = battleye.path().join("Binaries").join("Win64");
+        std::fs::create_dir_all(&binaries).unwrap();
+        std::fs::write(binaries.join("beclient_x64.dll"), b"fake").unwrap();
+        assert_eq!(detect_anticheat(battleye.path()), Some(AntiCheat::BattlEye));
+
+        let vanguard = TempDir::new().unwrap();
+        std::fs::write(vanguard.path().join("vgc.exe"), b"fake").unwrap();
+        assert!(uses_kernel_anticheat(vanguard.path()));
+
+        let plain = TempDir::new().unwrap();
+        std::fs::write(plain.path().join("BattlEye.txt"), b"readme").unwrap();
+        std::fs::create_dir_all(plain.path().join("vgc.exe")).unwrap();
+        assert_eq!(detect_anticheat(plain.path()), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! Cloud placeholder detection (OneDrive "online-only" files and other
+//! Cloud Files API providers).
+//!
+//! A placeholder has no local data; opening it for compression makes the
+//! provider download the whole file first, which can fill the disk rather
+//! than free it. Placeholders are recognised by their recall attributes or
+//! by the cloud reparse tag, both readable without hydrating the file.
+
+use std::path::Path;
+
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0000_0400;
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+/// `IO_REPARSE_TAG_CLOUD`; providers set sub-tags in the `0xF000` bits.
+const IO_REPARSE_TAG_CLOUD: u32 = 0x9000_001A;
+const IO_REPARSE_TAG_CLOUD_MASK: u32 = 0x0000_F000;
+
+/// Whether a file with these attributes (and reparse tag, 0 if none) is a
+/// cloud placeholder whose data is not stored locally.
+pub fn is_placeholder(attributes: u32, reparse_tag: u32) -> bool {
+    if attributes
+        & (FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS
+            | FILE_ATTRIBUTE_RECALL_ON_OPEN
+            | FILE_ATTRIBUTE_OFFLINE)
+        != 0
+    {
+        return true;
+    }
+    attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0
+        && reparse_tag & !IO_REPARSE_TAG_CLOUD_MASK == IO_REPARSE_TAG_CLOUD
+}
+
+/// Whether `path` is a cloud placeholder. The reparse tag is only queried
+/// when the attributes mark a reparse point, and the file is opened with
+/// attribute access only, so the check never triggers a download.
+#[cfg(windows)]
+pub fn is_cloud_placeholder(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return false;
+    };
+    let attributes = metadata.file_attributes();
+    if attributes & FILE_ATTRIBUTE_REPARSE_POINT == 0 {
+        return is_placeholder(attributes, 0);
+    }
+    is_placeholder(attributes, reparse_tag(path).unwrap_or(0))
+}
+
+#[cfg(not(windows))]
+pub fn is_cloud_placeholder(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn reparse_tag(path: &Path) -> Option<u32> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::Storage::FileSystem::{
+        FileAttributeTagInfo, GetFileInformationByHandleEx, FILE_ATTRIBUTE_TAG_INFO,
+    };
+
+    const FILE_READ_ATTRIBUTES: u32 = 0x0080;
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    const FILE_FLAG_OPEN_REPARSE_POINT: u32 = 0x0020_0000;
+
+    let file = std::fs::OpenOptions::new()
+        .access_mode(FILE_READ_ATTRIBUTES)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS | FILE_FLAG_OPEN_REPARSE_POINT)
+        .open(path)
+        .ok()?;
+    let mut info = FILE_ATTRIBUTE_TAG_INFO::default();
+    unsafe {
+        GetFileInformationByHandleEx(
+            HANDLE(file.as_raw_handle() as _),
+            FileAttributeTagInfo,
+            (&mut info as *mut FILE_ATTRIBUTE_TAG_INFO).cast(),
+            std::mem::size_of::<FILE_ATTRIBUTE_TAG_INFO>() as u32,
+        )
+    }
+    .ok()?;
+    Some(info.ReparseTag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recall_attributes_mark_placeholders() {
+        assert!(is_placeholder(FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS, 0));
+        assert!(is_placeholder(FILE_ATTRIBUTE_RECALL_ON_OPEN, 0));
+        assert!(is_placeholder(FILE_ATTRIBUTE_OFFLINE, 0));
+    }
+
+    #[test]
+    fn cloud_reparse_tags_mark_placeholders() {
+        // OneDrive uses IO_REPARSE_TAG_CLOUD_3.
+        assert!(is_placeholder(FILE_ATTRIBUTE_REPARSE_POINT, 0x9000_301A));
+        assert!(is_placeholder(
+            FILE_ATTRIBUTE_REPARSE_POINT,
+            IO_REPARSE_TAG_CLOUD
+        ));
+        // Without the reparse attribute the tag field is meaningless.
+        assert!(!is_placeholder(0, IO_REPARSE_TAG_CLOUD));
+    }
+
+    #[test]
+    fn local_and_wof_files_are_not_placeholders() {
+        const IO_REPARSE_TAG_WOF: u32 = 0x8000_0017;
+        const FILE_ATTRIBUTE_ARCHIVE: u32 = 0x0000_0020;
+        assert!(!is_placeholder(FILE_ATTRIBUTE_ARCHIVE, 0));
+        assert!(!is_placeholder(
+            FILE_ATTRIBUTE_REPARSE_POINT,
+            IO_REPARSE_TAG_WOF
+        ));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Games using DirectStorage must NOT be compressed, as WOF
 //! compression interferes with DirectStorage's GPU-direct I/O path.
+//! Results are cached per folder and can be overridden by the user; see
+//! [`super::directstorage_cache`].
 
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
@@ -9,6 +11,7 @@ use std::path::Path;
 
 use walkdir::WalkDir;
 
+use super::directstorage_cache::{self, DirectStorageConfidence};
 use super::known_games::{is_known_directstorage_game, learn_directstorage_game};
 
 const DIRECTSTORAGE_DLLS: &[&str] = &["dstorage.dll", "dstoragecore.dll"];
@@ -18,17 +21,34 @@ const PE_HEADER_SCAN_MAX_BYTES: u64 = 1024 * 1024;
 const MAX_PE_IMPORT_DESCRIPTORS: usize = 2048;
 const MAX_IMPORT_DLL_NAME_BYTES: usize = 260;
 
+/// Whether `game_path` must be kept uncompressed, honouring any user
+/// override before the detected result.
 pub fn is_directstorage_game(game_path: &Path) -> bool {
     if !game_path.is_dir() {
         return false;
     }
+    if let Some(allow) = directstorage_cache::override_for(game_path) {
+        return !allow;
+    }
+    detect_directstorage(game_path).is_some()
+}
+
+/// How confidently `game_path` was detected as a DirectStorage game,
+/// ignoring overrides. `None` if it was not detected.
+pub fn detect_directstorage(game_path: &Path) -> Option<DirectStorageConfidence> {
+    if !game_path.is_dir() {
+        return None;
+    }
+    directstorage_cache::lookup_or_scan(game_path, scan_for_directstorage)
+}
 
+fn scan_for_directstorage(game_path: &Path) -> Option<DirectStorageConfidence> {
     if is_known_directstorage_game(game_path) {
         log::info!(
             "DirectStorage detected via known-games database: {}",
             game_path.display()
         );
-        return true;
+        return Some(DirectStorageConfidence::KnownList);
     }
 
     for entry in WalkDir::new(game_path)
@@ -56,7 +76,7 @@ pub fn is_directstorage_game(game_path: &Path) -> bool {
                 );
 
                 learn_directstorage_game(game_path);
-                return true;
+                return Some(DirectStorageConfidence::DsDllPresent);
             }
             if DIRECTSTORAGE_MANIFESTS
                 .iter()
@@ -69,7 +89,7 @@ pub fn is_directstorage_game(game_path: &Path) -> bool {
                 );
 
                 learn_directstorage_game(game_path);
-                return true;
+                return Some(DirectStorageConfidence::DsDllPresent);
             }
 
             if is_pe_candidate(entry.path()) && pe_mentions_directstorage(entry.path()) {
@@ -80,12 +100,12 @@ pub fn is_directstorage_game(game_path: &Path) -> bool {
                 );
 
                 learn_directstorage_game(game_path);
-                return true;
+                return Some(DirectStorageConfidence::MetadataHeuristic);
             }
         }
     }
 
-    false
+    None
 }
 
 fn is_pe_candidate(path: &Path) -> bool {
@@ -617,6 +637,39 @@ mod tests {
         assert!(is_directstorage_game(&game_dir));
     }
 
+    #[test]
+    fn confidence_reflects_evidence_and_overrides_win() {
+        let dll = TempDir::new().unwrap();
+        let dll_game = dll.path().join("OverrideDllGame");
+        std::fs::create_dir(&dll_game).unwrap();
+        std::fs::write(dll_game.join("dstorage.dll"), b"fake").unwrap();
+        assert_eq!(
+            detect_directstorage(&dll_game),
+            Some(DirectStorageConfidence::DsDllPresent)
+        );
+
+        let pe = TempDir::new().unwrap();
+        let pe_game = pe.path().join("OverridePeGame");
+        std::fs::create_dir(&pe_game).unwrap();
+        std::fs::write(
+            pe_game.join("game.exe"),
+            minimal_pe_with_import("dstorage.dll"),
+        )
+        .unwrap();
+        assert_eq!(
+            detect_directstorage(&pe_game),
+            Some(DirectStorageConfidence::MetadataHeuristic)
+        );
+
+        directstorage_cache::set_override(&pe_game, true).unwrap();
+        assert!(!is_directstorage_game(&pe_game));
+        assert!(detect_directstorage(&pe_game).is_some());
+
+        let plain = TempDir::new().unwrap();
+        directstorage_cache::set_override(plain.path(), false).unwrap();
+        assert!(is_directstorage_game(plain.path()));
+    }
+
     fn pe_mentions_directstorage_bytes_for_test(bytes: &[u8]) -> bool {
         is_pe_file(bytes) && pe_imports_directstorage(bytes)
     }
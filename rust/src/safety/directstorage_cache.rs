@@ -0,0 +1,292 @@
+//! Persisted DirectStorage detection results and user overrides.
+//!
+//! A full detection pass walks the game folder and parses PE import
+//! tables, and it runs for every game on every discovery. Results are
+//! cached per path with the folder's change token and reused until the
+//! folder changes or [`DETECTION_VERSION`] is bumped. Users can override a
+//! result, most usefully to compress a game that was flagged by mistake.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(not(test))]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::{LazyLock, RwLock};
+
+use crate::discovery::cache::{compute_change_token, ChangeToken};
+use crate::utils::normalize_path_key;
+
+const STORE_FILE_NAME: &str = "directstorage_detections.json";
+const MAX_STORED_DETECTIONS: usize = 16_384;
+/// Bump when detection rules change so stale results are rescanned.
+const DETECTION_VERSION: u32 = 1;
+
+/// Why a game was classified as using DirectStorage, strongest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum DirectStorageConfidence {
+    /// Listed in the embedded or learned known-games database.
+    KnownList,
+    /// Ships the DirectStorage runtime DLLs or manifest.
+    DsDllPresent,
+    /// An executable imports the runtime; it may only load it optionally.
+    MetadataHeuristic,
+}
+
+impl DirectStorageConfidence {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::KnownList => "known DirectStorage game",
+            Self::DsDllPresent => "DirectStorage runtime present",
+            Self::MetadataHeuristic => "executable imports DirectStorage",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredDetection {
+    token: ChangeToken,
+    confidence: Option<DirectStorageConfidence>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct StoreFile {
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    detections: HashMap<String, StoredDetection>,
+    /// `true` allows compression despite detection; `false` blocks it
+    /// even when nothing was detected.
+    #[serde(default)]
+    overrides: HashMap<String, bool>,
+}
+
+#[cfg(not(test))]
+static STORE_DIR_CREATED: AtomicBool = AtomicBool::new(false);
+static STORE_DIRTY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+static STORE: LazyLock<RwLock<StoreFile>> = LazyLock::new(|| RwLock::new(load_store_file()));
+
+/// Cached detection for `game_path`, running `scan` when there is no
+/// result for the folder's current change token.
+pub(crate) fn lookup_or_scan(
+    game_path: &Path,
+    scan: impl FnOnce(&Path) -> Option<DirectStorageConfidence>,
+) -> Option<DirectStorageConfidence> {
+    let key = normalize_path_key(game_path);
+    let token = compute_change_token(game_path, false);
+    let cached = with_store_read(|store| {
+        store
+            .detections
+            .get(&key)
+            .filter(|stored| stored.token == token)
+            .map(|stored| stored.confidence)
+    });
+    if let Some(confidence) = cached {
+        return confidence;
+    }
+
+    let confidence = scan(game_path);
+    with_store_write(|store| {
+        if store.detections.len() >= MAX_STORED_DETECTIONS && !store.detections.contains_key(&key) {
+            if let Some(evicted) = store.detections.keys().next().cloned() {
+                store.detections.remove(&evicted);
+            }
+        }
+        store
+            .detections
+            .insert(key, StoredDetection { token, confidence });
+    });
+    STORE_DIRTY.store(true, Ordering::Relaxed);
+    confidence
+}
+
+/// Forget every cached detection, e.g. after the known-games database
+/// changed. Overrides are kept.
+pub(crate) fn invalidate_detections() {
+    let had_detections = with_store_write(|store| {
+        let had_detections = !store.detections.is_empty();
+        store.detections.clear();
+        had_detections
+    });
+    if had_detections {
+        STORE_DIRTY.store(true, Ordering::Relaxed);
+    }
+}
+
+/// User override for `game_path`: `Some(true)` to allow compression,
+/// `Some(false)` to always treat the game as DirectStorage.
+pub fn override_for(game_path: &Path) -> Option<bool> {
+    let key = normalize_path_key(game_path);
+    with_store_read(|store| store.overrides.get(&key).copied())
+}
+
+/// Record a user override and persist it immediately.
+pub fn set_override(game_path: &Path, allow: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let key = normalize_path_key(game_path);
+    let snapshot = with_store_write(|store| {
+        store.overrides.insert(key, allow);
+        store.clone()
+    });
+    log::info!(
+        "DirectStorage override for {}: {}",
+        game_path.display(),
+        if allow { "allow compression" } else { "block" }
+    );
+    save_store_file(&snapshot)
+}
+
+/// Drop the user override for `game_path`. Returns `false` if none was set.
+pub fn clear_override(game_path: &Path) -> Result<bool, Box<dyn std::error::Error>> {
+    let key = normalize_path_key(game_path);
+    let snapshot = with_store_write(|store| store.overrides.remove(&key).map(|_| store.clone()));
+    match snapshot {
+        Some(snapshot) => save_store_file(&snapshot).map(|()| true),
+        None => Ok(false),
+    }
+}
+
+pub fn persist_if_dirty() {
+    if !STORE_DIRTY.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    let snapshot = with_store_read(Clone::clone);
+    if let Err(e) = save_store_file(&snapshot) {
+        log::warn!("Failed to persist DirectStorage detections: {e}");
+        STORE_DIRTY.store(true, Ordering::Relaxed);
+    }
+}
+
+fn with_store_read<R>(f: impl FnOnce(&StoreFile) -> R) -> R {
+    match STORE.read() {
+        Ok(guard) => f(&guard),
+        Err(poisoned) => {
+            log::warn!("DirectStorage detection store lock poisoned (read); recovering");
+            f(&poisoned.into_inner())
+        }
+    }
+}
+
+fn with_store_write<R>(f: impl FnOnce(&mut StoreFile) -> R) -> R {
+    match STORE.write() {
+        Ok(mut guard) => f(&mut guard),
+        Err(poisoned) => {
+            log::warn!("DirectStorage detection store lock poisoned (write); recovering");
+            f(&mut poisoned.into_inner())
+        }
+    }
+}
+
+fn load_store_file() -> StoreFile {
+    let empty = || StoreFile {
+        version: DETECTION_VERSION,
+        ..StoreFile::default()
+    };
+    let Ok(path) = store_path() else {
+        return empty();
+    };
+    let Ok(contents) = fs::read_to_string(path) else {
+        return empty();
+    };
+
+    let mut store = serde_json::from_str::<StoreFile>(&contents).unwrap_or_else(|e| {
+        log::warn!("Failed to parse DirectStorage detections: {e}");
+        empty()
+    });
+    if store.version != DETECTION_VERSION {
+        // Overrides are user decisions and survive rule changes.
+        log::info!(
+            "DirectStorage detection rules changed (v{} -> v{}); rescanning",
+            store.version,
+            DETECTION_VERSION
+        );
+        store.detections.clear();
+        store.version = DETECTION_VERSION;
+    }
+    store
+}
+
+fn save_store_file(store: &StoreFile) -> Result<(), Box<dyn std::error::Error>> {
+    let path = store_path()?;
+    let json = serde_json::to_string(store)?;
+    crate::utils::atomic_write(&path, json.as_bytes())?;
+    Ok(())
+}
+
+fn store_path() -> Result<PathBuf, std::io::Error> {
+    #[cfg(test)]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        static TEST_CONFIG_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-directstorage-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+
+        fs::create_dir_all(&*TEST_CONFIG_DIR)?;
+        Ok(TEST_CONFIG_DIR.join(STORE_FILE_NAME))
+    }
+
+    #[cfg(not(test))]
+    {
+        let compact_games_dir = crate::paths::data_dir()?;
+
+        if !STORE_DIR_CREATED.load(Ordering::Relaxed) {
+            fs::create_dir_all(&compact_games_dir)?;
+            STORE_DIR_CREATED.store(true, Ordering::Relaxed);
+        }
+
+        Ok(compact_games_dir.join(STORE_FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detection_is_reused_until_the_folder_changes() {
+        let dir = TempDir::new().unwrap();
+        let scans = Cell::new(0);
+        let scan = |_: &Path| {
+            scans.set(scans.get() + 1);
+            Some(DirectStorageConfidence::DsDllPresent)
+        };
+
+        assert_eq!(
+            lookup_or_scan(dir.path(), scan),
+            Some(DirectStorageConfidence::DsDllPresent)
+        );
+        assert_eq!(
+            lookup_or_scan(dir.path(), scan),
+            Some(DirectStorageConfidence::DsDllPresent)
+        );
+        assert_eq!(scans.get(), 1);
+
+        std::fs::write(dir.path().join("patch.pak"), b"new").unwrap();
+        lookup_or_scan(dir.path(), scan);
+        assert_eq!(scans.get(), 2);
+    }
+
+    #[test]
+    fn overrides_round_trip() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(override_for(dir.path()), None);
+
+        set_override(dir.path(), true).unwrap();
+        assert_eq!(override_for(dir.path()), Some(true));
+        set_override(dir.path(), false).unwrap();
+        assert_eq!(override_for(dir.path()), Some(false));
+
+        assert!(clear_override(dir.path()).unwrap());
+        assert!(!clear_override(dir.path()).unwrap());
+        assert_eq!(override_for(dir.path()), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Filesystem capability detection for the volume holding a path.
+//!
+//! WOF compression only exists on NTFS. On ReFS, FAT32 and exFAT every
+//! WOF call fails with an API error that tells the user nothing, so the
+//! engine checks the volume before starting and discovery tags games
+//! installed on such volumes.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FilesystemKind {
+    Ntfs,
+    Refs,
+    Fat32,
+    ExFat,
+    /// A filesystem this app has no special knowledge of (UDF, network
+    /// redirectors and so on).
+    Other,
+    /// The volume could not be queried.
+    #[default]
+    Unknown,
+}
+
+impl FilesystemKind {
+    /// Map a name reported by `GetVolumeInformationW`.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "NTFS" => Self::Ntfs,
+            "REFS" => Self::Refs,
+            "FAT" | "FAT32" => Self::Fat32,
+            "EXFAT" => Self::ExFat,
+            "" => Self::Unknown,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ntfs => "NTFS",
+            Self::Refs => "ReFS",
+            Self::Fat32 => "FAT32",
+            Self::ExFat => "exFAT",
+            Self::Other => "an unrecognised filesystem",
+            Self::Unknown => "an unknown filesystem",
+        }
+    }
+
+    /// Whether WOF compression can work here. An unknown volume is given
+    /// the benefit of the doubt; the WOF calls report any real failure.
+    pub fn supports_wof(self) -> bool {
+        matches!(self, Self::Ntfs | Self::Unknown)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FsCapabilities {
+    pub filesystem: FilesystemKind,
+    pub supports_wof: bool,
+    /// Allocation unit in bytes, if the volume reported it.
+    pub cluster_size: Option<u32>,
+}
+
+impl FsCapabilities {
+    fn for_kind(filesystem: FilesystemKind, cluster_size: Option<u32>) -> Self {
+        Self {
+            filesystem,
+            supports_wof: filesystem.supports_wof(),
+            cluster_size,
+        }
+    }
+}
+
+static CAPABILITIES_CACHE: LazyLock<RwLock<HashMap<String, FsCapabilities>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Capabilities of the volume containing `path`, cached per mount point.
+pub fn fs_capabilities(path: &Path) -> FsCapabilities {
+    let Some(mount_point) = volume_mount_point(path) else {
+        return FsCapabilities::for_kind(FilesystemKind::Unknown, None);
+    };
+
+    let cached = CAPABILITIES_CACHE
+        .read()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Filesystem capability cache lock poisoned (read); recovering");
+            poisoned.into_inner()
+        })
+        .get(&mount_point)
+        .copied();
+    if let Some(capabilities) = cached {
+        return capabilities;
+    }
+
+    let capabilities = query_capabilities(&mount_point);
+    CAPABILITIES_CACHE
+        .write()
+        .unwrap_or_else(|poisoned| {
+            log::warn!("Filesystem capability cache lock poisoned (write); recovering");
+            poisoned.into_inner()
+        })
+        .insert(mount_point, capabilities);
+    capabilities
+}
+
+/// Whether the volume holding `path` is known not to support WOF.
+pub fn is_unsupported_filesystem(path: &Path) -> bool {
+    !fs_capabilities(path).supports_wof
+}
+
+#[cfg(windows)]
+fn volume_mount_point(path: &Path) -> Option<String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetVolumePathNameW;
+
+    let wide = crate::utils::wide_path(path);
+    let mut buffer = vec![0_u16; 1024];
+    unsafe { GetVolumePathNameW(PCWSTR(wide.as_ptr()), &mut buffer) }.ok()?;
+    let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    let mount_point = String::from_utf16_lossy(&buffer[..end]).to_ascii_lowercase();
+    Some(
+        mount_point
+            .strip_prefix(r"\\?\")
+            .map(str::to_string)
+            .unwrap_or(mount_point),
+    )
+}
+
+#[cfg(not(windows))]
+fn volume_mount_point(_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(windows)]
+fn query_capabilities(mount_point: &str) -> FsCapabilities {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{GetDiskFreeSpaceW, GetVolumeInformationW};
+
+    let wide = crate::utils::wide_null_str(mount_point);
+    let mut name = [0_u16; 64];
+    let filesystem = match unsafe {
+        GetVolumeInformationW(
+            PCWSTR(wide.as_ptr()),
+            None,
+            None,
+            None,
+            None,
+            Some(&mut name),
+        )
+    } {
+        Ok(()) => {
+            let end = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+            FilesystemKind::from_name(&String::from_utf16_lossy(&name[..end]))
+        }
+        Err(e) => {
+            log::debug!("GetVolumeInformationW failed for {mount_point}: {e}");
+            FilesystemKind::Unknown
+        }
+    };
+
+    let mut sectors_per_cluster = 0_u32;
+    let mut bytes_per_sector = 0_u32;
+    let cluster_size = unsafe {
+        GetDiskFreeSpaceW(
+            PCWSTR(wide.as_ptr()),
+            Some(&mut sectors_per_cluster),
+            Some(&mut bytes_per_sector),
+            None,
+            None,
+        )
+    }
+    .ok()
+    .and_then(|()| sectors_per_cluster.checked_mul(bytes_per_sector))
+    .filter(|&size| size > 0);
+
+    FsCapabilities::for_kind(filesystem, cluster_size)
+}
+
+#[cfg(not(windows))]
+fn query_capabilities(_mount_point: &str) -> FsCapabilities {
+    FsCapabilities::for_kind(FilesystemKind::Unknown, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filesystem_names_map_to_wof_support() {
+        assert_eq!(FilesystemKind::from_name("NTFS"), FilesystemKind::Ntfs);
+        assert_eq!(FilesystemKind::from_name("ReFS"), FilesystemKind::Refs);
+        assert_eq!(FilesystemKind::from_name("FAT32"), FilesystemKind::Fat32);
+        assert_eq!(FilesystemKind::from_name("exFAT"), FilesystemKind::ExFat);
+        assert_eq!(FilesystemKind::from_name("UDF"), FilesystemKind::Other);
+        assert_eq!(FilesystemKind::from_name(""), FilesystemKind::Unknown);
+
+        assert!(FilesystemKind::Ntfs.supports_wof());
+        assert!(FilesystemKind::Unknown.supports_wof());
+        assert!(!FilesystemKind::Refs.supports_wof());
+        assert!(!FilesystemKind::ExFat.supports_wof());
+        assert!(!FilesystemKind::Other.supports_wof());
+    }
+}
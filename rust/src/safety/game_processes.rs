@@ -0,0 +1,171 @@
+//! Shared sampling of discovered games' processes.
+//!
+//! Crash rollback, launch-lag timing and the running-games stream all
+//! watch the processes of discovered games over time. Rather than each
+//! polling the process list on its own thread, one sampler thread
+//! refreshes it once a second while anyone is subscribed and hands every
+//! subscriber the same sample.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use super::process::owning_game;
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A process whose executable lies inside a discovered game.
+#[derive(Debug, Clone)]
+pub struct GameProcess {
+    pub pid: u32,
+    /// The deepest discovered game path containing the executable.
+    pub game: PathBuf,
+    /// Bytes read since the process started.
+    pub total_read_bytes: u64,
+}
+
+/// One refresh of the process list.
+#[derive(Debug, Clone)]
+pub struct GameProcessSample {
+    pub taken_at: Instant,
+    pub processes: Vec<GameProcess>,
+}
+
+impl GameProcessSample {
+    /// Games with at least one process, sorted.
+    pub fn running_games(&self) -> Vec<PathBuf> {
+        let mut games: Vec<PathBuf> = self
+            .processes
+            .iter()
+            .map(|process| process.game.clone())
+            .collect();
+        games.sort();
+        games.dedup();
+        games
+    }
+}
+
+/// Returned by [`subscribe`]; pass it to [`unsubscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscriptionId(u64);
+
+type Observer = Box<dyn FnMut(&GameProcessSample) -> bool + Send>;
+
+struct Subscriber {
+    id: SubscriptionId,
+    observer: Observer,
+}
+
+static SUBSCRIBERS: Mutex<Vec<Subscriber>> = Mutex::new(Vec::new());
+static NEXT_SUBSCRIPTION_ID: AtomicU64 = AtomicU64::new(1);
+/// Guarded by the subscribers lock so a subscriber never lands just as the
+/// sampler thread decides to exit.
+static SAMPLER_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn subscribers_lock() -> MutexGuard<'static, Vec<Subscriber>> {
+    SUBSCRIBERS.lock().unwrap_or_else(|poisoned| {
+        log::warn!("Game process subscribers lock poisoned; recovering");
+        poisoned.into_inner()
+    })
+}
+
+/// Call `observer` with every sample until it returns `false` or is
+/// unsubscribed. It runs on the sampler thread with the subscriber list
+/// locked, so it must return quickly and must not subscribe or
+/// unsubscribe; hand slow work such as decompression to another thread.
+pub fn subscribe(
+    observer: impl FnMut(&GameProcessSample) -> bool + Send + 'static,
+) -> SubscriptionId {
+    let id = SubscriptionId(NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed));
+    let mut guard = subscribers_lock();
+    guard.push(Subscriber {
+        id,
+        observer: Box::new(observer),
+    });
+    if !SAMPLER_ACTIVE.swap(true, Ordering::AcqRel) {
+        if let Err(e) = std::thread::Builder::new()
+            .name("compact-games-game-processes".to_string())
+            .spawn(sampler_loop)
+        {
+            log::warn!("Failed to spawn game process sampler: {e}");
+            SAMPLER_ACTIVE.store(false, Ordering::Release);
+        }
+    }
+    id
+}
+
+/// Stop calling the observer behind `id`. Once this returns it is not
+/// running and will not run again.
+pub fn unsubscribe(id: SubscriptionId) {
+    subscribers_lock().retain(|subscriber| subscriber.id != id);
+}
+
+fn sampler_loop() {
+    let refresh = ProcessRefreshKind::nothing()
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        .with_disk_usage();
+    let mut system = System::new();
+
+    loop {
+        let game_paths = crate::discovery::index::game_paths();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+        let sample = GameProcessSample {
+            taken_at: Instant::now(),
+            processes: system
+                .processes()
+                .iter()
+                .filter_map(|(pid, process)| {
+                    let game = owning_game(process.exe()?, &game_paths)?;
+                    Some(GameProcess {
+                        pid: pid.as_u32(),
+                        game: game.clone(),
+                        total_read_bytes: process.disk_usage().total_read_bytes,
+                    })
+                })
+                .collect(),
+        };
+
+        {
+            let mut guard = subscribers_lock();
+            guard.retain_mut(|subscriber| (subscriber.observer)(&sample));
+            if guard.is_empty() {
+                SAMPLER_ACTIVE.store(false, Ordering::Release);
+                return;
+            }
+        }
+
+        std::thread::sleep(SAMPLE_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_games_lists_each_game_once() {
+        let process = |pid, game: &str| GameProcess {
+            pid,
+            game: PathBuf::from(game),
+            total_read_bytes: 0,
+        };
+        let sample = GameProcessSample {
+            taken_at: Instant::now(),
+            processes: vec![
+                process(3, "/games/Shooter"),
+                process(1, "/games/Puzzle"),
+                process(2, "/games/Shooter"),
+            ],
+        };
+        assert_eq!(
+            sample.running_games(),
+            vec![
+                PathBuf::from("/games/Puzzle"),
+                PathBuf::from("/games/Shooter")
+            ]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Opt-out marker files.
+//!
+//! A directory holding `.pressplay-ignore` or `compact.ignore` is left
+//! alone together with everything below it. Modders and developers keep
+//! working trees inside game folders; the marker keeps compression, size
+//! scans and automation out of them.
+
+use std::path::Path;
+
+/// File names that mark a directory as ignored.
+pub const IGNORE_MARKERS: &[&str] = &[".pressplay-ignore", "compact.ignore"];
+
+/// True when `dir` itself carries an ignore marker.
+pub fn has_ignore_marker(dir: &Path) -> bool {
+    IGNORE_MARKERS
+        .iter()
+        .any(|marker| dir.join(marker).is_file())
+}
+
+/// True when `path` lies in an ignored directory at or below `root`.
+/// Directories above `root` are not consulted.
+pub fn is_ignored_below(root: &Path, path: &Path) -> bool {
+    if !path.starts_with(root) {
+        return false;
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .any(has_ignore_marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn either_marker_ignores_its_directory() {
+        let dir = TempDir::new().unwrap();
+        assert!(!has_ignore_marker(dir.path()));
+
+        for marker in IGNORE_MARKERS {
+            let marked = dir.path().join(marker.trim_start_matches('.'));
+            fs::create_dir(&marked).unwrap();
+            fs::write(marked.join(marker), b"").unwrap();
+            assert!(has_ignore_marker(&marked), "{marker}");
+        }
+    }
+
+    #[test]
+    fn paths_below_a_marked_directory_are_ignored() {
+        let root = TempDir::new().unwrap();
+        let mods = root.path().join("mods");
+        let work = mods.join("wip");
+        fs::create_dir_all(&work).unwrap();
+        fs::write(mods.join("compact.ignore"), b"").unwrap();
+
+        assert!(is_ignored_below(root.path(), &work.join("asset.pak")));
+        assert!(is_ignored_below(root.path(), &mods.join("readme.txt")));
+        assert!(!is_ignored_below(
+            root.path(),
+            &root.path().join("game.exe")
+        ));
+    }
+
+    #[test]
+    fn markers_above_the_root_are_not_consulted() {
+        let outer = TempDir::new().unwrap();
+        let root = outer.path().join("Game");
+        fs::create_dir(&root).unwrap();
+        fs::write(outer.path().join(".pressplay-ignore"), b"").unwrap();
+
+        assert!(!is_ignored_below(&root, &root.join("game.exe")));
+    }
+}
@@ -1,8 +1,10 @@
-//! Hybrid DirectStorage game database: embedded + learned.
+//! Hybrid DirectStorage game database: embedded + runtime + learned.
 //!
 //! Provides fast lookup by game folder name (case-insensitive).
 //! Combines compile-time embedded list from SteamDB/community research
-//! with runtime learned list (games discovered via filesystem scan).
+//! with a versioned runtime database file (downloaded from releases or
+//! edited by the user, see [`reload_known_games`]) and a learned list
+//! (games discovered via filesystem scan).
 //!
 //! When filesystem scan detects DirectStorage, the game is added to
 //! the learned cache so future checks use O(1) lookup instead of slow scan.
@@ -17,6 +19,8 @@ use std::sync::{LazyLock, RwLock};
 
 const KNOWN_GAMES_JSON: &str = include_str!("known_directstorage_games.json");
 const MAX_LEARNED_GAMES: usize = 2048;
+const DATABASE_FILE_NAME: &str = "directstorage_games.json";
+const MAX_DATABASE_GAMES: usize = 8192;
 
 static EMBEDDED_GAMES: LazyLock<HashSet<String>> = LazyLock::new(|| {
     serde_json::from_str::<Vec<String>>(KNOWN_GAMES_JSON)
@@ -38,6 +42,16 @@ static LEARNED_GAMES: LazyLock<RwLock<HashSet<String>>> = LazyLock::new(|| {
     RwLock::new(learned)
 });
 
+/// Runtime database loaded from [`DATABASE_FILE_NAME`]; empty (version 0)
+/// until a file is installed or written by the user.
+static DATABASE: LazyLock<RwLock<KnownGamesDatabase>> = LazyLock::new(|| {
+    let database = load_database().unwrap_or_else(|e| {
+        log::warn!("Ignoring DirectStorage games database: {e}");
+        KnownGamesDatabase::default()
+    });
+    RwLock::new(database)
+});
+
 static SAVE_QUEUE: LazyLock<Option<SyncSender<()>>> = LazyLock::new(|| {
     let (tx, rx) = sync_channel(1);
     match std::thread::Builder::new()
@@ -55,6 +69,50 @@ static SAVE_QUEUE: LazyLock<Option<SyncSender<()>>> = LazyLock::new(|| {
 #[cfg(not(test))]
 static CONFIG_DIR_CREATED: AtomicBool = AtomicBool::new(false);
 
+#[derive(Debug, Default)]
+struct KnownGamesDatabase {
+    version: u32,
+    games: HashSet<String>,
+    rejected: u32,
+}
+
+impl KnownGamesDatabase {
+    fn summary(&self) -> DatabaseSummary {
+        DatabaseSummary {
+            version: self.version,
+            entries: self.games.len() as u32,
+            rejected: self.rejected,
+        }
+    }
+}
+
+/// On-disk format of the runtime database.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct DatabaseFile {
+    version: u32,
+    games: Vec<String>,
+}
+
+/// Outcome of loading a runtime database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseSummary {
+    pub version: u32,
+    pub entries: u32,
+    /// Names dropped because they were empty, hidden-folder names or
+    /// duplicates after normalization.
+    pub rejected: u32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KnownGamesError {
+    #[error("failed to access DirectStorage games database: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid DirectStorage games database: {0}")]
+    Invalid(String),
+    #[error("DirectStorage games database v{offered} is not newer than installed v{installed}")]
+    NotNewer { installed: u32, offered: u32 },
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum LearnedInsertOutcome {
     Inserted,
@@ -118,6 +176,113 @@ fn insert_learned_entry(
     }
 }
 
+fn parse_database(content: &str) -> Result<KnownGamesDatabase, KnownGamesError> {
+    let file: DatabaseFile =
+        serde_json::from_str(content).map_err(|e| KnownGamesError::Invalid(e.to_string()))?;
+    if file.version == 0 {
+        return Err(KnownGamesError::Invalid(
+            "version must be at least 1".to_string(),
+        ));
+    }
+    if file.games.len() > MAX_DATABASE_GAMES {
+        return Err(KnownGamesError::Invalid(format!(
+            "{} games listed; at most {MAX_DATABASE_GAMES} are supported",
+            file.games.len()
+        )));
+    }
+
+    let mut games = HashSet::with_capacity(file.games.len());
+    let mut rejected = 0_u32;
+    for game in &file.games {
+        match normalize_game_name_key(game) {
+            Some(key) if games.insert(key.clone()) => {}
+            _ => rejected = rejected.saturating_add(1),
+        }
+    }
+    if games.is_empty() {
+        return Err(KnownGamesError::Invalid("no valid game names".to_string()));
+    }
+
+    Ok(KnownGamesDatabase {
+        version: file.version,
+        games,
+        rejected,
+    })
+}
+
+fn load_database() -> Result<KnownGamesDatabase, KnownGamesError> {
+    let path = database_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => parse_database(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(KnownGamesDatabase::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Swap in `database` and drop cached scan results so games it adds are
+/// blocked on the next check.
+fn activate_database(database: KnownGamesDatabase) -> DatabaseSummary {
+    let summary = database.summary();
+    *DATABASE.write().unwrap_or_else(|poisoned| {
+        log::warn!("DirectStorage games database lock poisoned; recovering");
+        poisoned.into_inner()
+    }) = database;
+    super::directstorage_cache::invalidate_detections();
+    log::info!(
+        "Loaded DirectStorage games database v{} ({} games, {} rejected)",
+        summary.version,
+        summary.entries,
+        summary.rejected
+    );
+    summary
+}
+
+/// Re-read the runtime database file, e.g. after the user edited it. A
+/// missing file unloads the runtime database; an invalid one leaves the
+/// current database in place.
+pub fn reload_known_games() -> Result<DatabaseSummary, KnownGamesError> {
+    let path = database_path()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(activate_database(KnownGamesDatabase::default()));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(activate_database(parse_database(&content)?))
+}
+
+/// Validate and install a downloaded database. Only a newer version than
+/// the loaded one replaces it.
+pub fn install_known_games_database(content: &str) -> Result<DatabaseSummary, KnownGamesError> {
+    let database = parse_database(content)?;
+    let installed = known_games_database().version;
+    if database.version <= installed {
+        return Err(KnownGamesError::NotNewer {
+            installed,
+            offered: database.version,
+        });
+    }
+    crate::utils::atomic_write(&database_path()?, content.as_bytes())?;
+    Ok(activate_database(database))
+}
+
+/// The loaded runtime database; version 0 when none is loaded.
+pub fn known_games_database() -> DatabaseSummary {
+    DATABASE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .summary()
+}
+
+fn in_runtime_database(key: &str) -> bool {
+    DATABASE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .games
+        .contains(key)
+}
+
 fn recv_save_signal(rx: &Receiver<()>) -> bool {
     if rx.recv().is_err() {
         return false;
@@ -150,6 +315,10 @@ fn save_worker(rx: Receiver<()>) {
     }
 }
 
+fn database_path() -> Result<PathBuf, std::io::Error> {
+    Ok(learned_games_path()?.with_file_name(DATABASE_FILE_NAME))
+}
+
 fn learned_games_path() -> Result<PathBuf, std::io::Error> {
     #[cfg(test)]
     {
@@ -169,9 +338,7 @@ fn learned_games_path() -> Result<PathBuf, std::io::Error> {
 
     #[cfg(not(test))]
     {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-        let compact_games_dir = config_dir.join("compact_games");
+        let compact_games_dir = crate::paths::data_dir()?;
 
         if !CONFIG_DIR_CREATED.load(std::sync::atomic::Ordering::Relaxed) {
             fs::create_dir_all(&compact_games_dir)?;
@@ -242,7 +409,7 @@ pub fn is_known_directstorage_game(game_path: &Path) -> bool {
         None => return false,
     };
 
-    if EMBEDDED_GAMES.contains(&folder_name_key) {
+    if EMBEDDED_GAMES.contains(&folder_name_key) || in_runtime_database(&folder_name_key) {
         return true;
     }
 
@@ -276,7 +443,7 @@ pub fn learn_directstorage_game(game_path: &Path) {
         }
     };
 
-    if EMBEDDED_GAMES.contains(&folder_name) {
+    if EMBEDDED_GAMES.contains(&folder_name) || in_runtime_database(&folder_name) {
         return;
     }
 
@@ -454,6 +621,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn runtime_database_validates_and_tracks_versions() {
+        assert!(matches!(
+            parse_database(r#"{"version": 0, "games": ["A"]}"#),
+            Err(KnownGamesError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse_database(r#"{"version": 1, "games": ["", ".hidden"]}"#),
+            Err(KnownGamesError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse_database(r#"["A"]"#),
+            Err(KnownGamesError::Invalid(_))
+        ));
+
+        let game = Path::new(r"C:\Games\Runtime DB Test Game");
+        assert!(!is_known_directstorage_game(game));
+
+        let v1 = r#"{"version": 1, "games": ["Runtime DB Test Game", "runtime db test game", ""]}"#;
+        let summary = install_known_games_database(v1).unwrap();
+        assert_eq!(
+            summary,
+            DatabaseSummary {
+                version: 1,
+                entries: 1,
+                rejected: 2
+            }
+        );
+        assert!(is_known_directstorage_game(game));
+        assert!(matches!(
+            install_known_games_database(v1),
+            Err(KnownGamesError::NotNewer {
+                installed: 1,
+                offered: 1
+            })
+        ));
+
+        // A user edit on disk is picked up by reload regardless of version.
+        fs::write(
+            database_path().unwrap(),
+            r#"{"version": 1, "games": ["Another Game"]}"#,
+        )
+        .unwrap();
+        assert_eq!(reload_known_games().unwrap().entries, 1);
+        assert!(!is_known_directstorage_game(game));
+
+        fs::remove_file(database_path().unwrap()).unwrap();
+        assert_eq!(reload_known_games().unwrap().version, 0);
+        assert_eq!(known_games_database().version, 0);
+    }
+
     #[test]
     fn recv_save_signal_coalesces_burst() {
         let (tx, rx) = sync_channel(4);
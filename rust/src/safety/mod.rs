@@ -1,4 +1,14 @@
+pub mod anticheat;
+pub mod cloud;
 pub mod directstorage;
+pub mod directstorage_cache;
+pub mod filesystem;
+pub mod focus_assist;
+pub mod game_processes;
+pub mod ignore_marker;
 pub mod known_games;
 pub mod process;
+pub mod running_games;
+pub mod traversal;
 pub mod unsupported_games;
+pub mod uwp;
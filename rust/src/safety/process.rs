@@ -1,10 +1,10 @@
-﻿//! Running game detection via process enumeration.
+﻿//! Running game and do-not-disturb process detection via process enumeration.
 //!
 //! Thread-safe: all public methods take `&self` via interior `Mutex`.
 //! Only requests executable-path information from the OS, skipping
 //! CPU, memory, and disk I/O queries for maximum performance.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
@@ -22,8 +22,21 @@ struct ProcessCheckerInner {
     system: System,
     last_refresh: Instant,
     refresh_interval: Duration,
+    /// Normalized process names (lowercase, no `.exe`) that block compression.
+    blocking_processes: Vec<String>,
 }
 
+/// Suggested do-not-disturb list: recording, editing, and rendering tools
+/// that compete with compression for disk and CPU.
+pub const DEFAULT_BLOCKING_PROCESSES: &[&str] = &[
+    "obs64.exe",
+    "Adobe Premiere Pro.exe",
+    "AfterFX.exe",
+    "Resolve.exe",
+    "blender.exe",
+    "HandBrake.exe",
+];
+
 impl ProcessChecker {
     /// Create a new checker with the default 5-second refresh interval.
     pub fn new() -> Self {
@@ -38,19 +51,13 @@ impl ProcessChecker {
                 system,
                 last_refresh: Instant::now(),
                 refresh_interval,
+                blocking_processes: Vec::new(),
             }),
         }
     }
 
     pub fn is_game_running(&self, game_path: &Path) -> bool {
-        let mut inner = match self.inner.lock() {
-            Ok(guard) => guard,
-            Err(poisoned) => {
-                log::warn!("ProcessChecker lock poisoned; recovering");
-                poisoned.into_inner()
-            }
-        };
-
+        let mut inner = self.lock_inner();
         inner.maybe_refresh();
 
         for (_pid, process) in inner.system.processes() {
@@ -68,6 +75,98 @@ impl ProcessChecker {
 
         false
     }
+
+    /// Entries of `game_paths` with at least one running process. Each
+    /// process counts for the deepest game path containing its executable,
+    /// so a launcher folder does not shadow the games installed inside it.
+    pub fn running_game_paths(&self, game_paths: &[PathBuf]) -> Vec<PathBuf> {
+        if game_paths.is_empty() {
+            return Vec::new();
+        }
+        let mut inner = self.lock_inner();
+        inner.maybe_refresh();
+
+        let exes = inner
+            .system
+            .processes()
+            .values()
+            .filter_map(|process| process.exe());
+        match_running_games(exes, game_paths)
+    }
+
+    /// Replace the do-not-disturb list. Names match process image names
+    /// case-insensitively, with or without the `.exe` suffix.
+    pub fn set_blocking_processes(&self, names: &[String]) {
+        let mut normalized: Vec<String> = names
+            .iter()
+            .map(|name| normalize_process_name(name))
+            .filter(|name| !name.is_empty())
+            .collect();
+        normalized.sort();
+        normalized.dedup();
+        self.lock_inner().blocking_processes = normalized;
+    }
+
+    /// True when any process on the do-not-disturb list is running.
+    pub fn is_any_blocking_process_running(&self) -> bool {
+        let mut inner = self.lock_inner();
+        if inner.blocking_processes.is_empty() {
+            return false;
+        }
+        inner.maybe_refresh();
+
+        for (pid, process) in inner.system.processes() {
+            let name = normalize_process_name(&process.name().to_string_lossy());
+            if inner.blocking_processes.contains(&name) {
+                log::debug!("Blocking process detected: {name} (pid {pid})");
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn lock_inner(&self) -> std::sync::MutexGuard<'_, ProcessCheckerInner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                log::warn!("ProcessChecker lock poisoned; recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
+}
+
+fn match_running_games<'a>(
+    exes: impl Iterator<Item = &'a Path>,
+    game_paths: &[PathBuf],
+) -> Vec<PathBuf> {
+    let mut running: Vec<PathBuf> = Vec::new();
+    for exe in exes {
+        if let Some(game) = owning_game(exe, game_paths) {
+            if !running.contains(game) {
+                running.push(game.clone());
+            }
+        }
+    }
+    running.sort();
+    running
+}
+
+/// The deepest entry of `game_paths` containing `exe`.
+pub(crate) fn owning_game<'a>(exe: &Path, game_paths: &'a [PathBuf]) -> Option<&'a PathBuf> {
+    game_paths
+        .iter()
+        .filter(|game| exe.starts_with(game))
+        .max_by_key(|game| game.components().count())
+}
+
+fn normalize_process_name(name: &str) -> String {
+    let name = name.trim().to_ascii_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_owned(),
+        None => name,
+    }
 }
 
 impl Default for ProcessChecker {
@@ -128,6 +227,48 @@ mod tests {
         assert!(!checker.is_game_running(Path::new(r"C:\__nonexistent__")));
     }
 
+    #[test]
+    fn running_processes_map_to_deepest_game_path() {
+        let library = PathBuf::from("/games");
+        let shooter = library.join("Shooter");
+        let puzzle = library.join("Puzzle");
+        let exes = [
+            shooter.join("Binaries").join("Win64").join("Shooter.exe"),
+            shooter.join("Shooter.exe"),
+            PathBuf::from("/tools/obs64.exe"),
+        ];
+
+        let running = match_running_games(
+            exes.iter().map(PathBuf::as_path),
+            &[library.clone(), shooter.clone(), puzzle],
+        );
+        assert_eq!(running, vec![shooter]);
+
+        let launcher = match_running_games(
+            std::iter::once(library.join("launcher.exe").as_path()),
+            std::slice::from_ref(&library),
+        );
+        assert_eq!(launcher, vec![library]);
+    }
+
+    #[test]
+    fn blocking_list_empty_never_blocks() {
+        let checker = ProcessChecker::new();
+        assert!(!checker.is_any_blocking_process_running());
+    }
+
+    #[test]
+    fn blocking_list_matches_own_process_name_case_insensitively() {
+        let checker = ProcessChecker::new();
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_string_lossy().to_uppercase();
+        checker.set_blocking_processes(&[name]);
+        assert!(checker.is_any_blocking_process_running());
+
+        checker.set_blocking_processes(&["__nonexistent_compact_games_tool__".to_owned()]);
+        assert!(!checker.is_any_blocking_process_running());
+    }
+
     #[test]
     fn detects_own_process() {
         let checker = ProcessChecker::new();
@@ -0,0 +1,59 @@
+//! Continuous running-game tracking.
+//!
+//! [`ProcessChecker::is_game_running`] answers for one path at one moment.
+//! The monitor keeps the set of running games current across polls and
+//! reports when it changes, so automation can stop touching a game the
+//! moment it launches. The library's running-games stream follows
+//! [`super::game_processes`] instead.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::process::ProcessChecker;
+
+/// How often the monitor refreshes the process list. A game takes
+/// seconds to start, so a launch is seen before it gets far into loading.
+pub const RUNNING_GAMES_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub struct RunningGamesMonitor {
+    checker: ProcessChecker,
+    running: Vec<PathBuf>,
+}
+
+impl RunningGamesMonitor {
+    pub fn new() -> Self {
+        Self {
+            checker: ProcessChecker::with_interval(RUNNING_GAMES_POLL_INTERVAL),
+            running: Vec::new(),
+        }
+    }
+
+    /// Re-map running processes onto `game_paths`. Returns the new set
+    /// when it differs from the previous poll.
+    pub fn poll(&mut self, game_paths: &[PathBuf]) -> Option<&[PathBuf]> {
+        let running = self.checker.running_game_paths(game_paths);
+        if running == self.running {
+            return None;
+        }
+        for launched in running.iter().filter(|path| !self.running.contains(path)) {
+            log::info!("Game launched: {}", launched.display());
+        }
+        self.running = running;
+        Some(&self.running)
+    }
+
+    /// Game paths running as of the last poll, sorted.
+    pub fn running(&self) -> &[PathBuf] {
+        &self.running
+    }
+
+    pub fn is_running(&self, game_path: &Path) -> bool {
+        self.running.iter().any(|path| path == game_path)
+    }
+}
+
+impl Default for RunningGamesMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,248 @@
+//! Directory traversal policy for symlinks, junctions and mount points.
+//!
+//! Steam libraries sometimes contain junctions into other drives. Walking
+//! through them double-counts sizes and can point compression at folders
+//! the user never selected, so link-like reparse points are skipped unless
+//! the caller opts in to following them. WOF-compressed files are reparse
+//! points as well, but their tag does not redirect anywhere; they are
+//! treated as ordinary files.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::{DirEntry, WalkDir};
+
+use super::ignore_marker::has_ignore_marker;
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x0000_0400;
+
+/// How a walk treats symlinks, junctions and volume mount points below the
+/// root. The root itself is always entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TraversalPolicy {
+    /// Neither yield nor descend into link entries.
+    #[default]
+    SkipReparsePoints,
+    /// Follow links, visiting each target at most once and never re-visiting
+    /// a target that already lies under the root.
+    FollowReparsePoints,
+}
+
+impl TraversalPolicy {
+    pub fn follows_reparse_points(self) -> bool {
+        self == Self::FollowReparsePoints
+    }
+
+    /// Walk `root` under this policy.
+    pub fn walk(self, root: &Path) -> impl Iterator<Item = walkdir::Result<DirEntry>> {
+        self.walk_dir(WalkDir::new(root), root)
+    }
+
+    /// Walk `root` under this policy, at most `max_depth` levels deep.
+    pub fn walk_to_depth(
+        self,
+        root: &Path,
+        max_depth: usize,
+    ) -> impl Iterator<Item = walkdir::Result<DirEntry>> {
+        self.walk_dir(WalkDir::new(root).max_depth(max_depth), root)
+    }
+
+    fn walk_dir(
+        self,
+        walker: WalkDir,
+        root: &Path,
+    ) -> impl Iterator<Item = walkdir::Result<DirEntry>> {
+        let canonical_root = match self {
+            Self::SkipReparsePoints => None,
+            Self::FollowReparsePoints => fs::canonicalize(root).ok(),
+        };
+        let mut followed_targets = HashSet::<PathBuf>::new();
+
+        walker
+            .follow_links(self.follows_reparse_points())
+            .into_iter()
+            .filter_entry(move |entry| {
+                if entry.file_type().is_dir() && has_ignore_marker(entry.path()) {
+                    log::debug!("Skipping ignored directory {}", entry.path().display());
+                    return false;
+                }
+                if entry.depth() == 0 || !is_link_entry(entry) {
+                    return true;
+                }
+                match self {
+                    Self::SkipReparsePoints => {
+                        log::debug!("Skipping reparse point {}", entry.path().display());
+                        false
+                    }
+                    Self::FollowReparsePoints => should_follow(
+                        entry.path(),
+                        canonical_root.as_deref(),
+                        &mut followed_targets,
+                    ),
+                }
+            })
+    }
+}
+
+fn should_follow(
+    link: &Path,
+    canonical_root: Option<&Path>,
+    followed_targets: &mut HashSet<PathBuf>,
+) -> bool {
+    let Ok(target) = fs::canonicalize(link) else {
+        log::debug!("Skipping dangling reparse point {}", link.display());
+        return false;
+    };
+    let already_walked = canonical_root.is_some_and(|root| target.starts_with(root))
+        || followed_targets
+            .iter()
+            .any(|followed| target.starts_with(followed));
+    if already_walked {
+        log::debug!(
+            "Skipping reparse point {} into already visited {}",
+            link.display(),
+            target.display()
+        );
+        return false;
+    }
+    followed_targets.insert(target)
+}
+
+/// True for entries that redirect to another path: symlinks, junctions and
+/// volume mount points.
+///
+/// On Windows std reports every name-surrogate reparse tag as a symlink, so
+/// junctions and mount points are covered without querying the tag here.
+pub fn is_link_entry(entry: &DirEntry) -> bool {
+    entry.path_is_symlink()
+}
+
+/// True when `path` itself carries a reparse point of any kind (links, WOF
+/// compressed files, cloud placeholders, ...).
+#[cfg(windows)]
+pub fn is_reparse_point(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    fs::symlink_metadata(path)
+        .is_ok_and(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0)
+}
+
+#[cfg(not(windows))]
+pub fn is_reparse_point(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file_names(policy: TraversalPolicy, root: &Path) -> Vec<String> {
+        let mut names: Vec<String> = policy
+            .walk(root)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[cfg(unix)]
+    fn link_dir(target: &Path, link: &Path) {
+        std::os::unix::fs::symlink(target, link).unwrap();
+    }
+
+    #[cfg(windows)]
+    fn link_dir(target: &Path, link: &Path) {
+        // Needs developer mode or elevation; junction-only hosts skip the
+        // link tests below.
+        let _ = std::os::windows::fs::symlink_dir(target, link);
+    }
+
+    #[test]
+    fn plain_tree_is_walked_under_both_policies() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("data")).unwrap();
+        fs::write(dir.path().join("game.exe"), b"x").unwrap();
+        fs::write(dir.path().join("data").join("pak0.pak"), b"x").unwrap();
+
+        let expected = vec!["game.exe".to_string(), "pak0.pak".to_string()];
+        assert_eq!(
+            file_names(TraversalPolicy::SkipReparsePoints, dir.path()),
+            expected
+        );
+        assert_eq!(
+            file_names(TraversalPolicy::FollowReparsePoints, dir.path()),
+            expected
+        );
+        assert!(!is_reparse_point(dir.path()));
+    }
+
+    #[test]
+    fn marked_directories_are_skipped_under_both_policies() {
+        let dir = TempDir::new().unwrap();
+        let mods = dir.path().join("mods");
+        fs::create_dir(&mods).unwrap();
+        fs::write(dir.path().join("game.exe"), b"x").unwrap();
+        fs::write(mods.join("wip.pak"), b"x").unwrap();
+        fs::write(mods.join("compact.ignore"), b"").unwrap();
+
+        for policy in [
+            TraversalPolicy::SkipReparsePoints,
+            TraversalPolicy::FollowReparsePoints,
+        ] {
+            assert_eq!(file_names(policy, dir.path()), vec!["game.exe".to_string()]);
+        }
+
+        fs::write(dir.path().join(".pressplay-ignore"), b"").unwrap();
+        assert!(file_names(TraversalPolicy::SkipReparsePoints, dir.path()).is_empty());
+    }
+
+    #[test]
+    fn links_out_of_root_are_skipped_by_default_and_followed_on_request() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(root.path().join("game.exe"), b"x").unwrap();
+        fs::write(outside.path().join("shared.pak"), b"x").unwrap();
+        let link = root.path().join("linked");
+        link_dir(outside.path(), &link);
+        if !link.exists() {
+            return;
+        }
+
+        assert!(is_reparse_point(&link));
+        assert_eq!(
+            file_names(TraversalPolicy::SkipReparsePoints, root.path()),
+            vec!["game.exe".to_string()]
+        );
+        assert_eq!(
+            file_names(TraversalPolicy::FollowReparsePoints, root.path()),
+            vec!["game.exe".to_string(), "shared.pak".to_string()]
+        );
+    }
+
+    #[test]
+    fn followed_links_never_double_count() {
+        let root = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let data = root.path().join("data");
+        fs::create_dir(&data).unwrap();
+        fs::write(data.join("pak0.pak"), b"x").unwrap();
+        fs::write(outside.path().join("shared.pak"), b"x").unwrap();
+        link_dir(&data, &root.path().join("data_alias"));
+        link_dir(outside.path(), &root.path().join("shared_a"));
+        link_dir(outside.path(), &root.path().join("shared_b"));
+        if !root.path().join("shared_b").exists() {
+            return;
+        }
+
+        assert_eq!(
+            file_names(TraversalPolicy::FollowReparsePoints, root.path()),
+            vec!["pak0.pak".to_string(), "shared.pak".to_string()]
+        );
+    }
+}
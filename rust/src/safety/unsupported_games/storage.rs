@@ -97,9 +97,7 @@ fn config_dir() -> Result<PathBuf, std::io::Error> {
 
     #[cfg(not(test))]
     {
-        let dir = dirs::config_dir()
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no config dir"))?;
-        let compact_games_dir = dir.join("compact_games");
+        let compact_games_dir = crate::paths::data_dir()?;
         if !CONFIG_DIR_CREATED.load(std::sync::atomic::Ordering::Relaxed) {
             fs::create_dir_all(&compact_games_dir)?;
             CONFIG_DIR_CREATED.store(true, std::sync::atomic::Ordering::Relaxed);
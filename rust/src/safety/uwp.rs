@@ -0,0 +1,76 @@
+//! Protected UWP / MSIX package detection.
+//!
+//! Xbox app (Game Pass) titles and other Store packages are validated
+//! against `AppxBlockMap.xml` and `AppxSignature.p7x`. Rewriting their
+//! files through WOF can make the package fail integrity checks, after
+//! which the game refuses to launch until it is repaired. Such folders are
+//! treated as read-only.
+
+use std::path::Path;
+
+/// Folder that holds installed Store packages on every drive.
+const WINDOWS_APPS_DIR: &str = "WindowsApps";
+/// Files only present in the root of a signed package.
+const PACKAGE_SIGNATURE_FILES: &[&str] = &["AppxSignature.p7x", "AppxBlockMap.xml"];
+/// Xbox app installs put the package under `<Game>\Content`.
+const XBOX_CONTENT_DIR: &str = "Content";
+
+/// True when `path` is (or lives inside) a signed UWP package.
+pub fn is_protected_package(path: &Path) -> bool {
+    if path
+        .components()
+        .any(|c| c.as_os_str().eq_ignore_ascii_case(WINDOWS_APPS_DIR))
+    {
+        return true;
+    }
+
+    has_package_signature(path) || has_package_signature(&path.join(XBOX_CONTENT_DIR))
+}
+
+fn has_package_signature(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(|e| e.ok()).any(|entry| {
+        let name = entry.file_name();
+        PACKAGE_SIGNATURE_FILES
+            .iter()
+            .any(|marker| name.eq_ignore_ascii_case(marker))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn plain_game_folder_is_not_protected() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("game.exe"), b"fake").unwrap();
+        assert!(!is_protected_package(dir.path()));
+    }
+
+    #[test]
+    fn signed_package_root_is_protected() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("appxsignature.p7x"), b"fake").unwrap();
+        assert!(is_protected_package(dir.path()));
+    }
+
+    #[test]
+    fn xbox_content_folder_is_protected() {
+        let dir = TempDir::new().unwrap();
+        let content = dir.path().join("Content");
+        std::fs::create_dir(&content).unwrap();
+        std::fs::write(content.join("AppxBlockMap.xml"), b"<BlockMap/>").unwrap();
+        assert!(is_protected_package(dir.path()));
+    }
+
+    #[test]
+    fn windows_apps_paths_are_protected() {
+        assert!(is_protected_package(Path::new(
+            r"C:\Program Files\WindowsApps\Microsoft.Game_1.0.0.0_x64__8wekyb3d8bbwe"
+        )));
+    }
+}
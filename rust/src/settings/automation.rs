@@ -0,0 +1,226 @@
+//! Automation section of the settings file.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::automation_types::FrbAutomationConfig;
+use crate::automation::idle::IdlePolicy;
+use crate::compression::algorithm::CompressionAlgorithm;
+use crate::compression::rate_limit::MIN_BYTES_PER_SEC;
+
+/// Serde mirror of `FrbAutomationConfig`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationSettings {
+    pub cpu_threshold_percent: f32,
+    pub idle_duration_seconds: u64,
+    pub cooldown_seconds: u64,
+    #[serde(default)]
+    pub watch_paths: Vec<String>,
+    #[serde(default)]
+    pub excluded_paths: Vec<String>,
+    #[serde(default)]
+    pub algorithm: CompressionAlgorithm,
+    #[serde(default)]
+    pub allow_directstorage_override: bool,
+    #[serde(default)]
+    pub io_parallelism_override: Option<u64>,
+    #[serde(default)]
+    pub max_bytes_per_sec: Option<u64>,
+    #[serde(default)]
+    pub max_concurrent_jobs: Option<u32>,
+    #[serde(default)]
+    pub blocking_processes: Vec<String>,
+    #[serde(default)]
+    pub include_removable_drives: bool,
+    #[serde(default)]
+    pub allow_anticheat_compression: bool,
+    #[serde(default)]
+    pub watcher_ignored_dirs: Vec<String>,
+    #[serde(default)]
+    pub watcher_ignored_extensions: Vec<String>,
+    #[serde(default)]
+    pub watcher_temp_file_patterns: Vec<String>,
+    #[serde(default)]
+    pub watcher_max_event_depth: Option<u32>,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub input_idle_seconds: Option<u64>,
+    #[serde(default)]
+    pub idle_policy: IdlePolicy,
+    #[serde(default)]
+    pub ignore_focus_assist: bool,
+    #[serde(default)]
+    pub pause_when_thermally_constrained: bool,
+    #[serde(default)]
+    pub maintenance_wake_minute_of_day: Option<u32>,
+}
+
+impl From<&FrbAutomationConfig> for AutomationSettings {
+    fn from(c: &FrbAutomationConfig) -> Self {
+        Self {
+            cpu_threshold_percent: c.cpu_threshold_percent,
+            idle_duration_seconds: c.idle_duration_seconds,
+            cooldown_seconds: c.cooldown_seconds,
+            watch_paths: c.watch_paths.clone(),
+            excluded_paths: c.excluded_paths.clone(),
+            algorithm: c.algorithm.into(),
+            allow_directstorage_override: c.allow_directstorage_override,
+            io_parallelism_override: c.io_parallelism_override,
+            max_bytes_per_sec: c.max_bytes_per_sec,
+            max_concurrent_jobs: c.max_concurrent_jobs,
+            blocking_processes: c.blocking_processes.clone(),
+            include_removable_drives: c.include_removable_drives,
+            allow_anticheat_compression: c.allow_anticheat_compression,
+            watcher_ignored_dirs: c.watcher_ignored_dirs.clone(),
+            watcher_ignored_extensions: c.watcher_ignored_extensions.clone(),
+            watcher_temp_file_patterns: c.watcher_temp_file_patterns.clone(),
+            watcher_max_event_depth: c.watcher_max_event_depth,
+            dry_run: c.dry_run,
+            input_idle_seconds: c.input_idle_seconds,
+            idle_policy: c.idle_policy.into(),
+            ignore_focus_assist: c.ignore_focus_assist,
+            pause_when_thermally_constrained: c.pause_when_thermally_constrained,
+            maintenance_wake_minute_of_day: c.maintenance_wake_minute_of_day,
+        }
+    }
+}
+
+impl From<AutomationSettings> for FrbAutomationConfig {
+    fn from(c: AutomationSettings) -> Self {
+        Self {
+            cpu_threshold_percent: c.cpu_threshold_percent,
+            idle_duration_seconds: c.idle_duration_seconds,
+            cooldown_seconds: c.cooldown_seconds,
+            watch_paths: c.watch_paths,
+            excluded_paths: c.excluded_paths,
+            algorithm: c.algorithm.into(),
+            allow_directstorage_override: c.allow_directstorage_override,
+            io_parallelism_override: c.io_parallelism_override,
+            max_bytes_per_sec: c.max_bytes_per_sec,
+            max_concurrent_jobs: c.max_concurrent_jobs,
+            blocking_processes: c.blocking_processes,
+            include_removable_drives: c.include_removable_drives,
+            allow_anticheat_compression: c.allow_anticheat_compression,
+            watcher_ignored_dirs: c.watcher_ignored_dirs,
+            watcher_ignored_extensions: c.watcher_ignored_extensions,
+            watcher_temp_file_patterns: c.watcher_temp_file_patterns,
+            watcher_max_event_depth: c.watcher_max_event_depth,
+            dry_run: c.dry_run,
+            input_idle_seconds: c.input_idle_seconds,
+            idle_policy: c.idle_policy.into(),
+            ignore_focus_assist: c.ignore_focus_assist,
+            pause_when_thermally_constrained: c.pause_when_thermally_constrained,
+            maintenance_wake_minute_of_day: c.maintenance_wake_minute_of_day,
+        }
+    }
+}
+
+impl Default for AutomationSettings {
+    /// The app's first-run values.
+    fn default() -> Self {
+        Self {
+            cpu_threshold_percent: 40.0,
+            idle_duration_seconds: 5 * 60,
+            cooldown_seconds: 5 * 60,
+            watch_paths: Vec::new(),
+            excluded_paths: Vec::new(),
+            algorithm: CompressionAlgorithm::Xpress8K,
+            allow_directstorage_override: false,
+            io_parallelism_override: None,
+            max_bytes_per_sec: None,
+            max_concurrent_jobs: None,
+            blocking_processes: Vec::new(),
+            include_removable_drives: false,
+            allow_anticheat_compression: false,
+            watcher_ignored_dirs: Vec::new(),
+            watcher_ignored_extensions: Vec::new(),
+            watcher_temp_file_patterns: Vec::new(),
+            watcher_max_event_depth: None,
+            dry_run: false,
+            input_idle_seconds: None,
+            idle_policy: IdlePolicy::All,
+            ignore_focus_assist: false,
+            pause_when_thermally_constrained: false,
+            maintenance_wake_minute_of_day: None,
+        }
+    }
+}
+
+impl AutomationSettings {
+    /// Clamp values to the ranges the settings screen offers.
+    pub fn validated(mut self) -> Self {
+        self.cpu_threshold_percent = self.cpu_threshold_percent.clamp(5.0, 80.0);
+        self.idle_duration_seconds = self.idle_duration_seconds.clamp(3 * 60, 15 * 60);
+        self.input_idle_seconds = self
+            .input_idle_seconds
+            .filter(|&seconds| seconds > 0)
+            .map(|seconds| seconds.clamp(60, 60 * 60));
+        self.cooldown_seconds = self.cooldown_seconds.clamp(60, 120 * 60);
+        self.io_parallelism_override = self.io_parallelism_override.map(|n| n.clamp(1, 16));
+        self.max_bytes_per_sec = self
+            .max_bytes_per_sec
+            .filter(|&rate| rate > 0)
+            .map(|rate| rate.max(MIN_BYTES_PER_SEC));
+        self.max_concurrent_jobs = self.max_concurrent_jobs.filter(|&n| n > 0);
+        self.watcher_max_event_depth = self.watcher_max_event_depth.filter(|&depth| depth > 0);
+        self.maintenance_wake_minute_of_day = self
+            .maintenance_wake_minute_of_day
+            .filter(|&minute| minute < crate::automation::wake::MINUTES_PER_DAY);
+        for rules in [
+            &mut self.watcher_ignored_dirs,
+            &mut self.watcher_ignored_extensions,
+            &mut self.watcher_temp_file_patterns,
+        ] {
+            rules.retain(|rule| !rule.trim().is_empty());
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::automation_types::FrbIdlePolicy;
+    use crate::api::types::FrbCompressionAlgorithm;
+
+    #[test]
+    fn frb_config_round_trips() {
+        let config = FrbAutomationConfig {
+            cpu_threshold_percent: 15.0,
+            idle_duration_seconds: 300,
+            cooldown_seconds: 60,
+            watch_paths: vec!["D:\\Games".into()],
+            excluded_paths: Vec::new(),
+            algorithm: FrbCompressionAlgorithm::Lzx,
+            allow_directstorage_override: false,
+            io_parallelism_override: Some(2),
+            max_bytes_per_sec: Some(50 * 1024 * 1024),
+            max_concurrent_jobs: None,
+            blocking_processes: vec!["obs64.exe".into()],
+            include_removable_drives: false,
+            allow_anticheat_compression: false,
+            watcher_ignored_dirs: vec!["ModStaging".into()],
+            watcher_ignored_extensions: vec!["dmp".into()],
+            watcher_temp_file_patterns: vec!["*.egstmp".into()],
+            watcher_max_event_depth: Some(4),
+            dry_run: true,
+            input_idle_seconds: Some(600),
+            idle_policy: FrbIdlePolicy::Any,
+            ignore_focus_assist: true,
+            pause_when_thermally_constrained: true,
+            maintenance_wake_minute_of_day: Some(3 * 60),
+        };
+
+        let stored = AutomationSettings::from(&config);
+        let json = serde_json::to_string(&stored).unwrap();
+        let loaded: AutomationSettings = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded, stored);
+        assert_eq!(loaded.algorithm, CompressionAlgorithm::Lzx);
+        assert_eq!(loaded.idle_policy, IdlePolicy::Any);
+        assert_eq!(
+            AutomationSettings::from(&FrbAutomationConfig::from(loaded)),
+            stored
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Exclusion section of the settings file.
+//!
+//! Rules name one folder, a wildcard pattern over folder paths, or a whole
+//! platform. Discovery marks matching games `excluded`, automation ignores
+//! them, and manual compression warns before touching them.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::discovery::platform::{GameInfo, Platform};
+use crate::utils::{normalize_path_key, wildcard_match};
+
+/// One user exclusion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ExclusionRule {
+    /// This folder and everything below it.
+    Path(String),
+    /// `*` and `?` wildcards over the whole folder path, case-insensitive,
+    /// such as `D:\Games\*Demo*`.
+    Glob(String),
+    /// Every game from this launcher.
+    Platform(Platform),
+}
+
+impl ExclusionRule {
+    /// Trim the rule, or `None` when nothing is left of it.
+    fn validated(self) -> Option<Self> {
+        match self {
+            Self::Path(path) => Some(path.trim().to_string())
+                .filter(|path| !path.is_empty())
+                .map(Self::Path),
+            Self::Glob(pattern) => Some(pattern.trim().to_string())
+                .filter(|pattern| !pattern.is_empty())
+                .map(Self::Glob),
+            Self::Platform(platform) => Some(Self::Platform(platform)),
+        }
+    }
+
+    /// Key that treats differently spelled copies of a rule as one.
+    fn dedupe_key(&self) -> String {
+        match self {
+            Self::Path(path) => format!("path:{}", normalize_path_key(Path::new(path))),
+            Self::Glob(pattern) => format!("glob:{}", pattern.to_lowercase()),
+            Self::Platform(platform) => format!("platform:{platform:?}"),
+        }
+    }
+}
+
+/// Drop blank and duplicate rules, keeping the first of each.
+pub fn validated(rules: Vec<ExclusionRule>) -> Vec<ExclusionRule> {
+    let mut seen = HashSet::new();
+    rules
+        .into_iter()
+        .filter_map(ExclusionRule::validated)
+        .filter(|rule| seen.insert(rule.dedupe_key()))
+        .collect()
+}
+
+/// Rules prepared for matching.
+#[derive(Debug, Clone, Default)]
+pub struct Exclusions {
+    paths: Vec<(String, ExclusionRule)>,
+    globs: Vec<(String, ExclusionRule)>,
+    platforms: Vec<Platform>,
+}
+
+impl Exclusions {
+    pub fn new(rules: &[ExclusionRule]) -> Self {
+        let mut exclusions = Self::default();
+        for rule in rules {
+            match rule {
+                ExclusionRule::Path(path) => exclusions
+                    .paths
+                    .push((normalize_path_key(Path::new(path)), rule.clone())),
+                ExclusionRule::Glob(pattern) => exclusions
+                    .globs
+                    .push((pattern.replace('/', "\\"), rule.clone())),
+                ExclusionRule::Platform(platform) => exclusions.platforms.push(*platform),
+            }
+        }
+        exclusions
+    }
+
+    /// The saved rules plus automation's older flat list of excluded
+    /// folders, which `excluded_paths` stands in for when it comes from a
+    /// newer automation config than the one on disk.
+    pub fn with_excluded_paths(rules: &[ExclusionRule], excluded_paths: &[String]) -> Self {
+        let mut all = rules.to_vec();
+        all.extend(excluded_paths.iter().cloned().map(ExclusionRule::Path));
+        Self::new(&all)
+    }
+
+    /// Exclusions from the saved settings.
+    pub fn load() -> Self {
+        let settings = super::load();
+        Self::with_excluded_paths(&settings.exclusions, &settings.automation.excluded_paths)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.globs.is_empty() && self.platforms.is_empty()
+    }
+
+    /// The rule excluding the game at `path` from `platform`, if any.
+    pub fn matching(&self, path: &Path, platform: Option<Platform>) -> Option<ExclusionRule> {
+        if let Some(platform) = platform.filter(|platform| self.platforms.contains(platform)) {
+            return Some(ExclusionRule::Platform(platform));
+        }
+        let key = normalize_path_key(path);
+        let by_path = self.paths.iter().find(|(excluded, _)| {
+            key.strip_prefix(excluded.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['\\', '/']))
+        });
+        let by_glob = || {
+            let key = key.replace('/', "\\");
+            self.globs
+                .iter()
+                .find(|(pattern, _)| wildcard_match(pattern, &key))
+        };
+        by_path.or_else(by_glob).map(|(_, rule)| rule.clone())
+    }
+
+    /// [`Self::matching`] for a folder found outside discovery, such as an
+    /// automation event. Platform rules use the platform discovery last
+    /// recorded for it.
+    pub fn matching_install(&self, path: &Path) -> Option<ExclusionRule> {
+        let platform = if self.platforms.is_empty() {
+            None
+        } else {
+            crate::discovery::index::platform_of(path)
+        };
+        self.matching(path, platform)
+    }
+
+    /// Mark every game a rule matches as excluded. Games already excluded
+    /// by their scanner stay excluded.
+    pub fn mark(&self, games: &mut [GameInfo]) {
+        if self.is_empty() {
+            return;
+        }
+        for game in games {
+            if !game.excluded && self.matching(&game.path, Some(game.platform)).is_some() {
+                game.excluded = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_match_folders_patterns_and_platforms() {
+        let exclusions = Exclusions::new(&[
+            ExclusionRule::Path(r"D:\Games\Skyrim".into()),
+            ExclusionRule::Glob(r"D:\Games\*Demo*".into()),
+            ExclusionRule::Platform(Platform::XboxGamePass),
+        ]);
+        let matching = |path: &str, platform| exclusions.matching(Path::new(path), platform);
+
+        assert_eq!(
+            matching(r"d:\games\skyrim\Data", Some(Platform::Steam)),
+            Some(ExclusionRule::Path(r"D:\Games\Skyrim".into()))
+        );
+        assert_eq!(matching(r"D:\Games\SkyrimSE", Some(Platform::Steam)), None);
+        assert_eq!(
+            matching(r"D:\Games\Portal Demo", None),
+            Some(ExclusionRule::Glob(r"D:\Games\*Demo*".into()))
+        );
+        assert_eq!(
+            matching(r"C:\XboxGames\Halo", Some(Platform::XboxGamePass)),
+            Some(ExclusionRule::Platform(Platform::XboxGamePass))
+        );
+        assert_eq!(matching(r"C:\XboxGames\Halo", None), None);
+    }
+
+    #[test]
+    fn validation_drops_blank_and_duplicate_rules() {
+        let rules = validated(vec![
+            ExclusionRule::Path(r" D:\Games\Skyrim ".into()),
+            ExclusionRule::Path(r"d:\games\skyrim\".into()),
+            ExclusionRule::Glob("  ".into()),
+            ExclusionRule::Platform(Platform::Steam),
+            ExclusionRule::Platform(Platform::Steam),
+        ]);
+
+        assert_eq!(
+            rules,
+            [
+                ExclusionRule::Path(r"D:\Games\Skyrim".into()),
+                ExclusionRule::Platform(Platform::Steam),
+            ]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! Library reconcile section of the settings file.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest interval accepted, so a typo cannot switch the check off for
+/// years.
+const MAX_INTERVAL_HOURS: u32 = 90 * 24;
+
+/// How often previously compressed games are re-checked for lost savings;
+/// see [`crate::automation::library_reconcile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LibraryReconcileSettings {
+    /// Hours between checks; 0 turns them off.
+    pub interval_hours: u32,
+    /// A game keeping less than this share of the savings its last
+    /// compression achieved is compressed again.
+    pub min_retained_savings_percent: u8,
+}
+
+impl Default for LibraryReconcileSettings {
+    fn default() -> Self {
+        Self {
+            interval_hours: 7 * 24,
+            min_retained_savings_percent: 80,
+        }
+    }
+}
+
+impl LibraryReconcileSettings {
+    /// Clamp values to safe ranges.
+    pub fn validated(mut self) -> Self {
+        self.interval_hours = self.interval_hours.min(MAX_INTERVAL_HOURS);
+        self.min_retained_savings_percent = self.min_retained_savings_percent.clamp(1, 100);
+        self
+    }
+
+    /// Time between checks, or `None` when they are off.
+    pub fn interval(&self) -> Option<Duration> {
+        (self.interval_hours > 0)
+            .then(|| Duration::from_secs(u64::from(self.interval_hours) * 60 * 60))
+    }
+
+    pub fn min_retained_savings(&self) -> f64 {
+        f64::from(self.min_retained_savings_percent) / 100.0
+    }
+}
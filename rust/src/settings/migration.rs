@@ -0,0 +1,82 @@
+//! Upgrades older settings documents to the current schema.
+//!
+//! Each step rewrites the JSON of one version into the next, so a file of
+//! any age walks forward one version at a time before it is deserialized.
+
+use serde_json::{json, Value};
+
+use super::Settings;
+
+pub const SETTINGS_SCHEMA_VERSION: u32 = 2;
+
+/// Version of a parsed document. Version 1, the bare automation config the
+/// daemon saved before the settings store existed, has no version field.
+pub(super) fn schema_version(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map_or(1, |version| version as u32)
+}
+
+/// Bring a parsed settings document up to the current schema.
+pub(super) fn migrate(mut value: Value) -> serde_json::Result<Settings> {
+    let version = schema_version(&value);
+    if version > SETTINGS_SCHEMA_VERSION {
+        log::warn!(
+            "Settings schema {version} is newer than this build ({SETTINGS_SCHEMA_VERSION}); \
+             unknown fields are ignored"
+        );
+    }
+    if version < 2 {
+        value = v1_to_v2(value);
+    }
+    let mut settings: Settings = serde_json::from_value(value)?;
+    settings.schema_version = SETTINGS_SCHEMA_VERSION;
+    Ok(settings)
+}
+
+/// v1 held only the automation config; everything else takes defaults.
+fn v1_to_v2(automation: Value) -> Value {
+    json!({
+        "schema_version": 2,
+        "automation": automation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::algorithm::CompressionAlgorithm;
+
+    #[test]
+    fn v1_automation_config_becomes_automation_section() {
+        let v1 = json!({
+            "cpu_threshold_percent": 25.0,
+            "idle_duration_seconds": 600,
+            "cooldown_seconds": 120,
+            "algorithm": "Lzx",
+        });
+        assert_eq!(schema_version(&v1), 1);
+
+        let settings = migrate(v1).unwrap();
+
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.automation.idle_duration_seconds, 600);
+        assert_eq!(settings.automation.algorithm, CompressionAlgorithm::Lzx);
+        assert!(settings.auto_compress);
+    }
+
+    #[test]
+    fn newer_schema_keeps_known_fields() {
+        let future = json!({
+            "schema_version": SETTINGS_SCHEMA_VERSION + 1,
+            "auto_compress": false,
+            "some_future_setting": true,
+        });
+
+        let settings = migrate(future).unwrap();
+
+        assert!(!settings.auto_compress);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+    }
+}
@@ -0,0 +1,296 @@
+//! User settings shared by the app, the daemon and the CLI.
+//!
+//! One versioned JSON file in the per-user config directory. The app
+//! reads and writes it through the settings API; the daemon and CLI read
+//! the same file, so a change made in any of them applies to all.
+//! Documents from older builds are upgraded by [`migration`] on load.
+//! The SteamGridDB key is not stored here; it stays in the OS keychain.
+
+pub mod automation;
+pub mod exclusions;
+pub mod library_reconcile;
+pub mod migration;
+pub mod user_data;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+pub use self::automation::AutomationSettings;
+pub use self::exclusions::{ExclusionRule, Exclusions};
+pub use self::library_reconcile::LibraryReconcileSettings;
+pub use self::migration::SETTINGS_SCHEMA_VERSION;
+pub use self::user_data::UserDataSettings;
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+/// Schema v1: the automation config the daemon saved on its own.
+const LEGACY_AUTOMATION_FILE_NAME: &str = "automation_config.json";
+const DEFAULT_THEME_VARIANT: &str = "cinematicDesert";
+
+/// Serializes read-modify-write cycles within this process.
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub schema_version: u32,
+    /// Run automation at all; the app and daemon start it only when set.
+    pub auto_compress: bool,
+    pub automation: AutomationSettings,
+    /// Compress files only administrators may write, through backup
+    /// semantics. Needs an elevated app or the daemon service; see
+    /// [`crate::compression::privilege`].
+    pub compress_admin_only_files: bool,
+    /// Time game launches and flag games that launch slower since they
+    /// were compressed; see [`crate::compression::launch_lag`].
+    pub detect_launch_lag: bool,
+    /// Decompress a flagged game once it exits instead of only
+    /// recommending it. Needs `detect_launch_lag`.
+    pub auto_decompress_on_launch_lag: bool,
+    /// Decompress a game that keeps crashing right after launch while
+    /// compressed; see [`crate::compression::crash_rollback`].
+    pub rollback_on_crashes: bool,
+    /// Compress approved save and config folders; off by default.
+    pub user_data: UserDataSettings,
+    /// Games never to compress; see [`exclusions`].
+    pub exclusions: Vec<ExclusionRule>,
+    /// Periodic re-check of compressed games for lost savings.
+    pub library_reconcile: LibraryReconcileSettings,
+    pub notifications_enabled: bool,
+    pub minimize_to_tray: bool,
+    pub auto_check_updates: bool,
+    pub theme_variant: String,
+    /// BCP 47 tag such as `es`; `None` follows the system language.
+    pub locale_tag: Option<String>,
+    /// Where journals, caches, history and logs go instead of the default
+    /// directory; see [`crate::paths`]. Applies from the next start.
+    pub data_dir: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            auto_compress: true,
+            automation: AutomationSettings::default(),
+            compress_admin_only_files: false,
+            detect_launch_lag: false,
+            auto_decompress_on_launch_lag: false,
+            rollback_on_crashes: false,
+            user_data: UserDataSettings::default(),
+            exclusions: Vec::new(),
+            library_reconcile: LibraryReconcileSettings::default(),
+            notifications_enabled: true,
+            minimize_to_tray: true,
+            auto_check_updates: true,
+            theme_variant: DEFAULT_THEME_VARIANT.to_string(),
+            locale_tag: None,
+            data_dir: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Clamp values to safe ranges and normalize free-form strings.
+    pub fn validated(mut self) -> Self {
+        self.schema_version = SETTINGS_SCHEMA_VERSION;
+        self.automation = self.automation.validated();
+        self.user_data = self.user_data.validated();
+        self.exclusions = exclusions::validated(self.exclusions);
+        self.library_reconcile = self.library_reconcile.validated();
+        if self.theme_variant.trim().is_empty() {
+            self.theme_variant = DEFAULT_THEME_VARIANT.to_string();
+        }
+        self.locale_tag = self
+            .locale_tag
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty());
+        self.data_dir = self
+            .data_dir
+            .map(|dir| dir.trim().to_string())
+            .filter(|dir| !dir.is_empty());
+        self
+    }
+}
+
+/// Saved settings, or `None` before anything has been saved.
+pub fn load_saved() -> Option<Settings> {
+    load_saved_in(&settings_dir().ok()?)
+}
+
+/// Saved settings, or the defaults.
+pub fn load() -> Settings {
+    load_saved().unwrap_or_default()
+}
+
+/// Apply `edit` to the current settings and save the validated result.
+pub fn update(edit: impl FnOnce(&mut Settings)) -> io::Result<Settings> {
+    update_in(&settings_dir()?, edit)
+}
+
+/// The `data_dir` override from the saved settings, read without
+/// migration or logging so [`crate::paths`] can call it before anything
+/// else is set up.
+#[cfg(not(test))]
+pub(crate) fn saved_data_dir() -> Option<String> {
+    saved_data_dir_in(&settings_dir().ok()?)
+}
+
+fn saved_data_dir_in(dir: &Path) -> Option<String> {
+    #[derive(Deserialize)]
+    struct DataDirOnly {
+        #[serde(default)]
+        data_dir: Option<String>,
+    }
+
+    let contents = fs::read(dir.join(SETTINGS_FILE_NAME)).ok()?;
+    serde_json::from_slice::<DataDirOnly>(&contents)
+        .ok()?
+        .data_dir
+}
+
+fn load_saved_in(dir: &Path) -> Option<Settings> {
+    let path = dir.join(SETTINGS_FILE_NAME);
+    match fs::read(&path) {
+        Ok(contents) => load_document(dir, &path, &contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => load_legacy(dir),
+        Err(e) => {
+            log::warn!("Failed to read {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Parse and upgrade a document, saving it back if it was migrated.
+fn load_document(dir: &Path, path: &Path, contents: &[u8]) -> Option<Settings> {
+    let value: serde_json::Value = match serde_json::from_slice(contents) {
+        Ok(value) => value,
+        Err(e) => {
+            log::warn!("Ignoring unreadable {}: {e}", path.display());
+            return None;
+        }
+    };
+    let version = migration::schema_version(&value);
+    let settings = match migration::migrate(value) {
+        Ok(settings) => settings.validated(),
+        Err(e) => {
+            log::warn!("Ignoring invalid {}: {e}", path.display());
+            return None;
+        }
+    };
+    if version < SETTINGS_SCHEMA_VERSION {
+        log::info!("Migrated settings from schema {version} to {SETTINGS_SCHEMA_VERSION}");
+        if let Err(e) = save_in(dir, &settings) {
+            log::warn!("Failed to save migrated settings: {e}");
+        }
+    }
+    Some(settings)
+}
+
+/// Adopt the daemon's old automation-only file as a v1 document.
+fn load_legacy(dir: &Path) -> Option<Settings> {
+    let legacy_path = dir.join(LEGACY_AUTOMATION_FILE_NAME);
+    let contents = fs::read(&legacy_path).ok()?;
+    let settings = load_document(dir, &legacy_path, &contents)?;
+    if dir.join(SETTINGS_FILE_NAME).exists() {
+        let _ = fs::remove_file(&legacy_path);
+    }
+    Some(settings)
+}
+
+fn update_in(dir: &Path, edit: impl FnOnce(&mut Settings)) -> io::Result<Settings> {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = load_saved_in(dir).unwrap_or_default();
+    edit(&mut settings);
+    let settings = settings.validated();
+    save_in(dir, &settings)?;
+    Ok(settings)
+}
+
+fn save_in(dir: &Path, settings: &Settings) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(settings).map_err(io::Error::other)?;
+    crate::utils::atomic_write(&dir.join(SETTINGS_FILE_NAME), &json)
+}
+
+fn settings_dir() -> io::Result<PathBuf> {
+    #[cfg(test)]
+    {
+        static TEST_SETTINGS_DIR: std::sync::LazyLock<PathBuf> = std::sync::LazyLock::new(|| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            std::env::temp_dir().join(format!(
+                "compact-games-settings-tests-{}-{now}",
+                std::process::id()
+            ))
+        });
+        Ok(TEST_SETTINGS_DIR.clone())
+    }
+
+    #[cfg(not(test))]
+    {
+        crate::paths::default_data_dir()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::algorithm::CompressionAlgorithm;
+
+    #[test]
+    fn legacy_automation_file_is_migrated_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(LEGACY_AUTOMATION_FILE_NAME),
+            r#"{"cpu_threshold_percent":15.0,"idle_duration_seconds":300,
+                "cooldown_seconds":60,"watch_paths":["D:\\Games"],"algorithm":"Lzx"}"#,
+        )
+        .unwrap();
+
+        let settings = load_saved_in(dir.path()).expect("migrated settings");
+
+        assert_eq!(settings.automation.algorithm, CompressionAlgorithm::Lzx);
+        assert_eq!(settings.automation.watch_paths, ["D:\\Games"]);
+        assert!(dir.path().join(SETTINGS_FILE_NAME).exists());
+        assert!(!dir.path().join(LEGACY_AUTOMATION_FILE_NAME).exists());
+        assert_eq!(load_saved_in(dir.path()), Some(settings));
+    }
+
+    #[test]
+    fn update_validates_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_saved_in(dir.path()), None);
+
+        let saved = update_in(dir.path(), |settings| {
+            settings.auto_compress = false;
+            settings.automation.cpu_threshold_percent = 99.0;
+            settings.locale_tag = Some("  ".into());
+        })
+        .unwrap();
+
+        assert!(!saved.auto_compress);
+        assert_eq!(saved.automation.cpu_threshold_percent, 80.0);
+        assert_eq!(saved.locale_tag, None);
+        assert_eq!(load_saved_in(dir.path()), Some(saved));
+    }
+
+    #[test]
+    fn data_dir_override_is_read_on_its_own() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(saved_data_dir_in(dir.path()), None);
+
+        update_in(dir.path(), |settings| {
+            settings.data_dir = Some(r"  D:\CompactGames  ".into());
+        })
+        .unwrap();
+        assert_eq!(
+            saved_data_dir_in(dir.path()).as_deref(),
+            Some(r"D:\CompactGames")
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! User-data section of the settings file.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::normalize_path_key;
+
+/// Save and config folders the user opted into compressing; see
+/// [`crate::compression::user_data`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserDataSettings {
+    /// Allow compressing approved folders at all.
+    pub enabled: bool,
+    /// Folders the user approved. Nothing else under the profile is touched.
+    pub approved_paths: Vec<String>,
+    /// Let automation compress approved folders too. Off by default: games
+    /// write their saves while running, which automation cannot see.
+    pub include_in_automation: bool,
+}
+
+impl UserDataSettings {
+    /// Drop blank and duplicate approvals.
+    pub fn validated(mut self) -> Self {
+        let mut seen = HashSet::new();
+        self.approved_paths = self
+            .approved_paths
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty() && seen.insert(normalize_path_key(Path::new(path))))
+            .collect();
+        self
+    }
+}
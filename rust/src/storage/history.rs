@@ -0,0 +1,203 @@
+//! Compression history rows.
+
+use std::path::Path;
+
+use rusqlite::params;
+
+use super::Database;
+use crate::compression::history::cache::HistoryCache;
+use crate::compression::history::CompressionHistoryEntry;
+
+const LEGACY_IMPORT_NAME: &str = "compression_history.json";
+
+impl Database {
+    /// Newest-first history, at most `limit` entries.
+    pub fn history_entries(&self, limit: usize) -> rusqlite::Result<Vec<CompressionHistoryEntry>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT payload FROM compression_history
+                 ORDER BY timestamp_ms DESC, id DESC LIMIT ?1",
+            )?;
+            let payloads = stmt
+                .query_map([limit as i64], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(decode_entries(&payloads))
+        })
+    }
+
+    /// Newest-first history for one game, served from the path index.
+    pub fn history_for_path(
+        &self,
+        game_path: &Path,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<CompressionHistoryEntry>> {
+        let path_key = crate::utils::normalize_path_key(game_path);
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT payload FROM compression_history WHERE path_key = ?1
+                 ORDER BY timestamp_ms DESC, id DESC LIMIT ?2",
+            )?;
+            let payloads = stmt
+                .query_map(params![path_key, limit as i64], |row| {
+                    row.get::<_, String>(0)
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(decode_entries(&payloads))
+        })
+    }
+
+    /// Append new entries, then drop everything older than
+    /// `min_timestamp_ms` or beyond the newest `retain`.
+    pub fn append_history(
+        &self,
+        entries: &[CompressionHistoryEntry],
+        retain: usize,
+        min_timestamp_ms: u64,
+    ) -> rusqlite::Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.transaction()?;
+            for entry in entries {
+                insert_entry(&tx, entry)?;
+            }
+            prune(&tx, retain, min_timestamp_ms)?;
+            tx.commit()
+        })
+    }
+
+    /// Import `compression_history.json` from before the SQLite migration.
+    pub fn import_legacy_history(
+        &self,
+        legacy_path: &Path,
+        retain: usize,
+    ) -> rusqlite::Result<usize> {
+        self.import_legacy_once(LEGACY_IMPORT_NAME, legacy_path, |tx, contents| {
+            let entries = serde_json::from_str::<HistoryCache>(contents)
+                .map(|cache| cache.entries)
+                .unwrap_or_default();
+            for entry in &entries {
+                insert_entry(tx, entry)?;
+            }
+            prune(tx, retain, 0)?;
+            Ok(entries.len())
+        })
+    }
+}
+
+fn decode_entries(payloads: &[String]) -> Vec<CompressionHistoryEntry> {
+    payloads
+        .iter()
+        .filter_map(|payload| serde_json::from_str(payload).ok())
+        .collect()
+}
+
+fn insert_entry(
+    conn: &rusqlite::Connection,
+    entry: &CompressionHistoryEntry,
+) -> rusqlite::Result<()> {
+    let payload = serde_json::to_string(entry)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    conn.execute(
+        "INSERT INTO compression_history (path_key, timestamp_ms, payload)
+         VALUES (?1, ?2, ?3)",
+        params![
+            crate::utils::normalize_path_key(Path::new(&entry.game_path)),
+            entry.timestamp_ms as i64,
+            payload
+        ],
+    )?;
+    Ok(())
+}
+
+fn prune(
+    conn: &rusqlite::Connection,
+    retain: usize,
+    min_timestamp_ms: u64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "DELETE FROM compression_history WHERE timestamp_ms < ?2 OR id NOT IN (
+             SELECT id FROM compression_history
+             ORDER BY timestamp_ms DESC, id DESC LIMIT ?1
+         )",
+        params![retain as i64, min_timestamp_ms as i64],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::algorithm::CompressionAlgorithm;
+    use crate::compression::history::{ActualStats, EstimateSnapshot};
+
+    fn entry(game_path: &str, timestamp_ms: u64) -> CompressionHistoryEntry {
+        CompressionHistoryEntry {
+            game_path: game_path.to_owned(),
+            game_name: "Test Game".to_owned(),
+            timestamp_ms,
+            estimate: EstimateSnapshot {
+                scanned_files: 0,
+                sampled_bytes: 0,
+                estimated_saved_bytes: 0,
+            },
+            actual_stats: ActualStats {
+                original_bytes: 10_000,
+                compressed_bytes: 8_000,
+                actual_saved_bytes: 2_000,
+                files_processed: 10,
+            },
+            algorithm: CompressionAlgorithm::Xpress8K,
+            duration_ms: 100,
+            extensions: Vec::new(),
+            throughput: None,
+        }
+    }
+
+    #[test]
+    fn append_prunes_oldest_and_path_query_uses_normalized_key() {
+        let db = Database::open_in_memory().unwrap();
+        db.append_history(
+            &[
+                entry(r"C:\Games\Alpha", 1),
+                entry(r"C:\Games\Beta", 2),
+                entry(r"C:\Games\Alpha", 3),
+            ],
+            2,
+            0,
+        )
+        .unwrap();
+
+        let all = db.history_entries(10).unwrap();
+        assert_eq!(
+            all.iter().map(|e| e.timestamp_ms).collect::<Vec<_>>(),
+            vec![3, 2]
+        );
+
+        let alpha = db
+            .history_for_path(Path::new(r"c:\games\alpha\"), 10)
+            .unwrap();
+        assert_eq!(alpha.len(), 1);
+        assert_eq!(alpha[0].timestamp_ms, 3);
+    }
+
+    #[test]
+    fn append_prunes_entries_older_than_cutoff() {
+        let db = Database::open_in_memory().unwrap();
+        db.append_history(
+            &[
+                entry(r"C:\Games\Alpha", 10),
+                entry(r"C:\Games\Beta", 20),
+                entry(r"C:\Games\Gamma", 30),
+            ],
+            10,
+            20,
+        )
+        .unwrap();
+        db.append_history(&[], 10, 25).unwrap();
+
+        let all = db.history_entries(10).unwrap();
+        assert_eq!(
+            all.iter().map(|e| e.timestamp_ms).collect::<Vec<_>>(),
+            vec![30]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Automation journal rows.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use rusqlite::params;
+
+use super::Database;
+use crate::automation::journal::{recover_entries, JournalEntry};
+
+const LEGACY_IMPORT_NAME: &str = "automation_journal.json";
+
+impl Database {
+    /// All pending journal entries in queue order.
+    pub fn journal_entries(&self) -> rusqlite::Result<Vec<JournalEntry>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT payload FROM automation_journal ORDER BY queued_at_ms, rowid")?;
+            let payloads = stmt
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(payloads
+                .iter()
+                .filter_map(|payload| serde_json::from_str(payload).ok())
+                .collect())
+        })
+    }
+
+    /// Make the stored journal match `entries`, touching only rows whose
+    /// idempotency key was added or removed.
+    pub fn sync_journal(&self, entries: &[JournalEntry]) -> rusqlite::Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let stored: HashSet<String> = {
+                let mut stmt = tx.prepare("SELECT idempotency_key FROM automation_journal")?;
+                let keys = stmt
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<rusqlite::Result<HashSet<_>>>()?;
+                keys
+            };
+            let wanted: HashSet<&str> =
+                entries.iter().map(|e| e.idempotency_key.as_str()).collect();
+
+            {
+                let mut delete =
+                    tx.prepare("DELETE FROM automation_journal WHERE idempotency_key = ?1")?;
+                for key in stored.iter().filter(|k| !wanted.contains(k.as_str())) {
+                    delete.execute([key])?;
+                }
+            }
+            for entry in entries
+                .iter()
+                .filter(|e| !stored.contains(&e.idempotency_key))
+            {
+                insert_entry(&tx, entry)?;
+            }
+            tx.commit()
+        })
+    }
+
+    /// Import `automation_journal.json` from before the SQLite migration.
+    pub fn import_legacy_journal(&self, legacy_path: &Path) -> rusqlite::Result<usize> {
+        self.import_legacy_once(LEGACY_IMPORT_NAME, legacy_path, |tx, contents| {
+            let (entries, dropped) = recover_entries(contents);
+            if dropped > 0 {
+                log::warn!("Dropped {dropped} unreadable legacy automation journal entries");
+            }
+            for entry in &entries {
+                insert_entry(tx, entry)?;
+            }
+            Ok(entries.len())
+        })
+    }
+}
+
+fn insert_entry(conn: &rusqlite::Connection, entry: &JournalEntry) -> rusqlite::Result<()> {
+    let payload = serde_json::to_string(entry)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    let queued_at_ms = entry
+        .queued_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    conn.execute(
+        "INSERT OR REPLACE INTO automation_journal
+             (idempotency_key, game_path, queued_at_ms, payload)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            entry.idempotency_key,
+            entry.game_path.to_string_lossy(),
+            queued_at_ms,
+            payload
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Embedded SQLite storage for automation and compression state.
+//!
+//! One database file (`compact_games.db` in the data directory) in WAL
+//! mode backs the automation journal, scheduler snapshot and compression
+//! history. Rows keep the full record as a JSON payload next to the
+//! indexed lookup columns, so adding fields to the Rust types never needs
+//! a schema change.
+//!
+//! Legacy JSON files are imported once on first open and renamed to
+//! `*.migrated`.
+
+pub mod history;
+pub mod journal;
+pub mod scheduler;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use rusqlite::{Connection, OptionalExtension};
+
+const DATABASE_FILE_NAME: &str = "compact_games.db";
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Schema migrations, applied in order. `PRAGMA user_version` records how
+/// many have run.
+const MIGRATIONS: &[&str] = &[
+    // v1: journal, history, one-shot JSON import markers.
+    "CREATE TABLE automation_journal (
+         idempotency_key TEXT PRIMARY KEY NOT NULL,
+         game_path TEXT NOT NULL,
+         queued_at_ms INTEGER NOT NULL,
+         payload TEXT NOT NULL
+     );
+     CREATE TABLE compression_history (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         path_key TEXT NOT NULL,
+         timestamp_ms INTEGER NOT NULL,
+         payload TEXT NOT NULL
+     );
+     CREATE INDEX idx_compression_history_path_time
+         ON compression_history (path_key, timestamp_ms DESC);
+     CREATE INDEX idx_compression_history_time
+         ON compression_history (timestamp_ms DESC);
+     CREATE TABLE legacy_imports (
+         name TEXT PRIMARY KEY NOT NULL,
+         imported_at_ms INTEGER NOT NULL
+     );",
+    // v2: scheduler snapshot, a single row.
+    "CREATE TABLE scheduler_snapshot (
+         id INTEGER PRIMARY KEY CHECK (id = 1),
+         payload TEXT NOT NULL
+     );",
+];
+
+/// Shared handle to the storage database.
+///
+/// Thread-safe via interior `Mutex`; SQLite itself serializes writers.
+pub struct Database {
+    conn: Mutex<Connection>,
+}
+
+static GLOBAL_DATABASE: OnceLock<Option<Arc<Database>>> = OnceLock::new();
+
+impl Database {
+    /// Open (creating if needed) the database at `path` and run migrations.
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a private in-memory database (tests and fallbacks).
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(mut conn: Connection) -> rusqlite::Result<Self> {
+        migrate(&mut conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Process-wide database in the app config directory.
+    ///
+    /// Returns `None` when the file cannot be opened; callers fall back to
+    /// their JSON persistence in that case.
+    pub fn global() -> Option<Arc<Database>> {
+        GLOBAL_DATABASE
+            .get_or_init(|| {
+                let path = default_database_path();
+                match Self::open(&path) {
+                    Ok(db) => Some(Arc::new(db)),
+                    Err(e) => {
+                        log::warn!(
+                            "[storage] failed to open database {}: {e}; using JSON persistence",
+                            path.display()
+                        );
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    pub(crate) fn with_conn<R>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> rusqlite::Result<R>,
+    ) -> rusqlite::Result<R> {
+        let mut conn = self.conn.lock().unwrap_or_else(|p| {
+            log::warn!("Storage connection lock poisoned; recovering");
+            p.into_inner()
+        });
+        f(&mut conn)
+    }
+
+    /// Import a legacy JSON file exactly once.
+    ///
+    /// `import` runs inside a transaction together with the import marker,
+    /// so a crash mid-import leaves nothing behind and the import is retried.
+    /// The source file is renamed to `*.migrated` afterwards.
+    pub(crate) fn import_legacy_once(
+        &self,
+        name: &str,
+        legacy_path: &Path,
+        import: impl FnOnce(&rusqlite::Transaction<'_>, &str) -> rusqlite::Result<usize>,
+    ) -> rusqlite::Result<usize> {
+        let imported = self.with_conn(|conn| {
+            let tx = conn.transaction()?;
+            let already: Option<i64> = tx
+                .query_row(
+                    "SELECT imported_at_ms FROM legacy_imports WHERE name = ?1",
+                    [name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if already.is_some() {
+                return Ok(None);
+            }
+
+            let count = match fs::read_to_string(legacy_path) {
+                Ok(contents) => import(&tx, &contents)?,
+                Err(_) => 0,
+            };
+            tx.execute(
+                "INSERT INTO legacy_imports (name, imported_at_ms) VALUES (?1, ?2)",
+                rusqlite::params![name, crate::utils::unix_now_ms() as i64],
+            )?;
+            tx.commit()?;
+            Ok(Some(count))
+        })?;
+
+        let Some(count) = imported else {
+            return Ok(0);
+        };
+        if legacy_path.exists() {
+            let mut migrated = legacy_path.as_os_str().to_owned();
+            migrated.push(".migrated");
+            if let Err(e) = fs::rename(legacy_path, PathBuf::from(migrated)) {
+                log::warn!(
+                    "[storage] imported {} but could not rename it: {e}",
+                    legacy_path.display()
+                );
+            }
+            log::info!(
+                "[storage] imported {count} record(s) from {}",
+                legacy_path.display()
+            );
+        }
+        Ok(count)
+    }
+}
+
+fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    let applied = usize::try_from(version).unwrap_or(0);
+    if applied >= MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    let tx = conn.transaction()?;
+    for migration in &MIGRATIONS[applied..] {
+        tx.execute_batch(migration)?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len() as i64)?;
+    tx.commit()
+}
+
+fn config_dir() -> PathBuf {
+    crate::paths::data_dir_or_fallback()
+}
+
+fn default_database_path() -> PathBuf {
+    config_dir().join(DATABASE_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reopen_does_not_rerun_migrations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.db");
+        drop(Database::open(&path).unwrap());
+
+        let db = Database::open(&path).unwrap();
+        let version: i64 = db
+            .with_conn(|c| c.pragma_query_value(None, "user_version", |row| row.get(0)))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn legacy_import_runs_once_and_renames_source() {
+        let dir = TempDir::new().unwrap();
+        let legacy = dir.path().join("legacy.json");
+        fs::write(&legacy, "[1,2,3]").unwrap();
+        let db = Database::open_in_memory().unwrap();
+
+        let first = db
+            .import_legacy_once("legacy", &legacy, |_, contents| Ok(contents.len()))
+            .unwrap();
+        fs::write(&legacy, "[4]").unwrap();
+        let second = db
+            .import_legacy_once("legacy", &legacy, |_, _| panic!("imported twice"))
+            .unwrap();
+
+        assert_eq!(first, 7);
+        assert_eq!(second, 0);
+        assert!(dir.path().join("legacy.json.migrated").exists());
+    }
+}
@@ -0,0 +1,57 @@
+//! Automation scheduler snapshot.
+
+use rusqlite::OptionalExtension;
+
+use super::Database;
+use crate::automation::scheduler::SchedulerSnapshot;
+
+impl Database {
+    /// The saved scheduler snapshot, if any. An unreadable payload counts
+    /// as none.
+    pub fn scheduler_snapshot(&self) -> rusqlite::Result<Option<SchedulerSnapshot>> {
+        let payload: Option<String> = self.with_conn(|conn| {
+            conn.query_row(
+                "SELECT payload FROM scheduler_snapshot WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+        })?;
+        Ok(payload.and_then(|payload| serde_json::from_str(&payload).ok()))
+    }
+
+    /// Replace the saved scheduler snapshot.
+    pub fn save_scheduler_snapshot(&self, snapshot: &SchedulerSnapshot) -> rusqlite::Result<()> {
+        let payload = serde_json::to_string(snapshot)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO scheduler_snapshot (id, payload) VALUES (1, ?1)",
+                [payload],
+            )?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_roundtrips_and_is_replaced() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(db.scheduler_snapshot().unwrap().is_none());
+
+        for failures in [2, 3] {
+            db.save_scheduler_snapshot(&SchedulerSnapshot {
+                consecutive_failures: failures,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+
+        let restored = db.scheduler_snapshot().unwrap().unwrap();
+        assert_eq!(restored.consecutive_failures, 3);
+    }
+}
@@ -1,7 +1,7 @@
 use std::ffi::OsString;
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::UNIX_EPOCH;
 
@@ -38,6 +38,67 @@ pub fn wide_null_str(value: &str) -> Vec<u16> {
     value.encode_utf16().chain(std::iter::once(0)).collect()
 }
 
+/// Paths this long may not fit a plain Win32 path argument: MAX_PATH is
+/// 260 characters, and directory APIs reserve 12 of them for an 8.3 name.
+const EXTENDED_PATH_THRESHOLD: usize = 248;
+
+/// Extended-length (`\\?\`) form of a long absolute path, so Win32 calls
+/// that take a path string accept it past MAX_PATH. `std::fs` does this
+/// itself; raw FFI calls do not. Verbatim paths skip Win32 normalization,
+/// so separators are unified and `.` and `..` resolved here. Short,
+/// relative and already-prefixed paths are returned unchanged.
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    let Some(raw) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    if raw.len() < EXTENDED_PATH_THRESHOLD || raw.starts_with(r"\\?\") || raw.starts_with(r"\\.\") {
+        return path.to_path_buf();
+    }
+
+    let raw = raw.replace('/', "\\");
+    let bytes = raw.as_bytes();
+    let (prefix, rest, root_parts) = if let Some(unc) = raw.strip_prefix(r"\\") {
+        (r"\\?\UNC\", unc, 2)
+    } else if bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && bytes[2] == b'\\'
+    {
+        (r"\\?\", raw.as_str(), 1)
+    } else {
+        return path.to_path_buf();
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.len() > root_parts {
+                    parts.pop();
+                }
+            }
+            part => parts.push(part),
+        }
+    }
+    let mut extended = format!("{prefix}{}", parts.join("\\"));
+    if parts.len() == root_parts {
+        extended.push('\\');
+    }
+    PathBuf::from(extended)
+}
+
+/// Encode a path as a null-terminated UTF-16 buffer for Win32 path APIs,
+/// in extended-length form when it is too long for MAX_PATH.
+#[cfg(windows)]
+pub fn wide_path(path: &Path) -> Vec<u16> {
+    extended_length_path(path)
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
 /// Current time as milliseconds since Unix epoch.
 pub fn unix_now_ms() -> u64 {
     std::time::SystemTime::now()
@@ -66,8 +127,119 @@ pub fn normalize_path_key(path: &Path) -> String {
     }
 }
 
-/// Write a file via a sibling temp file and atomic replace where supported.
+/// Case-insensitive match of `name` against `pattern`, where `*` matches
+/// any run of characters and `?` exactly one.
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// 64-bit FNV-1a of `value`. Unlike `DefaultHasher` it is the same in
+/// every build, so separate processes can name shared objects with it.
+pub fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// A named kernel object held as a cross-process lock. The name is taken
+/// while any process keeps a handle open, so the OS releases it when its
+/// holder exits or crashes.
+pub struct NamedLock {
+    #[cfg(windows)]
+    handle: windows::Win32::Foundation::HANDLE,
+}
+
+// SAFETY: the handle is only closed, once, on drop; kernel handles may be
+// closed from any thread.
+#[cfg(windows)]
+unsafe impl Send for NamedLock {}
+
+#[cfg(windows)]
+impl Drop for NamedLock {
+    fn drop(&mut self) {
+        // SAFETY: the handle came from CreateMutexW and is closed once.
+        let _ = unsafe { windows::Win32::Foundation::CloseHandle(self.handle) };
+    }
+}
+
+/// Take the lock called `name`, such as `Global\CompactGames.Thing`.
+/// `Ok(None)` means another holder has it, including one in another
+/// Windows account; an error means named objects are unavailable.
+#[cfg(windows)]
+pub fn try_named_lock(name: &str) -> io::Result<Option<NamedLock>> {
+    use windows::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_ALREADY_EXISTS,
+    };
+    use windows::Win32::System::Threading::CreateMutexW;
+
+    let wide = wide_null_str(name);
+    // SAFETY: `wide` is NUL-terminated and outlives the call.
+    match unsafe { CreateMutexW(None, false, PCWSTR(wide.as_ptr())) } {
+        Ok(handle) => {
+            // SAFETY: reads the calling thread's last-error value.
+            if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
+                // SAFETY: `handle` was just opened and is not used again.
+                let _ = unsafe { CloseHandle(handle) };
+                return Ok(None);
+            }
+            Ok(Some(NamedLock { handle }))
+        }
+        // Another account created it; its default DACL keeps us out.
+        Err(e) if e.code() == ERROR_ACCESS_DENIED.to_hresult() => Ok(None),
+        Err(e) => Err(windows_error_to_io(e)),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn try_named_lock(_name: &str) -> io::Result<Option<NamedLock>> {
+    Ok(Some(NamedLock {}))
+}
+
+/// Whether [`atomic_write_with`] flushes the data to disk before replacing
+/// the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Durability {
+    /// fsync before the replace, so after a power loss the target holds
+    /// either the old or the new contents. For state that cannot be rebuilt.
+    Synced,
+    /// Skip the fsync. The replace still never exposes a half-written file
+    /// after a process crash, but a power loss can leave it empty. For
+    /// caches that are rebuilt when unreadable.
+    Buffered,
+}
+
+/// Write a file via a sibling temp file and atomic replace where supported,
+/// syncing it to disk first.
 pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    atomic_write_with(path, contents, Durability::Synced)
+}
+
+/// [`atomic_write`] with a choice of whether to fsync.
+pub fn atomic_write_with(path: &Path, contents: &[u8], durability: Durability) -> io::Result<()> {
     static ATOMIC_WRITE_SEQ: AtomicU64 = AtomicU64::new(0);
 
     let parent = path
@@ -97,7 +269,9 @@ pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
             .write(true)
             .open(&temp_path)?;
         temp_file.write_all(contents)?;
-        temp_file.sync_all()?;
+        if durability == Durability::Synced {
+            temp_file.sync_all()?;
+        }
         drop(temp_file);
         replace_file(&temp_path, path)
     })();
@@ -111,16 +285,8 @@ pub fn atomic_write(path: &Path, contents: &[u8]) -> io::Result<()> {
 
 #[cfg(windows)]
 fn replace_file(source: &Path, destination: &Path) -> io::Result<()> {
-    let source_wide: Vec<u16> = source
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
-    let destination_wide: Vec<u16> = destination
-        .as_os_str()
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
+    let source_wide = wide_path(source);
+    let destination_wide = wide_path(destination);
 
     const MAX_ATTEMPTS: usize = 8;
 
@@ -164,7 +330,7 @@ fn windows_error_code(error: &windows::core::Error) -> Option<i32> {
 }
 
 #[cfg(windows)]
-fn windows_error_to_io(error: windows::core::Error) -> io::Error {
+pub fn windows_error_to_io(error: windows::core::Error) -> io::Error {
     match windows_error_code(&error) {
         Some(code) => io::Error::from_raw_os_error(code),
         None => io::Error::other(error),
@@ -178,6 +344,36 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn extended_length_path_prefixes_paths_beyond_max_path() {
+        let nested = "very_long_mod_folder_name\\".repeat(12);
+        let long = format!("D:/Games/Skyrim/./Data/../Data\\{nested}texture.dds");
+        assert!(long.len() > 260);
+
+        let extended = extended_length_path(Path::new(&long));
+        assert_eq!(
+            extended.to_str().unwrap(),
+            format!(r"\\?\D:\Games\Skyrim\Data\{nested}texture.dds")
+        );
+        assert_eq!(
+            extended_length_path(Path::new(&format!(r"\\nas\games\..\..\{nested}a.pak")))
+                .to_str()
+                .unwrap(),
+            format!(r"\\?\UNC\nas\games\{nested}a.pak")
+        );
+
+        for unchanged in [
+            r"D:\Games\Skyrim".to_string(),
+            format!(r"\\?\D:\{nested}"),
+            format!("relative\\{nested}"),
+        ] {
+            assert_eq!(
+                extended_length_path(Path::new(&unchanged)),
+                Path::new(&unchanged)
+            );
+        }
+    }
+
     #[test]
     fn atomic_write_allows_overlapping_writes_to_same_target() {
         let dir = tempfile::TempDir::new().unwrap();
@@ -207,4 +403,15 @@ mod tests {
             "final file should contain one complete writer payload, got {final_contents}"
         );
     }
+
+    #[test]
+    fn buffered_atomic_write_replaces_without_leaving_temp_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cache.json");
+        atomic_write_with(&path, b"old", Durability::Buffered).unwrap();
+        atomic_write_with(&path, b"new", Durability::Buffered).unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
 }
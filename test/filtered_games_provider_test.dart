@@ -304,6 +304,8 @@ class _MinimalBridgeService implements RustBridgeService {
   @override
   void persistCompressionHistory() {}
   @override
+  void flushCompressionHistory() {}
+  @override
   Future<List<GameInfo>> scanCustomFolder(String path) async => const [];
   @override
   Future<GameInfo> addApplicationFolder(String path, {String? name}) async =>
@@ -348,6 +350,21 @@ class _MinimalBridgeService implements RustBridgeService {
     required CompressionAlgorithm algorithm,
     bool allowDirectStorageOverride = false,
     int? ioParallelismOverride,
+    int? maxConcurrentJobs,
+    List<String> blockingProcesses = const [],
+    bool includeRemovableDrives = false,
+    bool allowAnticheatCompression = false,
+    int? maxBytesPerSec,
+    List<String> watcherIgnoredDirs = const [],
+    List<String> watcherIgnoredExtensions = const [],
+    List<String> watcherTempFilePatterns = const [],
+    int? watcherMaxEventDepth,
+    bool dryRun = false,
+    int? inputIdleSeconds,
+    IdlePolicy idlePolicy = IdlePolicy.all,
+    bool ignoreFocusAssist = false,
+    bool pauseWhenThermallyConstrained = false,
+    int? maintenanceWakeMinuteOfDay,
   }) async {}
   @override
   SchedulerState getSchedulerState() => SchedulerState.idle;
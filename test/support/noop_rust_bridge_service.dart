@@ -28,6 +28,9 @@ class NoOpRustBridgeService implements RustBridgeService {
   @override
   void persistCompressionHistory() {}
 
+  @override
+  void flushCompressionHistory() {}
+
   @override
   Stream<CompressionProgress> compressGame({
     required String gamePath,
@@ -163,6 +166,21 @@ class NoOpRustBridgeService implements RustBridgeService {
     required CompressionAlgorithm algorithm,
     bool allowDirectStorageOverride = false,
     int? ioParallelismOverride,
+    int? maxConcurrentJobs,
+    List<String> blockingProcesses = const [],
+    bool includeRemovableDrives = false,
+    bool allowAnticheatCompression = false,
+    int? maxBytesPerSec,
+    List<String> watcherIgnoredDirs = const [],
+    List<String> watcherIgnoredExtensions = const [],
+    List<String> watcherTempFilePatterns = const [],
+    int? watcherMaxEventDepth,
+    bool dryRun = false,
+    int? inputIdleSeconds,
+    IdlePolicy idlePolicy = IdlePolicy.all,
+    bool ignoreFocusAssist = false,
+    bool pauseWhenThermallyConstrained = false,
+    int? maintenanceWakeMinuteOfDay,
   }) async {}
 
   @override
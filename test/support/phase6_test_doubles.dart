@@ -202,6 +202,9 @@ class _TestRustBridgeService implements RustBridgeService {
   @override
   void persistCompressionHistory() {}
 
+  @override
+  void flushCompressionHistory() {}
+
   @override
   Future<List<GameInfo>> scanCustomFolder(String path) async {
     return const [];
@@ -248,6 +251,21 @@ class _TestRustBridgeService implements RustBridgeService {
     required CompressionAlgorithm algorithm,
     bool allowDirectStorageOverride = false,
     int? ioParallelismOverride,
+    int? maxConcurrentJobs,
+    List<String> blockingProcesses = const [],
+    bool includeRemovableDrives = false,
+    bool allowAnticheatCompression = false,
+    int? maxBytesPerSec,
+    List<String> watcherIgnoredDirs = const [],
+    List<String> watcherIgnoredExtensions = const [],
+    List<String> watcherTempFilePatterns = const [],
+    int? watcherMaxEventDepth,
+    bool dryRun = false,
+    int? inputIdleSeconds,
+    IdlePolicy idlePolicy = IdlePolicy.all,
+    bool ignoreFocusAssist = false,
+    bool pauseWhenThermallyConstrained = false,
+    int? maintenanceWakeMinuteOfDay,
   }) async {}
 
   @override